@@ -4,13 +4,33 @@
 //!
 //! ## Arguments
 //!
-//! Mnemonic signer can take following arguments using via environment variables:
+//! Mnemonic signer can take following arguments using via environment variables (or via settings of signer provided
+//! by solo machine, e.g. `env` of a tenant's signer in configuration file, in which case environment variables are
+//! not read):
 //!
-//! - `SOLO_MNEMONIC`: Mnemonic phrase (required)
-//! - `SOLO_HD_PATH`: HD wallet path (default: "m/44'/118'/0'/0/0")
+//! - `SOLO_MNEMONIC`: Mnemonic phrase (required when neither `SOLO_KEYSTORE` nor `SOLO_KEYCHAIN_ENTRY` is provided)
+//! - `SOLO_KEYCHAIN_ENTRY`: Name of platform keychain entry containing mnemonic (created using `solo-machine keychain
+//!   store`), used instead of `SOLO_MNEMONIC` (requires `keyring` feature)
+//! - `SOLO_KEYSTORE`: Path of encrypted keystore file containing mnemonic (created using `solo-machine keystore`
+//!   commands), used instead of `SOLO_MNEMONIC`
+//! - `SOLO_KEYSTORE_PASSWORD`: Password of keystore file (prompted on terminal when not provided)
+//! - `SOLO_MNEMONIC_PASSPHRASE`: BIP-39 passphrase ("25th word") of mnemonic (default: "")
+//! - `SOLO_PROMPT_PASSPHRASE`: Prompts for BIP-39 passphrase on terminal (without echoing it) when set to "true"
+//!   (default: "false")
+//! - `SOLO_HD_PATH`: HD wallet path (default: "m/44'/118'/0'/0/0" for "secp256k1" and "m/44'/60'/0'/0/0" for
+//!   "eth-secp256k1")
 //! - `SOLO_ACCOUNT_PREFIX`: Account prefix for generating addresses (default: "cosmos")
 //! - `SOLO_ADDRESS_ALGO`: Algorithm of the key pair (default: "secp256k1") (possible values: ["secp256k1", "eth-secp256k1"])
-use std::{env, str::FromStr, sync::Arc};
+//! - `SOLO_KEYS`: Key registry of named accounts derived from same mnemonic (optional) (e.g.
+//!   "alice=0,bob=m/44'/118'/1'/0/0"). Each key is either an account index (address index of default HD path) or a
+//!   full HD path. A named key can be selected using `--key` CLI option.
+//!
+//! Mnemonic, seed and derived signing keys are zeroized when dropped. Signing keys are derived for each signature and
+//! dropped as soon as the signature is created.
+//!
+//! When used as a fee payer signer (`--fee-payer-signer`), the same arguments are read with `SOLO_FEE_PAYER_` prefix
+//! instead of `SOLO_` (e.g. `SOLO_FEE_PAYER_MNEMONIC`, `SOLO_FEE_PAYER_HD_PATH`, etc.).
+use std::{env, fmt, str::FromStr, sync::Arc};
 
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
@@ -19,11 +39,12 @@ use k256::ecdsa::{signature::DigestSigner, Signature, SigningKey};
 use ripemd160::Digest;
 use solo_machine_core::{
     cosmos::crypto::PublicKey,
+    keystore::Keystore,
     signer::{AddressAlgo, Message, SignerRegistrar},
     Signer, ToPublicKey,
 };
+use zeroize::Zeroizing;
 
-const DEFAULT_HD_PATH: &str = "m/44'/118'/0'/0/0";
 const DEFAULT_ACCOUNT_PREFIX: &str = "cosmos";
 const DEFAULT_ADDRESS_ALGO: &str = "secp256k1";
 
@@ -32,6 +53,8 @@ const DEFAULT_ADDRESS_ALGO: &str = "secp256k1";
 pub struct MnemonicSigner {
     /// Mnemonic of signer
     pub mnemonic: Mnemonic,
+    /// BIP-39 passphrase of mnemonic
+    pub passphrase: Zeroizing<String>,
     /// HD path of signer
     pub hd_path: String,
     /// Bech32 prefix
@@ -40,43 +63,166 @@ pub struct MnemonicSigner {
     pub algo: AddressAlgo,
 }
 
+impl fmt::Debug for MnemonicSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MnemonicSigner")
+            .field("mnemonic", &"[REDACTED]")
+            .field("passphrase", &"[REDACTED]")
+            .field("hd_path", &self.hd_path)
+            .field("account_prefix", &self.account_prefix)
+            .field("algo", &self.algo)
+            .finish()
+    }
+}
+
 impl MnemonicSigner {
     pub fn from_env() -> Result<Self> {
-        let mnemonic_str = get_env("SOLO_MNEMONIC")?;
-        let mnemonic = Mnemonic::new(mnemonic_str, Language::English)
+        Self::from_env_with_prefix("SOLO_")
+    }
+
+    pub fn from_env_with_prefix(prefix: &str) -> Result<Self> {
+        Self::from_settings(&|name| env::var(name).ok(), prefix)
+    }
+
+    /// Creates a new signer using given settings (looked up by names of arguments, e.g. `SOLO_MNEMONIC`)
+    pub fn from_settings(setting: &dyn Fn(&str) -> Option<String>, prefix: &str) -> Result<Self> {
+        let get_env = |key: &str| get_setting(setting, &format!("{}{}", prefix, key));
+
+        let mnemonic_str = match (get_env("KEYCHAIN_ENTRY"), get_env("KEYSTORE")) {
+            (Ok(name), _) => read_keychain(&name)?,
+            (_, Ok(path)) => read_keystore(&path, get_env("KEYSTORE_PASSWORD").ok())?,
+            _ => Zeroizing::new(get_env("MNEMONIC")?),
+        };
+        let mnemonic = Mnemonic::new(mnemonic_str.as_str(), Language::English)
             .map_err(|_| anyhow!("invalid mnemonic"))?;
 
-        let hd_path = get_env("SOLO_HD_PATH").unwrap_or_else(|_| DEFAULT_HD_PATH.to_string());
+        let passphrase = Zeroizing::new(match get_env("PROMPT_PASSPHRASE").as_deref() {
+            Ok("true") => rpassword::read_password_from_tty(Some(&format!(
+                "Enter BIP-39 passphrase for {}MNEMONIC: ",
+                prefix
+            )))
+            .context("unable to read BIP-39 passphrase from terminal")?,
+            _ => get_env("MNEMONIC_PASSPHRASE").unwrap_or_default(),
+        });
+
         let account_prefix =
-            get_env("SOLO_ACCOUNT_PREFIX").unwrap_or_else(|_| DEFAULT_ACCOUNT_PREFIX.to_string());
+            get_env("ACCOUNT_PREFIX").unwrap_or_else(|_| DEFAULT_ACCOUNT_PREFIX.to_string());
 
-        let algo = get_env("SOLO_ADDRESS_ALGO")
+        let algo: AddressAlgo = get_env("ADDRESS_ALGO")
             .unwrap_or_else(|_| DEFAULT_ADDRESS_ALGO.to_string())
             .parse()?;
 
+        let hd_path = get_env("HD_PATH").unwrap_or_else(|_| algo.default_hd_path());
+
         Ok(Self {
             mnemonic,
+            passphrase,
             hd_path,
             account_prefix,
             algo,
         })
     }
 
+    /// Returns all the named signers in key registry (`SOLO_KEYS`)
+    pub fn named_signers_from_env(&self, prefix: &str) -> Result<Vec<(String, Self)>> {
+        self.named_signers_from_settings(&|name| env::var(name).ok(), prefix)
+    }
+
+    /// Returns all the named signers in key registry (`SOLO_KEYS`) of given settings
+    pub fn named_signers_from_settings(
+        &self,
+        setting: &dyn Fn(&str) -> Option<String>,
+        prefix: &str,
+    ) -> Result<Vec<(String, Self)>> {
+        let keys = match setting(&format!("{}KEYS", prefix)) {
+            Some(keys) => keys,
+            None => return Ok(Vec::new()),
+        };
+
+        keys.split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(|key| {
+                let (name, path) = key.split_once('=').ok_or_else(|| {
+                    anyhow!("key should be of form `name=index_or_path`: {}", key)
+                })?;
+
+                let hd_path = match path.parse::<u32>() {
+                    Ok(index) => format!("m/44'/{}'/0'/0/{}", self.algo.coin_type(), index),
+                    Err(_) => path.to_string(),
+                };
+
+                DerivationPath::from_str(&hd_path)
+                    .context(format!("invalid HD path for key `{}`: {}", name, hd_path))?;
+
+                Ok((
+                    name.to_string(),
+                    Self {
+                        hd_path,
+                        ..self.clone()
+                    },
+                ))
+            })
+            .collect()
+    }
+
     fn get_signing_key(&self) -> Result<SigningKey> {
-        let seed = self.mnemonic.to_seed("");
+        let seed = self.mnemonic.to_seed(&self.passphrase);
         let hd_path = DerivationPath::from_str(&self.hd_path).context("invalid HD path")?;
         let private_key =
-            ExtendedPrivateKey::<SigningKey>::derive_from_path(seed.as_bytes(), &hd_path).unwrap();
+            ExtendedPrivateKey::<SigningKey>::derive_from_path(seed.as_bytes(), &hd_path)
+                .map_err(|_| anyhow!("unable to derive signing key from mnemonic"))?;
 
         Ok(private_key.into())
     }
+
+    fn sign_with(&self, signing_key: &SigningKey, message: Message<'_>) -> Vec<u8> {
+        let signature: Signature = match self.algo {
+            AddressAlgo::Secp256k1 => signing_key.sign_digest(sha2::Sha256::new().chain(message)),
+            #[cfg(feature = "ethermint")]
+            AddressAlgo::EthSecp256k1 => {
+                signing_key.sign_digest(sha3::Keccak256::new().chain(message))
+            }
+        };
+
+        signature.as_ref().to_vec()
+    }
+}
+
+/// Decrypts mnemonic from keystore file (password is prompted on terminal when not provided)
+fn read_keystore(path: &str, password: Option<String>) -> Result<Zeroizing<String>> {
+    let keystore = Keystore::load(path)?;
+
+    let password = Zeroizing::new(match password {
+        Some(password) => password,
+        None => rpassword::read_password_from_tty(Some(&format!(
+            "Enter password for keystore {}: ",
+            path
+        )))
+        .context("unable to read keystore password from terminal")?,
+    });
+
+    let secret = keystore.decrypt(&password)?;
+    let mnemonic = std::str::from_utf8(&secret).context("invalid mnemonic in keystore")?;
+
+    Ok(Zeroizing::new(mnemonic.to_string()))
 }
 
-fn get_env(key: &str) -> Result<String> {
-    env::var(key).context(format!(
-        "`{}` environment variable is required for mnemonic signer",
-        key
-    ))
+/// Loads mnemonic from platform keychain
+#[cfg(feature = "keyring")]
+fn read_keychain(name: &str) -> Result<Zeroizing<String>> {
+    solo_machine_core::keychain::load_secret(name)
+}
+
+#[cfg(not(feature = "keyring"))]
+fn read_keychain(_name: &str) -> Result<Zeroizing<String>> {
+    anyhow::bail!(
+        "mnemonic signer should be compiled with `keyring` feature to use platform keychain"
+    )
+}
+
+fn get_setting(setting: &dyn Fn(&str) -> Option<String>, key: &str) -> Result<String> {
+    setting(key).ok_or_else(|| anyhow!("`{}` is required for mnemonic signer", key))
 }
 
 impl ToPublicKey for MnemonicSigner {
@@ -105,21 +251,42 @@ impl ToPublicKey for MnemonicSigner {
 impl Signer for MnemonicSigner {
     async fn sign(&self, _request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
         let signing_key = self.get_signing_key()?;
+        Ok(self.sign_with(&signing_key, message))
+    }
 
-        let signature: Signature = match self.algo {
-            AddressAlgo::Secp256k1 => signing_key.sign_digest(sha2::Sha256::new().chain(message)),
-            #[cfg(feature = "ethermint")]
-            AddressAlgo::EthSecp256k1 => {
-                signing_key.sign_digest(sha3::Keccak256::new().chain(message))
-            }
-        };
+    async fn sign_batch(
+        &self,
+        _request_id: Option<&str>,
+        messages: &[Message<'_>],
+    ) -> Result<Vec<Vec<u8>>> {
+        // signing key is derived from mnemonic only once for the whole batch
+        let signing_key = self.get_signing_key()?;
 
-        Ok(signature.as_ref().to_vec())
+        Ok(messages
+            .iter()
+            .map(|message| self.sign_with(&signing_key, *message))
+            .collect())
     }
 }
 
 #[no_mangle]
 pub fn register_signer(registrar: &mut dyn SignerRegistrar) -> Result<()> {
-    registrar.register(Arc::new(MnemonicSigner::from_env()?));
+    let setting = |name: &str| registrar.setting(name);
+    let signer = MnemonicSigner::from_settings(&setting, "SOLO_")?;
+    let named_signers = signer.named_signers_from_settings(&setting, "SOLO_")?;
+
+    for (name, named_signer) in named_signers {
+        registrar.register_named(&name, Arc::new(named_signer));
+    }
+
+    registrar.register(Arc::new(signer));
+    Ok(())
+}
+
+#[no_mangle]
+pub fn register_fee_payer(registrar: &mut dyn SignerRegistrar) -> Result<()> {
+    let signer = MnemonicSigner::from_settings(&|name| registrar.setting(name), "SOLO_FEE_PAYER_")?;
+
+    registrar.register(Arc::new(signer));
     Ok(())
 }
@@ -1,9 +1,32 @@
 use std::{
+    collections::BTreeMap,
+    env,
     error::Error,
-    fs::{read_dir, DirEntry},
-    path::PathBuf,
+    fs::{read, read_dir, write, DirEntry},
+    path::{Path, PathBuf},
+    process::Command,
 };
 
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+/// Dependencies whose versions determine signing logic (reported in build attestation)
+const ATTESTED_DEPENDENCIES: &[&str] = &[
+    "bech32",
+    "cosmos-sdk-proto",
+    "ed25519-dalek",
+    "k256",
+    "prost",
+    "prost-types",
+    "ripemd160",
+    "serde_json",
+    "sha2",
+    "sha3",
+    "tendermint",
+    "tendermint-light-client",
+    "tendermint-rpc",
+];
+
 fn main() -> Result<(), Box<dyn Error>> {
     let mut files = Vec::new();
 
@@ -18,6 +41,14 @@ fn main() -> Result<(), Box<dyn Error>> {
             ".cosmos.auth.v1beta1",
             "::cosmos_sdk_proto::cosmos::auth::v1beta1",
         )
+        .extern_path(
+            ".cosmos.base.v1beta1",
+            "::cosmos_sdk_proto::cosmos::base::v1beta1",
+        )
+        .extern_path(
+            ".ibc.core.client.v1",
+            "::cosmos_sdk_proto::ibc::core::client::v1",
+        )
         .extern_path(
             ".ibc.core.connection.v1",
             "::cosmos_sdk_proto::ibc::core::connection::v1",
@@ -29,9 +60,139 @@ fn main() -> Result<(), Box<dyn Error>> {
         .build_server(false)
         .compile(&files, &["proto".into()])?;
 
+    write_attestation(&files)?;
+
+    Ok(())
+}
+
+/// Writes build attestation (exact crate versions, hashes of protobuf definitions and build metadata) to `OUT_DIR`
+fn write_attestation(proto_files: &[PathBuf]) -> Result<(), Box<dyn Error>> {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR")?);
+
+    let mut proto_hashes = BTreeMap::new();
+
+    for file in proto_files {
+        let name = file
+            .strip_prefix("./proto")
+            .unwrap_or(file)
+            .to_string_lossy()
+            .replace('\\', "/");
+        proto_hashes.insert(name, sha256_hex(&read(file)?));
+    }
+
+    let lockfile = manifest_dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.is_file());
+
+    let (lockfile_hash, dependencies) = match lockfile {
+        Some(ref lockfile) => {
+            println!("cargo:rerun-if-changed={}", lockfile.display());
+
+            let contents = read(lockfile)?;
+            (
+                Some(sha256_hex(&contents)),
+                locked_versions(&String::from_utf8_lossy(&contents)),
+            )
+        }
+        None => (None, BTreeMap::new()),
+    };
+
+    let mut features = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase().replace('_', "-"))
+        })
+        .collect::<Vec<_>>();
+    features.sort();
+
+    let git_commit = command_output("git", &["rev-parse", "HEAD"]);
+
+    if let Some(git_dir) = command_output("git", &["rev-parse", "--absolute-git-dir"]) {
+        let git_dir = Path::new(&git_dir);
+        println!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display());
+        println!("cargo:rerun-if-changed={}", git_dir.join("refs").display());
+    }
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+
+    let attestation = json!({
+        "crate_name": env::var("CARGO_PKG_NAME")?,
+        "crate_version": env::var("CARGO_PKG_VERSION")?,
+        "git_commit": git_commit,
+        "rustc_version": command_output(&rustc, &["--version"]),
+        "target": env::var("TARGET")?,
+        "profile": env::var("PROFILE")?,
+        "features": features,
+        "lockfile_hash": lockfile_hash,
+        "dependencies": dependencies,
+        "proto_hashes": proto_hashes,
+    });
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    write(
+        out_dir.join("attestation.json"),
+        serde_json::to_string(&attestation)?,
+    )?;
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    println!("cargo:rerun-if-changed=proto");
+    println!("cargo:rerun-if-changed=src");
+
     Ok(())
 }
 
+/// Extracts locked versions of attested dependencies from `Cargo.lock`
+fn locked_versions(lockfile: &str) -> BTreeMap<String, String> {
+    let mut versions = BTreeMap::new();
+    let mut name = None;
+
+    for line in lockfile.lines() {
+        if line == "[[package]]" {
+            name = None;
+        } else if let Some(value) = line.strip_prefix("name = ") {
+            name = Some(value.trim_matches('"').to_string());
+        } else if let Some(value) = line.strip_prefix("version = ") {
+            if let Some(name) = name.take() {
+                if ATTESTED_DEPENDENCIES.contains(&name.as_str()) {
+                    let version = value.trim_matches('"').to_string();
+
+                    // multiple versions of same crate can be locked
+                    versions
+                        .entry(name)
+                        .and_modify(|versions: &mut String| {
+                            versions.push_str(", ");
+                            versions.push_str(&version);
+                        })
+                        .or_insert(version);
+                }
+            }
+        }
+    }
+
+    versions
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|output| output.trim().to_string())
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
 fn get_files(path: DirEntry) -> Result<Vec<PathBuf>, Box<dyn Error>> {
     if path.file_type()?.is_file() {
         return Ok(vec![path.path()]);
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS chain_labels (
+    id BIGSERIAL PRIMARY KEY,
+    chain_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    target TEXT NOT NULL,
+    label TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    removed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS chain_labels_label_idx ON chain_labels (kind, label);
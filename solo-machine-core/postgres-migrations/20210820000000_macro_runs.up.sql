@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS macro_runs (
+    id BIGSERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    steps JSONB NOT NULL,
+    journal JSONB NOT NULL,
+    status TEXT NOT NULL,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,2 @@
+ALTER TABLE chains ADD COLUMN last_signed_sequence BIGINT;
+ALTER TABLE chains ADD COLUMN last_signature_hash TEXT;
@@ -0,0 +1,7 @@
+ALTER TABLE audit_log ADD COLUMN attestation TEXT;
+
+CREATE TABLE IF NOT EXISTS build_attestations (
+    digest TEXT PRIMARY KEY,
+    attestation TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1 @@
+ALTER TABLE chains DROP COLUMN generated_identifiers;
@@ -0,0 +1 @@
+ALTER TABLE chains DROP COLUMN handshake_progress;
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS api_keys (
+    id TEXT PRIMARY KEY NOT NULL,
+    secret_hash TEXT NOT NULL,
+    chain_id TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    operations JSONB NOT NULL,
+    denom TEXT,
+    max_amount BIGINT,
+    label TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    revoked_at TIMESTAMPTZ
+);
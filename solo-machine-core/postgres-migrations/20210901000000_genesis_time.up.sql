@@ -0,0 +1 @@
+ALTER TABLE chains ADD COLUMN genesis_time TIMESTAMPTZ;
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS packets (
+    chain_id TEXT NOT NULL,
+    port_id TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    sequence BIGINT NOT NULL,
+    state TEXT NOT NULL,
+    height BIGINT,
+    packet TEXT NOT NULL,
+    transaction_hash TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (chain_id, port_id, channel_id, sequence)
+);
+
+CREATE INDEX IF NOT EXISTS packets_state_idx ON packets (chain_id, state);
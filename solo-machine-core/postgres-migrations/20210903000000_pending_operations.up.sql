@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS pending_operations (
+    id BIGSERIAL PRIMARY KEY,
+    chain_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    request_id TEXT,
+    status TEXT NOT NULL,
+    tx TEXT NOT NULL,
+    transaction_hash TEXT NOT NULL,
+    sequence BIGINT NOT NULL,
+    packet_sequence BIGINT NOT NULL,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS pending_operations_status_idx ON pending_operations (chain_id, status);
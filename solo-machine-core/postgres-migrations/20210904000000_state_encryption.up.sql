@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS state_encryption (
+    id INTEGER PRIMARY KEY NOT NULL CHECK (id = 1),
+    kdf TEXT NOT NULL,
+    verifier TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS signature_log_append_only ON signature_log;
+DROP FUNCTION IF EXISTS signature_log_append_only();
+DROP TABLE IF EXISTS signature_log;
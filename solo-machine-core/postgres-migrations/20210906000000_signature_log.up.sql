@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS signature_log (
+    id BIGSERIAL PRIMARY KEY,
+    record TEXT NOT NULL,
+    prev_hash TEXT NOT NULL,
+    hash TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE OR REPLACE FUNCTION signature_log_append_only() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'signature log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER signature_log_append_only BEFORE UPDATE OR DELETE ON signature_log
+    FOR EACH ROW EXECUTE PROCEDURE signature_log_append_only();
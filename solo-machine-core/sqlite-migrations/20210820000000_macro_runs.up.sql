@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS macro_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    steps TEXT NOT NULL,
+    journal TEXT NOT NULL,
+    status TEXT NOT NULL,
+    error TEXT,
+    created_at TIMESTAMPZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
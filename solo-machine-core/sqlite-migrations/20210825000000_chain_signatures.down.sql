@@ -0,0 +1,2 @@
+ALTER TABLE chains DROP COLUMN last_signature_hash;
+ALTER TABLE chains DROP COLUMN last_signed_sequence;
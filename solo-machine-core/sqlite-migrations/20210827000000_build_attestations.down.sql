@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS build_attestations;
+ALTER TABLE audit_log DROP COLUMN attestation;
@@ -0,0 +1 @@
+ALTER TABLE chains ADD COLUMN generated_identifiers TEXT;
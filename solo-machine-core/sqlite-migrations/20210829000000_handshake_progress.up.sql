@@ -0,0 +1 @@
+ALTER TABLE chains ADD COLUMN handshake_progress TEXT;
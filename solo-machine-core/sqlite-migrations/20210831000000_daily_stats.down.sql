@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS uptime_daily_stats;
+DROP TABLE IF EXISTS chain_daily_stats;
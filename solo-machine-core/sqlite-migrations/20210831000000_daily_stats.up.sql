@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS chain_daily_stats (
+    chain_id TEXT NOT NULL,
+    day TEXT NOT NULL,
+    packets_relayed BIGINT NOT NULL DEFAULT 0,
+    operations BIGINT NOT NULL DEFAULT 0,
+    last_success_at TIMESTAMPZ,
+    PRIMARY KEY (chain_id, day)
+);
+
+CREATE TABLE IF NOT EXISTS uptime_daily_stats (
+    day TEXT PRIMARY KEY NOT NULL,
+    uptime_seconds BIGINT NOT NULL DEFAULT 0
+);
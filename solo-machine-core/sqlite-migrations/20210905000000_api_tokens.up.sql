@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS api_tokens (
+    id TEXT PRIMARY KEY NOT NULL,
+    secret_hash TEXT NOT NULL,
+    role TEXT NOT NULL,
+    label TEXT,
+    created_at TIMESTAMPZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    revoked_at TIMESTAMPZ
+);
@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS signature_log_no_delete;
+DROP TRIGGER IF EXISTS signature_log_no_update;
+DROP TABLE IF EXISTS signature_log;
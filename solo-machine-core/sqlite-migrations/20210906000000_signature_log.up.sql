@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS signature_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    record TEXT NOT NULL,
+    prev_hash TEXT NOT NULL,
+    hash TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TRIGGER IF NOT EXISTS signature_log_no_update BEFORE UPDATE ON signature_log
+BEGIN
+    SELECT RAISE(ABORT, 'signature log is append-only');
+END;
+
+CREATE TRIGGER IF NOT EXISTS signature_log_no_delete BEFORE DELETE ON signature_log
+BEGIN
+    SELECT RAISE(ABORT, 'signature log is append-only');
+END;
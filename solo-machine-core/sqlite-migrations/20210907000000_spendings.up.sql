@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS spendings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    chain_id TEXT NOT NULL,
+    denom TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    request_id TEXT,
+    escalated BOOLEAN NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS spendings_denom_idx ON spendings (denom, created_at);
@@ -0,0 +1,235 @@
+//! Human-readable alerts posted to Slack or Telegram on critical conditions (e.g. a frozen solo machine client,
+//! repeated broadcast failures or a divergence of sequence), so that operators are paged without running their own
+//! webhook receivers
+use std::{collections::HashMap, sync::Mutex};
+
+use anyhow::{ensure, Context, Result};
+use hyper::Uri;
+use serde_json::json;
+
+use crate::{
+    ibc::core::ics24_host::identifier::ChainId,
+    webhook::{https_client, post_json, HttpsClient},
+    Event,
+};
+
+/// Default number of consecutive failed broadcasts on a chain after which an alert is posted
+pub const DEFAULT_BROADCAST_FAILURE_THRESHOLD: u32 = 3;
+
+/// Base URL of Telegram bot API
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+
+/// Destination of alerts
+#[derive(Debug, Clone)]
+pub enum AlertChannel {
+    /// Slack incoming webhook
+    Slack {
+        /// URL of incoming webhook
+        webhook_url: String,
+    },
+    /// Telegram chat (messages are sent by a bot which is a member of the chat)
+    Telegram {
+        /// Token of bot
+        bot_token: String,
+        /// ID of chat (or `@username` of channel)
+        chat_id: String,
+    },
+}
+
+impl AlertChannel {
+    /// Returns name of channel
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Slack { .. } => "slack",
+            Self::Telegram { .. } => "telegram",
+        }
+    }
+
+    fn url(&self) -> Result<Uri> {
+        let url = match self {
+            Self::Slack { webhook_url } => webhook_url.clone(),
+            Self::Telegram { bot_token, .. } => {
+                format!("{}/bot{}/sendMessage", TELEGRAM_API_URL, bot_token)
+            }
+        };
+
+        let url: Uri = url
+            .parse()
+            .with_context(|| format!("invalid {} url", self.name()))?;
+
+        ensure!(
+            url.scheme_str() == Some("https"),
+            "invalid {} url (expected `https` url)",
+            self.name()
+        );
+
+        Ok(url)
+    }
+
+    fn body(&self, message: &str) -> serde_json::Value {
+        match self {
+            Self::Slack { .. } => json!({ "text": message }),
+            Self::Telegram { chat_id, .. } => json!({
+                "chat_id": chat_id,
+                "text": message,
+                "disable_web_page_preview": true,
+            }),
+        }
+    }
+}
+
+/// Posts human-readable alerts to Slack and/or Telegram for events reporting critical conditions, i.e.,
+/// [`Event::ClientFrozen`], [`Event::SequenceDiverged`], [`Event::SpendingLimitExceeded`],
+/// [`Event::ClientNearingExpiry`] and consecutive [`Event::BroadcastFailed`] on a chain (reaching the threshold)
+pub struct Alerter {
+    channels: Vec<(AlertChannel, Uri)>,
+    broadcast_failure_threshold: u32,
+    broadcast_failures: Mutex<HashMap<ChainId, u32>>,
+    client: HttpsClient,
+}
+
+impl Alerter {
+    /// Creates a new alerter posting to given channels
+    pub fn new(channels: Vec<AlertChannel>, broadcast_failure_threshold: u32) -> Result<Self> {
+        ensure!(
+            broadcast_failure_threshold > 0,
+            "broadcast failure threshold should be greater than zero"
+        );
+
+        let channels = channels
+            .into_iter()
+            .map(|channel| {
+                let url = channel.url()?;
+                Ok((channel, url))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            channels,
+            broadcast_failure_threshold,
+            broadcast_failures: Default::default(),
+            client: https_client(),
+        })
+    }
+
+    /// Returns `true` if alerter does not have any channel
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+
+    /// Posts alert for given event (if it reports a critical condition) to all the channels
+    pub async fn alert(&self, event: &Event) -> Result<()> {
+        let message = match self.message(event) {
+            Some(message) => format!("[solo-machine] {}", message),
+            None => return Ok(()),
+        };
+
+        let mut errors = Vec::new();
+
+        for (channel, url) in self.channels.iter() {
+            let body =
+                serde_json::to_vec(&channel.body(&message)).context("unable to serialize alert")?;
+
+            // URLs of channels contain secrets (e.g. bot token), so, only name of channel is logged
+            if let Err(err) = post_json(&self.client, url, channel.name(), &body, &[]).await {
+                errors.push(format!(
+                    "unable to post alert to {}: {:#}",
+                    channel.name(),
+                    err
+                ));
+            }
+        }
+
+        ensure!(errors.is_empty(), "{}", errors.join("; "));
+
+        Ok(())
+    }
+
+    /// Returns alert message for given event (counting consecutive broadcast failures on chains)
+    fn message(&self, event: &Event) -> Option<String> {
+        match event {
+            Event::ClientFrozen {
+                chain_id,
+                client_id,
+            } => Some(format!(
+                "Solo machine client{} on chain {} is frozen or expired. Chain rejects all the operations of solo \
+                 machine until the client is recovered on chain.",
+                client_id
+                    .as_ref()
+                    .map(|client_id| format!(" {}", client_id))
+                    .unwrap_or_default(),
+                chain_id
+            )),
+            Event::SequenceDiverged {
+                chain_id,
+                client_id,
+                local,
+                on_chain,
+            } => Some(format!(
+                "Local sequence ({}) of chain {} diverged from sequence ({}) of solo machine client {} on chain. \
+                 Signatures of solo machine are rejected until sequence is resynchronized using `resync`.",
+                local, chain_id, on_chain, client_id
+            )),
+            Event::SpendingLimitExceeded {
+                chain_id,
+                denom,
+                amount,
+                spent,
+                limit,
+                window,
+                approved,
+            } => Some(format!(
+                "Transfer of {} {} on chain {} exceeds spending limit of {} {} per {} ({} {} already transferred). {}",
+                amount,
+                denom,
+                chain_id,
+                limit,
+                denom,
+                window,
+                spent,
+                denom,
+                if *approved {
+                    "Transfer was approved by escalation."
+                } else {
+                    "Transfer was rejected."
+                }
+            )),
+            Event::ClientNearingExpiry {
+                chain_id,
+                client_id,
+                expires_at,
+            } => Some(format!(
+                "Tendermint client {} of chain {} expires at {} unless it is refreshed.",
+                client_id, chain_id, expires_at
+            )),
+            Event::BroadcastFailed {
+                chain_id,
+                operation_id,
+                kind,
+                error,
+            } => {
+                let mut broadcast_failures = self.broadcast_failures.lock().unwrap();
+                let failures = broadcast_failures.entry(chain_id.clone()).or_default();
+                *failures += 1;
+
+                // alert is posted once when threshold is reached (and again after next successful operation)
+                (*failures == self.broadcast_failure_threshold).then(|| {
+                    format!(
+                        "{} consecutive broadcasts failed on chain {}. Latest failure: {} operation {}: {}",
+                        failures, chain_id, kind, operation_id, error
+                    )
+                })
+            }
+            Event::TokensMinted { chain_id, .. }
+            | Event::TokensBurnt { chain_id, .. }
+            | Event::SignerUpdated { chain_id, .. }
+            | Event::PacketAcknowledged { chain_id, .. }
+            | Event::PacketTimedOut { chain_id, .. }
+            | Event::PendingOperationResolved { chain_id, .. } => {
+                self.broadcast_failures.lock().unwrap().remove(chain_id);
+                None
+            }
+            _ => None,
+        }
+    }
+}
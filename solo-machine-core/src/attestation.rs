@@ -0,0 +1,53 @@
+//! Attestation of build environment of signing logic (used by auditors to reproduce exact signing logic for any
+//! historical signature)
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Build attestation generated by build script
+const ATTESTATION: &str = include_str!(concat!(env!("OUT_DIR"), "/attestation.json"));
+
+/// Exact crate versions, hashes of protobuf definitions and build metadata of signing logic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildAttestation {
+    /// Name of crate containing signing logic
+    pub crate_name: String,
+    /// Version of crate containing signing logic
+    pub crate_version: String,
+    /// Git commit from which crate is built (`None` if built outside of a git repository)
+    pub git_commit: Option<String>,
+    /// Version of rust compiler used for building crate
+    pub rustc_version: Option<String>,
+    /// Target triple for which crate is built
+    pub target: String,
+    /// Build profile (`debug` or `release`)
+    pub profile: String,
+    /// Enabled features of crate
+    pub features: Vec<String>,
+    /// SHA-256 hash of `Cargo.lock` (in hex)
+    pub lockfile_hash: Option<String>,
+    /// Locked versions of dependencies which determine signing logic
+    pub dependencies: BTreeMap<String, String>,
+    /// SHA-256 hashes of protobuf definitions (in hex)
+    pub proto_hashes: BTreeMap<String, String>,
+}
+
+impl BuildAttestation {
+    /// Returns build attestation of current binary
+    pub fn current() -> Result<Self> {
+        serde_json::from_str(ATTESTATION).context("invalid build attestation")
+    }
+
+    /// Returns SHA-256 digest (in hex) of canonical JSON encoding of build attestation
+    pub fn digest(&self) -> Result<String> {
+        let json = self.to_canonical_json()?;
+        Ok(hex::encode(Sha256::digest(json.as_bytes())))
+    }
+
+    /// Returns canonical JSON encoding of build attestation (fields in declaration order and maps sorted by keys)
+    pub fn to_canonical_json(&self) -> Result<String> {
+        serde_json::to_string(self).context("unable to serialize build attestation")
+    }
+}
@@ -0,0 +1,515 @@
+//! Broadcasting of signed transactions to a chain (using tendermint RPC or gRPC tx service of cosmos SDK)
+use std::{fmt, str::FromStr, time::Duration};
+
+use anyhow::{anyhow, bail, Context, Error, Result};
+use cosmos_sdk_proto::cosmos::{
+    base::abci::v1beta1::TxResponse,
+    tx::v1beta1::{
+        service_client::ServiceClient, BroadcastMode as GrpcBroadcastMode, BroadcastTxRequest,
+    },
+};
+use serde::{Deserialize, Serialize};
+use tendermint::abci::{transaction::Hash, Code, DeliverTx, Event as AbciEvent};
+use tendermint_rpc::{endpoint::broadcast::tx_commit::TxResult, Client};
+use tokio::time::{sleep, Instant};
+
+use crate::{
+    cosmos::grpc_pool::grpc_channel,
+    failover::active_grpc_addr,
+    model::ChainConfig,
+    rpc_client::TimeoutRpcClient,
+    service::{TxError, TxStage},
+};
+
+/// Mode in which a transaction is broadcast
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BroadcastMode {
+    /// Returns after transaction is checked by mempool (`check_tx`)
+    Sync,
+    /// Returns immediately after transaction is submitted
+    Async,
+    /// Returns after transaction is committed in a block (`check_tx` and `deliver_tx`)
+    #[default]
+    Commit,
+}
+
+impl fmt::Display for BroadcastMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sync => write!(f, "sync"),
+            Self::Async => write!(f, "async"),
+            Self::Commit => write!(f, "commit"),
+        }
+    }
+}
+
+impl FromStr for BroadcastMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sync" => Ok(Self::Sync),
+            "async" => Ok(Self::Async),
+            "commit" => Ok(Self::Commit),
+            _ => Err(anyhow!("invalid broadcast mode: {}", s)),
+        }
+    }
+}
+
+/// Endpoint of chain used for broadcasting transactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BroadcastTransport {
+    /// Tendermint RPC (`broadcast_tx_sync`, `broadcast_tx_async` and `broadcast_tx_commit`)
+    #[default]
+    Rpc,
+    /// gRPC tx service of cosmos SDK (`cosmos.tx.v1beta1.Service/BroadcastTx`)
+    Grpc,
+}
+
+impl fmt::Display for BroadcastTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rpc => write!(f, "rpc"),
+            Self::Grpc => write!(f, "grpc"),
+        }
+    }
+}
+
+impl FromStr for BroadcastTransport {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rpc" => Ok(Self::Rpc),
+            "grpc" => Ok(Self::Grpc),
+            _ => Err(anyhow!("invalid broadcast transport: {}", s)),
+        }
+    }
+}
+
+/// Policy for waiting until a broadcast transaction is included in a block (by polling `/tx` endpoint of tendermint
+/// RPC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionPolicy {
+    /// Maximum time to wait for transaction to be included in a block
+    pub timeout: Duration,
+    /// Interval at which transaction is polled
+    pub interval: Duration,
+}
+
+impl Default for InclusionPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(60),
+            interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Attribute of an event emitted while executing a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxEventAttribute {
+    /// Key of attribute
+    pub key: String,
+    /// Value of attribute
+    pub value: String,
+}
+
+/// Event emitted while executing a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxEvent {
+    /// Type of event
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Attributes of event
+    pub attributes: Vec<TxEventAttribute>,
+}
+
+impl TxEvent {
+    /// Returns value of attribute with given key (if present)
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.as_str())
+    }
+}
+
+impl From<&AbciEvent> for TxEvent {
+    fn from(event: &AbciEvent) -> Self {
+        Self {
+            kind: event.type_str.clone(),
+            attributes: event
+                .attributes
+                .iter()
+                .map(|tag| TxEventAttribute {
+                    key: tag.key.to_string(),
+                    value: tag.value.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Result of executing a transaction (in `check_tx` or `deliver_tx`)
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ExecutionResult {
+    /// Response code (zero for success)
+    pub code: u32,
+    /// Namespace of response code
+    pub codespace: String,
+    /// Log returned by chain
+    pub log: String,
+    /// Amount of gas requested for transaction
+    pub gas_wanted: u64,
+    /// Amount of gas consumed by transaction
+    pub gas_used: u64,
+    /// Events emitted by transaction
+    pub events: Vec<TxEvent>,
+}
+
+impl ExecutionResult {
+    /// Returns `true` if transaction was executed successfully
+    pub fn is_ok(&self) -> bool {
+        self.code == 0
+    }
+}
+
+impl From<&TxResult> for ExecutionResult {
+    fn from(result: &TxResult) -> Self {
+        Self {
+            code: result.code.value(),
+            codespace: result.codespace.to_string(),
+            log: result.log.to_string(),
+            gas_wanted: result.gas_wanted.value(),
+            gas_used: result.gas_used.value(),
+            events: result.events.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&DeliverTx> for ExecutionResult {
+    fn from(result: &DeliverTx) -> Self {
+        Self {
+            code: result.code.value(),
+            codespace: result.codespace.to_string(),
+            log: result.log.to_string(),
+            gas_wanted: result.gas_wanted.value(),
+            gas_used: result.gas_used.value(),
+            events: result.events.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&TxResponse> for ExecutionResult {
+    fn from(response: &TxResponse) -> Self {
+        Self {
+            code: response.code,
+            codespace: response.codespace.clone(),
+            log: response.raw_log.clone(),
+            gas_wanted: response.gas_wanted as u64,
+            gas_used: response.gas_used as u64,
+            events: response
+                .logs
+                .iter()
+                .flat_map(|log| log.events.iter())
+                .map(|event| TxEvent {
+                    kind: event.r#type.clone(),
+                    attributes: event
+                        .attributes
+                        .iter()
+                        .map(|attribute| TxEventAttribute {
+                            key: attribute.key.clone(),
+                            value: attribute.value.clone(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Decoded result of broadcasting a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BroadcastResult {
+    /// Mode in which transaction was broadcast
+    pub mode: BroadcastMode,
+    /// Hash of transaction
+    pub transaction_hash: String,
+    /// Height of block in which transaction was committed (only for `commit` mode or after waiting for inclusion)
+    pub height: Option<u64>,
+    /// Result of `check_tx` (not available for `async` mode)
+    pub check_tx: Option<ExecutionResult>,
+    /// Result of `deliver_tx` (only for `commit` mode or after waiting for inclusion)
+    pub deliver_tx: Option<ExecutionResult>,
+}
+
+impl BroadcastResult {
+    /// Returns transaction hash if neither `check_tx` nor `deliver_tx` returned an error
+    pub fn ensure_success(&self) -> Result<String> {
+        for (stage, result) in [
+            (TxStage::CheckTx, &self.check_tx),
+            (TxStage::DeliverTx, &self.deliver_tx),
+        ] {
+            if let Some(err) = result
+                .as_ref()
+                .and_then(|result| TxError::from_result(stage, result))
+            {
+                return Err(err.into());
+            }
+        }
+
+        Ok(self.transaction_hash.clone())
+    }
+
+    /// Polls chain until transaction is included in a block and fills its height and `deliver_tx` result (returned as
+    /// is if transaction is already committed or is rejected by mempool)
+    #[tracing::instrument(skip_all, fields(transaction_hash = %self.transaction_hash))]
+    pub async fn wait_for_inclusion<C>(
+        mut self,
+        rpc_client: &C,
+        policy: &InclusionPolicy,
+    ) -> Result<Self>
+    where
+        C: Client + Send + Sync,
+    {
+        let rejected = self
+            .check_tx
+            .as_ref()
+            .map(|result| !result.is_ok())
+            .unwrap_or(false);
+
+        if self.deliver_tx.is_some() || rejected {
+            return Ok(self);
+        }
+
+        let hash: Hash = self.transaction_hash.parse().map_err(|err| {
+            anyhow!(
+                "invalid transaction hash {}: {}",
+                self.transaction_hash,
+                err
+            )
+        })?;
+        let deadline = Instant::now() + policy.timeout;
+
+        loop {
+            match rpc_client.tx(hash, false).await {
+                Ok(response) => {
+                    self.height = Some(response.height.value());
+                    self.deliver_tx = Some((&response.tx_result).into());
+
+                    return Ok(self);
+                }
+                Err(err) if Instant::now() >= deadline => {
+                    return Err(Error::from(err).context(format!(
+                        "transaction {} was not included in a block within {:?} (is transaction indexing enabled \
+                         on node?)",
+                        self.transaction_hash, policy.timeout
+                    )));
+                }
+                Err(_) => sleep(policy.interval).await,
+            }
+        }
+    }
+
+    /// Returns events emitted while executing transaction in block (empty if transaction was not committed)
+    pub fn events(&self) -> &[TxEvent] {
+        self.deliver_tx
+            .as_ref()
+            .map(|result| result.events.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// Broadcasts transactions using configured transport and mode
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Broadcaster {
+    transport: BroadcastTransport,
+    mode: BroadcastMode,
+    inclusion_policy: Option<InclusionPolicy>,
+}
+
+impl Broadcaster {
+    /// Creates a new broadcaster
+    pub fn new(transport: BroadcastTransport, mode: BroadcastMode) -> Self {
+        Self {
+            transport,
+            mode,
+            inclusion_policy: None,
+        }
+    }
+
+    /// Waits for broadcast transactions to be included in a block using given policy (transactions are always polled
+    /// using tendermint RPC)
+    pub fn with_inclusion_policy(mut self, inclusion_policy: InclusionPolicy) -> Self {
+        self.inclusion_policy = Some(inclusion_policy);
+        self
+    }
+
+    /// Broadcasts encoded transaction (`TxRaw`) to chain with given configuration (using RPC endpoint selected for
+    /// failover and currently preferred gRPC endpoint)
+    pub async fn broadcast(
+        &self,
+        config: &ChainConfig,
+        tx_bytes: Vec<u8>,
+    ) -> Result<BroadcastResult> {
+        let rpc_client = TimeoutRpcClient::for_chain(config).await?;
+
+        let result = match self.transport {
+            BroadcastTransport::Rpc => broadcast_rpc(&rpc_client, self.mode, tx_bytes).await?,
+            BroadcastTransport::Grpc => broadcast_grpc(config, self.mode, tx_bytes).await?,
+        };
+
+        match self.inclusion_policy {
+            Some(ref inclusion_policy) => {
+                result
+                    .wait_for_inclusion(&rpc_client, inclusion_policy)
+                    .await
+            }
+            None => Ok(result),
+        }
+    }
+}
+
+/// Broadcasts encoded transaction (`TxRaw`) using tendermint RPC
+#[tracing::instrument(skip_all, fields(mode = %mode))]
+pub async fn broadcast_rpc<C>(
+    rpc_client: &C,
+    mode: BroadcastMode,
+    tx_bytes: Vec<u8>,
+) -> Result<BroadcastResult>
+where
+    C: Client + Send + Sync,
+{
+    match mode {
+        BroadcastMode::Sync => {
+            let response = rpc_client
+                .broadcast_tx_sync(tx_bytes.into())
+                .await
+                .context("unable to broadcast transaction (sync)")?;
+
+            Ok(BroadcastResult {
+                mode,
+                transaction_hash: response.hash.to_string(),
+                height: None,
+                check_tx: Some(check_tx_result(response.code, response.log.to_string())),
+                deliver_tx: None,
+            })
+        }
+        BroadcastMode::Async => {
+            let response = rpc_client
+                .broadcast_tx_async(tx_bytes.into())
+                .await
+                .context("unable to broadcast transaction (async)")?;
+
+            Ok(BroadcastResult {
+                mode,
+                transaction_hash: response.hash.to_string(),
+                height: None,
+                check_tx: None,
+                deliver_tx: None,
+            })
+        }
+        BroadcastMode::Commit => {
+            let response = rpc_client
+                .broadcast_tx_commit(tx_bytes.into())
+                .await
+                .context("unable to broadcast transaction (commit)")?;
+
+            Ok(BroadcastResult {
+                mode,
+                transaction_hash: response.hash.to_string(),
+                height: Some(response.height.value()),
+                check_tx: Some((&response.check_tx).into()),
+                deliver_tx: Some((&response.deliver_tx).into()),
+            })
+        }
+    }
+}
+
+/// Broadcasts encoded transaction (`TxRaw`) using tendermint RPC (in `sync` mode) and waits for it to be included in
+/// a block
+pub async fn broadcast_and_wait<C>(
+    rpc_client: &C,
+    tx_bytes: Vec<u8>,
+    inclusion_policy: &InclusionPolicy,
+) -> Result<BroadcastResult>
+where
+    C: Client + Send + Sync,
+{
+    broadcast_rpc(rpc_client, BroadcastMode::Sync, tx_bytes)
+        .await?
+        .wait_for_inclusion(rpc_client, inclusion_policy)
+        .await
+}
+
+/// Broadcasts encoded transaction (`TxRaw`) using gRPC tx service of cosmos SDK (`commit` mode uses `block` broadcast
+/// mode of cosmos SDK)
+#[tracing::instrument(skip_all, fields(mode = %mode))]
+pub async fn broadcast_grpc(
+    config: &ChainConfig,
+    mode: BroadcastMode,
+    tx_bytes: Vec<u8>,
+) -> Result<BroadcastResult> {
+    let grpc_addr = active_grpc_addr(config);
+    let mut service_client = ServiceClient::new(grpc_channel(&grpc_addr, config).await?);
+
+    let grpc_mode = match mode {
+        BroadcastMode::Sync => GrpcBroadcastMode::Sync,
+        BroadcastMode::Async => GrpcBroadcastMode::Async,
+        BroadcastMode::Commit => GrpcBroadcastMode::Block,
+    };
+
+    let response = service_client
+        .broadcast_tx(BroadcastTxRequest {
+            tx_bytes,
+            mode: grpc_mode as i32,
+        })
+        .await
+        .context(format!("unable to broadcast transaction ({})", mode))?
+        .into_inner()
+        .tx_response
+        .ok_or_else(|| anyhow!("tx response is missing from broadcast response"))?;
+
+    if response.txhash.is_empty() {
+        bail!(
+            "transaction hash is missing from broadcast response: {}",
+            response.raw_log
+        );
+    }
+
+    let result = ExecutionResult::from(&response);
+
+    let (height, check_tx, deliver_tx) = match mode {
+        BroadcastMode::Sync => (None, Some(result), None),
+        BroadcastMode::Async => (None, Some(result).filter(|result| !result.is_ok()), None),
+        // cosmos SDK returns `check_tx` result (with zero height) when transaction is rejected by mempool
+        BroadcastMode::Commit if response.height <= 0 => (None, Some(result), None),
+        BroadcastMode::Commit => (
+            Some(response.height as u64),
+            Some(ExecutionResult {
+                gas_wanted: result.gas_wanted,
+                ..Default::default()
+            }),
+            Some(result),
+        ),
+    };
+
+    Ok(BroadcastResult {
+        mode,
+        transaction_hash: response.txhash,
+        height,
+        check_tx,
+        deliver_tx,
+    })
+}
+
+fn check_tx_result(code: Code, log: String) -> ExecutionResult {
+    ExecutionResult {
+        code: code.value(),
+        log,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,398 @@
+//! Encoding of solo machine client types (used for targeting chains which fork solo machine client with different type
+//! URLs or extra fields)
+use std::{fmt, sync::Arc};
+
+use anyhow::{Context, Result};
+use cosmos_sdk_proto::ibc::{
+    core::client::v1::Height, lightclients::solomachine::v1 as solomachine_v1,
+};
+use prost_types::Any;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "solomachine-v2"))]
+pub use cosmos_sdk_proto::ibc::lightclients::solomachine::v1::{
+    ClientState, ConsensusState, Header,
+};
+
+#[cfg(feature = "solomachine-v2")]
+pub use crate::proto::ibc::lightclients::solomachine::v2::{ClientState, ConsensusState, Header};
+
+use crate::{
+    ibc::core::ics02_client::height::IHeight,
+    ibc_version::SoloMachineRevision,
+    model::Chain,
+    proto::{
+        ibc::lightclients::{
+            solomachine::{v2 as solomachine_v2, v3 as solomachine_v3},
+            wasm::v1::{
+                ClientMessage as WasmClientMessage, ClientState as WasmClientState,
+                ConsensusState as WasmConsensusState,
+            },
+        },
+        proto_encode, AnyConvert,
+    },
+};
+
+/// Encoder of solo machine client types sent to a chain (in `MsgCreateClient` and `MsgUpdateClient`). Client types
+/// given to encoders are of default revision of this build (`solomachine.v2` with `solomachine-v2` feature, otherwise
+/// `solomachine.v1`).
+pub trait ClientEncoder: Send + Sync {
+    /// Encodes client state of solo machine client
+    fn encode_client_state(&self, chain: &Chain, client_state: &ClientState) -> Result<Any>;
+
+    /// Encodes consensus state of solo machine client
+    fn encode_consensus_state(
+        &self,
+        chain: &Chain,
+        consensus_state: &ConsensusState,
+    ) -> Result<Any>;
+
+    /// Encodes header of solo machine client
+    fn encode_header(&self, chain: &Chain, header: &Header) -> Result<Any>;
+}
+
+/// Kind of encoding used for solo machine client types on a chain
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", tag = "kind")]
+pub enum ClientEncoding {
+    /// Standard type URLs of ibc-go
+    #[default]
+    Standard,
+    /// Standard protobuf encoding with custom type URLs (standard type URL is used for types without a custom one)
+    Custom {
+        /// Type URL of client state
+        client_state_type_url: Option<String>,
+        /// Type URL of consensus state
+        consensus_state_type_url: Option<String>,
+        /// Type URL of header
+        header_type_url: Option<String>,
+    },
+    /// Types are encoded by client encoder plugin registered for chain (`--client-encoder-plugin`)
+    Plugin,
+    /// Standard types wrapped in ICS-08 wasm client types (for chains which only allow light clients through 08-wasm)
+    Wasm {
+        /// Hex encoded checksum of wasm light client code stored on chain
+        checksum: String,
+    },
+}
+
+impl fmt::Display for ClientEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Standard => write!(f, "standard"),
+            Self::Custom {
+                client_state_type_url,
+                consensus_state_type_url,
+                header_type_url,
+            } => write!(
+                f,
+                "custom (client state: {}, consensus state: {}, header: {})",
+                client_state_type_url.as_deref().unwrap_or("-"),
+                consensus_state_type_url.as_deref().unwrap_or("-"),
+                header_type_url.as_deref().unwrap_or("-"),
+            ),
+            Self::Plugin => write!(f, "plugin"),
+            Self::Wasm { checksum } => write!(f, "wasm (checksum: {})", checksum),
+        }
+    }
+}
+
+/// Client encoder which uses standard type URLs of ibc-go
+pub struct StandardClientEncoder;
+
+impl ClientEncoder for StandardClientEncoder {
+    fn encode_client_state(&self, _chain: &Chain, client_state: &ClientState) -> Result<Any> {
+        client_state.to_any()
+    }
+
+    fn encode_consensus_state(
+        &self,
+        _chain: &Chain,
+        consensus_state: &ConsensusState,
+    ) -> Result<Any> {
+        consensus_state.to_any()
+    }
+
+    fn encode_header(&self, _chain: &Chain, header: &Header) -> Result<Any> {
+        header.to_any()
+    }
+}
+
+/// Client encoder which uses standard protobuf encoding with custom type URLs
+pub struct TypeUrlClientEncoder<'a> {
+    /// Type URL of client state
+    pub client_state_type_url: Option<&'a str>,
+    /// Type URL of consensus state
+    pub consensus_state_type_url: Option<&'a str>,
+    /// Type URL of header
+    pub header_type_url: Option<&'a str>,
+}
+
+impl ClientEncoder for TypeUrlClientEncoder<'_> {
+    fn encode_client_state(&self, _chain: &Chain, client_state: &ClientState) -> Result<Any> {
+        with_type_url(client_state, self.client_state_type_url)
+    }
+
+    fn encode_consensus_state(
+        &self,
+        _chain: &Chain,
+        consensus_state: &ConsensusState,
+    ) -> Result<Any> {
+        with_type_url(consensus_state, self.consensus_state_type_url)
+    }
+
+    fn encode_header(&self, _chain: &Chain, header: &Header) -> Result<Any> {
+        with_type_url(header, self.header_type_url)
+    }
+}
+
+/// Client encoder which converts solo machine client types to another revision of their protobuf definitions (e.g.
+/// `solomachine.v3` for chains using ibc-go v7 onwards) and encodes them using standard or custom type URLs
+pub struct RevisionClientEncoder<'a> {
+    /// Revision of solo machine client types expected by chain
+    pub revision: SoloMachineRevision,
+    /// Type URL of client state (standard type URL of revision is used when not provided)
+    pub client_state_type_url: Option<&'a str>,
+    /// Type URL of consensus state (standard type URL of revision is used when not provided)
+    pub consensus_state_type_url: Option<&'a str>,
+    /// Type URL of header (standard type URL of revision is used when not provided)
+    pub header_type_url: Option<&'a str>,
+}
+
+impl ClientEncoder for RevisionClientEncoder<'_> {
+    fn encode_client_state(&self, _chain: &Chain, client_state: &ClientState) -> Result<Any> {
+        let type_url = self.client_state_type_url;
+        let sequence = client_state.sequence;
+        let is_frozen = is_frozen(client_state);
+        let consensus_state = client_state.consensus_state.as_ref();
+
+        match self.revision {
+            SoloMachineRevision::V1 => with_type_url(
+                &solomachine_v1::ClientState {
+                    sequence,
+                    frozen_sequence: if is_frozen { sequence } else { 0 },
+                    consensus_state: consensus_state.map(|state| solomachine_v1::ConsensusState {
+                        public_key: state.public_key.clone(),
+                        diversifier: state.diversifier.clone(),
+                        timestamp: state.timestamp,
+                    }),
+                    allow_update_after_proposal: client_state.allow_update_after_proposal,
+                },
+                type_url,
+            ),
+            SoloMachineRevision::V2 => with_type_url(
+                &solomachine_v2::ClientState {
+                    sequence,
+                    is_frozen,
+                    consensus_state: consensus_state.map(|state| solomachine_v2::ConsensusState {
+                        public_key: state.public_key.clone(),
+                        diversifier: state.diversifier.clone(),
+                        timestamp: state.timestamp,
+                    }),
+                    allow_update_after_proposal: client_state.allow_update_after_proposal,
+                },
+                type_url,
+            ),
+            SoloMachineRevision::V3 => with_type_url(
+                &solomachine_v3::ClientState {
+                    sequence,
+                    is_frozen,
+                    consensus_state: consensus_state.map(|state| solomachine_v3::ConsensusState {
+                        public_key: state.public_key.clone(),
+                        diversifier: state.diversifier.clone(),
+                        timestamp: state.timestamp,
+                    }),
+                },
+                type_url,
+            ),
+        }
+    }
+
+    fn encode_consensus_state(
+        &self,
+        _chain: &Chain,
+        consensus_state: &ConsensusState,
+    ) -> Result<Any> {
+        let type_url = self.consensus_state_type_url;
+        let public_key = consensus_state.public_key.clone();
+        let diversifier = consensus_state.diversifier.clone();
+        let timestamp = consensus_state.timestamp;
+
+        match self.revision {
+            SoloMachineRevision::V1 => with_type_url(
+                &solomachine_v1::ConsensusState {
+                    public_key,
+                    diversifier,
+                    timestamp,
+                },
+                type_url,
+            ),
+            SoloMachineRevision::V2 => with_type_url(
+                &solomachine_v2::ConsensusState {
+                    public_key,
+                    diversifier,
+                    timestamp,
+                },
+                type_url,
+            ),
+            SoloMachineRevision::V3 => with_type_url(
+                &solomachine_v3::ConsensusState {
+                    public_key,
+                    diversifier,
+                    timestamp,
+                },
+                type_url,
+            ),
+        }
+    }
+
+    fn encode_header(&self, _chain: &Chain, header: &Header) -> Result<Any> {
+        let type_url = self.header_type_url;
+        let sequence = header.sequence;
+        let timestamp = header.timestamp;
+        let signature = header.signature.clone();
+        let new_public_key = header.new_public_key.clone();
+        let new_diversifier = header.new_diversifier.clone();
+
+        match self.revision {
+            SoloMachineRevision::V1 => with_type_url(
+                &solomachine_v1::Header {
+                    sequence,
+                    timestamp,
+                    signature,
+                    new_public_key,
+                    new_diversifier,
+                },
+                type_url,
+            ),
+            SoloMachineRevision::V2 => with_type_url(
+                &solomachine_v2::Header {
+                    sequence,
+                    timestamp,
+                    signature,
+                    new_public_key,
+                    new_diversifier,
+                },
+                type_url,
+            ),
+            // `solomachine.v3` header does not contain sequence (sequence of client state is used instead)
+            SoloMachineRevision::V3 => with_type_url(
+                &solomachine_v3::Header {
+                    timestamp,
+                    signature,
+                    new_public_key,
+                    new_diversifier,
+                },
+                type_url,
+            ),
+        }
+    }
+}
+
+/// Client encoder which wraps solo machine client types encoded by another client encoder in ICS-08 wasm client types
+/// (`data` of wasm client types contains protobuf encoded `Any` of wrapped type)
+pub struct WasmClientEncoder<'a> {
+    /// Client encoder used for encoding wrapped types
+    pub inner: &'a dyn ClientEncoder,
+    /// Checksum of wasm light client code stored on chain
+    pub checksum: &'a [u8],
+}
+
+impl ClientEncoder for WasmClientEncoder<'_> {
+    fn encode_client_state(&self, chain: &Chain, client_state: &ClientState) -> Result<Any> {
+        let data = proto_encode(&self.inner.encode_client_state(chain, client_state)?)?;
+
+        WasmClientState {
+            data,
+            checksum: self.checksum.to_vec(),
+            latest_height: Some(Height::new(0, client_state.sequence)),
+        }
+        .to_any()
+    }
+
+    fn encode_consensus_state(
+        &self,
+        chain: &Chain,
+        consensus_state: &ConsensusState,
+    ) -> Result<Any> {
+        let data = proto_encode(&self.inner.encode_consensus_state(chain, consensus_state)?)?;
+
+        WasmConsensusState { data }.to_any()
+    }
+
+    fn encode_header(&self, chain: &Chain, header: &Header) -> Result<Any> {
+        let data = proto_encode(&self.inner.encode_header(chain, header)?)?;
+
+        WasmClientMessage { data }.to_any()
+    }
+}
+
+#[cfg(not(feature = "solomachine-v2"))]
+fn is_frozen(client_state: &ClientState) -> bool {
+    client_state.frozen_sequence != 0
+}
+
+#[cfg(feature = "solomachine-v2")]
+fn is_frozen(client_state: &ClientState) -> bool {
+    client_state.is_frozen
+}
+
+fn with_type_url<T>(message: &T, type_url: Option<&str>) -> Result<Any>
+where
+    T: AnyConvert + prost::Message,
+{
+    match type_url {
+        None => message.to_any(),
+        Some(type_url) => Ok(Any {
+            type_url: type_url.to_string(),
+            value: proto_encode(message)?,
+        }),
+    }
+}
+
+/// Leading fields of solo machine client state which are common to all the revisions of solo machine client (other
+/// fields are ignored while decoding)
+#[derive(Clone, PartialEq, prost::Message)]
+struct ClientStateHeader {
+    #[prost(uint64, tag = "1")]
+    sequence: u64,
+    /// `frozen_sequence` (v1) or `is_frozen` (v2 onwards), both of which are varints with non-zero value when client
+    /// is frozen
+    #[prost(uint64, tag = "2")]
+    frozen: u64,
+}
+
+/// Decodes sequence of an encoded solo machine client state (e.g. queried from chain). Client states wrapped in ICS-08
+/// wasm client state are unwrapped, and client states with custom type URLs or extra fields are decoded leniently.
+pub fn decode_client_state_sequence(client_state: &Any) -> Result<u64> {
+    decode_client_state_header(client_state).map(|header| header.sequence)
+}
+
+/// Returns `true` if an encoded solo machine client state (e.g. queried from chain) is frozen (decoded in the same way
+/// as [`decode_client_state_sequence`])
+pub fn is_client_state_frozen(client_state: &Any) -> Result<bool> {
+    decode_client_state_header(client_state).map(|header| header.frozen != 0)
+}
+
+fn decode_client_state_header(client_state: &Any) -> Result<ClientStateHeader> {
+    let client_state = match WasmClientState::from_any(client_state) {
+        Ok(wasm_client_state) => <Any as prost::Message>::decode(wasm_client_state.data.as_slice())
+            .context("unable to decode client state wrapped in wasm client state")?,
+        Err(_) => client_state.clone(),
+    };
+
+    <ClientStateHeader as prost::Message>::decode(client_state.value.as_slice()).with_context(
+        || {
+            format!(
+                "unable to decode sequence of client state `{}`",
+                client_state.type_url
+            )
+        },
+    )
+}
+
+/// Registrar used by client encoder plugins to register their client encoders for chains
+pub trait ClientEncoderRegistrar {
+    /// Registers a client encoder for chain with given ID
+    fn register(&mut self, chain_id: &str, client_encoder: Arc<dyn ClientEncoder>);
+}
@@ -1,4 +1,13 @@
 //! Cosmos SDK related types and implementations
 pub mod account;
+pub mod address;
+pub mod amino;
+pub mod bank;
 pub mod bit_array;
+pub mod chain_registry;
 pub mod crypto;
+#[cfg(feature = "ethermint")]
+pub mod eip712;
+pub mod feegrant;
+pub(crate) mod grpc_pool;
+pub mod name_resolver;
@@ -0,0 +1,232 @@
+//! Formatting of account addresses (bech32 or EIP-55 checksummed hex for ethermint based chains)
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error, Result};
+#[cfg(feature = "ethermint")]
+use anyhow::{ensure, Context};
+#[cfg(feature = "ethermint")]
+use bech32::{FromBase32, ToBase32, Variant};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ethermint")]
+use sha3::{Digest, Keccak256};
+
+/// Format in which account addresses are displayed and accepted for a chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AddressFormat {
+    /// Bech32 account address (e.g. `cosmos1...`)
+    #[default]
+    Bech32,
+    /// EIP-55 checksummed hex address (e.g. `0xAbC...`). Bech32 addresses are still accepted.
+    #[cfg(feature = "ethermint")]
+    Eip55,
+}
+
+impl AddressFormat {
+    /// Formats given bech32 account address
+    pub fn format(&self, address: &str) -> Result<String> {
+        match self {
+            Self::Bech32 => Ok(address.to_string()),
+            #[cfg(feature = "ethermint")]
+            Self::Eip55 => {
+                let (_, data, _) = bech32::decode(address)
+                    .context(format!("invalid bech32 address: {}", address))?;
+                to_eip55(&Vec::<u8>::from_base32(&data)?)
+            }
+        }
+    }
+
+    /// Parses given non-bech32 address into a bech32 account address with given prefix. Returns `None` if given
+    /// string is not such an address. Hex addresses are accepted in all the formats (with `ethermint` feature) as they
+    /// can never be mistaken for names.
+    #[cfg_attr(not(feature = "ethermint"), allow(unused_variables))]
+    pub fn parse(&self, address: &str, account_prefix: &str) -> Option<Result<String>> {
+        #[cfg(feature = "ethermint")]
+        if is_hex_address(address) {
+            return Some(from_eip55(address).and_then(|bytes| {
+                bech32::encode(account_prefix, bytes.to_base32(), Variant::Bech32)
+                    .map_err(Into::into)
+            }));
+        }
+
+        None
+    }
+}
+
+impl fmt::Display for AddressFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bech32 => write!(f, "bech32"),
+            #[cfg(feature = "ethermint")]
+            Self::Eip55 => write!(f, "eip55"),
+        }
+    }
+}
+
+impl FromStr for AddressFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bech32" => Ok(Self::Bech32),
+            #[cfg(feature = "ethermint")]
+            "eip55" => Ok(Self::Eip55),
+            _ => Err(anyhow!("invalid address format: {}", s)),
+        }
+    }
+}
+
+/// Returns `true` if given string is a `0x` prefixed hex address (checksum is not verified)
+#[cfg(feature = "ethermint")]
+fn is_hex_address(s: &str) -> bool {
+    s.len() == 42
+        && (s.starts_with("0x") || s.starts_with("0X"))
+        && s[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Encodes address bytes as EIP-55 checksummed hex address
+#[cfg(feature = "ethermint")]
+fn to_eip55(bytes: &[u8]) -> Result<String> {
+    ensure!(
+        bytes.len() == 20,
+        "address should be 20 bytes long for EIP-55 encoding"
+    );
+
+    let address = hex::encode(bytes);
+    let hash = Keccak256::digest(address.as_bytes());
+
+    let checksummed = address
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect::<String>();
+
+    Ok(format!("0x{}", checksummed))
+}
+
+/// Decodes a hex address. Checksum is verified for mixed-case addresses (all lowercase or all uppercase addresses do
+/// not carry a checksum).
+#[cfg(feature = "ethermint")]
+fn from_eip55(address: &str) -> Result<Vec<u8>> {
+    let hex_part = &address[2..];
+    let bytes = hex::decode(hex_part).context(format!("invalid hex address: {}", address))?;
+
+    let is_mixed_case = hex_part.chars().any(|c| c.is_ascii_lowercase())
+        && hex_part.chars().any(|c| c.is_ascii_uppercase());
+
+    if is_mixed_case {
+        ensure!(
+            to_eip55(&bytes)?[2..] == *hex_part,
+            "invalid EIP-55 checksum of address: {}",
+            address
+        );
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(all(test, feature = "ethermint"))]
+mod tests {
+    use super::*;
+
+    /// Test vectors of EIP-55
+    const CHECKSUMMED: [&str; 4] = [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+
+    /// Test vectors of EIP-55 whose checksummed form is all uppercase or all lowercase
+    const SINGLE_CASE: [&str; 4] = [
+        "0x52908400098527886E0F7030069857D2E4169EE7",
+        "0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+        "0xde709f2102306220921060314715629080e2fb77",
+        "0x27b1fdb04752bbc536007a920d24acb045561c26",
+    ];
+
+    #[test]
+    fn eip55_checksum_vectors() {
+        for address in CHECKSUMMED.iter().chain(SINGLE_CASE.iter()) {
+            let bytes = hex::decode(address[2..].to_ascii_lowercase()).unwrap();
+            assert_eq!(to_eip55(&bytes).unwrap(), *address);
+        }
+    }
+
+    #[test]
+    fn hex_addresses_round_trip_through_bech32() {
+        for address in CHECKSUMMED.iter().chain(SINGLE_CASE.iter()) {
+            let bech32_address = AddressFormat::Eip55
+                .parse(address, "cosmos")
+                .unwrap()
+                .unwrap();
+
+            assert!(bech32_address.starts_with("cosmos1"));
+            assert_eq!(
+                AddressFormat::Eip55.format(&bech32_address).unwrap(),
+                *address
+            );
+            // hex addresses are accepted in bech32 format as well
+            assert_eq!(
+                AddressFormat::Bech32
+                    .parse(address, "cosmos")
+                    .unwrap()
+                    .unwrap(),
+                bech32_address
+            );
+        }
+    }
+
+    #[test]
+    fn unchecksummed_addresses_are_accepted() {
+        for address in CHECKSUMMED {
+            let lowercase = format!("0x{}", address[2..].to_ascii_lowercase());
+            let uppercase = format!("0x{}", address[2..].to_ascii_uppercase());
+
+            assert_eq!(
+                from_eip55(&lowercase).unwrap(),
+                from_eip55(address).unwrap()
+            );
+            assert_eq!(
+                from_eip55(&uppercase).unwrap(),
+                from_eip55(address).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_checksum_is_rejected() {
+        for address in CHECKSUMMED {
+            // flips case of the last letter of address
+            let position = address.rfind(|c: char| c.is_ascii_alphabetic()).unwrap();
+            let mut tampered = address.to_string();
+            let flipped = match address.as_bytes()[position] {
+                c if c.is_ascii_uppercase() => c.to_ascii_lowercase(),
+                c => c.to_ascii_uppercase(),
+            };
+            tampered.replace_range(position..=position, &(flipped as char).to_string());
+
+            let error = AddressFormat::Eip55
+                .parse(&tampered, "cosmos")
+                .unwrap()
+                .unwrap_err();
+            assert!(error.to_string().contains("invalid EIP-55 checksum"));
+        }
+    }
+
+    #[test]
+    fn names_are_not_parsed_as_addresses() {
+        assert!(AddressFormat::Eip55
+            .parse("alice.cosmos", "cosmos")
+            .is_none());
+        assert!(AddressFormat::Eip55.parse("0x1234", "cosmos").is_none());
+    }
+}
@@ -0,0 +1,291 @@
+//! Legacy amino JSON encoding (used for signing transactions in `SIGN_MODE_LEGACY_AMINO_JSON`)
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error, Result};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use cosmos_sdk_proto::{
+    cosmos::{base::v1beta1::Coin, tx::v1beta1::Fee},
+    ibc::core::client::v1::Height,
+};
+use prost_types::{Any, Timestamp};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// Sign mode used when signing transactions for a chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SignMode {
+    /// `SIGN_MODE_DIRECT` (protobuf encoded sign doc)
+    #[default]
+    Direct,
+    /// `SIGN_MODE_LEGACY_AMINO_JSON` (canonical amino JSON sign doc)
+    LegacyAminoJson,
+    /// EIP-712 typed data of amino JSON sign doc (with `ExtensionOptionsWeb3Tx`, used by Ethermint based chains)
+    #[cfg(feature = "ethermint")]
+    Eip712,
+}
+
+impl SignMode {
+    /// Returns the value of `cosmos.tx.signing.v1beta1.SignMode` used in `ModeInfo` of signer infos
+    pub fn mode_info_value(&self) -> i32 {
+        match self {
+            Self::Direct => 1,
+            Self::LegacyAminoJson => 127,
+            #[cfg(feature = "ethermint")]
+            Self::Eip712 => 127,
+        }
+    }
+}
+
+impl fmt::Display for SignMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Direct => write!(f, "direct"),
+            Self::LegacyAminoJson => write!(f, "legacy-amino-json"),
+            #[cfg(feature = "ethermint")]
+            Self::Eip712 => write!(f, "eip712"),
+        }
+    }
+}
+
+impl FromStr for SignMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "direct" => Ok(Self::Direct),
+            "legacy-amino-json" => Ok(Self::LegacyAminoJson),
+            #[cfg(feature = "ethermint")]
+            "eip712" => Ok(Self::Eip712),
+            _ => Err(anyhow!("invalid sign mode: {}", s)),
+        }
+    }
+}
+
+/// Returns amino JSON `StdSignDoc` of a transaction
+pub fn std_sign_doc(
+    chain_id: &str,
+    account_number: u64,
+    sequence: u64,
+    fee: &Fee,
+    msgs: Vec<Value>,
+    memo: &str,
+) -> Value {
+    let mut std_fee = json!({
+        "amount": coins(&fee.amount),
+        "gas": fee.gas_limit.to_string(),
+    });
+
+    if !fee.payer.is_empty() {
+        std_fee["payer"] = Value::String(fee.payer.clone());
+    }
+
+    if !fee.granter.is_empty() {
+        std_fee["granter"] = Value::String(fee.granter.clone());
+    }
+
+    json!({
+        "account_number": account_number.to_string(),
+        "chain_id": chain_id,
+        "fee": std_fee,
+        "memo": memo,
+        "msgs": msgs,
+        "sequence": sequence.to_string(),
+    })
+}
+
+/// Returns canonical (sorted keys, no whitespace) JSON bytes of given value. Same as `MustSortJSON` of cosmos SDK (which
+/// uses Go's `encoding/json`), `&`, `<`, `>`, U+2028 and U+2029 are escaped, so that sign bytes match the ones verified
+/// by chain.
+pub fn canonical_json(value: Value) -> Result<Vec<u8>> {
+    let json = serde_json::to_string(&sort_keys(value))?;
+    Ok(escape_json(&json).into_bytes())
+}
+
+/// Returns amino JSON representation (`{"type": ..., "value": ...}`) of a message. Fields of `value` should be in the
+/// order of their protobuf definition (required for deriving EIP-712 types).
+pub fn amino_message(amino_name: &str, value: Value) -> Value {
+    json!({
+        "type": amino_name,
+        "value": value,
+    })
+}
+
+/// Returns amino JSON representation of a coin
+pub fn coin(coin: &Coin) -> Value {
+    json!({
+        "amount": coin.amount,
+        "denom": coin.denom,
+    })
+}
+
+/// Returns amino JSON representation of coins
+pub fn coins(coins: &[Coin]) -> Value {
+    Value::Array(coins.iter().map(coin).collect())
+}
+
+/// Returns amino JSON representation (RFC 3339) of a timestamp
+pub fn timestamp(timestamp: &Timestamp) -> Result<Value> {
+    let time = Utc
+        .timestamp_opt(timestamp.seconds, timestamp.nanos as u32)
+        .single()
+        .ok_or_else(|| anyhow!("invalid timestamp: {:?}", timestamp))?;
+
+    Ok(Value::String(
+        time.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+    ))
+}
+
+/// Returns amino JSON representation of a height (zero revision number and revision height are omitted)
+pub fn height(height: Option<&Height>) -> Value {
+    let height = height.cloned().unwrap_or_default();
+
+    omit_empty(json!({
+        "revision_number": uint(height.revision_number),
+        "revision_height": uint(height.revision_height),
+    }))
+}
+
+/// Returns amino JSON representation (base64) of bytes
+pub fn bytes(bytes: &[u8]) -> Value {
+    Value::String(base64::encode(bytes))
+}
+
+/// Returns amino JSON representation of an unsigned integer (amino JSON encodes 64-bit integers as strings), `null`
+/// for zero (which is omitted by [`omit_empty`])
+pub fn uint(value: u64) -> Value {
+    if value == 0 {
+        Value::Null
+    } else {
+        Value::String(value.to_string())
+    }
+}
+
+/// Returns amino JSON representation of an `Any` (`{"type": type_url, "value": base64 encoded protobuf bytes}`)
+pub fn any(any: Option<&Any>) -> Value {
+    match any {
+        None => Value::Null,
+        Some(any) => json!({
+            "type": any.type_url,
+            "value": bytes(&any.value),
+        }),
+    }
+}
+
+/// Removes empty values (empty strings, empty arrays and `null`) from objects in given value, same as `omitempty` of
+/// amino JSON (empty objects are kept as amino JSON does not omit non-nullable structs)
+pub fn omit_empty(value: Value) -> Value {
+    match value {
+        Value::Array(values) => Value::Array(values.into_iter().map(omit_empty).collect()),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(_, value)| !is_empty(value))
+                .map(|(key, value)| (key, omit_empty(value)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(values) => values.is_empty(),
+        _ => false,
+    }
+}
+
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_keys).collect()),
+        Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        value => value,
+    }
+}
+
+/// Escapes characters escaped by Go's `encoding/json` (these characters can only appear in strings of serialized JSON,
+/// so, escaping them in the whole JSON is safe)
+fn escape_json(json: &str) -> String {
+    let mut escaped = String::with_capacity(json.len());
+
+    for c in json.chars() {
+        match c {
+            '&' => escaped.push_str("\\u0026"),
+            '<' => escaped.push_str("\\u003c"),
+            '>' => escaped.push_str("\\u003e"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use k256::ecdsa::{signature::DigestSigner, Signature, SigningKey};
+    use sha2::{Digest, Sha256};
+
+    use super::*;
+
+    #[test]
+    fn canonical_json_escapes_html_characters_like_cosmos_sdk() {
+        let fee = Fee {
+            amount: vec![Coin {
+                denom: "stake".to_string(),
+                amount: "1000".to_string(),
+            }],
+            gas_limit: 200000,
+            payer: String::new(),
+            granter: String::new(),
+        };
+
+        let msg = amino_message(
+            "cosmos-sdk/MsgSend",
+            json!({
+                "from_address": "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu",
+                "to_address": "cosmos1qgpqyqszqgpqyqszqgpqyqszqgpqyqszrh8mx2",
+                "amount": [{ "denom": "stake", "amount": "10" }],
+            }),
+        );
+
+        let sign_doc = std_sign_doc("testing", 7, 3, &fee, vec![msg], "a&b<c>d");
+        let sign_bytes = canonical_json(sign_doc).unwrap();
+
+        // sign bytes produced by `MustSortJSON` of cosmos SDK (and `serializeSignDoc` of cosmjs)
+        assert_eq!(
+            std::str::from_utf8(&sign_bytes).unwrap(),
+            concat!(
+                r#"{"account_number":"7","chain_id":"testing","fee":{"amount":[{"amount":"1000","denom":"stake"}],"#,
+                r#""gas":"200000"},"memo":"a\u0026b\u003cc\u003ed","msgs":[{"type":"cosmos-sdk/MsgSend","value":"#,
+                r#"{"amount":[{"amount":"10","denom":"stake"}],"#,
+                r#""from_address":"cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu","#,
+                r#""to_address":"cosmos1qgpqyqszqgpqyqszqgpqyqszqgpqyqszrh8mx2"}}],"sequence":"3"}"#
+            )
+        );
+
+        let signing_key = SigningKey::from_bytes(&[1; 32]).unwrap();
+        let signature: Signature = signing_key.sign_digest(Sha256::new().chain(&sign_bytes));
+
+        assert_eq!(hex::encode(signature.as_ref()), "0b3967d6030a33a903c582226444270002002e992d09cced64c58c583002815f5eec7270afdeb4b3462025752606e9a87cd86a75c98f3c7876b3a72eba5bbcbf");
+    }
+
+    #[test]
+    fn canonical_json_escapes_line_separators() {
+        let json = canonical_json(json!({ "memo": "a\u{2028}b\u{2029}c" })).unwrap();
+
+        assert_eq!(json, br#"{"memo":"a\u2028b\u2029c"}"#.to_vec());
+    }
+}
@@ -0,0 +1,4 @@
+//! Token transfers between accounts (`x/bank` module)
+#![allow(missing_docs)]
+
+pub mod msg_send;
@@ -0,0 +1,22 @@
+use anyhow::Result;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use serde_json::{json, Value};
+
+use crate::cosmos::amino;
+
+pub const TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+
+const AMINO_NAME: &str = "cosmos-sdk/MsgSend";
+
+impl_any_conversion!(MsgSend, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgSend) -> Result<Value> {
+    Ok(amino::amino_message(
+        AMINO_NAME,
+        json!({
+            "from_address": message.from_address,
+            "to_address": message.to_address,
+            "amount": amino::coins(&message.amount),
+        }),
+    ))
+}
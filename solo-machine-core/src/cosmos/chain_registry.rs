@@ -0,0 +1,245 @@
+//! Fetching chain metadata (chain ID, bech32 prefix, fees and endpoints) from cosmos chain registry
+use std::time::Duration;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use hyper::{body, Body, Client, StatusCode, Uri};
+use hyper_rustls::HttpsConnector;
+use rust_decimal::Decimal;
+use serde::{de::DeserializeOwned, Deserialize};
+
+/// Base URL of cosmos chain registry (contents of `cosmos/chain-registry` repository)
+pub const DEFAULT_REGISTRY_URL: &str =
+    "https://raw.githubusercontent.com/cosmos/chain-registry/master";
+
+/// Timeout for fetching a file from chain registry
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Entry of a chain in chain registry (`chain.json` and `assetlist.json`)
+#[derive(Debug)]
+pub struct ChainRegistryEntry {
+    /// Chain metadata (`chain.json`)
+    pub chain: RegistryChain,
+    /// Assets of chain (`assetlist.json`)
+    pub assets: Vec<RegistryAsset>,
+}
+
+/// Chain metadata (`chain.json`)
+#[derive(Debug, Deserialize)]
+pub struct RegistryChain {
+    /// Name of chain in registry (e.g. `cosmoshub`)
+    pub chain_name: String,
+    /// Chain ID (e.g. `cosmoshub-4`)
+    pub chain_id: String,
+    /// Bech32 prefix of account addresses (e.g. `cosmos`)
+    pub bech32_prefix: String,
+    /// Tokens accepted for paying fees
+    #[serde(default)]
+    pub fees: Option<RegistryFees>,
+    /// Staking tokens of chain
+    #[serde(default)]
+    pub staking: Option<RegistryStaking>,
+    /// Public endpoints of chain
+    #[serde(default)]
+    pub apis: RegistryApis,
+}
+
+/// Tokens accepted for paying fees
+#[derive(Debug, Deserialize)]
+pub struct RegistryFees {
+    /// Fee tokens (in order of preference)
+    #[serde(default)]
+    pub fee_tokens: Vec<RegistryFeeToken>,
+}
+
+/// Token accepted for paying fees along with its gas prices
+#[derive(Debug, Deserialize)]
+pub struct RegistryFeeToken {
+    /// Denom of token
+    pub denom: String,
+    /// Fixed minimum gas price
+    #[serde(default)]
+    pub fixed_min_gas_price: Option<Decimal>,
+    /// Low gas price
+    #[serde(default)]
+    pub low_gas_price: Option<Decimal>,
+    /// Average gas price
+    #[serde(default)]
+    pub average_gas_price: Option<Decimal>,
+}
+
+/// Staking tokens of chain
+#[derive(Debug, Deserialize)]
+pub struct RegistryStaking {
+    /// Staking tokens
+    #[serde(default)]
+    pub staking_tokens: Vec<RegistryDenom>,
+}
+
+/// A denom
+#[derive(Debug, Deserialize)]
+pub struct RegistryDenom {
+    /// Denom of token
+    pub denom: String,
+}
+
+/// Public endpoints of chain
+#[derive(Debug, Default, Deserialize)]
+pub struct RegistryApis {
+    /// Tendermint RPC endpoints
+    #[serde(default)]
+    pub rpc: Vec<RegistryEndpoint>,
+    /// gRPC endpoints
+    #[serde(default)]
+    pub grpc: Vec<RegistryEndpoint>,
+}
+
+/// A public endpoint of chain
+#[derive(Debug, Deserialize)]
+pub struct RegistryEndpoint {
+    /// Address of endpoint
+    pub address: String,
+    /// Provider of endpoint
+    #[serde(default)]
+    pub provider: Option<String>,
+}
+
+/// Asset of chain (`assetlist.json`)
+#[derive(Debug, Deserialize)]
+pub struct RegistryAsset {
+    /// Base denom of asset
+    pub base: String,
+    /// Display denom of asset
+    #[serde(default)]
+    pub display: Option<String>,
+    /// Symbol of asset
+    #[serde(default)]
+    pub symbol: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegistryAssetList {
+    #[serde(default)]
+    assets: Vec<RegistryAsset>,
+}
+
+impl ChainRegistryEntry {
+    /// Fetches entry of chain with given name (e.g. `cosmoshub`, or `testnets/cosmoshubtestnet` for testnets) from
+    /// chain registry at given base URL
+    pub async fn fetch(registry_url: &str, chain_name: &str) -> Result<Self> {
+        let base = format!(
+            "{}/{}",
+            registry_url.trim_end_matches('/'),
+            chain_name.trim_matches('/')
+        );
+
+        let chain: RegistryChain = fetch_json(&format!("{}/chain.json", base)).await?;
+        let asset_list: RegistryAssetList = fetch_json(&format!("{}/assetlist.json", base)).await?;
+
+        Ok(Self {
+            chain,
+            assets: asset_list.assets,
+        })
+    }
+
+    /// Returns the preferred fee token of chain (first fee token, or first staking token or asset when chain does not
+    /// list any fee token)
+    pub fn fee_denom(&self) -> Option<&str> {
+        self.fee_token()
+            .map(|token| token.denom.as_str())
+            .or_else(|| {
+                self.chain
+                    .staking
+                    .as_ref()
+                    .and_then(|staking| staking.staking_tokens.first())
+                    .map(|token| token.denom.as_str())
+            })
+            .or_else(|| self.assets.first().map(|asset| asset.base.as_str()))
+    }
+
+    /// Returns fee amount for given gas limit computed using average (or low, or fixed minimum) gas price of
+    /// preferred fee token
+    pub fn fee_amount(&self, gas_limit: u64) -> Option<Decimal> {
+        let token = self.fee_token()?;
+        let gas_price = token
+            .average_gas_price
+            .or(token.low_gas_price)
+            .or(token.fixed_min_gas_price)?;
+
+        Some((gas_price * Decimal::from(gas_limit)).ceil())
+    }
+
+    /// Returns asset with given base denom
+    pub fn asset(&self, denom: &str) -> Option<&RegistryAsset> {
+        self.assets.iter().find(|asset| asset.base == denom)
+    }
+
+    /// Returns addresses of tendermint RPC endpoints of chain
+    pub fn rpc_addrs(&self) -> Vec<String> {
+        self.chain
+            .apis
+            .rpc
+            .iter()
+            .map(|endpoint| normalize_addr(&endpoint.address))
+            .collect()
+    }
+
+    /// Returns addresses of gRPC endpoints of chain
+    pub fn grpc_addrs(&self) -> Vec<String> {
+        self.chain
+            .apis
+            .grpc
+            .iter()
+            .map(|endpoint| normalize_addr(&endpoint.address))
+            .collect()
+    }
+
+    fn fee_token(&self) -> Option<&RegistryFeeToken> {
+        self.chain
+            .fees
+            .as_ref()
+            .and_then(|fees| fees.fee_tokens.first())
+    }
+}
+
+/// Adds scheme to endpoint addresses without one (registry lists some gRPC endpoints as `host:port`)
+fn normalize_addr(address: &str) -> String {
+    let address = address.trim_end_matches('/');
+
+    if address.contains("://") {
+        address.to_string()
+    } else if address.ends_with(":443") {
+        format!("https://{}", address)
+    } else {
+        format!("http://{}", address)
+    }
+}
+
+async fn fetch_json<T: DeserializeOwned>(url: &str) -> Result<T> {
+    let uri: Uri = url
+        .parse()
+        .with_context(|| format!("invalid chain registry url: {}", url))?;
+    let client = Client::builder().build::<_, Body>(HttpsConnector::with_native_roots());
+
+    let response = tokio::time::timeout(FETCH_TIMEOUT, client.get(uri))
+        .await
+        .map_err(|_| anyhow!("timed out fetching {}", url))?
+        .with_context(|| format!("unable to fetch {}", url))?;
+
+    ensure!(
+        response.status() != StatusCode::NOT_FOUND,
+        "{} not found in chain registry",
+        url
+    );
+    ensure!(
+        response.status().is_success(),
+        "unable to fetch {}: {}",
+        url,
+        response.status()
+    );
+
+    let bytes = body::to_bytes(response.into_body())
+        .await
+        .with_context(|| format!("unable to read response of {}", url))?;
+
+    serde_json::from_slice(&bytes).with_context(|| format!("invalid json in {}", url))
+}
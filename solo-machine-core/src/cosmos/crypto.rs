@@ -34,6 +34,7 @@ use crate::proto::{
     },
     proto_encode, AnyConvert,
 };
+use crate::signer::AddressAlgo;
 
 #[cfg(feature = "ethermint")]
 use self::eth_secp256k1::ETH_SECP256K1_PUB_KEY_TYPE_URL;
@@ -58,7 +59,13 @@ impl FromStr for PublicKeyAlgo {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             #[cfg(feature = "ethermint")]
-            "eth-secp256k1" => Ok(Self::EthSecp256k1),
+            "eth-secp256k1" => {
+                ensure!(
+                    !cfg!(feature = "fips"),
+                    "`eth-secp256k1` cannot be used in `fips` builds (Keccak-256 is not an approved hash function)"
+                );
+                Ok(Self::EthSecp256k1)
+            }
             "secp256k1" => Ok(Self::Secp256k1),
             _ => Err(anyhow!("invalid public key algorithm: {}", s)),
         }
@@ -98,6 +105,33 @@ impl PublicKey {
         }
     }
 
+    pub fn address_algo(&self) -> Option<AddressAlgo> {
+        match self {
+            #[cfg(feature = "ethermint")]
+            Self::EthSecp256k1(_) => Some(AddressAlgo::EthSecp256k1),
+            Self::Secp256k1(_) => Some(AddressAlgo::Secp256k1),
+            Self::Ed25519(_) | Self::Multisig(_) => None,
+        }
+    }
+
+    /// Returns this public key as used by accounts of chains generating addresses using given algorithm (the same
+    /// secp256k1 key is an `eth-secp256k1` key on ethermint based chains)
+    pub fn with_address_algo(self, algo: AddressAlgo) -> Result<Self> {
+        match (self, algo) {
+            #[cfg(feature = "ethermint")]
+            (Self::EthSecp256k1(key), AddressAlgo::Secp256k1) => Ok(Self::Secp256k1(key)),
+            #[cfg(feature = "ethermint")]
+            (Self::Secp256k1(key) | Self::EthSecp256k1(key), AddressAlgo::EthSecp256k1) => {
+                Ok(Self::EthSecp256k1(key))
+            }
+            #[cfg(feature = "ethermint")]
+            (_, AddressAlgo::EthSecp256k1) => Err(anyhow!(
+                "only secp256k1 keys can be used for `eth-secp256k1` addresses"
+            )),
+            (public_key, AddressAlgo::Secp256k1) => Ok(public_key),
+        }
+    }
+
     pub fn address(&self) -> Result<String> {
         Ok(hex::encode(&self.address_bytes()?))
     }
@@ -136,10 +170,15 @@ impl PublicKey {
                 PublicKey::EthSecp256k1(ref public_key),
                 SignatureData::Single(ref signature_data),
             ) => {
+                // ethermint signers sign keccak256 digest of message (instead of sha256)
                 let signature =
                     k256::ecdsa::Signature::try_from(signature_data.signature.as_slice())?;
-                k256::ecdsa::signature::Verifier::verify(public_key, message, &signature)
-                    .map_err(Into::into)
+                k256::ecdsa::signature::DigestVerifier::verify_digest(
+                    public_key,
+                    Keccak256::new().chain(message),
+                    &signature,
+                )
+                .map_err(Into::into)
             }
             _ => Err(anyhow!("invalid public key for signature type")),
         }
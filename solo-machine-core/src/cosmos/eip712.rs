@@ -0,0 +1,450 @@
+//! EIP-712 typed data signing of transactions (used by Ethermint based chains)
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryFrom,
+};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use k256::ecdsa::{recoverable, Signature, VerifyingKey};
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+
+pub use crate::proto::ethermint::types::v1::ExtensionOptionsWeb3Tx;
+
+/// Type URL of `ExtensionOptionsWeb3Tx`
+pub const WEB3_TX_TYPE_URL: &str = "/ethermint.types.v1.ExtensionOptionsWeb3Tx";
+
+impl_any_conversion!(ExtensionOptionsWeb3Tx, WEB3_TX_TYPE_URL);
+
+/// Type definitions of EIP-712 typed data (field order of each type is significant)
+type Types = BTreeMap<String, Vec<(String, String)>>;
+
+/// Returns EIP-155 chain ID from an Ethermint chain ID (e.g. `9001` for `evmos_9001-2`)
+pub fn parse_eip155_chain_id(chain_id: &str) -> Result<u64> {
+    let (_, rest) = chain_id
+        .split_once('_')
+        .ok_or_else(|| anyhow!("invalid ethermint chain id: {}", chain_id))?;
+    let (eip155_chain_id, _) = rest
+        .split_once('-')
+        .ok_or_else(|| anyhow!("invalid ethermint chain id: {}", chain_id))?;
+
+    eip155_chain_id
+        .parse()
+        .context(format!("invalid ethermint chain id: {}", chain_id))
+}
+
+/// Returns the bytes to be signed (`0x19 0x01 || domainSeparator || hashStruct(message)`) for legacy amino JSON sign
+/// doc of a transaction. Signers are expected to sign the keccak256 hash of these bytes.
+pub fn sign_bytes(eip155_chain_id: u64, sign_doc: &Value, fee_payer: &str) -> Result<Vec<u8>> {
+    let mut message = sign_doc.clone();
+    message["fee"]["feePayer"] = Value::String(fee_payer.to_owned());
+
+    let msgs = message["msgs"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let msg_value = msgs
+        .first()
+        .and_then(|msg| msg.get("value"))
+        .ok_or_else(|| anyhow!("transaction should have at least one message"))?;
+
+    // `MsgValue` type is derived from the first message, so, all the messages should be of same type
+    ensure!(
+        msgs.iter().all(|msg| msg["type"] == msgs[0]["type"]),
+        "EIP-712 signing of transactions with messages of different types is not supported"
+    );
+
+    let mut types = root_types();
+    add_value_types(&mut types, "MsgValue", "_", msg_value)?;
+
+    let domain = json!({
+        "name": "Cosmos Web3",
+        "version": "1.0.0",
+        "chainId": eip155_chain_id,
+        "verifyingContract": "cosmos",
+        "salt": "0",
+    });
+
+    let mut bytes = vec![0x19, 0x01];
+    bytes.extend_from_slice(&hash_struct(&types, "EIP712Domain", &domain)?);
+    bytes.extend_from_slice(&hash_struct(&types, "Tx", &message)?);
+
+    Ok(bytes)
+}
+
+/// Converts a 64 byte `[R || S]` signature on given sign bytes into 65 byte `[R || S || V]` signature
+pub fn to_recoverable_signature(
+    public_key: &VerifyingKey,
+    sign_bytes: &[u8],
+    signature: &[u8],
+) -> Result<Vec<u8>> {
+    let signature = Signature::try_from(signature).context("invalid secp256k1 signature")?;
+    let signature = recoverable::Signature::from_digest_trial_recovery(
+        public_key,
+        Keccak256::new().chain(sign_bytes),
+        &signature,
+    )
+    .map_err(|_| anyhow!("unable to compute recovery id of EIP-712 signature"))?;
+
+    Ok(signature.as_ref().to_vec())
+}
+
+fn root_types() -> Types {
+    let mut types = Types::new();
+
+    types.insert(
+        "EIP712Domain".to_owned(),
+        fields(&[
+            ("name", "string"),
+            ("version", "string"),
+            ("chainId", "uint256"),
+            ("verifyingContract", "string"),
+            ("salt", "string"),
+        ]),
+    );
+    types.insert(
+        "Tx".to_owned(),
+        fields(&[
+            ("account_number", "string"),
+            ("chain_id", "string"),
+            ("fee", "Fee"),
+            ("memo", "string"),
+            ("msgs", "Msg[]"),
+            ("sequence", "string"),
+        ]),
+    );
+    types.insert(
+        "Fee".to_owned(),
+        fields(&[
+            ("feePayer", "string"),
+            ("amount", "Coin[]"),
+            ("gas", "string"),
+        ]),
+    );
+    types.insert(
+        "Coin".to_owned(),
+        fields(&[("denom", "string"), ("amount", "string")]),
+    );
+    types.insert(
+        "Msg".to_owned(),
+        fields(&[("type", "string"), ("value", "MsgValue")]),
+    );
+
+    types
+}
+
+fn fields(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+    fields
+        .iter()
+        .map(|(name, ty)| (name.to_string(), ty.to_string()))
+        .collect()
+}
+
+/// Derives types of an object from its JSON value (nested objects are named after their path, e.g. `TypeTimeoutHeight`
+/// for `timeout_height`, empty arrays and `null` values are skipped)
+fn add_value_types(types: &mut Types, type_name: &str, prefix: &str, value: &Value) -> Result<()> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| anyhow!("expected JSON object for EIP-712 type `{}`", type_name))?;
+
+    let mut type_fields = Vec::with_capacity(object.len());
+
+    for (name, value) in object {
+        let field_prefix = format!("{}.{}", prefix, name);
+
+        let (ty, element) = match value {
+            Value::Array(values) => match values.first() {
+                None => continue,
+                Some(element) => (
+                    format!("{}[]", value_type(&field_prefix, element)?),
+                    element,
+                ),
+            },
+            Value::Null => continue,
+            _ => (value_type(&field_prefix, value)?, value),
+        };
+
+        if element.is_object() {
+            add_value_types(
+                types,
+                &sanitize_type_name(&field_prefix),
+                &field_prefix,
+                element,
+            )?;
+        }
+
+        type_fields.push((name.clone(), ty));
+    }
+
+    types.insert(type_name.to_owned(), type_fields);
+
+    Ok(())
+}
+
+fn value_type(prefix: &str, value: &Value) -> Result<String> {
+    match value {
+        Value::String(_) => Ok("string".to_owned()),
+        Value::Bool(_) => Ok("bool".to_owned()),
+        Value::Number(_) => Ok("int64".to_owned()),
+        Value::Object(_) => Ok(sanitize_type_name(prefix)),
+        _ => bail!("unsupported JSON value in EIP-712 typed data: {}", value),
+    }
+}
+
+/// Converts a field path to type name (e.g. `_.timeout_height` to `TypeTimeoutHeight`)
+fn sanitize_type_name(path: &str) -> String {
+    let mut type_name = String::new();
+
+    for part in path.split('.') {
+        if part == "_" {
+            type_name.push_str("Type");
+            continue;
+        }
+
+        for subpart in part.split('_') {
+            let mut chars = subpart.chars();
+
+            if let Some(first) = chars.next() {
+                type_name.extend(first.to_uppercase());
+                type_name.push_str(chars.as_str());
+            }
+        }
+    }
+
+    type_name
+}
+
+fn hash_struct(types: &Types, type_name: &str, value: &Value) -> Result<[u8; 32]> {
+    let mut hasher = Keccak256::new();
+    hasher.update(Keccak256::digest(encode_type(types, type_name)?.as_bytes()));
+
+    for (name, ty) in get_type(types, type_name)? {
+        hasher.update(encode_value(types, ty, &value[name.as_str()])?);
+    }
+
+    Ok(hasher.finalize().into())
+}
+
+fn encode_type(types: &Types, type_name: &str) -> Result<String> {
+    let mut dependencies = BTreeSet::new();
+    collect_dependencies(types, type_name, &mut dependencies)?;
+    dependencies.remove(type_name);
+
+    let mut encoded = String::new();
+
+    for name in std::iter::once(type_name).chain(dependencies.iter().map(String::as_str)) {
+        let fields = get_type(types, name)?
+            .iter()
+            .map(|(name, ty)| format!("{} {}", ty, name))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        encoded.push_str(&format!("{}({})", name, fields));
+    }
+
+    Ok(encoded)
+}
+
+fn collect_dependencies(
+    types: &Types,
+    type_name: &str,
+    dependencies: &mut BTreeSet<String>,
+) -> Result<()> {
+    if dependencies.contains(type_name) {
+        return Ok(());
+    }
+
+    dependencies.insert(type_name.to_owned());
+
+    for (_, ty) in get_type(types, type_name)? {
+        let ty = ty.trim_end_matches("[]");
+
+        if types.contains_key(ty) {
+            collect_dependencies(types, ty, dependencies)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn encode_value(types: &Types, ty: &str, value: &Value) -> Result<[u8; 32]> {
+    if let Some(element_type) = ty.strip_suffix("[]") {
+        let mut hasher = Keccak256::new();
+
+        for element in value.as_array().map(Vec::as_slice).unwrap_or_default() {
+            hasher.update(encode_value(types, element_type, element)?);
+        }
+
+        return Ok(hasher.finalize().into());
+    }
+
+    if types.contains_key(ty) {
+        return hash_struct(types, ty, value);
+    }
+
+    match ty {
+        "string" => {
+            let s = match value {
+                Value::String(s) => s.clone(),
+                Value::Null => String::new(),
+                value => value.to_string(),
+            };
+
+            Ok(Keccak256::digest(s.as_bytes()).into())
+        }
+        "bool" => Ok(encode_integer(value.as_bool().unwrap_or_default() as i128)),
+        "int64" | "uint64" | "uint256" => {
+            let integer = match value {
+                Value::Number(number) => number
+                    .as_i64()
+                    .map(i128::from)
+                    .or_else(|| number.as_u64().map(i128::from)),
+                Value::String(s) => s.parse().ok(),
+                Value::Null => Some(0),
+                _ => None,
+            }
+            .ok_or_else(|| anyhow!("invalid integer value in EIP-712 typed data: {}", value))?;
+
+            ensure!(
+                ty != "uint64" && ty != "uint256" || integer >= 0,
+                "invalid unsigned integer value in EIP-712 typed data: {}",
+                value
+            );
+
+            Ok(encode_integer(integer))
+        }
+        _ => bail!("unsupported EIP-712 type: {}", ty),
+    }
+}
+
+fn encode_integer(integer: i128) -> [u8; 32] {
+    let mut encoded = if integer < 0 { [0xff; 32] } else { [0; 32] };
+    encoded[16..].copy_from_slice(&integer.to_be_bytes());
+    encoded
+}
+
+fn get_type<'a>(types: &'a Types, type_name: &str) -> Result<&'a [(String, String)]> {
+    types
+        .get(type_name)
+        .map(Vec::as_slice)
+        .ok_or_else(|| anyhow!("unknown EIP-712 type: {}", type_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_type_matches_eip712_example() {
+        let mut types = Types::new();
+        types.insert(
+            "Mail".to_owned(),
+            fields(&[("from", "Person"), ("to", "Person"), ("contents", "string")]),
+        );
+        types.insert(
+            "Person".to_owned(),
+            fields(&[("wallet", "address"), ("name", "string")]),
+        );
+
+        let encoded = encode_type(&types, "Mail").unwrap();
+
+        assert_eq!(
+            encoded,
+            "Mail(Person from,Person to,string contents)Person(address wallet,string name)"
+        );
+        assert_eq!(
+            hex::encode(Keccak256::digest(encoded.as_bytes())),
+            "0da161f8e4238c5827dd64f2685de661647e4cda56b02996ab522f4a07ff0ff8"
+        );
+    }
+
+    #[test]
+    fn parses_eip155_chain_id() {
+        assert_eq!(parse_eip155_chain_id("evmos_9001-2").unwrap(), 9001);
+        assert!(parse_eip155_chain_id("cosmoshub-4").is_err());
+        assert!(parse_eip155_chain_id("evmos_abc-1").is_err());
+    }
+
+    #[test]
+    fn sanitizes_type_names() {
+        assert_eq!(sanitize_type_name("_.timeout_height"), "TypeTimeoutHeight");
+        assert_eq!(sanitize_type_name("_.packet.data"), "TypePacketData");
+    }
+
+    #[test]
+    fn encodes_integers_as_twos_complement() {
+        let mut one = [0; 32];
+        one[31] = 1;
+
+        assert_eq!(encode_integer(1), one);
+        assert_eq!(encode_integer(-1), [0xff; 32]);
+        assert_eq!(
+            encode_value(&Types::new(), "int64", &json!("-1")).unwrap(),
+            [0xff; 32]
+        );
+        assert!(encode_value(&Types::new(), "uint256", &json!(-1)).is_err());
+    }
+
+    #[test]
+    fn sign_bytes_of_msg_send() {
+        let sign_doc = json!({
+            "account_number": "7",
+            "chain_id": "evmos_9001-2",
+            "fee": {
+                "amount": [{ "amount": "1000", "denom": "aevmos" }],
+                "gas": "200000",
+            },
+            "memo": "",
+            "msgs": [{
+                "type": "cosmos-sdk/MsgSend",
+                "value": {
+                    "amount": [{ "amount": "10", "denom": "aevmos" }],
+                    "from_address": "evmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5k6w0ke",
+                    "to_address": "evmos1qgpqyqszqgpqyqszqgpqyqszqgpqyqszs6hzk8",
+                },
+            }],
+            "sequence": "3",
+        });
+
+        let mut types = root_types();
+        add_value_types(&mut types, "MsgValue", "_", &sign_doc["msgs"][0]["value"]).unwrap();
+
+        assert_eq!(
+            encode_type(&types, "Tx").unwrap(),
+            concat!(
+                "Tx(string account_number,string chain_id,Fee fee,string memo,Msg[] msgs,string sequence)",
+                "Coin(string denom,string amount)",
+                "Fee(string feePayer,Coin[] amount,string gas)",
+                "Msg(string type,MsgValue value)",
+                "MsgValue(TypeAmount[] amount,string from_address,string to_address)",
+                "TypeAmount(string amount,string denom)",
+            )
+        );
+
+        let sign_bytes = sign_bytes(
+            9001,
+            &sign_doc,
+            "evmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5k6w0ke",
+        )
+        .unwrap();
+
+        assert_eq!(&sign_bytes[..2], &[0x19, 0x01]);
+        assert_eq!(
+            hex::encode(Keccak256::digest(&sign_bytes)),
+            "a10736fda60d5daf4d54721471cb3f59b6e4631210237ab9862ab266c9a14350"
+        );
+    }
+
+    #[test]
+    fn rejects_messages_of_different_types() {
+        let sign_doc = json!({
+            "msgs": [
+                { "type": "cosmos-sdk/MsgSend", "value": { "from_address": "a" } },
+                { "type": "cosmos-sdk/MsgTransfer", "value": { "sender": "a" } },
+            ],
+        });
+
+        assert!(sign_bytes(9001, &sign_doc, "a").is_err());
+    }
+}
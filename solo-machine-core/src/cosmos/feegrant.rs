@@ -0,0 +1,6 @@
+//! Fee allowances granted by one account to another (`x/feegrant` module)
+#![allow(missing_docs)]
+
+pub mod basic_allowance;
+pub mod msg_grant_allowance;
+pub mod msg_revoke_allowance;
@@ -0,0 +1,25 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::{cosmos::amino, proto::cosmos::feegrant::v1beta1::BasicAllowance};
+
+pub const TYPE_URL: &str = "/cosmos.feegrant.v1beta1.BasicAllowance";
+
+const AMINO_NAME: &str = "cosmos-sdk/BasicAllowance";
+
+impl_any_conversion!(BasicAllowance, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(allowance: &BasicAllowance) -> Result<Value> {
+    let mut value = json!({});
+
+    // amino JSON omits empty values
+    if !allowance.spend_limit.is_empty() {
+        value["spend_limit"] = amino::coins(&allowance.spend_limit);
+    }
+
+    if let Some(ref expiration) = allowance.expiration {
+        value["expiration"] = amino::timestamp(expiration)?;
+    }
+
+    Ok(amino::amino_message(AMINO_NAME, value))
+}
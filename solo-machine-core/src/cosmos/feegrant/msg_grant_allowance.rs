@@ -0,0 +1,32 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+use crate::{
+    cosmos::amino,
+    proto::{
+        cosmos::feegrant::v1beta1::{BasicAllowance, MsgGrantAllowance},
+        AnyConvert,
+    },
+};
+
+pub const TYPE_URL: &str = "/cosmos.feegrant.v1beta1.MsgGrantAllowance";
+
+const AMINO_NAME: &str = "cosmos-sdk/MsgGrantAllowance";
+
+impl_any_conversion!(MsgGrantAllowance, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgGrantAllowance) -> Result<Value> {
+    let allowance = message
+        .allowance
+        .as_ref()
+        .ok_or_else(|| anyhow!("missing allowance in `MsgGrantAllowance`"))?;
+
+    Ok(amino::amino_message(
+        AMINO_NAME,
+        json!({
+            "granter": message.granter,
+            "grantee": message.grantee,
+            "allowance": BasicAllowance::from_any(allowance)?.to_amino_json()?,
+        }),
+    ))
+}
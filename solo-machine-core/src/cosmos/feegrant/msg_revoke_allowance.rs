@@ -0,0 +1,20 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::{cosmos::amino, proto::cosmos::feegrant::v1beta1::MsgRevokeAllowance};
+
+pub const TYPE_URL: &str = "/cosmos.feegrant.v1beta1.MsgRevokeAllowance";
+
+const AMINO_NAME: &str = "cosmos-sdk/MsgRevokeAllowance";
+
+impl_any_conversion!(MsgRevokeAllowance, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgRevokeAllowance) -> Result<Value> {
+    Ok(amino::amino_message(
+        AMINO_NAME,
+        json!({
+            "granter": message.granter,
+            "grantee": message.grantee,
+        }),
+    ))
+}
@@ -0,0 +1,118 @@
+//! Pool of gRPC channels shared by operations on chains
+use std::{collections::BTreeMap, fs, sync::Mutex};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint};
+
+use crate::{
+    model::{ChainConfig, GrpcTlsConfig, NetworkTimeouts},
+    proxy::{Proxy, ProxyConnector},
+    rate_limit::RateLimited,
+};
+
+const PEM_CERT_BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+const PEM_CERT_END: &str = "-----END CERTIFICATE-----";
+
+type ChannelKey = (String, GrpcTlsConfig, NetworkTimeouts, Option<Proxy>);
+
+/// Channel to gRPC endpoint of a chain (waiting for rate limit of endpoint before every request)
+pub(crate) type GrpcChannel = RateLimited<Channel>;
+
+/// Channels of current process (keyed by gRPC address, TLS settings, timeouts and proxy). Channels are cheap to clone
+/// and multiplex concurrent requests over a single HTTP/2 connection, so, all operations on an endpoint share the
+/// same channel.
+static CHANNELS: Mutex<BTreeMap<ChannelKey, Channel>> = Mutex::new(BTreeMap::new());
+
+/// Returns channel to gRPC endpoint at given address (using TLS settings, timeouts, proxy and rate limit of chain with
+/// given configuration), connecting to it when no channel is cached. Requests made on the channel fail once request timeout
+/// elapses and lost connections are re-established on next request (bounded by connect timeout).
+pub(crate) async fn grpc_channel(grpc_addr: &str, config: &ChainConfig) -> Result<GrpcChannel> {
+    let key = (
+        grpc_addr.to_string(),
+        config.grpc_tls.clone(),
+        config.timeouts,
+        config.proxy.clone(),
+    );
+
+    let rate_limited = |channel| RateLimited::new(channel, grpc_addr, config.rate_limit.clone());
+
+    if let Some(channel) = CHANNELS.lock().unwrap().get(&key) {
+        return Ok(rate_limited(channel.clone()));
+    }
+
+    let connector = ProxyConnector::new(config.proxy.clone(), config.timeouts.connect);
+    let endpoint = grpc_endpoint(grpc_addr, &config.grpc_tls)?.timeout(config.timeouts.request);
+    let connect = endpoint.connect_with_connector(connector);
+
+    // connect timeout of connector only bounds establishing of TCP connection (and not TLS and HTTP/2 handshakes)
+    let channel = tokio::time::timeout(config.timeouts.connect, connect)
+        .await
+        .with_context(|| format!("timed out connecting to grpc endpoint at {}", grpc_addr))?
+        .with_context(|| format!("unable to connect to grpc endpoint at {}", grpc_addr))?;
+
+    CHANNELS.lock().unwrap().insert(key, channel.clone());
+
+    Ok(rate_limited(channel))
+}
+
+/// Returns endpoint for given gRPC address. `https` addresses are verified using native root certificates along with
+/// custom root CA (if any) configured in TLS settings.
+pub(crate) fn grpc_endpoint(grpc_addr: &str, tls: &GrpcTlsConfig) -> Result<Endpoint> {
+    let endpoint = Endpoint::new(grpc_addr.to_string())
+        .with_context(|| format!("invalid grpc address: {}", grpc_addr))?;
+
+    if tls.is_default() {
+        return Ok(endpoint);
+    }
+
+    ensure!(
+        endpoint.uri().scheme_str() == Some("https"),
+        "grpc tls settings require an https grpc address: {}",
+        grpc_addr
+    );
+
+    let mut tls_config = ClientTlsConfig::new();
+
+    if let Some(ref ca_cert) = tls.ca_cert {
+        let pem = fs::read_to_string(ca_cert)
+            .with_context(|| format!("unable to read grpc ca certificate {}", ca_cert.display()))?;
+        validate_pem_certs(&pem)
+            .with_context(|| format!("invalid grpc ca certificate {}", ca_cert.display()))?;
+
+        tls_config = tls_config.ca_certificate(Certificate::from_pem(pem));
+    }
+
+    if let Some(ref domain_name) = tls.domain_name {
+        tls_config = tls_config.domain_name(domain_name.clone());
+    }
+
+    endpoint
+        .tls_config(tls_config)
+        .with_context(|| format!("invalid grpc tls settings for {}", grpc_addr))
+}
+
+/// Ensures that given PEM file contains at least one well-formed certificate (`tonic` panics when given a malformed
+/// certificate)
+fn validate_pem_certs(pem: &str) -> Result<()> {
+    let mut certs = 0;
+    let mut body: Option<String> = None;
+
+    for line in pem.lines().map(str::trim) {
+        if line == PEM_CERT_BEGIN {
+            body = Some(String::new());
+        } else if line == PEM_CERT_END {
+            let body = body
+                .take()
+                .ok_or_else(|| anyhow!("unexpected end of certificate"))?;
+            base64::decode(&body).context("invalid base64 encoding of certificate")?;
+            certs += 1;
+        } else if let Some(ref mut body) = body {
+            body.push_str(line);
+        }
+    }
+
+    ensure!(body.is_none(), "unterminated certificate");
+    ensure!(certs > 0, "no certificate found");
+
+    Ok(())
+}
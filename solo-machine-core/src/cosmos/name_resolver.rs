@@ -0,0 +1,299 @@
+//! Resolution of human-readable names (e.g. ICNS, Stargaze names) to account addresses
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Context, Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tonic::{
+    client::Grpc,
+    codec::ProstCodec,
+    codegen::http::uri::PathAndQuery,
+    transport::{Channel, Endpoint},
+    Request,
+};
+
+use crate::{
+    cosmos::address::AddressFormat,
+    model::NetworkTimeouts,
+    proto::cosmwasm::wasm::v1::{QuerySmartContractStateRequest, QuerySmartContractStateResponse},
+    proxy::{Proxy, ProxyConnector},
+};
+
+const SMART_CONTRACT_STATE_PATH: &str = "/cosmwasm.wasm.v1.Query/SmartContractState";
+
+/// Name service used to resolve human-readable names to account addresses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum NameResolverConfig {
+    /// Interchain Name Service (ICNS) resolver contract (e.g. `alice.cosmos`)
+    Icns {
+        /// gRPC address of the chain hosting resolver contract
+        grpc_addr: String,
+        /// Address of resolver contract
+        contract_address: String,
+    },
+    /// Stargaze names contract (e.g. `alice`)
+    StargazeNames {
+        /// gRPC address of the chain hosting names contract
+        grpc_addr: String,
+        /// Address of names contract
+        contract_address: String,
+    },
+}
+
+impl NameResolverConfig {
+    /// Creates a new name resolver configuration of given kind
+    pub fn new(kind: NameResolverKind, grpc_addr: String, contract_address: String) -> Self {
+        match kind {
+            NameResolverKind::Icns => Self::Icns {
+                grpc_addr,
+                contract_address,
+            },
+            NameResolverKind::StargazeNames => Self::StargazeNames {
+                grpc_addr,
+                contract_address,
+            },
+        }
+    }
+
+    /// Returns the kind of name resolver
+    pub fn kind(&self) -> NameResolverKind {
+        match self {
+            Self::Icns { .. } => NameResolverKind::Icns,
+            Self::StargazeNames { .. } => NameResolverKind::StargazeNames,
+        }
+    }
+
+    /// Connects to name service (through proxy when given) using given network timeouts. The returned resolver reuses
+    /// its connection for all the queries.
+    pub async fn connect(
+        &self,
+        proxy: Option<&Proxy>,
+        timeouts: &NetworkTimeouts,
+    ) -> Result<NameResolver> {
+        let grpc_addr = match self {
+            Self::Icns { grpc_addr, .. } => grpc_addr,
+            Self::StargazeNames { grpc_addr, .. } => grpc_addr,
+        };
+
+        let connector = ProxyConnector::new(proxy.cloned(), timeouts.connect);
+        let endpoint = Endpoint::new(grpc_addr.clone())?.timeout(timeouts.request);
+        let connect = endpoint.connect_with_connector(connector);
+
+        let channel: Channel = tokio::time::timeout(timeouts.connect, connect)
+            .await
+            .with_context(|| format!("timed out connecting to grpc query client at {}", grpc_addr))?
+            .with_context(|| format!("unable to connect to grpc query client at {}", grpc_addr))?;
+
+        Ok(NameResolver {
+            config: self.clone(),
+            client: Grpc::new(channel),
+        })
+    }
+}
+
+/// Name resolver connected to its name service
+#[derive(Debug, Clone)]
+pub struct NameResolver {
+    config: NameResolverConfig,
+    client: Grpc<Channel>,
+}
+
+impl NameResolver {
+    /// Resolves given name to an account address
+    pub async fn resolve(&self, name: &str) -> Result<String> {
+        let response = match self.config {
+            NameResolverConfig::Icns { .. } => {
+                let response = self
+                    .query(json!({ "address_by_icns": { "icns": name } }))
+                    .await?;
+                response.get("bech32_address").cloned()
+            }
+            NameResolverConfig::StargazeNames { .. } => Some(
+                self.query(json!({ "associated_address": { "name": name } }))
+                    .await?,
+            ),
+        };
+
+        response
+            .as_ref()
+            .and_then(Value::as_str)
+            .filter(|address| !address.is_empty())
+            .map(ToString::to_string)
+            .ok_or_else(|| anyhow!("unable to resolve name `{}` to an address", name))
+    }
+
+    /// Resolves given account address to its primary name (returns `None` if no name is registered)
+    pub async fn reverse_resolve(&self, address: &str) -> Result<Option<String>> {
+        let response = match self.config {
+            NameResolverConfig::Icns { .. } => {
+                let response = self
+                    .query(json!({ "primary_name": { "address": address } }))
+                    .await?;
+                response.get("name").cloned()
+            }
+            NameResolverConfig::StargazeNames { .. } => Some(
+                self.query(json!({ "name": { "address": address } }))
+                    .await?,
+            ),
+        };
+
+        Ok(response
+            .as_ref()
+            .and_then(Value::as_str)
+            .filter(|name| !name.is_empty())
+            .map(ToString::to_string))
+    }
+
+    async fn query(&self, query: Value) -> Result<Value> {
+        let contract_address = match self.config {
+            NameResolverConfig::Icns {
+                ref contract_address,
+                ..
+            } => contract_address,
+            NameResolverConfig::StargazeNames {
+                ref contract_address,
+                ..
+            } => contract_address,
+        };
+
+        let mut client = self.client.clone();
+        client
+            .ready()
+            .await
+            .context("grpc query client is not ready")?;
+
+        let response: QuerySmartContractStateResponse = client
+            .unary(
+                Request::new(QuerySmartContractStateRequest {
+                    address: contract_address.clone(),
+                    query_data: serde_json::to_vec(&query)?,
+                }),
+                PathAndQuery::from_static(SMART_CONTRACT_STATE_PATH),
+                ProstCodec::default(),
+            )
+            .await?
+            .into_inner();
+
+        serde_json::from_slice(&response.data).context("invalid json response from name resolver")
+    }
+}
+
+/// Supported name services
+#[derive(Debug, Clone, Copy)]
+pub enum NameResolverKind {
+    /// Interchain Name Service (ICNS)
+    Icns,
+    /// Stargaze names
+    StargazeNames,
+}
+
+impl fmt::Display for NameResolverKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Icns => write!(f, "icns"),
+            Self::StargazeNames => write!(f, "stargaze-names"),
+        }
+    }
+}
+
+impl FromStr for NameResolverKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "icns" => Ok(Self::Icns),
+            "stargaze-names" => Ok(Self::StargazeNames),
+            _ => Err(anyhow!("invalid name resolver: {}", s)),
+        }
+    }
+}
+
+/// Format used when displaying account addresses in outputs
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AddressDisplay {
+    /// Display raw account address
+    #[default]
+    Address,
+    /// Display reverse-resolved name (falls back to address when no name is registered)
+    Name,
+    /// Display both reverse-resolved name and account address
+    NameAndAddress,
+}
+
+impl fmt::Display for AddressDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Address => write!(f, "address"),
+            Self::Name => write!(f, "name"),
+            Self::NameAndAddress => write!(f, "name-and-address"),
+        }
+    }
+}
+
+impl FromStr for AddressDisplay {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "address" => Ok(Self::Address),
+            "name" => Ok(Self::Name),
+            "name-and-address" => Ok(Self::NameAndAddress),
+            _ => Err(anyhow!("invalid address display format: {}", s)),
+        }
+    }
+}
+
+/// Formats account addresses of a chain for display based on its address format and address display configuration
+#[derive(Debug, Clone)]
+pub struct AddressDisplayer {
+    address_format: AddressFormat,
+    address_display: AddressDisplay,
+    name_resolver: Option<NameResolver>,
+}
+
+impl AddressDisplayer {
+    /// Creates a new address displayer (addresses are reverse-resolved to names using given name resolver)
+    pub fn new(
+        address_format: AddressFormat,
+        address_display: AddressDisplay,
+        name_resolver: Option<NameResolver>,
+    ) -> Self {
+        Self {
+            address_format,
+            address_display,
+            name_resolver,
+        }
+    }
+
+    /// Formats given account address for display. Falls back to raw address if name cannot be resolved.
+    pub async fn display(&self, address: &str) -> String {
+        let formatted = self
+            .address_format
+            .format(address)
+            .unwrap_or_else(|_| address.to_string());
+
+        let name_resolver = match self.name_resolver {
+            Some(ref name_resolver) => name_resolver,
+            None => return formatted,
+        };
+
+        if let AddressDisplay::Address = self.address_display {
+            return formatted;
+        }
+
+        match name_resolver.reverse_resolve(address).await {
+            Ok(Some(name)) => match self.address_display {
+                AddressDisplay::Name => name,
+                _ => format!("{} ({})", name, formatted),
+            },
+            _ => formatted,
+        }
+    }
+}
+
+/// Returns `true` if given string is a valid bech32 account address with given prefix (and not a human-readable name)
+pub fn is_account_address(s: &str, account_prefix: &str) -> bool {
+    matches!(bech32::decode(s), Ok((hrp, _, _)) if hrp == account_prefix)
+}
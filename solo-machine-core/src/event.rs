@@ -2,13 +2,17 @@
 mod event_handler;
 
 use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{
     cosmos::crypto::PublicKey,
     ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, Identifier},
-    model::ConnectionDetails,
+    model::{
+        ConnectionDetails, HandshakeProgress, LabelKind, PendingOperationKind,
+        PendingOperationStatus,
+    },
 };
 
 pub use event_handler::*;
@@ -58,8 +62,121 @@ pub enum Event {
         /// New signer's public key
         new_public_key: PublicKey,
     },
+    /// Acknowledged a packet sent to solo machine from IBC enabled chain
+    PacketAcknowledged {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Sequence of acknowledged packet
+        packet_sequence: u64,
+        /// Hash of transaction on IBC enabled chain (in hex)
+        transaction_hash: String,
+    },
+    /// Timed out a packet sent to solo machine from IBC enabled chain (sender of packet is refunded on chain)
+    PacketTimedOut {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Sequence of timed out packet
+        packet_sequence: u64,
+        /// Hash of transaction on IBC enabled chain (in hex)
+        transaction_hash: String,
+    },
+    /// Resolved an operation left pending by an interrupted submission (its transaction is looked up on chain and
+    /// broadcast again if it is not found)
+    PendingOperationResolved {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// ID of pending operation
+        operation_id: i64,
+        /// Kind of operation
+        kind: PendingOperationKind,
+        /// Status of operation after resolving it
+        status: PendingOperationStatus,
+        /// Hash of transaction on IBC enabled chain (in hex)
+        transaction_hash: String,
+    },
+    /// Broadcast of an operation failed on IBC enabled chain (sequences consumed by the operation are rolled back)
+    BroadcastFailed {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// ID of pending operation
+        operation_id: i64,
+        /// Kind of operation
+        kind: PendingOperationKind,
+        /// Error returned by chain
+        error: String,
+    },
+    /// Solo machine client on IBC enabled chain is frozen or expired (chain rejects signatures of solo machine until
+    /// the client is recovered on chain)
+    ClientFrozen {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Client ID of solo machine client on IBC enabled chain (if connection is established)
+        client_id: Option<ClientId>,
+    },
+    /// Local sequence of solo machine diverged from sequence of solo machine client on IBC enabled chain
+    SequenceDiverged {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Client ID of solo machine client on IBC enabled chain
+        client_id: ClientId,
+        /// Sequence of chain stored on solo machine
+        local: u64,
+        /// Sequence of solo machine client on chain
+        on_chain: u64,
+    },
+    /// Transfer of tokens exceeded a spending limit of signing policy (transfer is rejected unless escalation was
+    /// approved)
+    SpendingLimitExceeded {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Denom of transferred tokens
+        denom: String,
+        /// Amount of transferred tokens
+        amount: u64,
+        /// Amount of tokens already transferred within window of limit
+        spent: u64,
+        /// Maximum amount of tokens transferred within window of limit
+        limit: u64,
+        /// Length of window of limit (e.g. `24h`)
+        window: String,
+        /// `true` if transfer was approved by escalation
+        approved: bool,
+    },
+    /// Tendermint client of IBC enabled chain on solo machine is about to expire (e.g. because it could not be
+    /// refreshed)
+    ClientNearingExpiry {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Client ID of IBC enabled chain on solo machine
+        client_id: ClientId,
+        /// Time after which client expires unless it is refreshed
+        expires_at: DateTime<Utc>,
+    },
+    /// Refreshed tendermint client on solo machine with latest verified header of IBC enabled chain
+    TendermintClientRefreshed {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Client ID of IBC enabled chain on solo machine
+        client_id: ClientId,
+        /// New latest height of tendermint client
+        height: u64,
+    },
+    /// Synced consensus timestamp of solo machine to block time of IBC enabled chain
+    ConsensusTimestampSynced {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// New consensus timestamp of solo machine
+        consensus_timestamp: DateTime<Utc>,
+    },
 
     // ----- IBC connection handshake events ----- //
+    /// Resumed an interrupted connection handshake (completed steps are skipped)
+    ConnectionHandshakeResumed {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Progress of handshake before resuming
+        progress: HandshakeProgress,
+    },
     /// Created solo machine client on IBC enabled chain
     CreatedSoloMachineClient {
         /// Client ID of solo machine client on IBC enabled chain
@@ -124,8 +241,152 @@ pub enum Event {
         /// Chain ID
         chain_id: ChainId,
     },
+    /// Imported state of a chain exported from another solo machine
+    ChainImported {
+        /// Chain ID
+        chain_id: ChainId,
+    },
+    /// Reset IBC state of a chain after the chain itself was reset (e.g. a devnet restarted from a new genesis)
+    ChainStateReset {
+        /// Chain ID
+        chain_id: ChainId,
+        /// New trusted height of chain
+        trusted_height: u64,
+        /// New trusted hash of chain (in hex)
+        trusted_hash: String,
+    },
+    /// Granted fee allowance to solo machine signer on IBC enabled chain
+    FeeAllowanceGranted {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Address of account granting fee allowance
+        granter: String,
+        /// Address of account receiving fee allowance
+        grantee: String,
+        /// Hash of transaction on IBC enabled chain (in hex)
+        transaction_hash: String,
+    },
+    /// Revoked fee allowance of solo machine signer on IBC enabled chain
+    FeeAllowanceRevoked {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Address of account which granted fee allowance
+        granter: String,
+        /// Address of account whose fee allowance is revoked
+        grantee: String,
+        /// Hash of transaction on IBC enabled chain (in hex)
+        transaction_hash: String,
+    },
+    /// Sent tokens from one account to another on IBC enabled chain (e.g. for funding solo machine signer)
+    TokensSent {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Address of account sending tokens
+        from_address: String,
+        /// Address of account receiving tokens
+        to_address: String,
+        /// Amount of tokens sent
+        amount: u64,
+        /// Denom of tokens sent
+        denom: String,
+        /// Hash of transaction on IBC enabled chain (in hex)
+        transaction_hash: String,
+    },
+
+    /// Assigned a label to a chain, connection or channel
+    LabelAssigned {
+        /// Chain ID
+        chain_id: ChainId,
+        /// Kind of labelled entity
+        kind: LabelKind,
+        /// Identifier of labelled entity
+        target: String,
+        /// Label
+        label: String,
+    },
+    /// Removed label of a chain, connection or channel
+    LabelRemoved {
+        /// Chain ID
+        chain_id: ChainId,
+        /// Kind of labelled entity
+        kind: LabelKind,
+        /// Identifier of labelled entity
+        target: String,
+        /// Label
+        label: String,
+    },
+
+    // ----- Macro events ----- //
+    /// Completed a step of macro run
+    MacroStepCompleted {
+        /// ID of macro run
+        run_id: i64,
+        /// Name of macro
+        name: String,
+        /// Index of completed step
+        step: usize,
+        /// Output of step (e.g. transaction hash)
+        output: String,
+    },
+    /// A step of macro run failed and is retried
+    MacroStepRetried {
+        /// ID of macro run
+        run_id: i64,
+        /// Name of macro
+        name: String,
+        /// Index of retried step
+        step: usize,
+        /// Number of retry (starting from 1)
+        attempt: u32,
+        /// Error message of failed attempt
+        error: String,
+    },
+    /// Completed all the steps of a macro run
+    MacroRunCompleted {
+        /// ID of macro run
+        run_id: i64,
+        /// Name of macro
+        name: String,
+    },
+    /// A step of macro run failed (macro run can be resumed)
+    MacroRunFailed {
+        /// ID of macro run
+        run_id: i64,
+        /// Name of macro
+        name: String,
+        /// Index of failed step
+        step: usize,
+        /// Error message
+        error: String,
+    },
+
+    // ----- Chain liveness events ----- //
+    /// IBC enabled chain stopped producing blocks (operations consuming sequences are paused)
+    ChainHalted {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Latest block height of chain
+        latest_height: u64,
+        /// Time of latest block of chain
+        latest_block_time: DateTime<Utc>,
+    },
+    /// IBC enabled chain resumed producing blocks (paused operations are resumed)
+    ChainResumed {
+        /// Chain ID of IBC enabled chain
+        chain_id: ChainId,
+        /// Latest block height of chain
+        latest_height: u64,
+    },
 
     // ----- Other events ----- //
+    /// Break in hash chain of audit log was explicitly accepted after investigation (entries between the break and
+    /// this event are not verified anymore)
+    AuditLogBreakAccepted {
+        /// ID of the first entry which breaks hash chain
+        entry_id: i64,
+        /// Reason of the break
+        reason: String,
+    },
     /// Warning
     Warning {
         /// Warning message
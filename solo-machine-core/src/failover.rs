@@ -0,0 +1,202 @@
+//! Failover between multiple RPC and gRPC endpoints of a chain along with tracking of their health
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{anyhow, ensure, Error, Result};
+use chrono::{DateTime, Utc};
+use tendermint_rpc::Client;
+use tracing::Instrument;
+
+use crate::{
+    model::{ChainConfig, NetworkTimeouts},
+    rpc_client::TimeoutRpcClient,
+};
+
+/// Time after which a failed endpoint is preferred again over the endpoints configured after it (so that operations
+/// move back to primary endpoint once it recovers)
+const FAILBACK_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Maximum time spent checking an RPC endpoint before failing over to the next one
+const RPC_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Health of endpoints observed by current process (keyed by address)
+static ENDPOINT_HEALTH: Mutex<BTreeMap<String, EndpointHealth>> = Mutex::new(BTreeMap::new());
+
+/// Health of an endpoint as observed by current process
+#[derive(Debug, Clone, Default)]
+pub struct EndpointHealth {
+    /// Number of successful requests
+    pub successes: u64,
+    /// Number of failed requests
+    pub failures: u64,
+    /// Number of failed requests since last successful one
+    pub consecutive_failures: u32,
+    /// Error of last failed request
+    pub last_error: Option<String>,
+    /// Time of last failed request
+    pub last_failure_at: Option<DateTime<Utc>>,
+}
+
+impl EndpointHealth {
+    /// Returns `true` if endpoint is not failing (or did not fail recently)
+    pub fn is_healthy(&self) -> bool {
+        match self.last_failure_at {
+            Some(last_failure_at) if self.consecutive_failures > 0 => (Utc::now()
+                - last_failure_at)
+                .to_std()
+                .map(|elapsed| elapsed >= FAILBACK_COOLDOWN)
+                .unwrap_or(false),
+            _ => true,
+        }
+    }
+}
+
+/// Returns health of endpoint at given address observed by current process
+pub fn endpoint_health(addr: &str) -> EndpointHealth {
+    ENDPOINT_HEALTH
+        .lock()
+        .unwrap()
+        .get(addr)
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn record_success(addr: &str) {
+    let mut health = ENDPOINT_HEALTH.lock().unwrap();
+    let health = health.entry(addr.to_string()).or_default();
+
+    health.successes += 1;
+    health.consecutive_failures = 0;
+}
+
+fn record_failure(addr: &str, error: &Error) {
+    tracing::warn!(endpoint = addr, "endpoint failed: {:#}", error);
+
+    let mut health = ENDPOINT_HEALTH.lock().unwrap();
+    let health = health.entry(addr.to_string()).or_default();
+
+    health.failures += 1;
+    health.consecutive_failures += 1;
+    health.last_error = Some(format!("{:#}", error));
+    health.last_failure_at = Some(Utc::now());
+}
+
+/// Orders given endpoints for failover: healthy endpoints in configured order followed by failing ones (endpoints
+/// with fewer consecutive failures first)
+pub fn failover_order(addrs: &[String]) -> Vec<String> {
+    let health = ENDPOINT_HEALTH.lock().unwrap();
+
+    let mut ordered: Vec<(usize, &String)> = addrs.iter().enumerate().collect();
+    ordered.sort_by_key(|(index, addr)| match health.get(*addr) {
+        Some(health) if !health.is_healthy() => (1, health.consecutive_failures, *index),
+        _ => (0, 0, *index),
+    });
+
+    ordered.into_iter().map(|(_, addr)| addr.clone()).collect()
+}
+
+/// Returns gRPC endpoint of chain currently preferred for requests which should not be sent to multiple endpoints
+/// (e.g. broadcasting transactions)
+pub fn active_grpc_addr(config: &ChainConfig) -> String {
+    failover_order(&config.grpc_addrs())
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| config.grpc_addr.clone())
+}
+
+/// Selects RPC endpoint of chain used for an operation. When fallback endpoints are configured, endpoints are checked
+/// in failover order and the first one which is reachable and not serving stale data (catching up, or latest block
+/// older than halt threshold of chain) is selected. Primary endpoint is selected when every endpoint is failing, so
+/// that the operation reports its error (or detects that the chain is halted).
+pub async fn select_rpc_addr(config: &ChainConfig) -> String {
+    if config.fallback_rpc_addrs.is_empty() {
+        return config.rpc_addr.clone();
+    }
+
+    for addr in failover_order(&config.rpc_addrs()) {
+        match check_rpc(config, &addr).await {
+            Ok(()) => {
+                record_success(&addr);
+                return addr;
+            }
+            Err(error) => record_failure(&addr, &error),
+        }
+    }
+
+    config.rpc_addr.clone()
+}
+
+async fn check_rpc(config: &ChainConfig, addr: &str) -> Result<()> {
+    let timeouts = NetworkTimeouts {
+        request: config.timeouts.request.min(RPC_CHECK_TIMEOUT),
+        ..config.timeouts
+    };
+    let status = TimeoutRpcClient::new(addr, &timeouts, config.proxy.as_ref())?
+        .with_rate_limit(config.rate_limit.clone())
+        .status()
+        .await?;
+
+    ensure!(!status.sync_info.catching_up, "node is catching up");
+
+    if !config.halt_threshold.is_zero() {
+        let block_age = SystemTime::now()
+            .duration_since(SystemTime::from(status.sync_info.latest_block_time))
+            .unwrap_or_default();
+
+        ensure!(
+            block_age <= config.halt_threshold,
+            "latest block is {}s old",
+            block_age.as_secs()
+        );
+    }
+
+    Ok(())
+}
+
+/// Runs given query on gRPC endpoints of chain in failover order, failing over to the next endpoint when it fails due
+/// to transient errors. Once every endpoint has failed, the query is retried according to retry policy of chain.
+pub async fn grpc_query<T, F, Fut>(
+    config: &ChainConfig,
+    description: &str,
+    mut query: F,
+) -> Result<T>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let policy = &config.retry_policy;
+    let mut attempt = 1;
+
+    loop {
+        let mut last_error = None;
+
+        for addr in failover_order(&config.grpc_addrs()) {
+            let span =
+                tracing::info_span!("grpc_query", query = description, endpoint = %addr, attempt);
+
+            match query(addr.clone()).instrument(span).await {
+                Ok(value) => {
+                    record_success(&addr);
+                    return Ok(value);
+                }
+                Err(error) if policy.is_retryable(&error) => {
+                    record_failure(&addr, &error);
+                    last_error = Some(error);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        let error = last_error.ok_or_else(|| anyhow!("no grpc endpoint configured"))?;
+
+        if !policy.wait_before_retry(description, attempt, &error).await {
+            return Err(error);
+        }
+
+        attempt += 1;
+    }
+}
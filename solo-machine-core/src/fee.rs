@@ -0,0 +1,135 @@
+//! Strategies for computing fees of transactions
+use std::{fmt, sync::Arc};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use async_trait::async_trait;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{
+    service_client::ServiceClient, AuthInfo, SimulateRequest, Tx, TxBody,
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cosmos::grpc_pool::grpc_channel,
+    failover::grpc_query,
+    model::{Chain, Fee},
+};
+
+/// Unsigned transaction for which fee is computed (fee in auth info is the one configured for chain)
+pub struct FeeRequest<'a> {
+    /// Body of transaction
+    pub tx_body: &'a TxBody,
+    /// Auth info of transaction
+    pub auth_info: &'a AuthInfo,
+}
+
+/// Strategy for computing fee (amount, denom and gas limit) of transactions sent to a chain
+#[async_trait]
+pub trait FeeStrategy: Send + Sync {
+    /// Computes fee of given transaction
+    async fn compute_fee(&self, chain: &Chain, request: FeeRequest<'_>) -> Result<Fee>;
+}
+
+/// Kind of fee strategy used for a chain
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", tag = "kind")]
+pub enum FeeStrategyKind {
+    /// Fee configured for chain is used as is
+    #[default]
+    Static,
+    /// Gas limit is computed by simulating transaction on chain (multiplied by `gas_adjustment`) and fee amount is
+    /// scaled using gas price of fee configured for chain
+    Simulate {
+        /// Multiplier applied to simulated gas usage
+        gas_adjustment: f64,
+    },
+    /// Fee is computed by fee strategy plugin (`--fee-strategy-plugin`)
+    Plugin,
+}
+
+impl fmt::Display for FeeStrategyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Static => write!(f, "static"),
+            Self::Simulate { gas_adjustment } => {
+                write!(f, "simulate (gas adjustment: {})", gas_adjustment)
+            }
+            Self::Plugin => write!(f, "plugin"),
+        }
+    }
+}
+
+/// Fee strategy which uses fee configured for chain as is
+pub struct StaticFeeStrategy;
+
+#[async_trait]
+impl FeeStrategy for StaticFeeStrategy {
+    async fn compute_fee(&self, chain: &Chain, _request: FeeRequest<'_>) -> Result<Fee> {
+        Ok(chain.config.fee.clone())
+    }
+}
+
+/// Fee strategy which simulates transaction on chain to compute gas limit
+pub struct SimulateFeeStrategy {
+    /// Multiplier applied to simulated gas usage
+    pub gas_adjustment: f64,
+}
+
+#[async_trait]
+impl FeeStrategy for SimulateFeeStrategy {
+    async fn compute_fee(&self, chain: &Chain, request: FeeRequest<'_>) -> Result<Fee> {
+        ensure!(
+            self.gas_adjustment > 0.0,
+            "gas adjustment should be positive: {}",
+            self.gas_adjustment
+        );
+
+        let config = &chain.config;
+
+        // signatures are not verified when simulating, but there should be one (empty) signature per signer
+        let tx = Tx {
+            body: Some(request.tx_body.clone()),
+            auth_info: Some(request.auth_info.clone()),
+            signatures: vec![Vec::new(); request.auth_info.signer_infos.len()],
+        };
+
+        let gas_used = grpc_query(&chain.config, "transaction simulation", |grpc_addr| {
+            let tx = tx.clone();
+
+            async move {
+                ServiceClient::new(grpc_channel(&grpc_addr, config).await?)
+                    .simulate(SimulateRequest { tx: Some(tx) })
+                    .await
+                    .context("unable to simulate transaction")
+            }
+        })
+        .await?
+        .into_inner()
+        .gas_info
+        .ok_or_else(|| anyhow!("gas info is missing from simulation response"))?
+        .gas_used;
+
+        let configured = &chain.config.fee;
+
+        ensure!(
+            configured.gas_limit > 0,
+            "gas limit of chain should be greater than zero to compute gas price"
+        );
+
+        let gas_limit = (gas_used as f64 * self.gas_adjustment).ceil() as u64;
+        let gas_price = configured.amount / Decimal::from(configured.gas_limit);
+
+        Ok(Fee {
+            amount: (gas_price * Decimal::from(gas_limit)).ceil(),
+            denom: configured.denom.clone(),
+            gas_limit,
+            granter: configured.granter.clone(),
+        })
+    }
+}
+
+/// Registrar used by fee strategy plugins to register their fee strategy
+pub trait FeeStrategyRegistrar {
+    /// Registers a new fee strategy
+    fn register(&mut self, fee_strategy: Arc<dyn FeeStrategy>);
+}
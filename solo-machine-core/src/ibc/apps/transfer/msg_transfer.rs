@@ -1,5 +1,41 @@
+use anyhow::Result;
 use cosmos_sdk_proto::ibc::applications::transfer::v1::MsgTransfer;
+use serde_json::{json, Value};
+
+use crate::cosmos::amino;
 
 const TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
 
-impl_any_conversion!(MsgTransfer, TYPE_URL);
+const AMINO_NAME: &str = "cosmos-sdk/MsgTransfer";
+
+impl_any_conversion!(MsgTransfer, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgTransfer) -> Result<Value> {
+    let mut value = json!({
+        "source_port": message.source_port,
+        "source_channel": message.source_channel,
+        "token": amino::coin(&message.token.clone().unwrap_or_default()),
+        "sender": message.sender,
+        "receiver": message.receiver,
+        "timeout_height": {},
+    });
+
+    // amino JSON omits zero values
+    if let Some(ref timeout_height) = message.timeout_height {
+        if timeout_height.revision_number != 0 {
+            value["timeout_height"]["revision_number"] =
+                Value::String(timeout_height.revision_number.to_string());
+        }
+
+        if timeout_height.revision_height != 0 {
+            value["timeout_height"]["revision_height"] =
+                Value::String(timeout_height.revision_height.to_string());
+        }
+    }
+
+    if message.timeout_timestamp != 0 {
+        value["timeout_timestamp"] = Value::String(message.timeout_timestamp.to_string());
+    }
+
+    Ok(amino::amino_message(AMINO_NAME, value))
+}
@@ -1,2 +1,3 @@
 pub mod ics06_solo_machine;
 pub mod ics07_tendermint;
+pub mod ics08_wasm;
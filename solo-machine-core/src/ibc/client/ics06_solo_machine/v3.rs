@@ -0,0 +1,3 @@
+pub mod client_state;
+pub mod consensus_state;
+pub mod header;
@@ -0,0 +1,3 @@
+pub mod client_message;
+pub mod client_state;
+pub mod consensus_state;
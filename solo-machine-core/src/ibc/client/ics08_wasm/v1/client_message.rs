@@ -0,0 +1,5 @@
+use crate::proto::ibc::lightclients::wasm::v1::ClientMessage;
+
+const TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ClientMessage";
+
+impl_any_conversion!(ClientMessage, TYPE_URL);
@@ -0,0 +1,5 @@
+use crate::proto::ibc::lightclients::wasm::v1::ClientState;
+
+const TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ClientState";
+
+impl_any_conversion!(ClientState, TYPE_URL);
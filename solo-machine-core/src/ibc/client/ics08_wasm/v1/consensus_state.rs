@@ -0,0 +1,5 @@
+use crate::proto::ibc::lightclients::wasm::v1::ConsensusState;
+
+const TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ConsensusState";
+
+impl_any_conversion!(ConsensusState, TYPE_URL);
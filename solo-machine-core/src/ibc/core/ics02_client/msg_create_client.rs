@@ -1,5 +1,22 @@
+use anyhow::Result;
 use cosmos_sdk_proto::ibc::core::client::v1::MsgCreateClient;
+use serde_json::{json, Value};
+
+use crate::cosmos::amino;
 
 const TYPE_URL: &str = "/ibc.core.client.v1.MsgCreateClient";
 
-impl_any_conversion!(MsgCreateClient, TYPE_URL);
+const AMINO_NAME: &str = "cosmos-sdk/MsgCreateClient";
+
+impl_any_conversion!(MsgCreateClient, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgCreateClient) -> Result<Value> {
+    Ok(amino::amino_message(
+        AMINO_NAME,
+        amino::omit_empty(json!({
+            "client_state": amino::any(message.client_state.as_ref()),
+            "consensus_state": amino::any(message.consensus_state.as_ref()),
+            "signer": message.signer,
+        })),
+    ))
+}
@@ -1,5 +1,22 @@
+use anyhow::Result;
 use cosmos_sdk_proto::ibc::core::client::v1::MsgUpdateClient;
+use serde_json::{json, Value};
+
+use crate::cosmos::amino;
 
 const TYPE_URL: &str = "/ibc.core.client.v1.MsgUpdateClient";
 
-impl_any_conversion!(MsgUpdateClient, TYPE_URL);
+const AMINO_NAME: &str = "cosmos-sdk/MsgUpdateClient";
+
+impl_any_conversion!(MsgUpdateClient, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgUpdateClient) -> Result<Value> {
+    Ok(amino::amino_message(
+        AMINO_NAME,
+        amino::omit_empty(json!({
+            "client_id": message.client_id,
+            "header": amino::any(message.header.as_ref()),
+            "signer": message.signer,
+        })),
+    ))
+}
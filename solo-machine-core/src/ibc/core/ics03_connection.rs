@@ -1,2 +1,15 @@
 pub mod msg_connection_open_ack;
 pub mod msg_connection_open_init;
+
+use cosmos_sdk_proto::ibc::core::connection::v1::Version;
+use serde_json::{json, Value};
+
+use crate::cosmos::amino;
+
+/// Returns amino JSON representation of a connection version
+fn version_to_amino_json(version: &Version) -> Value {
+    amino::omit_empty(json!({
+        "identifier": version.identifier,
+        "features": version.features,
+    }))
+}
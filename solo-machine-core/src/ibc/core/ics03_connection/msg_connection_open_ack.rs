@@ -1,5 +1,29 @@
+use anyhow::Result;
 use cosmos_sdk_proto::ibc::core::connection::v1::MsgConnectionOpenAck;
+use serde_json::{json, Value};
+
+use crate::{cosmos::amino, ibc::core::ics03_connection::version_to_amino_json};
 
 const TYPE_URL: &str = "/ibc.core.connection.v1.MsgConnectionOpenAck";
 
-impl_any_conversion!(MsgConnectionOpenAck, TYPE_URL);
+const AMINO_NAME: &str = "cosmos-sdk/MsgConnectionOpenAck";
+
+impl_any_conversion!(MsgConnectionOpenAck, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgConnectionOpenAck) -> Result<Value> {
+    Ok(amino::amino_message(
+        AMINO_NAME,
+        amino::omit_empty(json!({
+            "connection_id": message.connection_id,
+            "counterparty_connection_id": message.counterparty_connection_id,
+            "version": message.version.as_ref().map(version_to_amino_json),
+            "client_state": amino::any(message.client_state.as_ref()),
+            "proof_height": amino::height(message.proof_height.as_ref()),
+            "proof_try": amino::bytes(&message.proof_try),
+            "proof_client": amino::bytes(&message.proof_client),
+            "proof_consensus": amino::bytes(&message.proof_consensus),
+            "consensus_height": amino::height(message.consensus_height.as_ref()),
+            "signer": message.signer,
+        })),
+    ))
+}
@@ -1,5 +1,34 @@
+use anyhow::Result;
 use cosmos_sdk_proto::ibc::core::connection::v1::MsgConnectionOpenInit;
+use serde_json::{json, Value};
+
+use crate::{cosmos::amino, ibc::core::ics03_connection::version_to_amino_json};
 
 const TYPE_URL: &str = "/ibc.core.connection.v1.MsgConnectionOpenInit";
 
-impl_any_conversion!(MsgConnectionOpenInit, TYPE_URL);
+const AMINO_NAME: &str = "cosmos-sdk/MsgConnectionOpenInit";
+
+impl_any_conversion!(MsgConnectionOpenInit, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgConnectionOpenInit) -> Result<Value> {
+    let counterparty = message.counterparty.clone().unwrap_or_default();
+
+    Ok(amino::amino_message(
+        AMINO_NAME,
+        amino::omit_empty(json!({
+            "client_id": message.client_id,
+            "counterparty": {
+                "client_id": counterparty.client_id,
+                "connection_id": counterparty.connection_id,
+                "prefix": {
+                    "key_prefix": amino::bytes(
+                        &counterparty.prefix.unwrap_or_default().key_prefix,
+                    ),
+                },
+            },
+            "version": message.version.as_ref().map(version_to_amino_json),
+            "delay_period": amino::uint(message.delay_period),
+            "signer": message.signer,
+        })),
+    ))
+}
@@ -1,5 +1,24 @@
+use anyhow::Result;
 use cosmos_sdk_proto::ibc::core::channel::v1::MsgAcknowledgement;
+use serde_json::{json, Value};
+
+use crate::{cosmos::amino, ibc::core::ics04_channel::packet::IPacket};
 
 const TYPE_URL: &str = "/ibc.core.channel.v1.MsgAcknowledgement";
 
-impl_any_conversion!(MsgAcknowledgement, TYPE_URL);
+const AMINO_NAME: &str = "cosmos-sdk/MsgAcknowledgement";
+
+impl_any_conversion!(MsgAcknowledgement, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgAcknowledgement) -> Result<Value> {
+    Ok(amino::amino_message(
+        AMINO_NAME,
+        amino::omit_empty(json!({
+            "packet": message.packet.clone().unwrap_or_default().to_amino_json(),
+            "acknowledgement": amino::bytes(&message.acknowledgement),
+            "proof_acked": amino::bytes(&message.proof_acked),
+            "proof_height": amino::height(message.proof_height.as_ref()),
+            "signer": message.signer,
+        })),
+    ))
+}
@@ -1,5 +1,26 @@
+use anyhow::Result;
 use cosmos_sdk_proto::ibc::core::channel::v1::MsgChannelOpenAck;
+use serde_json::{json, Value};
+
+use crate::cosmos::amino;
 
 const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelOpenAck";
 
-impl_any_conversion!(MsgChannelOpenAck, TYPE_URL);
+const AMINO_NAME: &str = "cosmos-sdk/MsgChannelOpenAck";
+
+impl_any_conversion!(MsgChannelOpenAck, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgChannelOpenAck) -> Result<Value> {
+    Ok(amino::amino_message(
+        AMINO_NAME,
+        amino::omit_empty(json!({
+            "port_id": message.port_id,
+            "channel_id": message.channel_id,
+            "counterparty_channel_id": message.counterparty_channel_id,
+            "counterparty_version": message.counterparty_version,
+            "proof_try": amino::bytes(&message.proof_try),
+            "proof_height": amino::height(message.proof_height.as_ref()),
+            "signer": message.signer,
+        })),
+    ))
+}
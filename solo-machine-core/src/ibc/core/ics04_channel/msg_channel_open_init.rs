@@ -1,5 +1,35 @@
+use anyhow::Result;
 use cosmos_sdk_proto::ibc::core::channel::v1::MsgChannelOpenInit;
+use serde_json::{json, Value};
+
+use crate::cosmos::amino;
 
 const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelOpenInit";
 
-impl_any_conversion!(MsgChannelOpenInit, TYPE_URL);
+const AMINO_NAME: &str = "cosmos-sdk/MsgChannelOpenInit";
+
+impl_any_conversion!(MsgChannelOpenInit, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgChannelOpenInit) -> Result<Value> {
+    let channel = message.channel.clone().unwrap_or_default();
+    let counterparty = channel.counterparty.unwrap_or_default();
+
+    // enums are encoded as integers (and omitted when zero)
+    Ok(amino::amino_message(
+        AMINO_NAME,
+        amino::omit_empty(json!({
+            "port_id": message.port_id,
+            "channel": {
+                "state": (channel.state != 0).then_some(channel.state),
+                "ordering": (channel.ordering != 0).then_some(channel.ordering),
+                "counterparty": {
+                    "port_id": counterparty.port_id,
+                    "channel_id": counterparty.channel_id,
+                },
+                "connection_hops": channel.connection_hops,
+                "version": channel.version,
+            },
+            "signer": message.signer,
+        })),
+    ))
+}
@@ -1,5 +1,69 @@
+use anyhow::Result;
 use cosmos_sdk_proto::ibc::core::channel::v1::MsgRecvPacket;
+use serde_json::{json, Value};
+
+use crate::{cosmos::amino, ibc::core::ics04_channel::packet::IPacket};
 
 const TYPE_URL: &str = "/ibc.core.channel.v1.MsgRecvPacket";
 
-impl_any_conversion!(MsgRecvPacket, TYPE_URL);
+const AMINO_NAME: &str = "cosmos-sdk/MsgRecvPacket";
+
+impl_any_conversion!(MsgRecvPacket, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgRecvPacket) -> Result<Value> {
+    Ok(amino::amino_message(
+        AMINO_NAME,
+        amino::omit_empty(json!({
+            "packet": message.packet.clone().unwrap_or_default().to_amino_json(),
+            "proof_commitment": amino::bytes(&message.proof_commitment),
+            "proof_height": amino::height(message.proof_height.as_ref()),
+            "signer": message.signer,
+        })),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmos_sdk_proto::ibc::core::{channel::v1::Packet, client::v1::Height};
+
+    use super::*;
+    use crate::proto::AnyConvert;
+
+    #[test]
+    fn amino_json_omits_empty_fields() {
+        let message = MsgRecvPacket {
+            packet: Some(Packet {
+                sequence: 1,
+                source_port: "transfer".to_string(),
+                source_channel: "channel-0".to_string(),
+                destination_port: "transfer".to_string(),
+                destination_channel: "channel-1".to_string(),
+                data: b"{}".to_vec(),
+                timeout_height: Some(Height {
+                    revision_number: 0,
+                    revision_height: 100,
+                }),
+                timeout_timestamp: 0,
+            }),
+            proof_commitment: vec![1, 2, 3],
+            proof_height: Some(Height {
+                revision_number: 1,
+                revision_height: 10,
+            }),
+            signer: "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu".to_string(),
+        };
+
+        assert_eq!(
+            amino::canonical_json(message.to_amino_json().unwrap()).unwrap(),
+            concat!(
+                r#"{"type":"cosmos-sdk/MsgRecvPacket","value":{"packet":{"data":"e30=","#,
+                r#""destination_channel":"channel-1","destination_port":"transfer","sequence":"1","#,
+                r#""source_channel":"channel-0","source_port":"transfer","#,
+                r#""timeout_height":{"revision_height":"100"}},"proof_commitment":"AQID","#,
+                r#""proof_height":{"revision_height":"10","revision_number":"1"},"#,
+                r#""signer":"cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu"}}"#
+            )
+            .as_bytes()
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use anyhow::Result;
+use cosmos_sdk_proto::ibc::core::channel::v1::MsgTimeout;
+use serde_json::{json, Value};
+
+use crate::{cosmos::amino, ibc::core::ics04_channel::packet::IPacket};
+
+const TYPE_URL: &str = "/ibc.core.channel.v1.MsgTimeout";
+
+const AMINO_NAME: &str = "cosmos-sdk/MsgTimeout";
+
+impl_any_conversion!(MsgTimeout, TYPE_URL, amino_json = to_amino_json);
+
+fn to_amino_json(message: &MsgTimeout) -> Result<Value> {
+    Ok(amino::amino_message(
+        AMINO_NAME,
+        amino::omit_empty(json!({
+            "packet": message.packet.clone().unwrap_or_default().to_amino_json(),
+            "proof_unreceived": amino::bytes(&message.proof_unreceived),
+            "proof_height": amino::height(message.proof_height.as_ref()),
+            "next_sequence_recv": amino::uint(message.next_sequence_recv),
+            "signer": message.signer,
+        })),
+    ))
+}
@@ -1,9 +1,23 @@
-use anyhow::{anyhow, Result};
-use cosmos_sdk_proto::ibc::core::channel::v1::Packet;
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context, Result};
+use cosmos_sdk_proto::ibc::core::{channel::v1::Packet, client::v1::Height};
+use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
 
-pub trait IPacket {
+use crate::{
+    broadcaster::TxEventAttribute, cosmos::amino, ibc::core::ics02_client::height::IHeight,
+};
+
+pub trait IPacket: Sized {
     fn commitment_bytes(&self) -> Result<Vec<u8>>;
+
+    /// Parses packet from attributes of a packet event (e.g. `send_packet`). Data of packet is left empty for events
+    /// which do not carry it (e.g. `acknowledge_packet`).
+    fn from_event_attributes(attributes: &[TxEventAttribute]) -> Result<Self>;
+
+    /// Returns amino JSON representation of packet (used in amino JSON of packet messages)
+    fn to_amino_json(&self) -> Value;
 }
 
 impl IPacket for Packet {
@@ -22,4 +36,59 @@ impl IPacket for Packet {
 
         Ok(Sha256::digest(&buf).to_vec())
     }
+
+    fn to_amino_json(&self) -> Value {
+        amino::omit_empty(json!({
+            "sequence": amino::uint(self.sequence),
+            "source_port": self.source_port,
+            "source_channel": self.source_channel,
+            "destination_port": self.destination_port,
+            "destination_channel": self.destination_channel,
+            "data": amino::bytes(&self.data),
+            "timeout_height": amino::height(self.timeout_height.as_ref()),
+            "timeout_timestamp": amino::uint(self.timeout_timestamp),
+        }))
+    }
+
+    fn from_event_attributes(attributes: &[TxEventAttribute]) -> Result<Self> {
+        let mut attributes = attributes
+            .iter()
+            .map(|attribute| (attribute.key.as_str(), attribute.value.clone()))
+            .collect::<HashMap<_, _>>();
+
+        Ok(Self {
+            sequence: attributes
+                .remove("packet_sequence")
+                .ok_or_else(|| anyhow!("`packet_sequence` is missing from packet data"))?
+                .parse()
+                .context("invalid `packet_sequence`")?,
+            source_port: attributes
+                .remove("packet_src_port")
+                .ok_or_else(|| anyhow!("`packet_src_port` is missing from packet data"))?,
+            source_channel: attributes
+                .remove("packet_src_channel")
+                .ok_or_else(|| anyhow!("`packet_src_channel` is missing from packet data"))?,
+            destination_port: attributes
+                .remove("packet_dst_port")
+                .ok_or_else(|| anyhow!("`packet_dst_port` is missing from packet data"))?,
+            destination_channel: attributes
+                .remove("packet_dst_channel")
+                .ok_or_else(|| anyhow!("`packet_dst_channel` is missing from packet data"))?,
+            data: attributes
+                .remove("packet_data")
+                .unwrap_or_default()
+                .into_bytes(),
+            timeout_height: Some(
+                Height::from_str(&attributes.remove("packet_timeout_height").ok_or_else(|| {
+                    anyhow!("`packet_timeout_height` is missing from packet data")
+                })?)
+                .context("invalid `packet_timeout_height`")?,
+            ),
+            timeout_timestamp: attributes
+                .remove("packet_timeout_timestamp")
+                .ok_or_else(|| anyhow!("`packet_timeout_timestamp` is missing from packet data"))?
+                .parse()
+                .context("invalid `packet_timeout_timestamp`")?,
+        })
+    }
 }
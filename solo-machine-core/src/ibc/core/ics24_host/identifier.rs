@@ -84,7 +84,7 @@ impl ChannelId {
 }
 
 /// A chain identifier
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ChainId {
     id: Identifier,
     version: u64,
@@ -151,7 +151,7 @@ impl Deref for ChainId {
 /// # Specs
 ///
 /// <https://github.com/cosmos/ibc/tree/master/spec/core/ics-024-host-requirements#paths-identifiers-separators>
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Identifier(String);
 
 impl Identifier {
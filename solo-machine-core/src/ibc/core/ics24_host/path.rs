@@ -242,6 +242,21 @@ impl PacketCommitmentPath {
     }
 }
 
+impl_path!("Path for storing packet receipts", PacketReceiptPath);
+
+impl PacketReceiptPath {
+    pub fn new(port_id: &PortId, channel_id: &ChannelId, packet_sequence: u64) -> Self {
+        Self(
+            format!(
+                "receipts/ports/{}/channels/{}/sequences/{}",
+                port_id, channel_id, packet_sequence
+            )
+            .parse()
+            .unwrap(),
+        )
+    }
+}
+
 impl_path!("Denom trace of tokens transferred to IBC chain", DenomTrace);
 
 impl DenomTrace {
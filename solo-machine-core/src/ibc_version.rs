@@ -0,0 +1,143 @@
+//! Detection of IBC implementation (ibc-go, or IBC module of cosmos SDK on older chains) used by IBC enabled chains
+//! and of revision of solo machine client types expected by them
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Context, Error, Result};
+use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::{
+    service_client::ServiceClient, GetNodeInfoRequest, Module,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{cosmos::grpc_pool::grpc_channel, failover::active_grpc_addr, model::ChainConfig};
+
+const IBC_GO_MODULE: &str = "github.com/cosmos/ibc-go";
+const COSMOS_SDK_MODULE: &str = "github.com/cosmos/cosmos-sdk";
+
+/// IBC implementation of a chain (detected from build dependencies reported by its node)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IbcVersion {
+    /// Go module implementing IBC (e.g. `github.com/cosmos/ibc-go/v3`, or `github.com/cosmos/cosmos-sdk` for chains
+    /// using IBC module of cosmos SDK)
+    pub module: String,
+    /// Version of module (e.g. `v3.0.0`)
+    pub version: String,
+}
+
+/// Protobuf revision of solo machine client types (`ibc.lightclients.solomachine.vN`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SoloMachineRevision {
+    /// `solomachine.v1` (IBC module of cosmos SDK and ibc-go v1)
+    V1,
+    /// `solomachine.v2` (ibc-go v2 to v6)
+    V2,
+    /// `solomachine.v3` (ibc-go v7 onwards)
+    V3,
+}
+
+impl fmt::Display for SoloMachineRevision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1 => write!(f, "solomachine.v1"),
+            Self::V2 => write!(f, "solomachine.v2"),
+            Self::V3 => write!(f, "solomachine.v3"),
+        }
+    }
+}
+
+impl FromStr for SoloMachineRevision {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim_start_matches("solomachine.") {
+            "v1" => Ok(Self::V1),
+            "v2" => Ok(Self::V2),
+            "v3" => Ok(Self::V3),
+            _ => Err(anyhow!("invalid solo machine client revision: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for IbcVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ibc_go() {
+            write!(f, "ibc-go {}", self.version)
+        } else {
+            write!(f, "cosmos-sdk {}", self.version)
+        }
+    }
+}
+
+impl IbcVersion {
+    /// Finds IBC implementation in given build dependencies of a chain's node (`None` if chain does not report its
+    /// build dependencies)
+    pub fn from_build_deps(build_deps: &[Module]) -> Option<Self> {
+        let ibc_go = build_deps.iter().find(|module| {
+            module.path == IBC_GO_MODULE || module.path.starts_with(&format!("{}/v", IBC_GO_MODULE))
+        });
+
+        ibc_go
+            .or_else(|| {
+                build_deps
+                    .iter()
+                    .find(|module| module.path == COSMOS_SDK_MODULE)
+            })
+            .map(|module| Self {
+                module: module.path.clone(),
+                version: module.version.clone(),
+            })
+    }
+
+    /// Returns `true` if chain uses ibc-go (instead of IBC module of cosmos SDK)
+    pub fn is_ibc_go(&self) -> bool {
+        self.module.starts_with(IBC_GO_MODULE)
+    }
+
+    /// Returns major version of ibc-go (`None` for chains using IBC module of cosmos SDK)
+    pub fn ibc_go_major(&self) -> Option<u64> {
+        if !self.is_ibc_go() {
+            return None;
+        }
+
+        self.version
+            .trim_start_matches('v')
+            .split('.')
+            .next()
+            .and_then(|major| major.parse().ok())
+    }
+
+    /// Returns protobuf revision of solo machine client types used by chain
+    pub fn solo_machine_revision(&self) -> SoloMachineRevision {
+        match self.ibc_go_major() {
+            None | Some(0) | Some(1) => SoloMachineRevision::V1,
+            Some(2..=6) => SoloMachineRevision::V2,
+            Some(_) => SoloMachineRevision::V3,
+        }
+    }
+}
+
+/// Returns protobuf revision of solo machine client types used for chains whose IBC implementation is not known (also
+/// the revision of client types given to client encoders)
+pub fn default_solo_machine_revision() -> SoloMachineRevision {
+    if cfg!(feature = "solomachine-v2") {
+        SoloMachineRevision::V2
+    } else {
+        SoloMachineRevision::V1
+    }
+}
+
+/// Detects IBC implementation of chain by querying build dependencies of node at currently preferred gRPC endpoint
+pub async fn detect_ibc_version(config: &ChainConfig) -> Result<Option<IbcVersion>> {
+    let grpc_addr = active_grpc_addr(config);
+    let mut client = ServiceClient::new(grpc_channel(&grpc_addr, config).await?);
+
+    let application_version = client
+        .get_node_info(GetNodeInfoRequest {})
+        .await
+        .with_context(|| format!("unable to query node info at {}", grpc_addr))?
+        .into_inner()
+        .application_version
+        .ok_or_else(|| anyhow!("missing application version in response of {}", grpc_addr))?;
+
+    Ok(IbcVersion::from_build_deps(&application_version.build_deps))
+}
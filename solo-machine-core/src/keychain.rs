@@ -0,0 +1,43 @@
+//! Storage of secrets (e.g. mnemonic) of signers in platform keychain (macOS Keychain, Windows Credential Manager or
+//! Secret Service on Linux)
+use anyhow::{ensure, Context, Result};
+use keyring::Entry;
+use zeroize::Zeroizing;
+
+/// Service name under which secrets of solo machine are stored in platform keychain
+pub const KEYCHAIN_SERVICE: &str = "solo-machine";
+
+/// Stores a secret in platform keychain under given entry name (existing secret is overwritten)
+pub fn store_secret(name: &str, secret: &str) -> Result<()> {
+    entry(name)?.set_password(secret).context(format!(
+        "unable to store secret in keychain entry `{}`",
+        name
+    ))
+}
+
+/// Loads a secret from platform keychain (loaded secret is zeroized when dropped)
+pub fn load_secret(name: &str) -> Result<Zeroizing<String>> {
+    entry(name)?
+        .get_password()
+        .map(Zeroizing::new)
+        .context(format!(
+            "unable to load secret from keychain entry `{}`",
+            name
+        ))
+}
+
+/// Deletes a secret from platform keychain
+pub fn delete_secret(name: &str) -> Result<()> {
+    entry(name)?
+        .delete_password()
+        .context(format!("unable to delete keychain entry `{}`", name))
+}
+
+fn entry(name: &str) -> Result<Entry> {
+    ensure!(
+        !cfg!(feature = "fips"),
+        "platform keychain cannot be used in `fips` builds (secret service client links OpenSSL)"
+    );
+
+    Entry::new(KEYCHAIN_SERVICE, name).context(format!("invalid keychain entry `{}`", name))
+}
@@ -0,0 +1,340 @@
+//! Encrypted keystore for storing secrets (e.g. mnemonic) of signers at rest
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
+};
+
+use aes_gcm::{
+    aead::{Aead, NewAead},
+    Aes256Gcm, Key, Nonce,
+};
+use anyhow::{anyhow, ensure, Context, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use hmac::Hmac;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+/// Current version of keystore format
+pub const KEYSTORE_VERSION: u32 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+pub(crate) const KEY_LEN: usize = 32;
+
+/// Default memory cost (in KiB) of argon2id key derivation
+#[cfg(not(feature = "fips"))]
+const DEFAULT_MEMORY_COST: u32 = 19 * 1024;
+/// Default number of iterations of argon2id key derivation
+#[cfg(not(feature = "fips"))]
+const DEFAULT_TIME_COST: u32 = 2;
+/// Default degree of parallelism of argon2id key derivation
+#[cfg(not(feature = "fips"))]
+const DEFAULT_PARALLELISM: u32 = 1;
+/// Default number of iterations of PBKDF2-HMAC-SHA256 key derivation
+#[cfg(feature = "fips")]
+const DEFAULT_PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// Maximum memory cost (in KiB) of argon2id key derivation accepted from keystore files (1 GiB)
+const MAX_MEMORY_COST: u32 = 1024 * 1024;
+/// Maximum number of iterations of argon2id key derivation accepted from keystore files
+const MAX_TIME_COST: u32 = 64;
+/// Maximum degree of parallelism of argon2id key derivation accepted from keystore files
+const MAX_PARALLELISM: u32 = 16;
+/// Maximum number of iterations of PBKDF2-HMAC-SHA256 key derivation accepted from keystore files
+const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+
+/// Secret encrypted using a key derived from password (argon2id or, in `fips` builds, PBKDF2-HMAC-SHA256 +
+/// AES-256-GCM)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    /// Version of keystore format
+    pub version: u32,
+    /// Parameters of key derivation function
+    pub kdf: KdfParams,
+    /// Nonce used for encryption
+    #[serde(with = "hex")]
+    pub nonce: Vec<u8>,
+    /// Encrypted secret (along with authentication tag)
+    #[serde(with = "hex")]
+    pub ciphertext: Vec<u8>,
+}
+
+/// Key derivation function used to derive encryption key from password
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KdfAlgorithm {
+    /// Argon2id
+    #[default]
+    Argon2id,
+    /// PBKDF2-HMAC-SHA256 (used in `fips` builds)
+    Pbkdf2Sha256,
+}
+
+/// Parameters of key derivation function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Key derivation function (keystores without this field use argon2id)
+    #[serde(default)]
+    pub algorithm: KdfAlgorithm,
+    /// Salt used for key derivation
+    #[serde(with = "hex")]
+    pub salt: Vec<u8>,
+    /// Memory cost (in KiB, unused by PBKDF2)
+    pub memory_cost: u32,
+    /// Number of iterations
+    pub time_cost: u32,
+    /// Degree of parallelism (unused by PBKDF2)
+    pub parallelism: u32,
+}
+
+impl KdfParams {
+    #[cfg(not(feature = "fips"))]
+    pub(crate) fn generate() -> Self {
+        Self {
+            algorithm: KdfAlgorithm::Argon2id,
+            salt: generate_salt(),
+            memory_cost: DEFAULT_MEMORY_COST,
+            time_cost: DEFAULT_TIME_COST,
+            parallelism: DEFAULT_PARALLELISM,
+        }
+    }
+
+    #[cfg(feature = "fips")]
+    pub(crate) fn generate() -> Self {
+        Self {
+            algorithm: KdfAlgorithm::Pbkdf2Sha256,
+            salt: generate_salt(),
+            memory_cost: 0,
+            time_cost: DEFAULT_PBKDF2_ITERATIONS,
+            parallelism: 0,
+        }
+    }
+
+    /// Ensures that parameters are within bounds (parameters read from a file should neither disable key stretching
+    /// nor make key derivation exhaust memory or time)
+    pub(crate) fn validate(&self) -> Result<()> {
+        match self.algorithm {
+            KdfAlgorithm::Argon2id => {
+                ensure!(
+                    (1..=MAX_MEMORY_COST).contains(&self.memory_cost),
+                    "invalid memory cost of key derivation: {} (should be between 1 and {} KiB)",
+                    self.memory_cost,
+                    MAX_MEMORY_COST
+                );
+                ensure!(
+                    (1..=MAX_TIME_COST).contains(&self.time_cost),
+                    "invalid number of iterations of key derivation: {} (should be between 1 and {})",
+                    self.time_cost,
+                    MAX_TIME_COST
+                );
+                ensure!(
+                    (1..=MAX_PARALLELISM).contains(&self.parallelism),
+                    "invalid degree of parallelism of key derivation: {} (should be between 1 and {})",
+                    self.parallelism,
+                    MAX_PARALLELISM
+                );
+            }
+            KdfAlgorithm::Pbkdf2Sha256 => {
+                ensure!(
+                    (1..=MAX_PBKDF2_ITERATIONS).contains(&self.time_cost),
+                    "invalid number of iterations of key derivation: {} (should be between 1 and {})",
+                    self.time_cost,
+                    MAX_PBKDF2_ITERATIONS
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn derive_key(&self, password: &str) -> Result<Zeroizing<[u8; KEY_LEN]>> {
+        self.validate()?;
+
+        let mut key = Zeroizing::new([0; KEY_LEN]);
+
+        match self.algorithm {
+            KdfAlgorithm::Argon2id => {
+                ensure!(
+                    !cfg!(feature = "fips"),
+                    "argon2id keystores are not supported in `fips` builds (re-encrypt the secret using a `fips` build)"
+                );
+
+                let params = Params::new(
+                    self.memory_cost,
+                    self.time_cost,
+                    self.parallelism,
+                    Some(KEY_LEN),
+                )
+                .map_err(|err| anyhow!("invalid key derivation parameters: {}", err))?;
+
+                Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                    .hash_password_into(password.as_bytes(), &self.salt, &mut *key)
+                    .map_err(|err| anyhow!("unable to derive key from password: {}", err))?;
+            }
+            KdfAlgorithm::Pbkdf2Sha256 => {
+                pbkdf2::pbkdf2::<Hmac<Sha256>>(
+                    password.as_bytes(),
+                    &self.salt,
+                    self.time_cost,
+                    &mut *key,
+                );
+            }
+        }
+
+        Ok(key)
+    }
+}
+
+fn generate_salt() -> Vec<u8> {
+    let mut salt = vec![0; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+impl Keystore {
+    /// Encrypts a secret using given password
+    pub fn encrypt(secret: &[u8], password: &str) -> Result<Self> {
+        let kdf = KdfParams::generate();
+        let key = kdf.derive_key(password)?;
+
+        let mut nonce = vec![0; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+
+        let ciphertext = Aes256Gcm::new(Key::from_slice(&*key))
+            .encrypt(Nonce::from_slice(&nonce), secret)
+            .map_err(|_| anyhow!("unable to encrypt secret"))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            kdf,
+            nonce,
+            ciphertext,
+        })
+    }
+
+    /// Decrypts secret using given password (decrypted secret is zeroized when dropped)
+    pub fn decrypt(&self, password: &str) -> Result<Zeroizing<Vec<u8>>> {
+        ensure!(
+            self.version == KEYSTORE_VERSION,
+            "unsupported keystore version: {}",
+            self.version
+        );
+        ensure!(
+            self.nonce.len() == NONCE_LEN,
+            "invalid nonce length in keystore: {}",
+            self.nonce.len()
+        );
+
+        let key = self.kdf.derive_key(password)?;
+
+        Aes256Gcm::new(Key::from_slice(&*key))
+            .decrypt(Nonce::from_slice(&self.nonce), self.ciphertext.as_ref())
+            .map(Zeroizing::new)
+            .map_err(|_| {
+                anyhow!("unable to decrypt keystore (invalid password or corrupted keystore)")
+            })
+    }
+
+    /// Reads keystore from a file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        let bytes = fs::read(path)
+            .with_context(|| format!("unable to read keystore file: {}", path.display()))?;
+
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("invalid keystore file: {}", path.display()))
+    }
+
+    /// Writes keystore to a file (fails if file already exists). On unix, file is created readable and writable only
+    /// by its owner.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let bytes = serde_json::to_vec_pretty(self).context("unable to serialize keystore")?;
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = match options.open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+                return Err(anyhow!("keystore file already exists: {}", path.display()))
+            }
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("unable to create keystore file: {}", path.display()))
+            }
+        };
+
+        file.write_all(&bytes)
+            .and_then(|_| file.sync_all())
+            .with_context(|| format!("unable to write keystore file: {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"secret mnemonic";
+
+    #[test]
+    fn keystore_round_trip() {
+        let keystore = Keystore::encrypt(SECRET, "password").unwrap();
+        assert_ne!(keystore.ciphertext, SECRET);
+
+        let keystore: Keystore =
+            serde_json::from_slice(&serde_json::to_vec(&keystore).unwrap()).unwrap();
+        assert_eq!(&*keystore.decrypt("password").unwrap(), SECRET);
+    }
+
+    #[test]
+    fn wrong_password_is_rejected() {
+        let keystore = Keystore::encrypt(SECRET, "password").unwrap();
+
+        let error = keystore.decrypt("wrong password").unwrap_err();
+        assert!(error.to_string().contains("invalid password"));
+    }
+
+    #[test]
+    fn out_of_bounds_kdf_params_are_rejected() {
+        let keystore = Keystore::encrypt(SECRET, "password").unwrap();
+
+        let with_kdf = |update: fn(&mut KdfParams)| {
+            let mut keystore = keystore.clone();
+            update(&mut keystore.kdf);
+            keystore
+        };
+
+        for keystore in [
+            with_kdf(|kdf| kdf.time_cost = 0),
+            with_kdf(|kdf| kdf.time_cost = u32::MAX),
+            with_kdf(|kdf| {
+                kdf.algorithm = KdfAlgorithm::Argon2id;
+                kdf.memory_cost = 0;
+            }),
+            with_kdf(|kdf| {
+                kdf.algorithm = KdfAlgorithm::Argon2id;
+                kdf.memory_cost = u32::MAX;
+            }),
+            with_kdf(|kdf| {
+                kdf.algorithm = KdfAlgorithm::Argon2id;
+                kdf.memory_cost = 1024;
+                kdf.time_cost = 1;
+                kdf.parallelism = 0;
+            }),
+        ] {
+            let error = keystore.decrypt("password").unwrap_err();
+            assert!(error.to_string().starts_with("invalid"), "{:#}", error);
+        }
+    }
+}
@@ -3,13 +3,38 @@
 #[macro_use]
 pub mod proto;
 
+pub mod alert;
+pub mod attestation;
+pub mod broadcaster;
+pub mod client_encoder;
 pub mod cosmos;
 pub mod event;
+pub mod failover;
+pub mod fee;
 pub mod ibc;
+pub mod ibc_version;
+#[cfg(feature = "keyring")]
+pub mod keychain;
+pub mod keystore;
 pub mod model;
+pub mod plugins;
+pub mod policy;
+pub mod proxy;
+pub mod rate_limit;
+pub mod relayer;
+pub mod retry;
+pub mod rpc_client;
 pub mod service;
+pub mod sign_summary;
 pub mod signer;
+pub mod storage;
+pub mod subscriber;
+pub mod supervisor;
+#[cfg(all(test, not(feature = "postgres")))]
+mod test_util;
 pub(crate) mod transaction_builder;
+pub mod verifier;
+pub mod webhook;
 
 #[doc(inline)]
 pub use self::{
@@ -17,9 +42,14 @@ pub use self::{
     signer::{Signer, ToPublicKey},
 };
 
-use anyhow::{Context, Result};
+use std::collections::HashSet;
+
+use anyhow::{bail, Context, Result};
 use sqlx::migrate::{MigrateDatabase, Migrator};
 
+#[cfg(not(any(feature = "sqlite", feature = "postgres")))]
+compile_error!("at least one of `sqlite` or `postgres` features must be enabled");
+
 #[cfg(not(feature = "postgres"))]
 pub use sqlx::{Sqlite as Db, SqlitePool as DbPool};
 
@@ -41,11 +71,85 @@ pub async fn init_db(connection_str: &str) -> Result<()> {
         .context("unable to create database")
 }
 
-/// Connects to database and returns database pool
+/// Query returning `true` if migrations table exists (i.e. database is initialized)
+#[cfg(not(feature = "postgres"))]
+const MIGRATIONS_TABLE_EXISTS: &str =
+    "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')";
+
+/// Query returning `true` if migrations table exists (i.e. database is initialized)
+#[cfg(feature = "postgres")]
+const MIGRATIONS_TABLE_EXISTS: &str = "SELECT to_regclass('_sqlx_migrations') IS NOT NULL";
+
+/// Connects to database and returns database pool. Pending migrations are applied automatically on initialized
+/// databases (so that databases created by older versions of solo machine are upgraded instead of breaking).
 pub async fn connect_db(connection_str: &str) -> Result<DbPool> {
-    DbPool::connect(connection_str)
+    let db_pool = DbPool::connect(connection_str)
+        .await
+        .context("unable to connect to database")?;
+
+    upgrade_db(&db_pool).await?;
+
+    Ok(db_pool)
+}
+
+/// Applies pending migrations on an initialized database and returns versions of applied migrations (nothing is done
+/// on uninitialized databases). Fails if database was migrated by a newer version of solo machine.
+pub async fn upgrade_db(db_pool: &DbPool) -> Result<Vec<i64>> {
+    let (initialized,): (bool,) = sqlx::query_as(MIGRATIONS_TABLE_EXISTS)
+        .fetch_one(db_pool)
+        .await
+        .context("unable to check if database is initialized")?;
+
+    if !initialized {
+        return Ok(Vec::new());
+    }
+
+    let applied: Vec<(i64,)> = sqlx::query_as("SELECT version FROM _sqlx_migrations WHERE success")
+        .fetch_all(db_pool)
         .await
-        .context("unable to connect to database")
+        .context("unable to query applied migrations of database")?;
+    let applied: HashSet<i64> = applied.into_iter().map(|(version,)| version).collect();
+
+    let known: HashSet<i64> = MIGRATOR.iter().map(|migration| migration.version).collect();
+
+    if let Some(unknown) = applied.difference(&known).max() {
+        bail!(
+            "database is migrated to schema version {} by a newer version of solo machine (latest schema version known to this version: {})",
+            unknown,
+            latest_schema_version().unwrap_or_default()
+        );
+    }
+
+    let mut pending: Vec<i64> = known.difference(&applied).copied().collect();
+
+    if pending.is_empty() {
+        return Ok(pending);
+    }
+
+    pending.sort_unstable();
+    run_migrations(db_pool).await?;
+
+    for version in pending.iter() {
+        tracing::info!(version, "applied database migration");
+    }
+
+    Ok(pending)
+}
+
+/// Returns version of latest migration applied on database (`None` if no migration is applied)
+pub async fn schema_version(db_pool: &DbPool) -> Result<Option<i64>> {
+    let (version,): (Option<i64>,) =
+        sqlx::query_as("SELECT MAX(version) FROM _sqlx_migrations WHERE success")
+            .fetch_one(db_pool)
+            .await
+            .context("unable to query schema version of database")?;
+
+    Ok(version)
+}
+
+/// Returns version of latest migration known to this build of solo machine
+pub fn latest_schema_version() -> Option<i64> {
+    MIGRATOR.iter().map(|migration| migration.version).max()
 }
 
 /// Runs all the migrations on database
@@ -1,12 +1,36 @@
 //! Data types used by solo machine
+pub(crate) mod api_key;
+pub(crate) mod api_token;
+pub(crate) mod audit_log;
 pub(crate) mod chain;
+pub(crate) mod hash_chain;
 pub(crate) mod ibc;
+pub(crate) mod integrity;
+pub(crate) mod macro_run;
 pub(crate) mod operation;
+pub(crate) mod packet;
+pub(crate) mod pending_operation;
+pub(crate) mod signature_log;
+pub(crate) mod spending;
+pub(crate) mod stats;
 
 pub use self::{
+    api_key::{ApiKey, ApiOperation},
+    api_token::{ApiRole, ApiToken},
+    audit_log::{AuditLogEntry, AuditLogVerification},
     chain::{
         chain_keys::ChainKey,
-        {Chain, ChainConfig, ConnectionDetails, Fee},
+        chain_labels::{ChainLabel, LabelKind},
+        {
+            Chain, ChainConfig, ConnectionDetails, Fee, GeneratedIdentifiers, GrpcTlsConfig,
+            HandshakeProgress, HandshakeStep, NetworkTimeouts,
+        },
     },
+    integrity::IntegrityIssue,
+    macro_run::{MacroOperation, MacroRun, MacroRunStatus, MacroStep, MacroStepResult},
     operation::{Operation, OperationType},
+    packet::{PacketState, TrackedPacket},
+    pending_operation::{PendingOperation, PendingOperationKind, PendingOperationStatus},
+    signature_log::{SignatureLogEntry, SignatureLogVerification, SignatureRecord},
+    stats::{ChainDailyStats, UptimeDailyStats},
 };
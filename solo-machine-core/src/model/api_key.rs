@@ -0,0 +1,215 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, Executor, FromRow};
+
+use crate::{
+    ibc::core::ics24_host::identifier::{ChainId, ChannelId, Identifier},
+    Db,
+};
+
+/// Operation which can be authorized by an API key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiOperation {
+    /// Mint tokens on IBC enabled chain
+    Mint,
+    /// Burn tokens on IBC enabled chain
+    Burn,
+}
+
+impl fmt::Display for ApiOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mint => write!(f, "mint"),
+            Self::Burn => write!(f, "burn"),
+        }
+    }
+}
+
+impl FromStr for ApiOperation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mint" => Ok(Self::Mint),
+            "burn" => Ok(Self::Burn),
+            _ => Err(anyhow!("invalid api operation: {}", s)),
+        }
+    }
+}
+
+/// API credential scoped to a single channel of an IBC enabled chain and a set of operations
+#[derive(Debug)]
+pub struct ApiKey {
+    /// ID of API key (public part of API token)
+    pub id: String,
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+    /// Channel ID of solo machine client on IBC enabled chain
+    pub channel_id: ChannelId,
+    /// Operations authorized by API key
+    pub operations: Vec<ApiOperation>,
+    /// Denom to which operations are restricted (`None` if any denom is allowed)
+    pub denom: Option<Identifier>,
+    /// Maximum amount of a single operation (`None` if amount is not limited)
+    pub max_amount: Option<u64>,
+    /// Human-readable label of API key
+    pub label: Option<String>,
+    /// Creation time of API key
+    pub created_at: DateTime<Utc>,
+    /// Time at which API key was revoked (`None` if API key is active)
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw API key
+struct RawApiKey {
+    /// ID of API key
+    pub id: String,
+    /// SHA-256 hash of secret part of API token (in hex)
+    pub secret_hash: String,
+    /// Chain ID of IBC enabled chain
+    pub chain_id: String,
+    /// Channel ID of solo machine client on IBC enabled chain
+    pub channel_id: String,
+    /// Operations authorized by API key
+    pub operations: Json<Vec<ApiOperation>>,
+    /// Denom to which operations are restricted
+    pub denom: Option<String>,
+    /// Maximum amount of a single operation
+    pub max_amount: Option<i64>,
+    /// Human-readable label of API key
+    pub label: Option<String>,
+    /// Creation time of API key
+    pub created_at: DateTime<Utc>,
+    /// Time at which API key was revoked
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<RawApiKey> for ApiKey {
+    type Error = Error;
+
+    fn try_from(raw: RawApiKey) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: raw.id,
+            chain_id: raw.chain_id.parse()?,
+            channel_id: raw.channel_id.parse()?,
+            operations: raw.operations.0,
+            denom: raw.denom.map(|denom| denom.parse()).transpose()?,
+            max_amount: raw.max_amount.map(TryInto::try_into).transpose()?,
+            label: raw.label,
+            created_at: raw.created_at,
+            revoked_at: raw.revoked_at,
+        })
+    }
+}
+
+/// Adds a new API key
+#[allow(clippy::too_many_arguments)]
+pub async fn add_api_key<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: &str,
+    secret_hash: &str,
+    chain_id: &ChainId,
+    channel_id: &ChannelId,
+    operations: &[ApiOperation],
+    denom: Option<&Identifier>,
+    max_amount: Option<u64>,
+    label: Option<&str>,
+) -> Result<()> {
+    let max_amount = max_amount.map(i64::try_from).transpose()?;
+
+    let rows_affected = sqlx::query(
+        "INSERT INTO api_keys (id, secret_hash, chain_id, channel_id, operations, denom, max_amount, label, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+    )
+    .bind(id)
+    .bind(secret_hash)
+    .bind(chain_id.to_string())
+    .bind(channel_id.to_string())
+    .bind(Json(operations))
+    .bind(denom.map(ToString::to_string))
+    .bind(max_amount)
+    .bind(label)
+    .bind(Utc::now())
+    .execute(executor)
+    .await
+    .context("unable to add api key in database")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when adding new api key"
+    );
+
+    Ok(())
+}
+
+/// Fetches API key with given ID along with hash of its secret
+pub async fn get_api_key<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: &str,
+) -> Result<Option<(ApiKey, String)>> {
+    sqlx::query_as("SELECT * FROM api_keys WHERE id = $1")
+        .bind(id)
+        .fetch_optional(executor)
+        .await
+        .context("unable to query api key from database")?
+        .map(|mut raw: RawApiKey| {
+            let secret_hash = std::mem::take(&mut raw.secret_hash);
+            raw.try_into().map(|api_key| (api_key, secret_hash))
+        })
+        .transpose()
+}
+
+/// Fetches all the API keys (optionally, only for given chain)
+pub async fn get_api_keys<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: Option<&ChainId>,
+) -> Result<Vec<ApiKey>> {
+    let raw_api_keys: Vec<RawApiKey> = match chain_id {
+        None => {
+            sqlx::query_as("SELECT * FROM api_keys ORDER BY created_at DESC")
+                .fetch_all(executor)
+                .await
+        }
+        Some(chain_id) => {
+            sqlx::query_as("SELECT * FROM api_keys WHERE chain_id = $1 ORDER BY created_at DESC")
+                .bind(chain_id.to_string())
+                .fetch_all(executor)
+                .await
+        }
+    }
+    .context("unable to query api keys from database")?;
+
+    raw_api_keys.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Revokes API key with given ID
+pub async fn revoke_api_key<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: &str,
+) -> Result<()> {
+    let rows_affected =
+        sqlx::query("UPDATE api_keys SET revoked_at = $1 WHERE id = $2 AND revoked_at IS NULL")
+            .bind(Utc::now())
+            .bind(id)
+            .execute(executor)
+            .await
+            .context("unable to revoke api key")?
+            .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "active api key with id {} not found",
+        id
+    );
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, FromRow};
+
+use crate::Db;
+
+/// Role of an API token (roles are ordered, i.e. a role is authorized for all the operations of lower roles)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiRole {
+    /// Querying state of solo machine and chains
+    ReadOnly,
+    /// Signing and broadcasting transactions (connecting, minting, burning and relaying packets)
+    Operator,
+    /// Administrative operations (adding chains, updating signer and modifying sequences)
+    Admin,
+}
+
+impl fmt::Display for ApiRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReadOnly => write!(f, "read-only"),
+            Self::Operator => write!(f, "operator"),
+            Self::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+impl FromStr for ApiRole {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read-only" => Ok(Self::ReadOnly),
+            "operator" => Ok(Self::Operator),
+            "admin" => Ok(Self::Admin),
+            _ => Err(anyhow!("invalid api role: {}", s)),
+        }
+    }
+}
+
+/// API credential with a role (not scoped to any chain)
+#[derive(Debug)]
+pub struct ApiToken {
+    /// ID of API token (public part of API token)
+    pub id: String,
+    /// Role of API token
+    pub role: ApiRole,
+    /// Human-readable label of API token
+    pub label: Option<String>,
+    /// Creation time of API token
+    pub created_at: DateTime<Utc>,
+    /// Time at which API token was revoked (`None` if API token is active)
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw API token
+struct RawApiToken {
+    /// ID of API token
+    pub id: String,
+    /// SHA-256 hash of secret part of API token (in hex)
+    pub secret_hash: String,
+    /// Role of API token
+    pub role: String,
+    /// Human-readable label of API token
+    pub label: Option<String>,
+    /// Creation time of API token
+    pub created_at: DateTime<Utc>,
+    /// Time at which API token was revoked
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<RawApiToken> for ApiToken {
+    type Error = Error;
+
+    fn try_from(raw: RawApiToken) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: raw.id,
+            role: raw.role.parse()?,
+            label: raw.label,
+            created_at: raw.created_at,
+            revoked_at: raw.revoked_at,
+        })
+    }
+}
+
+/// Adds a new API token
+pub async fn add_api_token<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: &str,
+    secret_hash: &str,
+    role: ApiRole,
+    label: Option<&str>,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "INSERT INTO api_tokens (id, secret_hash, role, label, created_at) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(id)
+    .bind(secret_hash)
+    .bind(role.to_string())
+    .bind(label)
+    .bind(Utc::now())
+    .execute(executor)
+    .await
+    .context("unable to add api token in database")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when adding new api token"
+    );
+
+    Ok(())
+}
+
+/// Fetches API token with given ID along with hash of its secret
+pub async fn get_api_token<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: &str,
+) -> Result<Option<(ApiToken, String)>> {
+    sqlx::query_as("SELECT * FROM api_tokens WHERE id = $1")
+        .bind(id)
+        .fetch_optional(executor)
+        .await
+        .context("unable to query api token from database")?
+        .map(|mut raw: RawApiToken| {
+            let secret_hash = std::mem::take(&mut raw.secret_hash);
+            raw.try_into().map(|api_token| (api_token, secret_hash))
+        })
+        .transpose()
+}
+
+/// Fetches all the API tokens
+pub async fn get_api_tokens<'e>(
+    executor: impl Executor<'e, Database = Db>,
+) -> Result<Vec<ApiToken>> {
+    let raw_api_tokens: Vec<RawApiToken> =
+        sqlx::query_as("SELECT * FROM api_tokens ORDER BY created_at DESC")
+            .fetch_all(executor)
+            .await
+            .context("unable to query api tokens from database")?;
+
+    raw_api_tokens.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Revokes API token with given ID
+pub async fn revoke_api_token<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: &str,
+) -> Result<()> {
+    let rows_affected =
+        sqlx::query("UPDATE api_tokens SET revoked_at = $1 WHERE id = $2 AND revoked_at IS NULL")
+            .bind(Utc::now())
+            .bind(id)
+            .execute(executor)
+            .await
+            .context("unable to revoke api token")?
+            .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "active api token with id {} not found",
+        id
+    );
+
+    Ok(())
+}
@@ -0,0 +1,399 @@
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::{ensure, Context, Error, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use sqlx::{Executor, FromRow};
+
+use crate::{attestation::BuildAttestation, Db, Event};
+
+use super::hash_chain::{self, GENESIS_HASH};
+
+/// Entry in hash-chained audit log
+#[derive(Debug)]
+pub struct AuditLogEntry {
+    /// ID of entry
+    pub id: i64,
+    /// Logged event
+    pub event: Event,
+    /// Hash of previous entry (in hex)
+    pub prev_hash: String,
+    /// Hash of this entry (in hex)
+    pub hash: String,
+    /// Digest of build attestation of solo machine which logged the entry (`None` for entries logged before build
+    /// attestations were recorded)
+    pub attestation: Option<String>,
+    /// Time at which entry was logged
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw entry in hash-chained audit log
+struct RawAuditLogEntry {
+    /// ID of entry
+    pub id: i64,
+    /// JSON encoded event (stored as text so that hashed bytes are preserved)
+    pub event: String,
+    /// Hash of previous entry (in hex)
+    pub prev_hash: String,
+    /// Hash of this entry (in hex)
+    pub hash: String,
+    /// Digest of build attestation of solo machine which logged the entry (`None` for entries logged before build
+    /// attestations were recorded)
+    pub attestation: Option<String>,
+    /// Time at which entry was logged
+    pub created_at: DateTime<Utc>,
+}
+
+impl RawAuditLogEntry {
+    fn compute_hash(&self) -> String {
+        compute_hash(
+            &self.prev_hash,
+            &self.created_at,
+            &self.event,
+            self.attestation.as_deref(),
+        )
+    }
+}
+
+impl TryFrom<RawAuditLogEntry> for AuditLogEntry {
+    type Error = Error;
+
+    fn try_from(raw: RawAuditLogEntry) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: raw.id,
+            event: serde_json::from_str(&raw.event)
+                .context(format!("invalid event in audit log entry {}", raw.id))?,
+            prev_hash: raw.prev_hash,
+            hash: raw.hash,
+            attestation: raw.attestation,
+            created_at: raw.created_at,
+        })
+    }
+}
+
+/// Result of verifying hash chain of audit log
+#[derive(Debug)]
+pub struct AuditLogVerification {
+    /// Number of verified entries
+    pub verified_entries: u64,
+    /// Hash of the last verified entry (can be recorded externally to detect truncation of audit log)
+    pub head_hash: String,
+    /// IDs of the entries breaking hash chain whose breaks were explicitly accepted
+    pub accepted_breaks: Vec<i64>,
+    /// ID of the first entry which breaks hash chain and the reason (`None` if whole audit log is valid)
+    pub broken_entry: Option<(i64, String)>,
+}
+
+/// Appends an event to audit log
+pub async fn add_audit_log_entry(
+    transaction: &mut sqlx::Transaction<'_, Db>,
+    event: &Event,
+) -> Result<()> {
+    let head: Option<(String,)> =
+        sqlx::query_as("SELECT hash FROM audit_log ORDER BY id DESC LIMIT 1")
+            .fetch_optional(&mut *transaction)
+            .await
+            .context("unable to query head of audit log")?;
+
+    let prev_hash = head.map_or_else(|| GENESIS_HASH.to_string(), |(hash,)| hash);
+    // sub-second precision is dropped so that stored timestamp (and thus hash) is same for all the databases
+    let created_at = Utc.timestamp(Utc::now().timestamp(), 0);
+    let event = serde_json::to_string(event).context("unable to serialize event")?;
+    let attestation = add_build_attestation(&mut *transaction).await?;
+    let hash = compute_hash(&prev_hash, &created_at, &event, Some(&attestation));
+
+    let rows_affected = sqlx::query(
+        "INSERT INTO audit_log (event, prev_hash, hash, attestation, created_at) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(event)
+    .bind(prev_hash)
+    .bind(hash)
+    .bind(attestation)
+    .bind(created_at)
+    .execute(&mut *transaction)
+    .await
+    .context("unable to add new audit log entry")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when adding new audit log entry"
+    );
+
+    Ok(())
+}
+
+/// Records build attestation of current binary (if not already recorded) and returns its digest
+async fn add_build_attestation<'e>(executor: impl Executor<'e, Database = Db>) -> Result<String> {
+    let attestation = BuildAttestation::current()?;
+    let digest = attestation.digest()?;
+
+    sqlx::query(
+        "INSERT INTO build_attestations (digest, attestation) VALUES ($1, $2) ON CONFLICT (digest) DO NOTHING",
+    )
+    .bind(&digest)
+    .bind(attestation.to_canonical_json()?)
+    .execute(executor)
+    .await
+    .context("unable to add build attestation")?;
+
+    Ok(digest)
+}
+
+/// Fetches recorded build attestation with given digest
+pub async fn get_build_attestation<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    digest: &str,
+) -> Result<Option<BuildAttestation>> {
+    let raw: Option<(String,)> =
+        sqlx::query_as("SELECT attestation FROM build_attestations WHERE digest = $1")
+            .bind(digest)
+            .fetch_optional(executor)
+            .await
+            .context("unable to query build attestation from database")?;
+
+    raw.map(|(attestation,)| {
+        serde_json::from_str(&attestation)
+            .context(format!("invalid build attestation with digest {}", digest))
+    })
+    .transpose()
+}
+
+/// Fetches entries of audit log (latest first)
+pub async fn get_audit_log_entries<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<AuditLogEntry>> {
+    let raw: Vec<RawAuditLogEntry> =
+        sqlx::query_as("SELECT * FROM audit_log ORDER BY id DESC LIMIT $1 OFFSET $2")
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(executor)
+            .await
+            .context("unable to query audit log entries from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Verifies hash chain of all the entries of audit log. When a break was explicitly accepted, verification resumes
+/// at the entry accepting it (entries between the break and the accepting entry are skipped).
+pub async fn verify_audit_log<'e>(
+    executor: impl Executor<'e, Database = Db>,
+) -> Result<AuditLogVerification> {
+    let raw: Vec<RawAuditLogEntry> = sqlx::query_as("SELECT * FROM audit_log ORDER BY id")
+        .fetch_all(executor)
+        .await
+        .context("unable to query audit log entries from database")?;
+
+    let mut verification = AuditLogVerification {
+        verified_entries: 0,
+        head_hash: GENESIS_HASH.to_string(),
+        accepted_breaks: Vec::new(),
+        broken_entry: None,
+    };
+
+    let mut index = 0;
+
+    while index < raw.len() {
+        let entry = &raw[index];
+
+        let broken = if entry.prev_hash != verification.head_hash {
+            Some("previous hash does not match hash of previous entry")
+        } else if entry.hash != entry.compute_hash() {
+            Some("hash does not match contents of entry")
+        } else {
+            None
+        };
+
+        if let Some(reason) = broken {
+            match find_acceptance(&raw[index + 1..], entry.id) {
+                Some(offset) => {
+                    verification.accepted_breaks.push(entry.id);
+                    index += offset + 1;
+                    // accepting entry is chained to the stored hash of entry preceding it
+                    verification.head_hash = raw[index - 1].hash.clone();
+                }
+                None => {
+                    verification.broken_entry = Some((entry.id, reason.to_string()));
+                    break;
+                }
+            }
+
+            continue;
+        }
+
+        verification.verified_entries += 1;
+        verification.head_hash = entry.hash.clone();
+        index += 1;
+    }
+
+    Ok(verification)
+}
+
+/// Returns position of the entry accepting break at given entry (if any)
+fn find_acceptance(entries: &[RawAuditLogEntry], broken_id: i64) -> Option<usize> {
+    entries.iter().position(|entry| {
+        matches!(
+            serde_json::from_str(&entry.event),
+            Ok(Event::AuditLogBreakAccepted { entry_id, .. }) if entry_id == broken_id
+        )
+    })
+}
+
+fn compute_hash(
+    prev_hash: &str,
+    created_at: &DateTime<Utc>,
+    event: &str,
+    attestation: Option<&str>,
+) -> String {
+    match attestation {
+        Some(attestation) => hash_chain::compute_hash(prev_hash, created_at, &[event, attestation]),
+        // entries logged before build attestations were recorded do not have attestation
+        None => hash_chain::compute_hash(prev_hash, created_at, &[event]),
+    }
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use super::*;
+    use crate::{test_util::db_pool, DbPool};
+
+    fn event(entry_id: i64) -> Event {
+        Event::AuditLogBreakAccepted {
+            entry_id,
+            reason: "test".to_string(),
+        }
+    }
+
+    async fn add_entries(db_pool: &DbPool, count: i64) {
+        for entry_id in 0..count {
+            let mut transaction = db_pool.begin().await.unwrap();
+            add_audit_log_entry(&mut transaction, &event(100 + entry_id))
+                .await
+                .unwrap();
+            transaction.commit().await.unwrap();
+        }
+    }
+
+    async fn tamper(db_pool: &DbPool, id: i64, recompute_hash: bool) {
+        let event = serde_json::to_string(&event(42)).unwrap();
+        let (prev_hash, created_at, attestation): (String, DateTime<Utc>, Option<String>) =
+            sqlx::query_as(
+                "SELECT prev_hash, created_at, attestation FROM audit_log WHERE id = $1",
+            )
+            .bind(id)
+            .fetch_one(db_pool)
+            .await
+            .unwrap();
+
+        let hash = if recompute_hash {
+            compute_hash(&prev_hash, &created_at, &event, attestation.as_deref())
+        } else {
+            sqlx::query_as::<_, (String,)>("SELECT hash FROM audit_log WHERE id = $1")
+                .bind(id)
+                .fetch_one(db_pool)
+                .await
+                .unwrap()
+                .0
+        };
+
+        sqlx::query("UPDATE audit_log SET event = $1, hash = $2 WHERE id = $3")
+            .bind(event)
+            .bind(hash)
+            .bind(id)
+            .execute(db_pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn untampered_audit_log_is_verified() {
+        let db_pool = db_pool().await;
+        add_entries(&db_pool, 3).await;
+
+        let verification = verify_audit_log(&db_pool).await.unwrap();
+
+        assert_eq!(verification.verified_entries, 3);
+        assert_eq!(verification.broken_entry, None);
+        assert_eq!(
+            verification.head_hash,
+            get_audit_log_entries(&db_pool, 1, 0).await.unwrap()[0].hash
+        );
+    }
+
+    #[tokio::test]
+    async fn modified_entry_breaks_hash_chain() {
+        let db_pool = db_pool().await;
+        add_entries(&db_pool, 3).await;
+        tamper(&db_pool, 2, false).await;
+
+        let verification = verify_audit_log(&db_pool).await.unwrap();
+
+        assert_eq!(verification.verified_entries, 1);
+        assert_eq!(
+            verification.broken_entry,
+            Some((2, "hash does not match contents of entry".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn rehashed_entry_breaks_hash_chain_at_next_entry() {
+        let db_pool = db_pool().await;
+        add_entries(&db_pool, 3).await;
+        tamper(&db_pool, 2, true).await;
+
+        let verification = verify_audit_log(&db_pool).await.unwrap();
+
+        assert_eq!(verification.verified_entries, 2);
+        assert_eq!(
+            verification.broken_entry,
+            Some((
+                3,
+                "previous hash does not match hash of previous entry".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn deleted_entry_breaks_hash_chain() {
+        let db_pool = db_pool().await;
+        add_entries(&db_pool, 3).await;
+
+        sqlx::query("DELETE FROM audit_log WHERE id = 2")
+            .execute(&db_pool)
+            .await
+            .unwrap();
+
+        let verification = verify_audit_log(&db_pool).await.unwrap();
+
+        assert_eq!(verification.verified_entries, 1);
+        assert_eq!(
+            verification.broken_entry,
+            Some((
+                3,
+                "previous hash does not match hash of previous entry".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn accepted_break_resumes_verification() {
+        let db_pool = db_pool().await;
+        add_entries(&db_pool, 3).await;
+        tamper(&db_pool, 2, false).await;
+
+        let mut transaction = db_pool.begin().await.unwrap();
+        add_audit_log_entry(&mut transaction, &event(2))
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        let verification = verify_audit_log(&db_pool).await.unwrap();
+
+        assert_eq!(verification.accepted_breaks, vec![2]);
+        assert_eq!(verification.broken_entry, None);
+        // first entry and the entry accepting the break (entries in between are skipped)
+        assert_eq!(verification.verified_entries, 2);
+    }
+}
@@ -1,5 +1,6 @@
 #[allow(clippy::module_inception)]
 mod chain;
 pub mod chain_keys;
+pub mod chain_labels;
 
 pub use self::chain::*;
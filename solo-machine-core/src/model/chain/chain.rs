@@ -1,28 +1,64 @@
 use std::{
     convert::{TryFrom, TryInto},
+    fmt,
+    path::PathBuf,
     time::Duration,
 };
 
-use anyhow::{anyhow, ensure, Context, Error, Result};
+use anyhow::{anyhow, bail, ensure, Context, Error, Result};
 use chrono::{DateTime, Utc};
-use cosmos_sdk_proto::cosmos::bank::v1beta1::{
-    query_client::QueryClient as BankQueryClient, QueryBalanceRequest,
+use cosmos_sdk_proto::{
+    cosmos::bank::v1beta1::{query_client::QueryClient as BankQueryClient, QueryBalanceRequest},
+    ibc::core::{
+        channel::v1::{Channel, QueryChannelRequest, QueryChannelResponse},
+        client::v1::{QueryClientStateRequest, QueryClientStateResponse},
+        connection::v1::{ConnectionEnd, QueryConnectionRequest, QueryConnectionResponse},
+    },
 };
 use num_rational::Ratio;
+use prost_types::Any;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sha2::{Digest, Sha256};
 use sqlx::{types::Json, Executor, FromRow};
 use tendermint::{block::Height as BlockHeight, node::Id as NodeId};
+use tonic::{client::Grpc, codec::ProstCodec, codegen::http::uri::PathAndQuery, Code, Request};
 
 use crate::{
+    broadcaster::{InclusionPolicy, TxEvent},
+    client_encoder::{decode_client_state_sequence, ClientEncoding},
+    cosmos::{
+        address::AddressFormat,
+        amino::SignMode,
+        crypto::PublicKey,
+        grpc_pool::grpc_channel,
+        name_resolver::{is_account_address, AddressDisplay, AddressDisplayer, NameResolverConfig},
+    },
+    failover::grpc_query,
+    fee::FeeStrategyKind,
     ibc::core::ics24_host::{
         identifier::{ChainId, ChannelId, ClientId, ConnectionId, Identifier, PortId},
         path::DenomTrace,
     },
+    ibc_version::{default_solo_machine_revision, IbcVersion, SoloMachineRevision},
+    proxy::Proxy,
+    rate_limit::RateLimit,
+    retry::RetryPolicy,
+    signer::AddressAlgo,
+    storage::encryption,
     Db, ToPublicKey,
 };
 
+/// gRPC path of client state query of IBC client module
+const CLIENT_STATE_PATH: &str = "/ibc.core.client.v1.Query/ClientState";
+
+/// gRPC path of connection query of IBC connection module
+const CONNECTION_PATH: &str = "/ibc.core.connection.v1.Query/Connection";
+
+/// gRPC path of channel query of IBC channel module
+const CHANNEL_PATH: &str = "/ibc.core.channel.v1.Query/Channel";
+
 /// State of an IBC enabled chain
 #[derive(Debug)]
 pub struct Chain {
@@ -40,6 +76,12 @@ pub struct Chain {
     pub packet_sequence: u32,
     /// IBC connection details
     pub connection_details: Option<ConnectionDetails>,
+    /// Latest identifiers assigned by chain to solo machine's client, connection and channel
+    pub generated_identifiers: GeneratedIdentifiers,
+    /// Progress of an interrupted connection handshake (`None` if no handshake is in progress)
+    pub handshake_progress: Option<HandshakeProgress>,
+    /// Genesis time of chain (recorded when chain is first checked for a reset, used for detecting devnet resets)
+    pub genesis_time: Option<DateTime<Utc>>,
     /// Creation time of chain
     pub created_at: DateTime<Utc>,
     /// Last updation time of chain
@@ -63,36 +105,241 @@ impl Chain {
         Some(format!("ibc/{}", hex::encode_upper(hash)))
     }
 
+    /// Returns public key of signer's account on chain (as used by address algorithm of chain)
+    pub fn account_public_key(&self, signer: impl ToPublicKey) -> Result<PublicKey> {
+        signer
+            .to_public_key()?
+            .with_address_algo(self.config.address_algo)
+    }
+
+    /// Returns account address of signer on chain (derived using address algorithm of chain)
+    pub fn account_address(&self, signer: impl ToPublicKey) -> Result<String> {
+        self.account_public_key(&signer)?
+            .account_address(signer.get_account_prefix())
+    }
+
     /// Fetches on-chain balance of given denom
     pub async fn get_balance(
         &self,
         signer: impl ToPublicKey,
         denom: &Identifier,
     ) -> Result<Decimal> {
-        let mut query_client = BankQueryClient::connect(self.config.grpc_addr.clone())
-            .await
-            .context(format!(
-                "unable to connect to grpc query client at {}",
-                self.config.grpc_addr
-            ))?;
+        let config = &self.config;
 
         let denom = self
             .get_ibc_denom(denom)
             .ok_or_else(|| anyhow!("connection details not found when fetching balance"))?;
 
         let request = QueryBalanceRequest {
-            address: signer.to_account_address()?,
+            address: self.account_address(&signer)?,
             denom,
         };
 
-        Ok(query_client
-            .balance(request)
+        Ok(grpc_query(&self.config, "balance query", |grpc_addr| {
+            let request = request.clone();
+
+            async move {
+                BankQueryClient::new(grpc_channel(&grpc_addr, config).await?)
+                    .balance(request)
+                    .await
+                    .with_context(|| format!("unable to query balance from {}", grpc_addr))
+            }
+        })
+        .await?
+        .into_inner()
+        .balance
+        .map(|coin| coin.amount.parse())
+        .transpose()?
+        .unwrap_or_default())
+    }
+
+    /// Fetches sequence of solo machine client on chain (i.e. the sequence at which chain expects the next signature of
+    /// solo machine)
+    pub async fn get_solo_machine_sequence(&self) -> Result<u64> {
+        let connection_details = self
+            .connection_details
+            .as_ref()
+            .ok_or_else(|| anyhow!("connection details not found for chain with id {}", self.id))?;
+
+        let client_state = self
+            .query_client_state(&connection_details.solo_machine_client_id)
+            .await?
+            .ok_or_else(|| {
+                anyhow!(
+                    "solo machine client {} not found on chain {}",
+                    connection_details.solo_machine_client_id,
+                    self.id
+                )
+            })?;
+
+        decode_client_state_sequence(&client_state)
+    }
+
+    /// Queries client state of IBC client with given ID from chain (`None` if client does not exist)
+    pub async fn query_client_state(&self, client_id: &ClientId) -> Result<Option<Any>> {
+        let request = QueryClientStateRequest {
+            client_id: client_id.to_string(),
+        };
+
+        Ok(self
+            .ibc_query::<_, QueryClientStateResponse>(
+                "client state query",
+                CLIENT_STATE_PATH,
+                request,
+            )
+            .await?
+            .and_then(|response| response.client_state))
+    }
+
+    /// Queries connection with given ID from chain (`None` if connection does not exist)
+    pub async fn query_connection(
+        &self,
+        connection_id: &ConnectionId,
+    ) -> Result<Option<ConnectionEnd>> {
+        let request = QueryConnectionRequest {
+            connection_id: connection_id.to_string(),
+        };
+
+        Ok(self
+            .ibc_query::<_, QueryConnectionResponse>("connection query", CONNECTION_PATH, request)
             .await?
-            .into_inner()
-            .balance
-            .map(|coin| coin.amount.parse())
-            .transpose()?
-            .unwrap_or_default())
+            .and_then(|response| response.connection))
+    }
+
+    /// Queries channel with given port and channel ID from chain (`None` if channel does not exist)
+    pub async fn query_channel(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Option<Channel>> {
+        let request = QueryChannelRequest {
+            port_id: port_id.to_string(),
+            channel_id: channel_id.to_string(),
+        };
+
+        Ok(self
+            .ibc_query::<_, QueryChannelResponse>("channel query", CHANNEL_PATH, request)
+            .await?
+            .and_then(|response| response.channel))
+    }
+
+    /// Makes a gRPC query to IBC module of chain at given path (`None` if queried object is not found)
+    async fn ibc_query<Q, R>(
+        &self,
+        description: &str,
+        path: &'static str,
+        request: Q,
+    ) -> Result<Option<R>>
+    where
+        Q: prost::Message + Clone + Send + Sync + 'static,
+        R: prost::Message + Default + Send + Sync + 'static,
+    {
+        let config = &self.config;
+
+        grpc_query(&self.config, description, |grpc_addr| {
+            let request = request.clone();
+
+            async move {
+                let mut client = Grpc::new(grpc_channel(&grpc_addr, config).await?);
+
+                client
+                    .ready()
+                    .await
+                    .context("grpc query client is not ready")?;
+
+                let response = client
+                    .unary::<_, R, _>(
+                        Request::new(request),
+                        PathAndQuery::from_static(path),
+                        ProstCodec::default(),
+                    )
+                    .await;
+
+                match response {
+                    Ok(response) => Ok(Some(response.into_inner())),
+                    Err(status) if status.code() == Code::NotFound => Ok(None),
+                    Err(status) => Err(status).with_context(|| {
+                        format!("unable to make {} to {}", description, grpc_addr)
+                    }),
+                }
+            }
+        })
+        .await
+    }
+
+    /// Resolves given name or address to an account address on chain. If a valid account address with given account
+    /// prefix is given (or a hex address, which is converted to bech32 using given account prefix), it is returned as
+    /// is, otherwise, it is resolved using configured name resolver. Account addresses with a different prefix (given
+    /// or resolved) are rejected.
+    pub async fn resolve_account_address(
+        &self,
+        name_or_address: &str,
+        account_prefix: &str,
+    ) -> Result<String> {
+        if is_account_address(name_or_address, account_prefix) {
+            return Ok(name_or_address.to_string());
+        }
+
+        if let Some(address) = self
+            .config
+            .address_format
+            .parse(name_or_address, account_prefix)
+        {
+            return address;
+        }
+
+        if let Ok((prefix, _, _)) = bech32::decode(name_or_address) {
+            bail!(
+                "account address {} has prefix `{}` but chain {} expects `{}`",
+                name_or_address,
+                prefix,
+                self.id,
+                account_prefix
+            );
+        }
+
+        let name_resolver = self.config.name_resolver.as_ref().ok_or_else(|| {
+            anyhow!(
+                "`{}` is not a valid account address and no name resolver is configured for chain {}",
+                name_or_address,
+                self.id
+            )
+        })?;
+
+        let address = name_resolver
+            .connect(self.config.proxy.as_ref(), &self.config.timeouts)
+            .await?
+            .resolve(name_or_address)
+            .await?;
+
+        ensure!(
+            is_account_address(&address, account_prefix),
+            "`{}` resolved to {} which is not a valid account address with prefix `{}`",
+            name_or_address,
+            address,
+            account_prefix
+        );
+
+        Ok(address)
+    }
+
+    /// Returns address displayer for formatting account addresses of chain based on its address display
+    /// configuration. Connects to name resolver of chain (if addresses are displayed as names) once so that the
+    /// connection is reused for all the addresses; addresses are displayed as is if it is unreachable.
+    pub async fn address_displayer(&self) -> AddressDisplayer {
+        let name_resolver = match (&self.config.name_resolver, self.config.address_display) {
+            (None, _) | (_, AddressDisplay::Address) => None,
+            (Some(name_resolver), _) => name_resolver
+                .connect(self.config.proxy.as_ref(), &self.config.timeouts)
+                .await
+                .ok(),
+        };
+
+        AddressDisplayer::new(
+            self.config.address_format,
+            self.config.address_display,
+            name_resolver,
+        )
     }
 }
 
@@ -103,8 +350,8 @@ struct RawChain {
     pub id: String,
     /// Node ID of chain
     pub node_id: String,
-    /// Configuration for chain
-    pub config: Json<ChainConfig>,
+    /// Configuration for chain (encrypted if stored state is encrypted)
+    pub config: Json<Value>,
     /// Consensus timestamp of solo machine (used when creating transactions on chain)
     pub consensus_timestamp: DateTime<Utc>,
     /// Sequence of solo machine (used when creating transactions on chain)
@@ -113,6 +360,12 @@ struct RawChain {
     pub packet_sequence: i64,
     /// IBC connection details
     pub connection_details: Option<Json<ConnectionDetails>>,
+    /// Latest identifiers assigned by chain to solo machine's client, connection and channel
+    pub generated_identifiers: Option<Json<GeneratedIdentifiers>>,
+    /// Progress of an interrupted connection handshake
+    pub handshake_progress: Option<Json<HandshakeProgress>>,
+    /// Genesis time of chain
+    pub genesis_time: Option<DateTime<Utc>>,
     /// Creation time of chain
     pub created_at: DateTime<Utc>,
     /// Last updation time of chain
@@ -120,12 +373,25 @@ struct RawChain {
 }
 
 /// Configuration related to an IBC enabled chain
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainConfig {
     /// gRPC address
     pub grpc_addr: String,
+    /// TLS settings of gRPC endpoint (used with `https` gRPC address)
+    #[serde(default)]
+    pub grpc_tls: GrpcTlsConfig,
     /// RPC address
     pub rpc_addr: String,
+    /// Fallback gRPC addresses (in order of preference) used when gRPC endpoint at primary address is failing
+    #[serde(default)]
+    pub fallback_grpc_addrs: Vec<String>,
+    /// Fallback RPC addresses (in order of preference) used when RPC endpoint at primary address is failing or
+    /// serving stale data
+    #[serde(default)]
+    pub fallback_rpc_addrs: Vec<String>,
+    /// Proxy through which RPC and gRPC connections to chain are tunnelled
+    #[serde(default)]
+    pub proxy: Option<Proxy>,
     /// Fee and gas limits
     pub fee: Fee,
     /// Trust level (e.g. 1/3)
@@ -134,7 +400,7 @@ pub struct ChainConfig {
     pub trusting_period: Duration,
     /// Maximum clock drift
     pub max_clock_drift: Duration,
-    /// RPC timeout duration
+    /// Timeout of RPC requests made by light client when fetching blocks
     pub rpc_timeout: Duration,
     /// Diversifier used in transactions for chain
     pub diversifier: String,
@@ -145,10 +411,148 @@ pub struct ChainConfig {
     /// Block hash at trusted height of the chain
     #[serde(with = "hex::serde")]
     pub trusted_hash: [u8; 32],
+    /// Name service used to resolve human-readable names to account addresses
+    #[serde(default)]
+    pub name_resolver: Option<NameResolverConfig>,
+    /// Format used when displaying account addresses
+    #[serde(default)]
+    pub address_display: AddressDisplay,
+    /// Format in which account addresses are displayed and accepted (`eip55` for 0x-prefixed addresses on ethermint
+    /// based chains)
+    #[serde(default)]
+    pub address_format: AddressFormat,
+    /// Sign mode used when signing transactions for chain
+    #[serde(default)]
+    pub sign_mode: SignMode,
+    /// Algorithm used for generating account addresses on chain (`eth-secp256k1` for ethermint based chains)
+    #[serde(default)]
+    pub address_algo: AddressAlgo,
+    /// Maximum age of latest block after which chain is considered halted (operations consuming sequences are paused
+    /// until block production resumes, zero disables halt detection)
+    #[serde(default = "default_halt_threshold")]
+    pub halt_threshold: Duration,
+    /// Strategy used for computing fees of transactions (fee configured for chain is used as is by default)
+    #[serde(default)]
+    pub fee_strategy: FeeStrategyKind,
+    /// Encoding of solo machine client types (standard ibc-go type URLs by default)
+    #[serde(default)]
+    pub client_encoding: ClientEncoding,
+    /// Policy for waiting until transactions sent to chain are included in a block
+    #[serde(default)]
+    pub inclusion_policy: InclusionPolicy,
+    /// Policy for retrying queries made to chain when they fail due to transient network errors
+    #[serde(default)]
+    pub retry_policy: RetryPolicy,
+    /// Timeouts of RPC and gRPC requests made to chain
+    #[serde(default)]
+    pub timeouts: NetworkTimeouts,
+    /// Rate limit of requests made to each RPC and gRPC endpoint of chain (unlimited when not set)
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+    /// Offset added to time of latest verified block of chain to derive consensus timestamp of solo machine (`None`
+    /// keeps the host clock time at which chain was added). Consensus timestamp is synced whenever a new header of
+    /// chain is verified and is never moved backwards.
+    #[serde(default)]
+    pub timestamp_sync_offset: Option<Duration>,
+    /// IBC implementation of chain (detected when chain is added or connected, `None` if not known)
+    #[serde(default)]
+    pub ibc_version: Option<IbcVersion>,
+    /// Revision of solo machine client types used for chain (`None` selects it based on IBC version of chain)
+    #[serde(default)]
+    pub client_revision: Option<SoloMachineRevision>,
+    /// Verifies (using gRPC IBC queries) that client, connection and channel referenced by a message are in expected
+    /// state on chain before signing it, so that doomed transactions fail fast without consuming sequences
+    #[serde(default)]
+    pub strict_preflight: bool,
+}
+
+impl ChainConfig {
+    /// Returns all gRPC addresses of chain (primary address followed by fallback addresses)
+    pub fn grpc_addrs(&self) -> Vec<String> {
+        std::iter::once(self.grpc_addr.clone())
+            .chain(self.fallback_grpc_addrs.iter().cloned())
+            .collect()
+    }
+
+    /// Returns all RPC addresses of chain (primary address followed by fallback addresses)
+    pub fn rpc_addrs(&self) -> Vec<String> {
+        std::iter::once(self.rpc_addr.clone())
+            .chain(self.fallback_rpc_addrs.iter().cloned())
+            .collect()
+    }
+
+    /// Returns revision of solo machine client types used for chain (explicitly configured one, or the one expected
+    /// by IBC version of chain, or the default one when IBC version of chain is not known)
+    pub fn solo_machine_revision(&self) -> SoloMachineRevision {
+        self.client_revision
+            .or_else(|| {
+                self.ibc_version
+                    .as_ref()
+                    .map(IbcVersion::solo_machine_revision)
+            })
+            .unwrap_or_else(default_solo_machine_revision)
+    }
+}
+
+/// TLS settings of gRPC endpoint of a chain (`https` gRPC addresses are verified using native root certificates when
+/// no setting is provided)
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct GrpcTlsConfig {
+    /// Path of PEM encoded root CA certificate trusted in addition to native root certificates
+    #[serde(default)]
+    pub ca_cert: Option<PathBuf>,
+    /// Domain name used for SNI and for verifying certificate of server (host of gRPC address by default)
+    #[serde(default)]
+    pub domain_name: Option<String>,
+}
+
+impl GrpcTlsConfig {
+    /// Returns `true` if no TLS setting is provided
+    pub fn is_default(&self) -> bool {
+        self.ca_cert.is_none() && self.domain_name.is_none()
+    }
+}
+
+/// Timeouts of network calls made to a chain, so that a hung node cannot block an operation indefinitely
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct NetworkTimeouts {
+    /// Maximum time for establishing a connection with gRPC endpoint (including TLS handshake)
+    pub connect: Duration,
+    /// Maximum time for completing a single RPC or gRPC request
+    pub request: Duration,
+}
+
+impl Default for NetworkTimeouts {
+    fn default() -> Self {
+        Self {
+            connect: Duration::from_secs(10),
+            request: Duration::from_secs(30),
+        }
+    }
+}
+
+impl NetworkTimeouts {
+    /// Ensures that timeouts are valid
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            !self.connect.is_zero(),
+            "connect timeout should be greater than zero"
+        );
+        ensure!(
+            !self.request.is_zero(),
+            "request timeout should be greater than zero"
+        );
+
+        Ok(())
+    }
+}
+
+fn default_halt_threshold() -> Duration {
+    Duration::from_secs(120)
 }
 
 /// Fee and gas configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Fee {
     /// Fee amount
     pub amount: Decimal,
@@ -156,6 +560,10 @@ pub struct Fee {
     pub denom: Identifier,
     /// Gas limit
     pub gas_limit: u64,
+    /// Account which granted a fee allowance to solo machine signer (fees are deducted from granter's allowance
+    /// when set)
+    #[serde(default)]
+    pub granter: Option<String>,
 }
 
 /// IBC connection details
@@ -175,37 +583,218 @@ pub struct ConnectionDetails {
     pub tendermint_channel_id: ChannelId,
 }
 
-impl From<Chain> for RawChain {
-    fn from(chain: Chain) -> Self {
-        Self {
-            id: chain.id.to_string(),
-            node_id: chain.node_id.to_string(),
-            config: Json(chain.config),
-            consensus_timestamp: chain.consensus_timestamp,
-            sequence: chain.sequence.into(),
-            packet_sequence: chain.packet_sequence.into(),
-            connection_details: chain.connection_details.map(Json),
-            created_at: chain.created_at,
-            updated_at: chain.updated_at,
+/// Identifiers assigned by IBC enabled chain to solo machine's client, connection and channel (extracted from events
+/// of committed transactions)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeneratedIdentifiers {
+    /// Client ID of solo machine client on IBC enabled chain
+    pub client_id: Option<ClientId>,
+    /// Connection ID of solo machine client on IBC enabled chain
+    pub connection_id: Option<ConnectionId>,
+    /// Channel ID of solo machine client on IBC enabled chain
+    pub channel_id: Option<ChannelId>,
+}
+
+impl GeneratedIdentifiers {
+    /// Extracts identifiers generated by `MsgCreateClient`, `MsgConnectionOpenInit` and `MsgChannelOpenInit` from
+    /// events of a committed transaction
+    pub fn from_events(events: &[TxEvent]) -> Result<Self> {
+        let mut identifiers = Self::default();
+
+        for event in events {
+            match event.kind.as_str() {
+                "create_client" => {
+                    if let Some(client_id) = event.attribute("client_id") {
+                        identifiers.client_id = Some(client_id.parse()?);
+                    }
+                }
+                "connection_open_init" => {
+                    if let Some(connection_id) = event.attribute("connection_id") {
+                        identifiers.connection_id = Some(connection_id.parse()?);
+                    }
+                }
+                "channel_open_init" => {
+                    if let Some(channel_id) = event.attribute("channel_id") {
+                        identifiers.channel_id = Some(channel_id.parse()?);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(identifiers)
+    }
+
+    /// Returns `true` if no identifier is present
+    pub fn is_empty(&self) -> bool {
+        self.client_id.is_none() && self.connection_id.is_none() && self.channel_id.is_none()
+    }
+
+    /// Merges newly generated identifiers into current ones (new identifiers take precedence)
+    pub fn merge(&mut self, other: &Self) {
+        if other.client_id.is_some() {
+            self.client_id = other.client_id.clone();
+        }
+
+        if other.connection_id.is_some() {
+            self.connection_id = other.connection_id.clone();
+        }
+
+        if other.channel_id.is_some() {
+            self.channel_id = other.channel_id.clone();
+        }
+    }
+}
+
+impl fmt::Display for GeneratedIdentifiers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn display(id: Option<&impl fmt::Display>) -> String {
+            id.map_or_else(|| "-".to_string(), ToString::to_string)
+        }
+
+        write!(
+            f,
+            "client: {}, connection: {}, channel: {}",
+            display(self.client_id.as_ref()),
+            display(self.connection_id.as_ref()),
+            display(self.channel_id.as_ref())
+        )
+    }
+}
+
+/// Step of a connection handshake with an IBC enabled chain (in the order they are executed)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HandshakeStep {
+    /// Create solo machine client on IBC enabled chain
+    #[default]
+    CreateSoloMachineClient,
+    /// Create tendermint client on solo machine
+    CreateTendermintClient,
+    /// Initialize connection on IBC enabled chain
+    ConnectionOpenInit,
+    /// Initialize connection on solo machine
+    ConnectionOpenTry,
+    /// Acknowledge connection on IBC enabled chain
+    ConnectionOpenAck,
+    /// Open connection on solo machine
+    ConnectionOpenConfirm,
+    /// Initialize channel on IBC enabled chain
+    ChannelOpenInit,
+    /// Initialize channel on solo machine
+    ChannelOpenTry,
+    /// Acknowledge channel on IBC enabled chain
+    ChannelOpenAck,
+    /// Open channel on solo machine
+    ChannelOpenConfirm,
+}
+
+impl fmt::Display for HandshakeStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreateSoloMachineClient => write!(f, "create-solo-machine-client"),
+            Self::CreateTendermintClient => write!(f, "create-tendermint-client"),
+            Self::ConnectionOpenInit => write!(f, "connection-open-init"),
+            Self::ConnectionOpenTry => write!(f, "connection-open-try"),
+            Self::ConnectionOpenAck => write!(f, "connection-open-ack"),
+            Self::ConnectionOpenConfirm => write!(f, "connection-open-confirm"),
+            Self::ChannelOpenInit => write!(f, "channel-open-init"),
+            Self::ChannelOpenTry => write!(f, "channel-open-try"),
+            Self::ChannelOpenAck => write!(f, "channel-open-ack"),
+            Self::ChannelOpenConfirm => write!(f, "channel-open-confirm"),
         }
     }
 }
 
+/// Progress of a connection handshake with an IBC enabled chain, persisted as a state machine: the next step to
+/// execute is committed to database along with the outcome of each completed step, so that an interrupted handshake
+/// is resumed from where it stopped without creating new clients, connections and channels or signing a sequence
+/// twice.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HandshakeProgress {
+    /// Next step of the handshake
+    pub step: HandshakeStep,
+    /// ID of queued operation submitted for current step (set before the transaction leaves solo machine, so that
+    /// identifiers assigned by chain can be recovered from it if the process stops before they are recorded)
+    #[serde(default)]
+    pub pending_operation_id: Option<i64>,
+    /// Client ID of solo machine client on IBC enabled chain
+    pub solo_machine_client_id: Option<ClientId>,
+    /// Client ID of IBC enabled chain on solo machine
+    pub tendermint_client_id: Option<ClientId>,
+    /// Connection ID of solo machine client on IBC enabled chain
+    pub solo_machine_connection_id: Option<ConnectionId>,
+    /// Connection ID of IBC enabled chain on solo machine
+    pub tendermint_connection_id: Option<ConnectionId>,
+    /// Channel ID of solo machine client on IBC enabled chain
+    pub solo_machine_channel_id: Option<ChannelId>,
+    /// Channel ID of IBC enabled chain on solo machine
+    pub tendermint_channel_id: Option<ChannelId>,
+    /// Signed `MsgConnectionOpenAck` or `MsgChannelOpenAck` transaction (protobuf encoded `TxRaw` in hex) which is
+    /// not yet confirmed on chain. Its proofs have already consumed solo machine sequences, so, it is broadcasted
+    /// again (instead of signing new proofs) when resuming the handshake.
+    pub pending_tx: Option<String>,
+}
+
+impl HandshakeProgress {
+    /// Moves the handshake to given step
+    pub fn advance(&mut self, step: HandshakeStep) {
+        self.step = step;
+        self.pending_operation_id = None;
+    }
+}
+
+impl fmt::Display for HandshakeProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn display(id: Option<&impl fmt::Display>) -> String {
+            id.map_or_else(|| "-".to_string(), ToString::to_string)
+        }
+
+        write!(
+            f,
+            "next step: {}, clients: {} / {}, connections: {} / {}, channels: {} / {}",
+            self.step,
+            display(self.solo_machine_client_id.as_ref()),
+            display(self.tendermint_client_id.as_ref()),
+            display(self.solo_machine_connection_id.as_ref()),
+            display(self.tendermint_connection_id.as_ref()),
+            display(self.solo_machine_channel_id.as_ref()),
+            display(self.tendermint_channel_id.as_ref()),
+        )?;
+
+        if let Some(operation_id) = self.pending_operation_id {
+            write!(f, " (operation {})", operation_id)?;
+        } else if self.pending_tx.is_some() {
+            write!(f, " (pending transaction)")?;
+        }
+
+        Ok(())
+    }
+}
+
 impl TryFrom<RawChain> for Chain {
     type Error = Error;
 
     fn try_from(raw: RawChain) -> Result<Self, Self::Error> {
+        let config = encryption::open_json(&raw.id, raw.config.0)?;
+
         Ok(Self {
             id: raw.id.parse()?,
             node_id: raw
                 .node_id
                 .parse()
                 .map_err(|err| anyhow!("unable to parse node id: {}", err))?,
-            config: raw.config.0,
+            config,
             consensus_timestamp: raw.consensus_timestamp,
             sequence: raw.sequence.try_into()?,
             packet_sequence: raw.packet_sequence.try_into()?,
             connection_details: raw.connection_details.map(|json| json.0),
+            generated_identifiers: raw
+                .generated_identifiers
+                .map(|json| json.0)
+                .unwrap_or_default(),
+            handshake_progress: raw.handshake_progress.map(|json| json.0),
+            genesis_time: raw.genesis_time,
             created_at: raw.created_at,
             updated_at: raw.updated_at,
         })
@@ -221,7 +810,7 @@ pub async fn add_chain<'e>(
 ) -> Result<()> {
     let id = id.to_string();
     let node_id = node_id.to_string();
-    let config = Json(config);
+    let config = Json(encryption::seal_json(&id, config)?);
 
     let rows_affected = sqlx::query("INSERT INTO chains (id, node_id, config) VALUES ($1, $2, $3)")
         .bind(id)
@@ -240,6 +829,43 @@ pub async fn add_chain<'e>(
     Ok(())
 }
 
+/// Adds a chain to database with all of its state (sequences, consensus timestamp, connection details, etc.), e.g.
+/// when importing state exported from another solo machine
+pub async fn import_chain<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain: &Chain,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "INSERT INTO chains (id, node_id, config, consensus_timestamp, sequence, packet_sequence, connection_details, generated_identifiers, handshake_progress, genesis_time, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)",
+    )
+    .bind(chain.id.to_string())
+    .bind(chain.node_id.to_string())
+    .bind(Json(encryption::seal_json(
+        &chain.id.to_string(),
+        &chain.config,
+    )?))
+    .bind(chain.consensus_timestamp)
+    .bind(i64::from(chain.sequence))
+    .bind(i64::from(chain.packet_sequence))
+    .bind(chain.connection_details.as_ref().map(Json))
+    .bind(Json(&chain.generated_identifiers))
+    .bind(chain.handshake_progress.as_ref().map(Json))
+    .bind(chain.genesis_time)
+    .bind(chain.created_at)
+    .bind(chain.updated_at)
+    .execute(executor)
+    .await
+    .context("unable to import chain in database")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when importing chain"
+    );
+
+    Ok(())
+}
+
 /// Fetches chain from database
 pub async fn get_chain<'e>(
     executor: impl Executor<'e, Database = Db>,
@@ -254,6 +880,17 @@ pub async fn get_chain<'e>(
         .transpose()
 }
 
+/// Fetches all the chains from database
+pub async fn get_chains<'e>(executor: impl Executor<'e, Database = Db>) -> Result<Vec<Chain>> {
+    sqlx::query_as("SELECT * FROM chains ORDER BY id")
+        .fetch_all(executor)
+        .await
+        .context("unable to query chains from database")?
+        .into_iter()
+        .map(|raw: RawChain| raw.try_into())
+        .collect()
+}
+
 /// Adds connection details for given chain id
 pub async fn add_connection_details<'e>(
     executor: impl Executor<'e, Database = Db>,
@@ -280,20 +917,212 @@ pub async fn add_connection_details<'e>(
     Ok(())
 }
 
-pub async fn increment_sequence<'e>(
+/// Merges newly generated identifiers into identifiers recorded for given chain id and returns the merged identifiers
+pub async fn add_generated_identifiers(
+    transaction: &mut sqlx::Transaction<'_, Db>,
+    chain_id: &ChainId,
+    generated_identifiers: &GeneratedIdentifiers,
+) -> Result<GeneratedIdentifiers> {
+    let chain = get_chain(&mut *transaction, chain_id)
+        .await?
+        .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+    let mut identifiers = chain.generated_identifiers;
+    identifiers.merge(generated_identifiers);
+
+    let rows_affected =
+        sqlx::query("UPDATE chains SET generated_identifiers = $1, updated_at = $2 WHERE id = $3")
+            .bind(Json(&identifiers))
+            .bind(Utc::now())
+            .bind(chain_id.to_string())
+            .execute(&mut *transaction)
+            .await
+            .context("unable to add generated identifiers to chain")?
+            .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when adding generated identifiers to chain"
+    );
+
+    Ok(identifiers)
+}
+
+/// Sets (or clears) progress of connection handshake for given chain id
+pub async fn set_handshake_progress<'e>(
     executor: impl Executor<'e, Database = Db>,
     chain_id: &ChainId,
-) -> Result<Chain> {
-    let raw: RawChain = sqlx::query_as(
-        "UPDATE chains SET sequence = sequence + 1, updated_at = $1 WHERE id = $2 RETURNING *",
+    handshake_progress: Option<&HandshakeProgress>,
+) -> Result<()> {
+    let rows_affected =
+        sqlx::query("UPDATE chains SET handshake_progress = $1, updated_at = $2 WHERE id = $3")
+            .bind(handshake_progress.map(Json))
+            .bind(Utc::now())
+            .bind(chain_id.to_string())
+            .execute(executor)
+            .await
+            .context("unable to set handshake progress of chain")?
+            .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when setting handshake progress of chain"
+    );
+
+    Ok(())
+}
+
+/// Updates configuration of given chain
+pub async fn update_chain_config<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    config: &ChainConfig,
+) -> Result<()> {
+    let config = Json(encryption::seal_json(&chain_id.to_string(), config)?);
+
+    let rows_affected = sqlx::query("UPDATE chains SET config = $1, updated_at = $2 WHERE id = $3")
+        .bind(config)
+        .bind(Utc::now())
+        .bind(chain_id.to_string())
+        .execute(executor)
+        .await
+        .context("unable to update chain config")?
+        .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when updating chain config"
+    );
+
+    Ok(())
+}
+
+/// Moves consensus timestamp of solo machine for given chain forward to given timestamp. Returns `false` (without
+/// updating anything) if current consensus timestamp is not older than given timestamp.
+pub async fn advance_consensus_timestamp(
+    transaction: &mut sqlx::Transaction<'_, Db>,
+    chain_id: &ChainId,
+    consensus_timestamp: DateTime<Utc>,
+) -> Result<bool> {
+    let chain = get_chain(&mut *transaction, chain_id)
+        .await?
+        .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+    if chain.consensus_timestamp >= consensus_timestamp {
+        return Ok(false);
+    }
+
+    let rows_affected =
+        sqlx::query("UPDATE chains SET consensus_timestamp = $1, updated_at = $2 WHERE id = $3")
+            .bind(consensus_timestamp)
+            .bind(Utc::now())
+            .bind(chain_id.to_string())
+            .execute(&mut *transaction)
+            .await
+            .context("unable to update consensus timestamp of chain")?
+            .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when updating consensus timestamp of chain"
+    );
+
+    Ok(true)
+}
+
+/// Records genesis time of given chain
+pub async fn set_genesis_time<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    genesis_time: DateTime<Utc>,
+) -> Result<()> {
+    let rows_affected =
+        sqlx::query("UPDATE chains SET genesis_time = $1, updated_at = $2 WHERE id = $3")
+            .bind(genesis_time)
+            .bind(Utc::now())
+            .bind(chain_id.to_string())
+            .execute(executor)
+            .await
+            .context("unable to set genesis time of chain")?
+            .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when setting genesis time of chain"
+    );
+
+    Ok(())
+}
+
+/// Resets IBC state of a chain after the chain itself is reset (e.g. a devnet restarted from a new genesis): clears
+/// connection details, generated identifiers and handshake progress, and replaces node ID, configuration (with new
+/// trusted height and hash) and genesis time. Sequence is never moved backwards (so that no sequence is signed twice)
+/// while packet sequence starts again from 1.
+pub async fn reset_chain_state<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    node_id: &NodeId,
+    config: &ChainConfig,
+    genesis_time: DateTime<Utc>,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "UPDATE chains SET node_id = $1, config = $2, genesis_time = $3, packet_sequence = 1, connection_details = NULL, generated_identifiers = NULL, handshake_progress = NULL, updated_at = $4 WHERE id = $5",
     )
+    .bind(node_id.to_string())
+    .bind(Json(encryption::seal_json(&chain_id.to_string(), config)?))
+    .bind(genesis_time)
     .bind(Utc::now())
     .bind(chain_id.to_string())
-    .fetch_one(executor)
+    .execute(executor)
     .await
-    .context("unable to increment sequence of a chain")?;
+    .context("unable to reset state of chain")?
+    .rows_affected();
 
-    raw.try_into()
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when resetting state of chain"
+    );
+
+    Ok(())
+}
+
+/// Consumes consecutive sequences of a chain starting from `sequence` (one for each of the given signatures, in the
+/// order in which they were signed) in a single statement, so that either all or none of them are consumed. Fails
+/// without changing anything if current sequence of chain is not `sequence` anymore (i.e. signatures were made at
+/// sequences which are out of sync with stored state).
+pub async fn consume_sequences<'e, S>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    sequence: u32,
+    signatures: &[S],
+) -> Result<Chain>
+where
+    S: AsRef<[u8]>,
+{
+    let last_signature = signatures
+        .last()
+        .ok_or_else(|| anyhow!("no signatures to consume sequences of chain {}", chain_id))?;
+
+    let raw: Option<RawChain> = sqlx::query_as(
+        "UPDATE chains SET sequence = sequence + $1, last_signed_sequence = sequence + $1 - 1, last_signature_hash = $2, updated_at = $3 WHERE id = $4 AND sequence = $5 RETURNING *",
+    )
+    .bind(signatures.len() as i64)
+    .bind(hex::encode(Sha256::digest(last_signature.as_ref())))
+    .bind(Utc::now())
+    .bind(chain_id.to_string())
+    .bind(i64::from(sequence))
+    .fetch_optional(executor)
+    .await
+    .context("unable to consume sequences of a chain")?;
+
+    raw.ok_or_else(|| {
+        anyhow!(
+            "sequence of chain {} changed while signing at sequence {} (signatures are discarded)",
+            chain_id,
+            sequence
+        )
+    })?
+    .try_into()
 }
 
 pub async fn increment_packet_sequence<'e>(
@@ -311,3 +1140,112 @@ pub async fn increment_packet_sequence<'e>(
 
     raw.try_into()
 }
+
+/// Restores sequences of a chain to the ones before signing a transaction which failed on chain (so that sequences of
+/// solo machine stay in sync with the client on chain). Record of last signed sequence is cleared.
+pub async fn restore_sequences<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    sequence: u32,
+    packet_sequence: u32,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "UPDATE chains SET sequence = $1, packet_sequence = $2, last_signed_sequence = NULL, last_signature_hash = NULL, updated_at = $3 WHERE id = $4",
+    )
+    .bind(i64::from(sequence))
+    .bind(i64::from(packet_sequence))
+    .bind(Utc::now())
+    .bind(chain_id.to_string())
+    .execute(executor)
+    .await
+    .context("unable to restore sequences of chain")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when restoring sequences of chain"
+    );
+
+    Ok(())
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use super::*;
+    use crate::test_util::{add_chain, db_pool};
+
+    #[tokio::test]
+    async fn account_addresses_with_other_prefix_are_not_resolved() {
+        let db_pool = db_pool().await;
+        let chain_id = add_chain(&db_pool, "test-1").await;
+
+        let mut chain = get_chain(&db_pool, &chain_id).await.unwrap().unwrap();
+        // unreachable name resolver (addresses should be rejected without connecting to it)
+        chain.config.name_resolver = Some(NameResolverConfig::Icns {
+            grpc_addr: "http://0.0.0.0:1".to_string(),
+            contract_address: "cosmos1resolver".to_string(),
+        });
+
+        let address = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu";
+
+        assert_eq!(
+            chain
+                .resolve_account_address(address, "cosmos")
+                .await
+                .unwrap(),
+            address
+        );
+
+        let error = chain
+            .resolve_account_address(address, "osmo")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("has prefix `cosmos`"));
+    }
+
+    #[cfg(feature = "ethermint")]
+    #[tokio::test]
+    async fn account_addresses_are_derived_using_address_algorithm_of_chain() {
+        struct Key(PublicKey);
+
+        impl ToPublicKey for Key {
+            fn to_public_key(&self) -> Result<PublicKey> {
+                Ok(self.0.clone())
+            }
+
+            fn get_account_prefix(&self) -> &str {
+                "cosmos"
+            }
+
+            fn to_account_address(&self) -> Result<String> {
+                self.0.account_address("cosmos")
+            }
+        }
+
+        let db_pool = db_pool().await;
+        let chain_id = add_chain(&db_pool, "test-1").await;
+        let mut chain = get_chain(&db_pool, &chain_id).await.unwrap().unwrap();
+
+        let verifying_key = k256::ecdsa::SigningKey::from_bytes(&[1; 32])
+            .unwrap()
+            .verifying_key();
+        let key = Key(PublicKey::Secp256k1(verifying_key));
+
+        assert_eq!(
+            chain.account_address(&key).unwrap(),
+            key.to_account_address().unwrap()
+        );
+
+        chain.config.address_algo = AddressAlgo::EthSecp256k1;
+
+        let eth_address = PublicKey::EthSecp256k1(verifying_key)
+            .account_address("cosmos")
+            .unwrap();
+        assert_eq!(chain.account_address(&key).unwrap(), eth_address);
+        assert_ne!(eth_address, key.to_account_address().unwrap());
+        assert!(matches!(
+            chain.account_public_key(&key).unwrap(),
+            PublicKey::EthSecp256k1(_)
+        ));
+    }
+}
@@ -77,6 +77,22 @@ pub async fn add_chain_key<'e>(
     Ok(())
 }
 
+/// Removes latest chain key of a chain (e.g. when transaction updating signer of solo machine failed on chain)
+pub async fn remove_latest_chain_key<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<()> {
+    sqlx::query(
+        "DELETE FROM chain_keys WHERE id = (SELECT MAX(id) FROM chain_keys WHERE chain_id = $1)",
+    )
+    .bind(chain_id.to_string())
+    .execute(executor)
+    .await
+    .context("unable to remove latest chain key")?;
+
+    Ok(())
+}
+
 pub async fn get_chain_keys<'e>(
     executor: impl Executor<'e, Database = Db>,
     chain_id: &ChainId,
@@ -0,0 +1,256 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow};
+
+use crate::{ibc::core::ics24_host::identifier::ChainId, Db};
+
+/// Kind of entity a label is assigned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LabelKind {
+    /// IBC enabled chain
+    Chain,
+    /// IBC connection on IBC enabled chain
+    Connection,
+    /// IBC channel on IBC enabled chain
+    Channel,
+}
+
+impl fmt::Display for LabelKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Chain => write!(f, "chain"),
+            Self::Connection => write!(f, "connection"),
+            Self::Channel => write!(f, "channel"),
+        }
+    }
+}
+
+impl FromStr for LabelKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "chain" => Ok(Self::Chain),
+            "connection" => Ok(Self::Connection),
+            "channel" => Ok(Self::Channel),
+            _ => Err(anyhow!("invalid label kind: {}", s)),
+        }
+    }
+}
+
+/// User-assigned label (stored locally) for a chain, connection or channel
+#[derive(Debug)]
+pub struct ChainLabel {
+    /// ID of label entry
+    pub id: i64,
+    /// Chain ID
+    pub chain_id: ChainId,
+    /// Kind of labelled entity
+    pub kind: LabelKind,
+    /// Identifier of labelled entity (chain ID, connection ID or channel ID)
+    pub target: String,
+    /// Label
+    pub label: String,
+    /// Time at which label was assigned
+    pub created_at: DateTime<Utc>,
+    /// Time at which label was removed or reassigned (`None` if label is still active)
+    pub removed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw label entry for a chain, connection or channel
+struct RawChainLabel {
+    /// ID of label entry
+    pub id: i64,
+    /// Chain ID
+    pub chain_id: String,
+    /// Kind of labelled entity
+    pub kind: String,
+    /// Identifier of labelled entity
+    pub target: String,
+    /// Label
+    pub label: String,
+    /// Time at which label was assigned
+    pub created_at: DateTime<Utc>,
+    /// Time at which label was removed or reassigned
+    pub removed_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<RawChainLabel> for ChainLabel {
+    type Error = Error;
+
+    fn try_from(raw: RawChainLabel) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: raw.id,
+            chain_id: raw.chain_id.parse()?,
+            kind: raw.kind.parse()?,
+            target: raw.target,
+            label: raw.label,
+            created_at: raw.created_at,
+            removed_at: raw.removed_at,
+        })
+    }
+}
+
+/// Adds a new active label for given entity. Any active label with same name (chain labels are unique globally,
+/// connection and channel labels are unique per chain) or for same entity is marked as removed so that the history
+/// of labels is preserved.
+pub async fn add_chain_label(
+    transaction: &mut sqlx::Transaction<'_, Db>,
+    chain_id: &ChainId,
+    kind: LabelKind,
+    target: &str,
+    label: &str,
+) -> Result<()> {
+    let now = Utc::now();
+
+    let query = match kind {
+        LabelKind::Chain => "UPDATE chain_labels SET removed_at = $1 WHERE removed_at IS NULL AND kind = $2 AND (label = $3 OR (chain_id = $4 AND target = $5))",
+        LabelKind::Connection | LabelKind::Channel => "UPDATE chain_labels SET removed_at = $1 WHERE removed_at IS NULL AND kind = $2 AND ((label = $3 AND chain_id = $4) OR (chain_id = $4 AND target = $5))",
+    };
+
+    sqlx::query(query)
+        .bind(now)
+        .bind(kind.to_string())
+        .bind(label)
+        .bind(chain_id.to_string())
+        .bind(target)
+        .execute(&mut *transaction)
+        .await
+        .context("unable to remove existing labels")?;
+
+    let rows_affected = sqlx::query(
+        "INSERT INTO chain_labels (chain_id, kind, target, label, created_at) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(chain_id.to_string())
+    .bind(kind.to_string())
+    .bind(target)
+    .bind(label)
+    .bind(now)
+    .execute(&mut *transaction)
+    .await
+    .context("unable to add new label")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when adding new label"
+    );
+
+    Ok(())
+}
+
+/// Marks an active label as removed (returns removed label)
+pub async fn remove_chain_label<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    kind: LabelKind,
+    label: &str,
+) -> Result<ChainLabel> {
+    let raw: Option<RawChainLabel> = sqlx::query_as(
+        "UPDATE chain_labels SET removed_at = $1 WHERE removed_at IS NULL AND chain_id = $2 AND kind = $3 AND label = $4 RETURNING *",
+    )
+    .bind(Utc::now())
+    .bind(chain_id.to_string())
+    .bind(kind.to_string())
+    .bind(label)
+    .fetch_optional(executor)
+    .await
+    .context("unable to remove label")?;
+
+    raw.ok_or_else(|| anyhow!("{} label `{}` not found", kind, label))?
+        .try_into()
+}
+
+/// Marks all the active connection and channel labels of given chain as removed (returns removed labels)
+pub async fn remove_ibc_labels<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<Vec<ChainLabel>> {
+    let raw_labels: Vec<RawChainLabel> = sqlx::query_as(
+        "UPDATE chain_labels SET removed_at = $1 WHERE removed_at IS NULL AND chain_id = $2 AND kind IN ($3, $4) RETURNING *",
+    )
+    .bind(Utc::now())
+    .bind(chain_id.to_string())
+    .bind(LabelKind::Connection.to_string())
+    .bind(LabelKind::Channel.to_string())
+    .fetch_all(executor)
+    .await
+    .context("unable to remove connection and channel labels")?;
+
+    raw_labels.into_iter().map(TryFrom::try_from).collect()
+}
+
+/// Fetches active label with given name (`chain_id` is ignored for chain labels)
+pub async fn get_chain_label_by_name<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: Option<&ChainId>,
+    kind: LabelKind,
+    label: &str,
+) -> Result<Option<ChainLabel>> {
+    let raw: Option<RawChainLabel> = match chain_id {
+        Some(chain_id) if kind != LabelKind::Chain => sqlx::query_as(
+            "SELECT * FROM chain_labels WHERE removed_at IS NULL AND kind = $1 AND label = $2 AND chain_id = $3",
+        )
+        .bind(kind.to_string())
+        .bind(label)
+        .bind(chain_id.to_string())
+        .fetch_optional(executor)
+        .await,
+        _ => sqlx::query_as(
+            "SELECT * FROM chain_labels WHERE removed_at IS NULL AND kind = $1 AND label = $2",
+        )
+        .bind(kind.to_string())
+        .bind(label)
+        .fetch_optional(executor)
+        .await,
+    }
+    .context("unable to query label from database")?;
+
+    raw.map(TryFrom::try_from).transpose()
+}
+
+/// Fetches active label of given entity
+pub async fn get_chain_label_by_target<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    kind: LabelKind,
+    target: &str,
+) -> Result<Option<ChainLabel>> {
+    let raw: Option<RawChainLabel> = sqlx::query_as(
+        "SELECT * FROM chain_labels WHERE removed_at IS NULL AND chain_id = $1 AND kind = $2 AND target = $3",
+    )
+    .bind(chain_id.to_string())
+    .bind(kind.to_string())
+    .bind(target)
+    .fetch_optional(executor)
+    .await
+    .context("unable to query label from database")?;
+
+    raw.map(TryFrom::try_from).transpose()
+}
+
+/// Fetches all the labels (including removed ones) in reverse chronological order
+pub async fn get_chain_labels<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<ChainLabel>> {
+    let chain_labels: Vec<RawChainLabel> =
+        sqlx::query_as("SELECT * FROM chain_labels ORDER BY id DESC LIMIT $1 OFFSET $2")
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(executor)
+            .await
+            .context("unable to query labels from database")?;
+
+    chain_labels.into_iter().map(TryFrom::try_from).collect()
+}
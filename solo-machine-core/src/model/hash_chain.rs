@@ -0,0 +1,59 @@
+//! Hashing of entries of hash-chained logs (audit log and signature log)
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+/// Hash used as `prev_hash` of the first entry of a hash-chained log
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Computes hash (in hex) of an entry of a hash-chained log from hash of previous entry, time at which entry was added
+/// and fields of entry. Every field is prefixed with its length so that boundaries between fields are unambiguous.
+pub fn compute_hash(prev_hash: &str, created_at: &DateTime<Utc>, fields: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+
+    update_field(&mut hasher, prev_hash.as_bytes());
+    update_field(&mut hasher, &created_at.timestamp().to_be_bytes());
+
+    for field in fields {
+        update_field(&mut hasher, field.as_bytes());
+    }
+
+    hex::encode(hasher.finalize())
+}
+
+fn update_field(hasher: &mut Sha256, field: &[u8]) {
+    hasher.update((field.len() as u64).to_be_bytes());
+    hasher.update(field);
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn field_boundaries_are_part_of_hash() {
+        let created_at = Utc.timestamp(1_600_000_000, 0);
+
+        assert_ne!(
+            compute_hash(GENESIS_HASH, &created_at, &["ab", "c"]),
+            compute_hash(GENESIS_HASH, &created_at, &["a", "bc"])
+        );
+        assert_ne!(
+            compute_hash(GENESIS_HASH, &created_at, &["abc"]),
+            compute_hash(GENESIS_HASH, &created_at, &["abc", ""])
+        );
+    }
+
+    #[test]
+    fn hash_depends_on_previous_hash_and_time() {
+        let created_at = Utc.timestamp(1_600_000_000, 0);
+        let hash = compute_hash(GENESIS_HASH, &created_at, &["event"]);
+
+        assert_ne!(hash, compute_hash(&"1".repeat(64), &created_at, &["event"]));
+        assert_ne!(
+            hash,
+            compute_hash(GENESIS_HASH, &Utc.timestamp(1_600_000_001, 0), &["event"])
+        );
+    }
+}
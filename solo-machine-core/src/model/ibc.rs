@@ -6,7 +6,11 @@ use cosmos_sdk_proto::ibc::{
     },
 };
 use prost::Message;
-use sqlx::{Executor, FromRow};
+#[cfg(feature = "postgres")]
+use sqlx::PgConnection as DbConnection;
+#[cfg(not(feature = "postgres"))]
+use sqlx::SqliteConnection as DbConnection;
+use sqlx::{Executor, FromRow, Transaction};
 
 use crate::{
     ibc::core::ics24_host::{
@@ -14,9 +18,35 @@ use crate::{
         path::{ChannelPath, ClientStatePath, ConnectionPath, ConsensusStatePath},
     },
     proto::proto_encode,
-    Db,
+    storage::{cache, encryption},
+    Db, DbPool,
 };
 
+/// Executor of reads of IBC data, which tells whether decoded IBC data read using it can be cached
+pub(crate) trait IbcExecutor<'e>: Executor<'e, Database = Db> {
+    /// Returns `true` if reads only see committed data (reads using a connection may see writes of an uncommitted
+    /// transaction, which must not be cached as the transaction may be rolled back)
+    fn reads_committed(&self) -> bool;
+}
+
+impl<'e> IbcExecutor<'e> for &DbPool {
+    fn reads_committed(&self) -> bool {
+        true
+    }
+}
+
+impl<'e> IbcExecutor<'e> for &'e mut DbConnection {
+    fn reads_committed(&self) -> bool {
+        false
+    }
+}
+
+impl<'e> IbcExecutor<'e> for &'e mut Transaction<'_, Db> {
+    fn reads_committed(&self) -> bool {
+        false
+    }
+}
+
 #[derive(Debug, FromRow)]
 struct IbcData {
     path: String,
@@ -37,13 +67,25 @@ pub async fn add_tendermint_client_state<'e>(
 
 /// Fetches tendermint client state from database
 pub async fn get_tendermint_client_state<'e>(
-    executor: impl Executor<'e, Database = Db>,
+    executor: impl IbcExecutor<'e>,
     client_id: &ClientId,
 ) -> Result<Option<TendermintClientState>> {
     let path: String = ClientStatePath::new(client_id).into();
     get(executor, &path).await
 }
 
+/// Updates tendermint client state in database
+pub async fn update_tendermint_client_state<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    client_id: &ClientId,
+    client_state: &TendermintClientState,
+) -> Result<()> {
+    let path: String = ClientStatePath::new(client_id).into();
+    let data = proto_encode(client_state)?;
+
+    update(executor, &path, &data).await
+}
+
 /// Adds tendermint consensus state to database
 pub async fn add_tendermint_consensus_state<'e>(
     executor: impl Executor<'e, Database = Db>,
@@ -59,7 +101,7 @@ pub async fn add_tendermint_consensus_state<'e>(
 
 /// Fetches tendermint consensus state from database
 pub async fn get_tendermint_consensus_state<'e>(
-    executor: impl Executor<'e, Database = Db>,
+    executor: impl IbcExecutor<'e>,
     client_id: &ClientId,
     height: &Height,
 ) -> Result<Option<TendermintConsensusState>> {
@@ -81,7 +123,7 @@ pub async fn add_connection<'e>(
 
 /// Fetches connection from database
 pub async fn get_connection<'e>(
-    executor: impl Executor<'e, Database = Db>,
+    executor: impl IbcExecutor<'e>,
     connection_id: &ConnectionId,
 ) -> Result<Option<ConnectionEnd>> {
     let path: String = ConnectionPath::new(connection_id).into();
@@ -115,7 +157,7 @@ pub async fn add_channel<'e>(
 
 /// Fetches channel from database
 pub async fn get_channel<'e>(
-    executor: impl Executor<'e, Database = Db>,
+    executor: impl IbcExecutor<'e>,
     port_id: &PortId,
     channel_id: &ChannelId,
 ) -> Result<Option<Channel>> {
@@ -136,6 +178,74 @@ pub async fn update_channel<'e>(
     update(executor, &path, &data).await
 }
 
+/// Fetches all the IBC data (encoded client states, consensus states, connections and channels) from database along
+/// with their paths (ordered by path)
+pub async fn get_all_ibc_data<'e>(
+    executor: impl Executor<'e, Database = Db>,
+) -> Result<Vec<(String, Vec<u8>)>> {
+    let ibc_data: Vec<(String, Vec<u8>)> =
+        sqlx::query_as("SELECT path, data FROM ibc_data ORDER BY path")
+            .fetch_all(executor)
+            .await
+            .context("unable to query ibc data from database")?;
+
+    ibc_data
+        .into_iter()
+        .map(|(path, data)| {
+            let data = encryption::open_bytes(&path, data)?;
+            Ok((path, data))
+        })
+        .collect()
+}
+
+/// Adds encoded IBC data at given path to database (e.g. when importing state exported from another solo machine)
+pub async fn add_raw_ibc_data<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    path: &str,
+    data: &[u8],
+) -> Result<()> {
+    add(executor, path, data).await
+}
+
+/// Removes tendermint client state and all the consensus states of given client from database
+pub async fn remove_tendermint_client<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    client_id: &ClientId,
+) -> Result<()> {
+    let path: String = ClientStatePath::new(client_id).into();
+
+    sqlx::query("DELETE FROM ibc_data WHERE path = $1 OR path LIKE $2")
+        .bind(&path)
+        .bind(format!("clients/{}/consensusStates/%", client_id))
+        .execute(executor)
+        .await
+        .context("unable to remove tendermint client from database")?;
+
+    cache::invalidate(&path);
+    cache::invalidate_prefix(&format!("clients/{}/consensusStates/", client_id));
+
+    Ok(())
+}
+
+/// Removes connection from database
+pub async fn remove_connection<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    connection_id: &ConnectionId,
+) -> Result<()> {
+    let path: String = ConnectionPath::new(connection_id).into();
+    remove(executor, &path).await
+}
+
+/// Removes channel from database
+pub async fn remove_channel<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<()> {
+    let path: String = ChannelPath::new(port_id, channel_id).into();
+    remove(executor, &path).await
+}
+
 async fn add<'e>(
     executor: impl Executor<'e, Database = Db>,
     path: &str,
@@ -143,12 +253,14 @@ async fn add<'e>(
 ) -> Result<()> {
     let rows_affected = sqlx::query("INSERT INTO ibc_data (path, data) VALUES ($1, $2)")
         .bind(path)
-        .bind(data)
+        .bind(encryption::seal_bytes(path, data.to_vec())?)
         .execute(executor)
         .await
         .context("unable to add ibc data in database")?
         .rows_affected();
 
+    cache::invalidate(path);
+
     ensure!(
         rows_affected == 1,
         "rows_affected should be equal to 1 when adding a new ibc data"
@@ -163,13 +275,15 @@ async fn update<'e>(
     data: &[u8],
 ) -> Result<()> {
     let rows_affected = sqlx::query("UPDATE ibc_data SET data = $1 where path = $2")
-        .bind(data)
+        .bind(encryption::seal_bytes(path, data.to_vec())?)
         .bind(path)
         .execute(executor)
         .await
         .context("unable to update ibc data in database")?
         .rows_affected();
 
+    cache::invalidate(path);
+
     ensure!(
         rows_affected == 1,
         "rows_affected should be equal to 1 when updating ibc data"
@@ -178,15 +292,112 @@ async fn update<'e>(
     Ok(())
 }
 
-async fn get<'e, M>(executor: impl Executor<'e, Database = Db>, path: &str) -> Result<Option<M>>
+async fn remove<'e>(executor: impl Executor<'e, Database = Db>, path: &str) -> Result<()> {
+    sqlx::query("DELETE FROM ibc_data WHERE path = $1")
+        .bind(path)
+        .execute(executor)
+        .await
+        .context("unable to remove ibc data from database")?;
+
+    cache::invalidate(path);
+
+    Ok(())
+}
+
+async fn get<'e, M>(executor: impl IbcExecutor<'e>, path: &str) -> Result<Option<M>>
 where
-    M: Message + Default,
+    M: Message + Default + Clone + 'static,
 {
-    sqlx::query_as("SELECT * FROM ibc_data WHERE path = $1")
+    if let Some(value) = cache::get(path) {
+        return Ok(Some(value));
+    }
+
+    let reads_committed = executor.reads_committed();
+
+    let ibc_data: Option<IbcData> = sqlx::query_as("SELECT * FROM ibc_data WHERE path = $1")
         .bind(path)
         .fetch_optional(executor)
-        .await?
-        .map(|ibc_data: IbcData| M::decode(ibc_data.data.as_ref()))
-        .transpose()
-        .context("unable to decode protobuf bytes for ibc data")
+        .await?;
+
+    let value = ibc_data
+        .map(|ibc_data| {
+            let data = encryption::open_bytes(&ibc_data.path, ibc_data.data)?;
+            M::decode(data.as_slice()).context("unable to decode protobuf bytes for ibc data")
+        })
+        .transpose()?;
+
+    if reads_committed {
+        if let Some(ref value) = value {
+            cache::insert(path, value);
+        }
+    }
+
+    Ok(value)
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use super::*;
+    use crate::test_util::db_pool;
+
+    #[tokio::test]
+    async fn reads_of_rolled_back_writes_are_not_cached() {
+        let db_pool = db_pool().await;
+        let connection_id: ConnectionId = "connection-326".parse().unwrap();
+        let connection = ConnectionEnd {
+            client_id: "07-tendermint-0".to_string(),
+            ..Default::default()
+        };
+
+        let mut transaction = db_pool.begin().await.unwrap();
+
+        add_connection(&mut *transaction, &connection_id, &connection)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_connection(&mut *transaction, &connection_id)
+                .await
+                .unwrap(),
+            Some(connection.clone())
+        );
+
+        transaction.rollback().await.unwrap();
+
+        assert_eq!(
+            get_connection(&db_pool, &connection_id).await.unwrap(),
+            None
+        );
+
+        add_connection(&db_pool, &connection_id, &connection)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_connection(&db_pool, &connection_id).await.unwrap(),
+            Some(connection.clone())
+        );
+
+        let updated_connection = ConnectionEnd {
+            state: 3,
+            ..connection.clone()
+        };
+
+        let mut transaction = db_pool.begin().await.unwrap();
+
+        update_connection(&mut *transaction, &connection_id, &updated_connection)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_connection(&mut *transaction, &connection_id)
+                .await
+                .unwrap(),
+            Some(updated_connection)
+        );
+
+        transaction.rollback().await.unwrap();
+
+        assert_eq!(
+            get_connection(&db_pool, &connection_id).await.unwrap(),
+            Some(connection)
+        );
+    }
 }
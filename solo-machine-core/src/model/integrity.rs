@@ -0,0 +1,148 @@
+use std::{convert::TryFrom, fmt};
+
+use anyhow::{anyhow, Context, Error, Result};
+use chrono::Utc;
+use sqlx::{Executor, FromRow};
+
+use crate::{ibc::core::ics24_host::identifier::ChainId, Db};
+
+/// Inconsistency found in stored state of solo machine
+#[derive(Debug, Clone)]
+pub struct IntegrityIssue {
+    /// Chain ID of inconsistent chain state (`None` for inconsistencies in audit log)
+    pub chain_id: Option<ChainId>,
+    /// Description of inconsistency
+    pub description: String,
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.chain_id {
+            Some(ref chain_id) => write!(f, "[{}] {}", chain_id, self.description),
+            None => write!(f, "[audit-log] {}", self.description),
+        }
+    }
+}
+
+/// Sequence counters of a chain along with last signed sequence
+#[derive(Debug)]
+struct SequenceRecord {
+    chain_id: ChainId,
+    sequence: u64,
+    packet_sequence: u64,
+    last_signed_sequence: Option<u64>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw sequence counters of a chain
+struct RawSequenceRecord {
+    id: String,
+    sequence: i64,
+    packet_sequence: i64,
+    last_signed_sequence: Option<i64>,
+}
+
+impl TryFrom<RawSequenceRecord> for SequenceRecord {
+    type Error = Error;
+
+    fn try_from(raw: RawSequenceRecord) -> Result<Self, Self::Error> {
+        let chain_id = raw.id.parse()?;
+        let to_u64 = |value: i64, name: &str| {
+            u64::try_from(value).map_err(|_| anyhow!("negative {} of chain {}", name, chain_id))
+        };
+
+        Ok(Self {
+            sequence: to_u64(raw.sequence, "sequence")?,
+            packet_sequence: to_u64(raw.packet_sequence, "packet sequence")?,
+            last_signed_sequence: raw
+                .last_signed_sequence
+                .map(|sequence| to_u64(sequence, "last signed sequence"))
+                .transpose()?,
+            chain_id,
+        })
+    }
+}
+
+impl SequenceRecord {
+    fn issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        if self.sequence == 0 {
+            issues.push("sequence should be greater than 0".to_string());
+        }
+
+        if self.packet_sequence == 0 {
+            issues.push("packet sequence should be greater than 0".to_string());
+        }
+
+        if let Some(last_signed_sequence) = self.last_signed_sequence {
+            if self.sequence != last_signed_sequence + 1 {
+                issues.push(format!(
+                    "sequence ({}) is not consistent with last signed sequence ({})",
+                    self.sequence, last_signed_sequence
+                ));
+            }
+        }
+
+        issues
+    }
+}
+
+/// Verifies consistency of stored sequence counters of all the chains and returns all the inconsistencies found
+pub async fn verify_integrity<'e>(
+    executor: impl Executor<'e, Database = Db>,
+) -> Result<Vec<IntegrityIssue>> {
+    let raw_records: Vec<RawSequenceRecord> = sqlx::query_as(
+        "SELECT id, sequence, packet_sequence, last_signed_sequence FROM chains ORDER BY id",
+    )
+    .fetch_all(executor)
+    .await
+    .context("unable to query sequence counters of chains from database")?;
+
+    let mut issues = Vec::new();
+
+    for raw_record in raw_records {
+        let chain_id = raw_record.id.clone();
+
+        match SequenceRecord::try_from(raw_record) {
+            Ok(record) => {
+                issues.extend(
+                    record
+                        .issues()
+                        .into_iter()
+                        .map(|description| IntegrityIssue {
+                            chain_id: Some(record.chain_id.clone()),
+                            description,
+                        }),
+                )
+            }
+            Err(err) => issues.push(IntegrityIssue {
+                chain_id: Some(chain_id.parse()?),
+                description: err.to_string(),
+            }),
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Repairs sequence counters of a chain. Sequence is never moved backwards (so that no sequence is signed twice) and
+/// record of last signed sequence is cleared. Returns the repaired sequence.
+pub async fn repair_chain<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<u64> {
+    let sequence: Option<(i64,)> = sqlx::query_as(
+        "UPDATE chains SET sequence = CASE WHEN last_signed_sequence IS NOT NULL AND last_signed_sequence >= sequence THEN last_signed_sequence + 1 WHEN sequence < 1 THEN 1 ELSE sequence END, packet_sequence = CASE WHEN packet_sequence < 1 THEN 1 ELSE packet_sequence END, last_signed_sequence = NULL, last_signature_hash = NULL, updated_at = $1 WHERE id = $2 RETURNING sequence",
+    )
+    .bind(Utc::now())
+    .bind(chain_id.to_string())
+    .fetch_optional(executor)
+    .await
+    .context("unable to repair sequence counters of chain")?;
+
+    let (sequence,) =
+        sequence.ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+    u64::try_from(sequence).context("invalid repaired sequence")
+}
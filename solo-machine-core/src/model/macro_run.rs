@@ -0,0 +1,313 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    str::FromStr,
+    time::Duration,
+};
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, Executor, FromRow};
+
+use crate::{model::ChainConfig, Db};
+
+/// A single step in a macro: an operation along with its retry settings. All the fields can contain `${param}`
+/// placeholders which are substituted with macro parameters before execution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacroStep {
+    /// Operation executed by step
+    #[serde(flatten)]
+    pub operation: MacroOperation,
+    /// Number of times step is retried when it fails (before failing macro run)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub retries: u32,
+    /// Delay between retries of step (e.g. `10 sec`, defaults to `5 sec`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_delay: Option<String>,
+}
+
+impl MacroStep {
+    /// Returns delay between retries of step
+    pub fn retry_delay(&self) -> Result<Duration> {
+        let retry_delay = self.retry_delay.as_deref().unwrap_or("5 sec");
+        humantime::parse_duration(retry_delay)
+            .with_context(|| format!("invalid retry delay: {}", retry_delay))
+    }
+}
+
+/// An operation executed by a macro step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "kebab-case")]
+pub enum MacroOperation {
+    /// Adds an IBC enabled chain (skipped if chain is already added)
+    AddChain {
+        /// Chain ID of IBC enabled chain
+        chain_id: String,
+        /// Configuration of chain
+        config: Box<ChainConfig>,
+    },
+    /// Creates solo machine client on IBC enabled chain and tendermint client on solo machine (skipped if clients are
+    /// already created, connection handshake can then be completed using `connect`)
+    CreateClient {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+    },
+    /// Establishes connection with an IBC enabled chain (resuming an interrupted handshake, skipped if connection is
+    /// already established unless `force` is set)
+    #[serde(alias = "handshake")]
+    Connect {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Force create a new connection even if one already exists
+        #[serde(default)]
+        force: bool,
+    },
+    /// Mint some tokens on IBC enabled chain
+    Mint {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Amount to send to IBC enabled chain
+        amount: String,
+        /// Denom of tokens to send to IBC enabled chain
+        denom: String,
+        /// Optional receiver address or name (tokens are sent to signer's address if not provided)
+        #[serde(default)]
+        receiver: Option<String>,
+    },
+    /// Burn some tokens on IBC enabled chain
+    Burn {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Amount to receive from IBC enabled chain
+        amount: String,
+        /// Denom of tokens to receive from IBC enabled chain
+        denom: String,
+    },
+    /// Exports history of operations of signer to a JSON file
+    ExportHistory {
+        /// Path of JSON file
+        path: String,
+        /// Maximum number of operations to export
+        #[serde(default = "default_export_limit")]
+        limit: u32,
+    },
+}
+
+impl MacroOperation {
+    /// Returns name of operation (as used in macro definitions)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::AddChain { .. } => "add-chain",
+            Self::CreateClient { .. } => "create-client",
+            Self::Connect { .. } => "connect",
+            Self::Mint { .. } => "mint",
+            Self::Burn { .. } => "burn",
+            Self::ExportHistory { .. } => "export-history",
+        }
+    }
+
+    /// Returns chain ID (or label) of IBC enabled chain on which operation is executed
+    pub fn chain_id(&self) -> Option<&str> {
+        match self {
+            Self::AddChain { chain_id, .. }
+            | Self::CreateClient { chain_id }
+            | Self::Connect { chain_id, .. }
+            | Self::Mint { chain_id, .. }
+            | Self::Burn { chain_id, .. } => Some(chain_id),
+            Self::ExportHistory { .. } => None,
+        }
+    }
+}
+
+fn default_export_limit() -> u32 {
+    100
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+/// Journal entry of a successfully executed macro step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacroStepResult {
+    /// Index of step in macro
+    pub step: usize,
+    /// Output of step (e.g. transaction hash)
+    pub output: String,
+    /// Time at which step was completed
+    pub completed_at: DateTime<Utc>,
+}
+
+/// Status of a macro run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacroRunStatus {
+    /// Macro is being executed
+    Running,
+    /// All the steps of macro are executed successfully
+    Completed,
+    /// A step of macro failed (the run can be resumed from failed step)
+    Failed,
+}
+
+impl fmt::Display for MacroRunStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Running => write!(f, "running"),
+            Self::Completed => write!(f, "completed"),
+            Self::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl FromStr for MacroRunStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(Self::Running),
+            "completed" => Ok(Self::Completed),
+            "failed" => Ok(Self::Failed),
+            _ => Err(anyhow!("invalid macro run status: {}", s)),
+        }
+    }
+}
+
+/// Journaled execution of a macro
+#[derive(Debug)]
+pub struct MacroRun {
+    /// ID of macro run
+    pub id: i64,
+    /// Name of macro
+    pub name: String,
+    /// Steps of macro (with all the parameters substituted)
+    pub steps: Vec<MacroStep>,
+    /// Results of all the completed steps
+    pub journal: Vec<MacroStepResult>,
+    /// Status of macro run
+    pub status: MacroRunStatus,
+    /// Error message of failed step
+    pub error: Option<String>,
+    /// Time at which macro run was started
+    pub created_at: DateTime<Utc>,
+    /// Time at which macro run was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw journaled execution of a macro
+struct RawMacroRun {
+    /// ID of macro run
+    pub id: i64,
+    /// Name of macro
+    pub name: String,
+    /// Steps of macro
+    pub steps: Json<Vec<MacroStep>>,
+    /// Results of all the completed steps
+    pub journal: Json<Vec<MacroStepResult>>,
+    /// Status of macro run
+    pub status: String,
+    /// Error message of failed step
+    pub error: Option<String>,
+    /// Time at which macro run was started
+    pub created_at: DateTime<Utc>,
+    /// Time at which macro run was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<RawMacroRun> for MacroRun {
+    type Error = Error;
+
+    fn try_from(raw: RawMacroRun) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: raw.id,
+            name: raw.name,
+            steps: raw.steps.0,
+            journal: raw.journal.0,
+            status: raw.status.parse()?,
+            error: raw.error,
+            created_at: raw.created_at,
+            updated_at: raw.updated_at,
+        })
+    }
+}
+
+/// Adds a new macro run (in `running` state) to database
+pub async fn add_macro_run<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    name: &str,
+    steps: &[MacroStep],
+) -> Result<MacroRun> {
+    let raw: RawMacroRun = sqlx::query_as(
+        "INSERT INTO macro_runs (name, steps, journal, status) VALUES ($1, $2, $3, $4) RETURNING *",
+    )
+    .bind(name)
+    .bind(Json(steps))
+    .bind(Json(Vec::<MacroStepResult>::new()))
+    .bind(MacroRunStatus::Running.to_string())
+    .fetch_one(executor)
+    .await
+    .context("unable to add new macro run to database")?;
+
+    raw.try_into()
+}
+
+/// Updates journal and status of a macro run
+pub async fn update_macro_run<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+    journal: &[MacroStepResult],
+    status: MacroRunStatus,
+    error: Option<&str>,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "UPDATE macro_runs SET journal = $1, status = $2, error = $3, updated_at = $4 WHERE id = $5",
+    )
+    .bind(Json(journal))
+    .bind(status.to_string())
+    .bind(error)
+    .bind(Utc::now())
+    .bind(id)
+    .execute(executor)
+    .await
+    .context("unable to update macro run")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when updating macro run"
+    );
+
+    Ok(())
+}
+
+/// Fetches a macro run from database
+pub async fn get_macro_run<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+) -> Result<Option<MacroRun>> {
+    sqlx::query_as("SELECT * FROM macro_runs WHERE id = $1")
+        .bind(id)
+        .fetch_optional(executor)
+        .await
+        .context("unable to query macro run from database")?
+        .map(|raw: RawMacroRun| raw.try_into())
+        .transpose()
+}
+
+/// Fetches macro runs from database
+pub async fn get_macro_runs<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<MacroRun>> {
+    let raw: Vec<RawMacroRun> =
+        sqlx::query_as("SELECT * FROM macro_runs ORDER BY id DESC LIMIT $1 OFFSET $2")
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(executor)
+            .await
+            .context("unable to query macro runs from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
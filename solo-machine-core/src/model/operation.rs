@@ -112,6 +112,16 @@ pub enum OperationType {
     },
 }
 
+impl OperationType {
+    /// Returns chain ID of IBC enabled chain associated with operation
+    pub fn chain_id(&self) -> &ChainId {
+        match self {
+            Self::Mint { chain_id } => chain_id,
+            Self::Burn { chain_id } => chain_id,
+        }
+    }
+}
+
 impl fmt::Display for OperationType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
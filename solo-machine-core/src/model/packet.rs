@@ -0,0 +1,270 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use chrono::{DateTime, Utc};
+use cosmos_sdk_proto::ibc::core::channel::v1::Packet;
+use prost::Message;
+use sqlx::{Executor, FromRow};
+
+use crate::{
+    ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId},
+    proto::proto_encode,
+    Db,
+};
+
+/// State of a packet sent to solo machine in its lifecycle (`committed` → `received` → `acknowledged` / `timed-out`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketState {
+    /// Packet is sent on chain (i.e. its commitment is stored on chain) and is yet to be relayed
+    Committed,
+    /// Packet is received by solo machine (i.e. its acknowledgement is signed) but acknowledgement is not yet confirmed
+    /// on chain
+    Received,
+    /// Packet is acknowledged on chain
+    Acknowledged,
+    /// Packet is timed out on chain (i.e. its timeout had passed on solo machine)
+    TimedOut,
+}
+
+impl PacketState {
+    /// Returns `true` if no more work remains for packets in this state
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Acknowledged | Self::TimedOut)
+    }
+}
+
+impl fmt::Display for PacketState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Committed => write!(f, "committed"),
+            Self::Received => write!(f, "received"),
+            Self::Acknowledged => write!(f, "acknowledged"),
+            Self::TimedOut => write!(f, "timed-out"),
+        }
+    }
+}
+
+impl FromStr for PacketState {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "committed" => Ok(Self::Committed),
+            "received" => Ok(Self::Received),
+            "acknowledged" => Ok(Self::Acknowledged),
+            "timed-out" => Ok(Self::TimedOut),
+            _ => Err(anyhow!("invalid packet state: {}", s)),
+        }
+    }
+}
+
+/// A packet sent to solo machine tracked through its lifecycle
+#[derive(Debug)]
+pub struct TrackedPacket {
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+    /// Port on which packet is sent
+    pub port_id: PortId,
+    /// Channel on which packet is sent
+    pub channel_id: ChannelId,
+    /// Sequence of packet
+    pub sequence: u64,
+    /// State of packet
+    pub state: PacketState,
+    /// Block height of chain at which packet was sent (if known)
+    pub height: Option<u64>,
+    /// Packet
+    pub packet: Packet,
+    /// Hash of transaction which acknowledged (or timed out) the packet
+    pub transaction_hash: Option<String>,
+    /// Time at which packet was first tracked
+    pub created_at: DateTime<Utc>,
+    /// Time at which state of packet was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw packet sent to solo machine tracked through its lifecycle
+struct RawTrackedPacket {
+    /// Chain ID of IBC enabled chain
+    pub chain_id: String,
+    /// Port on which packet is sent
+    pub port_id: String,
+    /// Channel on which packet is sent
+    pub channel_id: String,
+    /// Sequence of packet
+    pub sequence: i64,
+    /// State of packet
+    pub state: String,
+    /// Block height of chain at which packet was sent (if known)
+    pub height: Option<i64>,
+    /// Hex encoded packet
+    pub packet: String,
+    /// Hash of transaction which acknowledged (or timed out) the packet
+    pub transaction_hash: Option<String>,
+    /// Time at which packet was first tracked
+    pub created_at: DateTime<Utc>,
+    /// Time at which state of packet was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<RawTrackedPacket> for TrackedPacket {
+    type Error = Error;
+
+    fn try_from(raw: RawTrackedPacket) -> Result<Self, Self::Error> {
+        let packet_bytes = hex::decode(&raw.packet).context("invalid hex encoding of packet")?;
+
+        Ok(Self {
+            chain_id: raw.chain_id.parse()?,
+            port_id: raw.port_id.parse()?,
+            channel_id: raw.channel_id.parse()?,
+            sequence: raw.sequence.try_into()?,
+            state: raw.state.parse()?,
+            height: raw.height.map(TryInto::try_into).transpose()?,
+            packet: Packet::decode(packet_bytes.as_slice()).context("unable to decode packet")?,
+            transaction_hash: raw.transaction_hash,
+            created_at: raw.created_at,
+            updated_at: raw.updated_at,
+        })
+    }
+}
+
+/// Starts tracking a packet sent to solo machine (in `committed` state). Already tracked packets are left unchanged.
+pub async fn track_packet<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    height: Option<u64>,
+    packet: &Packet,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO packets (chain_id, port_id, channel_id, sequence, state, height, packet) VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT (chain_id, port_id, channel_id, sequence) DO NOTHING",
+    )
+    .bind(chain_id.to_string())
+    .bind(&packet.source_port)
+    .bind(&packet.source_channel)
+    .bind(i64::try_from(packet.sequence)?)
+    .bind(PacketState::Committed.to_string())
+    .bind(height.map(i64::try_from).transpose()?)
+    .bind(hex::encode(proto_encode(packet)?))
+    .execute(executor)
+    .await
+    .context("unable to add packet to database")?;
+
+    Ok(())
+}
+
+/// Updates state of a tracked packet (along with hash of transaction which moved the packet to new state)
+pub async fn set_packet_state<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    packet: &Packet,
+    state: PacketState,
+    transaction_hash: Option<&str>,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "UPDATE packets SET state = $1, transaction_hash = $2, updated_at = $3 WHERE chain_id = $4 AND port_id = $5 AND channel_id = $6 AND sequence = $7",
+    )
+    .bind(state.to_string())
+    .bind(transaction_hash)
+    .bind(Utc::now())
+    .bind(chain_id.to_string())
+    .bind(&packet.source_port)
+    .bind(&packet.source_channel)
+    .bind(i64::try_from(packet.sequence)?)
+    .execute(executor)
+    .await
+    .context("unable to update state of packet in database")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when updating state of packet"
+    );
+
+    Ok(())
+}
+
+/// Fetches a tracked packet from database
+pub async fn get_packet<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: u64,
+) -> Result<Option<TrackedPacket>> {
+    sqlx::query_as(
+        "SELECT * FROM packets WHERE chain_id = $1 AND port_id = $2 AND channel_id = $3 AND sequence = $4",
+    )
+    .bind(chain_id.to_string())
+    .bind(port_id.to_string())
+    .bind(channel_id.to_string())
+    .bind(i64::try_from(sequence)?)
+    .fetch_optional(executor)
+    .await
+    .context("unable to query packet from database")?
+    .map(|raw: RawTrackedPacket| raw.try_into())
+    .transpose()
+}
+
+/// Fetches tracked packets of a chain, latest first (optionally, only the ones sent on given channel or in given
+/// state)
+pub async fn get_packets<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    channel_id: Option<&ChannelId>,
+    state: Option<PacketState>,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<TrackedPacket>> {
+    let raw: Vec<RawTrackedPacket> = sqlx::query_as(
+        "SELECT * FROM packets WHERE chain_id = $1 AND ($2 IS NULL OR channel_id = $2) AND ($3 IS NULL OR state = $3) ORDER BY created_at DESC, sequence DESC LIMIT $4 OFFSET $5",
+    )
+    .bind(chain_id.to_string())
+    .bind(channel_id.map(ToString::to_string))
+    .bind(state.map(|state| state.to_string()))
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(executor)
+    .await
+    .context("unable to query packets from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Fetches tracked packets of a chain for which work remains (i.e. packets in `committed` or `received` state), oldest
+/// first
+pub async fn get_pending_packets<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<Vec<TrackedPacket>> {
+    let raw: Vec<RawTrackedPacket> = sqlx::query_as(
+        "SELECT * FROM packets WHERE chain_id = $1 AND state IN ($2, $3) ORDER BY channel_id, sequence",
+    )
+    .bind(chain_id.to_string())
+    .bind(PacketState::Committed.to_string())
+    .bind(PacketState::Received.to_string())
+    .fetch_all(executor)
+    .await
+    .context("unable to query pending packets from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Removes all the tracked packets of a chain (e.g. when chain is reset) and returns number of removed packets
+pub async fn remove_packets<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<u64> {
+    let rows_affected = sqlx::query("DELETE FROM packets WHERE chain_id = $1")
+        .bind(chain_id.to_string())
+        .execute(executor)
+        .await
+        .context("unable to remove packets from database")?
+        .rows_affected();
+
+    Ok(rows_affected)
+}
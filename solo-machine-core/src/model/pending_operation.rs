@@ -0,0 +1,407 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use chrono::{DateTime, Utc};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{Executor, FromRow};
+
+use crate::{ibc::core::ics24_host::identifier::ChainId, proto::proto_encode, Db};
+
+/// Kind of on-chain submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PendingOperationKind {
+    /// Creates solo machine client on chain
+    CreateClient,
+    /// Initializes connection on chain
+    ConnectionOpenInit,
+    /// Acknowledges connection on chain
+    ConnectionOpenAck,
+    /// Initializes channel on chain
+    ChannelOpenInit,
+    /// Acknowledges channel on chain
+    ChannelOpenAck,
+    /// Mints tokens on chain
+    Mint,
+    /// Burns tokens on chain
+    Burn,
+    /// Updates signer of solo machine client on chain
+    UpdateSigner,
+    /// Acknowledges a packet sent to solo machine
+    AcknowledgePacket,
+    /// Times out a packet sent to solo machine
+    TimeoutPacket,
+    /// Grants fee allowance
+    GrantAllowance,
+    /// Revokes fee allowance
+    RevokeAllowance,
+    /// Sends tokens using bank module
+    SendTokens,
+}
+
+impl PendingOperationKind {
+    /// Returns `true` for IBC operations (i.e. operations signed by solo machine signer which may consume sequences of
+    /// solo machine)
+    pub fn is_ibc(&self) -> bool {
+        !matches!(
+            self,
+            Self::GrantAllowance | Self::RevokeAllowance | Self::SendTokens
+        )
+    }
+}
+
+impl fmt::Display for PendingOperationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreateClient => write!(f, "create-client"),
+            Self::ConnectionOpenInit => write!(f, "connection-open-init"),
+            Self::ConnectionOpenAck => write!(f, "connection-open-ack"),
+            Self::ChannelOpenInit => write!(f, "channel-open-init"),
+            Self::ChannelOpenAck => write!(f, "channel-open-ack"),
+            Self::Mint => write!(f, "mint"),
+            Self::Burn => write!(f, "burn"),
+            Self::UpdateSigner => write!(f, "update-signer"),
+            Self::AcknowledgePacket => write!(f, "acknowledge-packet"),
+            Self::TimeoutPacket => write!(f, "timeout-packet"),
+            Self::GrantAllowance => write!(f, "grant-allowance"),
+            Self::RevokeAllowance => write!(f, "revoke-allowance"),
+            Self::SendTokens => write!(f, "send-tokens"),
+        }
+    }
+}
+
+impl FromStr for PendingOperationKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "create-client" => Ok(Self::CreateClient),
+            "connection-open-init" => Ok(Self::ConnectionOpenInit),
+            "connection-open-ack" => Ok(Self::ConnectionOpenAck),
+            "channel-open-init" => Ok(Self::ChannelOpenInit),
+            "channel-open-ack" => Ok(Self::ChannelOpenAck),
+            "mint" => Ok(Self::Mint),
+            "burn" => Ok(Self::Burn),
+            "update-signer" => Ok(Self::UpdateSigner),
+            "acknowledge-packet" => Ok(Self::AcknowledgePacket),
+            "timeout-packet" => Ok(Self::TimeoutPacket),
+            "grant-allowance" => Ok(Self::GrantAllowance),
+            "revoke-allowance" => Ok(Self::RevokeAllowance),
+            "send-tokens" => Ok(Self::SendTokens),
+            _ => Err(anyhow!("invalid pending operation kind: {}", s)),
+        }
+    }
+}
+
+/// Status of an on-chain submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PendingOperationStatus {
+    /// Transaction is signed (and persisted) but its outcome on chain is not known yet
+    Pending,
+    /// Transaction is committed on chain successfully
+    Completed,
+    /// Transaction is rejected by chain before being included in a block (sequences consumed while signing it are
+    /// rolled back)
+    Failed,
+    /// Transaction is included in a block but failed on chain (sequences consumed while signing it stay consumed as
+    /// its signatures are public)
+    Reverted,
+}
+
+impl fmt::Display for PendingOperationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pending => write!(f, "pending"),
+            Self::Completed => write!(f, "completed"),
+            Self::Failed => write!(f, "failed"),
+            Self::Reverted => write!(f, "reverted"),
+        }
+    }
+}
+
+impl FromStr for PendingOperationStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "completed" => Ok(Self::Completed),
+            "failed" => Ok(Self::Failed),
+            "reverted" => Ok(Self::Reverted),
+            _ => Err(anyhow!("invalid pending operation status: {}", s)),
+        }
+    }
+}
+
+/// A signed transaction queued for submission to chain
+#[derive(Debug)]
+pub struct PendingOperation {
+    /// ID of operation
+    pub id: i64,
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+    /// Kind of operation
+    pub kind: PendingOperationKind,
+    /// Request ID for tracking purposes
+    pub request_id: Option<String>,
+    /// Status of operation
+    pub status: PendingOperationStatus,
+    /// Signed transaction
+    pub tx: TxRaw,
+    /// Hash of signed transaction (in hex)
+    pub transaction_hash: String,
+    /// Sequence of solo machine before signing the transaction
+    pub sequence: u32,
+    /// Packet sequence of solo machine before signing the transaction
+    pub packet_sequence: u32,
+    /// Error returned by chain (for failed operations)
+    pub error: Option<String>,
+    /// Time at which operation was queued
+    pub created_at: DateTime<Utc>,
+    /// Time at which operation was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw signed transaction queued for submission to chain
+struct RawPendingOperation {
+    /// ID of operation
+    pub id: i64,
+    /// Chain ID of IBC enabled chain
+    pub chain_id: String,
+    /// Kind of operation
+    pub kind: String,
+    /// Request ID for tracking purposes
+    pub request_id: Option<String>,
+    /// Status of operation
+    pub status: String,
+    /// Hex encoded signed transaction
+    pub tx: String,
+    /// Hash of signed transaction (in hex)
+    pub transaction_hash: String,
+    /// Sequence of solo machine before signing the transaction
+    pub sequence: i64,
+    /// Packet sequence of solo machine before signing the transaction
+    pub packet_sequence: i64,
+    /// Error returned by chain (for failed operations)
+    pub error: Option<String>,
+    /// Time at which operation was queued
+    pub created_at: DateTime<Utc>,
+    /// Time at which operation was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<RawPendingOperation> for PendingOperation {
+    type Error = Error;
+
+    fn try_from(raw: RawPendingOperation) -> Result<Self, Self::Error> {
+        let tx_bytes = hex::decode(&raw.tx).context("invalid hex encoding of transaction")?;
+
+        Ok(Self {
+            id: raw.id,
+            chain_id: raw.chain_id.parse()?,
+            kind: raw.kind.parse()?,
+            request_id: raw.request_id,
+            status: raw.status.parse()?,
+            tx: TxRaw::decode(tx_bytes.as_slice()).context("unable to decode transaction")?,
+            transaction_hash: raw.transaction_hash,
+            sequence: raw.sequence.try_into()?,
+            packet_sequence: raw.packet_sequence.try_into()?,
+            error: raw.error,
+            created_at: raw.created_at,
+            updated_at: raw.updated_at,
+        })
+    }
+}
+
+/// Computes hash of a signed transaction (in upper case hex, as returned by tendermint)
+pub fn transaction_hash(tx: &TxRaw) -> Result<String> {
+    Ok(hex::encode_upper(Sha256::digest(&proto_encode(tx)?)))
+}
+
+/// Adds a signed transaction to pending operation queue (in `pending` state) along with sequences of solo machine
+/// before signing it
+#[allow(clippy::too_many_arguments)]
+pub async fn add_pending_operation<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    kind: PendingOperationKind,
+    request_id: Option<&str>,
+    tx: &TxRaw,
+    sequence: u32,
+    packet_sequence: u32,
+) -> Result<PendingOperation> {
+    let raw: RawPendingOperation = sqlx::query_as(
+        "INSERT INTO pending_operations (chain_id, kind, request_id, status, tx, transaction_hash, sequence, packet_sequence) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING *",
+    )
+    .bind(chain_id.to_string())
+    .bind(kind.to_string())
+    .bind(request_id)
+    .bind(PendingOperationStatus::Pending.to_string())
+    .bind(hex::encode(proto_encode(tx)?))
+    .bind(transaction_hash(tx)?)
+    .bind(i64::from(sequence))
+    .bind(i64::from(packet_sequence))
+    .fetch_one(executor)
+    .await
+    .context("unable to add pending operation to database")?;
+
+    raw.try_into()
+}
+
+/// Replaces signed transaction of a pending operation (e.g. when transaction is rebuilt after an account sequence
+/// mismatch)
+pub async fn update_pending_operation_tx<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+    tx: &TxRaw,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "UPDATE pending_operations SET tx = $1, transaction_hash = $2, updated_at = $3 WHERE id = $4",
+    )
+    .bind(hex::encode(proto_encode(tx)?))
+    .bind(transaction_hash(tx)?)
+    .bind(Utc::now())
+    .bind(id)
+    .execute(executor)
+    .await
+    .context("unable to update transaction of pending operation")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when updating transaction of pending operation"
+    );
+
+    Ok(())
+}
+
+/// Updates status of a pending operation
+pub async fn set_pending_operation_status<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+    status: PendingOperationStatus,
+    error: Option<&str>,
+) -> Result<()> {
+    let rows_affected = sqlx::query(
+        "UPDATE pending_operations SET status = $1, error = $2, updated_at = $3 WHERE id = $4",
+    )
+    .bind(status.to_string())
+    .bind(error)
+    .bind(Utc::now())
+    .bind(id)
+    .execute(executor)
+    .await
+    .context("unable to update status of pending operation")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when updating status of pending operation"
+    );
+
+    Ok(())
+}
+
+/// Marks all the pending operations of a chain as failed with given error (e.g. when chain is reset) and returns
+/// number of failed operations
+pub async fn fail_unresolved_operations<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    error: &str,
+) -> Result<u64> {
+    let rows_affected = sqlx::query(
+        "UPDATE pending_operations SET status = $1, error = $2, updated_at = $3 WHERE chain_id = $4 AND status = $5",
+    )
+    .bind(PendingOperationStatus::Failed.to_string())
+    .bind(error)
+    .bind(Utc::now())
+    .bind(chain_id.to_string())
+    .bind(PendingOperationStatus::Pending.to_string())
+    .execute(executor)
+    .await
+    .context("unable to fail pending operations of chain")?
+    .rows_affected();
+
+    Ok(rows_affected)
+}
+
+/// Fetches failed operations of a chain queued after its latest operation which is not failed (i.e. the operations
+/// which can be rolled back as no operation signed after them may be committed), oldest first
+pub async fn get_trailing_failed_operations<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<Vec<PendingOperation>> {
+    let raw: Vec<RawPendingOperation> = sqlx::query_as(
+        "SELECT * FROM pending_operations WHERE chain_id = $1 AND id > COALESCE((SELECT MAX(id) FROM pending_operations WHERE chain_id = $1 AND status <> $2), 0) ORDER BY id",
+    )
+    .bind(chain_id.to_string())
+    .bind(PendingOperationStatus::Failed.to_string())
+    .fetch_all(executor)
+    .await
+    .context("unable to query failed pending operations from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Fetches a queued operation with given ID
+pub async fn get_pending_operation<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    id: i64,
+) -> Result<Option<PendingOperation>> {
+    let raw: Option<RawPendingOperation> =
+        sqlx::query_as("SELECT * FROM pending_operations WHERE id = $1")
+            .bind(id)
+            .fetch_optional(executor)
+            .await
+            .context("unable to query pending operation from database")?;
+
+    raw.map(TryInto::try_into).transpose()
+}
+
+/// Fetches operations of a chain which are still in `pending` state, oldest first
+pub async fn get_unresolved_operations<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+) -> Result<Vec<PendingOperation>> {
+    let raw: Vec<RawPendingOperation> = sqlx::query_as(
+        "SELECT * FROM pending_operations WHERE chain_id = $1 AND status = $2 ORDER BY id",
+    )
+    .bind(chain_id.to_string())
+    .bind(PendingOperationStatus::Pending.to_string())
+    .fetch_all(executor)
+    .await
+    .context("unable to query pending operations from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Fetches queued operations, latest first (optionally, only the ones of given chain or in given status)
+pub async fn get_pending_operations<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: Option<&ChainId>,
+    status: Option<PendingOperationStatus>,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<PendingOperation>> {
+    let raw: Vec<RawPendingOperation> = sqlx::query_as(
+        "SELECT * FROM pending_operations WHERE ($1 IS NULL OR chain_id = $1) AND ($2 IS NULL OR status = $2) ORDER BY id DESC LIMIT $3 OFFSET $4",
+    )
+    .bind(chain_id.map(ToString::to_string))
+    .bind(status.map(|status| status.to_string()))
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(executor)
+    .await
+    .context("unable to query pending operations from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
@@ -0,0 +1,287 @@
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::{ensure, Context, Error, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{AuthInfo, SignDoc, TxBody};
+use prost::Message as _;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sqlx::{Executor, FromRow};
+
+use crate::{
+    ibc_version::SoloMachineRevision, signer::Message, transaction_builder::decode_sign_bytes, Db,
+};
+
+use super::hash_chain::{compute_hash, GENESIS_HASH};
+
+/// Details of a message signed by solo machine's signer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureRecord {
+    /// Type of signed message (e.g. `sign-bytes`, `sign-doc`)
+    pub message_type: String,
+    /// SHA-256 hash of signed message (in hex)
+    pub message_hash: String,
+    /// Purpose of signature: type of signed data for `SignBytes` (e.g. `Header`, `PacketCommitment`) and type URLs of
+    /// messages in signed transaction for sign docs
+    pub purpose: String,
+    /// Chain ID of signed transaction (`None` for `SignBytes` which do not contain chain ID)
+    pub chain_id: Option<String>,
+    /// Sequence of solo machine client (for `SignBytes`) or of signing account (for sign docs)
+    pub sequence: Option<u64>,
+    /// Consensus timestamp of solo machine client (for `SignBytes`)
+    pub timestamp: Option<u64>,
+    /// Optional request ID (for tracking purposes)
+    pub request_id: Option<String>,
+    /// Resulting signature (in hex)
+    pub signature: String,
+}
+
+impl SignatureRecord {
+    /// Creates a new record of a signed message (details of message are decoded from message itself)
+    pub fn new(message: Message<'_>, request_id: Option<&str>, signature: &[u8]) -> Self {
+        let bytes = message.as_ref();
+
+        let mut record = Self {
+            message_type: message.message_type().to_string(),
+            message_hash: hex::encode(Sha256::digest(bytes)),
+            purpose: "unknown".to_string(),
+            chain_id: None,
+            sequence: None,
+            timestamp: None,
+            request_id: request_id.map(ToString::to_string),
+            signature: hex::encode(signature),
+        };
+
+        // messages which cannot be decoded are still recorded (with their hash)
+        let _ = match message {
+            Message::SignBytes(bytes) => record.decode_sign_bytes(bytes),
+            Message::SignDoc(bytes) => record.decode_sign_doc(bytes),
+            Message::AminoJsonSignDoc(bytes) => record.decode_amino_json_sign_doc(bytes),
+            Message::Eip712(_) => {
+                // EIP-712 sign bytes only contain hashes of typed data
+                record.purpose = "eip712".to_string();
+                Ok(())
+            }
+        };
+
+        record
+    }
+
+    fn decode_sign_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        // `solomachine.v3` sign bytes contain a path (bytes) in place of data type (enum), so, they fail to decode as
+        // `solomachine.v2` sign bytes
+        let preview = decode_sign_bytes(SoloMachineRevision::V2, bytes.to_vec())
+            .or_else(|_| decode_sign_bytes(SoloMachineRevision::V3, bytes.to_vec()))?;
+
+        self.purpose = preview.data_type;
+        self.sequence = Some(preview.sequence);
+        self.timestamp = Some(preview.timestamp);
+
+        Ok(())
+    }
+
+    fn decode_sign_doc(&mut self, bytes: &[u8]) -> Result<()> {
+        let sign_doc = SignDoc::decode(bytes)?;
+        let tx_body = TxBody::decode(sign_doc.body_bytes.as_slice())?;
+        let auth_info = AuthInfo::decode(sign_doc.auth_info_bytes.as_slice())?;
+
+        self.purpose = tx_body
+            .messages
+            .iter()
+            .map(|message| message.type_url.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        self.chain_id = Some(sign_doc.chain_id);
+        self.sequence = auth_info
+            .signer_infos
+            .first()
+            .map(|signer_info| signer_info.sequence);
+
+        Ok(())
+    }
+
+    fn decode_amino_json_sign_doc(&mut self, bytes: &[u8]) -> Result<()> {
+        let sign_doc: Value = serde_json::from_slice(bytes)?;
+
+        self.purpose = sign_doc["msgs"]
+            .as_array()
+            .map(|msgs| {
+                msgs.iter()
+                    .filter_map(|msg| msg["type"].as_str())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .unwrap_or_default();
+        self.chain_id = sign_doc["chain_id"].as_str().map(ToString::to_string);
+        self.sequence = sign_doc["sequence"]
+            .as_str()
+            .and_then(|sequence| sequence.parse().ok());
+
+        Ok(())
+    }
+}
+
+/// Entry in hash-chained signature log
+#[derive(Debug)]
+pub struct SignatureLogEntry {
+    /// ID of entry
+    pub id: i64,
+    /// Signed message along with its signature
+    pub record: SignatureRecord,
+    /// Hash of previous entry (in hex)
+    pub prev_hash: String,
+    /// Hash of this entry (in hex)
+    pub hash: String,
+    /// Time at which message was signed
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw entry in hash-chained signature log
+struct RawSignatureLogEntry {
+    /// ID of entry
+    pub id: i64,
+    /// JSON encoded signature record (stored as text so that hashed bytes are preserved)
+    pub record: String,
+    /// Hash of previous entry (in hex)
+    pub prev_hash: String,
+    /// Hash of this entry (in hex)
+    pub hash: String,
+    /// Time at which message was signed
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<RawSignatureLogEntry> for SignatureLogEntry {
+    type Error = Error;
+
+    fn try_from(raw: RawSignatureLogEntry) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: raw.id,
+            record: serde_json::from_str(&raw.record)
+                .context(format!("invalid record in signature log entry {}", raw.id))?,
+            prev_hash: raw.prev_hash,
+            hash: raw.hash,
+            created_at: raw.created_at,
+        })
+    }
+}
+
+/// Result of verifying hash chain of signature log
+#[derive(Debug)]
+pub struct SignatureLogVerification {
+    /// Number of verified entries
+    pub verified_entries: u64,
+    /// Hash of the last verified entry (can be recorded externally to detect truncation of signature log)
+    pub head_hash: String,
+    /// ID of the first entry which breaks hash chain and the reason (`None` if whole signature log is valid)
+    pub broken_entry: Option<(i64, String)>,
+}
+
+/// Appends a signature record to signature log (entries can never be updated or deleted)
+pub async fn add_signature_log_entry(
+    transaction: &mut sqlx::Transaction<'_, Db>,
+    record: &SignatureRecord,
+) -> Result<()> {
+    let head: Option<(String,)> =
+        sqlx::query_as("SELECT hash FROM signature_log ORDER BY id DESC LIMIT 1")
+            .fetch_optional(&mut *transaction)
+            .await
+            .context("unable to query head of signature log")?;
+
+    let prev_hash = head.map_or_else(|| GENESIS_HASH.to_string(), |(hash,)| hash);
+    // sub-second precision is dropped so that stored timestamp (and thus hash) is same for all the databases
+    let created_at = Utc.timestamp(Utc::now().timestamp(), 0);
+    let record = serde_json::to_string(record).context("unable to serialize signature record")?;
+    let hash = compute_hash(&prev_hash, &created_at, &[&record]);
+
+    let rows_affected = sqlx::query(
+        "INSERT INTO signature_log (record, prev_hash, hash, created_at) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(record)
+    .bind(prev_hash)
+    .bind(hash)
+    .bind(created_at)
+    .execute(&mut *transaction)
+    .await
+    .context("unable to add new signature log entry")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when adding new signature log entry"
+    );
+
+    Ok(())
+}
+
+/// Fetches entries of signature log (latest first)
+pub async fn get_signature_log_entries<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<SignatureLogEntry>> {
+    let raw: Vec<RawSignatureLogEntry> =
+        sqlx::query_as("SELECT * FROM signature_log ORDER BY id DESC LIMIT $1 OFFSET $2")
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(executor)
+            .await
+            .context("unable to query signature log entries from database")?;
+
+    raw.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Returns number of entries added to signature log after given time
+pub async fn count_signature_log_entries<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    since: DateTime<Utc>,
+) -> Result<u64> {
+    let (count,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM signature_log WHERE created_at > $1")
+            .bind(since)
+            .fetch_one(executor)
+            .await
+            .context("unable to count signature log entries in database")?;
+
+    Ok(count.unsigned_abs())
+}
+
+/// Verifies hash chain of all the entries of signature log
+pub async fn verify_signature_log<'e>(
+    executor: impl Executor<'e, Database = Db>,
+) -> Result<SignatureLogVerification> {
+    let raw: Vec<RawSignatureLogEntry> = sqlx::query_as("SELECT * FROM signature_log ORDER BY id")
+        .fetch_all(executor)
+        .await
+        .context("unable to query signature log entries from database")?;
+
+    let mut verification = SignatureLogVerification {
+        verified_entries: 0,
+        head_hash: GENESIS_HASH.to_string(),
+        broken_entry: None,
+    };
+
+    for entry in raw {
+        if entry.prev_hash != verification.head_hash {
+            verification.broken_entry = Some((
+                entry.id,
+                "previous hash does not match hash of previous entry".to_string(),
+            ));
+            break;
+        }
+
+        if entry.hash != compute_hash(&entry.prev_hash, &entry.created_at, &[&entry.record]) {
+            verification.broken_entry = Some((
+                entry.id,
+                "hash does not match contents of entry".to_string(),
+            ));
+            break;
+        }
+
+        verification.verified_entries += 1;
+        verification.head_hash = entry.hash;
+    }
+
+    Ok(verification)
+}
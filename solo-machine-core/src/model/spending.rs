@@ -0,0 +1,61 @@
+use std::convert::TryFrom;
+
+use anyhow::{ensure, Context, Result};
+use chrono::{DateTime, Utc};
+use sqlx::Executor;
+
+use crate::{ibc::core::ics24_host::identifier::ChainId, Db};
+
+/// Adds a transfer of tokens to spendings (used to enforce spending limits of signing policy)
+pub async fn add_spending<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    denom: &str,
+    amount: u64,
+    request_id: Option<&str>,
+    escalated: bool,
+    created_at: DateTime<Utc>,
+) -> Result<()> {
+    let amount = i64::try_from(amount).context("amount of spending is too large")?;
+
+    let rows_affected = sqlx::query(
+        "INSERT INTO spendings (chain_id, denom, amount, request_id, escalated, created_at) VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(chain_id.to_string())
+    .bind(denom)
+    .bind(amount)
+    .bind(request_id)
+    .bind(escalated)
+    .bind(created_at)
+    .execute(executor)
+    .await
+    .context("unable to add spending to database")?
+    .rows_affected();
+
+    ensure!(
+        rows_affected == 1,
+        "rows_affected should be equal to 1 when adding a spending"
+    );
+
+    Ok(())
+}
+
+/// Returns total amount of given denom transferred after given time (on all the chains)
+pub async fn get_spent_amount<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    denom: &str,
+    since: DateTime<Utc>,
+) -> Result<u128> {
+    let amounts: Vec<(i64,)> =
+        sqlx::query_as("SELECT amount FROM spendings WHERE denom = $1 AND created_at > $2")
+            .bind(denom)
+            .bind(since)
+            .fetch_all(executor)
+            .await
+            .context("unable to query spendings from database")?;
+
+    Ok(amounts
+        .into_iter()
+        .map(|(amount,)| u128::from(amount.unsigned_abs()))
+        .sum())
+}
@@ -0,0 +1,171 @@
+use std::convert::{TryFrom, TryInto};
+
+use anyhow::{Context, Error, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{Executor, FromRow};
+
+use crate::{ibc::core::ics24_host::identifier::ChainId, Db};
+
+/// Format in which days are stored in database
+const DAY_FORMAT: &str = "%Y-%m-%d";
+
+/// Counters of successful operations of a chain on a day (UTC)
+#[derive(Debug)]
+pub struct ChainDailyStats {
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+    /// Day (UTC)
+    pub day: NaiveDate,
+    /// Number of packets relayed (acknowledged) on the day
+    pub packets_relayed: u64,
+    /// Number of successful operations on the day
+    pub operations: u64,
+    /// Time of last successful operation on the day
+    pub last_success_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw counters of successful operations of a chain on a day
+struct RawChainDailyStats {
+    /// Chain ID of IBC enabled chain
+    pub chain_id: String,
+    /// Day (UTC, `YYYY-MM-DD`)
+    pub day: String,
+    /// Number of packets relayed on the day
+    pub packets_relayed: i64,
+    /// Number of successful operations on the day
+    pub operations: i64,
+    /// Time of last successful operation on the day
+    pub last_success_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<RawChainDailyStats> for ChainDailyStats {
+    type Error = Error;
+
+    fn try_from(raw: RawChainDailyStats) -> Result<Self, Self::Error> {
+        Ok(Self {
+            chain_id: raw.chain_id.parse()?,
+            day: parse_day(&raw.day)?,
+            packets_relayed: raw.packets_relayed.try_into()?,
+            operations: raw.operations.try_into()?,
+            last_success_at: raw.last_success_at,
+        })
+    }
+}
+
+/// Uptime of long running solo machine processes (gRPC server and relayer) on a day (UTC)
+#[derive(Debug)]
+pub struct UptimeDailyStats {
+    /// Day (UTC)
+    pub day: NaiveDate,
+    /// Number of seconds for which solo machine was running on the day
+    pub uptime_seconds: u64,
+}
+
+#[derive(Debug, FromRow)]
+/// Raw uptime of solo machine on a day
+struct RawUptimeDailyStats {
+    /// Day (UTC, `YYYY-MM-DD`)
+    pub day: String,
+    /// Number of seconds for which solo machine was running on the day
+    pub uptime_seconds: i64,
+}
+
+impl TryFrom<RawUptimeDailyStats> for UptimeDailyStats {
+    type Error = Error;
+
+    fn try_from(raw: RawUptimeDailyStats) -> Result<Self, Self::Error> {
+        Ok(Self {
+            day: parse_day(&raw.day)?,
+            uptime_seconds: raw.uptime_seconds.try_into()?,
+        })
+    }
+}
+
+/// Adds given counters of successful operations to daily stats of a chain
+pub async fn add_chain_daily_stats<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: &ChainId,
+    at: DateTime<Utc>,
+    packets_relayed: u64,
+    operations: u64,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO chain_daily_stats (chain_id, day, packets_relayed, operations, last_success_at) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (chain_id, day) DO UPDATE SET packets_relayed = chain_daily_stats.packets_relayed + excluded.packets_relayed, operations = chain_daily_stats.operations + excluded.operations, last_success_at = excluded.last_success_at",
+    )
+    .bind(chain_id.to_string())
+    .bind(at.format(DAY_FORMAT).to_string())
+    .bind(i64::try_from(packets_relayed)?)
+    .bind(i64::try_from(operations)?)
+    .bind(at)
+    .execute(executor)
+    .await
+    .context("unable to add daily stats of chain in database")?;
+
+    Ok(())
+}
+
+/// Adds given number of seconds to uptime of solo machine on given day
+pub async fn add_uptime<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    day: NaiveDate,
+    seconds: u64,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO uptime_daily_stats (day, uptime_seconds) VALUES ($1, $2) ON CONFLICT (day) DO UPDATE SET uptime_seconds = uptime_daily_stats.uptime_seconds + excluded.uptime_seconds",
+    )
+    .bind(day.format(DAY_FORMAT).to_string())
+    .bind(i64::try_from(seconds)?)
+    .execute(executor)
+    .await
+    .context("unable to add uptime in database")?;
+
+    Ok(())
+}
+
+/// Fetches daily stats of chains since given day (optionally, only for given chain)
+pub async fn get_chain_daily_stats<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    chain_id: Option<&ChainId>,
+    since: NaiveDate,
+) -> Result<Vec<ChainDailyStats>> {
+    let since = since.format(DAY_FORMAT).to_string();
+
+    let raw_stats: Vec<RawChainDailyStats> = match chain_id {
+        None => {
+            sqlx::query_as("SELECT * FROM chain_daily_stats WHERE day >= $1 ORDER BY day DESC")
+                .bind(since)
+                .fetch_all(executor)
+                .await
+        }
+        Some(chain_id) => sqlx::query_as(
+            "SELECT * FROM chain_daily_stats WHERE chain_id = $1 AND day >= $2 ORDER BY day DESC",
+        )
+        .bind(chain_id.to_string())
+        .bind(since)
+        .fetch_all(executor)
+        .await,
+    }
+    .context("unable to query daily stats of chains from database")?;
+
+    raw_stats.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Fetches daily uptime of solo machine since given day
+pub async fn get_uptime_daily_stats<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    since: NaiveDate,
+) -> Result<Vec<UptimeDailyStats>> {
+    let raw_stats: Vec<RawUptimeDailyStats> =
+        sqlx::query_as("SELECT * FROM uptime_daily_stats WHERE day >= $1 ORDER BY day DESC")
+            .bind(since.format(DAY_FORMAT).to_string())
+            .fetch_all(executor)
+            .await
+            .context("unable to query daily uptime from database")?;
+
+    raw_stats.into_iter().map(TryInto::try_into).collect()
+}
+
+fn parse_day(day: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(day, DAY_FORMAT).context(format!("invalid day: {}", day))
+}
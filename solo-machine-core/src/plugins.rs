@@ -0,0 +1,41 @@
+//! Plugins used while building transactions (fee strategy for chains with `plugin` fee strategy and client encoders for
+//! chains with `plugin` client encoding)
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    client_encoder::ClientEncoder, fee::FeeStrategy, ibc::core::ics24_host::identifier::ChainId,
+};
+
+/// Plugins given to services (and by them to transaction builder) for chains configured to use them
+#[derive(Clone, Default)]
+pub struct Plugins {
+    fee_strategy: Option<Arc<dyn FeeStrategy>>,
+    client_encoders: HashMap<String, Arc<dyn ClientEncoder>>,
+}
+
+impl Plugins {
+    /// Creates plugins with given fee strategy and client encoders (keyed by chain ID)
+    pub fn new(
+        fee_strategy: Option<Arc<dyn FeeStrategy>>,
+        client_encoders: HashMap<String, Arc<dyn ClientEncoder>>,
+    ) -> Self {
+        Self {
+            fee_strategy,
+            client_encoders,
+        }
+    }
+
+    /// Returns the fee strategy used for chains with `plugin` fee strategy (`None` if no fee strategy plugin is
+    /// registered)
+    pub fn fee_strategy(&self) -> Option<&dyn FeeStrategy> {
+        self.fee_strategy.as_deref()
+    }
+
+    /// Returns the client encoder used for given chain when it uses `plugin` client encoding (`None` if no client
+    /// encoder is registered for chain)
+    pub fn client_encoder(&self, chain_id: &ChainId) -> Option<&dyn ClientEncoder> {
+        self.client_encoders
+            .get(&chain_id.to_string())
+            .map(|client_encoder| &**client_encoder)
+    }
+}
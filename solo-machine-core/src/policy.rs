@@ -0,0 +1,514 @@
+//! Signing policy evaluated before every signature of solo machine's signer (allowed chains and message types, maximum
+//! transfer amounts per denom, allowed recipients and spending limits per time window), so that a signer running as a
+//! service only signs what it is expected to
+use std::{collections::BTreeMap, error::Error as StdError, fmt, time::Duration};
+
+use anyhow::{Context, Result};
+use cosmos_sdk_proto::{
+    cosmos::{
+        bank::v1beta1::MsgSend,
+        tx::v1beta1::{SignDoc, TxBody},
+    },
+    ibc::{applications::transfer::v1::MsgTransfer, core::channel::v1::MsgRecvPacket},
+};
+use prost::Message as _;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::signer::Message;
+
+const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+const MSG_TRANSFER_TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
+const MSG_RECV_PACKET_TYPE_URL: &str = "/ibc.core.channel.v1.MsgRecvPacket";
+
+const AMINO_MSG_SEND_NAME: &str = "cosmos-sdk/MsgSend";
+const AMINO_MSG_TRANSFER_NAME: &str = "cosmos-sdk/MsgTransfer";
+const AMINO_MSG_RECV_PACKET_NAME: &str = "cosmos-sdk/MsgRecvPacket";
+
+/// Error returned when a signature is rejected by signing policy
+#[derive(Debug, Clone)]
+pub struct PolicyViolation {
+    /// Reason of rejection
+    pub reason: String,
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rejected by signing policy: {}", self.reason)
+    }
+}
+
+impl StdError for PolicyViolation {}
+
+/// Action taken when a transfer would exceed a spending limit
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LimitAction {
+    /// Transfer is rejected
+    #[default]
+    Block,
+    /// Transfer is rejected unless it is explicitly approved by operator
+    Escalate,
+}
+
+/// Maximum total amount of a denom transferred (minted, burnt or sent) within a sliding time window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpendingLimit {
+    /// Denom of limited tokens
+    pub denom: String,
+    /// Maximum total amount of tokens transferred within window
+    pub amount: u64,
+    /// Length of sliding window (e.g. `24h`)
+    pub window: String,
+    /// Action taken when a transfer would exceed the limit
+    #[serde(default)]
+    pub on_exceed: LimitAction,
+}
+
+impl SpendingLimit {
+    /// Returns length of sliding window
+    pub fn window(&self) -> Result<Duration> {
+        humantime::parse_duration(&self.window).with_context(|| {
+            format!(
+                "invalid window of spending limit of {}: {}",
+                self.denom, self.window
+            )
+        })
+    }
+}
+
+/// Policy evaluated before every signature (every rule is optional, an empty policy allows everything)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SigningPolicy {
+    /// Chain IDs of chains for which messages can be signed (all the chains when empty)
+    pub allowed_chains: Vec<String>,
+    /// Type URLs of messages which can be signed in transactions (e.g. `/ibc.core.channel.v1.MsgRecvPacket`, or amino
+    /// names for chains using `legacy-amino-json` sign mode; all the messages when empty)
+    pub allowed_message_types: Vec<String>,
+    /// Maximum amount of a single transfer per denom (e.g. `{ gld = 1000 }`, transfers of other denoms are not limited)
+    pub max_transfer_amounts: BTreeMap<String, u64>,
+    /// Addresses to which tokens can be transferred (all the addresses when empty)
+    pub allowed_recipients: Vec<String>,
+    /// Maximum total amounts of denoms transferred within sliding time windows (tracked in database, so, limits are
+    /// enforced across restarts)
+    pub spending_limits: Vec<SpendingLimit>,
+    /// Maximum number of signatures of signer within a minute (not limited when not provided)
+    pub max_signatures_per_minute: Option<u32>,
+    /// Maximum number of signatures of signer within an hour (not limited when not provided)
+    pub max_signatures_per_hour: Option<u32>,
+    /// Whether transfers exceeding spending limits with [`LimitAction::Escalate`] action are approved (never read from
+    /// configuration, set by operator for a single invocation)
+    #[serde(skip)]
+    pub escalation_approved: bool,
+}
+
+impl SigningPolicy {
+    /// Returns `true` if policy does not have any rule
+    pub fn is_empty(&self) -> bool {
+        Self {
+            escalation_approved: false,
+            ..self.clone()
+        } == Self::default()
+    }
+
+    /// Ensures that all the rules of policy are valid (e.g. windows of spending limits)
+    pub fn validate(&self) -> Result<()> {
+        for limit in self.spending_limits.iter() {
+            limit.window()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns maximum numbers of signatures within sliding time windows (empty if signatures are not rate limited)
+    pub fn signature_rate_limits(&self) -> Vec<(u32, Duration)> {
+        let mut limits = Vec::new();
+
+        if let Some(max_signatures) = self.max_signatures_per_minute {
+            limits.push((max_signatures, Duration::from_secs(60)));
+        }
+
+        if let Some(max_signatures) = self.max_signatures_per_hour {
+            limits.push((max_signatures, Duration::from_secs(60 * 60)));
+        }
+
+        limits
+    }
+
+    /// Ensures that messages for given chain can be signed
+    pub fn check_chain(&self, chain_id: &str) -> Result<(), PolicyViolation> {
+        if self.allowed_chains.is_empty() || self.allowed_chains.iter().any(|id| id == chain_id) {
+            Ok(())
+        } else {
+            Err(violation(format!("chain {} is not allowed", chain_id)))
+        }
+    }
+
+    /// Ensures that a message of given type can be signed
+    pub fn check_message_type(&self, type_url: &str) -> Result<(), PolicyViolation> {
+        if self.allowed_message_types.is_empty()
+            || self.allowed_message_types.iter().any(|t| t == type_url)
+        {
+            Ok(())
+        } else {
+            Err(violation(format!(
+                "message type {} is not allowed",
+                type_url
+            )))
+        }
+    }
+
+    /// Ensures that a transfer of given amount of tokens (to given recipient, if any) on given chain is allowed
+    pub fn check_transfer(
+        &self,
+        chain_id: &str,
+        denom: &str,
+        amount: u128,
+        recipient: Option<&str>,
+    ) -> Result<(), PolicyViolation> {
+        self.check_chain(chain_id)?;
+
+        if let Some(max_amount) = self.max_transfer_amounts.get(denom) {
+            if amount > u128::from(*max_amount) {
+                return Err(violation(format!(
+                    "transfer of {} {} exceeds maximum amount of {} {}",
+                    amount, denom, max_amount, denom
+                )));
+            }
+        }
+
+        match recipient {
+            Some(recipient)
+                if !self.allowed_recipients.is_empty()
+                    && !self.allowed_recipients.iter().any(|r| r == recipient) =>
+            {
+                Err(violation(format!("recipient {} is not allowed", recipient)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Evaluates policy for a message given to signer. `SignBytes` (proofs of solo machine) do not contain chain ID or
+    /// messages, so, their operations are checked before they are signed (e.g. using [`Self::check_transfer`] before
+    /// minting tokens).
+    pub fn evaluate(&self, message: Message<'_>) -> Result<()> {
+        match message {
+            Message::SignBytes(_) => Ok(()),
+            Message::SignDoc(bytes) => self.evaluate_sign_doc(bytes),
+            Message::AminoJsonSignDoc(bytes) => self.evaluate_amino_json_sign_doc(bytes),
+            Message::Eip712(_) => {
+                // EIP-712 sign bytes only contain hashes of typed data, so, no rule on chains, messages or transfers
+                // can be evaluated for them
+                if self.allowed_chains.is_empty()
+                    && self.allowed_message_types.is_empty()
+                    && self.max_transfer_amounts.is_empty()
+                    && self.allowed_recipients.is_empty()
+                {
+                    Ok(())
+                } else {
+                    Err(violation(
+                        "EIP-712 messages cannot be evaluated against allowed chains, message types, maximum \
+                        transfer amounts and allowed recipients"
+                            .to_string(),
+                    )
+                    .into())
+                }
+            }
+        }
+    }
+
+    fn evaluate_sign_doc(&self, bytes: &[u8]) -> Result<()> {
+        let sign_doc = SignDoc::decode(bytes).context("invalid sign doc")?;
+        let tx_body =
+            TxBody::decode(sign_doc.body_bytes.as_slice()).context("invalid transaction body")?;
+
+        self.check_chain(&sign_doc.chain_id)?;
+
+        for message in tx_body.messages.iter() {
+            self.check_message_type(&message.type_url)?;
+
+            match message.type_url.as_str() {
+                MSG_SEND_TYPE_URL => {
+                    let msg = MsgSend::decode(message.value.as_slice())?;
+
+                    for coin in msg.amount.iter() {
+                        self.check_transfer(
+                            &sign_doc.chain_id,
+                            &coin.denom,
+                            parse_amount(&coin.amount)?,
+                            Some(msg.to_address.as_str()),
+                        )?;
+                    }
+                }
+                MSG_TRANSFER_TYPE_URL => {
+                    let msg = MsgTransfer::decode(message.value.as_slice())?;
+
+                    // tokens are sent back to solo machine (burn), so, receiver is not checked
+                    if let Some(ref token) = msg.token {
+                        self.check_transfer(
+                            &sign_doc.chain_id,
+                            &token.denom,
+                            parse_amount(&token.amount)?,
+                            None,
+                        )?;
+                    }
+                }
+                MSG_RECV_PACKET_TYPE_URL => {
+                    let msg = MsgRecvPacket::decode(message.value.as_slice())?;
+
+                    if let Some(packet) = msg.packet {
+                        self.check_packet_data(&sign_doc.chain_id, &packet.data)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn evaluate_amino_json_sign_doc(&self, bytes: &[u8]) -> Result<()> {
+        let sign_doc: Value = serde_json::from_slice(bytes).context("invalid amino sign doc")?;
+        let chain_id = sign_doc["chain_id"].as_str().unwrap_or_default();
+
+        self.check_chain(chain_id)?;
+
+        for msg in sign_doc["msgs"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+        {
+            self.check_message_type(msg["type"].as_str().unwrap_or_default())?;
+
+            let value = &msg["value"];
+
+            match msg["type"].as_str().unwrap_or_default() {
+                AMINO_MSG_SEND_NAME => {
+                    for coin in value["amount"]
+                        .as_array()
+                        .map(Vec::as_slice)
+                        .unwrap_or_default()
+                    {
+                        self.check_transfer(
+                            chain_id,
+                            coin["denom"].as_str().unwrap_or_default(),
+                            parse_amount(coin["amount"].as_str().unwrap_or_default())?,
+                            Some(value["to_address"].as_str().unwrap_or_default()),
+                        )?;
+                    }
+                }
+                AMINO_MSG_TRANSFER_NAME => {
+                    // tokens are sent back to solo machine (burn), so, receiver is not checked
+                    if let Some(token) = value.get("token").filter(|token| token.is_object()) {
+                        self.check_transfer(
+                            chain_id,
+                            token["denom"].as_str().unwrap_or_default(),
+                            parse_amount(token["amount"].as_str().unwrap_or_default())?,
+                            None,
+                        )?;
+                    }
+                }
+                AMINO_MSG_RECV_PACKET_NAME => {
+                    if let Some(data) = value["packet"]["data"].as_str() {
+                        let data = base64::decode(data).context("invalid packet data")?;
+                        self.check_packet_data(chain_id, &data)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks transfer carried by data of a packet (token transfers sent by solo machine (mint) carry fungible token
+    /// packet data)
+    fn check_packet_data(&self, chain_id: &str, data: &[u8]) -> Result<()> {
+        if let Ok(packet_data) = serde_json::from_slice::<Value>(data) {
+            if let (Some(denom), Some(amount)) = (
+                packet_data["denom"].as_str(),
+                packet_data["amount"].as_str(),
+            ) {
+                self.check_transfer(
+                    chain_id,
+                    denom,
+                    parse_amount(amount)?,
+                    packet_data["receiver"].as_str(),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn violation(reason: String) -> PolicyViolation {
+    PolicyViolation { reason }
+}
+
+fn parse_amount(amount: &str) -> Result<u128> {
+    amount
+        .parse()
+        .with_context(|| format!("invalid amount: {}", amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{cosmos::amino, proto::proto_encode};
+
+    fn policy() -> SigningPolicy {
+        SigningPolicy {
+            allowed_chains: vec!["test-1".to_string()],
+            max_transfer_amounts: vec![("gld".to_string(), 100)].into_iter().collect(),
+            allowed_recipients: vec!["cosmos1allowed".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn amino_sign_doc(chain_id: &str, msg: Value) -> Vec<u8> {
+        amino::canonical_json(json!({
+            "account_number": "1",
+            "chain_id": chain_id,
+            "fee": { "amount": [], "gas": "200000" },
+            "memo": "",
+            "msgs": [msg],
+            "sequence": "1",
+        }))
+        .unwrap()
+    }
+
+    fn amino_msg_send(amount: &str, to_address: &str) -> Vec<u8> {
+        amino_sign_doc(
+            "test-1",
+            amino::amino_message(
+                AMINO_MSG_SEND_NAME,
+                json!({
+                    "amount": [{ "amount": amount, "denom": "gld" }],
+                    "from_address": "cosmos1sender",
+                    "to_address": to_address,
+                }),
+            ),
+        )
+    }
+
+    fn is_violation(result: Result<()>) -> bool {
+        matches!(result, Err(err) if err.downcast_ref::<PolicyViolation>().is_some())
+    }
+
+    #[test]
+    fn sign_doc_transfers_are_checked() {
+        let sign_doc = |amount: &str| {
+            let msg = MsgSend {
+                from_address: "cosmos1sender".to_string(),
+                to_address: "cosmos1allowed".to_string(),
+                amount: vec![Coin {
+                    denom: "gld".to_string(),
+                    amount: amount.to_string(),
+                }],
+            };
+
+            let body = TxBody {
+                messages: vec![prost_types::Any {
+                    type_url: MSG_SEND_TYPE_URL.to_string(),
+                    value: proto_encode(&msg).unwrap(),
+                }],
+                ..Default::default()
+            };
+
+            proto_encode(&SignDoc {
+                body_bytes: proto_encode(&body).unwrap(),
+                chain_id: "test-1".to_string(),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+
+        assert!(policy()
+            .evaluate(Message::SignDoc(&sign_doc("100")))
+            .is_ok());
+        assert!(is_violation(
+            policy().evaluate(Message::SignDoc(&sign_doc("101")))
+        ));
+    }
+
+    #[test]
+    fn amino_json_transfers_are_checked() {
+        let policy = policy();
+
+        assert!(policy
+            .evaluate(Message::AminoJsonSignDoc(&amino_msg_send(
+                "100",
+                "cosmos1allowed"
+            )))
+            .is_ok());
+        assert!(is_violation(policy.evaluate(Message::AminoJsonSignDoc(
+            &amino_msg_send("101", "cosmos1allowed")
+        ))));
+        assert!(is_violation(policy.evaluate(Message::AminoJsonSignDoc(
+            &amino_msg_send("100", "cosmos1other")
+        ))));
+
+        let transfer = amino_sign_doc(
+            "test-1",
+            amino::amino_message(
+                AMINO_MSG_TRANSFER_NAME,
+                json!({ "token": { "amount": "101", "denom": "gld" } }),
+            ),
+        );
+        assert!(is_violation(
+            policy.evaluate(Message::AminoJsonSignDoc(&transfer))
+        ));
+
+        let packet_data = json!({ "amount": "101", "denom": "gld", "receiver": "cosmos1allowed" });
+        let recv_packet = amino_sign_doc(
+            "test-1",
+            amino::amino_message(
+                AMINO_MSG_RECV_PACKET_NAME,
+                json!({ "packet": { "data": amino::bytes(packet_data.to_string().as_bytes()) } }),
+            ),
+        );
+        assert!(is_violation(
+            policy.evaluate(Message::AminoJsonSignDoc(&recv_packet))
+        ));
+    }
+
+    #[test]
+    fn amino_json_chains_are_checked() {
+        let sign_doc = amino_sign_doc(
+            "test-2",
+            amino::amino_message(AMINO_MSG_SEND_NAME, json!({})),
+        );
+
+        assert!(is_violation(
+            policy().evaluate(Message::AminoJsonSignDoc(&sign_doc))
+        ));
+    }
+
+    #[test]
+    fn eip712_messages_fail_closed_with_transfer_rules() {
+        assert!(SigningPolicy::default()
+            .evaluate(Message::Eip712(&[0x19, 0x01]))
+            .is_ok());
+
+        for policy in [
+            SigningPolicy {
+                max_transfer_amounts: vec![("gld".to_string(), 100)].into_iter().collect(),
+                ..Default::default()
+            },
+            SigningPolicy {
+                allowed_recipients: vec!["cosmos1allowed".to_string()],
+                ..Default::default()
+            },
+        ] {
+            assert!(is_violation(
+                policy.evaluate(Message::Eip712(&[0x19, 0x01]))
+            ));
+        }
+    }
+}
@@ -21,6 +21,20 @@ pub mod cosmos {
             tonic::include_proto!("cosmos.crypto.secp256r1");
         }
     }
+
+    pub mod feegrant {
+        pub mod v1beta1 {
+            tonic::include_proto!("cosmos.feegrant.v1beta1");
+        }
+    }
+}
+
+pub mod cosmwasm {
+    pub mod wasm {
+        pub mod v1 {
+            tonic::include_proto!("cosmwasm.wasm.v1");
+        }
+    }
 }
 
 #[cfg(feature = "ethermint")]
@@ -40,17 +54,28 @@ pub mod ethermint {
     }
 }
 
-#[cfg(feature = "solomachine-v2")]
 pub mod ibc {
     pub mod lightclients {
         pub mod solomachine {
             pub mod v2 {
                 tonic::include_proto!("ibc.lightclients.solomachine.v2");
             }
+
+            pub mod v3 {
+                tonic::include_proto!("ibc.lightclients.solomachine.v3");
+            }
+        }
+
+        pub mod wasm {
+            pub mod v1 {
+                tonic::include_proto!("ibc.lightclients.wasm.v1");
+            }
         }
     }
 }
 
+pub mod json;
+
 use anyhow::{Context, Result};
 use prost::Message;
 use prost_types::Any;
@@ -67,10 +92,27 @@ pub trait AnyConvert: Sized {
     fn from_any(value: &Any) -> Result<Self>;
 
     fn to_any(&self) -> Result<Any>;
+
+    fn to_amino_json(&self) -> Result<serde_json::Value> {
+        Err(anyhow::anyhow!(
+            "legacy amino JSON encoding is not supported for `{}`",
+            self.to_any()?.type_url
+        ))
+    }
 }
 
 macro_rules! impl_any_conversion {
     ($type: ty, $type_url: ident) => {
+        impl_any_conversion!($type, $type_url, {});
+    };
+    ($type: ty, $type_url: ident, amino_json = $amino_json: path) => {
+        impl_any_conversion!($type, $type_url, {
+            fn to_amino_json(&self) -> ::anyhow::Result<::serde_json::Value> {
+                $amino_json(self)
+            }
+        });
+    };
+    ($type: ty, $type_url: ident, { $($amino: tt)* }) => {
         impl $crate::proto::AnyConvert for $type {
             fn from_any(value: &::prost_types::Any) -> ::anyhow::Result<Self> {
                 ::anyhow::ensure!(
@@ -89,6 +131,8 @@ macro_rules! impl_any_conversion {
                     value: $crate::proto::proto_encode(self)?,
                 })
             }
+
+            $($amino)*
         }
     };
 }
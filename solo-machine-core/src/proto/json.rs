@@ -0,0 +1,854 @@
+//! Proto3 JSON rendering of protobuf messages built by solo machine (matches the JSON produced by gogoproto `jsonpb`
+//! with original field names and default values, i.e., the JSON printed by ibc-go and cosmos SDK CLIs)
+use anyhow::{Context, Result};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use cosmos_sdk_proto::{
+    cosmos::{
+        bank::v1beta1::MsgSend,
+        base::v1beta1::Coin,
+        crypto::multisig::v1beta1::CompactBitArray,
+        tx::v1beta1::{
+            mode_info::{Multi, Single, Sum},
+            AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, Tx, TxBody, TxRaw,
+        },
+    },
+    ibc::{
+        applications::transfer::v1::MsgTransfer,
+        core::{
+            channel::v1::{
+                Channel, Counterparty as ChannelCounterparty, MsgAcknowledgement,
+                MsgChannelOpenAck, MsgChannelOpenInit, MsgRecvPacket, MsgTimeout, Packet,
+            },
+            client::v1::{Height, MsgCreateClient, MsgUpdateClient},
+            commitment::v1::{MerklePrefix, MerkleRoot},
+            connection::v1::{
+                ConnectionEnd, Counterparty as ConnectionCounterparty, MsgConnectionOpenAck,
+                MsgConnectionOpenInit, Version,
+            },
+        },
+        lightclients::{
+            solomachine::v1 as solomachine_v1,
+            tendermint::v1::{
+                ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+                Fraction,
+            },
+        },
+    },
+    ics23::{InnerSpec, LeafOp, ProofSpec},
+};
+use prost::Message;
+use prost_types::{Any, Duration, Timestamp};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::proto::cosmos::{
+    crypto::{
+        ed25519::PubKey as Ed25519PubKey, multisig::LegacyAminoPubKey,
+        secp256k1::PubKey as Secp256k1PubKey,
+    },
+    feegrant::v1beta1::{BasicAllowance, MsgGrantAllowance, MsgRevokeAllowance},
+};
+#[cfg(feature = "ethermint")]
+use crate::proto::ethermint::{
+    crypto::v1::ethsecp256k1::PubKey as EthSecp256k1PubKey, types::v1::ExtensionOptionsWeb3Tx,
+};
+use crate::{
+    ibc_version::SoloMachineRevision,
+    proto::ibc::lightclients::{
+        solomachine::{v2 as solomachine_v2, v3 as solomachine_v3},
+        wasm::v1 as wasm_v1,
+    },
+};
+
+/// Proto3 JSON representation of a protobuf message
+pub trait ProtoJson {
+    /// Returns proto3 JSON representation of message
+    fn to_proto_json(&self) -> Result<Value>;
+}
+
+/// Renders an encoded transaction (`TxRaw`) as proto3 JSON of decoded transaction (`Tx`)
+pub fn tx_raw_to_proto_json(tx_raw: &TxRaw) -> Result<Value> {
+    let tx = Tx {
+        body: Some(
+            TxBody::decode(tx_raw.body_bytes.as_slice()).context("invalid transaction body")?,
+        ),
+        auth_info: Some(
+            AuthInfo::decode(tx_raw.auth_info_bytes.as_slice())
+                .context("invalid transaction auth info")?,
+        ),
+        signatures: tx_raw.signatures.clone(),
+    };
+
+    tx.to_proto_json()
+}
+
+/// Renders an encoded transaction (`TxRaw`) in the JSON layout used by cosmos SDK CLIs (e.g. output of
+/// `tx ... --generate-only`), which is accepted by `tx sign` and `tx broadcast`. Signer infos and signatures are
+/// removed from unsigned transactions (they are added by `tx sign`).
+pub fn tx_raw_to_cosmos_json(tx_raw: &TxRaw, signed: bool) -> Result<Value> {
+    let mut tx = tx_raw_to_proto_json(tx_raw)?;
+
+    if !signed {
+        tx["auth_info"]["signer_infos"] = Value::Array(Vec::new());
+        tx["signatures"] = Value::Array(Vec::new());
+    }
+
+    Ok(tx)
+}
+
+/// Decodes an encoded transaction (bytes of `TxRaw`, e.g. of a failed submission) and renders its body (including
+/// messages and IBC payloads packed in them), auth info and signatures as proto3 JSON along with its hash (in upper
+/// case hex, as returned by tendermint)
+pub fn decode_tx(tx_bytes: &[u8]) -> Result<Value> {
+    let tx_raw = TxRaw::decode(tx_bytes).context("invalid transaction bytes")?;
+
+    let mut map = Map::new();
+    map.insert(
+        "hash".to_owned(),
+        Value::String(hex::encode_upper(Sha256::digest(tx_bytes))),
+    );
+
+    if let Value::Object(fields) = tx_raw_to_proto_json(&tx_raw)? {
+        map.extend(fields);
+    }
+
+    Ok(Value::Object(map))
+}
+
+/// Renders protobuf encoded solo machine `SignBytes` (of given revision of solo machine client types) as proto3 JSON
+/// (`solomachine.v1` and `solomachine.v2` sign bytes are identical on wire)
+pub fn sign_bytes_to_proto_json(revision: SoloMachineRevision, sign_bytes: &[u8]) -> Result<Value> {
+    match revision {
+        SoloMachineRevision::V1 | SoloMachineRevision::V2 => {
+            solomachine_v2::SignBytes::decode(sign_bytes)
+                .context("invalid sign bytes")?
+                .to_proto_json()
+        }
+        SoloMachineRevision::V3 => solomachine_v3::SignBytes::decode(sign_bytes)
+            .context("invalid sign bytes")?
+            .to_proto_json(),
+    }
+}
+
+macro_rules! impl_proto_json {
+    ($type: ty { $($field: ident: $kind: ident $(($arg: expr))?),* $(,)? }) => {
+        impl ProtoJson for $type {
+            fn to_proto_json(&self) -> Result<Value> {
+                #[allow(unused_mut)]
+                let mut map = Map::new();
+                $(map.insert(stringify!($field).to_owned(), field::$kind(&self.$field $(, $arg)?)?);)*
+                Ok(Value::Object(map))
+            }
+        }
+    };
+}
+
+impl ProtoJson for Any {
+    /// Known types are rendered with an additional `@type` field, unknown ones with base64 encoded `value`
+    fn to_proto_json(&self) -> Result<Value> {
+        macro_rules! decode {
+            ($type: ty) => {
+                <$type>::decode(self.value.as_slice())
+                    .with_context(|| format!("unable to decode `{}`", self.type_url))?
+                    .to_proto_json()?
+            };
+        }
+
+        let value = match self.type_url.as_str() {
+            "/cosmos.bank.v1beta1.MsgSend" => decode!(MsgSend),
+            "/cosmos.feegrant.v1beta1.MsgGrantAllowance" => decode!(MsgGrantAllowance),
+            "/cosmos.feegrant.v1beta1.MsgRevokeAllowance" => decode!(MsgRevokeAllowance),
+            "/cosmos.feegrant.v1beta1.BasicAllowance" => decode!(BasicAllowance),
+            "/cosmos.crypto.secp256k1.PubKey" => decode!(Secp256k1PubKey),
+            "/cosmos.crypto.ed25519.PubKey" => decode!(Ed25519PubKey),
+            "/cosmos.crypto.multisig.LegacyAminoPubKey" => decode!(LegacyAminoPubKey),
+            #[cfg(feature = "ethermint")]
+            "/ethermint.crypto.v1.ethsecp256k1.PubKey" => decode!(EthSecp256k1PubKey),
+            #[cfg(feature = "ethermint")]
+            "/ethermint.types.v1.ExtensionOptionsWeb3Tx" => decode!(ExtensionOptionsWeb3Tx),
+            "/ibc.core.client.v1.MsgCreateClient" => decode!(MsgCreateClient),
+            "/ibc.core.client.v1.MsgUpdateClient" => decode!(MsgUpdateClient),
+            "/ibc.core.connection.v1.MsgConnectionOpenInit" => decode!(MsgConnectionOpenInit),
+            "/ibc.core.connection.v1.MsgConnectionOpenAck" => decode!(MsgConnectionOpenAck),
+            "/ibc.core.channel.v1.MsgChannelOpenInit" => decode!(MsgChannelOpenInit),
+            "/ibc.core.channel.v1.MsgChannelOpenAck" => decode!(MsgChannelOpenAck),
+            "/ibc.core.channel.v1.MsgRecvPacket" => decode!(MsgRecvPacket),
+            "/ibc.core.channel.v1.MsgAcknowledgement" => decode!(MsgAcknowledgement),
+            "/ibc.core.channel.v1.MsgTimeout" => decode!(MsgTimeout),
+            "/ibc.applications.transfer.v1.MsgTransfer" => decode!(MsgTransfer),
+            "/ibc.lightclients.tendermint.v1.ClientState" => decode!(TendermintClientState),
+            "/ibc.lightclients.tendermint.v1.ConsensusState" => {
+                decode!(TendermintConsensusState)
+            }
+            "/ibc.lightclients.solomachine.v1.ClientState" => {
+                decode!(solomachine_v1::ClientState)
+            }
+            "/ibc.lightclients.solomachine.v1.ConsensusState" => {
+                decode!(solomachine_v1::ConsensusState)
+            }
+            "/ibc.lightclients.solomachine.v1.Header" => decode!(solomachine_v1::Header),
+            "/ibc.lightclients.solomachine.v2.ClientState" => {
+                decode!(solomachine_v2::ClientState)
+            }
+            "/ibc.lightclients.solomachine.v2.ConsensusState" => {
+                decode!(solomachine_v2::ConsensusState)
+            }
+            "/ibc.lightclients.solomachine.v2.Header" => decode!(solomachine_v2::Header),
+            "/ibc.lightclients.solomachine.v3.ClientState" => {
+                decode!(solomachine_v3::ClientState)
+            }
+            "/ibc.lightclients.solomachine.v3.ConsensusState" => {
+                decode!(solomachine_v3::ConsensusState)
+            }
+            "/ibc.lightclients.solomachine.v3.Header" => decode!(solomachine_v3::Header),
+            "/ibc.lightclients.wasm.v1.ClientState" => decode!(wasm_v1::ClientState),
+            "/ibc.lightclients.wasm.v1.ConsensusState" => decode!(wasm_v1::ConsensusState),
+            "/ibc.lightclients.wasm.v1.ClientMessage" => decode!(wasm_v1::ClientMessage),
+            _ => {
+                let mut map = Map::new();
+                map.insert("@type".to_owned(), Value::String(self.type_url.clone()));
+                map.insert("value".to_owned(), field::bytes(&self.value)?);
+                return Ok(Value::Object(map));
+            }
+        };
+
+        let mut map = Map::new();
+        map.insert("@type".to_owned(), Value::String(self.type_url.clone()));
+
+        if let Value::Object(fields) = value {
+            map.extend(fields);
+        }
+
+        Ok(Value::Object(map))
+    }
+}
+
+impl ProtoJson for ModeInfo {
+    fn to_proto_json(&self) -> Result<Value> {
+        let mut map = Map::new();
+
+        match self.sum {
+            Some(Sum::Single(ref single)) => {
+                map.insert("single".to_owned(), single.to_proto_json()?);
+            }
+            Some(Sum::Multi(ref multi)) => {
+                map.insert("multi".to_owned(), multi.to_proto_json()?);
+            }
+            None => {}
+        }
+
+        Ok(Value::Object(map))
+    }
+}
+
+const SIGN_MODE: &[(i32, &str)] = &[
+    (0, "SIGN_MODE_UNSPECIFIED"),
+    (1, "SIGN_MODE_DIRECT"),
+    (2, "SIGN_MODE_TEXTUAL"),
+    (127, "SIGN_MODE_LEGACY_AMINO_JSON"),
+];
+
+const CHANNEL_STATE: &[(i32, &str)] = &[
+    (0, "STATE_UNINITIALIZED_UNSPECIFIED"),
+    (1, "STATE_INIT"),
+    (2, "STATE_TRYOPEN"),
+    (3, "STATE_OPEN"),
+    (4, "STATE_CLOSED"),
+];
+
+const CHANNEL_ORDER: &[(i32, &str)] = &[
+    (0, "ORDER_NONE_UNSPECIFIED"),
+    (1, "ORDER_UNORDERED"),
+    (2, "ORDER_ORDERED"),
+];
+
+const CONNECTION_STATE: &[(i32, &str)] = &[
+    (0, "STATE_UNINITIALIZED_UNSPECIFIED"),
+    (1, "STATE_INIT"),
+    (2, "STATE_TRYOPEN"),
+    (3, "STATE_OPEN"),
+];
+
+const DATA_TYPE: &[(i32, &str)] = &[
+    (0, "DATA_TYPE_UNINITIALIZED_UNSPECIFIED"),
+    (1, "DATA_TYPE_CLIENT_STATE"),
+    (2, "DATA_TYPE_CONSENSUS_STATE"),
+    (3, "DATA_TYPE_CONNECTION_STATE"),
+    (4, "DATA_TYPE_CHANNEL_STATE"),
+    (5, "DATA_TYPE_PACKET_COMMITMENT"),
+    (6, "DATA_TYPE_PACKET_ACKNOWLEDGEMENT"),
+    (7, "DATA_TYPE_PACKET_RECEIPT_ABSENCE"),
+    (8, "DATA_TYPE_NEXT_SEQUENCE_RECV"),
+    (9, "DATA_TYPE_HEADER"),
+];
+
+const HASH_OP: &[(i32, &str)] = &[
+    (0, "NO_HASH"),
+    (1, "SHA256"),
+    (2, "SHA512"),
+    (3, "KECCAK"),
+    (4, "RIPEMD160"),
+    (5, "BITCOIN"),
+];
+
+const LENGTH_OP: &[(i32, &str)] = &[
+    (0, "NO_PREFIX"),
+    (1, "VAR_PROTO"),
+    (2, "VAR_RLP"),
+    (3, "FIXED32_BIG"),
+    (4, "FIXED32_LITTLE"),
+    (5, "FIXED64_BIG"),
+    (6, "FIXED64_LITTLE"),
+    (7, "REQUIRE_32_BYTES"),
+    (8, "REQUIRE_64_BYTES"),
+];
+
+// cosmos SDK
+
+impl_proto_json!(Tx {
+    body: message,
+    auth_info: message,
+    signatures: repeated_bytes,
+});
+
+impl_proto_json!(TxBody {
+    messages: repeated,
+    memo: string,
+    timeout_height: uint64,
+    extension_options: repeated,
+    non_critical_extension_options: repeated,
+});
+
+impl_proto_json!(AuthInfo {
+    signer_infos: repeated,
+    fee: message,
+});
+
+impl_proto_json!(SignDoc {
+    body_bytes: bytes,
+    auth_info_bytes: bytes,
+    chain_id: string,
+    account_number: uint64,
+});
+
+impl_proto_json!(SignerInfo {
+    public_key: message,
+    mode_info: message,
+    sequence: uint64,
+});
+
+impl_proto_json!(Single {
+    mode: enumeration(SIGN_MODE),
+});
+
+impl_proto_json!(Multi {
+    bitarray: message,
+    mode_infos: repeated,
+});
+
+impl_proto_json!(CompactBitArray {
+    extra_bits_stored: uint32,
+    elems: bytes,
+});
+
+impl_proto_json!(Fee {
+    amount: repeated,
+    gas_limit: uint64,
+    payer: string,
+    granter: string,
+});
+
+impl_proto_json!(Coin {
+    denom: string,
+    amount: string,
+});
+
+impl_proto_json!(MsgSend {
+    from_address: string,
+    to_address: string,
+    amount: repeated,
+});
+
+impl_proto_json!(MsgGrantAllowance {
+    granter: string,
+    grantee: string,
+    allowance: message,
+});
+
+impl_proto_json!(MsgRevokeAllowance {
+    granter: string,
+    grantee: string,
+});
+
+impl_proto_json!(BasicAllowance {
+    spend_limit: repeated,
+    expiration: timestamp,
+});
+
+impl_proto_json!(Secp256k1PubKey { key: bytes });
+
+impl_proto_json!(Ed25519PubKey { key: bytes });
+
+impl_proto_json!(LegacyAminoPubKey {
+    threshold: uint32,
+    public_keys: repeated,
+});
+
+#[cfg(feature = "ethermint")]
+impl_proto_json!(EthSecp256k1PubKey { key: bytes });
+
+#[cfg(feature = "ethermint")]
+impl_proto_json!(ExtensionOptionsWeb3Tx {
+    typed_data_chain_id: uint64,
+    fee_payer: string,
+    fee_payer_sig: bytes,
+});
+
+// ibc-go
+
+impl_proto_json!(Height {
+    revision_number: uint64,
+    revision_height: uint64,
+});
+
+impl_proto_json!(MsgCreateClient {
+    client_state: message,
+    consensus_state: message,
+    signer: string,
+});
+
+impl_proto_json!(MsgUpdateClient {
+    client_id: string,
+    header: message,
+    signer: string,
+});
+
+impl_proto_json!(MerklePrefix { key_prefix: bytes });
+
+impl_proto_json!(MerkleRoot { hash: bytes });
+
+impl_proto_json!(ConnectionCounterparty {
+    client_id: string,
+    connection_id: string,
+    prefix: message,
+});
+
+impl_proto_json!(Version {
+    identifier: string,
+    features: repeated_string,
+});
+
+impl_proto_json!(ConnectionEnd {
+    client_id: string,
+    versions: repeated,
+    state: enumeration(CONNECTION_STATE),
+    counterparty: message,
+    delay_period: uint64,
+});
+
+impl_proto_json!(MsgConnectionOpenInit {
+    client_id: string,
+    counterparty: message,
+    version: message,
+    delay_period: uint64,
+    signer: string,
+});
+
+impl_proto_json!(MsgConnectionOpenAck {
+    connection_id: string,
+    counterparty_connection_id: string,
+    version: message,
+    client_state: message,
+    proof_height: message,
+    proof_try: bytes,
+    proof_client: bytes,
+    proof_consensus: bytes,
+    consensus_height: message,
+    signer: string,
+});
+
+impl_proto_json!(ChannelCounterparty {
+    port_id: string,
+    channel_id: string,
+});
+
+impl_proto_json!(Channel {
+    state: enumeration(CHANNEL_STATE),
+    ordering: enumeration(CHANNEL_ORDER),
+    counterparty: message,
+    connection_hops: repeated_string,
+    version: string,
+});
+
+impl_proto_json!(MsgChannelOpenInit {
+    port_id: string,
+    channel: message,
+    signer: string,
+});
+
+impl_proto_json!(MsgChannelOpenAck {
+    port_id: string,
+    channel_id: string,
+    counterparty_channel_id: string,
+    counterparty_version: string,
+    proof_try: bytes,
+    proof_height: message,
+    signer: string,
+});
+
+impl_proto_json!(Packet {
+    sequence: uint64,
+    source_port: string,
+    source_channel: string,
+    destination_port: string,
+    destination_channel: string,
+    data: bytes,
+    timeout_height: message,
+    timeout_timestamp: uint64,
+});
+
+impl_proto_json!(MsgRecvPacket {
+    packet: message,
+    proof_commitment: bytes,
+    proof_height: message,
+    signer: string,
+});
+
+impl_proto_json!(MsgAcknowledgement {
+    packet: message,
+    acknowledgement: bytes,
+    proof_acked: bytes,
+    proof_height: message,
+    signer: string,
+});
+
+impl_proto_json!(MsgTimeout {
+    packet: message,
+    proof_unreceived: bytes,
+    proof_height: message,
+    next_sequence_recv: uint64,
+    signer: string,
+});
+
+impl_proto_json!(MsgTransfer {
+    source_port: string,
+    source_channel: string,
+    token: message,
+    sender: string,
+    receiver: string,
+    timeout_height: message,
+    timeout_timestamp: uint64,
+});
+
+impl_proto_json!(Fraction {
+    numerator: uint64,
+    denominator: uint64,
+});
+
+impl_proto_json!(LeafOp {
+    hash: enumeration(HASH_OP),
+    prehash_key: enumeration(HASH_OP),
+    prehash_value: enumeration(HASH_OP),
+    length: enumeration(LENGTH_OP),
+    prefix: bytes,
+});
+
+impl_proto_json!(InnerSpec {
+    child_order: repeated_int32,
+    child_size: int32,
+    min_prefix_length: int32,
+    max_prefix_length: int32,
+    empty_child: bytes,
+    hash: enumeration(HASH_OP),
+});
+
+impl_proto_json!(ProofSpec {
+    leaf_spec: message,
+    inner_spec: message,
+    max_depth: int32,
+    min_depth: int32,
+});
+
+impl_proto_json!(TendermintClientState {
+    chain_id: string,
+    trust_level: message,
+    trusting_period: duration,
+    unbonding_period: duration,
+    max_clock_drift: duration,
+    frozen_height: message,
+    latest_height: message,
+    proof_specs: repeated,
+    upgrade_path: repeated_string,
+    allow_update_after_expiry: boolean,
+    allow_update_after_misbehaviour: boolean,
+});
+
+impl_proto_json!(TendermintConsensusState {
+    timestamp: timestamp,
+    root: message,
+    next_validators_hash: bytes,
+});
+
+impl_proto_json!(solomachine_v1::ClientState {
+    sequence: uint64,
+    frozen_sequence: uint64,
+    consensus_state: message,
+    allow_update_after_proposal: boolean,
+});
+
+impl_proto_json!(solomachine_v1::ConsensusState {
+    public_key: message,
+    diversifier: string,
+    timestamp: uint64,
+});
+
+impl_proto_json!(solomachine_v1::Header {
+    sequence: uint64,
+    timestamp: uint64,
+    signature: bytes,
+    new_public_key: message,
+    new_diversifier: string,
+});
+
+impl_proto_json!(solomachine_v2::ClientState {
+    sequence: uint64,
+    is_frozen: boolean,
+    consensus_state: message,
+    allow_update_after_proposal: boolean,
+});
+
+impl_proto_json!(solomachine_v2::ConsensusState {
+    public_key: message,
+    diversifier: string,
+    timestamp: uint64,
+});
+
+impl_proto_json!(solomachine_v2::Header {
+    sequence: uint64,
+    timestamp: uint64,
+    signature: bytes,
+    new_public_key: message,
+    new_diversifier: string,
+});
+
+impl_proto_json!(solomachine_v2::TimestampedSignatureData {
+    signature_data: bytes,
+    timestamp: uint64,
+});
+
+impl_proto_json!(solomachine_v2::SignBytes {
+    sequence: uint64,
+    timestamp: uint64,
+    diversifier: string,
+    data_type: enumeration(DATA_TYPE),
+    data: bytes,
+});
+
+impl_proto_json!(solomachine_v2::HeaderData {
+    new_pub_key: message,
+    new_diversifier: string,
+});
+
+impl_proto_json!(solomachine_v2::ClientStateData {
+    path: bytes,
+    client_state: message,
+});
+
+impl_proto_json!(solomachine_v2::ConsensusStateData {
+    path: bytes,
+    consensus_state: message,
+});
+
+impl_proto_json!(solomachine_v2::ConnectionStateData {
+    path: bytes,
+    connection: message,
+});
+
+impl_proto_json!(solomachine_v2::ChannelStateData {
+    path: bytes,
+    channel: message,
+});
+
+impl_proto_json!(solomachine_v2::PacketCommitmentData {
+    path: bytes,
+    commitment: bytes,
+});
+
+impl_proto_json!(solomachine_v2::PacketAcknowledgementData {
+    path: bytes,
+    acknowledgement: bytes,
+});
+
+impl_proto_json!(solomachine_v2::PacketReceiptAbsenceData { path: bytes });
+
+impl_proto_json!(solomachine_v2::NextSequenceRecvData {
+    path: bytes,
+    next_seq_recv: uint64,
+});
+
+impl_proto_json!(solomachine_v3::ClientState {
+    sequence: uint64,
+    is_frozen: boolean,
+    consensus_state: message,
+});
+
+impl_proto_json!(solomachine_v3::ConsensusState {
+    public_key: message,
+    diversifier: string,
+    timestamp: uint64,
+});
+
+impl_proto_json!(solomachine_v3::Header {
+    timestamp: uint64,
+    signature: bytes,
+    new_public_key: message,
+    new_diversifier: string,
+});
+
+impl_proto_json!(solomachine_v3::TimestampedSignatureData {
+    signature_data: bytes,
+    timestamp: uint64,
+});
+
+impl_proto_json!(solomachine_v3::SignBytes {
+    sequence: uint64,
+    timestamp: uint64,
+    diversifier: string,
+    path: bytes,
+    data: bytes,
+});
+
+impl_proto_json!(solomachine_v3::HeaderData {
+    new_pub_key: message,
+    new_diversifier: string,
+});
+
+impl_proto_json!(wasm_v1::ClientState {
+    data: bytes,
+    checksum: bytes,
+    latest_height: message,
+});
+
+impl_proto_json!(wasm_v1::ConsensusState { data: bytes });
+
+impl_proto_json!(wasm_v1::ClientMessage { data: bytes });
+
+/// Proto3 JSON mapping of field types
+mod field {
+    use std::convert::TryFrom;
+
+    use anyhow::{anyhow, Result};
+    use serde_json::Value;
+
+    use super::{Duration, ProtoJson, SecondsFormat, TimeZone, Timestamp, Utc};
+
+    pub fn string(value: &str) -> Result<Value> {
+        Ok(Value::String(value.to_owned()))
+    }
+
+    pub fn bytes(value: &[u8]) -> Result<Value> {
+        Ok(Value::String(base64::encode(value)))
+    }
+
+    pub fn boolean(value: &bool) -> Result<Value> {
+        Ok(Value::Bool(*value))
+    }
+
+    pub fn int32(value: &i32) -> Result<Value> {
+        Ok(Value::from(*value))
+    }
+
+    pub fn uint32(value: &u32) -> Result<Value> {
+        Ok(Value::from(*value))
+    }
+
+    /// 64-bit integers are rendered as strings
+    pub fn uint64(value: &u64) -> Result<Value> {
+        Ok(Value::String(value.to_string()))
+    }
+
+    /// Enum values are rendered using their names (unknown values are rendered as numbers)
+    pub fn enumeration(value: &i32, names: &[(i32, &str)]) -> Result<Value> {
+        Ok(names
+            .iter()
+            .find(|(number, _)| number == value)
+            .map(|(_, name)| Value::String((*name).to_owned()))
+            .unwrap_or_else(|| Value::from(*value)))
+    }
+
+    pub fn message<T: ProtoJson>(value: &Option<T>) -> Result<Value> {
+        value
+            .as_ref()
+            .map(ProtoJson::to_proto_json)
+            .unwrap_or(Ok(Value::Null))
+    }
+
+    pub fn repeated<T: ProtoJson>(values: &[T]) -> Result<Value> {
+        values
+            .iter()
+            .map(ProtoJson::to_proto_json)
+            .collect::<Result<_>>()
+            .map(Value::Array)
+    }
+
+    pub fn repeated_string(values: &[String]) -> Result<Value> {
+        values.iter().map(|value| string(value)).collect()
+    }
+
+    pub fn repeated_bytes(values: &[Vec<u8>]) -> Result<Value> {
+        values.iter().map(|value| bytes(value)).collect()
+    }
+
+    pub fn repeated_int32(values: &[i32]) -> Result<Value> {
+        values.iter().map(int32).collect()
+    }
+
+    /// Timestamps are rendered in RFC 3339 format (e.g. `2021-01-01T00:00:00Z`)
+    pub fn timestamp(value: &Option<Timestamp>) -> Result<Value> {
+        match value {
+            None => Ok(Value::Null),
+            Some(timestamp) => {
+                let nanos = u32::try_from(timestamp.nanos)
+                    .map_err(|_| anyhow!("invalid timestamp: {:?}", timestamp))?;
+                let date_time = Utc
+                    .timestamp_opt(timestamp.seconds, nanos)
+                    .single()
+                    .ok_or_else(|| anyhow!("invalid timestamp: {:?}", timestamp))?;
+
+                Ok(Value::String(
+                    date_time.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                ))
+            }
+        }
+    }
+
+    /// Durations are rendered in seconds with `s` suffix (e.g. `1.500s`)
+    pub fn duration(value: &Option<Duration>) -> Result<Value> {
+        match value {
+            None => Ok(Value::Null),
+            Some(duration) if duration.nanos == 0 => {
+                Ok(Value::String(format!("{}s", duration.seconds)))
+            }
+            Some(duration) => {
+                let sign = if duration.seconds < 0 || duration.nanos < 0 {
+                    "-"
+                } else {
+                    ""
+                };
+                let mut nanos = format!("{:09}", duration.nanos.unsigned_abs());
+
+                // fractional part is rendered with 3, 6 or 9 digits
+                while nanos.ends_with("000") {
+                    nanos.truncate(nanos.len() - 3);
+                }
+
+                Ok(Value::String(format!(
+                    "{}{}.{}s",
+                    sign,
+                    duration.seconds.unsigned_abs(),
+                    nanos
+                )))
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Rate limiting of requests made to RPC and gRPC endpoints of chains (so that polling public nodes does not get the
+//! operator banned)
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use anyhow::{ensure, Result};
+use futures::future::poll_fn;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use tonic::codegen::Service;
+
+/// Token buckets of endpoints (keyed by address) shared by all the operations of current process
+static BUCKETS: Mutex<BTreeMap<String, TokenBucket>> = Mutex::new(BTreeMap::new());
+
+/// Token bucket rate limit of requests made to an endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Sustained number of requests per second
+    pub requests_per_second: f64,
+    /// Maximum number of requests which can be made in a burst (capacity of token bucket)
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// Creates a new rate limit (burst defaults to one second worth of requests)
+    pub fn new(requests_per_second: f64, burst: Option<u32>) -> Self {
+        Self {
+            requests_per_second,
+            burst: burst.unwrap_or_else(|| requests_per_second.ceil().max(1.0) as u32),
+        }
+    }
+
+    /// Ensures that rate limit is valid
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.requests_per_second.is_finite() && self.requests_per_second > 0.0,
+            "rate limit should be greater than zero: {}",
+            self.requests_per_second
+        );
+        ensure!(
+            self.burst > 0,
+            "rate limit burst should be greater than zero"
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Waits until a request can be made to endpoint at given address without exceeding given rate limit (returns
+/// immediately when no rate limit is given). Tokens are reserved before waiting, so, concurrent requests are served in
+/// the order in which they arrive.
+pub async fn acquire(addr: &str, rate_limit: Option<&RateLimit>) {
+    let rate_limit = match rate_limit {
+        None => return,
+        Some(rate_limit) => rate_limit,
+    };
+
+    let wait = {
+        let now = Instant::now();
+        let burst = f64::from(rate_limit.burst);
+
+        let mut buckets = BUCKETS.lock().unwrap();
+        let bucket = buckets
+            .entry(addr.to_string())
+            .or_insert_with(|| TokenBucket {
+                tokens: burst,
+                updated_at: now,
+            });
+
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens =
+            (bucket.tokens + elapsed.as_secs_f64() * rate_limit.requests_per_second).min(burst);
+        bucket.updated_at = now;
+        bucket.tokens -= 1.0;
+
+        if bucket.tokens >= 0.0 {
+            return;
+        }
+
+        Duration::from_secs_f64(-bucket.tokens / rate_limit.requests_per_second)
+    };
+
+    tracing::debug!(endpoint = addr, "rate limiting request for {:?}", wait);
+    tokio::time::sleep(wait).await;
+}
+
+/// Service (wrapping a gRPC channel) which waits for rate limit of endpoint before every request
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimited<S> {
+    inner: S,
+    addr: String,
+    rate_limit: Option<RateLimit>,
+}
+
+impl<S> RateLimited<S> {
+    /// Wraps given service making requests to endpoint at given address
+    pub(crate) fn new(inner: S, addr: &str, rate_limit: Option<RateLimit>) -> Self {
+        Self {
+            inner,
+            addr: addr.to_string(),
+            rate_limit,
+        }
+    }
+}
+
+impl<S, R> Service<R> for RateLimited<S>
+where
+    S: Service<R> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    R: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        if self.rate_limit.is_none() {
+            return Box::pin(self.inner.call(request));
+        }
+
+        // service made ready by `poll_ready` is moved into the future (leaving a clone in its place)
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let addr = self.addr.clone();
+        let rate_limit = self.rate_limit.clone();
+
+        Box::pin(async move {
+            acquire(&addr, rate_limit.as_ref()).await;
+            poll_fn(|cx| inner.poll_ready(cx)).await?;
+            inner.call(request).await
+        })
+    }
+}
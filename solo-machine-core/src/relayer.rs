@@ -0,0 +1,648 @@
+//! Daemon mode relayer which watches connected chains, keeps tendermint clients on solo machine fresh and
+//! acknowledges packets sent to solo machine (as soon as their `send_packet` events are observed over websocket and, as a
+//! catch-up, in every relaying round)
+pub(crate) mod engine;
+
+pub use self::engine::{RelayerEngine, RelayerHandle};
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use cosmos_sdk_proto::ibc::core::channel::v1::Packet;
+use futures::future::join_all;
+use tendermint_rpc::Client;
+use tokio::{
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    time::sleep,
+};
+
+use crate::{
+    event::{notify_event, Event},
+    failover::select_rpc_addr,
+    ibc::core::ics24_host::identifier::ChainId,
+    model::{chain, pending_operation, Chain},
+    plugins::Plugins,
+    rpc_client::TimeoutRpcClient,
+    service::{status_service::client_status, ChainService, IbcService, PacketRelayOutcome},
+    subscriber::{EventSubscriber, IbcEvent, IbcEventHandler},
+    supervisor::CancellationToken,
+    DbPool, Signer,
+};
+
+/// Tendermint client on solo machine is refreshed when its latest consensus state is older than
+/// `trusting_period / CLIENT_REFRESH_DIVISOR`
+const CLIENT_REFRESH_DIVISOR: u32 = 3;
+
+/// Tendermint client on solo machine is reported to be nearing expiry when it expires in less than
+/// `trusting_period / CLIENT_EXPIRY_WARNING_DIVISOR`
+const CLIENT_EXPIRY_WARNING_DIVISOR: u32 = 6;
+
+/// Configuration of relayer
+#[derive(Debug, Clone)]
+pub struct RelayerConfig {
+    /// Interval between relaying rounds
+    pub interval: Duration,
+    /// Chains to relay for (all the connected chains when empty)
+    pub chain_ids: Vec<ChainId>,
+    /// Memo used in transactions sent by relayer
+    pub memo: String,
+    /// Whether to subscribe to events of chains over websocket and acknowledge packets as soon as they are sent
+    pub subscribe: bool,
+}
+
+/// Relays for connected chains in rounds until cancelled. In each round, for every chain:
+///
+/// - chain is skipped if it is halted (i.e. latest block is older than chain's halt threshold) or if it has been reset
+///   (checked in first round and after a failed round, e.g. a devnet restarted from a new genesis)
+/// - tendermint client on solo machine is refreshed when its latest consensus state is older than a third of trusting
+///   period (and is reported once when it expires in less than a sixth of trusting period, e.g. when it cannot be
+///   refreshed)
+/// - pending packets sent to solo machine are acknowledged (packets whose timeout has passed on solo machine are timed
+///   out, refunding their senders on chain)
+///
+/// When subscribing to events, packets sent to solo machine are also acknowledged as soon as their `send_packet` events
+/// are observed, in between the rounds.
+pub struct Relayer {
+    db_pool: DbPool,
+    notifier: Option<UnboundedSender<Event>>,
+    config: RelayerConfig,
+    plugins: Plugins,
+    states: Mutex<HashMap<ChainId, ChainState>>,
+}
+
+/// Relaying status of a chain (as of last relaying round)
+#[derive(Debug, Clone)]
+pub struct ChainRelayStatus {
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+    /// Block height of chain up to which pending packets are relayed
+    pub relayed_height: u64,
+    /// Whether chain was halted in last round
+    pub halted: bool,
+    /// Whether chain is found to be reset (chain is skipped until its state is reset on solo machine)
+    pub reset_detected: bool,
+}
+
+/// Control messages accepted by a running relayer
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum RelayerControl {
+    /// Starts next relaying round immediately
+    RelayNow,
+    /// Pauses relaying (rounds and forwarded packets are skipped) until resumed
+    Pause,
+    /// Resumes relaying and starts next relaying round immediately
+    Resume,
+}
+
+/// In-memory relaying state of a chain
+#[derive(Debug, Default, Clone)]
+struct ChainState {
+    /// Block height of chain up to which pending packets are relayed
+    relayed_height: u64,
+    /// Whether chain was halted in last round
+    halted: bool,
+    /// Whether chain is checked for a reset since last failed round
+    reset_checked: bool,
+    /// Whether chain is found to be reset (chain is skipped until its state is reset on solo machine)
+    reset_detected: bool,
+    /// Whether tendermint client of chain is reported to be nearing expiry (since it was last refreshed)
+    expiry_notified: bool,
+}
+
+impl Relayer {
+    /// Creates a new instance of relayer
+    pub fn new(db_pool: DbPool, config: RelayerConfig) -> Self {
+        Self {
+            db_pool,
+            notifier: None,
+            config,
+            plugins: Default::default(),
+            states: Default::default(),
+        }
+    }
+
+    /// Creates a new instance of relayer with notifier
+    pub fn new_with_notifier(
+        db_pool: DbPool,
+        notifier: UnboundedSender<Event>,
+        config: RelayerConfig,
+    ) -> Self {
+        Self {
+            db_pool,
+            notifier: Some(notifier),
+            config,
+            plugins: Default::default(),
+            states: Default::default(),
+        }
+    }
+
+    /// Uses given plugins for chains with `plugin` fee strategy or client encoding
+    pub fn with_plugins(mut self, plugins: Plugins) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
+    /// Runs relaying rounds at configured interval until cancellation token is cancelled. In every round, all the chains
+    /// are relayed for concurrently. Failures for a chain are reported as warnings and do not stop relaying for other
+    /// chains. Cancellation is only observed between rounds (and between packets) so that a transaction signed by
+    /// relayer is always recorded before stopping.
+    ///
+    /// When subscribing to events, chains to subscribe to are determined once on start (chains connected later are
+    /// only relayed for in rounds).
+    pub async fn run(&self, signer: impl Signer, token: CancellationToken) -> Result<()> {
+        let (_control_sender, control) = unbounded_channel();
+        self.run_controlled(signer, token, control).await
+    }
+
+    /// Returns relaying status of chains relayed for so far
+    pub fn chain_statuses(&self) -> Vec<ChainRelayStatus> {
+        let mut statuses: Vec<ChainRelayStatus> = self
+            .states
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(chain_id, state)| ChainRelayStatus {
+                chain_id: chain_id.clone(),
+                relayed_height: state.relayed_height,
+                halted: state.halted,
+                reset_detected: state.reset_detected,
+            })
+            .collect();
+
+        statuses.sort_by_key(|status| status.chain_id.to_string());
+        statuses
+    }
+
+    /// Same as `run` but also accepts control messages (e.g. for pausing relaying) while running
+    pub(crate) async fn run_controlled(
+        &self,
+        signer: impl Signer,
+        token: CancellationToken,
+        mut control: UnboundedReceiver<RelayerControl>,
+    ) -> Result<()> {
+        let (sender, mut receiver) = unbounded_channel();
+
+        let subscribers = if self.config.subscribe {
+            self.subscribers().await?
+        } else {
+            Vec::new()
+        };
+
+        let forwarder = PacketForwarder {
+            channels: subscribers
+                .iter()
+                .map(|(chain_id, _, channel)| (chain_id.clone(), channel.clone()))
+                .collect(),
+            sender,
+        };
+
+        // subscriptions only forward packets to relaying loop (they never sign anything), so, they are stopped as soon
+        // as relaying loop stops
+        let subscription_token = CancellationToken::new();
+
+        let rounds = async {
+            let result = self
+                .run_rounds(&signer, &token, &mut receiver, &mut control)
+                .await;
+            subscription_token.cancel();
+            result
+        };
+
+        let subscriptions = join_all(
+            subscribers
+                .iter()
+                .map(|(_, subscriber, _)| subscriber.run(&forwarder, subscription_token.clone())),
+        );
+
+        let (result, subscription_results) = tokio::join!(rounds, subscriptions);
+
+        subscription_results.into_iter().collect::<Result<()>>()?;
+        result
+    }
+
+    /// Runs a single relaying round for given chain
+    pub async fn relay(&self, signer: impl Signer, chain_id: &ChainId) -> Result<()> {
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        ensure!(
+            chain.connection_details.is_some(),
+            "connection is not established with chain {}",
+            chain_id
+        );
+
+        if !self.is_producing_blocks(&chain).await? || self.is_reset(chain_id).await? {
+            return Ok(());
+        }
+
+        let ibc_service = self.ibc_service();
+
+        // a stale tendermint client does not prevent acknowledging packets, so, failure to refresh it is only reported
+        if let Err(err) = ibc_service
+            .refresh_tendermint_client(
+                chain_id,
+                chain.config.trusting_period / CLIENT_REFRESH_DIVISOR,
+            )
+            .await
+        {
+            notify_event(
+                &self.notifier,
+                Event::Warning {
+                    message: format!(
+                        "unable to refresh tendermint client of chain {}: {:#}",
+                        chain_id, err
+                    ),
+                },
+            )?;
+        }
+
+        self.check_client_expiry(&chain).await?;
+
+        let mut state = self.state(chain_id);
+
+        let report = ibc_service
+            .relay_pending_packets(
+                signer,
+                chain_id,
+                state.relayed_height,
+                self.config.memo.clone(),
+            )
+            .await?;
+
+        state.relayed_height = report.relayed_height;
+        self.set_state(chain_id, state);
+
+        Ok(())
+    }
+
+    /// Acknowledges a packet sent to solo machine (e.g. when its `send_packet` event is observed), or times it out if
+    /// its timeout has passed on solo machine
+    pub async fn relay_packet(
+        &self,
+        signer: impl Signer,
+        chain_id: &ChainId,
+        packet: Packet,
+    ) -> Result<PacketRelayOutcome> {
+        self.ibc_service()
+            .relay_packet(signer, chain_id, packet, self.config.memo.clone())
+            .await
+    }
+
+    /// Runs relaying rounds and relays packets forwarded by subscriptions in between the rounds
+    async fn run_rounds(
+        &self,
+        signer: impl Signer,
+        token: &CancellationToken,
+        receiver: &mut UnboundedReceiver<(ChainId, Packet)>,
+        control: &mut UnboundedReceiver<RelayerControl>,
+    ) -> Result<()> {
+        let mut paused = false;
+
+        loop {
+            let chain_ids = if paused {
+                Vec::new()
+            } else {
+                self.chain_ids().await?
+            };
+
+            if token.is_cancelled() {
+                return Ok(());
+            }
+
+            // chains are relayed for concurrently (operations on a chain are serialized using its chain lock)
+            let results = join_all(
+                chain_ids
+                    .iter()
+                    .map(|chain_id| self.relay(&signer, chain_id)),
+            )
+            .await;
+
+            for (chain_id, result) in chain_ids.into_iter().zip(results) {
+                if let Err(err) = result {
+                    notify_event(
+                        &self.notifier,
+                        Event::Warning {
+                            message: format!("unable to relay for chain {}: {:#}", chain_id, err),
+                        },
+                    )?;
+
+                    // failures are often caused by a reset of chain
+                    let mut state = self.state(&chain_id);
+                    state.reset_checked = false;
+                    self.set_state(&chain_id, state);
+                }
+            }
+
+            let next_round = sleep(self.config.interval);
+            tokio::pin!(next_round);
+
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => return Ok(()),
+                    _ = &mut next_round => break,
+                    Some(control) = control.recv() => match control {
+                        RelayerControl::RelayNow => break,
+                        RelayerControl::Pause => paused = true,
+                        RelayerControl::Resume => {
+                            paused = false;
+                            break;
+                        }
+                    },
+                    Some((chain_id, packet)) = receiver.recv() => {
+                        // packets skipped while paused are relayed in rounds after resuming
+                        if paused {
+                            continue;
+                        }
+
+                        let packet_sequence = packet.sequence;
+
+                        if let Err(err) = self.relay_packet(&signer, &chain_id, packet).await {
+                            notify_event(
+                                &self.notifier,
+                                Event::Warning {
+                                    message: format!(
+                                        "unable to relay packet {} of {} (it is retried in next round): {:#}",
+                                        packet_sequence, chain_id, err
+                                    ),
+                                },
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns subscribers (along with the port and channel on which chain sends packets to solo machine) of chains to
+    /// relay for
+    async fn subscribers(&self) -> Result<Vec<(ChainId, EventSubscriber, (String, String))>> {
+        let mut subscribers = Vec::new();
+
+        for chain_id in self.chain_ids().await? {
+            let chain = chain::get_chain(&self.db_pool, &chain_id)
+                .await?
+                .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+            let connection_details = match chain.connection_details {
+                Some(ref connection_details) => connection_details,
+                None => continue,
+            };
+
+            // websocket connections cannot be tunnelled through proxy, packets of such chains are relayed in rounds
+            if chain.config.proxy.is_some() {
+                tracing::warn!(
+                    chain_id = %chain_id,
+                    "not subscribing to events of chain as it is configured with a proxy"
+                );
+                continue;
+            }
+
+            let rpc_addr = select_rpc_addr(&chain.config).await;
+
+            let subscriber = match self.notifier {
+                None => EventSubscriber::new(chain_id.clone(), &rpc_addr),
+                Some(ref notifier) => EventSubscriber::new_with_notifier(
+                    chain_id.clone(),
+                    &rpc_addr,
+                    notifier.clone(),
+                ),
+            };
+
+            subscribers.push((
+                chain_id,
+                subscriber,
+                (
+                    chain.config.port_id.to_string(),
+                    connection_details.solo_machine_channel_id.to_string(),
+                ),
+            ));
+        }
+
+        Ok(subscribers)
+    }
+
+    fn ibc_service(&self) -> IbcService {
+        let ibc_service = match self.notifier {
+            None => IbcService::new(self.db_pool.clone()),
+            Some(ref notifier) => {
+                IbcService::new_with_notifier(self.db_pool.clone(), notifier.clone())
+            }
+        };
+
+        ibc_service.with_plugins(self.plugins.clone())
+    }
+
+    /// Returns IDs of chains to relay for
+    async fn chain_ids(&self) -> Result<Vec<ChainId>> {
+        if !self.config.chain_ids.is_empty() {
+            return Ok(self.config.chain_ids.clone());
+        }
+
+        Ok(chain::get_chains(&self.db_pool)
+            .await?
+            .into_iter()
+            .filter(|chain| chain.connection_details.is_some())
+            .map(|chain| chain.id)
+            .collect())
+    }
+
+    /// Returns `true` if chain is producing blocks (i.e. latest block is not older than chain's halt threshold) and
+    /// notifies when chain halts or resumes
+    async fn is_producing_blocks(&self, chain: &Chain) -> Result<bool> {
+        let halt_threshold = chain.config.halt_threshold;
+
+        if halt_threshold == Duration::from_secs(0) {
+            return Ok(true);
+        }
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        let status = rpc_client
+            .status()
+            .await
+            .context("unable to query status of chain")?;
+
+        let latest_height = status.sync_info.latest_block_height.value();
+        let latest_block_time: DateTime<Utc> =
+            SystemTime::from(status.sync_info.latest_block_time).into();
+
+        let halted = (Utc::now() - latest_block_time)
+            .to_std()
+            .unwrap_or_default()
+            > halt_threshold;
+
+        let mut state = self.state(&chain.id);
+
+        if halted != state.halted {
+            let event = if halted {
+                Event::ChainHalted {
+                    chain_id: chain.id.clone(),
+                    latest_height,
+                    latest_block_time,
+                }
+            } else {
+                Event::ChainResumed {
+                    chain_id: chain.id.clone(),
+                    latest_height,
+                }
+            };
+
+            notify_event(&self.notifier, event)?;
+
+            state.halted = halted;
+            self.set_state(&chain.id, state);
+        }
+
+        Ok(!halted)
+    }
+
+    /// Notifies when tendermint client of chain on solo machine is nearing expiry (only once until it is refreshed)
+    async fn check_client_expiry(&self, chain: &Chain) -> Result<()> {
+        let connection_details = match chain.connection_details {
+            Some(ref connection_details) => connection_details,
+            None => return Ok(()),
+        };
+
+        let client = client_status(
+            &self.db_pool,
+            &connection_details.tendermint_client_id,
+            chain.config.trusting_period,
+        )
+        .await?;
+
+        let warning_period = chrono::Duration::from_std(
+            chain.config.trusting_period / CLIENT_EXPIRY_WARNING_DIVISOR,
+        )
+        .context("invalid trusting period")?;
+        let nearing_expiry = client.expires_at - Utc::now() < warning_period;
+
+        let mut state = self.state(&chain.id);
+
+        if nearing_expiry && !state.expiry_notified {
+            notify_event(
+                &self.notifier,
+                Event::ClientNearingExpiry {
+                    chain_id: chain.id.clone(),
+                    client_id: client.client_id,
+                    expires_at: client.expires_at,
+                },
+            )?;
+        }
+
+        if nearing_expiry != state.expiry_notified {
+            state.expiry_notified = nearing_expiry;
+            self.set_state(&chain.id, state);
+        }
+
+        Ok(())
+    }
+
+    /// Notifies when local sequence of chain diverged from sequence of solo machine client on chain (unless chain has
+    /// unresolved pending operations, whose signatures may still be included on chain)
+    async fn check_sequence(&self, chain_service: &ChainService, chain_id: &ChainId) -> Result<()> {
+        if !pending_operation::get_unresolved_operations(&self.db_pool, chain_id)
+            .await?
+            .is_empty()
+        {
+            return Ok(());
+        }
+
+        let status = chain_service.check_sequence(chain_id).await?;
+
+        if !status.is_in_sync() {
+            notify_event(
+                &self.notifier,
+                Event::SequenceDiverged {
+                    chain_id: chain_id.clone(),
+                    client_id: status.client_id,
+                    local: status.local,
+                    on_chain: status.on_chain,
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if chain has been reset (chain is checked in first round and after a failed round) and notifies
+    /// when a reset or a divergence of sequence is detected
+    async fn is_reset(&self, chain_id: &ChainId) -> Result<bool> {
+        let mut state = self.state(chain_id);
+
+        if state.reset_detected || state.reset_checked {
+            return Ok(state.reset_detected);
+        }
+
+        let chain_service = ChainService::new(self.db_pool.clone());
+
+        if let Some(evidence) = chain_service.detect_reset(chain_id).await? {
+            notify_event(
+                &self.notifier,
+                Event::Warning {
+                    message: format!(
+                        "chain {} has been reset ({}), skipping it until its state is reset using `chain reset-state`",
+                        chain_id, evidence
+                    ),
+                },
+            )?;
+
+            state.reset_detected = true;
+        } else {
+            self.check_sequence(&chain_service, chain_id).await?;
+        }
+
+        state.reset_checked = true;
+        self.set_state(chain_id, state.clone());
+
+        Ok(state.reset_detected)
+    }
+
+    fn state(&self, chain_id: &ChainId) -> ChainState {
+        self.states
+            .lock()
+            .unwrap()
+            .get(chain_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn set_state(&self, chain_id: &ChainId, state: ChainState) {
+        self.states.lock().unwrap().insert(chain_id.clone(), state);
+    }
+}
+
+/// Forwards packets sent to solo machine (observed in `send_packet` events) to relaying loop
+struct PacketForwarder {
+    /// Port and channel on which chains send packets to solo machine
+    channels: HashMap<ChainId, (String, String)>,
+    sender: UnboundedSender<(ChainId, Packet)>,
+}
+
+#[async_trait]
+impl IbcEventHandler for PacketForwarder {
+    async fn handle(&self, chain_id: &ChainId, event: IbcEvent) -> Result<()> {
+        if let IbcEvent::SendPacket { packet, .. } = event {
+            let is_sent_to_solo_machine =
+                self.channels
+                    .get(chain_id)
+                    .is_some_and(|(port_id, channel_id)| {
+                        packet.source_port == *port_id && packet.source_channel == *channel_id
+                    });
+
+            if is_sent_to_solo_machine {
+                self.sender
+                    .send((chain_id.clone(), packet))
+                    .map_err(|_| anyhow!("relaying loop is stopped"))?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+//! In-process relaying engine for applications embedding solo machine core
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use tokio::{
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    task::JoinHandle,
+};
+
+use crate::{event::Event, plugins::Plugins, supervisor::CancellationToken, DbPool, Signer};
+
+use super::{ChainRelayStatus, Relayer, RelayerConfig, RelayerControl};
+
+/// Runs the relayer of daemon mode (see [`Relayer`]) as a task on the current tokio runtime, so that applications
+/// embedding solo machine core can relay without running the CLI
+pub struct RelayerEngine<S> {
+    db_pool: DbPool,
+    signer: S,
+    plugins: Plugins,
+}
+
+impl<S> RelayerEngine<S>
+where
+    S: Signer + 'static,
+{
+    /// Creates a new relaying engine which signs transactions using given signer
+    pub fn new(db_pool: DbPool, signer: S) -> Self {
+        Self {
+            db_pool,
+            signer,
+            plugins: Default::default(),
+        }
+    }
+
+    /// Uses given plugins for chains with `plugin` fee strategy or client encoding
+    pub fn with_plugins(mut self, plugins: Plugins) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
+    /// Spawns relayer with given configuration on the current tokio runtime and returns a handle for controlling it
+    /// and receiving its events
+    pub fn spawn(self, config: RelayerConfig) -> RelayerHandle {
+        let (event_sender, events) = unbounded_channel();
+        let (control, control_receiver) = unbounded_channel();
+        let token = CancellationToken::new();
+
+        let relayer = Arc::new(
+            Relayer::new_with_notifier(self.db_pool, event_sender, config)
+                .with_plugins(self.plugins),
+        );
+
+        let task = tokio::spawn({
+            let relayer = relayer.clone();
+            let token = token.clone();
+            let signer = self.signer;
+
+            async move {
+                relayer
+                    .run_controlled(signer, token, control_receiver)
+                    .await
+            }
+        });
+
+        RelayerHandle {
+            relayer,
+            control,
+            events: Some(events),
+            token,
+            task,
+        }
+    }
+}
+
+/// Handle of a relayer spawned by [`RelayerEngine`]. Dropping the handle does not stop the relayer (use `shutdown`).
+pub struct RelayerHandle {
+    relayer: Arc<Relayer>,
+    control: UnboundedSender<RelayerControl>,
+    events: Option<UnboundedReceiver<Event>>,
+    token: CancellationToken,
+    task: JoinHandle<Result<()>>,
+}
+
+impl RelayerHandle {
+    /// Takes the stream of events emitted by relayer (e.g. acknowledged packets and warnings). Events are buffered
+    /// until taken and `None` is returned once taken.
+    pub fn take_events(&mut self) -> Option<UnboundedReceiver<Event>> {
+        self.events.take()
+    }
+
+    /// Starts next relaying round immediately
+    pub fn relay_now(&self) -> Result<()> {
+        self.send(RelayerControl::RelayNow)
+    }
+
+    /// Pauses relaying until resumed (a round in progress is completed)
+    pub fn pause(&self) -> Result<()> {
+        self.send(RelayerControl::Pause)
+    }
+
+    /// Resumes a paused relayer and starts next relaying round immediately
+    pub fn resume(&self) -> Result<()> {
+        self.send(RelayerControl::Resume)
+    }
+
+    /// Returns relaying status of chains relayed for so far
+    pub fn chain_statuses(&self) -> Vec<ChainRelayStatus> {
+        self.relayer.chain_statuses()
+    }
+
+    /// Returns `true` if relayer has stopped (i.e. it is shut down or it failed)
+    pub fn is_stopped(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Stops relayer (after the chain or packet being relayed is completed) and waits for it to stop
+    pub async fn shutdown(self) -> Result<()> {
+        self.token.cancel();
+        self.task.await.context("relayer task panicked")?
+    }
+
+    fn send(&self, control: RelayerControl) -> Result<()> {
+        self.control
+            .send(control)
+            .map_err(|_| anyhow!("relayer is stopped"))
+    }
+}
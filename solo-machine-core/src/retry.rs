@@ -0,0 +1,234 @@
+//! Retrying of queries made to chains when they fail due to transient network errors
+use std::{fmt, future::Future, str::FromStr, time::Duration};
+
+use anyhow::{anyhow, ensure, Error, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tendermint_light_client::{components::io::IoError, errors::ErrorKind as LightClientErrorKind};
+use tendermint_rpc::error::Code as RpcCode;
+use tokio::time::error::Elapsed;
+use tonic::{Code, Status};
+
+/// Policy for retrying queries made to a chain (account and staking queries over gRPC, status queries over RPC and
+/// fetching of blocks by light client). Queries are retried with exponential backoff only when they fail due to
+/// transient errors (connection errors, timeouts and retryable gRPC status codes).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts (including the first one, `1` disables retries)
+    pub max_attempts: u32,
+    /// Backoff before first retry (doubled after every retry)
+    pub initial_backoff: Duration,
+    /// Maximum backoff between retries
+    pub max_backoff: Duration,
+    /// Fraction of backoff randomly added to or subtracted from it (between `0` and `1`)
+    pub jitter: f64,
+    /// gRPC status codes on which queries are retried
+    pub retryable_codes: Vec<RetryableCode>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            jitter: 0.2,
+            retryable_codes: vec![
+                RetryableCode::Unavailable,
+                RetryableCode::DeadlineExceeded,
+                RetryableCode::ResourceExhausted,
+                RetryableCode::Aborted,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Ensures that policy is valid
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.max_attempts > 0,
+            "maximum retry attempts should be greater than zero"
+        );
+        ensure!(
+            (0.0..=1.0).contains(&self.jitter),
+            "retry jitter should be between 0 and 1: {}",
+            self.jitter
+        );
+        ensure!(
+            self.initial_backoff <= self.max_backoff,
+            "initial retry backoff should not be greater than maximum retry backoff"
+        );
+
+        Ok(())
+    }
+
+    /// Returns backoff before given retry (starting from `1`)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(31);
+        let backoff = self
+            .initial_backoff
+            .checked_mul(1 << exponent)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+
+        if self.jitter <= 0.0 {
+            return backoff;
+        }
+
+        let factor = rand::thread_rng().gen_range((1.0 - self.jitter)..=(1.0 + self.jitter));
+        backoff.mul_f64(factor)
+    }
+
+    /// Returns `true` if given error is transient and the failed query should be retried
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        error.chain().any(|cause| {
+            if let Some(status) = cause.downcast_ref::<Status>() {
+                return self
+                    .retryable_codes
+                    .iter()
+                    .any(|code| code.code() == status.code());
+            }
+
+            if cause.is::<tonic::transport::Error>() || cause.is::<Elapsed>() {
+                return true;
+            }
+
+            if let Some(error) = cause.downcast_ref::<tendermint_rpc::Error>() {
+                return is_retryable_rpc_code(error.code());
+            }
+
+            if let Some(error) = cause.downcast_ref::<tendermint_light_client::errors::Error>() {
+                return match error.kind() {
+                    LightClientErrorKind::Io(IoError::Timeout(_)) => true,
+                    LightClientErrorKind::Io(IoError::RpcError(error)) => {
+                        is_retryable_rpc_code(error.code())
+                    }
+                    _ => false,
+                };
+            }
+
+            false
+        })
+    }
+
+    /// Runs given query, retrying it according to policy when it fails due to transient errors
+    pub async fn retry<T, F, Fut>(&self, description: &str, mut query: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            match query().await {
+                Ok(value) => return Ok(value),
+                Err(error) => {
+                    if !self.wait_before_retry(description, attempt, &error).await {
+                        return Err(error);
+                    }
+
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Waits for backoff before retrying a query which failed with given error on given attempt (starting from `1`).
+    /// Returns `false` without waiting if the query should not be retried.
+    pub async fn wait_before_retry(&self, description: &str, attempt: u32, error: &Error) -> bool {
+        if attempt >= self.max_attempts || !self.is_retryable(error) {
+            return false;
+        }
+
+        let backoff = self.backoff(attempt);
+
+        tracing::warn!(
+            query = description,
+            attempt,
+            max_attempts = self.max_attempts,
+            "query failed, retrying in {:?}: {:#}",
+            backoff,
+            error
+        );
+
+        tokio::time::sleep(backoff).await;
+        true
+    }
+}
+
+fn is_retryable_rpc_code(code: RpcCode) -> bool {
+    matches!(
+        code,
+        RpcCode::HttpError
+            | RpcCode::WebSocketError
+            | RpcCode::ClientInternalError
+            | RpcCode::InternalError
+    )
+}
+
+/// gRPC status code on which queries can be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RetryableCode {
+    /// Query was cancelled
+    Cancelled,
+    /// Unknown error
+    Unknown,
+    /// Deadline expired before query could complete
+    DeadlineExceeded,
+    /// Some resource (e.g. rate limit) has been exhausted
+    ResourceExhausted,
+    /// Query was aborted
+    Aborted,
+    /// Internal error of node
+    Internal,
+    /// Node is currently unavailable
+    Unavailable,
+}
+
+impl RetryableCode {
+    /// Returns corresponding gRPC status code
+    pub fn code(self) -> Code {
+        match self {
+            Self::Cancelled => Code::Cancelled,
+            Self::Unknown => Code::Unknown,
+            Self::DeadlineExceeded => Code::DeadlineExceeded,
+            Self::ResourceExhausted => Code::ResourceExhausted,
+            Self::Aborted => Code::Aborted,
+            Self::Internal => Code::Internal,
+            Self::Unavailable => Code::Unavailable,
+        }
+    }
+}
+
+impl fmt::Display for RetryableCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "cancelled"),
+            Self::Unknown => write!(f, "unknown"),
+            Self::DeadlineExceeded => write!(f, "deadline-exceeded"),
+            Self::ResourceExhausted => write!(f, "resource-exhausted"),
+            Self::Aborted => write!(f, "aborted"),
+            Self::Internal => write!(f, "internal"),
+            Self::Unavailable => write!(f, "unavailable"),
+        }
+    }
+}
+
+impl FromStr for RetryableCode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cancelled" => Ok(Self::Cancelled),
+            "unknown" => Ok(Self::Unknown),
+            "deadline-exceeded" => Ok(Self::DeadlineExceeded),
+            "resource-exhausted" => Ok(Self::ResourceExhausted),
+            "aborted" => Ok(Self::Aborted),
+            "internal" => Ok(Self::Internal),
+            "unavailable" => Ok(Self::Unavailable),
+            _ => Err(anyhow!("invalid retryable grpc status code: {}", s)),
+        }
+    }
+}
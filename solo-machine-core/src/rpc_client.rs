@@ -0,0 +1,312 @@
+//! Tendermint RPC client bounding every request with request timeout and rate limit of chain
+use std::{io::Read, sync::Arc, time::Duration};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use hyper::{body::Buf, header, Body, Request, Uri};
+use hyper_rustls::HttpsConnector;
+use rustls::ClientConfig;
+use tendermint::{account::Id as AccountId, block::signed_header::SignedHeader, validator::Set};
+use tendermint_light_client::{
+    components::io::{AtHeight, Io, IoError},
+    types::{LightBlock, PeerId},
+};
+use tendermint_rpc::{Client, Error, Paging, Response, Result, SimpleRequest};
+
+use crate::{
+    failover::select_rpc_addr,
+    model::{ChainConfig, NetworkTimeouts},
+    proxy::{Proxy, ProxyConnector},
+    rate_limit::{self, RateLimit},
+};
+
+/// Tendermint RPC client which fails requests not completed within request timeout (timed out requests fail with an
+/// HTTP error, so, they are retried like other transient errors). Requests wait for rate limit of endpoint (if any)
+/// before being sent.
+#[derive(Debug, Clone)]
+pub struct TimeoutRpcClient {
+    inner: RpcTransport,
+    addr: String,
+    timeout: Duration,
+    rate_limit: Option<RateLimit>,
+}
+
+impl TimeoutRpcClient {
+    /// Creates a new client for RPC endpoint at given address (connecting through proxy when given)
+    pub fn new(
+        rpc_addr: &str,
+        timeouts: &NetworkTimeouts,
+        proxy: Option<&Proxy>,
+    ) -> anyhow::Result<Self> {
+        let inner = RpcTransport::new(rpc_addr, timeouts, proxy)
+            .context("unable to connect to rpc client")?;
+
+        Ok(Self {
+            inner,
+            addr: rpc_addr.to_string(),
+            timeout: timeouts.request,
+            rate_limit: None,
+        })
+    }
+
+    /// Creates a new client for RPC endpoint at given address using timeouts, proxy and rate limit of chain with
+    /// given configuration
+    pub fn with_config(rpc_addr: &str, config: &ChainConfig) -> anyhow::Result<Self> {
+        Ok(
+            Self::new(rpc_addr, &config.timeouts, config.proxy.as_ref())?
+                .with_rate_limit(config.rate_limit.clone()),
+        )
+    }
+
+    /// Creates a new client for RPC endpoint of chain selected for failover
+    pub async fn for_chain(config: &ChainConfig) -> anyhow::Result<Self> {
+        Self::with_config(&select_rpc_addr(config).await, config)
+    }
+
+    /// Limits rate of requests made to endpoint
+    pub fn with_rate_limit(mut self, rate_limit: Option<RateLimit>) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Waits for rate limit of endpoint before a request made using underlying transport (e.g. by light client)
+    pub async fn throttle(&self) {
+        rate_limit::acquire(&self.addr, self.rate_limit.as_ref()).await
+    }
+
+    /// Returns underlying transport (requests made using it are only bounded by timeouts of their users, e.g. RPC
+    /// timeout of light client, and are not rate limited)
+    pub fn inner(&self) -> &RpcTransport {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl Client for TimeoutRpcClient {
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        self.throttle().await;
+
+        tokio::time::timeout(self.timeout, self.inner.perform(request))
+            .await
+            .map_err(|_| Error::http_error(format!("request timed out after {:?}", self.timeout)))?
+    }
+}
+
+/// HTTP(S) transport of tendermint RPC requests. Connections are made using the same connector as gRPC channels, so,
+/// they are tunnelled through proxy of chain (if any) without any intermediate local proxy, and `https` endpoints are
+/// verified using native root certificates (with rustls).
+#[derive(Debug, Clone)]
+pub struct RpcTransport {
+    uri: Uri,
+    connector: HttpsConnector<ProxyConnector>,
+    client: hyper::Client<HttpsConnector<ProxyConnector>>,
+}
+
+impl RpcTransport {
+    /// Creates a new transport for RPC endpoint at given address (connecting through proxy when given)
+    pub fn new(
+        rpc_addr: &str,
+        timeouts: &NetworkTimeouts,
+        proxy: Option<&Proxy>,
+    ) -> anyhow::Result<Self> {
+        let uri: Uri = rpc_addr
+            .parse()
+            .with_context(|| format!("invalid rpc address: {}", rpc_addr))?;
+
+        let tls_config = match uri.scheme_str() {
+            Some("http") => ClientConfig::new(),
+            Some("https") => tls_config()?,
+            _ => anyhow::bail!("rpc address should be an http or https url: {}", rpc_addr),
+        };
+
+        let connector: HttpsConnector<ProxyConnector> = (
+            ProxyConnector::new(proxy.cloned(), timeouts.connect),
+            Arc::new(tls_config),
+        )
+            .into();
+        let client = hyper::Client::builder().build(connector.clone());
+
+        Ok(Self {
+            uri,
+            connector,
+            client,
+        })
+    }
+
+    /// Returns a copy of transport which never reuses connections (connections cannot be used once the runtime in
+    /// which they were made shuts down, e.g. runtimes of light client I/O)
+    fn unpooled(&self) -> Self {
+        Self {
+            uri: self.uri.clone(),
+            connector: self.connector.clone(),
+            client: hyper::Client::builder()
+                .pool_max_idle_per_host(0)
+                .build(self.connector.clone()),
+        }
+    }
+}
+
+#[async_trait]
+impl Client for RpcTransport {
+    async fn perform<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: SimpleRequest,
+    {
+        let mut http_request = Request::builder()
+            .method("POST")
+            .uri(&self.uri)
+            .body(Body::from(request.into_json().into_bytes()))?;
+
+        let headers = http_request.headers_mut();
+        headers.insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("application/json"),
+        );
+        headers.insert(
+            header::USER_AGENT,
+            header::HeaderValue::from_static(concat!("solo-machine/", env!("CARGO_PKG_VERSION"))),
+        );
+
+        let response = self.client.request(http_request).await?;
+
+        let mut response_body = String::new();
+        hyper::body::aggregate(response.into_body())
+            .await?
+            .reader()
+            .read_to_string(&mut response_body)
+            .map_err(|_| Error::client_internal_error("failed to read response body to string"))?;
+
+        R::Response::from_string(&response_body)
+    }
+}
+
+/// Returns TLS configuration of `https` RPC endpoints (trusting native root certificates)
+fn tls_config() -> anyhow::Result<ClientConfig> {
+    let mut config = ClientConfig::new();
+
+    config.root_store = match rustls_native_certs::load_native_certs() {
+        Ok(store) => store,
+        Err((Some(store), err)) => {
+            tracing::warn!("unable to load all the native root certificates: {}", err);
+            store
+        }
+        Err((None, err)) => {
+            return Err(err).context("unable to load native root certificates");
+        }
+    };
+
+    anyhow::ensure!(
+        !config.root_store.is_empty(),
+        "no native root certificates found"
+    );
+
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+    Ok(config)
+}
+
+/// Light client I/O fetching light blocks using RPC transport of chain (same as `ProdIo` of light client which only
+/// supports tendermint's HTTP client)
+pub(crate) struct LightClientIo {
+    peer_id: PeerId,
+    transport: RpcTransport,
+    timeout: Option<Duration>,
+}
+
+impl LightClientIo {
+    /// Creates a new light client I/O bounding every fetch with given timeout
+    pub(crate) fn new(peer_id: PeerId, transport: RpcTransport, timeout: Option<Duration>) -> Self {
+        Self {
+            peer_id,
+            transport: transport.unpooled(),
+            timeout,
+        }
+    }
+
+    fn fetch_signed_header(&self, height: AtHeight) -> std::result::Result<SignedHeader, IoError> {
+        let transport = self.transport.clone();
+
+        let response = block_on(self.timeout, async move {
+            match height {
+                AtHeight::Highest => transport.latest_commit().await,
+                AtHeight::At(height) => transport.commit(height).await,
+            }
+        })?
+        .map_err(IoError::RpcError)?;
+
+        Ok(response.signed_header)
+    }
+
+    fn fetch_validator_set(
+        &self,
+        height: AtHeight,
+        proposer_address: Option<AccountId>,
+    ) -> std::result::Result<Set, IoError> {
+        let height = match height {
+            AtHeight::Highest => {
+                return Err(IoError::InvalidHeight(
+                    "given height must be greater than 0".to_string(),
+                ))
+            }
+            AtHeight::At(height) => height,
+        };
+
+        let transport = self.transport.clone();
+
+        let response = block_on(self.timeout, async move {
+            transport.validators(height, Paging::All).await
+        })?
+        .map_err(IoError::RpcError)?;
+
+        match proposer_address {
+            Some(proposer_address) => Set::with_proposer(response.validators, proposer_address)
+                .map_err(|err| IoError::InvalidValidatorSet(err.to_string())),
+            None => Ok(Set::without_proposer(response.validators)),
+        }
+    }
+}
+
+impl Io for LightClientIo {
+    fn fetch_light_block(&self, height: AtHeight) -> std::result::Result<LightBlock, IoError> {
+        let signed_header = self.fetch_signed_header(height)?;
+        let height = signed_header.header.height;
+        let proposer_address = signed_header.header.proposer_address;
+
+        let validator_set = self.fetch_validator_set(height.into(), Some(proposer_address))?;
+        let next_validator_set = self.fetch_validator_set(height.increment().into(), None)?;
+
+        Ok(LightBlock::new(
+            signed_header,
+            validator_set,
+            next_validator_set,
+            self.peer_id,
+        ))
+    }
+}
+
+/// Runs given future to completion on a new thread (light client is synchronous and may be called from within a
+/// runtime), bounded with given timeout
+fn block_on<F>(timeout: Option<Duration>, future: F) -> std::result::Result<F::Output, IoError>
+where
+    F: std::future::Future + Send + 'static,
+    F::Output: Send,
+{
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|_| IoError::Runtime)?;
+
+        match timeout {
+            Some(timeout) => runtime
+                .block_on(tokio::time::timeout(timeout, future))
+                .map_err(|_| IoError::Timeout(timeout)),
+            None => Ok(runtime.block_on(future)),
+        }
+    })
+    .join()
+    .map_err(|_| IoError::Runtime)?
+}
@@ -1,5 +1,40 @@
 //! Services exposed by solo machine
+pub(crate) mod api_key_service;
+pub(crate) mod api_token_service;
+pub(crate) mod audit_service;
+pub(crate) mod backup_service;
+pub(crate) mod chain_lock;
 pub(crate) mod chain_service;
+pub(crate) mod doctor_service;
+pub(crate) mod endpoint_probe;
+pub(crate) mod health_service;
 pub(crate) mod ibc_service;
+pub(crate) mod integrity_service;
+pub(crate) mod macro_service;
+pub(crate) mod operation_queue;
+pub(crate) mod preflight;
+pub(crate) mod spending_limit;
+pub(crate) mod stats_service;
+pub(crate) mod status_service;
+pub(crate) mod tx_error;
 
-pub use self::{chain_service::ChainService, ibc_service::IbcService};
+pub use self::{
+    api_key_service::ApiKeyService,
+    api_token_service::ApiTokenService,
+    audit_service::AuditService,
+    backup_service::{
+        BackupService, ExportedChain, ExportedIbcData, StateExport, STATE_EXPORT_VERSION,
+    },
+    chain_service::{ChainResetEvidence, ChainService, SequenceStatus},
+    doctor_service::{DoctorService, Finding, Severity},
+    health_service::{HealthCheck, HealthReport, HealthService},
+    ibc_service::{IbcService, PacketRelayOutcome, PacketRelayReport},
+    integrity_service::IntegrityService,
+    macro_service::{MacroDefinition, MacroService},
+    stats_service::{ChainStats, DailyStats, StatsService},
+    status_service::{
+        ChainStatus, ClientStatus, ConnectionStatus, SignerStatus, StatusReport, StatusService,
+        StorageStatus,
+    },
+    tx_error::{TxError, TxErrorKind, TxStage},
+};
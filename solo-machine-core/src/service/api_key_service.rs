@@ -0,0 +1,187 @@
+use anyhow::{anyhow, ensure, Context, Result};
+use rand::{rngs::OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    model::{
+        api_key::{self, ApiKey, ApiOperation},
+        chain,
+    },
+    DbPool,
+};
+
+/// Length (in bytes) of ID of an API key
+pub(crate) const API_KEY_ID_LEN: usize = 8;
+
+/// Length (in bytes) of secret of an API key
+pub(crate) const API_KEY_SECRET_LEN: usize = 32;
+
+/// Used to create, revoke and authorize channel scoped API keys (API tokens are of the form `<id>.<secret>` and only
+/// hash of secret is stored)
+pub struct ApiKeyService {
+    db_pool: DbPool,
+}
+
+impl ApiKeyService {
+    /// Creates a new instance of API key service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Creates a new API key scoped to current channel of given chain and returns it along with its API token (the
+    /// token cannot be retrieved later)
+    pub async fn create(
+        &self,
+        chain_id: &ChainId,
+        operations: Vec<ApiOperation>,
+        denom: Option<Identifier>,
+        max_amount: Option<u64>,
+        label: Option<String>,
+    ) -> Result<(ApiKey, String)> {
+        ensure!(
+            !operations.is_empty(),
+            "at least one operation is required for an api key"
+        );
+
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let connection_details = chain.connection_details.ok_or_else(|| {
+            anyhow!(
+                "connection is not established with chain {}, api keys are scoped to a channel",
+                chain_id
+            )
+        })?;
+
+        let id = random_hex(API_KEY_ID_LEN);
+        let secret = random_hex(API_KEY_SECRET_LEN);
+
+        let mut operations = operations;
+        operations.sort_by_key(ToString::to_string);
+        operations.dedup();
+
+        api_key::add_api_key(
+            &self.db_pool,
+            &id,
+            &hash_secret(&secret),
+            chain_id,
+            &connection_details.solo_machine_channel_id,
+            &operations,
+            denom.as_ref(),
+            max_amount,
+            label.as_deref(),
+        )
+        .await?;
+
+        let api_key = self.get(&id).await?;
+
+        Ok((api_key, format!("{}.{}", id, secret)))
+    }
+
+    /// Fetches API key with given ID
+    pub async fn get(&self, id: &str) -> Result<ApiKey> {
+        api_key::get_api_key(&self.db_pool, id)
+            .await?
+            .map(|(api_key, _)| api_key)
+            .ok_or_else(|| anyhow!("api key with id {} not found", id))
+    }
+
+    /// Fetches all the API keys (optionally, only for given chain)
+    pub async fn list(&self, chain_id: Option<&ChainId>) -> Result<Vec<ApiKey>> {
+        api_key::get_api_keys(&self.db_pool, chain_id).await
+    }
+
+    /// Revokes API key with given ID
+    pub async fn revoke(&self, id: &str) -> Result<()> {
+        api_key::revoke_api_key(&self.db_pool, id).await
+    }
+
+    /// Authorizes an operation on given chain using API token. Operation is authorized only if API key is active,
+    /// chain is still connected over the channel API key is scoped to, and operation, denom and amount are within the
+    /// scope of API key.
+    pub async fn authorize(
+        &self,
+        token: &str,
+        operation: ApiOperation,
+        chain_id: &ChainId,
+        denom: &Identifier,
+        amount: u64,
+    ) -> Result<ApiKey> {
+        let (id, secret) = token
+            .split_once('.')
+            .ok_or_else(|| anyhow!("malformed api token"))?;
+
+        let (api_key, secret_hash) = api_key::get_api_key(&self.db_pool, id)
+            .await?
+            .ok_or_else(|| anyhow!("invalid api token"))?;
+
+        ensure!(hash_secret(secret) == secret_hash, "invalid api token");
+        ensure!(
+            api_key.revoked_at.is_none(),
+            "api key {} is revoked",
+            api_key.id
+        );
+        ensure!(
+            api_key.chain_id == *chain_id,
+            "api key {} is not authorized for chain {}",
+            api_key.id,
+            chain_id
+        );
+        ensure!(
+            api_key.operations.contains(&operation),
+            "api key {} is not authorized for {} operation",
+            api_key.id,
+            operation
+        );
+
+        if let Some(ref allowed_denom) = api_key.denom {
+            ensure!(
+                allowed_denom == denom,
+                "api key {} is not authorized for denom {}",
+                api_key.id,
+                denom
+            );
+        }
+
+        if let Some(max_amount) = api_key.max_amount {
+            ensure!(
+                amount <= max_amount,
+                "api key {} is not authorized for amounts greater than {}",
+                api_key.id,
+                max_amount
+            );
+        }
+
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let channel_id = chain
+            .connection_details
+            .map(|connection_details| connection_details.solo_machine_channel_id)
+            .context("connection is not established with chain")?;
+
+        ensure!(
+            channel_id == api_key.channel_id,
+            "api key {} is scoped to channel {} but chain {} is connected over channel {}",
+            api_key.id,
+            api_key.channel_id,
+            chain_id,
+            channel_id
+        );
+
+        Ok(api_key)
+    }
+}
+
+pub(crate) fn random_hex(len: usize) -> String {
+    let mut bytes = vec![0; len];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+pub(crate) fn hash_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
@@ -0,0 +1,80 @@
+use anyhow::{anyhow, ensure, Result};
+
+use crate::{
+    model::api_token::{self, ApiRole, ApiToken},
+    service::api_key_service::{hash_secret, random_hex, API_KEY_ID_LEN, API_KEY_SECRET_LEN},
+    DbPool,
+};
+
+/// Used to create, revoke and authenticate API tokens with roles (API tokens are of the form `<id>.<secret>`, same as
+/// channel scoped API keys, and only hash of secret is stored)
+pub struct ApiTokenService {
+    db_pool: DbPool,
+}
+
+impl ApiTokenService {
+    /// Creates a new instance of API token service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Creates a new API token with given role and returns it along with the token (the token cannot be retrieved
+    /// later)
+    pub async fn create(&self, role: ApiRole, label: Option<String>) -> Result<(ApiToken, String)> {
+        let id = random_hex(API_KEY_ID_LEN);
+        let secret = random_hex(API_KEY_SECRET_LEN);
+
+        api_token::add_api_token(
+            &self.db_pool,
+            &id,
+            &hash_secret(&secret),
+            role,
+            label.as_deref(),
+        )
+        .await?;
+
+        let api_token = self.get(&id).await?;
+
+        Ok((api_token, format!("{}.{}", id, secret)))
+    }
+
+    /// Fetches API token with given ID
+    pub async fn get(&self, id: &str) -> Result<ApiToken> {
+        api_token::get_api_token(&self.db_pool, id)
+            .await?
+            .map(|(api_token, _)| api_token)
+            .ok_or_else(|| anyhow!("api token with id {} not found", id))
+    }
+
+    /// Fetches all the API tokens
+    pub async fn list(&self) -> Result<Vec<ApiToken>> {
+        api_token::get_api_tokens(&self.db_pool).await
+    }
+
+    /// Revokes API token with given ID
+    pub async fn revoke(&self, id: &str) -> Result<()> {
+        api_token::revoke_api_token(&self.db_pool, id).await
+    }
+
+    /// Authenticates given token. Returns `None` if ID of token does not belong to an API token (e.g. it is a channel
+    /// scoped API key) and an error if secret does not match or API token is revoked.
+    pub async fn authenticate(&self, token: &str) -> Result<Option<ApiToken>> {
+        let (id, secret) = token
+            .split_once('.')
+            .ok_or_else(|| anyhow!("malformed api token"))?;
+
+        let (api_token, secret_hash) = match api_token::get_api_token(&self.db_pool, id).await? {
+            None => return Ok(None),
+            Some(api_token) => api_token,
+        };
+
+        ensure!(hash_secret(secret) == secret_hash, "invalid api token");
+        ensure!(
+            api_token.revoked_at.is_none(),
+            "api token {} is revoked",
+            api_token.id
+        );
+
+        Ok(Some(api_token))
+    }
+}
@@ -0,0 +1,74 @@
+use anyhow::{Context, Result};
+
+use crate::{
+    attestation::BuildAttestation,
+    model::{
+        audit_log, signature_log, AuditLogEntry, AuditLogVerification, SignatureLogEntry,
+        SignatureLogVerification,
+    },
+    DbPool, Event,
+};
+
+/// Used to record events in (and verify) hash-chained audit log and to inspect hash-chained log of signed messages
+pub struct AuditService {
+    db_pool: DbPool,
+}
+
+impl AuditService {
+    /// Creates a new instance of audit service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Appends an event to audit log
+    pub async fn log(&self, event: &Event) -> Result<()> {
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        audit_log::add_audit_log_entry(&mut transaction, event).await?;
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for adding audit log entry")
+    }
+
+    /// Fetches entries of audit log (latest first)
+    pub async fn entries(&self, limit: u32, offset: u32) -> Result<Vec<AuditLogEntry>> {
+        audit_log::get_audit_log_entries(&self.db_pool, limit, offset).await
+    }
+
+    /// Fetches recorded build attestation with given digest (referred by audit log entries)
+    pub async fn attestation(&self, digest: &str) -> Result<Option<BuildAttestation>> {
+        audit_log::get_build_attestation(&self.db_pool, digest).await
+    }
+
+    /// Verifies hash chain of audit log
+    pub async fn verify(&self) -> Result<AuditLogVerification> {
+        audit_log::verify_audit_log(&self.db_pool).await
+    }
+
+    /// Fetches entries of signature log, i.e., messages signed by signer (latest first)
+    pub async fn signatures(&self, limit: u32, offset: u32) -> Result<Vec<SignatureLogEntry>> {
+        signature_log::get_signature_log_entries(&self.db_pool, limit, offset).await
+    }
+
+    /// Verifies hash chain of signature log
+    pub async fn verify_signatures(&self) -> Result<SignatureLogVerification> {
+        signature_log::verify_signature_log(&self.db_pool).await
+    }
+
+    /// Explicitly accepts a break in hash chain of audit log after investigation. Existing entries are never
+    /// rewritten: an entry accepting the break is appended to audit log, so that the break stays visible while
+    /// verification resumes after it.
+    pub async fn accept_break(&self, entry_id: i64, reason: &str) -> Result<()> {
+        self.log(&Event::AuditLogBreakAccepted {
+            entry_id,
+            reason: reason.to_string(),
+        })
+        .await
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    event::notify_event,
+    ibc::core::ics24_host::identifier::ChainId,
+    model::{
+        chain::{self, chain_keys},
+        ibc, Chain, ChainConfig, ConnectionDetails, GeneratedIdentifiers, HandshakeProgress,
+    },
+    DbPool, Event,
+};
+
+/// Version of format of state exports (bumped whenever format changes in a backward incompatible way)
+pub const STATE_EXPORT_VERSION: u32 = 1;
+
+/// Maximum number of keys of a chain included in state export
+const MAX_EXPORTED_CHAIN_KEYS: u32 = 10_000;
+
+/// Versioned export of stored state of solo machine (used for backup and for migrating solo machine to another host)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StateExport {
+    /// Version of export format
+    pub version: u32,
+    /// Version of solo machine which created the export
+    pub solo_machine_version: String,
+    /// Time at which state was exported
+    pub exported_at: DateTime<Utc>,
+    /// State of all the chains
+    pub chains: Vec<ExportedChain>,
+    /// Encoded IBC data (client states, consensus states, connections and channels) of solo machine
+    pub ibc_data: Vec<ExportedIbcData>,
+}
+
+/// Exported state of an IBC enabled chain
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedChain {
+    /// ID of chain
+    pub id: String,
+    /// Node ID of chain
+    pub node_id: String,
+    /// Configuration for chain (including diversifier)
+    pub config: ChainConfig,
+    /// Consensus timestamp of solo machine
+    pub consensus_timestamp: DateTime<Utc>,
+    /// Sequence of solo machine
+    pub sequence: u32,
+    /// Packet sequence of solo machine
+    pub packet_sequence: u32,
+    /// IBC connection details
+    pub connection_details: Option<ConnectionDetails>,
+    /// Latest identifiers assigned by chain to solo machine's client, connection and channel
+    #[serde(default)]
+    pub generated_identifiers: GeneratedIdentifiers,
+    /// Progress of an interrupted connection handshake
+    pub handshake_progress: Option<HandshakeProgress>,
+    /// Genesis time of chain
+    pub genesis_time: Option<DateTime<Utc>>,
+    /// Public keys of solo machine used on chain (oldest first)
+    pub public_keys: Vec<String>,
+    /// Creation time of chain
+    pub created_at: DateTime<Utc>,
+    /// Last updation time of chain
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Exported IBC data
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedIbcData {
+    /// ICS-24 path of data
+    pub path: String,
+    /// Hex encoded protobuf bytes of data
+    pub data: String,
+}
+
+impl From<Chain> for ExportedChain {
+    fn from(chain: Chain) -> Self {
+        Self {
+            id: chain.id.to_string(),
+            node_id: chain.node_id.to_string(),
+            config: chain.config,
+            consensus_timestamp: chain.consensus_timestamp,
+            sequence: chain.sequence,
+            packet_sequence: chain.packet_sequence,
+            connection_details: chain.connection_details,
+            generated_identifiers: chain.generated_identifiers,
+            handshake_progress: chain.handshake_progress,
+            genesis_time: chain.genesis_time,
+            public_keys: Vec::new(),
+            created_at: chain.created_at,
+            updated_at: chain.updated_at,
+        }
+    }
+}
+
+/// Used to export and import stored state of solo machine
+pub struct BackupService {
+    db_pool: DbPool,
+    notifier: Option<UnboundedSender<Event>>,
+}
+
+impl BackupService {
+    /// Creates a new instance of backup service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self {
+            db_pool,
+            notifier: None,
+        }
+    }
+
+    /// Creates a new instance of backup service with notifier
+    pub fn new_with_notifier(db_pool: DbPool, notifier: UnboundedSender<Event>) -> Self {
+        Self {
+            db_pool,
+            notifier: Some(notifier),
+        }
+    }
+
+    /// Exports state of all the chains (chain records, sequences, diversifiers and public keys) along with all the
+    /// IBC data (client states, consensus states, connections and channels). Secrets of signer are never exported.
+    pub async fn export(&self) -> Result<StateExport> {
+        // all the state is read in one transaction so that export is consistent
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let mut chains = Vec::new();
+
+        for chain in chain::get_chains(&mut transaction).await? {
+            let mut public_keys: Vec<String> =
+                chain_keys::get_chain_keys(&mut transaction, &chain.id, MAX_EXPORTED_CHAIN_KEYS, 0)
+                    .await?
+                    .into_iter()
+                    .map(|chain_key| chain_key.public_key)
+                    .collect();
+            public_keys.reverse();
+
+            let mut exported_chain = ExportedChain::from(chain);
+            exported_chain.public_keys = public_keys;
+
+            chains.push(exported_chain);
+        }
+
+        let ibc_data = ibc::get_all_ibc_data(&mut transaction)
+            .await?
+            .into_iter()
+            .map(|(path, data)| ExportedIbcData {
+                path,
+                data: hex::encode(data),
+            })
+            .collect();
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for exporting state")?;
+
+        Ok(StateExport {
+            version: STATE_EXPORT_VERSION,
+            solo_machine_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: Utc::now(),
+            chains,
+            ibc_data,
+        })
+    }
+
+    /// Imports state exported by [`BackupService::export`] and returns IDs of imported chains. Import is atomic and
+    /// fails if any of the chains (or IBC data) already exists in storage.
+    pub async fn import(&self, state: StateExport) -> Result<Vec<ChainId>> {
+        ensure!(
+            state.version == STATE_EXPORT_VERSION,
+            "unsupported version of state export: {} (supported version: {})",
+            state.version,
+            STATE_EXPORT_VERSION
+        );
+
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let mut chain_ids = Vec::with_capacity(state.chains.len());
+
+        for exported_chain in state.chains {
+            let chain = Chain {
+                id: exported_chain.id.parse()?,
+                node_id: exported_chain
+                    .node_id
+                    .parse()
+                    .map_err(|err| anyhow!("unable to parse node id: {}", err))?,
+                config: exported_chain.config,
+                consensus_timestamp: exported_chain.consensus_timestamp,
+                sequence: exported_chain.sequence,
+                packet_sequence: exported_chain.packet_sequence,
+                connection_details: exported_chain.connection_details,
+                generated_identifiers: exported_chain.generated_identifiers,
+                handshake_progress: exported_chain.handshake_progress,
+                genesis_time: exported_chain.genesis_time,
+                created_at: exported_chain.created_at,
+                updated_at: exported_chain.updated_at,
+            };
+
+            ensure!(
+                chain.sequence > 0 && chain.packet_sequence > 0,
+                "sequences of chain {} should be greater than 0",
+                chain.id
+            );
+            ensure!(
+                chain::get_chain(&mut transaction, &chain.id)
+                    .await?
+                    .is_none(),
+                "chain {} already exists",
+                chain.id
+            );
+
+            chain::import_chain(&mut transaction, &chain).await?;
+
+            for public_key in exported_chain.public_keys.iter() {
+                chain_keys::add_chain_key(&mut transaction, &chain.id, public_key).await?;
+            }
+
+            chain_ids.push(chain.id);
+        }
+
+        let existing_paths: HashSet<String> = ibc::get_all_ibc_data(&mut transaction)
+            .await?
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+
+        for ibc_data in state.ibc_data {
+            ensure!(
+                !existing_paths.contains(&ibc_data.path),
+                "ibc data at path {} already exists",
+                ibc_data.path
+            );
+
+            let data = hex::decode(&ibc_data.data).with_context(|| {
+                format!("invalid hex encoding of ibc data at {}", ibc_data.path)
+            })?;
+
+            ibc::add_raw_ibc_data(&mut transaction, &ibc_data.path, &data).await?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for importing state")?;
+
+        for chain_id in chain_ids.iter() {
+            notify_event(
+                &self.notifier,
+                Event::ChainImported {
+                    chain_id: chain_id.clone(),
+                },
+            )?;
+        }
+
+        Ok(chain_ids)
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+use crate::{ibc::core::ics24_host::identifier::ChainId, DbPool};
+
+/// Locks of current process (keyed by chain ID for chain locks). Operations which consume or modify sequences of a
+/// chain hold its lock, so operations on a chain are serialized while operations on different chains run concurrently.
+static LOCKS: Mutex<BTreeMap<String, Arc<AsyncMutex<()>>>> = Mutex::new(BTreeMap::new());
+
+/// Interval at which a lock held by another process is polled
+const PROCESS_LOCK_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Guard of a lock (lock is released when guard is dropped)
+pub(crate) struct LockGuard {
+    // process lock is declared first so that it is released before the lock of current process
+    _process_lock: Option<ProcessLock>,
+    _guard: OwnedMutexGuard<()>,
+}
+
+/// Acquires lock of given chain, waiting for the running operation on the chain (if any) to finish. Along with the
+/// lock of current process, an advisory lock is acquired on database (a lock file next to SQLite database or a session
+/// level advisory lock on PostgreSQL) so that concurrently running solo machine processes sharing the database never
+/// sign at the same sequence. Advisory locks are released by the operating system or database even if a process
+/// crashes. Locks are not reentrant, so, an operation holding a chain lock should never call another operation
+/// acquiring the same lock.
+pub(crate) async fn lock_chain(db_pool: &DbPool, chain_id: &ChainId) -> Result<LockGuard> {
+    lock(db_pool, &chain_id.to_string()).await
+}
+
+/// Acquires spending lock of given denom (shared by all the chains), waiting for the running transfer of the denom (if
+/// any) to finish. Spending lock is always acquired after chain lock (never the other way round), so, transfers holding
+/// both never deadlock.
+pub(crate) async fn lock_spending(db_pool: &DbPool, denom: &str) -> Result<LockGuard> {
+    // denoms may contain `/` (e.g. IBC denoms) which cannot be a part of name of lock file
+    lock(db_pool, &format!("spending-{}", denom.replace('/', "-"))).await
+}
+
+/// Acquires lock of current process and advisory lock on database for given key
+async fn lock(db_pool: &DbPool, key: &str) -> Result<LockGuard> {
+    let lock = LOCKS
+        .lock()
+        .unwrap()
+        .entry(key.to_string())
+        .or_default()
+        .clone();
+
+    let guard = lock.lock_owned().await;
+    let process_lock = ProcessLock::acquire(db_pool, key).await?;
+
+    Ok(LockGuard {
+        _process_lock: process_lock,
+        _guard: guard,
+    })
+}
+
+/// Advisory lock held by current process (released when dropped)
+#[cfg(not(feature = "postgres"))]
+struct ProcessLock {
+    _file: std::fs::File,
+}
+
+#[cfg(not(feature = "postgres"))]
+impl ProcessLock {
+    /// Acquires lock file of given key next to SQLite database (`None` for in-memory databases which cannot be shared
+    /// between processes)
+    async fn acquire(db_pool: &DbPool, key: &str) -> Result<Option<Self>> {
+        use std::{fs::OpenOptions, path::PathBuf};
+
+        use fs2::FileExt;
+
+        let (_, _, db_path): (i64, String, String) = sqlx::query_as("PRAGMA database_list")
+            .fetch_one(db_pool)
+            .await
+            .context("unable to query path of database")?;
+
+        if db_path.is_empty() {
+            return Ok(None);
+        }
+
+        let path = PathBuf::from(format!("{}.{}.lock", db_path, key));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("unable to open lock file {}", path.display()))?;
+
+        let mut waiting = false;
+
+        while let Err(err) = file.try_lock_exclusive() {
+            if err.raw_os_error() != fs2::lock_contended_error().raw_os_error() {
+                return Err(err).with_context(|| format!("unable to lock {}", path.display()));
+            }
+
+            if !waiting {
+                tracing::info!(
+                    lock = key,
+                    "lock is held by another solo machine process, waiting for it to be released"
+                );
+                waiting = true;
+            }
+
+            tokio::time::sleep(PROCESS_LOCK_POLL_INTERVAL).await;
+        }
+
+        Ok(Some(Self { _file: file }))
+    }
+}
+
+/// Advisory lock held by current process (released when dropped)
+#[cfg(feature = "postgres")]
+struct ProcessLock {
+    /// Dedicated connection (detached from pool) holding session level advisory lock, so that the lock is released
+    /// when connection is closed
+    _connection: sqlx::PgConnection,
+}
+
+#[cfg(feature = "postgres")]
+impl ProcessLock {
+    /// Namespace of advisory locks of solo machine (first key of two-key advisory locks)
+    const NAMESPACE: i32 = 0x534d_4c4b;
+
+    /// Acquires session level advisory lock of given key on a dedicated connection
+    async fn acquire(db_pool: &DbPool, key: &str) -> Result<Option<Self>> {
+        let mut connection = db_pool
+            .acquire()
+            .await
+            .context("unable to acquire database connection for lock")?
+            .detach();
+
+        let mut waiting = false;
+
+        loop {
+            let (locked,): (bool,) =
+                sqlx::query_as("SELECT pg_try_advisory_lock($1, hashtext($2))")
+                    .bind(Self::NAMESPACE)
+                    .bind(key)
+                    .fetch_one(&mut connection)
+                    .await
+                    .context("unable to acquire advisory lock")?;
+
+            if locked {
+                return Ok(Some(Self {
+                    _connection: connection,
+                }));
+            }
+
+            if !waiting {
+                tracing::info!(
+                    lock = key,
+                    "lock is held by another solo machine process, waiting for it to be released"
+                );
+                waiting = true;
+            }
+
+            tokio::time::sleep(PROCESS_LOCK_POLL_INTERVAL).await;
+        }
+    }
+}
@@ -1,24 +1,121 @@
-use anyhow::{anyhow, Context, Result};
+use std::{convert::TryFrom, fmt, time::SystemTime};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
 use chain_keys::ChainKey;
+use chrono::{DateTime, Utc};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use prost::Message;
 use rust_decimal::Decimal;
-use tendermint::node::Id as NodeId;
-use tendermint_rpc::{Client, HttpClient};
+use serde_json::Value;
+use tendermint::{block::Height as BlockHeight, Hash as TendermintHash};
+use tendermint_rpc::Client;
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{
+    broadcaster::{
+        BroadcastMode, BroadcastResult, BroadcastTransport, Broadcaster, ExecutionResult,
+    },
+    cosmos::name_resolver::AddressDisplayer,
     event::notify_event,
-    ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, Identifier},
     model::{
-        chain::{self, chain_keys},
-        Chain, ChainConfig,
+        chain::{self, chain_keys, chain_labels},
+        ibc, packet, pending_operation, Chain, ChainConfig, ChainLabel, GeneratedIdentifiers,
+        LabelKind, PendingOperationKind,
     },
-    DbPool, Event, ToPublicKey,
+    plugins::Plugins,
+    proto::json,
+    rpc_client::TimeoutRpcClient,
+    signer::{DryRunReport, DryRunSigner},
+    transaction_builder, DbPool, Event, Signer, ToPublicKey,
+};
+
+use super::{
+    chain_lock::lock_chain,
+    endpoint_probe::{probe_endpoints, ProbeReport},
+    operation_queue::{self, Checkpoint},
+    spending_limit,
 };
 
+/// Evidence that an IBC enabled chain has been reset (e.g. a devnet restarted from a new genesis), which invalidates
+/// all the IBC state of solo machine for the chain
+#[derive(Debug, Clone)]
+pub enum ChainResetEvidence {
+    /// Genesis time of chain differs from the recorded one
+    GenesisTimeChanged {
+        /// Recorded genesis time
+        recorded: DateTime<Utc>,
+        /// Current genesis time
+        current: DateTime<Utc>,
+    },
+    /// Latest block height of chain is lower than trusted height
+    HeightRegressed {
+        /// Trusted height of chain
+        trusted_height: u64,
+        /// Latest block height of chain
+        latest_height: u64,
+    },
+    /// Hash of block at trusted height differs from trusted hash
+    BlockHashMismatch {
+        /// Trusted height of chain
+        height: u64,
+        /// Trusted hash (in hex)
+        trusted_hash: String,
+        /// Hash of block at trusted height (in hex)
+        block_hash: String,
+    },
+}
+
+impl fmt::Display for ChainResetEvidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GenesisTimeChanged { recorded, current } => {
+                write!(f, "genesis time changed from {} to {}", recorded, current)
+            }
+            Self::HeightRegressed {
+                trusted_height,
+                latest_height,
+            } => write!(
+                f,
+                "latest block height {} is lower than trusted height {}",
+                latest_height, trusted_height
+            ),
+            Self::BlockHashMismatch {
+                height,
+                trusted_hash,
+                block_hash,
+            } => write!(
+                f,
+                "hash of block at trusted height {} is {} instead of {}",
+                height, block_hash, trusted_hash
+            ),
+        }
+    }
+}
+
+/// Local sequence of a chain along with sequence of solo machine client on chain
+#[derive(Debug, Clone)]
+pub struct SequenceStatus {
+    /// ID of solo machine client on chain
+    pub client_id: ClientId,
+    /// Sequence of chain stored on solo machine
+    pub local: u64,
+    /// Sequence of solo machine client on chain
+    pub on_chain: u64,
+}
+
+impl SequenceStatus {
+    /// Returns `true` if local sequence is same as sequence of solo machine client on chain
+    pub fn is_in_sync(&self) -> bool {
+        self.local == self.on_chain
+    }
+}
+
 /// Used to manage IBC enabled chain's state and metadata
 pub struct ChainService {
     db_pool: DbPool,
     notifier: Option<UnboundedSender<Event>>,
+    plugins: Plugins,
 }
 
 impl ChainService {
@@ -27,6 +124,7 @@ impl ChainService {
         Self {
             db_pool,
             notifier: None,
+            plugins: Default::default(),
         }
     }
 
@@ -35,16 +133,54 @@ impl ChainService {
         Self {
             db_pool,
             notifier: Some(notifier),
+            plugins: Default::default(),
         }
     }
 
-    /// Add details of an IBC enabled chain
+    /// Uses given plugins for chains with `plugin` fee strategy or client encoding
+    pub fn with_plugins(mut self, plugins: Plugins) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
+    /// Add details of an IBC enabled chain. RPC and gRPC endpoints of chain are probed before adding it: chains whose
+    /// endpoints are unreachable or report different chain IDs, chains without IBC and trusted blocks not matching
+    /// the chain are rejected, while other issues are reported as warnings. IBC version of chain is detected and
+    /// recorded along with its details (it selects the revision of solo machine client types used for chain).
     pub async fn add(&self, config: &ChainConfig, public_key: &str) -> Result<ChainId> {
-        let tendermint_client = HttpClient::new(config.rpc_addr.as_str())?;
-        let status = tendermint_client.status().await?;
+        config.retry_policy.validate()?;
+        config.timeouts.validate()?;
 
-        let chain_id: ChainId = status.node_info.network.to_string().parse()?;
-        let node_id: NodeId = status.node_info.id;
+        if let Some(ref rate_limit) = config.rate_limit {
+            rate_limit.validate()?;
+        }
+
+        let ProbeReport {
+            chain_id,
+            node_id,
+            ibc_version,
+            warnings,
+        } = probe_endpoints(config).await?;
+
+        for message in warnings {
+            notify_event(&self.notifier, Event::Warning { message })?;
+        }
+
+        let mut config = config.clone();
+
+        match ibc_version {
+            Some(ibc_version) => config.ibc_version = Some(ibc_version),
+            None if config.ibc_version.is_none() => notify_event(
+                &self.notifier,
+                Event::Warning {
+                    message: format!(
+                        "unable to detect IBC version of chain {} (node does not report its build dependencies)",
+                        chain_id
+                    ),
+                },
+            )?,
+            None => {}
+        }
 
         let mut transaction = self
             .db_pool
@@ -52,7 +188,7 @@ impl ChainService {
             .await
             .context("unable to begin database transaction")?;
 
-        chain::add_chain(&mut transaction, &chain_id, &node_id, config).await?;
+        chain::add_chain(&mut transaction, &chain_id, &node_id, &config).await?;
         chain_keys::add_chain_key(&mut transaction, &chain_id, public_key).await?;
 
         transaction
@@ -81,11 +217,593 @@ impl ChainService {
             .ok_or_else(|| anyhow!("connection details not found when computing ibc denom"))
     }
 
+    /// Resolves given name or address to an account address on given chain using its name resolver (addresses in
+    /// address format of chain are converted to bech32 addresses with given account prefix)
+    pub async fn resolve_account_address(
+        &self,
+        chain_id: &ChainId,
+        name_or_address: &str,
+        account_prefix: &str,
+    ) -> Result<String> {
+        let chain = self
+            .get(chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details not found when resolving account address"))?;
+
+        chain
+            .resolve_account_address(name_or_address, account_prefix)
+            .await
+    }
+
+    /// Returns address displayer for formatting account addresses based on address display configuration of given
+    /// chain
+    pub async fn address_displayer(&self, chain_id: &ChainId) -> Result<AddressDisplayer> {
+        let chain = self
+            .get(chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details not found when displaying account address"))?;
+
+        Ok(chain.address_displayer().await)
+    }
+
+    /// Grants a basic fee allowance from `granter` to solo machine signer (`grantee`) on given chain and uses the
+    /// allowance for paying fees of all the future transactions of solo machine signer
+    #[allow(clippy::too_many_arguments)]
+    pub async fn grant_allowance(
+        &self,
+        granter: impl Signer,
+        grantee: impl ToPublicKey,
+        chain_id: &ChainId,
+        spend_limit: Option<Decimal>,
+        expiration: Option<DateTime<Utc>>,
+        memo: String,
+        request_id: Option<String>,
+    ) -> Result<String> {
+        let mut chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let granter_address = chain.account_address(&granter)?;
+        let grantee_address = chain.account_address(&grantee)?;
+
+        let msg = transaction_builder::msg_grant_allowance(
+            &granter,
+            &self.plugins,
+            &chain,
+            grantee_address.clone(),
+            spend_limit,
+            expiration,
+            memo,
+            request_id.as_deref(),
+        )
+        .await?;
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        let response = self
+            .submit(
+                &granter,
+                &rpc_client,
+                &chain,
+                PendingOperationKind::GrantAllowance,
+                msg,
+                request_id.as_deref(),
+            )
+            .await?;
+
+        let transaction_hash = response.ensure_success()?;
+
+        chain.config.fee.granter = Some(granter_address.clone());
+        chain::update_chain_config(&self.db_pool, chain_id, &chain.config).await?;
+
+        notify_event(
+            &self.notifier,
+            Event::FeeAllowanceGranted {
+                chain_id: chain_id.clone(),
+                granter: granter_address,
+                grantee: grantee_address,
+                transaction_hash: transaction_hash.clone(),
+            },
+        )?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Builds the transaction for granting a basic fee allowance from `granter` to solo machine signer (`grantee`) in
+    /// dry-run mode, i.e., without signing, persisting or broadcasting anything
+    pub async fn dry_run_grant_allowance(
+        &self,
+        granter: impl Signer,
+        grantee: impl ToPublicKey,
+        chain_id: &ChainId,
+        spend_limit: Option<Decimal>,
+        expiration: Option<DateTime<Utc>>,
+        memo: String,
+    ) -> Result<DryRunReport> {
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let granter = DryRunSigner::new(granter)?;
+
+        let tx_raw = transaction_builder::msg_grant_allowance(
+            &granter,
+            &self.plugins,
+            &chain,
+            chain.account_address(&grantee)?,
+            spend_limit,
+            expiration,
+            memo,
+            None,
+        )
+        .await?;
+
+        DryRunReport::new(chain_id, &tx_raw, granter.messages())
+    }
+
+    /// Revokes fee allowance granted by `granter` to solo machine signer (`grantee`) on given chain
+    pub async fn revoke_allowance(
+        &self,
+        granter: impl Signer,
+        grantee: impl ToPublicKey,
+        chain_id: &ChainId,
+        memo: String,
+        request_id: Option<String>,
+    ) -> Result<String> {
+        let mut chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let granter_address = chain.account_address(&granter)?;
+        let grantee_address = chain.account_address(&grantee)?;
+
+        let msg = transaction_builder::msg_revoke_allowance(
+            &granter,
+            &self.plugins,
+            &chain,
+            grantee_address.clone(),
+            memo,
+            request_id.as_deref(),
+        )
+        .await?;
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        let response = self
+            .submit(
+                &granter,
+                &rpc_client,
+                &chain,
+                PendingOperationKind::RevokeAllowance,
+                msg,
+                request_id.as_deref(),
+            )
+            .await?;
+
+        let transaction_hash = response.ensure_success()?;
+
+        if chain.config.fee.granter.as_deref() == Some(granter_address.as_str()) {
+            chain.config.fee.granter = None;
+            chain::update_chain_config(&self.db_pool, chain_id, &chain.config).await?;
+        }
+
+        notify_event(
+            &self.notifier,
+            Event::FeeAllowanceRevoked {
+                chain_id: chain_id.clone(),
+                granter: granter_address,
+                grantee: grantee_address,
+                transaction_hash: transaction_hash.clone(),
+            },
+        )?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Builds the transaction for revoking fee allowance granted by `granter` to solo machine signer (`grantee`) in
+    /// dry-run mode, i.e., without signing, persisting or broadcasting anything
+    pub async fn dry_run_revoke_allowance(
+        &self,
+        granter: impl Signer,
+        grantee: impl ToPublicKey,
+        chain_id: &ChainId,
+        memo: String,
+    ) -> Result<DryRunReport> {
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let granter = DryRunSigner::new(granter)?;
+
+        let tx_raw = transaction_builder::msg_revoke_allowance(
+            &granter,
+            &self.plugins,
+            &chain,
+            chain.account_address(&grantee)?,
+            memo,
+            None,
+        )
+        .await?;
+
+        DryRunReport::new(chain_id, &tx_raw, granter.messages())
+    }
+
+    /// Builds the transaction for granting a basic fee allowance from `granter` to solo machine signer (`grantee`) and
+    /// returns it in the JSON layout of cosmos SDK CLIs (to be signed using `tx sign` when unsigned, and broadcasted
+    /// using `tx broadcast`). Transaction is never broadcasted, so, configuration of chain is not updated to use the
+    /// allowance.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn export_grant_allowance(
+        &self,
+        granter: impl Signer,
+        grantee: impl ToPublicKey,
+        chain_id: &ChainId,
+        spend_limit: Option<Decimal>,
+        expiration: Option<DateTime<Utc>>,
+        memo: String,
+        request_id: Option<String>,
+        signed: bool,
+    ) -> Result<Value> {
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let grantee_address = chain.account_address(&grantee)?;
+
+        let tx_raw = if signed {
+            transaction_builder::msg_grant_allowance(
+                &granter,
+                &self.plugins,
+                &chain,
+                grantee_address,
+                spend_limit,
+                expiration,
+                memo,
+                request_id.as_deref(),
+            )
+            .await?
+        } else {
+            transaction_builder::msg_grant_allowance(
+                DryRunSigner::new(granter)?,
+                &self.plugins,
+                &chain,
+                grantee_address,
+                spend_limit,
+                expiration,
+                memo,
+                request_id.as_deref(),
+            )
+            .await?
+        };
+
+        json::tx_raw_to_cosmos_json(&tx_raw, signed)
+    }
+
+    /// Builds the transaction for revoking fee allowance granted by `granter` to solo machine signer (`grantee`) and
+    /// returns it in the JSON layout of cosmos SDK CLIs (to be signed using `tx sign` when unsigned, and broadcasted
+    /// using `tx broadcast`). Transaction is never broadcasted, so, configuration of chain is not updated.
+    pub async fn export_revoke_allowance(
+        &self,
+        granter: impl Signer,
+        grantee: impl ToPublicKey,
+        chain_id: &ChainId,
+        memo: String,
+        request_id: Option<String>,
+        signed: bool,
+    ) -> Result<Value> {
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let grantee_address = chain.account_address(&grantee)?;
+
+        let tx_raw = if signed {
+            transaction_builder::msg_revoke_allowance(
+                &granter,
+                &self.plugins,
+                &chain,
+                grantee_address,
+                memo,
+                request_id.as_deref(),
+            )
+            .await?
+        } else {
+            transaction_builder::msg_revoke_allowance(
+                DryRunSigner::new(granter)?,
+                &self.plugins,
+                &chain,
+                grantee_address,
+                memo,
+                request_id.as_deref(),
+            )
+            .await?
+        };
+
+        json::tx_raw_to_cosmos_json(&tx_raw, signed)
+    }
+
+    /// Sends `amount` tokens of `denom` from `signer` to `to_address` on given chain (e.g. for funding solo machine
+    /// signer on a development chain)
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_tokens(
+        &self,
+        signer: impl Signer,
+        chain_id: &ChainId,
+        to_address: String,
+        amount: u64,
+        denom: String,
+        memo: String,
+        request_id: Option<String>,
+    ) -> Result<String> {
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let from_address = chain.account_address(&signer)?;
+        let to_address = chain
+            .resolve_account_address(&to_address, signer.get_account_prefix())
+            .await?;
+
+        // transfers are also checked when signing (except in EIP-712 sign mode whose messages are only hashed)
+        let spending = match signer.signing_policy() {
+            Some(policy) => {
+                policy.check_transfer(
+                    &chain_id.to_string(),
+                    &denom,
+                    amount.into(),
+                    Some(to_address.as_str()),
+                )?;
+
+                spending_limit::check_spending(
+                    &self.db_pool,
+                    &self.notifier,
+                    policy,
+                    chain_id,
+                    &denom,
+                    amount,
+                    request_id.as_deref(),
+                )
+                .await?
+            }
+            None => None,
+        };
+
+        let msg = transaction_builder::msg_send(
+            &signer,
+            &self.plugins,
+            &chain,
+            to_address.clone(),
+            amount,
+            denom.clone(),
+            memo,
+            request_id.as_deref(),
+        )
+        .await?;
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        let response = self
+            .submit(
+                &signer,
+                &rpc_client,
+                &chain,
+                PendingOperationKind::SendTokens,
+                msg,
+                request_id.as_deref(),
+            )
+            .await?;
+
+        let transaction_hash = response.ensure_success()?;
+
+        if let Some(spending) = spending {
+            spending.record(&self.db_pool).await?;
+        }
+
+        notify_event(
+            &self.notifier,
+            Event::TokensSent {
+                chain_id: chain_id.clone(),
+                from_address,
+                to_address,
+                amount,
+                denom,
+                transaction_hash: transaction_hash.clone(),
+            },
+        )?;
+
+        Ok(transaction_hash)
+    }
+
+    /// Builds the transaction for sending `amount` tokens of `denom` from `signer` to `to_address` on given chain in
+    /// dry-run mode, i.e., without signing, persisting or broadcasting anything
+    pub async fn dry_run_send_tokens(
+        &self,
+        signer: impl Signer,
+        chain_id: &ChainId,
+        to_address: String,
+        amount: u64,
+        denom: String,
+        memo: String,
+    ) -> Result<DryRunReport> {
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let to_address = chain
+            .resolve_account_address(&to_address, signer.get_account_prefix())
+            .await?;
+
+        let signer = DryRunSigner::new(signer)?;
+
+        let tx_raw = transaction_builder::msg_send(
+            &signer,
+            &self.plugins,
+            &chain,
+            to_address,
+            amount,
+            denom,
+            memo,
+            None,
+        )
+        .await?;
+
+        DryRunReport::new(chain_id, &tx_raw, signer.messages())
+    }
+
+    /// Assigns a label to a chain (when `target` is `None`), connection or channel. If the label is already assigned
+    /// to another entity (or entity already has a label), older label is marked as removed.
+    pub async fn set_label(
+        &self,
+        chain_id: &ChainId,
+        kind: LabelKind,
+        target: Option<&str>,
+        label: &str,
+    ) -> Result<()> {
+        let label: Identifier = label.parse().context("invalid label")?;
+
+        let target = match (kind, target) {
+            (LabelKind::Chain, None) => chain_id.to_string(),
+            (LabelKind::Connection, Some(target)) => target.parse::<ConnectionId>()?.to_string(),
+            (LabelKind::Channel, Some(target)) => target.parse::<ChannelId>()?.to_string(),
+            (LabelKind::Chain, Some(_)) => bail!("chain labels cannot have a target"),
+            (_, None) => bail!("target is required for {} labels", kind),
+        };
+
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        ensure!(
+            chain::get_chain(&mut transaction, chain_id)
+                .await?
+                .is_some(),
+            "chain details for {} not found",
+            chain_id
+        );
+
+        chain_labels::add_chain_label(&mut transaction, chain_id, kind, &target, &label).await?;
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for assigning label")?;
+
+        notify_event(
+            &self.notifier,
+            Event::LabelAssigned {
+                chain_id: chain_id.clone(),
+                kind,
+                target,
+                label: label.to_string(),
+            },
+        )
+    }
+
+    /// Removes an active label of a chain, connection or channel (label stays in label history)
+    pub async fn remove_label(
+        &self,
+        chain_id: &ChainId,
+        kind: LabelKind,
+        label: &str,
+    ) -> Result<()> {
+        let chain_label =
+            chain_labels::remove_chain_label(&self.db_pool, chain_id, kind, label).await?;
+
+        notify_event(
+            &self.notifier,
+            Event::LabelRemoved {
+                chain_id: chain_label.chain_id,
+                kind: chain_label.kind,
+                target: chain_label.target,
+                label: chain_label.label,
+            },
+        )
+    }
+
+    /// Returns active label of a chain, connection or channel
+    pub async fn get_label(
+        &self,
+        chain_id: &ChainId,
+        kind: LabelKind,
+        target: &str,
+    ) -> Result<Option<String>> {
+        Ok(
+            chain_labels::get_chain_label_by_target(&self.db_pool, chain_id, kind, target)
+                .await?
+                .map(|chain_label| chain_label.label),
+        )
+    }
+
+    /// Fetches history of all the labels (including removed ones)
+    pub async fn get_labels(&self, limit: u32, offset: u32) -> Result<Vec<ChainLabel>> {
+        chain_labels::get_chain_labels(&self.db_pool, limit, offset).await
+    }
+
+    /// Resolves given chain label or chain ID to a chain ID
+    pub async fn resolve_chain_id(&self, label_or_id: &str) -> Result<ChainId> {
+        match chain_labels::get_chain_label_by_name(
+            &self.db_pool,
+            None,
+            LabelKind::Chain,
+            label_or_id,
+        )
+        .await?
+        {
+            Some(chain_label) => Ok(chain_label.chain_id),
+            None => label_or_id.parse(),
+        }
+    }
+
+    /// Resolves given connection label or connection ID to a connection ID on given chain
+    pub async fn resolve_connection_id(
+        &self,
+        chain_id: &ChainId,
+        label_or_id: &str,
+    ) -> Result<ConnectionId> {
+        match chain_labels::get_chain_label_by_name(
+            &self.db_pool,
+            Some(chain_id),
+            LabelKind::Connection,
+            label_or_id,
+        )
+        .await?
+        {
+            Some(chain_label) => chain_label.target.parse(),
+            None => label_or_id.parse(),
+        }
+    }
+
+    /// Resolves given channel label or channel ID to a channel ID on given chain
+    pub async fn resolve_channel_id(
+        &self,
+        chain_id: &ChainId,
+        label_or_id: &str,
+    ) -> Result<ChannelId> {
+        match chain_labels::get_chain_label_by_name(
+            &self.db_pool,
+            Some(chain_id),
+            LabelKind::Channel,
+            label_or_id,
+        )
+        .await?
+        {
+            Some(chain_label) => chain_label.target.parse(),
+            None => label_or_id.parse(),
+        }
+    }
+
     /// Fetches details of a chain
     pub async fn get(&self, chain_id: &ChainId) -> Result<Option<Chain>> {
         chain::get_chain(&self.db_pool, chain_id).await
     }
 
+    /// Fetches details of all the chains
+    pub async fn get_all(&self) -> Result<Vec<Chain>> {
+        chain::get_chains(&self.db_pool).await
+    }
+
     /// Fetches all the public keys associated with solo machine client on given chain
     pub async fn get_public_keys(
         &self,
@@ -110,4 +828,375 @@ impl ChainService {
 
         chain.get_balance(signer, denom).await
     }
+
+    /// Broadcasts an encoded and signed transaction (`TxRaw`) to IBC enabled chain (optionally waiting for it to be
+    /// included in a block using inclusion policy of chain). Identifiers assigned by chain to solo machine's client,
+    /// connection or channel in a committed transaction are recorded for the chain.
+    pub async fn broadcast(
+        &self,
+        chain_id: &ChainId,
+        tx_bytes: Vec<u8>,
+        transport: BroadcastTransport,
+        mode: BroadcastMode,
+        wait: bool,
+    ) -> Result<BroadcastResult> {
+        let chain = self
+            .get(chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details not found when broadcasting transaction"))?;
+
+        TxRaw::decode(tx_bytes.as_slice())
+            .context("invalid transaction bytes (expected `TxRaw`)")?;
+
+        let mut broadcaster = Broadcaster::new(transport, mode);
+
+        if wait {
+            broadcaster = broadcaster.with_inclusion_policy(chain.config.inclusion_policy);
+        }
+
+        let result = broadcaster.broadcast(&chain.config, tx_bytes).await?;
+
+        // identifiers are only assigned once transaction is committed successfully
+        if result
+            .deliver_tx
+            .as_ref()
+            .is_some_and(ExecutionResult::is_ok)
+        {
+            let generated_identifiers = GeneratedIdentifiers::from_events(result.events())?;
+
+            if !generated_identifiers.is_empty() {
+                self.record_generated_identifiers(&chain.id, &generated_identifiers)
+                    .await?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Checks whether given chain has been reset (e.g. a devnet restarted from a new genesis) since its state was
+    /// recorded on solo machine. Genesis time of chain is recorded when it is checked for the first time (failure to
+    /// fetch genesis, e.g. when it is too large, skips the genesis time check).
+    pub async fn detect_reset(&self, chain_id: &ChainId) -> Result<Option<ChainResetEvidence>> {
+        let chain = self
+            .get(chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        if let Ok(genesis) = rpc_client.genesis().await {
+            let current: DateTime<Utc> = SystemTime::from(genesis.genesis_time).into();
+
+            match chain.genesis_time {
+                None => chain::set_genesis_time(&self.db_pool, chain_id, current).await?,
+                Some(recorded) if recorded != current => {
+                    return Ok(Some(ChainResetEvidence::GenesisTimeChanged {
+                        recorded,
+                        current,
+                    }))
+                }
+                Some(_) => {}
+            }
+        }
+
+        let latest_height = rpc_client
+            .status()
+            .await
+            .context("unable to query status of chain")?
+            .sync_info
+            .latest_block_height
+            .value();
+        let trusted_height = chain.config.trusted_height.value();
+
+        if latest_height < trusted_height {
+            return Ok(Some(ChainResetEvidence::HeightRegressed {
+                trusted_height,
+                latest_height,
+            }));
+        }
+
+        // block at trusted height may be pruned on the node, in which case hashes cannot be compared
+        if let Ok(commit) = rpc_client.commit(chain.config.trusted_height).await {
+            let block_hash = commit.signed_header.header.hash();
+
+            if block_hash.as_bytes() != chain.config.trusted_hash {
+                return Ok(Some(ChainResetEvidence::BlockHashMismatch {
+                    height: trusted_height,
+                    trusted_hash: hex::encode_upper(chain.config.trusted_hash),
+                    block_hash: hex::encode_upper(block_hash.as_bytes()),
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Resets IBC state of given chain after it has been reset (e.g. a devnet restarted from a new genesis): removes
+    /// stored tendermint client, connection and channel of chain along with its connection and channel labels, clears
+    /// connection details and trusts given block of chain (latest block when not provided). Keys of chain and state of
+    /// other chains are preserved, and a new connection can be established afterwards.
+    pub async fn reset_state(
+        &self,
+        chain_id: &ChainId,
+        trusted: Option<(BlockHeight, [u8; 32])>,
+    ) -> Result<()> {
+        let _chain_lock = lock_chain(&self.db_pool, chain_id).await?;
+
+        let mut chain = self
+            .get(chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        let status = rpc_client
+            .status()
+            .await
+            .context("unable to query status of chain")?;
+
+        ensure!(
+            status.node_info.network.to_string() == chain_id.to_string(),
+            "rpc node of chain {} is on a different network ({})",
+            chain_id,
+            status.node_info.network
+        );
+
+        let (trusted_height, trusted_hash) = match trusted {
+            Some(trusted) => trusted,
+            None => match status.sync_info.latest_block_hash {
+                TendermintHash::Sha256(hash) => (status.sync_info.latest_block_height, hash),
+                TendermintHash::None => bail!("latest block hash of chain {} is empty", chain_id),
+            },
+        };
+
+        let genesis = rpc_client
+            .genesis()
+            .await
+            .context("unable to query genesis of chain")?;
+        let genesis_time: DateTime<Utc> = SystemTime::from(genesis.genesis_time).into();
+
+        chain.config.trusted_height = trusted_height;
+        chain.config.trusted_hash = trusted_hash;
+
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let progress = chain.handshake_progress.clone().unwrap_or_default();
+        let connection_details = chain.connection_details.as_ref();
+
+        let client_ids = connection_details
+            .map(|details| details.tendermint_client_id.clone())
+            .into_iter()
+            .chain(progress.tendermint_client_id);
+        let connection_ids = connection_details
+            .map(|details| details.tendermint_connection_id.clone())
+            .into_iter()
+            .chain(progress.tendermint_connection_id);
+        let channel_ids = connection_details
+            .map(|details| details.tendermint_channel_id.clone())
+            .into_iter()
+            .chain(progress.tendermint_channel_id);
+
+        for client_id in client_ids {
+            ibc::remove_tendermint_client(&mut transaction, &client_id).await?;
+        }
+
+        for connection_id in connection_ids {
+            ibc::remove_connection(&mut transaction, &connection_id).await?;
+        }
+
+        for channel_id in channel_ids {
+            ibc::remove_channel(&mut transaction, &chain.config.port_id, &channel_id).await?;
+        }
+
+        chain::reset_chain_state(
+            &mut transaction,
+            chain_id,
+            &status.node_info.id,
+            &chain.config,
+            genesis_time,
+        )
+        .await?;
+
+        let removed_labels = chain_labels::remove_ibc_labels(&mut transaction, chain_id).await?;
+
+        packet::remove_packets(&mut transaction, chain_id).await?;
+        pending_operation::fail_unresolved_operations(&mut transaction, chain_id, "chain is reset")
+            .await?;
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for resetting state of chain")?;
+
+        for label in removed_labels {
+            notify_event(
+                &self.notifier,
+                Event::LabelRemoved {
+                    chain_id: label.chain_id,
+                    kind: label.kind,
+                    target: label.target,
+                    label: label.label,
+                },
+            )?;
+        }
+
+        notify_event(
+            &self.notifier,
+            Event::ChainStateReset {
+                chain_id: chain_id.clone(),
+                trusted_height: trusted_height.value(),
+                trusted_hash: hex::encode_upper(trusted_hash),
+            },
+        )
+    }
+
+    /// Compares local sequence of chain with sequence of solo machine client on chain (divergence is notified using
+    /// [`Event::SequenceDiverged`])
+    pub async fn check_sequence(&self, chain_id: &ChainId) -> Result<SequenceStatus> {
+        let chain = self
+            .get(chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let status = sequence_status(&chain).await?;
+
+        if !status.is_in_sync() {
+            notify_event(
+                &self.notifier,
+                Event::SequenceDiverged {
+                    chain_id: chain_id.clone(),
+                    client_id: status.client_id.clone(),
+                    local: status.local,
+                    on_chain: status.on_chain,
+                },
+            )?;
+        }
+
+        Ok(status)
+    }
+
+    /// Resynchronizes local sequence of chain with sequence of solo machine client on chain (when they diverge) and
+    /// returns the status before resynchronization. Resynchronization is refused while the chain has unresolved
+    /// pending operations (their signatures may still be included on chain).
+    pub async fn resync_sequence(&self, chain_id: &ChainId) -> Result<SequenceStatus> {
+        let _chain_lock = lock_chain(&self.db_pool, chain_id).await?;
+
+        let chain = self
+            .get(chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let unresolved = pending_operation::get_unresolved_operations(&self.db_pool, chain_id)
+            .await?
+            .len();
+
+        ensure!(
+            unresolved == 0,
+            "chain {} has {} unresolved pending operation(s), resolve them before resynchronizing sequence",
+            chain_id,
+            unresolved
+        );
+
+        let status = sequence_status(&chain).await?;
+
+        if !status.is_in_sync() {
+            let sequence = u32::try_from(status.on_chain).with_context(|| {
+                format!(
+                    "sequence of solo machine client on chain is too large: {}",
+                    status.on_chain
+                )
+            })?;
+
+            chain::restore_sequences(&self.db_pool, chain_id, sequence, chain.packet_sequence)
+                .await?;
+        }
+
+        Ok(status)
+    }
+
+    /// Records identifiers assigned by chain to solo machine's client, connection or channel (merged with already
+    /// recorded identifiers) and returns all the recorded identifiers
+    pub async fn record_generated_identifiers(
+        &self,
+        chain_id: &ChainId,
+        generated_identifiers: &GeneratedIdentifiers,
+    ) -> Result<GeneratedIdentifiers> {
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let identifiers =
+            chain::add_generated_identifiers(&mut transaction, chain_id, generated_identifiers)
+                .await?;
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for adding generated identifiers")?;
+
+        Ok(identifiers)
+    }
+
+    /// Queues a signed transaction (which does not change state of solo machine) in pending operation queue and
+    /// submits it
+    async fn submit<C>(
+        &self,
+        signer: impl Signer,
+        rpc_client: &C,
+        chain: &Chain,
+        kind: PendingOperationKind,
+        msg: TxRaw,
+        request_id: Option<&str>,
+    ) -> Result<BroadcastResult>
+    where
+        C: Client + Send + Sync,
+    {
+        let transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let operation = operation_queue::enqueue(
+            transaction,
+            &chain.id,
+            Checkpoint::of(chain),
+            kind,
+            request_id,
+            &msg,
+        )
+        .await?;
+
+        operation_queue::submit(
+            &self.db_pool,
+            &self.notifier,
+            signer,
+            &self.plugins,
+            rpc_client,
+            chain,
+            &operation,
+        )
+        .await
+    }
+}
+
+async fn sequence_status(chain: &Chain) -> Result<SequenceStatus> {
+    let connection_details = chain.connection_details.as_ref().ok_or_else(|| {
+        anyhow!(
+            "connection details not found for chain with id {}",
+            chain.id
+        )
+    })?;
+
+    Ok(SequenceStatus {
+        client_id: connection_details.solo_machine_client_id.clone(),
+        local: chain.sequence.into(),
+        on_chain: chain.get_solo_machine_sequence().await?,
+    })
 }
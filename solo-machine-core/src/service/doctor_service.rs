@@ -0,0 +1,631 @@
+use std::{
+    fmt,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use cosmos_sdk_proto::{
+    cosmos::bank::v1beta1::{query_client::QueryClient as BankQueryClient, QueryBalanceRequest},
+    ibc::core::client::v1::{QueryClientStateRequest, QueryClientStateResponse},
+};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tendermint_rpc::{endpoint::status::Response as StatusResponse, Client};
+use tonic::{client::Grpc, codec::ProstCodec, codegen::http::uri::PathAndQuery, Code, Request};
+
+use crate::{
+    cosmos::grpc_pool::grpc_channel,
+    ibc::core::ics24_host::identifier::ChainId,
+    model::{chain, Chain, ChainConfig},
+    rpc_client::TimeoutRpcClient,
+    transaction_builder::{get_account_details, get_unbonding_period},
+    DbPool, ToPublicKey,
+};
+
+use super::{endpoint_probe::grpc_network, status_service::client_status};
+
+const CLIENT_STATE_PATH: &str = "/ibc.core.client.v1.Query/ClientState";
+
+/// Balance of fee denom below this multiple of fee amount is reported as low
+const LOW_BALANCE_FEE_MULTIPLE: u64 = 10;
+
+/// Severity of a diagnostic finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Severity {
+    /// Check passed
+    Ok,
+    /// Check found an issue which may cause operations to fail
+    Warning,
+    /// Check found an issue which causes operations to fail
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ok => write!(f, "ok"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Outcome of a diagnostic check on a chain
+#[derive(Debug, Serialize)]
+pub struct Finding {
+    /// Chain ID of IBC enabled chain
+    pub chain_id: String,
+    /// Name of check (e.g. `rpc`, `balance`)
+    pub check: &'static str,
+    /// Severity of finding
+    pub severity: Severity,
+    /// Description of finding
+    pub message: String,
+    /// Suggested action to resolve the issue (if any)
+    pub action: Option<String>,
+}
+
+/// Used to diagnose configuration and connectivity issues of chains by querying them
+pub struct DoctorService {
+    db_pool: DbPool,
+}
+
+impl DoctorService {
+    /// Creates a new instance of doctor service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Diagnoses given chains (all the chains when empty): checks connectivity of RPC and gRPC endpoints, consistency
+    /// of chain IDs, sync status of nodes, existence and balance of signer's account, unbonding period of chain
+    /// against configured trusting period and health of IBC clients
+    pub async fn diagnose(
+        &self,
+        signer: impl ToPublicKey,
+        chain_ids: &[ChainId],
+    ) -> Result<Vec<Finding>> {
+        let chains = if chain_ids.is_empty() {
+            chain::get_chains(&self.db_pool).await?
+        } else {
+            let mut chains = Vec::with_capacity(chain_ids.len());
+
+            for chain_id in chain_ids {
+                chains.push(
+                    chain::get_chain(&self.db_pool, chain_id)
+                        .await?
+                        .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?,
+                );
+            }
+
+            chains
+        };
+
+        let mut findings = Vec::new();
+
+        for chain in chains {
+            let mut diagnosis = Diagnosis {
+                db_pool: &self.db_pool,
+                chain: &chain,
+                findings: &mut findings,
+            };
+
+            diagnosis.check_rpc().await;
+            diagnosis.check_fallbacks().await;
+
+            // remaining checks query chain over grpc
+            if diagnosis.check_grpc().await {
+                if diagnosis.check_account(&signer).await {
+                    diagnosis.check_balance(&signer).await;
+                }
+
+                diagnosis.check_trusting_period().await;
+            }
+
+            diagnosis.check_clients().await?;
+        }
+
+        Ok(findings)
+    }
+}
+
+struct Diagnosis<'a> {
+    db_pool: &'a DbPool,
+    chain: &'a Chain,
+    findings: &'a mut Vec<Finding>,
+}
+
+impl Diagnosis<'_> {
+    fn push(
+        &mut self,
+        check: &'static str,
+        severity: Severity,
+        message: String,
+        action: Option<String>,
+    ) {
+        self.findings.push(Finding {
+            chain_id: self.chain.id.to_string(),
+            check,
+            severity,
+            message,
+            action,
+        })
+    }
+
+    async fn check_rpc(&mut self) {
+        let rpc_addr = self.chain.config.rpc_addr.clone();
+
+        let status = match query_status(&rpc_addr, &self.chain.config).await {
+            Ok(status) => status,
+            Err(err) => {
+                self.push(
+                    "rpc",
+                    Severity::Error,
+                    format!("unable to query status at {}: {:#}", rpc_addr, err),
+                    Some(
+                        "check that node is running and reachable at configured RPC address"
+                            .to_string(),
+                    ),
+                );
+                return;
+            }
+        };
+
+        self.push(
+            "rpc",
+            Severity::Ok,
+            format!("rpc endpoint at {} is reachable", rpc_addr),
+            None,
+        );
+
+        let network = status.node_info.network.to_string();
+
+        if network != self.chain.id.to_string() {
+            self.push(
+                "chain-id",
+                Severity::Error,
+                format!("rpc endpoint at {} serves chain {}", rpc_addr, network),
+                Some("configured RPC address points to a different chain".to_string()),
+            );
+            return;
+        }
+
+        self.push(
+            "chain-id",
+            Severity::Ok,
+            format!("rpc endpoint serves chain {}", network),
+            None,
+        );
+
+        let latest_height = status.sync_info.latest_block_height;
+        let latest_block_time: DateTime<Utc> =
+            SystemTime::from(status.sync_info.latest_block_time).into();
+        let block_age = (Utc::now() - latest_block_time)
+            .to_std()
+            .unwrap_or_default();
+        let halt_threshold = self.chain.config.halt_threshold;
+
+        if status.sync_info.catching_up {
+            self.push(
+                "sync",
+                Severity::Warning,
+                format!("node is catching up (latest height: {})", latest_height),
+                Some("wait for node to sync or use another node".to_string()),
+            );
+        } else if !halt_threshold.is_zero() && block_age > halt_threshold {
+            self.push(
+                "sync",
+                Severity::Warning,
+                format!(
+                    "latest block {} was produced at {} (chain may be halted)",
+                    latest_height, latest_block_time
+                ),
+                Some(
+                    "operations consuming sequences are paused until block production resumes"
+                        .to_string(),
+                ),
+            );
+        } else {
+            self.push(
+                "sync",
+                Severity::Ok,
+                format!("node is synced (latest height: {})", latest_height),
+                None,
+            );
+        }
+    }
+
+    /// Checks that fallback endpoints of chain are reachable and serve the chain
+    async fn check_fallbacks(&mut self) {
+        let chain_id = self.chain.id.to_string();
+
+        for rpc_addr in self.chain.config.fallback_rpc_addrs.clone() {
+            let network = query_status(&rpc_addr, &self.chain.config)
+                .await
+                .map(|status| status.node_info.network.to_string());
+            self.push_fallback("rpc", &rpc_addr, &chain_id, network);
+        }
+
+        for grpc_addr in self.chain.config.fallback_grpc_addrs.clone() {
+            let network = grpc_network(&grpc_addr, &self.chain.config).await;
+            self.push_fallback("grpc", &grpc_addr, &chain_id, network);
+        }
+    }
+
+    fn push_fallback(&mut self, kind: &str, addr: &str, chain_id: &str, network: Result<String>) {
+        match network {
+            Err(err) => self.push(
+                "fallback",
+                Severity::Warning,
+                format!(
+                    "fallback {} endpoint at {} is unreachable: {:#}",
+                    kind, addr, err
+                ),
+                Some("operations cannot fail over to this endpoint until it recovers".to_string()),
+            ),
+            Ok(network) if network != chain_id => self.push(
+                "fallback",
+                Severity::Error,
+                format!(
+                    "fallback {} endpoint at {} serves chain {}",
+                    kind, addr, network
+                ),
+                Some("fallback address points to a different chain".to_string()),
+            ),
+            Ok(_) => self.push(
+                "fallback",
+                Severity::Ok,
+                format!("fallback {} endpoint at {} is reachable", kind, addr),
+                None,
+            ),
+        }
+    }
+
+    /// Returns `true` if gRPC endpoint of chain is reachable and serves the chain
+    async fn check_grpc(&mut self) -> bool {
+        let grpc_addr = self.chain.config.grpc_addr.clone();
+
+        match grpc_network(&grpc_addr, &self.chain.config).await {
+            Err(err) => {
+                self.push(
+                    "grpc",
+                    Severity::Error,
+                    format!("{:#}", err),
+                    Some(
+                        "check that gRPC is enabled on node and reachable at configured gRPC address"
+                            .to_string(),
+                    ),
+                );
+                false
+            }
+            Ok(network) if network != self.chain.id.to_string() => {
+                self.push(
+                    "grpc",
+                    Severity::Error,
+                    format!("grpc endpoint at {} serves chain {}", grpc_addr, network),
+                    Some("configured gRPC address points to a different chain".to_string()),
+                );
+                false
+            }
+            Ok(_) => {
+                self.push(
+                    "grpc",
+                    Severity::Ok,
+                    format!("grpc endpoint at {} is reachable", grpc_addr),
+                    None,
+                );
+                true
+            }
+        }
+    }
+
+    /// Returns `true` if account of signer exists on chain
+    async fn check_account(&mut self, signer: impl ToPublicKey) -> bool {
+        let address = match self.chain.account_address(&signer) {
+            Ok(address) => address,
+            Err(err) => {
+                self.push(
+                    "account",
+                    Severity::Error,
+                    format!("unable to compute account address of signer: {:#}", err),
+                    None,
+                );
+                return false;
+            }
+        };
+
+        match get_account_details(signer, self.chain).await {
+            Ok((account_number, sequence)) => {
+                self.push(
+                    "account",
+                    Severity::Ok,
+                    format!(
+                        "account {} exists (account number: {}, sequence: {})",
+                        address, account_number, sequence
+                    ),
+                    None,
+                );
+                true
+            }
+            Err(err) => {
+                self.push(
+                    "account",
+                    Severity::Error,
+                    format!("{:#}", err),
+                    Some(format!(
+                        "fund account {} with {} to create it on chain",
+                        address, self.chain.config.fee.denom
+                    )),
+                );
+                false
+            }
+        }
+    }
+
+    async fn check_balance(&mut self, signer: impl ToPublicKey) {
+        let fee = &self.chain.config.fee;
+
+        if let Some(ref granter) = fee.granter {
+            self.push(
+                "balance",
+                Severity::Ok,
+                format!("fees are paid using fee allowance of {}", granter),
+                None,
+            );
+            return;
+        }
+
+        let balance = match query_balance(self.chain, signer).await {
+            Ok(balance) => balance,
+            Err(err) => {
+                self.push(
+                    "balance",
+                    Severity::Warning,
+                    format!("unable to query balance: {:#}", err),
+                    None,
+                );
+                return;
+            }
+        };
+
+        if balance < fee.amount {
+            self.push(
+                "balance",
+                Severity::Error,
+                format!(
+                    "balance {} {} is less than fee amount {} {}",
+                    balance, fee.denom, fee.amount, fee.denom
+                ),
+                Some(format!("fund account with {}", fee.denom)),
+            );
+        } else if balance < fee.amount * Decimal::from(LOW_BALANCE_FEE_MULTIPLE) {
+            self.push(
+                "balance",
+                Severity::Warning,
+                format!(
+                    "balance {} {} covers fewer than {} transactions",
+                    balance, fee.denom, LOW_BALANCE_FEE_MULTIPLE
+                ),
+                Some(format!("fund account with {}", fee.denom)),
+            );
+        } else {
+            self.push(
+                "balance",
+                Severity::Ok,
+                format!("balance is {} {}", balance, fee.denom),
+                None,
+            );
+        }
+    }
+
+    async fn check_trusting_period(&mut self) {
+        let trusting_period = self.chain.config.trusting_period;
+
+        let unbonding_period = match get_unbonding_period(self.chain).await {
+            Ok(unbonding_period) => Duration::new(
+                unbonding_period.seconds.max(0) as u64,
+                unbonding_period.nanos.max(0) as u32,
+            ),
+            Err(err) => {
+                self.push(
+                    "trusting-period",
+                    Severity::Warning,
+                    format!("unable to query unbonding period: {:#}", err),
+                    None,
+                );
+                return;
+            }
+        };
+
+        if trusting_period >= unbonding_period {
+            self.push(
+                "trusting-period",
+                Severity::Error,
+                format!(
+                    "trusting period ({}) is not less than unbonding period ({}) of chain",
+                    format_days(trusting_period),
+                    format_days(unbonding_period)
+                ),
+                Some("add chain again with a trusting period less than unbonding period (e.g. 2/3 of it)".to_string()),
+            );
+        } else {
+            self.push(
+                "trusting-period",
+                Severity::Ok,
+                format!(
+                    "trusting period ({}) is less than unbonding period ({})",
+                    format_days(trusting_period),
+                    format_days(unbonding_period)
+                ),
+                None,
+            );
+        }
+    }
+
+    async fn check_clients(&mut self) -> Result<()> {
+        let chain = self.chain;
+
+        let connection_details = match chain.connection_details {
+            Some(ref connection_details) => connection_details,
+            None => {
+                self.push(
+                    "tendermint-client",
+                    Severity::Ok,
+                    "chain is not connected yet".to_string(),
+                    None,
+                );
+                return Ok(());
+            }
+        };
+
+        let client = client_status(
+            self.db_pool,
+            &connection_details.tendermint_client_id,
+            chain.config.trusting_period,
+        )
+        .await?;
+
+        let refresh_margin = chrono::Duration::from_std(chain.config.trusting_period / 3)
+            .context("invalid trusting period")?;
+
+        if client.expired {
+            self.push(
+                "tendermint-client",
+                Severity::Error,
+                format!(
+                    "tendermint client {} expired at {}",
+                    client.client_id, client.expires_at
+                ),
+                Some(format!(
+                    "expired clients cannot be updated, reset IBC state of chain using `chain reset-state {} --force \
+                     --yes` and connect again",
+                    chain.id
+                )),
+            );
+        } else if client.expires_at - Utc::now() < refresh_margin {
+            self.push(
+                "tendermint-client",
+                Severity::Warning,
+                format!(
+                    "tendermint client {} expires at {}",
+                    client.client_id, client.expires_at
+                ),
+                Some("run `relay` to keep tendermint client refreshed".to_string()),
+            );
+        } else {
+            self.push(
+                "tendermint-client",
+                Severity::Ok,
+                format!(
+                    "tendermint client {} expires at {}",
+                    client.client_id, client.expires_at
+                ),
+                None,
+            );
+        }
+
+        let solo_machine_client_id = connection_details.solo_machine_client_id.to_string();
+
+        match query_client_state(&chain.config, &solo_machine_client_id).await {
+            Ok(true) => self.push(
+                "solo-machine-client",
+                Severity::Ok,
+                format!(
+                    "solo machine client {} exists on chain",
+                    solo_machine_client_id
+                ),
+                None,
+            ),
+            Ok(false) => self.push(
+                "solo-machine-client",
+                Severity::Error,
+                format!(
+                    "solo machine client {} is not found on chain",
+                    solo_machine_client_id
+                ),
+                Some(format!(
+                    "chain may have been reset, check it using `chain reset-state {}`",
+                    chain.id
+                )),
+            ),
+            Err(err) => self.push(
+                "solo-machine-client",
+                Severity::Warning,
+                format!(
+                    "unable to query solo machine client {}: {:#}",
+                    solo_machine_client_id, err
+                ),
+                None,
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+async fn query_status(rpc_addr: &str, config: &ChainConfig) -> Result<StatusResponse> {
+    TimeoutRpcClient::with_config(rpc_addr, config)?
+        .status()
+        .await
+        .map_err(Into::into)
+}
+
+async fn query_balance(chain: &Chain, signer: impl ToPublicKey) -> Result<Decimal> {
+    let mut query_client =
+        BankQueryClient::new(grpc_channel(&chain.config.grpc_addr, &chain.config).await?);
+
+    let balance = query_client
+        .balance(QueryBalanceRequest {
+            address: chain.account_address(&signer)?,
+            denom: chain.config.fee.denom.to_string(),
+        })
+        .await
+        .with_context(|| format!("unable to query balance from {}", chain.config.grpc_addr))?
+        .into_inner()
+        .balance;
+
+    match balance {
+        None => Ok(Decimal::ZERO),
+        Some(coin) => coin.amount.parse().context("invalid balance amount"),
+    }
+}
+
+/// Returns `true` if client with given ID exists on chain
+async fn query_client_state(config: &ChainConfig, client_id: &str) -> Result<bool> {
+    let channel = grpc_channel(&config.grpc_addr, config).await?;
+
+    let mut client = Grpc::new(channel);
+
+    client
+        .ready()
+        .await
+        .context("grpc query client is not ready")?;
+
+    let response = client
+        .unary::<_, QueryClientStateResponse, _>(
+            Request::new(QueryClientStateRequest {
+                client_id: client_id.to_string(),
+            }),
+            PathAndQuery::from_static(CLIENT_STATE_PATH),
+            ProstCodec::default(),
+        )
+        .await;
+
+    match response {
+        Ok(response) => Ok(response.into_inner().client_state.is_some()),
+        Err(status) if status.code() == Code::NotFound => Ok(false),
+        Err(status) => Err(anyhow!("{}", status.message())),
+    }
+}
+
+fn format_days(duration: Duration) -> String {
+    let days = duration.as_secs() / (24 * 60 * 60);
+    let hours = duration.as_secs() % (24 * 60 * 60) / (60 * 60);
+
+    match (days, hours) {
+        (0, hours) => format!("{}h", hours),
+        (days, 0) => format!("{} days", days),
+        (days, hours) => format!("{} days {}h", days, hours),
+    }
+}
@@ -0,0 +1,273 @@
+//! Probing of RPC and gRPC endpoints of an IBC enabled chain before it is registered
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use cosmos_sdk_proto::{
+    cosmos::base::{
+        query::v1beta1::PageRequest,
+        tendermint::v1beta1::{
+            service_client::ServiceClient, GetNodeInfoRequest, GetNodeInfoResponse,
+        },
+    },
+    ibc::{
+        applications::transfer::v1::{QueryParamsRequest, QueryParamsResponse},
+        core::client::v1::{QueryClientStatesRequest, QueryClientStatesResponse},
+    },
+};
+use tendermint::node::Id as NodeId;
+use tendermint_rpc::Client;
+use tonic::{client::Grpc, codec::ProstCodec, codegen::http::uri::PathAndQuery, Code, Request};
+
+use crate::{
+    cosmos::grpc_pool::{grpc_channel, grpc_endpoint, GrpcChannel},
+    ibc::core::ics24_host::identifier::ChainId,
+    ibc_version::IbcVersion,
+    model::ChainConfig,
+    rpc_client::TimeoutRpcClient,
+};
+
+const CLIENT_STATES_PATH: &str = "/ibc.core.client.v1.Query/ClientStates";
+const TRANSFER_PARAMS_PATH: &str = "/ibc.applications.transfer.v1.Query/Params";
+
+/// Outcome of probing endpoints of a chain
+#[derive(Debug)]
+pub(crate) struct ProbeReport {
+    /// Chain ID reported by endpoints
+    pub chain_id: ChainId,
+    /// Node ID of RPC endpoint
+    pub node_id: NodeId,
+    /// IBC implementation of chain (`None` if node does not report its build dependencies)
+    pub ibc_version: Option<IbcVersion>,
+    /// Non-fatal issues found while probing
+    pub warnings: Vec<String>,
+}
+
+/// Probes RPC and gRPC endpoints of chain with given configuration. Unreachable endpoints, endpoints reporting
+/// different chain IDs, chains without IBC and trusted blocks not matching the chain are rejected.
+pub(crate) async fn probe_endpoints(config: &ChainConfig) -> Result<ProbeReport> {
+    let mut warnings = Vec::new();
+
+    // reject invalid gRPC address or TLS settings before reaching out to any endpoint
+    grpc_endpoint(&config.grpc_addr, &config.grpc_tls)?;
+
+    let rpc_client = TimeoutRpcClient::with_config(&config.rpc_addr, config)
+        .with_context(|| format!("unable to connect to rpc client at {}", config.rpc_addr))?;
+    let status = rpc_client
+        .status()
+        .await
+        .with_context(|| format!("unable to query status of chain at {}", config.rpc_addr))?;
+
+    let chain_id: ChainId = status.node_info.network.to_string().parse()?;
+
+    if status.sync_info.catching_up {
+        warnings.push(format!(
+            "node at {} is catching up with chain {}",
+            config.rpc_addr, chain_id
+        ));
+    }
+
+    let latest_height = status.sync_info.latest_block_height.value();
+    let trusted_height = config.trusted_height.value();
+
+    ensure!(
+        trusted_height <= latest_height,
+        "trusted height {} is above latest height {} of chain {}",
+        trusted_height,
+        latest_height,
+        chain_id
+    );
+
+    // block at trusted height may be pruned on the node, in which case hashes cannot be compared
+    match rpc_client.commit(config.trusted_height).await {
+        Ok(commit) => {
+            let block_hash = commit.signed_header.header.hash();
+
+            ensure!(
+                block_hash.as_bytes() == config.trusted_hash,
+                "trusted hash {} does not match hash {} of block at height {} of chain {}",
+                hex::encode_upper(config.trusted_hash),
+                hex::encode_upper(block_hash.as_bytes()),
+                trusted_height,
+                chain_id
+            );
+        }
+        Err(_) => warnings.push(format!(
+            "block at trusted height {} is not available on node at {} (trusted hash is not verified)",
+            trusted_height, config.rpc_addr
+        )),
+    }
+
+    let ibc_version = probe_grpc(config, &chain_id, &mut warnings).await?;
+
+    probe_fallbacks(config, &chain_id, &mut warnings).await?;
+
+    Ok(ProbeReport {
+        chain_id,
+        node_id: status.node_info.id,
+        ibc_version,
+        warnings,
+    })
+}
+
+async fn probe_grpc(
+    config: &ChainConfig,
+    chain_id: &ChainId,
+    warnings: &mut Vec<String>,
+) -> Result<Option<IbcVersion>> {
+    let channel = grpc_channel(&config.grpc_addr, config).await?;
+
+    let node_info = query_node_info(channel.clone(), &config.grpc_addr).await?;
+    let network = node_info
+        .default_node_info
+        .ok_or_else(|| anyhow!("missing node info in response of {}", config.grpc_addr))?
+        .network;
+    let ibc_version = node_info
+        .application_version
+        .and_then(|version| IbcVersion::from_build_deps(&version.build_deps));
+
+    ensure!(
+        network == chain_id.to_string(),
+        "grpc endpoint at {} serves chain {} while rpc endpoint at {} serves chain {}",
+        config.grpc_addr,
+        network,
+        config.rpc_addr,
+        chain_id
+    );
+
+    let mut client = Grpc::new(channel);
+
+    client
+        .ready()
+        .await
+        .context("grpc query client is not ready")?;
+
+    let client_states = client
+        .unary::<_, QueryClientStatesResponse, _>(
+            Request::new(QueryClientStatesRequest {
+                pagination: Some(PageRequest {
+                    limit: 1,
+                    ..Default::default()
+                }),
+            }),
+            PathAndQuery::from_static(CLIENT_STATES_PATH),
+            ProstCodec::default(),
+        )
+        .await;
+
+    match client_states {
+        Ok(_) => {}
+        Err(status) if status.code() == Code::Unimplemented => {
+            bail!("IBC is not enabled on chain {}", chain_id)
+        }
+        Err(status) => warnings.push(format!(
+            "unable to query IBC clients of chain {}: {}",
+            chain_id,
+            status.message()
+        )),
+    }
+
+    if config.port_id.to_string() != "transfer" {
+        return Ok(ibc_version);
+    }
+
+    client
+        .ready()
+        .await
+        .context("grpc query client is not ready")?;
+
+    let transfer_params = client
+        .unary::<_, QueryParamsResponse, _>(
+            Request::new(QueryParamsRequest {}),
+            PathAndQuery::from_static(TRANSFER_PARAMS_PATH),
+            ProstCodec::default(),
+        )
+        .await;
+
+    match transfer_params.map(|response| response.into_inner().params) {
+        Ok(Some(params)) => {
+            if !params.send_enabled || !params.receive_enabled {
+                warnings.push(format!(
+                    "IBC transfers are disabled on chain {} (send enabled: {}, receive enabled: {})",
+                    chain_id, params.send_enabled, params.receive_enabled
+                ));
+            }
+        }
+        Ok(None) => {}
+        Err(status) if status.code() == Code::Unimplemented => {
+            bail!("IBC transfer module is not enabled on chain {}", chain_id)
+        }
+        Err(status) => warnings.push(format!(
+            "unable to query IBC transfer params of chain {}: {}",
+            chain_id,
+            status.message()
+        )),
+    }
+
+    Ok(ibc_version)
+}
+
+/// Probes fallback endpoints of chain. Fallback endpoints serving a different chain are rejected, while unreachable
+/// ones are reported as warnings (they may be temporarily down).
+async fn probe_fallbacks(
+    config: &ChainConfig,
+    chain_id: &ChainId,
+    warnings: &mut Vec<String>,
+) -> Result<()> {
+    for rpc_addr in config.fallback_rpc_addrs.iter() {
+        let status = match TimeoutRpcClient::with_config(rpc_addr, config) {
+            Ok(rpc_client) => rpc_client.status().await.map_err(Into::into),
+            Err(err) => Err(err),
+        };
+
+        match status {
+            Ok(status) => ensure!(
+                status.node_info.network.to_string() == chain_id.to_string(),
+                "fallback rpc endpoint at {} serves chain {} instead of {}",
+                rpc_addr,
+                status.node_info.network,
+                chain_id
+            ),
+            Err(err) => warnings.push(format!(
+                "fallback rpc endpoint at {} is unreachable: {:#}",
+                rpc_addr, err
+            )),
+        }
+    }
+
+    for grpc_addr in config.fallback_grpc_addrs.iter() {
+        let network = grpc_network(grpc_addr, config).await;
+
+        match network {
+            Ok(network) => ensure!(
+                network == chain_id.to_string(),
+                "fallback grpc endpoint at {} serves chain {} instead of {}",
+                grpc_addr,
+                network,
+                chain_id
+            ),
+            Err(err) => warnings.push(format!(
+                "fallback grpc endpoint at {} is unreachable: {:#}",
+                grpc_addr, err
+            )),
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns chain ID served by gRPC endpoint at given address (using TLS settings, timeouts and proxy of given chain)
+pub(crate) async fn grpc_network(grpc_addr: &str, config: &ChainConfig) -> Result<String> {
+    let channel = grpc_channel(grpc_addr, config).await?;
+
+    Ok(query_node_info(channel, grpc_addr)
+        .await?
+        .default_node_info
+        .ok_or_else(|| anyhow!("missing node info in response of {}", grpc_addr))?
+        .network)
+}
+
+async fn query_node_info(channel: GrpcChannel, grpc_addr: &str) -> Result<GetNodeInfoResponse> {
+    Ok(ServiceClient::new(channel)
+        .get_node_info(GetNodeInfoRequest {})
+        .await
+        .with_context(|| format!("unable to query node info at {}", grpc_addr))?
+        .into_inner())
+}
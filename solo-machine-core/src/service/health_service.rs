@@ -0,0 +1,149 @@
+use anyhow::{ensure, Result};
+use futures::future::join_all;
+use serde::Serialize;
+use tendermint_rpc::Client;
+
+use crate::{
+    model::{chain, Chain, ChainConfig},
+    rpc_client::TimeoutRpcClient,
+    schema_version, DbPool, ToPublicKey,
+};
+
+use super::endpoint_probe::grpc_network;
+
+/// Outcome of a health check
+#[derive(Debug, Serialize)]
+pub struct HealthCheck {
+    /// Name of check (e.g. `storage`, `signer`, `rpc`)
+    pub check: &'static str,
+    /// Chain ID of IBC enabled chain (for per-chain checks)
+    pub chain_id: Option<String>,
+    /// `true` if check passed
+    pub healthy: bool,
+    /// Error returned by checked component (if any)
+    pub error: Option<String>,
+}
+
+impl HealthCheck {
+    fn new(check: &'static str, chain_id: Option<String>, result: Result<()>) -> Self {
+        match result {
+            Ok(()) => Self {
+                check,
+                chain_id,
+                healthy: true,
+                error: None,
+            },
+            Err(err) => Self {
+                check,
+                chain_id,
+                healthy: false,
+                error: Some(format!("{:#}", err)),
+            },
+        }
+    }
+}
+
+/// Health of solo machine (healthy only if all of its checks passed)
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    /// `true` if all the checks passed
+    pub healthy: bool,
+    /// Outcome of all the checks
+    pub checks: Vec<HealthCheck>,
+}
+
+impl From<Vec<HealthCheck>> for HealthReport {
+    fn from(checks: Vec<HealthCheck>) -> Self {
+        Self {
+            healthy: checks.iter().all(|check| check.healthy),
+            checks,
+        }
+    }
+}
+
+/// Used to check liveness and readiness of solo machine (e.g. by orchestrators restarting or draining an instance)
+pub struct HealthService {
+    db_pool: DbPool,
+}
+
+impl HealthService {
+    /// Creates a new instance of health service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Checks liveness of solo machine, i.e., availability of its storage
+    pub async fn liveness(&self) -> HealthReport {
+        vec![self.check_storage().await].into()
+    }
+
+    /// Checks readiness of solo machine to serve requests, i.e., availability of storage and signer and reachability
+    /// of RPC and gRPC endpoints of all the chains (chains are checked concurrently)
+    pub async fn readiness(&self, signer: impl ToPublicKey) -> HealthReport {
+        let mut checks = vec![
+            self.check_storage().await,
+            HealthCheck::new("signer", None, signer.to_public_key().map(|_| ())),
+        ];
+
+        match chain::get_chains(&self.db_pool).await {
+            Ok(chains) => {
+                for chain_checks in join_all(chains.iter().map(check_endpoints)).await {
+                    checks.extend(chain_checks);
+                }
+            }
+            Err(err) => checks.push(HealthCheck::new("chains", None, Err(err))),
+        }
+
+        checks.into()
+    }
+
+    async fn check_storage(&self) -> HealthCheck {
+        HealthCheck::new(
+            "storage",
+            None,
+            schema_version(&self.db_pool).await.map(|_| ()),
+        )
+    }
+}
+
+/// Checks that RPC and gRPC endpoints of chain are reachable and serve the chain
+async fn check_endpoints(chain: &Chain) -> Vec<HealthCheck> {
+    let chain_id = chain.id.to_string();
+
+    let (rpc, grpc) = futures::join!(
+        check_rpc(&chain.config, &chain_id),
+        check_grpc(&chain.config, &chain_id)
+    );
+
+    vec![
+        HealthCheck::new("rpc", Some(chain_id.clone()), rpc),
+        HealthCheck::new("grpc", Some(chain_id), grpc),
+    ]
+}
+
+async fn check_rpc(config: &ChainConfig, chain_id: &str) -> Result<()> {
+    let status = TimeoutRpcClient::with_config(&config.rpc_addr, config)?
+        .status()
+        .await?;
+
+    ensure_network(
+        &config.rpc_addr,
+        status.node_info.network.as_str(),
+        chain_id,
+    )
+}
+
+async fn check_grpc(config: &ChainConfig, chain_id: &str) -> Result<()> {
+    let network = grpc_network(&config.grpc_addr, config).await?;
+    ensure_network(&config.grpc_addr, &network, chain_id)
+}
+
+fn ensure_network(addr: &str, network: &str, chain_id: &str) -> Result<()> {
+    ensure!(
+        network == chain_id,
+        "endpoint at {} serves chain {}",
+        addr,
+        network
+    );
+    Ok(())
+}
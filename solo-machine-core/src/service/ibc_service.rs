@@ -1,57 +1,135 @@
-use std::collections::HashMap;
+use std::{
+    cmp::Ordering,
+    convert::TryFrom,
+    time::{Duration, SystemTime},
+};
 
-use anyhow::{anyhow, ensure, Context, Result};
-use cosmos_sdk_proto::ibc::core::{
-    channel::v1::{
-        Channel, Counterparty as ChannelCounterparty, Order as ChannelOrder, Packet,
-        State as ChannelState,
-    },
-    client::v1::Height,
-    commitment::v1::MerklePrefix,
-    connection::v1::{
-        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
-        Version as ConnectionVersion,
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use chrono::{DateTime, Utc};
+use cosmos_sdk_proto::{
+    cosmos::tx::v1beta1::TxRaw,
+    ibc::{
+        core::{
+            channel::v1::{
+                Channel, Counterparty as ChannelCounterparty, Order as ChannelOrder, Packet,
+                State as ChannelState,
+            },
+            client::v1::Height,
+            commitment::v1::MerklePrefix,
+            connection::v1::{
+                ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+                Version as ConnectionVersion,
+            },
+        },
+        lightclients::tendermint::v1::ConsensusState as TendermintConsensusState,
     },
 };
+use prost::Message;
+use serde_json::Value;
 use sqlx::{Executor, Transaction};
-use tendermint::{
-    abci::{
-        tag::{Key, Tag},
-        Event as AbciEvent,
-    },
-    trust_threshold::TrustThresholdFraction,
-    Hash as TendermintHash,
-};
+use tendermint::{trust_threshold::TrustThresholdFraction, Hash as TendermintHash};
 use tendermint_light_client::{
-    builder::LightClientBuilder, light_client::Options, store::memory::MemoryStore,
-    store::LightStore, supervisor::Instance,
-};
-use tendermint_rpc::{
-    endpoint::broadcast::tx_commit::Response as TxCommitResponse, Client, HttpClient,
+    builder::LightClientBuilder,
+    components::{clock::SystemClock, scheduler, verifier::ProdVerifier},
+    light_client::Options,
+    operations::ProdHasher,
+    predicates::ProdPredicates,
+    store::memory::MemoryStore,
+    store::LightStore,
+    supervisor::Instance,
 };
-use tokio::sync::mpsc::UnboundedSender;
+use tendermint_rpc::{query::Query, Client, Order};
+use tokio::{sync::mpsc::UnboundedSender, time::sleep};
 
 use crate::{
+    broadcaster::{TxEvent, TxEventAttribute},
     cosmos::crypto::PublicKey,
     event::{notify_event, Event},
-    ibc::core::{
-        ics02_client::{client_type::ClientType, height::IHeight},
-        ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, Identifier, PortId},
+    ibc::{
+        client::ics07_tendermint::consensus_state::IConsensusState,
+        core::{
+            ics02_client::{client_type::ClientType, height::IHeight},
+            ics04_channel::packet::IPacket,
+            ics24_host::{
+                identifier::{ChainId, ChannelId, ClientId, ConnectionId, Identifier, PortId},
+                path::{ChannelPath, ConnectionPath, PacketCommitmentPath},
+            },
+        },
     },
+    ibc_version::{detect_ibc_version, SoloMachineRevision},
     model::{
         chain::{self, chain_keys},
         ibc as ibc_handler,
         operation::{self, Operation},
-        Chain, ConnectionDetails as ChainConnectionDetails, OperationType,
+        packet::{self, PacketState, TrackedPacket},
+        pending_operation, Chain, ConnectionDetails as ChainConnectionDetails,
+        GeneratedIdentifiers, HandshakeProgress, HandshakeStep, OperationType, PendingOperation,
+        PendingOperationKind, PendingOperationStatus,
     },
-    proto::proto_encode,
-    transaction_builder, Db, DbPool, Signer, ToPublicKey,
+    plugins::Plugins,
+    proto::json,
+    rpc_client::{LightClientIo, RpcTransport, TimeoutRpcClient},
+    service::{
+        chain_lock::lock_chain,
+        operation_queue::{self, Checkpoint},
+        preflight, spending_limit, ChainService,
+    },
+    signer::{DryRunReport, DryRunSigner, PreviewSigner, SignBytesPreview},
+    transaction_builder,
+    verifier::{ClaimedState, ProofVerifier, VerifiedProof},
+    Db, DbPool, Signer, ToPublicKey,
 };
 
+/// ABCI query path of IBC store of chain
+const IBC_STORE_QUERY_PATH: &str = "store/ibc/key";
+
+/// Interval at which chain is polled while waiting for a connection or channel initialized on chain
+const COUNTERPARTY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Number of times chain is polled while waiting for a connection or channel initialized on chain
+const COUNTERPARTY_WAIT_ATTEMPTS: usize = 60;
+
+/// Number of transactions fetched in a single page while searching packets sent on chain
+const TX_SEARCH_PAGE_SIZE: u8 = 100;
+
+/// Interval at which latest block of a halted chain is polled
+const HALT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Outcome of relaying pending packets of a chain
+#[derive(Debug, Default)]
+pub struct PacketRelayReport {
+    /// Block height of chain up to which all the packets sent on chain are tracked (next search for pending packets
+    /// should start after this height)
+    pub relayed_height: u64,
+    /// Sequences of acknowledged packets
+    pub acknowledged: Vec<u64>,
+    /// Sequences of packets timed out on chain (i.e. their timeout had passed on solo machine)
+    pub timed_out: Vec<u64>,
+}
+
+/// Outcome of relaying a single packet sent to solo machine
+#[derive(Debug)]
+pub enum PacketRelayOutcome {
+    /// Packet is acknowledged in transaction with given hash
+    Acknowledged {
+        /// Hash of transaction acknowledging the packet
+        transaction_hash: String,
+    },
+    /// Packet commitment is no longer present on chain (i.e. packet is already acknowledged or timed out)
+    AlreadyRelayed,
+    /// Timeout of packet has passed on solo machine and packet is timed out in transaction with given hash (sender of
+    /// packet is refunded on chain)
+    TimedOut {
+        /// Hash of transaction timing out the packet
+        transaction_hash: String,
+    },
+}
+
 /// Used to connect, send tokens and receive tokens over IBC
 pub struct IbcService {
     db_pool: DbPool,
     notifier: Option<UnboundedSender<Event>>,
+    plugins: Plugins,
 }
 
 impl IbcService {
@@ -60,6 +138,7 @@ impl IbcService {
         Self {
             db_pool,
             notifier: None,
+            plugins: Default::default(),
         }
     }
 
@@ -68,10 +147,22 @@ impl IbcService {
         Self {
             db_pool,
             notifier: Some(notifier),
+            plugins: Default::default(),
         }
     }
 
-    /// Establishes connection with an IBC enabled chain
+    /// Uses given plugins for chains with `plugin` fee strategy or client encoding
+    pub fn with_plugins(mut self, plugins: Plugins) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
+    /// Establishes connection with an IBC enabled chain by running the full handshake (create clients, open
+    /// connection and open channel). Each step is committed to database as soon as it completes, so, an interrupted
+    /// handshake is resumed from the last completed step when this function is called again (unless `force` is set,
+    /// in which case a new handshake is started). Identifiers assigned by chain during the handshake are recorded for
+    /// the chain even if the handshake fails midway.
+    #[tracing::instrument(name = "connect", skip_all, fields(chain_id = %chain_id, force))]
     pub async fn connect(
         &self,
         signer: impl Signer,
@@ -79,13 +170,92 @@ impl IbcService {
         memo: String,
         force: bool,
     ) -> Result<()> {
-        let mut transaction = self
-            .db_pool
-            .begin()
-            .await
-            .context("unable to begin database transaction")?;
+        self.handshake(signer, chain_id, memo, force, None).await
+    }
 
-        let mut chain = chain::get_chain(&mut transaction, &chain_id)
+    /// Creates solo machine client on IBC enabled chain and tendermint client on solo machine, i.e., runs the first
+    /// steps of connection handshake and stops before opening connection. Does nothing if clients are already created.
+    /// The handshake is completed (without creating new clients) by calling [`Self::connect`].
+    #[tracing::instrument(name = "create_client", skip_all, fields(chain_id = %chain_id))]
+    pub async fn create_client(
+        &self,
+        signer: impl Signer,
+        chain_id: ChainId,
+        memo: String,
+    ) -> Result<()> {
+        let chain = chain::get_chain(&self.db_pool, &chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let clients_created = chain
+            .handshake_progress
+            .is_some_and(|progress| progress.step >= HandshakeStep::ConnectionOpenInit);
+
+        if chain.connection_details.is_some() || clients_created {
+            return Ok(());
+        }
+
+        self.handshake(
+            signer,
+            chain_id,
+            memo,
+            false,
+            Some(HandshakeStep::ConnectionOpenInit),
+        )
+        .await
+    }
+
+    /// Runs connection handshake until given step (or till the end when `until` is not provided)
+    async fn handshake(
+        &self,
+        signer: impl Signer,
+        chain_id: ChainId,
+        memo: String,
+        force: bool,
+        until: Option<HandshakeStep>,
+    ) -> Result<()> {
+        let _chain_lock = lock_chain(&self.db_pool, &chain_id).await?;
+
+        let mut generated_identifiers = GeneratedIdentifiers::default();
+
+        let result = self
+            .establish_connection(
+                signer,
+                &chain_id,
+                memo,
+                force,
+                until,
+                &mut generated_identifiers,
+            )
+            .await;
+
+        match result {
+            Err(err) if !generated_identifiers.is_empty() => {
+                ChainService::new(self.db_pool.clone())
+                    .record_generated_identifiers(&chain_id, &generated_identifiers)
+                    .await?;
+
+                Err(err.context(format!(
+                    "connection handshake failed after chain assigned identifiers ({}), run `connect` again to resume it",
+                    generated_identifiers
+                )))
+            }
+            result => result,
+        }
+    }
+
+    async fn establish_connection(
+        &self,
+        signer: impl Signer,
+        chain_id: &ChainId,
+        memo: String,
+        force: bool,
+        until: Option<HandshakeStep>,
+        generated_identifiers: &mut GeneratedIdentifiers,
+    ) -> Result<()> {
+        self.wait_for_block_production(chain_id).await?;
+
+        let mut chain = chain::get_chain(&self.db_pool, chain_id)
             .await?
             .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
 
@@ -96,181 +266,620 @@ impl IbcService {
             );
         }
 
-        let rpc_client = HttpClient::new(chain.config.rpc_addr.as_str())
-            .context("unable to connect to rpc client")?;
-        let mut instance =
-            prepare_light_client(&chain, rpc_client.clone(), Box::new(MemoryStore::new()))?;
-
-        let solo_machine_client_id =
-            create_solo_machine_client(&signer, &rpc_client, &chain, memo.clone()).await?;
+        // revision of solo machine client types is fixed once solo machine client is created
+        if force || chain.handshake_progress.is_none() {
+            self.record_ibc_version(&mut chain).await?;
+        }
 
-        notify_event(
-            &self.notifier,
-            Event::CreatedSoloMachineClient {
-                client_id: solo_machine_client_id.clone(),
-            },
-        )?;
+        let mut progress = match chain.handshake_progress.take() {
+            Some(progress) if !force => {
+                notify_event(
+                    &self.notifier,
+                    Event::ConnectionHandshakeResumed {
+                        chain_id: chain_id.clone(),
+                        progress: progress.clone(),
+                    },
+                )?;
+
+                progress
+            }
+            _ => HandshakeProgress::default(),
+        };
 
-        let tendermint_client_id =
-            create_tendermint_client(&mut transaction, &mut instance, &chain).await?;
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
 
-        notify_event(
+        operation_queue::resume(
+            &self.db_pool,
             &self.notifier,
-            Event::CreatedTendermintClient {
-                client_id: tendermint_client_id.clone(),
-            },
-        )?;
-
-        let solo_machine_connection_id = connection_open_init(
             &signer,
+            &self.plugins,
             &rpc_client,
             &chain,
-            &solo_machine_client_id,
-            &tendermint_client_id,
-            memo.clone(),
-        )
-        .await?;
-
-        notify_event(
-            &self.notifier,
-            Event::InitializedConnectionOnTendermint {
-                connection_id: solo_machine_connection_id.clone(),
-            },
-        )?;
-
-        let tendermint_connection_id = connection_open_try(
-            &mut transaction,
-            &tendermint_client_id,
-            &solo_machine_client_id,
-            &solo_machine_connection_id,
         )
         .await?;
 
-        notify_event(
-            &self.notifier,
-            Event::InitializedConnectionOnSoloMachine {
-                connection_id: tendermint_connection_id.clone(),
-            },
-        )?;
-
-        connection_open_ack(
-            &mut transaction,
-            &signer,
-            &rpc_client,
-            &mut chain,
-            &solo_machine_connection_id,
-            &tendermint_client_id,
-            &tendermint_connection_id,
-            memo.clone(),
-        )
-        .await?;
+        loop {
+            generated_identifiers.client_id = progress.solo_machine_client_id.clone();
+            generated_identifiers.connection_id = progress.solo_machine_connection_id.clone();
+            generated_identifiers.channel_id = progress.solo_machine_channel_id.clone();
 
-        notify_event(
-            &self.notifier,
-            Event::ConfirmedConnectionOnTendermint {
-                connection_id: solo_machine_connection_id.clone(),
-            },
-        )?;
+            if matches!(until, Some(until) if progress.step >= until) {
+                return Ok(());
+            }
 
-        connection_open_confirm(&mut transaction, &tendermint_connection_id).await?;
+            match progress.step {
+                HandshakeStep::CreateSoloMachineClient => {
+                    let events = match self
+                        .recover_handshake_step(&rpc_client, &mut progress)
+                        .await?
+                    {
+                        Some(events) => events,
+                        None => {
+                            let msg = transaction_builder::msg_create_solo_machine_client(
+                                &signer,
+                                &self.plugins,
+                                &chain,
+                                memo.clone(),
+                            )
+                            .await?;
+
+                            self.submit_handshake_step(
+                                self.begin_handshake_step().await?,
+                                Checkpoint::of(&chain),
+                                &signer,
+                                &rpc_client,
+                                &chain,
+                                &mut progress,
+                                PendingOperationKind::CreateClient,
+                                msg,
+                            )
+                            .await?
+                        }
+                    };
+
+                    let client_id = GeneratedIdentifiers::from_events(&events)?
+                        .client_id
+                        .ok_or_else(|| {
+                            anyhow!(
+                                "client id not found in events of `MsgCreateClient` transaction"
+                            )
+                        })?;
+
+                    progress.solo_machine_client_id = Some(client_id.clone());
+                    progress.advance(HandshakeStep::CreateTendermintClient);
+                    chain::set_handshake_progress(&self.db_pool, chain_id, Some(&progress)).await?;
+
+                    notify_event(
+                        &self.notifier,
+                        Event::CreatedSoloMachineClient { client_id },
+                    )?;
+                }
+                HandshakeStep::CreateTendermintClient => {
+                    rpc_client.throttle().await;
+
+                    let mut instance = prepare_light_client(
+                        &chain,
+                        rpc_client.inner().clone(),
+                        Box::new(MemoryStore::new()),
+                    )?;
+
+                    let mut transaction = self.begin_handshake_step().await?;
+
+                    let (client_id, consensus_timestamp) =
+                        create_tendermint_client(&mut transaction, &mut instance, &chain).await?;
+
+                    progress.tendermint_client_id = Some(client_id.clone());
+                    progress.advance(HandshakeStep::ConnectionOpenInit);
+                    commit_handshake_step(transaction, chain_id, &progress).await?;
+
+                    notify_event(&self.notifier, Event::CreatedTendermintClient { client_id })?;
+
+                    if let Some(consensus_timestamp) = consensus_timestamp {
+                        chain.consensus_timestamp = consensus_timestamp;
+
+                        notify_event(
+                            &self.notifier,
+                            Event::ConsensusTimestampSynced {
+                                chain_id: chain_id.clone(),
+                                consensus_timestamp,
+                            },
+                        )?;
+                    }
+                }
+                HandshakeStep::ConnectionOpenInit => {
+                    let events = match self
+                        .recover_handshake_step(&rpc_client, &mut progress)
+                        .await?
+                    {
+                        Some(events) => events,
+                        None => {
+                            preflight::verify_client(
+                                &chain,
+                                &handshake_id(&progress.solo_machine_client_id)?,
+                            )
+                            .await?;
+
+                            let msg = transaction_builder::msg_connection_open_init(
+                                &signer,
+                                &self.plugins,
+                                &chain,
+                                &handshake_id(&progress.solo_machine_client_id)?,
+                                &handshake_id(&progress.tendermint_client_id)?,
+                                memo.clone(),
+                            )
+                            .await?;
+
+                            self.submit_handshake_step(
+                                self.begin_handshake_step().await?,
+                                Checkpoint::of(&chain),
+                                &signer,
+                                &rpc_client,
+                                &chain,
+                                &mut progress,
+                                PendingOperationKind::ConnectionOpenInit,
+                                msg,
+                            )
+                            .await?
+                        }
+                    };
+
+                    let connection_id = GeneratedIdentifiers::from_events(&events)?
+                        .connection_id
+                        .ok_or_else(|| {
+                            anyhow!(
+                                "connection id not found in events of `MsgConnectionOpenInit` transaction"
+                            )
+                        })?;
+
+                    progress.solo_machine_connection_id = Some(connection_id.clone());
+                    progress.advance(HandshakeStep::ConnectionOpenTry);
+                    chain::set_handshake_progress(&self.db_pool, chain_id, Some(&progress)).await?;
+
+                    notify_event(
+                        &self.notifier,
+                        Event::InitializedConnectionOnTendermint { connection_id },
+                    )?;
+                }
+                HandshakeStep::ConnectionOpenTry => {
+                    let solo_machine_connection_id =
+                        handshake_id(&progress.solo_machine_connection_id)?;
+
+                    wait_for_counterparty(
+                        &rpc_client,
+                        ConnectionPath::new(&solo_machine_connection_id).into_bytes(),
+                    )
+                    .await?;
+
+                    let mut transaction = self.begin_handshake_step().await?;
+
+                    let connection_id = connection_open_try(
+                        &mut transaction,
+                        &handshake_id(&progress.tendermint_client_id)?,
+                        &handshake_id(&progress.solo_machine_client_id)?,
+                        &solo_machine_connection_id,
+                    )
+                    .await?;
+
+                    progress.tendermint_connection_id = Some(connection_id.clone());
+                    progress.advance(HandshakeStep::ConnectionOpenAck);
+                    commit_handshake_step(transaction, chain_id, &progress).await?;
+
+                    notify_event(
+                        &self.notifier,
+                        Event::InitializedConnectionOnSoloMachine { connection_id },
+                    )?;
+                }
+                HandshakeStep::ConnectionOpenAck => {
+                    let solo_machine_connection_id =
+                        handshake_id(&progress.solo_machine_connection_id)?;
+
+                    let confirmed = match self
+                        .recover_handshake_step(&rpc_client, &mut progress)
+                        .await?
+                    {
+                        Some(_) => true,
+                        None if query_connection_state(
+                            &rpc_client,
+                            &solo_machine_connection_id,
+                        )
+                        .await?
+                            != Some(ConnectionState::Open) =>
+                        {
+                            preflight::verify_client(
+                                &chain,
+                                &handshake_id(&progress.solo_machine_client_id)?,
+                            )
+                            .await?;
+                            preflight::verify_connection(
+                                &chain,
+                                &solo_machine_connection_id,
+                                ConnectionState::Init,
+                            )
+                            .await?;
+
+                            let checkpoint = Checkpoint::of(&chain);
+                            let mut transaction = self.begin_handshake_step().await?;
+
+                            // transactions persisted by older versions (before pending operation queue) are queued
+                            // as is
+                            let msg = match progress.pending_tx.take() {
+                                Some(pending_tx) => decode_pending_tx(&pending_tx)?,
+                                None => {
+                                    transaction_builder::msg_connection_open_ack(
+                                        &mut transaction,
+                                        &signer,
+                                        &self.plugins,
+                                        &mut chain,
+                                        &solo_machine_connection_id,
+                                        &handshake_id(&progress.tendermint_client_id)?,
+                                        &handshake_id(&progress.tendermint_connection_id)?,
+                                        memo.clone(),
+                                    )
+                                    .await?
+                                }
+                            };
+
+                            // signed proofs are persisted (along with incremented sequence) before they leave solo
+                            // machine
+                            self.submit_handshake_step(
+                                transaction,
+                                checkpoint,
+                                &signer,
+                                &rpc_client,
+                                &chain,
+                                &mut progress,
+                                PendingOperationKind::ConnectionOpenAck,
+                                msg,
+                            )
+                            .await?;
+
+                            true
+                        }
+                        None => false,
+                    };
+
+                    progress.advance(HandshakeStep::ConnectionOpenConfirm);
+                    chain::set_handshake_progress(&self.db_pool, chain_id, Some(&progress)).await?;
+
+                    if confirmed {
+                        notify_event(
+                            &self.notifier,
+                            Event::ConfirmedConnectionOnTendermint {
+                                connection_id: solo_machine_connection_id,
+                            },
+                        )?;
+                    }
+                }
+                HandshakeStep::ConnectionOpenConfirm => {
+                    let tendermint_connection_id =
+                        handshake_id(&progress.tendermint_connection_id)?;
+
+                    let mut transaction = self.begin_handshake_step().await?;
+
+                    let confirmed =
+                        connection_open_confirm(&mut transaction, &tendermint_connection_id)
+                            .await?;
+
+                    progress.advance(HandshakeStep::ChannelOpenInit);
+                    commit_handshake_step(transaction, chain_id, &progress).await?;
+
+                    if confirmed {
+                        notify_event(
+                            &self.notifier,
+                            Event::ConfirmedConnectionOnSoloMachine {
+                                connection_id: tendermint_connection_id,
+                            },
+                        )?;
+                    }
+                }
+                HandshakeStep::ChannelOpenInit => {
+                    let events = match self
+                        .recover_handshake_step(&rpc_client, &mut progress)
+                        .await?
+                    {
+                        Some(events) => events,
+                        None => {
+                            preflight::verify_connection(
+                                &chain,
+                                &handshake_id(&progress.solo_machine_connection_id)?,
+                                ConnectionState::Open,
+                            )
+                            .await?;
+
+                            let msg = transaction_builder::msg_channel_open_init(
+                                &signer,
+                                &self.plugins,
+                                &chain,
+                                &handshake_id(&progress.solo_machine_connection_id)?,
+                                memo.clone(),
+                            )
+                            .await?;
+
+                            self.submit_handshake_step(
+                                self.begin_handshake_step().await?,
+                                Checkpoint::of(&chain),
+                                &signer,
+                                &rpc_client,
+                                &chain,
+                                &mut progress,
+                                PendingOperationKind::ChannelOpenInit,
+                                msg,
+                            )
+                            .await?
+                        }
+                    };
+
+                    let channel_id = GeneratedIdentifiers::from_events(&events)?
+                        .channel_id
+                        .ok_or_else(|| {
+                            anyhow!("channel id not found in events of `MsgChannelOpenInit` transaction")
+                        })?;
+
+                    progress.solo_machine_channel_id = Some(channel_id.clone());
+                    progress.advance(HandshakeStep::ChannelOpenTry);
+                    chain::set_handshake_progress(&self.db_pool, chain_id, Some(&progress)).await?;
+
+                    notify_event(
+                        &self.notifier,
+                        Event::InitializedChannelOnTendermint { channel_id },
+                    )?;
+                }
+                HandshakeStep::ChannelOpenTry => {
+                    let solo_machine_channel_id = handshake_id(&progress.solo_machine_channel_id)?;
+
+                    wait_for_counterparty(
+                        &rpc_client,
+                        ChannelPath::new(&chain.config.port_id, &solo_machine_channel_id)
+                            .into_bytes(),
+                    )
+                    .await?;
+
+                    let mut transaction = self.begin_handshake_step().await?;
+
+                    let channel_id = channel_open_try(
+                        &mut transaction,
+                        &chain.config.port_id,
+                        &solo_machine_channel_id,
+                        &handshake_id(&progress.tendermint_connection_id)?,
+                    )
+                    .await?;
+
+                    progress.tendermint_channel_id = Some(channel_id.clone());
+                    progress.advance(HandshakeStep::ChannelOpenAck);
+                    commit_handshake_step(transaction, chain_id, &progress).await?;
+
+                    notify_event(
+                        &self.notifier,
+                        Event::InitializedChannelOnSoloMachine { channel_id },
+                    )?;
+                }
+                HandshakeStep::ChannelOpenAck => {
+                    let solo_machine_channel_id = handshake_id(&progress.solo_machine_channel_id)?;
+
+                    let confirmed = match self
+                        .recover_handshake_step(&rpc_client, &mut progress)
+                        .await?
+                    {
+                        Some(_) => true,
+                        None if query_channel_state(
+                            &rpc_client,
+                            &chain.config.port_id,
+                            &solo_machine_channel_id,
+                        )
+                        .await?
+                            != Some(ChannelState::Open) =>
+                        {
+                            preflight::verify_client(
+                                &chain,
+                                &handshake_id(&progress.solo_machine_client_id)?,
+                            )
+                            .await?;
+                            preflight::verify_channel(
+                                &chain,
+                                &solo_machine_channel_id,
+                                ChannelState::Init,
+                            )
+                            .await?;
+
+                            let checkpoint = Checkpoint::of(&chain);
+                            let mut transaction = self.begin_handshake_step().await?;
+
+                            // transactions persisted by older versions (before pending operation queue) are queued
+                            // as is
+                            let msg = match progress.pending_tx.take() {
+                                Some(pending_tx) => decode_pending_tx(&pending_tx)?,
+                                None => {
+                                    transaction_builder::msg_channel_open_ack(
+                                        &mut transaction,
+                                        &signer,
+                                        &self.plugins,
+                                        &mut chain,
+                                        &solo_machine_channel_id,
+                                        &handshake_id(&progress.tendermint_channel_id)?,
+                                        memo.clone(),
+                                    )
+                                    .await?
+                                }
+                            };
+
+                            // signed proofs are persisted (along with incremented sequence) before they leave solo
+                            // machine
+                            self.submit_handshake_step(
+                                transaction,
+                                checkpoint,
+                                &signer,
+                                &rpc_client,
+                                &chain,
+                                &mut progress,
+                                PendingOperationKind::ChannelOpenAck,
+                                msg,
+                            )
+                            .await?;
+
+                            true
+                        }
+                        None => false,
+                    };
+
+                    progress.advance(HandshakeStep::ChannelOpenConfirm);
+                    chain::set_handshake_progress(&self.db_pool, chain_id, Some(&progress)).await?;
+
+                    if confirmed {
+                        notify_event(
+                            &self.notifier,
+                            Event::ConfirmedChannelOnTendermint {
+                                channel_id: solo_machine_channel_id,
+                            },
+                        )?;
+                    }
+                }
+                HandshakeStep::ChannelOpenConfirm => {
+                    let connection_details = ChainConnectionDetails {
+                        solo_machine_client_id: handshake_id(&progress.solo_machine_client_id)?,
+                        tendermint_client_id: handshake_id(&progress.tendermint_client_id)?,
+                        solo_machine_connection_id: handshake_id(
+                            &progress.solo_machine_connection_id,
+                        )?,
+                        tendermint_connection_id: handshake_id(&progress.tendermint_connection_id)?,
+                        solo_machine_channel_id: handshake_id(&progress.solo_machine_channel_id)?,
+                        tendermint_channel_id: handshake_id(&progress.tendermint_channel_id)?,
+                    };
+
+                    let mut transaction = self.begin_handshake_step().await?;
+
+                    let confirmed = channel_open_confirm(
+                        &mut transaction,
+                        &chain.config.port_id,
+                        &connection_details.tendermint_channel_id,
+                    )
+                    .await?;
+
+                    chain::add_connection_details(&mut transaction, &chain.id, &connection_details)
+                        .await?;
+                    chain::add_generated_identifiers(
+                        &mut transaction,
+                        &chain.id,
+                        generated_identifiers,
+                    )
+                    .await?;
+                    chain::set_handshake_progress(&mut transaction, &chain.id, None).await?;
+
+                    transaction
+                        .commit()
+                        .await
+                        .context("unable to commit transaction for creating ibc connection")?;
+
+                    if confirmed {
+                        notify_event(
+                            &self.notifier,
+                            Event::ConfirmedChannelOnSoloMachine {
+                                channel_id: connection_details.tendermint_channel_id.clone(),
+                            },
+                        )?;
+                    }
+
+                    return notify_event(
+                        &self.notifier,
+                        Event::ConnectionEstablished {
+                            chain_id: chain_id.clone(),
+                            connection_details,
+                        },
+                    );
+                }
+            }
+        }
+    }
 
-        notify_event(
-            &self.notifier,
-            Event::ConfirmedConnectionOnSoloMachine {
-                connection_id: tendermint_connection_id.clone(),
-            },
-        )?;
+    /// Returns events of the transaction submitted for current handshake step before the handshake was interrupted
+    /// (`None` if no transaction was submitted for current step or it failed, i.e. the step has to be executed again)
+    #[tracing::instrument(skip_all, fields(step = ?progress.step))]
+    async fn recover_handshake_step<C>(
+        &self,
+        rpc_client: &C,
+        progress: &mut HandshakeProgress,
+    ) -> Result<Option<Vec<TxEvent>>>
+    where
+        C: Client + Send + Sync,
+    {
+        let operation_id = match progress.pending_operation_id.take() {
+            Some(operation_id) => operation_id,
+            None => return Ok(None),
+        };
 
-        let solo_machine_channel_id = channel_open_init(
-            &signer,
-            &rpc_client,
-            &chain,
-            &solo_machine_connection_id,
-            memo.clone(),
-        )
-        .await?;
+        let operation = pending_operation::get_pending_operation(&self.db_pool, operation_id)
+            .await?
+            .ok_or_else(|| {
+                anyhow!(
+                    "operation {} of handshake step {} not found",
+                    operation_id,
+                    progress.step
+                )
+            })?;
+
+        match operation.status {
+            PendingOperationStatus::Completed => operation_queue::events_of(rpc_client, &operation)
+                .await
+                .map(Some),
+            PendingOperationStatus::Failed | PendingOperationStatus::Reverted => Ok(None),
+            PendingOperationStatus::Pending => Err(anyhow!(
+                "operation {} of handshake step {} is still pending",
+                operation_id,
+                progress.step
+            )),
+        }
+    }
 
-        notify_event(
-            &self.notifier,
-            Event::InitializedChannelOnTendermint {
-                channel_id: solo_machine_channel_id.clone(),
-            },
-        )?;
+    /// Queues the transaction of a handshake step (recording its operation in handshake progress within given
+    /// database transaction, which contains the state changes made while signing it) and submits it. Returns events
+    /// emitted by the transaction.
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(skip_all, fields(step = ?progress.step, kind = ?kind))]
+    async fn submit_handshake_step<C>(
+        &self,
+        mut transaction: Transaction<'_, Db>,
+        checkpoint: Checkpoint,
+        signer: impl Signer,
+        rpc_client: &C,
+        chain: &Chain,
+        progress: &mut HandshakeProgress,
+        kind: PendingOperationKind,
+        msg: TxRaw,
+    ) -> Result<Vec<TxEvent>>
+    where
+        C: Client + Send + Sync,
+    {
+        let operation =
+            operation_queue::push(&mut transaction, &chain.id, checkpoint, kind, None, &msg)
+                .await?;
 
-        let tendermint_channel_id = channel_open_try(
-            &mut transaction,
-            &chain.config.port_id,
-            &solo_machine_channel_id,
-            &tendermint_connection_id,
-        )
-        .await?;
+        progress.pending_operation_id = Some(operation.id);
+        commit_handshake_step(transaction, &chain.id, progress).await?;
 
-        notify_event(
+        let response = operation_queue::submit(
+            &self.db_pool,
             &self.notifier,
-            Event::InitializedChannelOnSoloMachine {
-                channel_id: tendermint_channel_id.clone(),
-            },
-        )?;
-
-        channel_open_ack(
-            &mut transaction,
             signer,
-            &rpc_client,
-            &mut chain,
-            &solo_machine_channel_id,
-            &tendermint_channel_id,
-            memo,
-        )
-        .await?;
-
-        notify_event(
-            &self.notifier,
-            Event::ConfirmedChannelOnTendermint {
-                channel_id: solo_machine_channel_id.clone(),
-            },
-        )?;
-
-        channel_open_confirm(
-            &mut transaction,
-            &chain.config.port_id,
-            &tendermint_channel_id,
+            &self.plugins,
+            rpc_client,
+            chain,
+            &operation,
         )
         .await?;
+        response.ensure_success()?;
 
-        notify_event(
-            &self.notifier,
-            Event::ConfirmedChannelOnSoloMachine {
-                channel_id: tendermint_channel_id.clone(),
-            },
-        )?;
-
-        let connection_details = ChainConnectionDetails {
-            solo_machine_client_id,
-            tendermint_client_id,
-            solo_machine_connection_id,
-            tendermint_connection_id,
-            solo_machine_channel_id,
-            tendermint_channel_id,
-        };
-
-        chain::add_connection_details(&mut transaction, &chain.id, &connection_details).await?;
-
-        notify_event(
-            &self.notifier,
-            Event::ConnectionEstablished {
-                chain_id,
-                connection_details,
-            },
-        )?;
+        Ok(response.events().to_vec())
+    }
 
-        transaction
-            .commit()
+    async fn begin_handshake_step(&self) -> Result<Transaction<'static, Db>> {
+        self.db_pool
+            .begin()
             .await
-            .context("unable to commit transaction for creating ibc connection")
+            .context("unable to begin database transaction")
     }
 
     /// Mint some tokens on IBC enabled chain
     #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(name = "mint", skip_all, fields(chain_id = %chain_id, amount, denom = %denom))]
     pub async fn mint(
         &self,
         signer: impl Signer,
@@ -281,15 +890,63 @@ impl IbcService {
         receiver: Option<String>,
         memo: String,
     ) -> Result<String> {
+        let _chain_lock = lock_chain(&self.db_pool, &chain_id).await?;
+
+        self.wait_for_block_production(&chain_id).await?;
+
         let mut chain = chain::get_chain(&self.db_pool, &chain_id)
             .await?
             .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
 
-        let address = signer.to_account_address()?;
-        let receiver = receiver.unwrap_or_else(|| address.clone());
+        let address = chain.account_address(&signer)?;
+        let receiver = match receiver {
+            Some(receiver) => {
+                chain
+                    .resolve_account_address(&receiver, signer.get_account_prefix())
+                    .await?
+            }
+            None => address,
+        };
+
+        // proofs of solo machine are signed before transaction, so, transfer is checked before signing anything
+        let spending = match signer.signing_policy() {
+            Some(policy) => {
+                policy.check_transfer(
+                    &chain_id.to_string(),
+                    &denom.to_string(),
+                    amount.into(),
+                    Some(receiver.as_str()),
+                )?;
+
+                spending_limit::check_spending(
+                    &self.db_pool,
+                    &self.notifier,
+                    policy,
+                    &chain_id,
+                    &denom.to_string(),
+                    amount,
+                    request_id.as_deref(),
+                )
+                .await?
+            }
+            None => None,
+        };
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
 
-        let rpc_client = HttpClient::new(chain.config.rpc_addr.as_str())
-            .context("unable to connect to rpc client")?;
+        operation_queue::resume(
+            &self.db_pool,
+            &self.notifier,
+            &signer,
+            &self.plugins,
+            &rpc_client,
+            &chain,
+        )
+        .await?;
+
+        preflight::verify_packet_route(&chain).await?;
+
+        let checkpoint = Checkpoint::of(&chain);
 
         let mut transaction = self
             .db_pool
@@ -299,7 +956,8 @@ impl IbcService {
 
         let msg = transaction_builder::msg_token_send(
             &mut transaction,
-            signer,
+            &signer,
+            &self.plugins,
             &rpc_client,
             &mut chain,
             amount,
@@ -310,23 +968,31 @@ impl IbcService {
         )
         .await?;
 
-        let response = rpc_client
-            .broadcast_tx_commit(proto_encode(&msg)?.into())
-            .await?;
+        let operation = operation_queue::enqueue(
+            transaction,
+            &chain_id,
+            checkpoint,
+            PendingOperationKind::Mint,
+            request_id.as_deref(),
+            &msg,
+        )
+        .await?;
 
-        let transaction_hash = ensure_response_success(&response)?;
+        let response = operation_queue::submit(
+            &self.db_pool,
+            &self.notifier,
+            &signer,
+            &self.plugins,
+            &rpc_client,
+            &chain,
+            &operation,
+        )
+        .await?;
 
-        transaction
-            .commit()
-            .await
-            .context("unable to commit transaction for sending tokens over IBC")?;
+        let transaction_hash = response.ensure_success()?;
 
-        let success: bool = extract_attribute(
-            &response.deliver_tx.events,
-            "fungible_token_packet",
-            "success",
-        )?
-        .parse()?;
+        let success: bool =
+            extract_attribute(response.events(), "fungible_token_packet", "success")?.parse()?;
 
         if success {
             operation::add_operation(
@@ -342,6 +1008,10 @@ impl IbcService {
             )
             .await?;
 
+            if let Some(spending) = spending {
+                spending.record(&self.db_pool).await?;
+            }
+
             notify_event(
                 &self.notifier,
                 Event::TokensMinted {
@@ -356,11 +1026,8 @@ impl IbcService {
 
             Ok(transaction_hash)
         } else {
-            let error = extract_attribute(
-                &response.deliver_tx.events,
-                "write_acknowledgement",
-                "packet_ack",
-            )?;
+            let error =
+                extract_attribute(response.events(), "write_acknowledgement", "packet_ack")?;
 
             Err(anyhow!(
                 "Failed to mint tokens on IBC enabled chain: {}",
@@ -371,6 +1038,7 @@ impl IbcService {
 
     /// Burn some tokens on IBC enabled chain
     #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(name = "burn", skip_all, fields(chain_id = %chain_id, amount, denom = %denom))]
     pub async fn burn(
         &self,
         signer: impl Signer,
@@ -380,12 +1048,51 @@ impl IbcService {
         denom: Identifier,
         memo: String,
     ) -> Result<String> {
+        // denom of burnt tokens on chain is an IBC denom, so, transfer is checked against denom on solo machine
+        if let Some(policy) = signer.signing_policy() {
+            policy.check_transfer(
+                &chain_id.to_string(),
+                &denom.to_string(),
+                amount.into(),
+                None,
+            )?;
+        }
+
+        let _chain_lock = lock_chain(&self.db_pool, &chain_id).await?;
+
+        self.wait_for_block_production(&chain_id).await?;
+
         let mut chain = chain::get_chain(&self.db_pool, &chain_id)
             .await?
             .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
 
-        let rpc_client = HttpClient::new(chain.config.rpc_addr.as_str())
-            .context("unable to connect to rpc client")?;
+        let spending = match signer.signing_policy() {
+            Some(policy) => {
+                spending_limit::check_spending(
+                    &self.db_pool,
+                    &self.notifier,
+                    policy,
+                    &chain_id,
+                    &denom.to_string(),
+                    amount,
+                    request_id.as_deref(),
+                )
+                .await?
+            }
+            None => None,
+        };
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        operation_queue::resume(
+            &self.db_pool,
+            &self.notifier,
+            &signer,
+            &self.plugins,
+            &rpc_client,
+            &chain,
+        )
+        .await?;
 
         // TODO: Remove this code once fully tested that updating solo machine client is not needed
         //
@@ -415,10 +1122,22 @@ impl IbcService {
         //     .await
         //     .context("unable to commit transaction for receiving tokens over IBC")?;
 
-        let address = signer.to_account_address()?;
+        if let Some(ref connection_details) = chain.connection_details {
+            preflight::verify_channel(
+                &chain,
+                &connection_details.solo_machine_channel_id,
+                ChannelState::Open,
+            )
+            .await?;
+        }
+
+        let address = chain.account_address(&signer)?;
+
+        let checkpoint = Checkpoint::of(&chain);
 
         let msg = transaction_builder::msg_token_receive(
             &signer,
+            &self.plugins,
             &chain,
             amount,
             &denom,
@@ -428,17 +1147,40 @@ impl IbcService {
         )
         .await?;
 
-        let response = rpc_client
-            .broadcast_tx_commit(proto_encode(&msg)?.into())
-            .await?;
+        let transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
 
-        let transaction_hash = ensure_response_success(&response)?;
+        let operation = operation_queue::enqueue(
+            transaction,
+            &chain_id,
+            checkpoint,
+            PendingOperationKind::Burn,
+            request_id.as_deref(),
+            &msg,
+        )
+        .await?;
 
-        operation::add_operation(
+        let response = operation_queue::submit(
             &self.db_pool,
-            request_id.as_deref(),
-            &address,
-            &denom,
+            &self.notifier,
+            &signer,
+            &self.plugins,
+            &rpc_client,
+            &chain,
+            &operation,
+        )
+        .await?;
+
+        let transaction_hash = response.ensure_success()?;
+
+        operation::add_operation(
+            &self.db_pool,
+            request_id.as_deref(),
+            &address,
+            &denom,
             amount,
             &OperationType::Burn {
                 chain_id: chain_id.clone(),
@@ -447,6 +1189,10 @@ impl IbcService {
         )
         .await?;
 
+        if let Some(spending) = spending {
+            spending.record(&self.db_pool).await?;
+        }
+
         notify_event(
             &self.notifier,
             Event::TokensBurnt {
@@ -464,7 +1210,7 @@ impl IbcService {
                 signer,
                 &rpc_client,
                 &mut chain,
-                extract_packets(&response)?,
+                extract_packets(response.events())?,
                 memo,
                 request_id,
             )
@@ -480,71 +1226,1217 @@ impl IbcService {
             )?;
         }
 
-        Ok(transaction_hash)
+        Ok(transaction_hash)
+    }
+
+    /// Updates signer for future IBC transactions
+    #[tracing::instrument(name = "update_signer", skip_all, fields(chain_id = %chain_id))]
+    pub async fn update_signer(
+        &self,
+        signer: impl Signer,
+        chain_id: ChainId,
+        new_public_key: PublicKey,
+        memo: String,
+    ) -> Result<()> {
+        let _chain_lock = lock_chain(&self.db_pool, &chain_id).await?;
+
+        self.wait_for_block_production(&chain_id).await?;
+
+        let chain = chain::get_chain(&self.db_pool, &chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        operation_queue::resume(
+            &self.db_pool,
+            &self.notifier,
+            &signer,
+            &self.plugins,
+            &rpc_client,
+            &chain,
+        )
+        .await?;
+
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let mut chain = chain::get_chain(&mut transaction, &chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+        let checkpoint = Checkpoint::of(&chain);
+
+        if let Some(ref connection_details) = chain.connection_details {
+            preflight::verify_client(&chain, &connection_details.solo_machine_client_id).await?;
+        }
+
+        chain_keys::add_chain_key(&mut transaction, &chain_id, &new_public_key.encode()).await?;
+
+        let msg = transaction_builder::msg_update_solo_machine_client(
+            &mut transaction,
+            &signer,
+            &self.plugins,
+            &mut chain,
+            Some(&new_public_key),
+            memo.clone(),
+        )
+        .await?;
+
+        let operation = operation_queue::enqueue(
+            transaction,
+            &chain_id,
+            checkpoint,
+            PendingOperationKind::UpdateSigner,
+            None,
+            &msg,
+        )
+        .await?;
+
+        operation_queue::submit(
+            &self.db_pool,
+            &self.notifier,
+            &signer,
+            &self.plugins,
+            &rpc_client,
+            &chain,
+            &operation,
+        )
+        .await?
+        .ensure_success()?;
+
+        notify_event(
+            &self.notifier,
+            Event::SignerUpdated {
+                chain_id,
+                old_public_key: signer.to_public_key()?,
+                new_public_key,
+            },
+        )
+    }
+
+    /// Previews the `SignBytes` which will be signed next when minting tokens on an IBC enabled chain. The signer
+    /// backend is never invoked and nothing is persisted or broadcasted. Note that packet commitment depends on the
+    /// latest height of chain at the time of preview.
+    pub async fn preview_mint(
+        &self,
+        signer: impl ToPublicKey + Send + Sync,
+        chain_id: ChainId,
+        amount: u64,
+        denom: Identifier,
+        receiver: Option<String>,
+    ) -> Result<SignBytesPreview> {
+        let mut chain = chain::get_chain(&self.db_pool, &chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let receiver = match receiver {
+            Some(receiver) => {
+                chain
+                    .resolve_account_address(&receiver, signer.get_account_prefix())
+                    .await?
+            }
+            None => chain.account_address(&signer)?,
+        };
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        let signer = PreviewSigner::new(signer);
+
+        // database transaction is never committed
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let result = transaction_builder::msg_token_send(
+            &mut transaction,
+            &signer,
+            &self.plugins,
+            &rpc_client,
+            &mut chain,
+            amount,
+            &denom,
+            receiver,
+            "".to_owned(),
+            None,
+        )
+        .await;
+
+        preview_sign_bytes(chain.config.solo_machine_revision(), signer, result)
+    }
+
+    /// Previews the `SignBytes` which will be signed next when updating signer's public key on an IBC enabled chain.
+    /// The signer backend is never invoked and nothing is persisted or broadcasted.
+    pub async fn preview_update_signer(
+        &self,
+        signer: impl ToPublicKey + Send + Sync,
+        chain_id: ChainId,
+        new_public_key: PublicKey,
+    ) -> Result<SignBytesPreview> {
+        let mut chain = chain::get_chain(&self.db_pool, &chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let signer = PreviewSigner::new(signer);
+
+        // database transaction is never committed
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let result = transaction_builder::msg_update_solo_machine_client(
+            &mut transaction,
+            &signer,
+            &self.plugins,
+            &mut chain,
+            Some(&new_public_key),
+            "".to_owned(),
+        )
+        .await;
+
+        preview_sign_bytes(chain.config.solo_machine_revision(), signer, result)
+    }
+
+    /// Builds the transaction for minting tokens on an IBC enabled chain in dry-run mode, i.e., without signing,
+    /// persisting (e.g. sequence of solo machine) or broadcasting anything. Proofs in returned messages contain
+    /// placeholder signatures.
+    pub async fn dry_run_mint(
+        &self,
+        signer: impl Signer,
+        chain_id: ChainId,
+        amount: u64,
+        denom: Identifier,
+        receiver: Option<String>,
+        memo: String,
+    ) -> Result<DryRunReport> {
+        let mut chain = chain::get_chain(&self.db_pool, &chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let receiver = match receiver {
+            Some(receiver) => {
+                chain
+                    .resolve_account_address(&receiver, signer.get_account_prefix())
+                    .await?
+            }
+            None => chain.account_address(&signer)?,
+        };
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        let signer = DryRunSigner::new(signer)?;
+
+        // database transaction is never committed
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let tx_raw = transaction_builder::msg_token_send(
+            &mut transaction,
+            &signer,
+            &self.plugins,
+            &rpc_client,
+            &mut chain,
+            amount,
+            &denom,
+            receiver,
+            memo,
+            None,
+        )
+        .await?;
+
+        DryRunReport::new(&chain_id, &tx_raw, signer.messages())
+    }
+
+    /// Builds the transaction for burning tokens on an IBC enabled chain in dry-run mode, i.e., without signing,
+    /// persisting or broadcasting anything
+    pub async fn dry_run_burn(
+        &self,
+        signer: impl Signer,
+        chain_id: ChainId,
+        amount: u64,
+        denom: Identifier,
+        memo: String,
+    ) -> Result<DryRunReport> {
+        let chain = chain::get_chain(&self.db_pool, &chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let address = chain.account_address(&signer)?;
+        let signer = DryRunSigner::new(signer)?;
+
+        let tx_raw = transaction_builder::msg_token_receive(
+            &signer,
+            &self.plugins,
+            &chain,
+            amount,
+            &denom,
+            address,
+            memo,
+            None,
+        )
+        .await?;
+
+        DryRunReport::new(&chain_id, &tx_raw, signer.messages())
+    }
+
+    /// Builds the transaction for burning tokens on an IBC enabled chain and returns it in the JSON layout of cosmos
+    /// SDK CLIs (to be signed using `tx sign` when unsigned, and broadcasted using `tx broadcast`). Transaction is
+    /// never broadcasted, so, tokens burnt by an exported transaction are not recorded in history (signed exports still
+    /// count towards spending limits).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn export_burn(
+        &self,
+        signer: impl Signer,
+        chain_id: ChainId,
+        amount: u64,
+        denom: Identifier,
+        memo: String,
+        request_id: Option<String>,
+        signed: bool,
+    ) -> Result<Value> {
+        let chain = chain::get_chain(&self.db_pool, &chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let address = chain.account_address(&signer)?;
+
+        let tx_raw = if signed {
+            let spending = match signer.signing_policy() {
+                Some(policy) => {
+                    policy.check_transfer(
+                        &chain_id.to_string(),
+                        &denom.to_string(),
+                        amount.into(),
+                        None,
+                    )?;
+
+                    spending_limit::check_spending(
+                        &self.db_pool,
+                        &self.notifier,
+                        policy,
+                        &chain_id,
+                        &denom.to_string(),
+                        amount,
+                        request_id.as_deref(),
+                    )
+                    .await?
+                }
+                None => None,
+            };
+
+            let tx_raw = transaction_builder::msg_token_receive(
+                &signer,
+                &self.plugins,
+                &chain,
+                amount,
+                &denom,
+                address,
+                memo,
+                request_id.as_deref(),
+            )
+            .await?;
+
+            if let Some(spending) = spending {
+                spending.record(&self.db_pool).await?;
+            }
+
+            tx_raw
+        } else {
+            transaction_builder::msg_token_receive(
+                DryRunSigner::new(signer)?,
+                &self.plugins,
+                &chain,
+                amount,
+                &denom,
+                address,
+                memo,
+                request_id.as_deref(),
+            )
+            .await?
+        };
+
+        json::tx_raw_to_cosmos_json(&tx_raw, signed)
+    }
+
+    /// Builds the transaction for updating signer's public key on an IBC enabled chain in dry-run mode, i.e., without
+    /// signing, persisting (e.g. sequence of solo machine) or broadcasting anything
+    pub async fn dry_run_update_signer(
+        &self,
+        signer: impl Signer,
+        chain_id: ChainId,
+        new_public_key: PublicKey,
+        memo: String,
+    ) -> Result<DryRunReport> {
+        let mut chain = chain::get_chain(&self.db_pool, &chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let signer = DryRunSigner::new(signer)?;
+
+        // database transaction is never committed
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let tx_raw = transaction_builder::msg_update_solo_machine_client(
+            &mut transaction,
+            &signer,
+            &self.plugins,
+            &mut chain,
+            Some(&new_public_key),
+            memo,
+        )
+        .await?;
+
+        DryRunReport::new(&chain_id, &tx_raw, signer.messages())
+    }
+
+    /// Builds the next transaction which [`Self::connect`] would submit to an IBC enabled chain in dry-run mode, i.e.,
+    /// without signing, persisting or broadcasting anything (`MsgCreateClient` for a new handshake). Steps run on solo
+    /// machine (and the transactions following them) depend on state of chain after the previous steps, so, they
+    /// cannot be built in dry-run mode.
+    pub async fn dry_run_connect(
+        &self,
+        signer: impl Signer,
+        chain_id: ChainId,
+        memo: String,
+        force: bool,
+    ) -> Result<DryRunReport> {
+        let mut chain = chain::get_chain(&self.db_pool, &chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        if !force {
+            ensure!(
+                chain.connection_details.is_none(),
+                "connection is already established with given chain"
+            );
+        }
+
+        let progress = match chain.handshake_progress.take() {
+            Some(progress) if !force => progress,
+            _ => HandshakeProgress::default(),
+        };
+
+        ensure!(
+            progress.pending_operation_id.is_none() && progress.pending_tx.is_none(),
+            "transaction of handshake step {} is already submitted, run `connect` to resume the handshake",
+            progress.step
+        );
+
+        let signer = DryRunSigner::new(signer)?;
+
+        // database transaction is never committed
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let tx_raw = match progress.step {
+            HandshakeStep::CreateSoloMachineClient => {
+                // IBC version (which decides solo machine client types) is detected without recording it
+                if chain.config.ibc_version.is_none() {
+                    chain.config.ibc_version = detect_ibc_version(&chain.config).await.ok().flatten();
+                }
+
+                transaction_builder::msg_create_solo_machine_client(&signer, &self.plugins, &chain, memo).await?
+            }
+            HandshakeStep::ConnectionOpenInit => {
+                transaction_builder::msg_connection_open_init(
+                    &signer, &self.plugins,
+                    &chain,
+                    &handshake_id(&progress.solo_machine_client_id)?,
+                    &handshake_id(&progress.tendermint_client_id)?,
+                    memo,
+                )
+                .await?
+            }
+            HandshakeStep::ConnectionOpenAck => {
+                transaction_builder::msg_connection_open_ack(
+                    &mut transaction,
+                    &signer, &self.plugins,
+                    &mut chain,
+                    &handshake_id(&progress.solo_machine_connection_id)?,
+                    &handshake_id(&progress.tendermint_client_id)?,
+                    &handshake_id(&progress.tendermint_connection_id)?,
+                    memo,
+                )
+                .await?
+            }
+            HandshakeStep::ChannelOpenInit => {
+                transaction_builder::msg_channel_open_init(
+                    &signer, &self.plugins,
+                    &chain,
+                    &handshake_id(&progress.solo_machine_connection_id)?,
+                    memo,
+                )
+                .await?
+            }
+            HandshakeStep::ChannelOpenAck => {
+                transaction_builder::msg_channel_open_ack(
+                    &mut transaction,
+                    &signer, &self.plugins,
+                    &mut chain,
+                    &handshake_id(&progress.solo_machine_channel_id)?,
+                    &handshake_id(&progress.tendermint_channel_id)?,
+                    memo,
+                )
+                .await?
+            }
+            step => bail!(
+                "next step of handshake ({}) runs on solo machine and cannot be built in dry-run mode, run `connect` to \
+                continue the handshake",
+                step
+            ),
+        };
+
+        DryRunReport::new(&chain_id, &tx_raw, signer.messages())
+    }
+
+    /// Builds the transaction timing out a packet sent to solo machine (whose timeout has passed on solo machine) in
+    /// dry-run mode, i.e., without signing, persisting or broadcasting anything
+    pub async fn dry_run_timeout(
+        &self,
+        signer: impl Signer,
+        chain_id: &ChainId,
+        packet: Packet,
+        memo: String,
+    ) -> Result<DryRunReport> {
+        let mut chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        ensure!(
+            is_packet_expired(&chain, &packet),
+            "timeout of packet {} has not passed on solo machine",
+            packet.sequence
+        );
+
+        let signer = DryRunSigner::new(signer)?;
+
+        // database transaction is never committed
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let tx_raw = transaction_builder::msg_timeout(
+            &mut *transaction,
+            &signer,
+            &self.plugins,
+            &mut chain,
+            packet,
+            memo,
+            None,
+        )
+        .await?;
+
+        DryRunReport::new(chain_id, &tx_raw, signer.messages())
+    }
+
+    /// Verifies a solo machine proof (`TimestampedSignatureData`) over claimed state against signer's public key and
+    /// diversifier of given chain. Only proofs for sequences which are already signed are accepted.
+    pub async fn verify_proof(
+        &self,
+        signer: impl ToPublicKey,
+        chain_id: &ChainId,
+        sequence: u64,
+        proof: &[u8],
+        claimed_state: &ClaimedState,
+    ) -> Result<VerifiedProof> {
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        ensure!(
+            sequence < u64::from(chain.sequence),
+            "sequence {} is not signed yet on {} (current sequence: {})",
+            sequence,
+            chain_id,
+            chain.sequence
+        );
+
+        let revision = chain.config.solo_machine_revision();
+
+        ProofVerifier::new(signer.to_public_key()?, chain.config.diversifier)
+            .with_revision(revision)
+            .verify(sequence, proof, claimed_state)
+    }
+
+    /// Refreshes tendermint client of given chain on solo machine when its latest consensus state is older than
+    /// `max_age`. Latest header of chain is verified using light client and its consensus state is stored. Trusted
+    /// height of chain is also moved to verified header so that light client verification stays within trusting
+    /// period. Returns new latest height of tendermint client (`None` if client is still fresh).
+    pub async fn refresh_tendermint_client(
+        &self,
+        chain_id: &ChainId,
+        max_age: Duration,
+    ) -> Result<Option<Height>> {
+        let _chain_lock = lock_chain(&self.db_pool, chain_id).await?;
+
+        let mut chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let client_id = chain
+            .connection_details
+            .as_ref()
+            .map(|connection_details| connection_details.tendermint_client_id.clone())
+            .ok_or_else(|| anyhow!("connection is not established with chain {}", chain_id))?;
+
+        let mut client_state = ibc_handler::get_tendermint_client_state(&self.db_pool, &client_id)
+            .await?
+            .ok_or_else(|| anyhow!("client state for {} not found", client_id))?;
+
+        let latest_height = client_state
+            .latest_height
+            .clone()
+            .ok_or_else(|| anyhow!("latest height cannot be absent in client state"))?;
+
+        let consensus_state =
+            ibc_handler::get_tendermint_consensus_state(&self.db_pool, &client_id, &latest_height)
+                .await?
+                .ok_or_else(|| {
+                    anyhow!(
+                        "consensus state for {} at height {} not found",
+                        client_id,
+                        latest_height.to_string()
+                    )
+                })?;
+
+        let consensus_time: DateTime<Utc> = SystemTime::from(
+            consensus_state
+                .timestamp
+                .ok_or_else(|| anyhow!("timestamp cannot be absent in consensus state"))?,
+        )
+        .into();
+
+        if (Utc::now() - consensus_time).to_std().unwrap_or_default() < max_age {
+            return Ok(None);
+        }
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+        rpc_client.throttle().await;
+
+        let mut instance = prepare_light_client(
+            &chain,
+            rpc_client.inner().clone(),
+            Box::new(MemoryStore::new()),
+        )?;
+
+        let header = transaction_builder::get_latest_header(&chain, &mut instance).await?;
+        let height = transaction_builder::get_block_height(&chain, &header);
+
+        if height.cmp(&latest_height) != Ordering::Greater {
+            return Ok(None);
+        }
+
+        chain.config.trusted_height = header.height;
+        chain.config.trusted_hash = match header.hash() {
+            TendermintHash::Sha256(hash) => hash,
+            TendermintHash::None => bail!("hash of verified header of {} is empty", chain_id),
+        };
+        client_state.latest_height = Some(height.clone());
+
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let block_time: DateTime<Utc> = SystemTime::from(header.time).into();
+
+        ibc_handler::add_tendermint_consensus_state(
+            &mut transaction,
+            &client_id,
+            &height,
+            &TendermintConsensusState::from_block_header(header),
+        )
+        .await?;
+        ibc_handler::update_tendermint_client_state(&mut transaction, &client_id, &client_state)
+            .await?;
+        chain::update_chain_config(&mut transaction, chain_id, &chain.config).await?;
+
+        let consensus_timestamp =
+            sync_consensus_timestamp(&mut transaction, &chain, block_time).await?;
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for refreshing tendermint client")?;
+
+        notify_event(
+            &self.notifier,
+            Event::TendermintClientRefreshed {
+                chain_id: chain_id.clone(),
+                client_id,
+                height: height.revision_height,
+            },
+        )?;
+
+        if let Some(consensus_timestamp) = consensus_timestamp {
+            notify_event(
+                &self.notifier,
+                Event::ConsensusTimestampSynced {
+                    chain_id: chain_id.clone(),
+                    consensus_timestamp,
+                },
+            )?;
+        }
+
+        Ok(Some(height))
+    }
+
+    /// Acknowledges pending packets sent to solo machine over the connected channel of given chain in blocks after
+    /// `from_height` (i.e. packets whose commitments are still present on chain). Packets whose timeout has passed on
+    /// solo machine are timed out instead (refunding their senders on chain). All the found packets are tracked through
+    /// their lifecycle and tracked packets which are still pending (e.g. because an earlier attempt failed) are retried
+    /// first. Failure to relay a packet is reported as a warning and the packet is retried in next call.
+    #[tracing::instrument(name = "relay_pending_packets", skip_all, fields(chain_id = %chain_id, from_height))]
+    pub async fn relay_pending_packets(
+        &self,
+        signer: impl Signer,
+        chain_id: &ChainId,
+        from_height: u64,
+        memo: String,
+    ) -> Result<PacketRelayReport> {
+        let _chain_lock = lock_chain(&self.db_pool, chain_id).await?;
+
+        let mut chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let channel_id = chain
+            .connection_details
+            .as_ref()
+            .map(|connection_details| connection_details.solo_machine_channel_id.clone())
+            .ok_or_else(|| anyhow!("connection is not established with chain {}", chain_id))?;
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        operation_queue::resume(
+            &self.db_pool,
+            &self.notifier,
+            &signer,
+            &self.plugins,
+            &rpc_client,
+            &chain,
+        )
+        .await?;
+
+        let latest_height = rpc_client
+            .status()
+            .await
+            .context("unable to query status of chain")?
+            .sync_info
+            .latest_block_height
+            .value();
+
+        let mut report = PacketRelayReport {
+            relayed_height: latest_height.max(from_height),
+            ..Default::default()
+        };
+
+        let mut packets: Vec<Packet> = packet::get_pending_packets(&self.db_pool, chain_id)
+            .await?
+            .into_iter()
+            .filter(|tracked| {
+                tracked.port_id == chain.config.port_id && tracked.channel_id == channel_id
+            })
+            .map(|tracked| tracked.packet)
+            .collect();
+
+        if latest_height > from_height {
+            let query = Query::eq(
+                "send_packet.packet_src_port",
+                chain.config.port_id.to_string(),
+            )
+            .and_eq("send_packet.packet_src_channel", channel_id.to_string())
+            .and_gt("tx.height", from_height)
+            .and_lte("tx.height", latest_height);
+
+            for (height, found) in search_packets(&rpc_client, query).await? {
+                if found.source_port != chain.config.port_id.to_string()
+                    || found.source_channel != channel_id.to_string()
+                    || packets
+                        .iter()
+                        .any(|pending| pending.sequence == found.sequence)
+                {
+                    continue;
+                }
+
+                packet::track_packet(&self.db_pool, chain_id, Some(height), &found).await?;
+
+                let is_relayed = packet::get_packet(
+                    &self.db_pool,
+                    chain_id,
+                    &chain.config.port_id,
+                    &channel_id,
+                    found.sequence,
+                )
+                .await?
+                .is_some_and(|tracked| tracked.state.is_final());
+
+                if !is_relayed {
+                    packets.push(found);
+                }
+            }
+        }
+
+        for packet in packets {
+            let packet_sequence = packet.sequence;
+
+            match self
+                .relay_packet_with(&signer, &rpc_client, &mut chain, packet, memo.clone())
+                .await
+            {
+                Ok(PacketRelayOutcome::Acknowledged { .. }) => {
+                    report.acknowledged.push(packet_sequence)
+                }
+                Ok(PacketRelayOutcome::AlreadyRelayed) => {}
+                Ok(PacketRelayOutcome::TimedOut { .. }) => report.timed_out.push(packet_sequence),
+                Err(err) => {
+                    notify_event(
+                        &self.notifier,
+                        Event::Warning {
+                            message: format!(
+                                "unable to relay packet {} of {}: {:#}",
+                                packet_sequence, chain_id, err
+                            ),
+                        },
+                    )?;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Acknowledges a single packet sent to solo machine over the connected channel of given chain (e.g. when its
+    /// `send_packet` event is observed). Packets which are already acknowledged (or timed out) on chain are skipped and
+    /// packets whose timeout has passed on solo machine are timed out instead.
+    #[tracing::instrument(name = "relay_packet", skip_all, fields(chain_id = %chain_id, sequence = packet.sequence))]
+    pub async fn relay_packet(
+        &self,
+        signer: impl Signer,
+        chain_id: &ChainId,
+        packet: Packet,
+        memo: String,
+    ) -> Result<PacketRelayOutcome> {
+        let _chain_lock = lock_chain(&self.db_pool, chain_id).await?;
+
+        let mut chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        operation_queue::resume(
+            &self.db_pool,
+            &self.notifier,
+            &signer,
+            &self.plugins,
+            &rpc_client,
+            &chain,
+        )
+        .await?;
+
+        self.relay_packet_with(signer, &rpc_client, &mut chain, packet, memo)
+            .await
+    }
+
+    /// Fetches packets sent to solo machine by given chain tracked through their lifecycle, latest first (optionally,
+    /// only the ones sent on given channel or in given state)
+    pub async fn packets(
+        &self,
+        chain_id: &ChainId,
+        channel_id: Option<&ChannelId>,
+        state: Option<PacketState>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<TrackedPacket>> {
+        packet::get_packets(&self.db_pool, chain_id, channel_id, state, limit, offset).await
+    }
+
+    /// Fetches a tracked packet sent to solo machine by given chain on given channel (connected channel when not
+    /// provided)
+    pub async fn packet(
+        &self,
+        chain_id: &ChainId,
+        channel_id: Option<&ChannelId>,
+        sequence: u64,
+    ) -> Result<Option<TrackedPacket>> {
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let channel_id = match channel_id {
+            Some(channel_id) => channel_id.clone(),
+            None => chain
+                .connection_details
+                .map(|connection_details| connection_details.solo_machine_channel_id)
+                .ok_or_else(|| anyhow!("connection is not established with chain {}", chain_id))?,
+        };
+
+        packet::get_packet(
+            &self.db_pool,
+            chain_id,
+            &chain.config.port_id,
+            &channel_id,
+            sequence,
+        )
+        .await
+    }
+
+    /// Fetches operations queued for submission to chains, latest first (optionally, only the ones of given chain or
+    /// in given status)
+    pub async fn pending_operations(
+        &self,
+        chain_id: Option<&ChainId>,
+        status: Option<PendingOperationStatus>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<PendingOperation>> {
+        pending_operation::get_pending_operations(&self.db_pool, chain_id, status, limit, offset)
+            .await
+    }
+
+    /// Resolves operations of given chain left pending by interrupted submissions (e.g. when solo machine crashed
+    /// after signing a transaction) and returns the resolved operations. Transactions which are not found on chain are
+    /// broadcast again without signing them again.
+    pub async fn resume_pending_operations(
+        &self,
+        signer: impl Signer,
+        chain_id: &ChainId,
+    ) -> Result<Vec<PendingOperation>> {
+        let _chain_lock = lock_chain(&self.db_pool, chain_id).await?;
+
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        operation_queue::resume(
+            &self.db_pool,
+            &self.notifier,
+            signer,
+            &self.plugins,
+            &rpc_client,
+            &chain,
+        )
+        .await
+    }
+
+    /// Rolls back state changes made while signing failed operations of a chain (the ones queued after the latest
+    /// operation which is not failed), so that sequences consumed by transactions which were never accepted on chain
+    /// are not burned. This is also done automatically when an operation fails. Returns restored sequence of chain
+    /// (`None` if there was nothing to roll back).
+    pub async fn rollback_failed_operations(&self, chain_id: &ChainId) -> Result<Option<u32>> {
+        let _chain_lock = lock_chain(&self.db_pool, chain_id).await?;
+
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        let sequence = operation_queue::rollback(&mut transaction, chain_id).await?;
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for rolling back failed operations")?;
+
+        Ok(sequence)
+    }
+
+    /// Fetches history of all operations
+    pub async fn history(
+        &self,
+        signer: impl ToPublicKey,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Operation>> {
+        let account_address = signer.to_account_address()?;
+        operation::get_operations(&self.db_pool, &account_address, limit, offset).await
+    }
+
+    /// Detects and records IBC version of chain when it was not recorded while adding the chain (so that solo machine
+    /// client is created using the revision of client types expected by chain)
+    async fn record_ibc_version(&self, chain: &mut Chain) -> Result<()> {
+        if chain.config.ibc_version.is_some() {
+            return Ok(());
+        }
+
+        match detect_ibc_version(&chain.config).await {
+            Ok(Some(ibc_version)) => {
+                chain.config.ibc_version = Some(ibc_version);
+                chain::update_chain_config(&self.db_pool, &chain.id, &chain.config).await
+            }
+            Ok(None) => Ok(()),
+            Err(err) => notify_event(
+                &self.notifier,
+                Event::Warning {
+                    message: format!(
+                        "unable to detect IBC version of chain {}: {:#}",
+                        chain.id, err
+                    ),
+                },
+            ),
+        }
+    }
+
+    /// Waits until given chain is producing blocks (i.e. latest block is not older than chain's halt threshold) so
+    /// that sequences are not consumed and proofs are not produced for a halted chain
+    async fn wait_for_block_production(&self, chain_id: &ChainId) -> Result<()> {
+        let chain = chain::get_chain(&self.db_pool, chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let halt_threshold = chain.config.halt_threshold;
+
+        if halt_threshold == Duration::from_secs(0) {
+            return Ok(());
+        }
+
+        let rpc_client = TimeoutRpcClient::for_chain(&chain.config).await?;
+
+        let mut halted = false;
+
+        loop {
+            let status = rpc_client
+                .status()
+                .await
+                .context("unable to query status of chain")?;
+
+            let latest_height = status.sync_info.latest_block_height.value();
+            let latest_block_time: DateTime<Utc> =
+                SystemTime::from(status.sync_info.latest_block_time).into();
+
+            let block_age = (Utc::now() - latest_block_time)
+                .to_std()
+                .unwrap_or_default();
+
+            if block_age <= halt_threshold {
+                if halted {
+                    notify_event(
+                        &self.notifier,
+                        Event::ChainResumed {
+                            chain_id: chain_id.clone(),
+                            latest_height,
+                        },
+                    )?;
+                }
+
+                return Ok(());
+            }
+
+            if !halted {
+                halted = true;
+
+                notify_event(
+                    &self.notifier,
+                    Event::ChainHalted {
+                        chain_id: chain_id.clone(),
+                        latest_height,
+                        latest_block_time,
+                    },
+                )?;
+            }
+
+            sleep(HALT_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn relay_packet_with<C>(
+        &self,
+        signer: impl Signer,
+        rpc_client: &C,
+        chain: &mut Chain,
+        packet: Packet,
+        memo: String,
+    ) -> Result<PacketRelayOutcome>
+    where
+        C: Client + Send + Sync,
+    {
+        let channel_id = chain
+            .connection_details
+            .as_ref()
+            .map(|connection_details| connection_details.solo_machine_channel_id.clone())
+            .ok_or_else(|| anyhow!("connection is not established with chain {}", chain.id))?;
+
+        ensure!(
+            chain.config.port_id.to_string() == packet.source_port,
+            "invalid source port id"
+        );
+        ensure!(
+            channel_id.to_string() == packet.source_channel,
+            "invalid source channel id"
+        );
+
+        let tracked = packet::get_packet(
+            &self.db_pool,
+            &chain.id,
+            &chain.config.port_id,
+            &channel_id,
+            packet.sequence,
+        )
+        .await?;
+
+        if tracked
+            .as_ref()
+            .is_some_and(|tracked| tracked.state.is_final())
+        {
+            return Ok(PacketRelayOutcome::AlreadyRelayed);
+        }
+
+        if tracked.is_none() {
+            packet::track_packet(&self.db_pool, &chain.id, None, &packet).await?;
+        }
+
+        let commitment_path =
+            PacketCommitmentPath::new(&chain.config.port_id, &channel_id, packet.sequence);
+
+        if query_ibc_store(rpc_client, commitment_path.into_bytes())
+            .await?
+            .is_none()
+        {
+            // only solo machine can prove receipt of packet (or its absence), so, packet was relayed by an earlier
+            // attempt whose outcome could not be recorded
+            let is_received = tracked.is_some_and(|tracked| tracked.state == PacketState::Received);
+            let state = if !is_received && is_packet_expired(chain, &packet) {
+                PacketState::TimedOut
+            } else {
+                PacketState::Acknowledged
+            };
+
+            packet::set_packet_state(&self.db_pool, &chain.id, &packet, state, None).await?;
+
+            return Ok(PacketRelayOutcome::AlreadyRelayed);
+        }
+
+        if is_packet_expired(chain, &packet) {
+            let transaction_hash = self
+                .timeout_packet_with(signer, rpc_client, chain, packet, memo)
+                .await?;
+
+            return Ok(PacketRelayOutcome::TimedOut { transaction_hash });
+        }
+
+        let packet_sequence = packet.sequence;
+
+        let transaction_hash = self
+            .process_packets(signer, rpc_client, chain, vec![packet], memo, None)
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow!("packet {} is not acknowledged", packet_sequence))?;
+
+        notify_event(
+            &self.notifier,
+            Event::PacketAcknowledged {
+                chain_id: chain.id.clone(),
+                packet_sequence,
+                transaction_hash: transaction_hash.clone(),
+            },
+        )?;
+
+        Ok(PacketRelayOutcome::Acknowledged { transaction_hash })
     }
 
-    /// Updates signer for future IBC transactions
-    pub async fn update_signer(
+    /// Times out a packet sent to solo machine whose timeout has passed on solo machine (sender of packet is refunded
+    /// on chain) and returns hash of the transaction
+    async fn timeout_packet_with<C>(
         &self,
         signer: impl Signer,
-        chain_id: ChainId,
-        new_public_key: PublicKey,
+        rpc_client: &C,
+        chain: &mut Chain,
+        packet: Packet,
         memo: String,
-    ) -> Result<()> {
+    ) -> Result<String>
+    where
+        C: Client + Send + Sync,
+    {
+        preflight::verify_packet_route(chain).await?;
+
+        let packet_sequence = packet.sequence;
+        let tracked_packet = packet.clone();
+        let checkpoint = Checkpoint::of(chain);
+
         let mut transaction = self
             .db_pool
             .begin()
             .await
             .context("unable to begin database transaction")?;
 
-        let mut chain = chain::get_chain(&mut transaction, &chain_id)
-            .await?
-            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+        packet::track_packet(&mut *transaction, &chain.id, None, &tracked_packet).await?;
 
-        chain_keys::add_chain_key(&mut transaction, &chain_id, &new_public_key.encode()).await?;
+        let msg = transaction_builder::msg_timeout(
+            &mut *transaction,
+            &signer,
+            &self.plugins,
+            &mut *chain,
+            packet,
+            memo,
+            None,
+        )
+        .await?;
 
-        let rpc_client = HttpClient::new(chain.config.rpc_addr.as_str())
-            .context("unable to connect to rpc client")?;
+        let operation = operation_queue::enqueue(
+            transaction,
+            &chain.id,
+            checkpoint,
+            PendingOperationKind::TimeoutPacket,
+            None,
+            &msg,
+        )
+        .await?;
 
-        let msg = transaction_builder::msg_update_solo_machine_client(
-            &mut transaction,
+        let response = operation_queue::submit(
+            &self.db_pool,
+            &self.notifier,
             &signer,
-            &mut chain,
-            Some(&new_public_key),
-            memo.clone(),
+            &self.plugins,
+            rpc_client,
+            chain,
+            &operation,
         )
         .await?;
 
-        let response = rpc_client
-            .broadcast_tx_commit(proto_encode(&msg)?.into())
-            .await?;
-
-        ensure_response_success(&response)?;
+        let transaction_hash = response.ensure_success()?;
 
-        transaction
-            .commit()
-            .await
-            .context("unable to commit transaction for receiving tokens over IBC")?;
+        packet::set_packet_state(
+            &self.db_pool,
+            &chain.id,
+            &tracked_packet,
+            PacketState::TimedOut,
+            Some(&transaction_hash),
+        )
+        .await?;
 
         notify_event(
             &self.notifier,
-            Event::SignerUpdated {
-                chain_id,
-                old_public_key: signer.to_public_key()?,
-                new_public_key,
+            Event::PacketTimedOut {
+                chain_id: chain.id.clone(),
+                packet_sequence,
+                transaction_hash: transaction_hash.clone(),
             },
-        )
-    }
+        )?;
 
-    /// Fetches history of all operations
-    pub async fn history(
-        &self,
-        signer: impl ToPublicKey,
-        limit: u32,
-        offset: u32,
-    ) -> Result<Vec<Operation>> {
-        let account_address = signer.to_account_address()?;
-        operation::get_operations(&self.db_pool, &account_address, limit, offset).await
+        Ok(transaction_hash)
     }
 
     async fn process_packets<C>(
@@ -555,10 +2447,12 @@ impl IbcService {
         packets: Vec<Packet>,
         memo: String,
         request_id: Option<String>,
-    ) -> Result<()>
+    ) -> Result<Vec<String>>
     where
         C: Client + Send + Sync,
     {
+        let mut transaction_hashes = Vec::with_capacity(packets.len());
+
         let connection_details = chain.connection_details.clone().ok_or_else(|| {
             anyhow!(
                 "connection details for chain with id {} are missing",
@@ -566,6 +2460,8 @@ impl IbcService {
             )
         })?;
 
+        preflight::verify_packet_route(chain).await?;
+
         for packet in packets {
             ensure!(
                 chain.config.port_id.to_string() == packet.source_port,
@@ -584,15 +2480,21 @@ impl IbcService {
                 "invalid destination channel id"
             );
 
+            let tracked_packet = packet.clone();
+            let checkpoint = Checkpoint::of(chain);
+
             let mut transaction = self
                 .db_pool
                 .begin()
                 .await
                 .context("unable to begin database transaction")?;
 
+            packet::track_packet(&mut *transaction, &chain.id, None, &tracked_packet).await?;
+
             let msg = transaction_builder::msg_token_receive_ack(
                 &mut *transaction,
                 &signer,
+                &self.plugins,
                 &mut *chain,
                 packet,
                 memo.clone(),
@@ -600,47 +2502,77 @@ impl IbcService {
             )
             .await?;
 
-            let response = rpc_client
-                .broadcast_tx_commit(proto_encode(&msg)?.into())
-                .await?;
+            // packet is received as soon as its acknowledgement is signed (recorded along with the consumed sequence)
+            packet::set_packet_state(
+                &mut *transaction,
+                &chain.id,
+                &tracked_packet,
+                PacketState::Received,
+                None,
+            )
+            .await?;
 
-            transaction
-                .commit()
-                .await
-                .context("unable to commit transaction for processing IBC packets")?;
+            let operation = operation_queue::enqueue(
+                transaction,
+                &chain.id,
+                checkpoint,
+                PendingOperationKind::AcknowledgePacket,
+                request_id.as_deref(),
+                &msg,
+            )
+            .await?;
 
-            ensure_response_success(&response)?;
-        }
+            let response = operation_queue::submit(
+                &self.db_pool,
+                &self.notifier,
+                &signer,
+                &self.plugins,
+                rpc_client,
+                chain,
+                &operation,
+            )
+            .await?;
 
-        Ok(())
-    }
-}
+            let transaction_hash = response.ensure_success()?;
 
-async fn create_solo_machine_client<C>(
-    signer: impl Signer,
-    rpc_client: &C,
-    chain: &Chain,
-    memo: String,
-) -> Result<ClientId>
-where
-    C: Client + Send + Sync,
-{
-    let msg = transaction_builder::msg_create_solo_machine_client(signer, chain, memo).await?;
+            packet::set_packet_state(
+                &self.db_pool,
+                &chain.id,
+                &tracked_packet,
+                PacketState::Acknowledged,
+                Some(&transaction_hash),
+            )
+            .await?;
 
-    let response = rpc_client
-        .broadcast_tx_commit(proto_encode(&msg)?.into())
-        .await?;
+            transaction_hashes.push(transaction_hash);
+        }
 
-    ensure_response_success(&response)?;
+        Ok(transaction_hashes)
+    }
+}
 
-    extract_attribute(&response.deliver_tx.events, "create_client", "client_id")?.parse()
+fn preview_sign_bytes<T, R>(
+    revision: SoloMachineRevision,
+    signer: PreviewSigner<T>,
+    result: Result<R>,
+) -> Result<SignBytesPreview> {
+    match signer.into_sign_bytes() {
+        Some(sign_bytes) => transaction_builder::decode_sign_bytes(revision, sign_bytes),
+        None => Err(result
+            .err()
+            .unwrap_or_else(|| anyhow!("operation completed without requesting a signature"))
+            .context("unable to preview sign bytes")),
+    }
 }
 
+/// Creates tendermint client on solo machine (syncing consensus timestamp of solo machine to time of verified header
+/// when enabled for chain). Returns client ID and new consensus timestamp (if it moved forward).
+#[tracing::instrument(skip_all, fields(chain_id = %chain.id))]
 async fn create_tendermint_client(
     transaction: &mut Transaction<'_, Db>,
     instance: &mut Instance,
     chain: &Chain,
-) -> Result<ClientId> {
+) -> Result<(ClientId, Option<DateTime<Utc>>)> {
     let (client_state, consensus_state) =
         transaction_builder::msg_create_tendermint_client(chain, instance).await?;
 
@@ -659,43 +2591,40 @@ async fn create_tendermint_client(
     )
     .await?;
 
-    Ok(client_id)
+    let block_time = SystemTime::from(
+        consensus_state
+            .timestamp
+            .ok_or_else(|| anyhow!("timestamp cannot be absent in consensus state"))?,
+    )
+    .into();
+    let consensus_timestamp = sync_consensus_timestamp(transaction, chain, block_time).await?;
+
+    Ok((client_id, consensus_timestamp))
 }
 
-async fn connection_open_init<C>(
-    signer: impl Signer,
-    rpc_client: &C,
+/// Moves consensus timestamp of solo machine forward to given block time of chain plus configured offset (only when
+/// timestamp syncing is enabled for chain). Returns new consensus timestamp (`None` if it is not changed).
+async fn sync_consensus_timestamp(
+    transaction: &mut Transaction<'_, Db>,
     chain: &Chain,
-    solo_machine_client_id: &ClientId,
-    tendermint_client_id: &ClientId,
-    memo: String,
-) -> Result<ConnectionId>
-where
-    C: Client + Send + Sync,
-{
-    let msg = transaction_builder::msg_connection_open_init(
-        signer,
-        chain,
-        solo_machine_client_id,
-        tendermint_client_id,
-        memo,
-    )
-    .await?;
-
-    let response = rpc_client
-        .broadcast_tx_commit(proto_encode(&msg)?.into())
-        .await?;
+    block_time: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>> {
+    let offset = match chain.config.timestamp_sync_offset {
+        Some(offset) => offset,
+        None => return Ok(None),
+    };
 
-    ensure_response_success(&response)?;
+    let consensus_timestamp =
+        block_time + chrono::Duration::from_std(offset).context("invalid timestamp sync offset")?;
 
-    extract_attribute(
-        &response.deliver_tx.events,
-        "connection_open_init",
-        "connection_id",
-    )?
-    .parse()
+    if chain::advance_consensus_timestamp(transaction, &chain.id, consensus_timestamp).await? {
+        Ok(Some(consensus_timestamp))
+    } else {
+        Ok(None)
+    }
 }
 
+#[tracing::instrument(skip_all, fields(connection_id = %solo_machine_connection_id))]
 async fn connection_open_try<'e>(
     executor: impl Executor<'e, Database = Db>,
     tendermint_client_id: &ClientId,
@@ -726,80 +2655,28 @@ async fn connection_open_try<'e>(
     Ok(connection_id)
 }
 
-#[allow(clippy::too_many_arguments)]
-async fn connection_open_ack<C>(
-    transaction: &mut Transaction<'_, Db>,
-    signer: impl Signer,
-    rpc_client: &C,
-    chain: &mut Chain,
-    solo_machine_connection_id: &ConnectionId,
-    tendermint_client_id: &ClientId,
-    tendermint_connection_id: &ConnectionId,
-    memo: String,
-) -> Result<()>
-where
-    C: Client + Send + Sync,
-{
-    let msg = transaction_builder::msg_connection_open_ack(
-        transaction,
-        signer,
-        chain,
-        solo_machine_connection_id,
-        tendermint_client_id,
-        tendermint_connection_id,
-        memo,
-    )
-    .await?;
-
-    let response = rpc_client
-        .broadcast_tx_commit(proto_encode(&msg)?.into())
-        .await?;
-
-    ensure_response_success(&response)?;
-
-    Ok(())
-}
-
+/// Opens connection on solo machine. Returns `false` if connection is already open.
+#[tracing::instrument(skip_all, fields(connection_id = %connection_id))]
 async fn connection_open_confirm(
     transaction: &mut Transaction<'_, Db>,
     connection_id: &ConnectionId,
-) -> Result<()> {
+) -> Result<bool> {
     let mut connection = ibc_handler::get_connection(&mut *transaction, connection_id)
         .await?
         .ok_or_else(|| anyhow!("connection for connection id ({}) not found", connection_id))?;
-    connection.set_state(ConnectionState::Open);
-
-    ibc_handler::update_connection(&mut *transaction, connection_id, &connection).await
-}
 
-async fn channel_open_init<C>(
-    signer: impl Signer,
-    rpc_client: &C,
-    chain: &Chain,
-    solo_machine_connection_id: &ConnectionId,
-    memo: String,
-) -> Result<ChannelId>
-where
-    C: Client + Send + Sync,
-{
-    let msg =
-        transaction_builder::msg_channel_open_init(signer, chain, solo_machine_connection_id, memo)
-            .await?;
+    if connection.state() == ConnectionState::Open {
+        return Ok(false);
+    }
 
-    let response = rpc_client
-        .broadcast_tx_commit(proto_encode(&msg)?.into())
-        .await?;
+    connection.set_state(ConnectionState::Open);
 
-    ensure_response_success(&response)?;
+    ibc_handler::update_connection(&mut *transaction, connection_id, &connection).await?;
 
-    extract_attribute(
-        &response.deliver_tx.events,
-        "channel_open_init",
-        "channel_id",
-    )?
-    .parse()
+    Ok(true)
 }
 
+#[tracing::instrument(skip_all, fields(channel_id = %solo_machine_channel_id))]
 async fn channel_open_try<'e>(
     executor: impl Executor<'e, Database = Db>,
     port_id: &PortId,
@@ -824,42 +2701,13 @@ async fn channel_open_try<'e>(
     Ok(channel_id)
 }
 
-async fn channel_open_ack<C>(
-    transaction: &mut Transaction<'_, Db>,
-    signer: impl Signer,
-    rpc_client: &C,
-    chain: &mut Chain,
-    solo_machine_channel_id: &ChannelId,
-    tendermint_channel_id: &ChannelId,
-    memo: String,
-) -> Result<()>
-where
-    C: Client + Send + Sync,
-{
-    let msg = transaction_builder::msg_channel_open_ack(
-        transaction,
-        signer,
-        chain,
-        solo_machine_channel_id,
-        tendermint_channel_id,
-        memo,
-    )
-    .await?;
-
-    let response = rpc_client
-        .broadcast_tx_commit(proto_encode(&msg)?.into())
-        .await?;
-
-    ensure_response_success(&response)?;
-
-    Ok(())
-}
-
+/// Opens channel on solo machine. Returns `false` if channel is already open.
+#[tracing::instrument(skip_all, fields(channel_id = %channel_id))]
 async fn channel_open_confirm(
     transaction: &mut Transaction<'_, Db>,
     port_id: &PortId,
     channel_id: &ChannelId,
-) -> Result<()> {
+) -> Result<bool> {
     let mut channel = ibc_handler::get_channel(&mut *transaction, port_id, channel_id)
         .await?
         .ok_or_else(|| {
@@ -869,20 +2717,194 @@ async fn channel_open_confirm(
                 port_id
             )
         })?;
+
+    if channel.state() == ChannelState::Open {
+        return Ok(false);
+    }
+
     channel.set_state(ChannelState::Open);
 
-    ibc_handler::update_channel(&mut *transaction, port_id, channel_id, &channel).await
+    ibc_handler::update_channel(&mut *transaction, port_id, channel_id, &channel).await?;
+
+    Ok(true)
+}
+
+/// Records progress of a completed handshake step and commits its database transaction
+async fn commit_handshake_step(
+    mut transaction: Transaction<'_, Db>,
+    chain_id: &ChainId,
+    progress: &HandshakeProgress,
+) -> Result<()> {
+    chain::set_handshake_progress(&mut transaction, chain_id, Some(progress)).await?;
+
+    transaction
+        .commit()
+        .await
+        .context("unable to commit transaction for connection handshake step")
+}
+
+fn decode_pending_tx(pending_tx: &str) -> Result<TxRaw> {
+    let bytes = hex::decode(pending_tx).context("invalid hex encoding of pending transaction")?;
+    TxRaw::decode(bytes.as_slice()).context("unable to decode pending transaction")
+}
+
+/// Waits until an entity (connection or channel) initialized on IBC enabled chain is queryable from chain's committed
+/// state
+#[tracing::instrument(skip_all, fields(path = %String::from_utf8_lossy(&key)))]
+async fn wait_for_counterparty<C>(rpc_client: &C, key: Vec<u8>) -> Result<()>
+where
+    C: Client + Send + Sync,
+{
+    for _ in 0..COUNTERPARTY_WAIT_ATTEMPTS {
+        if query_ibc_store(rpc_client, key.clone()).await?.is_some() {
+            return Ok(());
+        }
+
+        sleep(COUNTERPARTY_POLL_INTERVAL).await;
+    }
+
+    Err(anyhow!(
+        "`{}` not found in committed state of chain after {} attempts",
+        String::from_utf8_lossy(&key),
+        COUNTERPARTY_WAIT_ATTEMPTS
+    ))
+}
+
+/// Queries state of connection on IBC enabled chain (`None` if connection does not exist)
+async fn query_connection_state<C>(
+    rpc_client: &C,
+    connection_id: &ConnectionId,
+) -> Result<Option<ConnectionState>>
+where
+    C: Client + Send + Sync,
+{
+    query_ibc_store(rpc_client, ConnectionPath::new(connection_id).into_bytes())
+        .await?
+        .map(|value| {
+            let connection = ConnectionEnd::decode(value.as_slice())
+                .context("unable to decode connection queried from chain")?;
+            ConnectionState::from_i32(connection.state)
+                .ok_or_else(|| anyhow!("invalid connection state: {}", connection.state))
+        })
+        .transpose()
+}
+
+/// Queries state of channel on IBC enabled chain (`None` if channel does not exist)
+async fn query_channel_state<C>(
+    rpc_client: &C,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<Option<ChannelState>>
+where
+    C: Client + Send + Sync,
+{
+    query_ibc_store(
+        rpc_client,
+        ChannelPath::new(port_id, channel_id).into_bytes(),
+    )
+    .await?
+    .map(|value| {
+        let channel = Channel::decode(value.as_slice())
+            .context("unable to decode channel queried from chain")?;
+        ChannelState::from_i32(channel.state)
+            .ok_or_else(|| anyhow!("invalid channel state: {}", channel.state))
+    })
+    .transpose()
+}
+
+/// Queries value stored at given key in IBC store of chain (`None` if key is not present)
+async fn query_ibc_store<C>(rpc_client: &C, key: Vec<u8>) -> Result<Option<Vec<u8>>>
+where
+    C: Client + Send + Sync,
+{
+    let path = IBC_STORE_QUERY_PATH
+        .parse()
+        .map_err(|err| anyhow!("invalid abci query path: {}", err))?;
+
+    let response = rpc_client
+        .abci_query(Some(path), key, None, false)
+        .await
+        .context("unable to query ibc store of chain")?;
+
+    ensure!(
+        response.code.is_ok(),
+        "ibc store query failed: {}",
+        response.log
+    );
+
+    if response.value.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(response.value))
+    }
+}
+
+/// Searches transactions matching given query and returns packets sent in them along with their block heights
+async fn search_packets<C>(rpc_client: &C, query: Query) -> Result<Vec<(u64, Packet)>>
+where
+    C: Client + Send + Sync,
+{
+    let mut packets = vec![];
+    let mut page = 1;
+    let mut fetched = 0;
+
+    loop {
+        let response = rpc_client
+            .tx_search(
+                query.clone(),
+                false,
+                page,
+                TX_SEARCH_PAGE_SIZE,
+                Order::Ascending,
+            )
+            .await
+            .context("unable to search packets sent on chain")?;
+
+        fetched += response.txs.len();
+
+        for tx in response.txs.iter() {
+            let events: Vec<TxEvent> = tx.tx_result.events.iter().map(Into::into).collect();
+            let height = tx.height.value();
+
+            packets.extend(
+                extract_packets(&events)?
+                    .into_iter()
+                    .map(|packet| (height, packet)),
+            );
+        }
+
+        if response.txs.is_empty() || fetched >= response.total_count as usize {
+            return Ok(packets);
+        }
+
+        page += 1;
+    }
+}
+
+/// Returns `true` if timeout height or timeout timestamp of packet has passed on solo machine (i.e. the packet can only
+/// be timed out). Timeout timestamp is compared with consensus timestamp of solo machine as it is verified on chain.
+fn is_packet_expired(chain: &Chain, packet: &Packet) -> bool {
+    let height_passed = packet
+        .timeout_height
+        .as_ref()
+        .is_some_and(|timeout_height| {
+            !timeout_height.is_zero() && u64::from(chain.sequence) >= timeout_height.revision_height
+        });
+
+    let timestamp_passed = packet.timeout_timestamp != 0
+        && u64::try_from(chain.consensus_timestamp.timestamp())
+            .is_ok_and(|timestamp| timestamp >= packet.timeout_timestamp);
+
+    height_passed || timestamp_passed
 }
 
 fn prepare_light_client(
     chain: &Chain,
-    rpc_client: HttpClient,
+    rpc_transport: RpcTransport,
     light_store: Box<dyn LightStore>,
 ) -> Result<Instance> {
-    let builder = LightClientBuilder::prod(
+    let builder = LightClientBuilder::custom(
         chain.node_id,
-        rpc_client,
-        light_store,
         Options {
             trust_threshold: TrustThresholdFraction::new(
                 *chain.config.trust_level.numer(),
@@ -892,7 +2914,17 @@ fn prepare_light_client(
             trusting_period: chain.config.trusting_period,
             clock_drift: chain.config.max_clock_drift,
         },
-        Some(chain.config.rpc_timeout),
+        light_store,
+        Box::new(LightClientIo::new(
+            chain.node_id,
+            rpc_transport,
+            Some(chain.config.rpc_timeout),
+        )),
+        Box::new(ProdHasher),
+        Box::new(SystemClock),
+        Box::new(ProdVerifier::default()),
+        Box::new(scheduler::basic_bisecting_schedule),
+        Box::new(ProdPredicates),
     );
 
     let builder = builder.trust_primary_at(
@@ -903,82 +2935,19 @@ fn prepare_light_client(
     Ok(builder.build())
 }
 
-fn extract_packets(response: &TxCommitResponse) -> Result<Vec<Packet>> {
-    let mut packets = vec![];
-
-    for event in response.deliver_tx.events.iter() {
-        if event.type_str == "send_packet" {
-            let mut attributes = HashMap::new();
-
-            for tag in event.attributes.iter() {
-                attributes.insert(tag.key.to_string(), tag.value.to_string());
-            }
-
-            let packet = Packet {
-                sequence: attributes
-                    .remove("packet_sequence")
-                    .ok_or_else(|| anyhow!("`packet_sequence` is missing from packet data"))?
-                    .parse()
-                    .context("invalid `packet_sequence`")?,
-                source_port: attributes
-                    .remove("packet_src_port")
-                    .ok_or_else(|| anyhow!("`packet_src_port` is missing from packet data"))?,
-                source_channel: attributes
-                    .remove("packet_src_channel")
-                    .ok_or_else(|| anyhow!("`packet_src_channel` is missing from packet data"))?,
-                destination_port: attributes
-                    .remove("packet_dst_port")
-                    .ok_or_else(|| anyhow!("`packet_dst_port` is missing from packet data"))?,
-                destination_channel: attributes
-                    .remove("packet_dst_channel")
-                    .ok_or_else(|| anyhow!("`packet_dst_channel` is missing from packet data"))?,
-                data: attributes
-                    .remove("packet_data")
-                    .ok_or_else(|| anyhow!("`packet_data` is missing from packet data"))?
-                    .into_bytes(),
-                timeout_height: Some(
-                    Height::from_str(&attributes.remove("packet_timeout_height").ok_or_else(
-                        || anyhow!("`packet_timeout_height` is missing from packet data"),
-                    )?)
-                    .context("invalid `packet_timeout_height`")?,
-                ),
-                timeout_timestamp: attributes
-                    .remove("packet_timeout_timestamp")
-                    .ok_or_else(|| {
-                        anyhow!("`packet_timeout_timestamp` is missing from packet data")
-                    })?
-                    .parse()
-                    .context("invalid `packet_timeout_timestamp`")?,
-            };
-
-            packets.push(packet);
-        }
-    }
-
-    Ok(packets)
-}
-
-fn ensure_response_success(response: &TxCommitResponse) -> Result<String> {
-    ensure!(
-        response.check_tx.code.is_ok(),
-        "check_tx response contains error code: {}",
-        response.check_tx.log
-    );
-
-    ensure!(
-        response.deliver_tx.code.is_ok(),
-        "deliver_tx response contains error code: {}",
-        response.deliver_tx.log
-    );
-
-    Ok(response.hash.to_string())
+fn extract_packets(events: &[TxEvent]) -> Result<Vec<Packet>> {
+    events
+        .iter()
+        .filter(|event| event.kind == "send_packet")
+        .map(|event| Packet::from_event_attributes(&event.attributes))
+        .collect()
 }
 
-fn extract_attribute(events: &[AbciEvent], event_type: &str, key: &str) -> Result<String> {
+fn extract_attribute(events: &[TxEvent], event_type: &str, key: &str) -> Result<String> {
     let mut attribute = None;
 
     for event in events {
-        if event.type_str == event_type {
+        if event.kind == event_type {
             attribute = Some(get_attribute(&event.attributes, key)?);
         }
     }
@@ -993,16 +2962,19 @@ fn extract_attribute(events: &[AbciEvent], event_type: &str, key: &str) -> Resul
     })
 }
 
-fn get_attribute(tags: &[Tag], key: &str) -> Result<String> {
-    let key: Key = key
-        .parse()
-        .map_err(|e| anyhow!("unable to parse attribute key `{}`: {}", key, e))?;
-
-    for tag in tags {
-        if tag.key == key {
-            return Ok(tag.value.to_string());
+fn get_attribute(attributes: &[TxEventAttribute], key: &str) -> Result<String> {
+    for attribute in attributes {
+        if attribute.key == key {
+            return Ok(attribute.value.clone());
         }
     }
 
-    Err(anyhow!("{} not found in tags: {:?}", key, tags))
+    Err(anyhow!("{} not found in attributes: {:?}", key, attributes))
+}
+
+/// Returns an identifier recorded by a completed handshake step
+fn handshake_id<T: Clone>(id: &Option<T>) -> Result<T> {
+    id.clone().ok_or_else(|| {
+        anyhow!("identifier of a completed handshake step is missing in handshake progress")
+    })
 }
@@ -0,0 +1,41 @@
+use anyhow::Result;
+
+use crate::{
+    ibc::core::ics24_host::identifier::ChainId,
+    model::{audit_log, integrity, IntegrityIssue},
+    DbPool,
+};
+
+/// Used to verify (and repair) integrity of stored state of solo machine
+pub struct IntegrityService {
+    db_pool: DbPool,
+}
+
+impl IntegrityService {
+    /// Creates a new instance of integrity service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Verifies integrity of stored state (sequence counters of chains and hash chain of audit log) and returns all
+    /// the inconsistencies found (empty if storage is consistent)
+    pub async fn verify(&self) -> Result<Vec<IntegrityIssue>> {
+        let mut issues = integrity::verify_integrity(&self.db_pool).await?;
+
+        let verification = audit_log::verify_audit_log(&self.db_pool).await?;
+
+        if let Some((id, reason)) = verification.broken_entry {
+            issues.push(IntegrityIssue {
+                chain_id: None,
+                description: format!("hash chain is broken at entry {}: {}", id, reason),
+            });
+        }
+
+        Ok(issues)
+    }
+
+    /// Repairs stored state of a chain and returns repaired sequence
+    pub async fn repair(&self, chain_id: &ChainId) -> Result<u64> {
+        integrity::repair_chain(&self.db_pool, chain_id).await
+    }
+}
@@ -0,0 +1,487 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    event::notify_event,
+    ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    model::macro_run::{
+        self, MacroOperation, MacroRun, MacroRunStatus, MacroStep, MacroStepResult,
+    },
+    plugins::Plugins,
+    DbPool, Event, Signer,
+};
+
+use super::{ChainService, IbcService};
+
+/// Named sequence of operations with parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacroDefinition {
+    /// Description of macro
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Names of parameters required by macro (used as `${name}` in steps)
+    #[serde(default)]
+    pub params: Vec<String>,
+    /// Steps of macro
+    pub steps: Vec<MacroStep>,
+}
+
+impl MacroDefinition {
+    /// Returns steps of macro after substituting all the `${param}` placeholders with given parameters
+    pub fn instantiate(&self, params: &HashMap<String, String>) -> Result<Vec<MacroStep>> {
+        for param in self.params.iter() {
+            ensure!(
+                params.contains_key(param),
+                "missing macro parameter: {}",
+                param
+            );
+        }
+
+        for param in params.keys() {
+            ensure!(
+                self.params.contains(param),
+                "unknown macro parameter: {}",
+                param
+            );
+        }
+
+        let mut steps = serde_json::to_value(&self.steps)?;
+        substitute_params(&mut steps, params)?;
+
+        serde_json::from_value(steps).context("invalid macro steps after substituting parameters")
+    }
+}
+
+/// Used to execute macros (with journaling so that failed runs can be resumed)
+pub struct MacroService {
+    db_pool: DbPool,
+    notifier: Option<UnboundedSender<Event>>,
+    chain_service: ChainService,
+    ibc_service: IbcService,
+}
+
+impl MacroService {
+    /// Creates a new instance of macro service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self {
+            chain_service: ChainService::new(db_pool.clone()),
+            ibc_service: IbcService::new(db_pool.clone()),
+            db_pool,
+            notifier: None,
+        }
+    }
+
+    /// Creates a new instance of macro service with notifier
+    pub fn new_with_notifier(db_pool: DbPool, notifier: UnboundedSender<Event>) -> Self {
+        Self {
+            chain_service: ChainService::new_with_notifier(db_pool.clone(), notifier.clone()),
+            ibc_service: IbcService::new_with_notifier(db_pool.clone(), notifier.clone()),
+            db_pool,
+            notifier: Some(notifier),
+        }
+    }
+
+    /// Uses given plugins for chains with `plugin` fee strategy or client encoding
+    pub fn with_plugins(mut self, plugins: Plugins) -> Self {
+        self.chain_service = self.chain_service.with_plugins(plugins.clone());
+        self.ibc_service = self.ibc_service.with_plugins(plugins);
+        self
+    }
+
+    /// Runs a macro with given parameters. All the steps are validated before executing any of them and result of
+    /// each step is journaled in database.
+    pub async fn run(
+        &self,
+        signer: impl Signer,
+        name: &str,
+        definition: &MacroDefinition,
+        params: &HashMap<String, String>,
+        memo: String,
+    ) -> Result<MacroRun> {
+        let steps = definition.instantiate(params)?;
+        self.run_steps(signer, name, &steps, memo).await
+    }
+
+    /// Runs given steps (e.g. of a pipeline) as a macro with given name. All the steps are validated before executing
+    /// any of them (chains added by `add-chain` steps can be used by later steps) and result of each step is
+    /// journaled in database.
+    pub async fn run_steps(
+        &self,
+        signer: impl Signer,
+        name: &str,
+        steps: &[MacroStep],
+        memo: String,
+    ) -> Result<MacroRun> {
+        ensure!(
+            !steps.is_empty(),
+            "macro `{}` does not have any steps",
+            name
+        );
+
+        let mut added_chains = Vec::new();
+
+        for (index, step) in steps.iter().enumerate() {
+            self.validate_step(step, &added_chains)
+                .await
+                .context(format!("invalid step {} of macro `{}`", index, name))?;
+
+            if let MacroOperation::AddChain { ref chain_id, .. } = step.operation {
+                added_chains.push(chain_id.clone());
+            }
+        }
+
+        let run = macro_run::add_macro_run(&self.db_pool, name, steps).await?;
+        self.execute(signer, run, memo).await
+    }
+
+    /// Resumes a failed (or interrupted) macro run from its first incomplete step
+    pub async fn resume(&self, signer: impl Signer, run_id: i64, memo: String) -> Result<MacroRun> {
+        let run = self
+            .get_run(run_id)
+            .await?
+            .ok_or_else(|| anyhow!("macro run with id {} not found", run_id))?;
+
+        ensure!(
+            run.status != MacroRunStatus::Completed,
+            "macro run with id {} is already completed",
+            run_id
+        );
+
+        self.execute(signer, run, memo).await
+    }
+
+    /// Fetches a macro run
+    pub async fn get_run(&self, run_id: i64) -> Result<Option<MacroRun>> {
+        macro_run::get_macro_run(&self.db_pool, run_id).await
+    }
+
+    /// Fetches all the macro runs
+    pub async fn get_runs(&self, limit: u32, offset: u32) -> Result<Vec<MacroRun>> {
+        macro_run::get_macro_runs(&self.db_pool, limit, offset).await
+    }
+
+    async fn execute(&self, signer: impl Signer, run: MacroRun, memo: String) -> Result<MacroRun> {
+        let mut journal = run.journal.clone();
+
+        macro_run::update_macro_run(
+            &self.db_pool,
+            run.id,
+            &journal,
+            MacroRunStatus::Running,
+            None,
+        )
+        .await?;
+
+        for (index, step) in run.steps.iter().enumerate().skip(journal.len()) {
+            let request_id = format!("macro-{}-{}", run.id, index);
+            let mut attempt = 0;
+
+            let result = loop {
+                match self
+                    .execute_step(&signer, &step.operation, memo.clone(), request_id.clone())
+                    .await
+                {
+                    Err(err) if attempt < step.retries => {
+                        attempt += 1;
+
+                        notify_event(
+                            &self.notifier,
+                            Event::MacroStepRetried {
+                                run_id: run.id,
+                                name: run.name.clone(),
+                                step: index,
+                                attempt,
+                                error: format!("{:#}", err),
+                            },
+                        )?;
+
+                        tokio::time::sleep(step.retry_delay()?).await;
+                    }
+                    result => break result,
+                }
+            };
+
+            match result {
+                Ok(output) => {
+                    journal.push(MacroStepResult {
+                        step: index,
+                        output: output.clone(),
+                        completed_at: Utc::now(),
+                    });
+
+                    macro_run::update_macro_run(
+                        &self.db_pool,
+                        run.id,
+                        &journal,
+                        MacroRunStatus::Running,
+                        None,
+                    )
+                    .await?;
+
+                    notify_event(
+                        &self.notifier,
+                        Event::MacroStepCompleted {
+                            run_id: run.id,
+                            name: run.name.clone(),
+                            step: index,
+                            output,
+                        },
+                    )?;
+                }
+                Err(err) => {
+                    let error = err.to_string();
+
+                    macro_run::update_macro_run(
+                        &self.db_pool,
+                        run.id,
+                        &journal,
+                        MacroRunStatus::Failed,
+                        Some(&error),
+                    )
+                    .await?;
+
+                    notify_event(
+                        &self.notifier,
+                        Event::MacroRunFailed {
+                            run_id: run.id,
+                            name: run.name.clone(),
+                            step: index,
+                            error,
+                        },
+                    )?;
+
+                    return Err(err.context(format!(
+                        "step {} of macro `{}` failed (macro run {} can be resumed)",
+                        index, run.name, run.id
+                    )));
+                }
+            }
+        }
+
+        macro_run::update_macro_run(
+            &self.db_pool,
+            run.id,
+            &journal,
+            MacroRunStatus::Completed,
+            None,
+        )
+        .await?;
+
+        notify_event(
+            &self.notifier,
+            Event::MacroRunCompleted {
+                run_id: run.id,
+                name: run.name.clone(),
+            },
+        )?;
+
+        self.get_run(run.id)
+            .await?
+            .ok_or_else(|| anyhow!("macro run with id {} not found", run.id))
+    }
+
+    async fn validate_step(&self, step: &MacroStep, added_chains: &[String]) -> Result<()> {
+        step.retry_delay()?;
+
+        match step.operation {
+            MacroOperation::AddChain {
+                ref chain_id,
+                ref config,
+            } => {
+                chain_id.parse::<ChainId>()?;
+                config.retry_policy.validate()?;
+                config.timeouts.validate()?;
+            }
+            MacroOperation::CreateClient { ref chain_id }
+            | MacroOperation::Connect { ref chain_id, .. } => {
+                self.validate_chain_id(chain_id, added_chains).await?;
+            }
+            MacroOperation::Mint {
+                ref chain_id,
+                ref amount,
+                ref denom,
+                ..
+            }
+            | MacroOperation::Burn {
+                ref chain_id,
+                ref amount,
+                ref denom,
+            } => {
+                self.validate_chain_id(chain_id, added_chains).await?;
+                amount
+                    .parse::<u64>()
+                    .context(format!("invalid amount: {}", amount))?;
+                denom.parse::<Identifier>()?;
+            }
+            MacroOperation::ExportHistory { ref path, .. } => {
+                ensure!(!path.is_empty(), "export path cannot be empty");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates chain ID (or label) used in a step (chains added by previous steps are not yet in database)
+    async fn validate_chain_id(&self, chain_id: &str, added_chains: &[String]) -> Result<()> {
+        if !added_chains
+            .iter()
+            .any(|added_chain| added_chain == chain_id)
+        {
+            self.chain_service.resolve_chain_id(chain_id).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn execute_step(
+        &self,
+        signer: impl Signer,
+        operation: &MacroOperation,
+        memo: String,
+        request_id: String,
+    ) -> Result<String> {
+        match operation {
+            MacroOperation::AddChain { chain_id, config } => {
+                let chain_id: ChainId = chain_id.parse()?;
+
+                if self.chain_service.get(&chain_id).await?.is_some() {
+                    return Ok(format!("{} (already added)", chain_id));
+                }
+
+                let added_chain_id = self
+                    .chain_service
+                    .add(config, &signer.to_public_key()?.encode())
+                    .await?;
+
+                ensure!(
+                    added_chain_id == chain_id,
+                    "chain at {} has id `{}` (expected `{}`)",
+                    config.rpc_addr,
+                    added_chain_id,
+                    chain_id
+                );
+
+                Ok(chain_id.to_string())
+            }
+            MacroOperation::CreateClient { chain_id } => {
+                let chain_id = self.chain_service.resolve_chain_id(chain_id).await?;
+                self.ibc_service
+                    .create_client(signer, chain_id.clone(), memo)
+                    .await?;
+
+                Ok(chain_id.to_string())
+            }
+            MacroOperation::Connect { chain_id, force } => {
+                let chain_id = self.chain_service.resolve_chain_id(chain_id).await?;
+
+                let connected = self
+                    .chain_service
+                    .get(&chain_id)
+                    .await?
+                    .is_some_and(|chain| chain.connection_details.is_some());
+
+                if connected && !*force {
+                    return Ok(format!("{} (already connected)", chain_id));
+                }
+
+                self.ibc_service
+                    .connect(signer, chain_id.clone(), memo, *force)
+                    .await?;
+
+                Ok(chain_id.to_string())
+            }
+            MacroOperation::Mint {
+                chain_id,
+                amount,
+                denom,
+                receiver,
+            } => {
+                let chain_id = self.chain_service.resolve_chain_id(chain_id).await?;
+
+                self.ibc_service
+                    .mint(
+                        signer,
+                        chain_id,
+                        Some(request_id),
+                        amount.parse()?,
+                        denom.parse()?,
+                        receiver.clone(),
+                        memo,
+                    )
+                    .await
+            }
+            MacroOperation::Burn {
+                chain_id,
+                amount,
+                denom,
+            } => {
+                let chain_id = self.chain_service.resolve_chain_id(chain_id).await?;
+
+                self.ibc_service
+                    .burn(
+                        signer,
+                        chain_id,
+                        Some(request_id),
+                        amount.parse()?,
+                        denom.parse()?,
+                        memo,
+                    )
+                    .await
+            }
+            MacroOperation::ExportHistory { path, limit } => {
+                let operations = self.ibc_service.history(signer, *limit, 0).await?;
+
+                let report = operations
+                    .into_iter()
+                    .map(|operation| {
+                        json!({
+                            "id": operation.id,
+                            "request_id": operation.request_id,
+                            "address": operation.address,
+                            "denom": operation.denom.to_string(),
+                            "amount": operation.amount,
+                            "operation_type": operation.operation_type,
+                            "transaction_hash": operation.transaction_hash,
+                            "created_at": operation.created_at.to_rfc3339(),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                std::fs::write(path, serde_json::to_vec_pretty(&report)?)
+                    .context(format!("unable to write history to {}", path))?;
+
+                Ok(path.clone())
+            }
+        }
+    }
+}
+
+fn substitute_params(value: &mut Value, params: &HashMap<String, String>) -> Result<()> {
+    match value {
+        Value::String(s) => {
+            for (name, param) in params.iter() {
+                *s = s.replace(&format!("${{{}}}", name), param);
+            }
+
+            ensure!(!s.contains("${"), "unresolved macro parameter in `{}`", s);
+        }
+        Value::Array(values) => {
+            for value in values.iter_mut() {
+                substitute_params(value, params)?;
+            }
+        }
+        Value::Object(map) => {
+            for value in map.values_mut() {
+                substitute_params(value, params)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
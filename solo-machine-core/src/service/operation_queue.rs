@@ -0,0 +1,552 @@
+//! Durable queue of on-chain submissions. Every signed transaction is persisted (along with the sequences consumed
+//! while signing it) before it leaves solo machine, so that a crash between signing and broadcasting neither loses
+//! nor double-submits an operation.
+use anyhow::{anyhow, ensure, Context, Result};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use sqlx::Transaction;
+use tendermint::abci::transaction::Hash;
+use tendermint_rpc::Client;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    broadcaster::{broadcast_and_wait, BroadcastResult, ExecutionResult, TxEvent},
+    event::{notify_event, Event},
+    ibc::core::ics24_host::identifier::ChainId,
+    model::{
+        chain::{self, chain_keys},
+        pending_operation, Chain, PendingOperation, PendingOperationKind, PendingOperationStatus,
+    },
+    plugins::Plugins,
+    proto::proto_encode,
+    service::{TxError, TxErrorKind, TxStage},
+    transaction_builder, Db, DbPool, Signer,
+};
+
+/// Maximum number of times a transaction rejected because of account sequence mismatch is rebuilt and retried
+const MAX_SEQUENCE_MISMATCH_RETRIES: usize = 3;
+
+/// Sequences of solo machine before signing a transaction (restored if the transaction is rejected by chain)
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Checkpoint {
+    sequence: u32,
+    packet_sequence: u32,
+}
+
+impl Checkpoint {
+    /// Captures current sequences of given chain
+    pub(super) fn of(chain: &Chain) -> Self {
+        Self {
+            sequence: chain.sequence,
+            packet_sequence: chain.packet_sequence,
+        }
+    }
+}
+
+/// Adds a signed transaction to pending operation queue and commits given database transaction (which contains the
+/// state changes made while signing it)
+pub(super) async fn enqueue(
+    mut transaction: Transaction<'_, Db>,
+    chain_id: &ChainId,
+    checkpoint: Checkpoint,
+    kind: PendingOperationKind,
+    request_id: Option<&str>,
+    tx: &TxRaw,
+) -> Result<PendingOperation> {
+    let operation = push(&mut transaction, chain_id, checkpoint, kind, request_id, tx).await?;
+
+    transaction
+        .commit()
+        .await
+        .context("unable to commit transaction for queueing operation")?;
+
+    Ok(operation)
+}
+
+/// Adds a signed transaction to pending operation queue without committing given database transaction (for callers
+/// which record the queued operation along with other state changes)
+pub(super) async fn push(
+    transaction: &mut Transaction<'_, Db>,
+    chain_id: &ChainId,
+    checkpoint: Checkpoint,
+    kind: PendingOperationKind,
+    request_id: Option<&str>,
+    tx: &TxRaw,
+) -> Result<PendingOperation> {
+    pending_operation::add_pending_operation(
+        &mut *transaction,
+        chain_id,
+        kind,
+        request_id,
+        tx,
+        checkpoint.sequence,
+        checkpoint.packet_sequence,
+    )
+    .await
+}
+
+/// Fetches events emitted by the transaction of a completed operation from chain
+pub(super) async fn events_of<C>(
+    rpc_client: &C,
+    operation: &PendingOperation,
+) -> Result<Vec<TxEvent>>
+where
+    C: Client + Send + Sync,
+{
+    ensure!(
+        operation.status == PendingOperationStatus::Completed,
+        "operation {} is not completed (status: {})",
+        operation.id,
+        operation.status
+    );
+
+    let response = rpc_client
+        .tx(parse_hash(&operation.transaction_hash)?, false)
+        .await
+        .with_context(|| {
+            format!(
+                "unable to fetch transaction {} of operation {}",
+                operation.transaction_hash, operation.id
+            )
+        })?;
+
+    Ok(ExecutionResult::from(&response.tx_result).events)
+}
+
+/// Broadcasts a queued transaction (rebuilding it on account sequence mismatch) and records its outcome in pending
+/// operation queue. When outcome of broadcast is not known (e.g. chain is unreachable or transaction is already in
+/// mempool), operation is left pending and is resolved before next operation on the chain. Failed broadcasts are
+/// notified using [`Event::BroadcastFailed`] (along with [`Event::ClientFrozen`] when chain rejects transaction
+/// because solo machine client is not active).
+pub(super) async fn submit<C>(
+    db_pool: &DbPool,
+    notifier: &Option<UnboundedSender<Event>>,
+    signer: impl Signer,
+    plugins: &Plugins,
+    rpc_client: &C,
+    chain: &Chain,
+    operation: &PendingOperation,
+) -> Result<BroadcastResult>
+where
+    C: Client + Send + Sync,
+{
+    submit_with(
+        db_pool, notifier, signer, plugins, rpc_client, chain, operation, true,
+    )
+    .await
+}
+
+/// Broadcasts a queued transaction and records its outcome in pending operation queue. Transaction is rebuilt on
+/// account sequence mismatch only if `rebuild` is `true` (i.e. when given signer signed the transaction).
+#[tracing::instrument(skip_all, fields(chain_id = %chain.id, operation_id = operation.id, kind = ?operation.kind))]
+#[allow(clippy::too_many_arguments)]
+async fn submit_with<C>(
+    db_pool: &DbPool,
+    notifier: &Option<UnboundedSender<Event>>,
+    signer: impl Signer,
+    plugins: &Plugins,
+    rpc_client: &C,
+    chain: &Chain,
+    operation: &PendingOperation,
+    rebuild: bool,
+) -> Result<BroadcastResult>
+where
+    C: Client + Send + Sync,
+{
+    let mut tx = operation.tx.clone();
+    let mut retries = 0;
+
+    loop {
+        let response = broadcast_and_wait(
+            rpc_client,
+            proto_encode(&tx)?,
+            &chain.config.inclusion_policy,
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "outcome of operation {} is not known (it is resolved before next operation on {})",
+                operation.id, chain.id
+            )
+        })?;
+
+        let error = match response.ensure_success() {
+            Ok(_) => {
+                pending_operation::set_pending_operation_status(
+                    db_pool,
+                    operation.id,
+                    PendingOperationStatus::Completed,
+                    None,
+                )
+                .await?;
+
+                return Ok(response);
+            }
+            Err(err) => err,
+        };
+
+        let error_kind = error
+            .downcast_ref::<TxError>()
+            .map(|err| err.kind)
+            .unwrap_or(TxErrorKind::Unknown);
+        let included = error
+            .downcast_ref::<TxError>()
+            .is_some_and(|err| err.stage == TxStage::DeliverTx);
+
+        if rebuild
+            && error_kind == TxErrorKind::SequenceMismatch
+            && retries < MAX_SEQUENCE_MISMATCH_RETRIES
+        {
+            retries += 1;
+            tracing::warn!(
+                chain_id = %chain.id,
+                operation_id = operation.id,
+                retry = retries,
+                max_retries = MAX_SEQUENCE_MISMATCH_RETRIES,
+                "account sequence mismatch, rebuilding transaction"
+            );
+
+            tx = transaction_builder::rebuild(
+                &signer,
+                plugins,
+                chain,
+                &tx,
+                operation.request_id.as_deref(),
+            )
+            .await
+            .context("unable to rebuild transaction after account sequence mismatch")?;
+
+            pending_operation::update_pending_operation_tx(db_pool, operation.id, &tx).await?;
+
+            continue;
+        }
+
+        if error_kind != TxErrorKind::AlreadyInMempool {
+            fail(db_pool, operation, &error.to_string(), included).await?;
+
+            notify_event(
+                notifier,
+                Event::BroadcastFailed {
+                    chain_id: chain.id.clone(),
+                    operation_id: operation.id,
+                    kind: operation.kind,
+                    error: error.to_string(),
+                },
+            )?;
+
+            if error_kind == TxErrorKind::ClientNotActive {
+                notify_event(
+                    notifier,
+                    Event::ClientFrozen {
+                        chain_id: chain.id.clone(),
+                        client_id: chain
+                            .connection_details
+                            .as_ref()
+                            .map(|details| details.solo_machine_client_id.clone()),
+                    },
+                )?;
+            }
+        }
+
+        return Ok(response);
+    }
+}
+
+/// Resolves operations of given chain left pending by interrupted submissions, oldest first. Transactions found on
+/// chain are marked as completed (or failed) and the ones which are not found are broadcast again (without signing
+/// them again). Stops at the first operation whose outcome cannot be determined, so that operations signed later are
+/// never submitted before it. Only IBC operations (signed by given signer) are rebuilt on account sequence mismatch.
+pub(super) async fn resume<C>(
+    db_pool: &DbPool,
+    notifier: &Option<UnboundedSender<Event>>,
+    signer: impl Signer,
+    plugins: &Plugins,
+    rpc_client: &C,
+    chain: &Chain,
+) -> Result<Vec<PendingOperation>>
+where
+    C: Client + Send + Sync,
+{
+    let mut resolved = Vec::new();
+
+    for mut operation in pending_operation::get_unresolved_operations(db_pool, &chain.id).await? {
+        let hash = parse_hash(&operation.transaction_hash)?;
+
+        let (status, error) = match rpc_client.tx(hash, false).await {
+            Ok(response) if response.tx_result.code.is_ok() => {
+                pending_operation::set_pending_operation_status(
+                    db_pool,
+                    operation.id,
+                    PendingOperationStatus::Completed,
+                    None,
+                )
+                .await?;
+
+                (PendingOperationStatus::Completed, None)
+            }
+            Ok(response) => {
+                let error = response.tx_result.log.to_string();
+                let status = fail(db_pool, &operation, &error, true).await?;
+
+                (status, Some(error))
+            }
+            Err(_) => {
+                let response = submit_with(
+                    db_pool,
+                    notifier,
+                    &signer,
+                    plugins,
+                    rpc_client,
+                    chain,
+                    &operation,
+                    operation.kind.is_ibc(),
+                )
+                .await?;
+
+                operation.transaction_hash = response.transaction_hash.clone();
+
+                match response.ensure_success() {
+                    Ok(_) => (PendingOperationStatus::Completed, None),
+                    Err(err)
+                        if err
+                            .downcast_ref::<TxError>()
+                            .is_some_and(|err| err.kind == TxErrorKind::AlreadyInMempool) =>
+                    {
+                        return Err(err.context(format!(
+                            "operation {} is still in mempool of {}",
+                            operation.id, chain.id
+                        )));
+                    }
+                    Err(err)
+                        if err
+                            .downcast_ref::<TxError>()
+                            .is_some_and(|err| err.stage == TxStage::DeliverTx) =>
+                    {
+                        (PendingOperationStatus::Reverted, Some(err.to_string()))
+                    }
+                    Err(err) => (PendingOperationStatus::Failed, Some(err.to_string())),
+                }
+            }
+        };
+
+        operation.status = status;
+        operation.error = error;
+
+        notify_event(
+            notifier,
+            Event::PendingOperationResolved {
+                chain_id: chain.id.clone(),
+                operation_id: operation.id,
+                kind: operation.kind,
+                status: operation.status,
+                transaction_hash: operation.transaction_hash.clone(),
+            },
+        )?;
+
+        resolved.push(operation);
+    }
+
+    Ok(resolved)
+}
+
+/// Records failure of an operation and returns its new status. Operations rejected before being included in a block
+/// are marked as failed and the state changes made while signing failed operations of their chain are rolled back
+/// (see [`rollback`]). Operations included in a block (i.e. failed in `deliver_tx`) are marked as reverted without
+/// rolling back: signatures and proofs in them are public on chain, so, signing different data with the sequences
+/// they consumed would be a misbehaviour of solo machine.
+async fn fail(
+    db_pool: &DbPool,
+    operation: &PendingOperation,
+    error: &str,
+    included: bool,
+) -> Result<PendingOperationStatus> {
+    let status = if included {
+        PendingOperationStatus::Reverted
+    } else {
+        PendingOperationStatus::Failed
+    };
+
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("unable to begin database transaction")?;
+
+    pending_operation::set_pending_operation_status(
+        &mut transaction,
+        operation.id,
+        status,
+        Some(error),
+    )
+    .await?;
+
+    if !included {
+        rollback(&mut transaction, &operation.chain_id).await?;
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("unable to commit transaction for failing queued operation")?;
+
+    Ok(status)
+}
+
+/// Rolls back the state changes made while signing failed operations of given chain, so that the sequences consumed
+/// by transactions which were never accepted on chain are not burned. Only the failed operations queued after the
+/// latest operation which is not failed (including reverted ones) are rolled back (so that a sequence is never signed
+/// twice for transactions which may still be committed or which are already included in a block). Returns restored sequence of chain (if any operation was rolled back).
+pub(super) async fn rollback(
+    transaction: &mut Transaction<'_, Db>,
+    chain_id: &ChainId,
+) -> Result<Option<u32>> {
+    let operations =
+        pending_operation::get_trailing_failed_operations(&mut *transaction, chain_id).await?;
+
+    let chain = chain::get_chain(&mut *transaction, chain_id)
+        .await?
+        .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+    let mut sequence = chain.sequence;
+    let mut packet_sequence = chain.packet_sequence;
+
+    // operations are rolled back from the latest one; an operation signed at or after current sequence was already
+    // rolled back (the sequences it consumed were returned when it failed)
+    for operation in operations.iter().rev() {
+        if operation.sequence >= sequence {
+            continue;
+        }
+
+        if operation.kind == PendingOperationKind::UpdateSigner {
+            chain_keys::remove_latest_chain_key(&mut *transaction, chain_id).await?;
+        }
+
+        sequence = operation.sequence;
+        packet_sequence = operation.packet_sequence;
+    }
+
+    if sequence == chain.sequence {
+        return Ok(None);
+    }
+
+    chain::restore_sequences(&mut *transaction, chain_id, sequence, packet_sequence).await?;
+
+    tracing::info!(
+        chain_id = %chain_id,
+        from_sequence = chain.sequence,
+        sequence,
+        "rolled back sequence after failed operations"
+    );
+
+    Ok(Some(sequence))
+}
+
+fn parse_hash(transaction_hash: &str) -> Result<Hash> {
+    transaction_hash
+        .parse()
+        .map_err(|err| anyhow!("invalid transaction hash {}: {}", transaction_hash, err))
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    /// Queues an operation consuming one sequence of given chain
+    async fn queue_operation(db_pool: &DbPool, chain_id: &ChainId, memo: u8) -> PendingOperation {
+        let mut transaction = db_pool.begin().await.unwrap();
+
+        let chain = chain::get_chain(&mut transaction, chain_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let checkpoint = Checkpoint::of(&chain);
+
+        chain::restore_sequences(
+            &mut transaction,
+            chain_id,
+            chain.sequence + 1,
+            chain.packet_sequence,
+        )
+        .await
+        .unwrap();
+
+        let tx = TxRaw {
+            body_bytes: vec![memo],
+            auth_info_bytes: Vec::new(),
+            signatures: Vec::new(),
+        };
+
+        enqueue(
+            transaction,
+            chain_id,
+            checkpoint,
+            PendingOperationKind::Mint,
+            None,
+            &tx,
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn sequence_of(db_pool: &DbPool, chain_id: &ChainId) -> u32 {
+        chain::get_chain(db_pool, chain_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .sequence
+    }
+
+    async fn status_of(db_pool: &DbPool, operation: &PendingOperation) -> PendingOperationStatus {
+        pending_operation::get_pending_operation(db_pool, operation.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .status
+    }
+
+    #[tokio::test]
+    async fn rejected_operation_is_rolled_back() {
+        let db_pool = test_util::db_pool().await;
+        let chain_id = test_util::add_chain(&db_pool, "test-1").await;
+        let sequence = sequence_of(&db_pool, &chain_id).await;
+
+        let operation = queue_operation(&db_pool, &chain_id, 1).await;
+        assert_eq!(sequence_of(&db_pool, &chain_id).await, sequence + 1);
+
+        let status = fail(&db_pool, &operation, "rejected in check_tx", false)
+            .await
+            .unwrap();
+
+        assert_eq!(status, PendingOperationStatus::Failed);
+        assert_eq!(status_of(&db_pool, &operation).await, status);
+        assert_eq!(sequence_of(&db_pool, &chain_id).await, sequence);
+    }
+
+    #[tokio::test]
+    async fn included_operation_keeps_sequences_consumed() {
+        let db_pool = test_util::db_pool().await;
+        let chain_id = test_util::add_chain(&db_pool, "test-1").await;
+        let sequence = sequence_of(&db_pool, &chain_id).await;
+
+        let reverted = queue_operation(&db_pool, &chain_id, 1).await;
+
+        let status = fail(&db_pool, &reverted, "failed in deliver_tx", true)
+            .await
+            .unwrap();
+
+        assert_eq!(status, PendingOperationStatus::Reverted);
+        assert_eq!(status_of(&db_pool, &reverted).await, status);
+        assert_eq!(sequence_of(&db_pool, &chain_id).await, sequence + 1);
+
+        // a later rejected operation is rolled back only up to the sequences consumed by reverted operation
+        let rejected = queue_operation(&db_pool, &chain_id, 2).await;
+        assert_eq!(sequence_of(&db_pool, &chain_id).await, sequence + 2);
+
+        fail(&db_pool, &rejected, "rejected in check_tx", false)
+            .await
+            .unwrap();
+        assert_eq!(sequence_of(&db_pool, &chain_id).await, sequence + 1);
+
+        let mut transaction = db_pool.begin().await.unwrap();
+        assert_eq!(rollback(&mut transaction, &chain_id).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,169 @@
+//! Strict pre-flight verification of state of IBC client, connection and channel on chain before signing messages
+//! referencing them (enabled using `strict_preflight` in configuration of chain). Messages signed by solo machine
+//! consume its sequences, so, a message which is bound to fail on chain (e.g. because chain was reset or a channel was
+//! closed) is rejected before signing it instead of wasting sequences on a doomed transaction.
+use anyhow::{anyhow, ensure, Result};
+use cosmos_sdk_proto::ibc::core::{
+    channel::v1::State as ChannelState, connection::v1::State as ConnectionState,
+};
+
+use crate::{
+    client_encoder::{decode_client_state_sequence, is_client_state_frozen},
+    ibc::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId},
+    model::Chain,
+};
+
+/// Verifies that solo machine client exists on chain, is not frozen and expects next signature of solo machine at
+/// current sequence of chain
+pub(crate) async fn verify_client(chain: &Chain, client_id: &ClientId) -> Result<()> {
+    if !chain.config.strict_preflight {
+        return Ok(());
+    }
+
+    let client_state = chain.query_client_state(client_id).await?.ok_or_else(|| {
+        anyhow!(
+            "pre-flight verification failed: solo machine client {} does not exist on chain {} (chain may have been \
+             reset, check it using `chain reset-state {}`)",
+            client_id,
+            chain.id,
+            chain.id
+        )
+    })?;
+
+    ensure!(
+        !is_client_state_frozen(&client_state)?,
+        "pre-flight verification failed: solo machine client {} is frozen on chain {} (frozen clients cannot verify \
+         signatures of solo machine)",
+        client_id,
+        chain.id
+    );
+
+    let on_chain_sequence = decode_client_state_sequence(&client_state)?;
+
+    ensure!(
+        on_chain_sequence == u64::from(chain.sequence),
+        "pre-flight verification failed: solo machine client {} on chain {} expects signature at sequence {} but local \
+         sequence is {} (check it using `resync {}`)",
+        client_id,
+        chain.id,
+        on_chain_sequence,
+        chain.sequence,
+        chain.id
+    );
+
+    Ok(())
+}
+
+/// Verifies that connection with given ID exists on chain and is in expected state
+pub(crate) async fn verify_connection(
+    chain: &Chain,
+    connection_id: &ConnectionId,
+    expected: ConnectionState,
+) -> Result<()> {
+    if !chain.config.strict_preflight {
+        return Ok(());
+    }
+
+    let connection = chain
+        .query_connection(connection_id)
+        .await?
+        .ok_or_else(|| {
+            anyhow!(
+                "pre-flight verification failed: connection {} does not exist on chain {}",
+                connection_id,
+                chain.id
+            )
+        })?;
+
+    let state = ConnectionState::from_i32(connection.state)
+        .ok_or_else(|| anyhow!("invalid connection state: {}", connection.state))?;
+
+    ensure!(
+        state == expected,
+        "pre-flight verification failed: connection {} on chain {} is in {} state (expected {})",
+        connection_id,
+        chain.id,
+        connection_state_name(state),
+        connection_state_name(expected)
+    );
+
+    Ok(())
+}
+
+/// Verifies that channel with given ID (on port of solo machine) exists on chain and is in expected state
+pub(crate) async fn verify_channel(
+    chain: &Chain,
+    channel_id: &ChannelId,
+    expected: ChannelState,
+) -> Result<()> {
+    if !chain.config.strict_preflight {
+        return Ok(());
+    }
+
+    let channel = chain
+        .query_channel(&chain.config.port_id, channel_id)
+        .await?
+        .ok_or_else(|| {
+            anyhow!(
+                "pre-flight verification failed: channel {} on port {} does not exist on chain {}",
+                channel_id,
+                chain.config.port_id,
+                chain.id
+            )
+        })?;
+
+    let state = ChannelState::from_i32(channel.state)
+        .ok_or_else(|| anyhow!("invalid channel state: {}", channel.state))?;
+
+    ensure!(
+        state == expected,
+        "pre-flight verification failed: channel {} on port {} of chain {} is in {} state (expected {})",
+        channel_id,
+        chain.config.port_id,
+        chain.id,
+        channel_state_name(state),
+        channel_state_name(expected)
+    );
+
+    Ok(())
+}
+
+/// Verifies that solo machine client and channel of an established connection are ready for relaying packets (i.e.
+/// client is active and channel is open)
+pub(crate) async fn verify_packet_route(chain: &Chain) -> Result<()> {
+    if !chain.config.strict_preflight {
+        return Ok(());
+    }
+
+    let connection_details = chain
+        .connection_details
+        .as_ref()
+        .ok_or_else(|| anyhow!("connection is not established with chain {}", chain.id))?;
+
+    verify_client(chain, &connection_details.solo_machine_client_id).await?;
+    verify_channel(
+        chain,
+        &connection_details.solo_machine_channel_id,
+        ChannelState::Open,
+    )
+    .await
+}
+
+fn connection_state_name(state: ConnectionState) -> &'static str {
+    match state {
+        ConnectionState::UninitializedUnspecified => "UNINITIALIZED",
+        ConnectionState::Init => "INIT",
+        ConnectionState::Tryopen => "TRYOPEN",
+        ConnectionState::Open => "OPEN",
+    }
+}
+
+fn channel_state_name(state: ChannelState) -> &'static str {
+    match state {
+        ChannelState::UninitializedUnspecified => "UNINITIALIZED",
+        ChannelState::Init => "INIT",
+        ChannelState::Tryopen => "TRYOPEN",
+        ChannelState::Open => "OPEN",
+        ChannelState::Closed => "CLOSED",
+    }
+}
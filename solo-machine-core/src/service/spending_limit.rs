@@ -0,0 +1,299 @@
+use std::convert::TryFrom;
+
+use anyhow::{Context, Result};
+use chrono::{Duration, TimeZone, Utc};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    event::{notify_event, Event},
+    ibc::core::ics24_host::identifier::ChainId,
+    model::spending,
+    policy::{violation, LimitAction, SigningPolicy},
+    service::chain_lock::{lock_spending, LockGuard},
+    DbPool,
+};
+
+/// A transfer which passed spending limits. Spending lock of its denom is held until the transfer is recorded (or
+/// dropped when the transfer fails), so, concurrent transfers of the denom (on any chain and in any solo machine
+/// process sharing the database) never exceed a limit together.
+pub(crate) struct Spending {
+    chain_id: ChainId,
+    denom: String,
+    amount: u64,
+    request_id: Option<String>,
+    escalated: bool,
+    _lock: LockGuard,
+}
+
+impl Spending {
+    /// Records transfer in spendings (to be called once the transfer succeeds, so that failed transfers never count
+    /// towards limits)
+    pub(crate) async fn record(self, db_pool: &DbPool) -> Result<()> {
+        // sub-second precision is dropped so that stored timestamps compare correctly on all the databases
+        let now = Utc.timestamp(Utc::now().timestamp(), 0);
+
+        spending::add_spending(
+            db_pool,
+            &self.chain_id,
+            &self.denom,
+            self.amount,
+            self.request_id.as_deref(),
+            self.escalated,
+            now,
+        )
+        .await
+    }
+}
+
+/// Checks a transfer against spending limits of given policy. Transfers exceeding a limit are rejected unless the
+/// limit escalates and escalation is approved. Returns `None` when denom of transfer is not limited, otherwise, the
+/// returned spending should be recorded once the transfer succeeds.
+pub(crate) async fn check_spending(
+    db_pool: &DbPool,
+    notifier: &Option<UnboundedSender<Event>>,
+    policy: &SigningPolicy,
+    chain_id: &ChainId,
+    denom: &str,
+    amount: u64,
+    request_id: Option<&str>,
+) -> Result<Option<Spending>> {
+    let limits = policy
+        .spending_limits
+        .iter()
+        .filter(|limit| limit.denom == denom)
+        .collect::<Vec<_>>();
+
+    if limits.is_empty() {
+        return Ok(None);
+    }
+
+    let lock = lock_spending(db_pool, denom).await?;
+
+    let now = Utc::now();
+    let mut escalated = false;
+
+    for limit in limits {
+        let window = Duration::from_std(limit.window()?)
+            .with_context(|| format!("window of spending limit is too large: {}", limit.window))?;
+        let spent = spending::get_spent_amount(db_pool, denom, now - window).await?;
+
+        if spent + u128::from(amount) <= u128::from(limit.amount) {
+            continue;
+        }
+
+        let approved = limit.on_exceed == LimitAction::Escalate && policy.escalation_approved;
+
+        notify_event(
+            notifier,
+            Event::SpendingLimitExceeded {
+                chain_id: chain_id.clone(),
+                denom: denom.to_string(),
+                amount,
+                spent: u64::try_from(spent).unwrap_or(u64::MAX),
+                limit: limit.amount,
+                window: limit.window.clone(),
+                approved,
+            },
+        )?;
+
+        if !approved {
+            return Err(violation(format!(
+                "transfer of {} {} exceeds spending limit of {} {} per {} ({} {} already transferred{})",
+                amount,
+                denom,
+                limit.amount,
+                denom,
+                limit.window,
+                spent,
+                denom,
+                match limit.on_exceed {
+                    LimitAction::Block => "",
+                    LimitAction::Escalate => ", escalation required",
+                }
+            ))
+            .into());
+        }
+
+        escalated = true;
+    }
+
+    Ok(Some(Spending {
+        chain_id: chain_id.clone(),
+        denom: denom.to_string(),
+        amount,
+        request_id: request_id.map(ToString::to_string),
+        escalated,
+        _lock: lock,
+    }))
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use super::*;
+    use crate::{
+        policy::{PolicyViolation, SpendingLimit},
+        test_util::db_pool,
+    };
+
+    fn policy(denom: &str, on_exceed: LimitAction) -> SigningPolicy {
+        SigningPolicy {
+            spending_limits: vec![SpendingLimit {
+                denom: denom.to_string(),
+                amount: 100,
+                window: "1h".to_string(),
+                on_exceed,
+            }],
+            ..Default::default()
+        }
+    }
+
+    async fn spend(db_pool: &DbPool, denom: &str, amount: u64, ago: Duration) {
+        let created_at = Utc.timestamp(Utc::now().timestamp(), 0) - ago;
+
+        spending::add_spending(
+            db_pool,
+            &"test-1".parse().unwrap(),
+            denom,
+            amount,
+            None,
+            false,
+            created_at,
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn check(
+        db_pool: &DbPool,
+        policy: &SigningPolicy,
+        denom: &str,
+        amount: u64,
+    ) -> Result<Option<Spending>> {
+        check_spending(
+            db_pool,
+            &None,
+            policy,
+            &"test-1".parse().unwrap(),
+            denom,
+            amount,
+            None,
+        )
+        .await
+    }
+
+    fn is_violation(result: Result<Option<Spending>>) -> bool {
+        matches!(result, Err(err) if err.downcast_ref::<PolicyViolation>().is_some())
+    }
+
+    #[tokio::test]
+    async fn transfers_up_to_limit_are_allowed() {
+        let db_pool = db_pool().await;
+        let policy = policy("upto", LimitAction::Block);
+
+        spend(&db_pool, "upto", 60, Duration::minutes(30)).await;
+
+        let spending = check(&db_pool, &policy, "upto", 40).await.unwrap();
+        assert!(spending.is_some());
+        drop(spending);
+
+        assert!(is_violation(check(&db_pool, &policy, "upto", 41).await));
+    }
+
+    #[tokio::test]
+    async fn spendings_outside_window_are_not_counted() {
+        let db_pool = db_pool().await;
+        let policy = policy("window", LimitAction::Block);
+
+        // just outside the window (window of check starts after current time read by `spend`)
+        spend(
+            &db_pool,
+            "window",
+            100,
+            Duration::hours(1) + Duration::seconds(1),
+        )
+        .await;
+        assert!(check(&db_pool, &policy, "window", 100)
+            .await
+            .unwrap()
+            .is_some());
+
+        // just inside the window (with a margin for slow test runs)
+        spend(
+            &db_pool,
+            "window",
+            1,
+            Duration::hours(1) - Duration::seconds(30),
+        )
+        .await;
+        assert!(check(&db_pool, &policy, "window", 99)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(is_violation(check(&db_pool, &policy, "window", 100).await));
+    }
+
+    #[tokio::test]
+    async fn spending_at_start_of_window_is_not_counted() {
+        let db_pool = db_pool().await;
+        let since = Utc.timestamp(1_600_000_000, 0);
+
+        for (amount, created_at) in [
+            (1, since),
+            (10, since + Duration::seconds(1)),
+            (100, since + Duration::hours(1)),
+        ] {
+            spending::add_spending(
+                &db_pool,
+                &"test-1".parse().unwrap(),
+                "start",
+                amount,
+                None,
+                false,
+                created_at,
+            )
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(
+            spending::get_spent_amount(&db_pool, "start", since)
+                .await
+                .unwrap(),
+            110
+        );
+    }
+
+    #[tokio::test]
+    async fn other_denoms_are_not_limited() {
+        let db_pool = db_pool().await;
+        let policy = policy("limited", LimitAction::Block);
+
+        spend(&db_pool, "unlimited", 1000, Duration::minutes(1)).await;
+
+        assert!(check(&db_pool, &policy, "unlimited", 1000)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(check(&db_pool, &policy, "limited", 100)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn escalated_transfers_exceed_limit_only_when_approved() {
+        let db_pool = db_pool().await;
+        let mut policy = policy("escalate", LimitAction::Escalate);
+
+        spend(&db_pool, "escalate", 100, Duration::minutes(1)).await;
+        assert!(is_violation(check(&db_pool, &policy, "escalate", 1).await));
+
+        policy.escalation_approved = true;
+
+        let spending = check(&db_pool, &policy, "escalate", 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(spending.escalated);
+    }
+}
@@ -0,0 +1,177 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use tokio::time::sleep;
+
+use crate::{
+    ibc::core::ics24_host::identifier::ChainId, model::stats, supervisor::CancellationToken,
+    DbPool, Event,
+};
+
+/// Aggregated stats of solo machine on a day (UTC)
+#[derive(Debug)]
+pub struct DailyStats {
+    /// Day (UTC)
+    pub day: NaiveDate,
+    /// Number of seconds for which solo machine (gRPC server or relayer) was running on the day
+    pub uptime_seconds: u64,
+    /// Number of packets relayed (acknowledged) on the day
+    pub packets_relayed: u64,
+    /// Number of successful operations on the day
+    pub operations: u64,
+}
+
+/// Stats of a chain aggregated over all the recorded days
+#[derive(Debug)]
+pub struct ChainStats {
+    /// Chain ID of IBC enabled chain
+    pub chain_id: ChainId,
+    /// Total number of packets relayed (acknowledged)
+    pub packets_relayed: u64,
+    /// Total number of successful operations
+    pub operations: u64,
+    /// Time of last successful operation
+    pub last_success_at: Option<DateTime<Utc>>,
+}
+
+/// Used to persist counters of successful operations and uptime, and to query their daily aggregates
+pub struct StatsService {
+    db_pool: DbPool,
+}
+
+impl StatsService {
+    /// Creates a new instance of stats service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Records successful operation represented by given event in daily stats of its chain (events which do not
+    /// represent a successful operation on a chain are ignored)
+    pub async fn record(&self, event: &Event) -> Result<()> {
+        let (chain_id, packets_relayed) = match event {
+            Event::PacketAcknowledged { chain_id, .. } => (chain_id, 1),
+            Event::TokensMinted { chain_id, .. }
+            | Event::TokensBurnt { chain_id, .. }
+            | Event::SignerUpdated { chain_id, .. }
+            | Event::PacketTimedOut { chain_id, .. }
+            | Event::TendermintClientRefreshed { chain_id, .. }
+            | Event::ConnectionEstablished { chain_id, .. }
+            | Event::FeeAllowanceGranted { chain_id, .. }
+            | Event::FeeAllowanceRevoked { chain_id, .. }
+            | Event::TokensSent { chain_id, .. } => (chain_id, 0),
+            _ => return Ok(()),
+        };
+
+        stats::add_chain_daily_stats(&self.db_pool, chain_id, Utc::now(), packets_relayed, 1).await
+    }
+
+    /// Records uptime of solo machine between given times (split across days)
+    pub async fn record_uptime(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<()> {
+        let mut from = from;
+
+        while from < to {
+            let next_day = (from.date() + chrono::Duration::days(1)).and_hms(0, 0, 0);
+            let until = next_day.min(to);
+
+            let seconds = (until - from).num_seconds().max(0) as u64;
+
+            if seconds > 0 {
+                stats::add_uptime(&self.db_pool, from.date().naive_utc(), seconds).await?;
+            }
+
+            from = until;
+        }
+
+        Ok(())
+    }
+
+    /// Tracks uptime of current process by recording elapsed time at given interval until cancellation token is
+    /// cancelled (elapsed time since last record is also recorded on cancellation)
+    pub async fn track_uptime(&self, interval: Duration, token: CancellationToken) -> Result<()> {
+        let mut last_recorded_at = Utc::now();
+
+        loop {
+            let cancelled = tokio::select! {
+                _ = token.cancelled() => true,
+                _ = sleep(interval) => false,
+            };
+
+            // only whole seconds are recorded, remaining time is carried over to next record
+            let now = Utc::now();
+            let elapsed = chrono::Duration::seconds((now - last_recorded_at).num_seconds());
+            let recorded_until = last_recorded_at + elapsed;
+
+            self.record_uptime(last_recorded_at, recorded_until)
+                .await
+                .context("unable to record uptime")?;
+
+            last_recorded_at = recorded_until;
+
+            if cancelled {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Returns daily aggregates of last `days` days (including today), latest day first (optionally, counters of
+    /// operations are only for given chain)
+    pub async fn daily(&self, chain_id: Option<&ChainId>, days: u32) -> Result<Vec<DailyStats>> {
+        let today = Utc::today().naive_utc();
+        let since = today - chrono::Duration::days(i64::from(days.max(1)) - 1);
+
+        let mut daily_stats = BTreeMap::new();
+
+        for day in since.iter_days().take_while(|day| *day <= today) {
+            daily_stats.insert(
+                day,
+                DailyStats {
+                    day,
+                    uptime_seconds: 0,
+                    packets_relayed: 0,
+                    operations: 0,
+                },
+            );
+        }
+
+        for uptime in stats::get_uptime_daily_stats(&self.db_pool, since).await? {
+            if let Some(daily) = daily_stats.get_mut(&uptime.day) {
+                daily.uptime_seconds += uptime.uptime_seconds;
+            }
+        }
+
+        for chain_stats in stats::get_chain_daily_stats(&self.db_pool, chain_id, since).await? {
+            if let Some(daily) = daily_stats.get_mut(&chain_stats.day) {
+                daily.packets_relayed += chain_stats.packets_relayed;
+                daily.operations += chain_stats.operations;
+            }
+        }
+
+        Ok(daily_stats.into_values().rev().collect())
+    }
+
+    /// Returns stats of chains aggregated over all the recorded days (optionally, only for given chain)
+    pub async fn chains(&self, chain_id: Option<&ChainId>) -> Result<Vec<ChainStats>> {
+        let mut chain_stats: BTreeMap<String, ChainStats> = BTreeMap::new();
+
+        for daily in
+            stats::get_chain_daily_stats(&self.db_pool, chain_id, NaiveDate::from_ymd(1970, 1, 1))
+                .await?
+        {
+            let entry = chain_stats
+                .entry(daily.chain_id.to_string())
+                .or_insert_with(|| ChainStats {
+                    chain_id: daily.chain_id.clone(),
+                    packets_relayed: 0,
+                    operations: 0,
+                    last_success_at: None,
+                });
+
+            entry.packets_relayed += daily.packets_relayed;
+            entry.operations += daily.operations;
+            entry.last_success_at = entry.last_success_at.max(daily.last_success_at);
+        }
+
+        Ok(chain_stats.into_values().collect())
+    }
+}
@@ -0,0 +1,293 @@
+use std::{fmt, time::SystemTime};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Serializer};
+
+use crate::{
+    ibc::core::ics24_host::identifier::{ChainId, ClientId},
+    latest_schema_version,
+    model::{chain, ibc as ibc_handler, packet, pending_operation, Chain, HandshakeStep},
+    schema_version,
+    service::IntegrityService,
+    storage::is_state_encrypted,
+    DbPool, ToPublicKey,
+};
+
+/// Name of storage backend this build of solo machine uses
+#[cfg(not(feature = "postgres"))]
+const STORAGE_BACKEND: &str = "sqlite";
+
+/// Name of storage backend this build of solo machine uses
+#[cfg(feature = "postgres")]
+const STORAGE_BACKEND: &str = "postgres";
+
+/// Aggregated status of solo machine and all its chains (computed from stored state only, without querying chains)
+#[derive(Debug, Serialize)]
+pub struct StatusReport {
+    /// Version of solo machine
+    pub version: &'static str,
+    /// Status of storage backend
+    pub storage: StorageStatus,
+    /// Status of signer (`None` if no signer is provided)
+    pub signer: Option<SignerStatus>,
+    /// Integrity issues in stored state (signing is refused until they are repaired)
+    pub integrity_issues: Vec<String>,
+    /// Total number of queued operations whose outcome is not known yet (i.e. outbox size)
+    pub pending_operations: usize,
+    /// Status of all the chains
+    pub chains: Vec<ChainStatus>,
+    /// Time at which report was generated
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Status of storage backend
+#[derive(Debug, Serialize)]
+pub struct StorageStatus {
+    /// Storage backend (`sqlite` or `postgres`)
+    pub backend: &'static str,
+    /// `true` if stored state is encrypted at rest
+    pub encrypted: bool,
+    /// Version of latest migration applied on database
+    pub schema_version: Option<i64>,
+    /// Version of latest migration known to this build (database is out of date if it differs from
+    /// `schema_version`)
+    pub latest_schema_version: Option<i64>,
+}
+
+/// Status of signer backend
+#[derive(Debug, Serialize)]
+pub struct SignerStatus {
+    /// Public key of signer
+    pub public_key: Option<String>,
+    /// Account address of signer
+    pub account_address: Option<String>,
+    /// Error returned by signer backend (if any)
+    pub error: Option<String>,
+}
+
+/// Connection status of a chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConnectionStatus {
+    /// IBC connection is established
+    Connected,
+    /// Connection handshake is interrupted
+    HandshakeInterrupted,
+    /// No connection handshake is started
+    NotConnected,
+}
+
+impl fmt::Display for ConnectionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connected => write!(f, "connected"),
+            Self::HandshakeInterrupted => write!(f, "handshake-interrupted"),
+            Self::NotConnected => write!(f, "not-connected"),
+        }
+    }
+}
+
+/// Status of an IBC enabled chain
+#[derive(Debug, Serialize)]
+pub struct ChainStatus {
+    /// Chain ID of IBC enabled chain
+    #[serde(serialize_with = "serialize_chain_id")]
+    pub chain_id: ChainId,
+    /// Connection status
+    pub connection: ConnectionStatus,
+    /// Next step of interrupted connection handshake
+    pub handshake_step: Option<HandshakeStep>,
+    /// Sequence of solo machine
+    pub sequence: u32,
+    /// Packet sequence of solo machine
+    pub packet_sequence: u32,
+    /// Consensus timestamp of solo machine
+    pub consensus_timestamp: DateTime<Utc>,
+    /// Health of tendermint client of chain on solo machine
+    pub tendermint_client: Option<ClientStatus>,
+    /// State of connection on solo machine
+    pub connection_state: Option<String>,
+    /// State of channel on solo machine
+    pub channel_state: Option<String>,
+    /// Number of packets sent to solo machine which are yet to be acknowledged or timed out
+    pub pending_packets: usize,
+    /// Number of queued operations whose outcome is not known yet
+    pub pending_operations: usize,
+}
+
+/// Health of tendermint client on solo machine
+#[derive(Debug, Serialize)]
+pub struct ClientStatus {
+    /// Client ID
+    pub client_id: ClientId,
+    /// Latest height of client
+    pub latest_height: u64,
+    /// Time of latest consensus state of client
+    pub latest_timestamp: DateTime<Utc>,
+    /// Time after which client expires unless it is refreshed (latest timestamp plus trusting period)
+    pub expires_at: DateTime<Utc>,
+    /// `true` if client has expired
+    pub expired: bool,
+}
+
+/// Used to aggregate status of all the subsystems of solo machine into one report
+pub struct StatusService {
+    db_pool: DbPool,
+}
+
+impl StatusService {
+    /// Creates a new instance of status service
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Returns status report of solo machine (signer status is included only if a signer is given)
+    pub async fn status(&self, signer: Option<impl ToPublicKey>) -> Result<StatusReport> {
+        let storage = StorageStatus {
+            backend: STORAGE_BACKEND,
+            encrypted: is_state_encrypted(&self.db_pool).await?,
+            schema_version: schema_version(&self.db_pool).await?,
+            latest_schema_version: latest_schema_version(),
+        };
+
+        let signer = signer.map(|signer| match signer.to_public_key() {
+            Ok(public_key) => SignerStatus {
+                public_key: Some(public_key.encode()),
+                account_address: signer.to_account_address().ok(),
+                error: None,
+            },
+            Err(err) => SignerStatus {
+                public_key: None,
+                account_address: None,
+                error: Some(format!("{:#}", err)),
+            },
+        });
+
+        let integrity_issues = IntegrityService::new(self.db_pool.clone())
+            .verify()
+            .await?
+            .into_iter()
+            .map(|issue| issue.to_string())
+            .collect();
+
+        let mut chains = Vec::new();
+
+        for chain in chain::get_chains(&self.db_pool).await? {
+            chains.push(chain_status(&self.db_pool, chain).await?);
+        }
+
+        Ok(StatusReport {
+            version: env!("CARGO_PKG_VERSION"),
+            storage,
+            signer,
+            integrity_issues,
+            pending_operations: chains.iter().map(|chain| chain.pending_operations).sum(),
+            chains,
+            generated_at: Utc::now(),
+        })
+    }
+}
+
+/// Computes status of a chain from its state in database
+async fn chain_status(db_pool: &DbPool, chain: Chain) -> Result<ChainStatus> {
+    let pending_packets = packet::get_pending_packets(db_pool, &chain.id).await?.len();
+    let pending_operations = pending_operation::get_unresolved_operations(db_pool, &chain.id)
+        .await?
+        .len();
+
+    let mut status = ChainStatus {
+        chain_id: chain.id.clone(),
+        connection: ConnectionStatus::NotConnected,
+        handshake_step: None,
+        sequence: chain.sequence,
+        packet_sequence: chain.packet_sequence,
+        consensus_timestamp: chain.consensus_timestamp,
+        tendermint_client: None,
+        connection_state: None,
+        channel_state: None,
+        pending_packets,
+        pending_operations,
+    };
+
+    let connection_details = match (chain.connection_details, chain.handshake_progress) {
+        (Some(connection_details), _) => connection_details,
+        (None, Some(progress)) => {
+            status.connection = ConnectionStatus::HandshakeInterrupted;
+            status.handshake_step = Some(progress.step);
+            return Ok(status);
+        }
+        (None, None) => return Ok(status),
+    };
+
+    status.connection = ConnectionStatus::Connected;
+    status.tendermint_client = Some(
+        client_status(
+            db_pool,
+            &connection_details.tendermint_client_id,
+            chain.config.trusting_period,
+        )
+        .await?,
+    );
+    status.connection_state =
+        ibc_handler::get_connection(db_pool, &connection_details.tendermint_connection_id)
+            .await?
+            .map(|connection| format!("{:?}", connection.state()));
+    status.channel_state = ibc_handler::get_channel(
+        db_pool,
+        &chain.config.port_id,
+        &connection_details.tendermint_channel_id,
+    )
+    .await?
+    .map(|channel| format!("{:?}", channel.state()));
+
+    Ok(status)
+}
+
+/// Computes health of a tendermint client from its state in database
+pub(crate) async fn client_status(
+    db_pool: &DbPool,
+    client_id: &ClientId,
+    trusting_period: std::time::Duration,
+) -> Result<ClientStatus> {
+    let client_state = ibc_handler::get_tendermint_client_state(db_pool, client_id)
+        .await?
+        .ok_or_else(|| anyhow!("client state for {} not found", client_id))?;
+
+    let latest_height = client_state
+        .latest_height
+        .ok_or_else(|| anyhow!("latest height cannot be absent in client state"))?;
+
+    let consensus_state =
+        ibc_handler::get_tendermint_consensus_state(db_pool, client_id, &latest_height)
+            .await?
+            .ok_or_else(|| {
+                anyhow!(
+                    "consensus state for {} at height {} not found",
+                    client_id,
+                    latest_height.revision_height
+                )
+            })?;
+
+    let latest_timestamp: DateTime<Utc> = SystemTime::from(
+        consensus_state
+            .timestamp
+            .ok_or_else(|| anyhow!("timestamp cannot be absent in consensus state"))?,
+    )
+    .into();
+
+    let expires_at = latest_timestamp
+        + chrono::Duration::from_std(trusting_period).context("invalid trusting period")?;
+
+    Ok(ClientStatus {
+        client_id: client_id.clone(),
+        latest_height: latest_height.revision_height,
+        latest_timestamp,
+        expires_at,
+        expired: expires_at <= Utc::now(),
+    })
+}
+
+fn serialize_chain_id<S: Serializer>(chain_id: &ChainId, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(chain_id)
+}
@@ -0,0 +1,209 @@
+use std::fmt;
+
+use crate::broadcaster::ExecutionResult;
+
+/// Stage of transaction execution at which an error occurred
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxStage {
+    /// Transaction was rejected by mempool (`check_tx`)
+    CheckTx,
+    /// Transaction failed while executing in a block (`deliver_tx`)
+    DeliverTx,
+}
+
+impl fmt::Display for TxStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CheckTx => write!(f, "check_tx"),
+            Self::DeliverTx => write!(f, "deliver_tx"),
+        }
+    }
+}
+
+/// Known kinds of errors returned by cosmos SDK and ibc-go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxErrorKind {
+    /// Solo machine client on chain is frozen or expired
+    ClientNotActive,
+    /// Solo machine client does not exist on chain
+    ClientNotFound,
+    /// Proof (signature of solo machine) failed verification on chain
+    InvalidProof,
+    /// Connection does not exist on chain
+    ConnectionNotFound,
+    /// Channel does not exist on chain
+    ChannelNotFound,
+    /// Account sequence in transaction does not match account sequence on chain
+    SequenceMismatch,
+    /// Same transaction is already in mempool of chain (e.g. when a transaction is broadcast again)
+    AlreadyInMempool,
+    /// Account does not have enough funds (e.g. for paying fees)
+    InsufficientFunds,
+    /// Fee provided in transaction is lower than minimum gas price of chain
+    InsufficientFee,
+    /// Gas limit of transaction is too low
+    OutOfGas,
+    /// Signer is not authorized to perform the operation (or signature is invalid)
+    Unauthorized,
+    /// Unknown error
+    Unknown,
+}
+
+impl TxErrorKind {
+    /// Classifies an error using its codespace, code and log (ibc-go errors are matched using log because their codes
+    /// differ across versions)
+    pub fn classify(codespace: &str, code: u32, log: &str) -> Self {
+        let log = log.to_lowercase();
+
+        let matches = |patterns: &[&str]| patterns.iter().any(|pattern| log.contains(pattern));
+
+        if matches(&[
+            "client is not active",
+            "client state is not active",
+            "client is frozen",
+            "client state is frozen",
+            "client is expired",
+        ]) {
+            Self::ClientNotActive
+        } else if matches(&["client not found", "light client not found"]) {
+            Self::ClientNotFound
+        } else if matches(&[
+            "invalid proof",
+            "failed to verify",
+            "signature verification failed",
+            "proof verification failed",
+        ]) {
+            Self::InvalidProof
+        } else if matches(&["connection not found"]) {
+            Self::ConnectionNotFound
+        } else if matches(&["channel not found"]) {
+            Self::ChannelNotFound
+        } else if codespace == "sdk" {
+            match code {
+                4 => Self::Unauthorized,
+                5 => Self::InsufficientFunds,
+                11 => Self::OutOfGas,
+                13 => Self::InsufficientFee,
+                19 => Self::AlreadyInMempool,
+                32 => Self::SequenceMismatch,
+                _ => Self::Unknown,
+            }
+        } else if matches(&["account sequence mismatch", "incorrect account sequence"]) {
+            // `check_tx` responses of `broadcast_tx_sync` do not carry codespace
+            Self::SequenceMismatch
+        } else if matches(&["tx already in mempool", "tx already exists in cache"]) {
+            Self::AlreadyInMempool
+        } else if matches(&["insufficient fee"]) {
+            Self::InsufficientFee
+        } else if matches(&["insufficient funds"]) {
+            Self::InsufficientFunds
+        } else if matches(&["out of gas"]) {
+            Self::OutOfGas
+        } else if matches(&["unauthorized"]) {
+            Self::Unauthorized
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Returns an actionable hint for resolving the error
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::ClientNotActive => Some(
+                "solo machine client on chain is frozen or expired; it has to be recovered on chain (e.g. using a client \
+                 update governance proposal) before any further IBC operation",
+            ),
+            Self::ClientNotFound => Some(
+                "solo machine client does not exist on chain; run `ibc connect` (with `--force` to create a new client)",
+            ),
+            Self::InvalidProof => Some(
+                "proof of solo machine failed verification; check that sequence and signer of solo machine are in sync \
+                 with the client on chain (`solo-machine repair`, `ibc update-signer`)",
+            ),
+            Self::ConnectionNotFound => Some(
+                "connection does not exist on chain; run `ibc connect` (with `--force` to create a new connection)",
+            ),
+            Self::ChannelNotFound => Some(
+                "channel does not exist on chain; run `ibc connect` (with `--force` to create a new channel)",
+            ),
+            Self::SequenceMismatch => Some(
+                "account sequence is out of sync (another transaction may be pending from same account) and it did not \
+                 settle after automatic retries; retry after the pending transaction is committed",
+            ),
+            Self::AlreadyInMempool => Some(
+                "transaction is already in mempool of chain; it is resolved (`ibc resume`) once it is committed",
+            ),
+            Self::InsufficientFunds => Some("fund the signer (or fee payer) account on chain"),
+            Self::InsufficientFee => Some(
+                "increase fee amount of chain (`--fee-amount` when adding chain) to satisfy minimum gas price",
+            ),
+            Self::OutOfGas => {
+                Some("increase gas limit of chain (`--gas-limit` when adding chain)")
+            }
+            Self::Unauthorized => Some(
+                "check that signer's key (and address algorithm) matches the account on chain",
+            ),
+            Self::Unknown => None,
+        }
+    }
+}
+
+/// Error returned by chain when executing a transaction
+#[derive(Debug, Clone)]
+pub struct TxError {
+    /// Stage at which transaction failed
+    pub stage: TxStage,
+    /// Codespace of error
+    pub codespace: String,
+    /// Error code
+    pub code: u32,
+    /// Raw log returned by chain
+    pub log: String,
+    /// Kind of error
+    pub kind: TxErrorKind,
+}
+
+impl TxError {
+    /// Returns error if transaction result contains an error code
+    pub(crate) fn from_result(stage: TxStage, result: &ExecutionResult) -> Option<Self> {
+        if result.is_ok() {
+            return None;
+        }
+
+        let codespace = result.codespace.clone();
+        let code = result.code;
+        let log = result.log.clone();
+        let kind = TxErrorKind::classify(&codespace, code, &log);
+
+        Some(Self {
+            stage,
+            codespace,
+            code,
+            log,
+            kind,
+        })
+    }
+
+    /// Returns an actionable hint for resolving the error
+    pub fn hint(&self) -> Option<&'static str> {
+        self.kind.hint()
+    }
+}
+
+impl fmt::Display for TxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} response contains error code {} (codespace: {}): {}",
+            self.stage, self.code, self.codespace, self.log
+        )?;
+
+        if let Some(hint) = self.hint() {
+            write!(f, " [hint: {}]", hint)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for TxError {}
@@ -0,0 +1,215 @@
+//! Human-readable summaries of messages given to signer (message types, token transfers, fee and consumed sequence),
+//! e.g. for confirming signatures interactively
+use std::fmt;
+
+use anyhow::{Context, Result};
+use cosmos_sdk_proto::{
+    cosmos::{
+        bank::v1beta1::MsgSend,
+        base::v1beta1::Coin,
+        tx::v1beta1::{AuthInfo, SignDoc, TxBody},
+    },
+    ibc::{applications::transfer::v1::MsgTransfer, core::channel::v1::MsgRecvPacket},
+};
+use prost::Message as _;
+use serde_json::Value;
+
+use crate::{
+    ibc_version::SoloMachineRevision, signer::Message, transaction_builder::decode_sign_bytes,
+};
+
+const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+const MSG_TRANSFER_TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
+const MSG_RECV_PACKET_TYPE_URL: &str = "/ibc.core.channel.v1.MsgRecvPacket";
+
+/// Summary of a message given to signer
+#[derive(Debug, Default)]
+pub struct SignSummary {
+    /// Type of message (e.g. `sign-bytes`, `sign-doc`)
+    pub message_type: &'static str,
+    /// Chain ID of signed transaction (`None` for `SignBytes` which do not contain chain ID)
+    pub chain_id: Option<String>,
+    /// Type URLs (or amino names) of messages in signed transaction, or type of data signed in `SignBytes`
+    pub messages: Vec<String>,
+    /// Token transfers in signed transaction (e.g. `1000 gld to cosmos1...`)
+    pub transfers: Vec<String>,
+    /// Fee of signed transaction (e.g. `2000uatom (gas limit: 200000)`)
+    pub fee: Option<String>,
+    /// Sequence consumed by signature (of solo machine client for `SignBytes` and of signing account for transactions)
+    pub sequence: Option<u64>,
+}
+
+impl SignSummary {
+    /// Creates summary of given message (details which cannot be decoded are omitted)
+    pub fn new(message: Message<'_>) -> Self {
+        let mut summary = Self {
+            message_type: message.message_type(),
+            ..Default::default()
+        };
+
+        let _ = match message {
+            Message::SignBytes(bytes) => summary.decode_sign_bytes(bytes),
+            Message::SignDoc(bytes) => summary.decode_sign_doc(bytes),
+            Message::AminoJsonSignDoc(bytes) => summary.decode_amino_json_sign_doc(bytes),
+            Message::Eip712(_) => {
+                // EIP-712 sign bytes only contain hashes of typed data
+                summary.messages.push("EIP-712 typed data".to_string());
+                Ok(())
+            }
+        };
+
+        summary
+    }
+
+    fn decode_sign_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let preview = decode_sign_bytes(SoloMachineRevision::V2, bytes.to_vec())
+            .or_else(|_| decode_sign_bytes(SoloMachineRevision::V3, bytes.to_vec()))?;
+
+        self.messages.push(preview.data_type);
+        self.sequence = Some(preview.sequence);
+
+        Ok(())
+    }
+
+    fn decode_sign_doc(&mut self, bytes: &[u8]) -> Result<()> {
+        let sign_doc = SignDoc::decode(bytes).context("invalid sign doc")?;
+        let tx_body =
+            TxBody::decode(sign_doc.body_bytes.as_slice()).context("invalid transaction body")?;
+        let auth_info =
+            AuthInfo::decode(sign_doc.auth_info_bytes.as_slice()).context("invalid auth info")?;
+
+        self.chain_id = Some(sign_doc.chain_id);
+        self.sequence = auth_info
+            .signer_infos
+            .first()
+            .map(|signer_info| signer_info.sequence);
+        self.fee = auth_info.fee.map(|fee| {
+            format!(
+                "{} (gas limit: {})",
+                format_coins(&fee.amount),
+                fee.gas_limit
+            )
+        });
+
+        for message in tx_body.messages {
+            match message.type_url.as_str() {
+                MSG_SEND_TYPE_URL => {
+                    let msg = MsgSend::decode(message.value.as_slice())?;
+                    self.transfers.push(format!(
+                        "{} to {}",
+                        format_coins(&msg.amount),
+                        msg.to_address
+                    ));
+                }
+                MSG_TRANSFER_TYPE_URL => {
+                    let msg = MsgTransfer::decode(message.value.as_slice())?;
+                    self.transfers.push(format!(
+                        "{} to {} over {}",
+                        msg.token
+                            .as_ref()
+                            .map(|token| format_coins(std::slice::from_ref(token)))
+                            .unwrap_or_default(),
+                        msg.receiver,
+                        msg.source_channel
+                    ));
+                }
+                MSG_RECV_PACKET_TYPE_URL => {
+                    let msg = MsgRecvPacket::decode(message.value.as_slice())?;
+
+                    if let Some(packet_data) = msg
+                        .packet
+                        .and_then(|packet| serde_json::from_slice::<Value>(&packet.data).ok())
+                    {
+                        if let (Some(denom), Some(amount), Some(receiver)) = (
+                            packet_data["denom"].as_str(),
+                            packet_data["amount"].as_str(),
+                            packet_data["receiver"].as_str(),
+                        ) {
+                            self.transfers
+                                .push(format!("{}{} to {}", amount, denom, receiver));
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            self.messages.push(message.type_url);
+        }
+
+        Ok(())
+    }
+
+    fn decode_amino_json_sign_doc(&mut self, bytes: &[u8]) -> Result<()> {
+        let sign_doc: Value = serde_json::from_slice(bytes).context("invalid amino sign doc")?;
+
+        self.chain_id = sign_doc["chain_id"].as_str().map(ToString::to_string);
+        self.sequence = sign_doc["sequence"]
+            .as_str()
+            .and_then(|sequence| sequence.parse().ok());
+        self.fee = Some(format!(
+            "{} (gas limit: {})",
+            sign_doc["fee"]["amount"]
+                .as_array()
+                .map(|coins| {
+                    coins
+                        .iter()
+                        .map(|coin| {
+                            format!(
+                                "{}{}",
+                                coin["amount"].as_str().unwrap_or_default(),
+                                coin["denom"].as_str().unwrap_or_default()
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join(",")
+                })
+                .unwrap_or_default(),
+            sign_doc["fee"]["gas"].as_str().unwrap_or_default()
+        ));
+
+        if let Some(msgs) = sign_doc["msgs"].as_array() {
+            self.messages.extend(
+                msgs.iter()
+                    .filter_map(|msg| msg["type"].as_str())
+                    .map(ToString::to_string),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for SignSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Message type: {}", self.message_type)?;
+
+        if let Some(ref chain_id) = self.chain_id {
+            writeln!(f, "Chain ID: {}", chain_id)?;
+        }
+
+        if !self.messages.is_empty() {
+            writeln!(f, "Messages: {}", self.messages.join(", "))?;
+        }
+
+        for transfer in self.transfers.iter() {
+            writeln!(f, "Transfer: {}", transfer)?;
+        }
+
+        if let Some(ref fee) = self.fee {
+            writeln!(f, "Fee: {}", fee)?;
+        }
+
+        match self.sequence {
+            Some(sequence) => write!(f, "Sequence: {}", sequence),
+            None => write!(f, "Sequence: unknown"),
+        }
+    }
+}
+
+fn format_coins(coins: &[Coin]) -> String {
+    coins
+        .iter()
+        .map(|coin| format!("{}{}", coin.amount, coin.denom))
+        .collect::<Vec<_>>()
+        .join(",")
+}
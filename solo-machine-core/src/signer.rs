@@ -1,21 +1,59 @@
 //! Utilities for signing transactions
-use std::{fmt, str::FromStr, sync::Arc};
+use std::{
+    fmt,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
-use anyhow::{anyhow, Error, Result};
+use anyhow::{anyhow, bail, Context, Error, Result};
 use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{SignDoc, TxRaw};
+use futures::future::try_join_all;
+use prost::Message as _;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::cosmos::crypto::PublicKey;
+use crate::{
+    cosmos::crypto::PublicKey,
+    ibc::core::ics24_host::identifier::ChainId,
+    ibc_version::SoloMachineRevision,
+    model::{signature_log, SignatureRecord},
+    policy::{violation, SigningPolicy},
+    proto::json::{sign_bytes_to_proto_json, tx_raw_to_proto_json, ProtoJson},
+    DbPool,
+};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 /// Supported algorithms for address generation
 pub enum AddressAlgo {
     /// Secp256k1 (tendermint)
+    #[default]
     Secp256k1,
     #[cfg(feature = "ethermint")]
     /// EthSecp256k1 (ethermint)
     EthSecp256k1,
 }
 
+impl AddressAlgo {
+    /// Returns BIP-44 coin type of addresses generated using this algorithm (`118` for cosmos and `60` for ethereum
+    /// style addresses)
+    pub fn coin_type(&self) -> u32 {
+        match self {
+            Self::Secp256k1 => 118,
+            #[cfg(feature = "ethermint")]
+            Self::EthSecp256k1 => 60,
+        }
+    }
+
+    /// Returns default HD path (`m/44'/{coin_type}'/0'/0/0`) for this algorithm
+    pub fn default_hd_path(&self) -> String {
+        format!("m/44'/{}'/0'/0/0", self.coin_type())
+    }
+}
+
 impl fmt::Display for AddressAlgo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -33,19 +71,29 @@ impl FromStr for AddressAlgo {
         match s {
             "secp256k1" => Ok(Self::Secp256k1),
             #[cfg(feature = "ethermint")]
-            "eth-secp256k1" => Ok(Self::EthSecp256k1),
+            "eth-secp256k1" => {
+                if cfg!(feature = "fips") {
+                    bail!("`eth-secp256k1` cannot be used in `fips` builds (Keccak-256 is not an approved hash function)");
+                }
+
+                Ok(Self::EthSecp256k1)
+            }
             _ => Err(anyhow!("invalid address generation algorithm: {}", s)),
         }
     }
 }
 
 /// Type of message given to a signer
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Message<'a> {
     /// [cosmos_sdk_proto::ibc::lightclients::solomachine::v1::SignBytes]
     SignBytes(&'a [u8]),
     /// [cosmos_sdk_proto::cosmos::tx::v1beta1::SignDoc]
     SignDoc(&'a [u8]),
+    /// Canonical amino JSON encoded `StdSignDoc` (used in `SIGN_MODE_LEGACY_AMINO_JSON`)
+    AminoJsonSignDoc(&'a [u8]),
+    /// EIP-712 encoded typed data (`0x19 0x01 || domainSeparator || hashStruct(message)`) of amino JSON sign doc
+    Eip712(&'a [u8]),
 }
 
 impl<'a> Message<'a> {
@@ -54,6 +102,8 @@ impl<'a> Message<'a> {
         match self {
             Self::SignBytes(_) => "sign-bytes",
             Self::SignDoc(_) => "sign-doc",
+            Self::AminoJsonSignDoc(_) => "amino-json-sign-doc",
+            Self::Eip712(_) => "eip712",
         }
     }
 }
@@ -63,6 +113,8 @@ impl AsRef<[u8]> for Message<'_> {
         match self {
             Self::SignBytes(bytes) => bytes,
             Self::SignDoc(bytes) => bytes,
+            Self::AminoJsonSignDoc(bytes) => bytes,
+            Self::Eip712(bytes) => bytes,
         }
     }
 }
@@ -79,7 +131,7 @@ pub trait ToPublicKey {
     fn to_account_address(&self) -> Result<String>;
 }
 
-impl<T: ToPublicKey> ToPublicKey for &T {
+impl<T: ToPublicKey + ?Sized> ToPublicKey for &T {
     fn to_public_key(&self) -> Result<PublicKey> {
         (*self).to_public_key()
     }
@@ -112,13 +164,57 @@ impl<T: ToPublicKey + ?Sized> ToPublicKey for Arc<T> {
 pub trait Signer: ToPublicKey + Send + Sync {
     /// Signs the given message
     async fn sign(&self, request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>>;
+
+    /// Signs all the messages required by a single operation (e.g. the three proofs of `MsgConnectionOpenAck`) and
+    /// returns their signatures in the same order. By default, all the messages are signed concurrently (so that
+    /// latency of remote signers is paid once per batch instead of once per message); signers backed by slow external
+    /// devices (e.g. HSMs) which cannot sign concurrently should override this to submit the whole batch in one round
+    /// trip.
+    async fn sign_batch(
+        &self,
+        request_id: Option<&str>,
+        messages: &[Message<'_>],
+    ) -> Result<Vec<Vec<u8>>> {
+        try_join_all(
+            messages
+                .iter()
+                .map(|message| self.sign(request_id, *message)),
+        )
+        .await
+    }
+
+    /// Returns the signer of account paying transaction fees (`None` if fees are paid by current signer)
+    fn fee_payer(&self) -> Option<&dyn Signer> {
+        None
+    }
+
+    /// Returns the signing policy evaluated before signing (`None` if signing is not restricted by a policy)
+    fn signing_policy(&self) -> Option<&SigningPolicy> {
+        None
+    }
 }
 
 #[async_trait]
-impl<T: Signer> Signer for &T {
+impl<T: Signer + ?Sized> Signer for &T {
     async fn sign(&self, request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
         (*self).sign(request_id, message).await
     }
+
+    async fn sign_batch(
+        &self,
+        request_id: Option<&str>,
+        messages: &[Message<'_>],
+    ) -> Result<Vec<Vec<u8>>> {
+        (*self).sign_batch(request_id, messages).await
+    }
+
+    fn fee_payer(&self) -> Option<&dyn Signer> {
+        (*self).fee_payer()
+    }
+
+    fn signing_policy(&self) -> Option<&SigningPolicy> {
+        (*self).signing_policy()
+    }
 }
 
 #[async_trait]
@@ -126,10 +222,644 @@ impl<T: Signer + ?Sized> Signer for Arc<T> {
     async fn sign(&self, request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
         (**self).sign(request_id, message).await
     }
+
+    async fn sign_batch(
+        &self,
+        request_id: Option<&str>,
+        messages: &[Message<'_>],
+    ) -> Result<Vec<Vec<u8>>> {
+        (**self).sign_batch(request_id, messages).await
+    }
+
+    fn fee_payer(&self) -> Option<&dyn Signer> {
+        (**self).fee_payer()
+    }
+
+    fn signing_policy(&self) -> Option<&SigningPolicy> {
+        (**self).signing_policy()
+    }
+}
+
+/// Signer which delegates payment of transaction fees to a separate fee payer account
+pub struct FeePayerSigner {
+    signer: Arc<dyn Signer>,
+    fee_payer: Arc<dyn Signer>,
+}
+
+impl FeePayerSigner {
+    /// Creates a new signer with given fee payer
+    pub fn new(signer: Arc<dyn Signer>, fee_payer: Arc<dyn Signer>) -> Self {
+        Self { signer, fee_payer }
+    }
+}
+
+impl ToPublicKey for FeePayerSigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        self.signer.to_public_key()
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        self.signer.get_account_prefix()
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.signer.to_account_address()
+    }
+}
+
+#[async_trait]
+impl Signer for FeePayerSigner {
+    async fn sign(&self, request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
+        self.signer.sign(request_id, message).await
+    }
+
+    async fn sign_batch(
+        &self,
+        request_id: Option<&str>,
+        messages: &[Message<'_>],
+    ) -> Result<Vec<Vec<u8>>> {
+        self.signer.sign_batch(request_id, messages).await
+    }
+
+    fn fee_payer(&self) -> Option<&dyn Signer> {
+        Some(&*self.fee_payer)
+    }
+
+    fn signing_policy(&self) -> Option<&SigningPolicy> {
+        self.signer.signing_policy()
+    }
+}
+
+/// Signer which refuses to sign anything (e.g. when integrity verification of stored state fails on startup). Public
+/// key and fee payer of wrapped signer are still available for read-only operations.
+pub struct LockedSigner {
+    signer: Arc<dyn Signer>,
+    reason: String,
+}
+
+impl LockedSigner {
+    /// Creates a new locked signer which fails all signing requests with given reason
+    pub fn new(signer: Arc<dyn Signer>, reason: String) -> Self {
+        Self { signer, reason }
+    }
+}
+
+impl ToPublicKey for LockedSigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        self.signer.to_public_key()
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        self.signer.get_account_prefix()
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.signer.to_account_address()
+    }
+}
+
+#[async_trait]
+impl Signer for LockedSigner {
+    async fn sign(&self, _request_id: Option<&str>, _message: Message<'_>) -> Result<Vec<u8>> {
+        bail!("refusing to sign: {}", self.reason)
+    }
+
+    async fn sign_batch(
+        &self,
+        _request_id: Option<&str>,
+        _messages: &[Message<'_>],
+    ) -> Result<Vec<Vec<u8>>> {
+        bail!("refusing to sign: {}", self.reason)
+    }
+
+    fn fee_payer(&self) -> Option<&dyn Signer> {
+        self.signer.fee_payer()
+    }
+
+    fn signing_policy(&self) -> Option<&SigningPolicy> {
+        self.signer.signing_policy()
+    }
+}
+
+/// Signer which evaluates a signing policy before signing any message (messages violating the policy are never given
+/// to wrapped signer)
+pub struct PolicySigner {
+    signer: Arc<dyn Signer>,
+    policy: SigningPolicy,
+}
+
+impl PolicySigner {
+    /// Creates a new signer restricted by given signing policy
+    pub fn new(signer: Arc<dyn Signer>, policy: SigningPolicy) -> Self {
+        Self { signer, policy }
+    }
+}
+
+impl ToPublicKey for PolicySigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        self.signer.to_public_key()
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        self.signer.get_account_prefix()
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.signer.to_account_address()
+    }
+}
+
+#[async_trait]
+impl Signer for PolicySigner {
+    async fn sign(&self, request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
+        self.policy.evaluate(message)?;
+        self.signer.sign(request_id, message).await
+    }
+
+    async fn sign_batch(
+        &self,
+        request_id: Option<&str>,
+        messages: &[Message<'_>],
+    ) -> Result<Vec<Vec<u8>>> {
+        for message in messages {
+            self.policy.evaluate(*message)?;
+        }
+
+        self.signer.sign_batch(request_id, messages).await
+    }
+
+    fn fee_payer(&self) -> Option<&dyn Signer> {
+        self.signer.fee_payer()
+    }
+
+    fn signing_policy(&self) -> Option<&SigningPolicy> {
+        Some(&self.policy)
+    }
+}
+
+/// Signer which records every signed message (its hash, purpose, sequence and resulting signature) in append-only,
+/// hash-chained signature log, so that operators can prove exactly what the key signed and when. A signature which
+/// cannot be recorded is never returned.
+pub struct AuditedSigner {
+    signer: Arc<dyn Signer>,
+    db_pool: DbPool,
+    /// Serializes appends to signature log (concurrently signed messages would otherwise fork its hash chain)
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl AuditedSigner {
+    /// Creates a new signer recording signatures of wrapped signer in signature log of given database
+    pub fn new(signer: Arc<dyn Signer>, db_pool: DbPool) -> Self {
+        Self {
+            signer,
+            db_pool,
+            lock: Default::default(),
+        }
+    }
+
+    async fn record(&self, records: &[SignatureRecord]) -> Result<()> {
+        let _lock = self.lock.lock().await;
+
+        let mut transaction = self
+            .db_pool
+            .begin()
+            .await
+            .context("unable to begin database transaction")?;
+
+        for record in records {
+            signature_log::add_signature_log_entry(&mut transaction, record).await?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .context("unable to commit transaction for adding signature log entries")
+    }
+}
+
+impl ToPublicKey for AuditedSigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        self.signer.to_public_key()
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        self.signer.get_account_prefix()
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.signer.to_account_address()
+    }
+}
+
+#[async_trait]
+impl Signer for AuditedSigner {
+    async fn sign(&self, request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
+        let signature = self.signer.sign(request_id, message).await?;
+
+        self.record(&[SignatureRecord::new(message, request_id, &signature)])
+            .await
+            .context("unable to record signature in signature log")?;
+
+        Ok(signature)
+    }
+
+    async fn sign_batch(
+        &self,
+        request_id: Option<&str>,
+        messages: &[Message<'_>],
+    ) -> Result<Vec<Vec<u8>>> {
+        let signatures = self.signer.sign_batch(request_id, messages).await?;
+
+        let records = messages
+            .iter()
+            .zip(signatures.iter())
+            .map(|(message, signature)| SignatureRecord::new(*message, request_id, signature))
+            .collect::<Vec<_>>();
+
+        self.record(&records)
+            .await
+            .context("unable to record signatures in signature log")?;
+
+        Ok(signatures)
+    }
+
+    fn fee_payer(&self) -> Option<&dyn Signer> {
+        self.signer.fee_payer()
+    }
+
+    fn signing_policy(&self) -> Option<&SigningPolicy> {
+        self.signer.signing_policy()
+    }
+}
+
+/// Signer which caps the number of signatures within sliding time windows (e.g. per minute and per hour), so that a
+/// compromised host or API credential can only obtain a bounded number of signatures. Signatures are counted in
+/// signature log, so, wrapped signer should record its signatures (see [`AuditedSigner`]) and limits hold across
+/// restarts and processes sharing the database.
+pub struct RateLimitedSigner {
+    signer: Arc<dyn Signer>,
+    db_pool: DbPool,
+    limits: Vec<(u32, Duration)>,
+    /// Serializes signing, so that concurrent signatures are counted only after previous ones are recorded
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl RateLimitedSigner {
+    /// Creates a new signer allowing at most given number of signatures within each given window
+    pub fn new(signer: Arc<dyn Signer>, db_pool: DbPool, limits: Vec<(u32, Duration)>) -> Self {
+        Self {
+            signer,
+            db_pool,
+            limits,
+            lock: Default::default(),
+        }
+    }
+
+    /// Ensures that given number of new signatures does not exceed any limit
+    async fn check(&self, signatures: usize) -> Result<()> {
+        // timestamps of signature log entries do not have sub-second precision
+        let now = Utc.timestamp(Utc::now().timestamp(), 0);
+
+        for (max_signatures, window) in self.limits.iter() {
+            let since = now - chrono::Duration::from_std(*window)?;
+            let signed = signature_log::count_signature_log_entries(&self.db_pool, since).await?;
+
+            if signed + signatures as u64 > u64::from(*max_signatures) {
+                return Err(violation(format!(
+                    "signature rate limit of {} per {} exceeded",
+                    max_signatures,
+                    humantime::format_duration(*window)
+                ))
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ToPublicKey for RateLimitedSigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        self.signer.to_public_key()
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        self.signer.get_account_prefix()
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.signer.to_account_address()
+    }
+}
+
+#[async_trait]
+impl Signer for RateLimitedSigner {
+    async fn sign(&self, request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
+        let _lock = self.lock.lock().await;
+
+        self.check(1).await?;
+        self.signer.sign(request_id, message).await
+    }
+
+    async fn sign_batch(
+        &self,
+        request_id: Option<&str>,
+        messages: &[Message<'_>],
+    ) -> Result<Vec<Vec<u8>>> {
+        let _lock = self.lock.lock().await;
+
+        self.check(messages.len()).await?;
+        self.signer.sign_batch(request_id, messages).await
+    }
+
+    fn fee_payer(&self) -> Option<&dyn Signer> {
+        self.signer.fee_payer()
+    }
+
+    fn signing_policy(&self) -> Option<&SigningPolicy> {
+        self.signer.signing_policy()
+    }
+}
+
+/// Decoded `SignBytes` which will be signed next by solo machine for a pending operation
+#[derive(Debug, Serialize)]
+pub struct SignBytesPreview {
+    /// Sequence of solo machine client
+    pub sequence: u64,
+    /// Consensus timestamp of solo machine client
+    pub timestamp: u64,
+    /// Diversifier of solo machine client
+    pub diversifier: String,
+    /// Type of signed data (e.g. `Header`)
+    pub data_type: String,
+    /// Decoded signed data
+    pub data: Value,
+    /// Exact protobuf encoded `SignBytes` given to signer
+    #[serde(with = "hex::serde")]
+    pub sign_bytes: Vec<u8>,
+}
+
+/// Signer which never signs anything. It captures the first `SignBytes` message given to it and aborts the operation
+/// (used to preview the `SignBytes` of an operation without involving the actual signer backend).
+pub(crate) struct PreviewSigner<T> {
+    signer: T,
+    sign_bytes: Mutex<Option<Vec<u8>>>,
+}
+
+impl<T> PreviewSigner<T> {
+    pub(crate) fn new(signer: T) -> Self {
+        Self {
+            signer,
+            sign_bytes: Mutex::new(None),
+        }
+    }
+
+    /// Returns captured `SignBytes` (if any)
+    pub(crate) fn into_sign_bytes(self) -> Option<Vec<u8>> {
+        self.sign_bytes
+            .into_inner()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<T: ToPublicKey> ToPublicKey for PreviewSigner<T> {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        self.signer.to_public_key()
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        self.signer.get_account_prefix()
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.signer.to_account_address()
+    }
+}
+
+#[async_trait]
+impl<T: ToPublicKey + Send + Sync> Signer for PreviewSigner<T> {
+    async fn sign(&self, _request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
+        if let Message::SignBytes(bytes) = message {
+            let mut sign_bytes = self.sign_bytes.lock().unwrap_or_else(|e| e.into_inner());
+
+            if sign_bytes.is_none() {
+                *sign_bytes = Some(bytes.to_vec());
+            }
+        }
+
+        bail!("signing is disabled while previewing sign bytes")
+    }
+}
+
+/// Message which would be signed by an operation run in dry-run mode
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunMessage {
+    /// Signer of message (`signer` or `fee-payer`)
+    pub signer: &'static str,
+    /// Type of message (e.g. `sign-bytes`, `sign-doc`)
+    pub message_type: &'static str,
+    /// Exact bytes which would be given to signer
+    #[serde(with = "hex::serde")]
+    pub bytes: Vec<u8>,
+    /// Decoded message (proto3 JSON of `SignBytes` or `SignDoc`, or amino JSON sign doc), if it can be decoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<Value>,
+}
+
+/// Transaction built by an operation in dry-run mode (nothing is signed, persisted or broadcasted)
+#[derive(Debug, Serialize)]
+pub struct DryRunReport {
+    /// Chain ID of IBC enabled chain
+    pub chain_id: String,
+    /// Messages of transaction (proto3 JSON, proofs of solo machine contain placeholder signatures)
+    pub messages: Value,
+    /// Fee of transaction (proto3 JSON)
+    pub fee: Value,
+    /// Memo of transaction
+    pub memo: String,
+    /// Messages which would be signed, in the order in which they would be signed
+    pub sign_bytes: Vec<DryRunMessage>,
+}
+
+impl DryRunReport {
+    /// Creates a report of given transaction built in dry-run mode along with the messages given to signer
+    pub(crate) fn new(
+        chain_id: &ChainId,
+        tx_raw: &TxRaw,
+        sign_bytes: Vec<DryRunMessage>,
+    ) -> Result<Self> {
+        let tx = tx_raw_to_proto_json(tx_raw)?;
+
+        Ok(Self {
+            chain_id: chain_id.to_string(),
+            messages: tx["body"]["messages"].clone(),
+            fee: tx["auth_info"]["fee"].clone(),
+            memo: tx["body"]["memo"].as_str().unwrap_or_default().to_string(),
+            sign_bytes,
+        })
+    }
+}
+
+/// Placeholder signature returned by signers in dry-run mode (length of a secp256k1 signature)
+const DRY_RUN_SIGNATURE: [u8; 64] = [0; 64];
+
+/// Signer which never signs anything. It records every message given to it (and to its fee payer) and returns a
+/// placeholder signature, so that an operation builds its complete transaction without involving the actual signer
+/// backend.
+pub(crate) struct DryRunSigner<T> {
+    signer: T,
+    fee_payer: Option<DryRunFeePayer>,
+    messages: Arc<Mutex<Vec<DryRunMessage>>>,
+}
+
+impl<T: Signer> DryRunSigner<T> {
+    pub(crate) fn new(signer: T) -> Result<Self> {
+        let messages: Arc<Mutex<Vec<DryRunMessage>>> = Default::default();
+
+        let fee_payer = match signer.fee_payer() {
+            Some(fee_payer) => Some(DryRunFeePayer {
+                public_key: fee_payer.to_public_key()?,
+                account_prefix: fee_payer.get_account_prefix().to_string(),
+                account_address: fee_payer.to_account_address()?,
+                messages: messages.clone(),
+            }),
+            None => None,
+        };
+
+        Ok(Self {
+            signer,
+            fee_payer,
+            messages,
+        })
+    }
+
+    /// Returns all the recorded messages
+    pub(crate) fn messages(&self) -> Vec<DryRunMessage> {
+        self.messages
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+fn record_dry_run_message(
+    messages: &Mutex<Vec<DryRunMessage>>,
+    signer: &'static str,
+    message: Message<'_>,
+) -> Vec<u8> {
+    messages
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(DryRunMessage {
+            signer,
+            message_type: message.message_type(),
+            bytes: message.as_ref().to_vec(),
+            decoded: decode_dry_run_message(message),
+        });
+
+    DRY_RUN_SIGNATURE.to_vec()
+}
+
+/// Decodes a message given to signer in dry-run mode (revision of solo machine client types is not known to signer,
+/// so, `SignBytes` are decoded using the revision they can be decoded with)
+fn decode_dry_run_message(message: Message<'_>) -> Option<Value> {
+    match message {
+        Message::SignBytes(bytes) => sign_bytes_to_proto_json(SoloMachineRevision::V2, bytes)
+            .or_else(|_| sign_bytes_to_proto_json(SoloMachineRevision::V3, bytes))
+            .ok(),
+        Message::SignDoc(bytes) => SignDoc::decode(bytes)
+            .ok()
+            .and_then(|sign_doc| sign_doc.to_proto_json().ok()),
+        Message::AminoJsonSignDoc(bytes) => serde_json::from_slice(bytes).ok(),
+        Message::Eip712(_) => None,
+    }
+}
+
+impl<T: ToPublicKey> ToPublicKey for DryRunSigner<T> {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        self.signer.to_public_key()
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        self.signer.get_account_prefix()
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.signer.to_account_address()
+    }
+}
+
+#[async_trait]
+impl<T: Signer> Signer for DryRunSigner<T> {
+    async fn sign(&self, _request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
+        Ok(record_dry_run_message(&self.messages, "signer", message))
+    }
+
+    async fn sign_batch(
+        &self,
+        _request_id: Option<&str>,
+        messages: &[Message<'_>],
+    ) -> Result<Vec<Vec<u8>>> {
+        Ok(messages
+            .iter()
+            .map(|message| record_dry_run_message(&self.messages, "signer", *message))
+            .collect())
+    }
+
+    fn fee_payer(&self) -> Option<&dyn Signer> {
+        self.fee_payer
+            .as_ref()
+            .map(|fee_payer| fee_payer as &dyn Signer)
+    }
+
+    fn signing_policy(&self) -> Option<&SigningPolicy> {
+        self.signer.signing_policy()
+    }
+}
+
+/// Fee payer of [`DryRunSigner`] (records messages given to it instead of signing them)
+struct DryRunFeePayer {
+    public_key: PublicKey,
+    account_prefix: String,
+    account_address: String,
+    messages: Arc<Mutex<Vec<DryRunMessage>>>,
+}
+
+impl ToPublicKey for DryRunFeePayer {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        Ok(self.public_key.clone())
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        &self.account_prefix
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        Ok(self.account_address.clone())
+    }
+}
+
+#[async_trait]
+impl Signer for DryRunFeePayer {
+    async fn sign(&self, _request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
+        Ok(record_dry_run_message(&self.messages, "fee-payer", message))
+    }
 }
 
 /// Trait to register a signer
 pub trait SignerRegistrar {
     /// Registers a new signer
     fn register(&mut self, signer: Arc<dyn Signer>);
+
+    /// Registers a named signer (e.g. one of the multiple accounts derived from same mnemonic) which can be selected
+    /// by its name instead of the default signer. Registrars which do not support named signers ignore them.
+    fn register_named(&mut self, name: &str, signer: Arc<dyn Signer>) {
+        let _ = (name, signer);
+    }
+
+    /// Returns value of given setting of signer (e.g. `SOLO_MNEMONIC`). Registrars of signers with explicitly provided
+    /// settings (e.g. signers of tenants) only return those settings, others read environment variables of process.
+    fn setting(&self, name: &str) -> Option<String> {
+        std::env::var(name).ok()
+    }
 }
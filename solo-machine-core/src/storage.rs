@@ -0,0 +1,10 @@
+//! Storage of state persisted by solo machine (caching and encryption at rest)
+pub(crate) mod cache;
+pub(crate) mod encryption;
+
+pub use self::{
+    cache::set_ibc_cache_ttl,
+    encryption::{
+        disable_state_encryption, enable_state_encryption, is_state_encrypted, unlock_state,
+    },
+};
@@ -0,0 +1,90 @@
+//! In-process cache of decoded IBC data (client states, consensus states, connections and channels) so that repeated
+//! proof generations (e.g. during a connection handshake) do not re-read database and re-decode protobuf bytes
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    sync::{Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
+/// Default time for which decoded IBC data is cached
+const DEFAULT_IBC_CACHE_TTL: Duration = Duration::from_secs(30);
+
+static CACHE_TTL: RwLock<Duration> = RwLock::new(DEFAULT_IBC_CACHE_TTL);
+
+/// Decoded IBC data indexed by their ICS-24 paths
+static CACHE: Mutex<BTreeMap<String, CacheEntry>> = Mutex::new(BTreeMap::new());
+
+struct CacheEntry {
+    value: Box<dyn Any + Send>,
+    expires_at: Instant,
+}
+
+/// Sets the time for which decoded IBC data is cached (zero disables caching). Entries are invalidated when they're
+/// written by current process, so, TTL only bounds how long changes made by other processes sharing the database can
+/// go unnoticed.
+pub fn set_ibc_cache_ttl(ttl: Duration) {
+    *CACHE_TTL.write().unwrap() = ttl;
+
+    if ttl.is_zero() {
+        clear();
+    }
+}
+
+/// Returns cached value at given path (if it has not expired)
+pub(crate) fn get<M>(path: &str) -> Option<M>
+where
+    M: Clone + 'static,
+{
+    let mut cache = CACHE.lock().unwrap();
+    let entry = cache.get(path)?;
+
+    if entry.expires_at <= Instant::now() {
+        cache.remove(path);
+        return None;
+    }
+
+    entry.value.downcast_ref::<M>().cloned()
+}
+
+/// Caches value at given path (only values read outside of transactions, i.e., committed values, should be cached)
+pub(crate) fn insert<M>(path: &str, value: &M)
+where
+    M: Clone + Send + 'static,
+{
+    let ttl = *CACHE_TTL.read().unwrap();
+
+    if ttl.is_zero() {
+        return;
+    }
+
+    let now = Instant::now();
+    let mut cache = CACHE.lock().unwrap();
+
+    cache.retain(|_, entry| entry.expires_at > now);
+    cache.insert(
+        path.to_string(),
+        CacheEntry {
+            value: Box::new(value.clone()),
+            expires_at: now + ttl,
+        },
+    );
+}
+
+/// Invalidates cached value at given path
+pub(crate) fn invalidate(path: &str) {
+    CACHE.lock().unwrap().remove(path);
+}
+
+/// Invalidates cached values at all the paths starting with given prefix
+pub(crate) fn invalidate_prefix(prefix: &str) {
+    CACHE
+        .lock()
+        .unwrap()
+        .retain(|path, _| !path.starts_with(prefix));
+}
+
+/// Invalidates all the cached values
+pub(crate) fn clear() {
+    CACHE.lock().unwrap().clear();
+}
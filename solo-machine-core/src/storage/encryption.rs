@@ -0,0 +1,326 @@
+use std::sync::RwLock;
+
+use aes_gcm::{
+    aead::{Aead, NewAead, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use rand::{rngs::OsRng, RngCore};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use sqlx::{types::Json, Executor};
+use zeroize::Zeroizing;
+
+use crate::{
+    keystore::{KdfParams, KEY_LEN},
+    Db, DbPool,
+};
+
+const NONCE_LEN: usize = 12;
+
+/// Prefix of encrypted binary values (a leading zero byte never starts a valid protobuf message)
+const BYTES_PREFIX: &[u8] = b"\0enc1";
+/// Prefix of encrypted JSON values (which are stored as JSON strings)
+const JSON_PREFIX: &str = "enc1:";
+
+/// Additional authenticated data and plaintext of verifier (used for detecting invalid passphrases)
+const VERIFIER_AAD: &str = "state-encryption";
+const VERIFIER_PLAINTEXT: &[u8] = b"solo-machine";
+
+/// Cipher of stored state of current process (`None` if stored state is not encrypted or not yet unlocked)
+static STATE_CIPHER: RwLock<Option<StateCipher>> = RwLock::new(None);
+
+/// AES-256-GCM cipher for values in stored state. Every value is bound to its location in storage (chain ID or ICS-24
+/// path) using additional authenticated data so that encrypted values cannot be swapped between records.
+struct StateCipher {
+    key: Zeroizing<[u8; KEY_LEN]>,
+}
+
+impl StateCipher {
+    fn derive(kdf: &KdfParams, passphrase: &str) -> Result<Self> {
+        Ok(Self {
+            key: kdf.derive_key(passphrase)?,
+        })
+    }
+
+    fn encrypt(&self, aad: &str, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut sealed = vec![0; NONCE_LEN];
+        OsRng.fill_bytes(&mut sealed);
+
+        let ciphertext = Aes256Gcm::new(Key::from_slice(&self.key[..]))
+            .encrypt(
+                Nonce::from_slice(&sealed),
+                Payload {
+                    msg: plaintext,
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("unable to encrypt value at {}", aad))?;
+
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    fn decrypt(&self, aad: &str, sealed: &[u8]) -> Result<Vec<u8>> {
+        ensure!(
+            sealed.len() > NONCE_LEN,
+            "invalid encrypted value at {}",
+            aad
+        );
+
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+
+        Aes256Gcm::new(Key::from_slice(&self.key[..]))
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("unable to decrypt value at {} (corrupted state)", aad))
+    }
+}
+
+fn seal_bytes_with(cipher: Option<&StateCipher>, aad: &str, data: Vec<u8>) -> Result<Vec<u8>> {
+    match cipher {
+        None => Ok(data),
+        Some(cipher) => {
+            let mut sealed = BYTES_PREFIX.to_vec();
+            sealed.extend(cipher.encrypt(aad, &data)?);
+            Ok(sealed)
+        }
+    }
+}
+
+fn open_bytes_with(cipher: Option<&StateCipher>, aad: &str, data: Vec<u8>) -> Result<Vec<u8>> {
+    match (data.strip_prefix(BYTES_PREFIX), cipher) {
+        (None, None) => Ok(data),
+        (Some(sealed), Some(cipher)) => cipher.decrypt(aad, sealed),
+        (Some(_), None) => bail!(locked_error()),
+        (None, Some(_)) => bail!("unencrypted value found at {} in encrypted state", aad),
+    }
+}
+
+fn seal_json_with(cipher: Option<&StateCipher>, aad: &str, value: Value) -> Result<Value> {
+    match cipher {
+        None => Ok(value),
+        Some(cipher) => {
+            let bytes = serde_json::to_vec(&value).context("unable to serialize value")?;
+            let sealed = cipher.encrypt(aad, &bytes)?;
+
+            Ok(Value::String(format!(
+                "{}{}",
+                JSON_PREFIX,
+                hex::encode(sealed)
+            )))
+        }
+    }
+}
+
+fn open_json_with(cipher: Option<&StateCipher>, aad: &str, value: Value) -> Result<Value> {
+    let sealed = value
+        .as_str()
+        .and_then(|value| value.strip_prefix(JSON_PREFIX));
+
+    match (sealed, cipher) {
+        (None, None) => Ok(value),
+        (Some(sealed), Some(cipher)) => {
+            let sealed = hex::decode(sealed)
+                .with_context(|| format!("invalid encrypted value at {}", aad))?;
+            let bytes = cipher.decrypt(aad, &sealed)?;
+
+            serde_json::from_slice(&bytes).context("unable to deserialize decrypted value")
+        }
+        (Some(_), None) => bail!(locked_error()),
+        (None, Some(_)) => bail!("unencrypted value found at {} in encrypted state", aad),
+    }
+}
+
+fn locked_error() -> &'static str {
+    "stored state is encrypted (state passphrase is required to unlock it)"
+}
+
+/// Encrypts binary value at given location (chain ID or ICS-24 path) if stored state is encrypted
+pub(crate) fn seal_bytes(aad: &str, data: Vec<u8>) -> Result<Vec<u8>> {
+    seal_bytes_with(STATE_CIPHER.read().unwrap().as_ref(), aad, data)
+}
+
+/// Decrypts binary value at given location (chain ID or ICS-24 path) if stored state is encrypted
+pub(crate) fn open_bytes(aad: &str, data: Vec<u8>) -> Result<Vec<u8>> {
+    open_bytes_with(STATE_CIPHER.read().unwrap().as_ref(), aad, data)
+}
+
+/// Serializes (and encrypts, if stored state is encrypted) JSON value at given location
+pub(crate) fn seal_json<T: Serialize>(aad: &str, value: &T) -> Result<Value> {
+    let value = serde_json::to_value(value).context("unable to serialize value")?;
+    seal_json_with(STATE_CIPHER.read().unwrap().as_ref(), aad, value)
+}
+
+/// Deserializes (and decrypts, if stored state is encrypted) JSON value at given location
+pub(crate) fn open_json<T: DeserializeOwned>(aad: &str, value: Value) -> Result<T> {
+    let value = open_json_with(STATE_CIPHER.read().unwrap().as_ref(), aad, value)?;
+    serde_json::from_value(value).context("unable to deserialize value")
+}
+
+async fn get_encryption_params<'e>(
+    executor: impl Executor<'e, Database = Db>,
+) -> Result<Option<(KdfParams, Vec<u8>)>> {
+    let row: Option<(String, String)> =
+        sqlx::query_as("SELECT kdf, verifier FROM state_encryption WHERE id = 1")
+            .fetch_optional(executor)
+            .await
+            .context("unable to query state encryption parameters from database")?;
+
+    row.map(|(kdf, verifier)| {
+        Ok((
+            serde_json::from_str(&kdf).context("invalid state encryption parameters")?,
+            hex::decode(verifier).context("invalid state encryption verifier")?,
+        ))
+    })
+    .transpose()
+}
+
+fn unlock_cipher(kdf: &KdfParams, verifier: &[u8], passphrase: &str) -> Result<StateCipher> {
+    let cipher = StateCipher::derive(kdf, passphrase)?;
+
+    let plaintext = cipher
+        .decrypt(VERIFIER_AAD, verifier)
+        .map_err(|_| anyhow!("invalid state passphrase"))?;
+    ensure!(plaintext == VERIFIER_PLAINTEXT, "invalid state passphrase");
+
+    Ok(cipher)
+}
+
+/// Re-encrypts chain configurations and IBC data (client states, consensus states, connections and channels) from one
+/// cipher to another (`None` means unencrypted) and returns number of re-encrypted records
+async fn reencrypt_state(
+    transaction: &mut sqlx::Transaction<'_, Db>,
+    from: Option<&StateCipher>,
+    to: Option<&StateCipher>,
+) -> Result<usize> {
+    let chains: Vec<(String, Json<Value>)> = sqlx::query_as("SELECT id, config FROM chains")
+        .fetch_all(&mut *transaction)
+        .await
+        .context("unable to query chains from database")?;
+
+    let ibc_data: Vec<(String, Vec<u8>)> = sqlx::query_as("SELECT path, data FROM ibc_data")
+        .fetch_all(&mut *transaction)
+        .await
+        .context("unable to query ibc data from database")?;
+
+    let count = chains.len() + ibc_data.len();
+
+    for (id, config) in chains {
+        let config = open_json_with(from, &id, config.0)?;
+        let config = seal_json_with(to, &id, config)?;
+
+        sqlx::query("UPDATE chains SET config = $1 WHERE id = $2")
+            .bind(Json(config))
+            .bind(id)
+            .execute(&mut *transaction)
+            .await
+            .context("unable to update chain config in database")?;
+    }
+
+    for (path, data) in ibc_data {
+        let data = open_bytes_with(from, &path, data)?;
+        let data = seal_bytes_with(to, &path, data)?;
+
+        sqlx::query("UPDATE ibc_data SET data = $1 WHERE path = $2")
+            .bind(data)
+            .bind(path)
+            .execute(&mut *transaction)
+            .await
+            .context("unable to update ibc data in database")?;
+    }
+
+    Ok(count)
+}
+
+/// Returns `true` if stored state is encrypted
+pub async fn is_state_encrypted(db_pool: &DbPool) -> Result<bool> {
+    Ok(get_encryption_params(db_pool).await?.is_some())
+}
+
+/// Unlocks encrypted stored state for current process using given passphrase. Returns `false` if stored state is not
+/// encrypted.
+pub async fn unlock_state(db_pool: &DbPool, passphrase: &str) -> Result<bool> {
+    match get_encryption_params(db_pool).await? {
+        None => Ok(false),
+        Some((kdf, verifier)) => {
+            let cipher = unlock_cipher(&kdf, &verifier, passphrase)?;
+            *STATE_CIPHER.write().unwrap() = Some(cipher);
+
+            Ok(true)
+        }
+    }
+}
+
+/// Encrypts stored state (chain configurations, client states, consensus states, connections and channels) using a
+/// key derived from given passphrase and returns number of encrypted records. Stored state stays unlocked for current
+/// process.
+pub async fn enable_state_encryption(db_pool: &DbPool, passphrase: &str) -> Result<usize> {
+    ensure!(!passphrase.is_empty(), "state passphrase cannot be empty");
+
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("unable to begin database transaction")?;
+
+    ensure!(
+        get_encryption_params(&mut transaction).await?.is_none(),
+        "stored state is already encrypted"
+    );
+
+    let kdf = KdfParams::generate();
+    let cipher = StateCipher::derive(&kdf, passphrase)?;
+    let verifier = cipher.encrypt(VERIFIER_AAD, VERIFIER_PLAINTEXT)?;
+
+    sqlx::query("INSERT INTO state_encryption (id, kdf, verifier) VALUES (1, $1, $2)")
+        .bind(serde_json::to_string(&kdf).context("unable to serialize kdf parameters")?)
+        .bind(hex::encode(verifier))
+        .execute(&mut transaction)
+        .await
+        .context("unable to add state encryption parameters to database")?;
+
+    let count = reencrypt_state(&mut transaction, None, Some(&cipher)).await?;
+
+    transaction
+        .commit()
+        .await
+        .context("unable to commit transaction for encrypting stored state")?;
+
+    *STATE_CIPHER.write().unwrap() = Some(cipher);
+
+    Ok(count)
+}
+
+/// Decrypts stored state encrypted using given passphrase and returns number of decrypted records
+pub async fn disable_state_encryption(db_pool: &DbPool, passphrase: &str) -> Result<usize> {
+    let mut transaction = db_pool
+        .begin()
+        .await
+        .context("unable to begin database transaction")?;
+
+    let (kdf, verifier) = get_encryption_params(&mut transaction)
+        .await?
+        .ok_or_else(|| anyhow!("stored state is not encrypted"))?;
+    let cipher = unlock_cipher(&kdf, &verifier, passphrase)?;
+
+    let count = reencrypt_state(&mut transaction, Some(&cipher), None).await?;
+
+    sqlx::query("DELETE FROM state_encryption")
+        .execute(&mut transaction)
+        .await
+        .context("unable to remove state encryption parameters from database")?;
+
+    transaction
+        .commit()
+        .await
+        .context("unable to commit transaction for decrypting stored state")?;
+
+    *STATE_CIPHER.write().unwrap() = None;
+
+    Ok(count)
+}
@@ -0,0 +1,462 @@
+//! Subscription to tendermint `Tx` and `NewBlock` events of a chain over websocket, dispatching typed IBC events to
+//! handlers
+use std::{convert::TryFrom, fmt, time::Duration};
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use cosmos_sdk_proto::ibc::core::channel::v1::Packet;
+use futures::{stream::select, StreamExt};
+use tendermint_rpc::{
+    event::{Event as RpcEvent, EventData},
+    query::EventType,
+    SubscriptionClient, WebSocketClient,
+};
+use tokio::{sync::mpsc::UnboundedSender, time::sleep};
+
+use crate::{
+    broadcaster::TxEvent,
+    event::{notify_event, Event},
+    ibc::core::{
+        ics02_client::height::IHeight, ics04_channel::packet::IPacket,
+        ics24_host::identifier::ChainId,
+    },
+    supervisor::CancellationToken,
+};
+
+/// Interval after which subscription is re-established when websocket connection is lost
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Kind of channel handshake event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelEventKind {
+    /// `channel_open_init`
+    OpenInit,
+    /// `channel_open_try`
+    OpenTry,
+    /// `channel_open_ack`
+    OpenAck,
+    /// `channel_open_confirm`
+    OpenConfirm,
+    /// `channel_close_init`
+    CloseInit,
+    /// `channel_close_confirm`
+    CloseConfirm,
+}
+
+impl ChannelEventKind {
+    fn from_event_type(event_type: &str) -> Option<Self> {
+        match event_type {
+            "channel_open_init" => Some(Self::OpenInit),
+            "channel_open_try" => Some(Self::OpenTry),
+            "channel_open_ack" => Some(Self::OpenAck),
+            "channel_open_confirm" => Some(Self::OpenConfirm),
+            "channel_close_init" => Some(Self::CloseInit),
+            "channel_close_confirm" => Some(Self::CloseConfirm),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ChannelEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OpenInit => write!(f, "channel_open_init"),
+            Self::OpenTry => write!(f, "channel_open_try"),
+            Self::OpenAck => write!(f, "channel_open_ack"),
+            Self::OpenConfirm => write!(f, "channel_open_confirm"),
+            Self::CloseInit => write!(f, "channel_close_init"),
+            Self::CloseConfirm => write!(f, "channel_close_confirm"),
+        }
+    }
+}
+
+/// Typed IBC event observed on a chain
+#[derive(Debug, Clone)]
+pub enum IbcEvent {
+    /// A new block is committed on chain
+    NewBlock {
+        /// Height of block
+        height: u64,
+        /// Time of block
+        time: DateTime<Utc>,
+    },
+    /// A packet is sent from chain (`send_packet`)
+    SendPacket {
+        /// Height of block containing the event
+        height: u64,
+        /// Sent packet
+        packet: Packet,
+    },
+    /// A packet is received on chain (`recv_packet`)
+    RecvPacket {
+        /// Height of block containing the event
+        height: u64,
+        /// Received packet
+        packet: Packet,
+    },
+    /// An acknowledgement of a received packet is written on chain (`write_acknowledgement`)
+    WriteAcknowledgement {
+        /// Height of block containing the event
+        height: u64,
+        /// Acknowledged packet
+        packet: Packet,
+        /// Acknowledgement bytes
+        acknowledgement: Vec<u8>,
+    },
+    /// Acknowledgement of a packet sent from chain is processed (`acknowledge_packet`, packet data is not included)
+    AcknowledgePacket {
+        /// Height of block containing the event
+        height: u64,
+        /// Acknowledged packet
+        packet: Packet,
+    },
+    /// A packet sent from chain is timed out (`timeout_packet`, packet data is not included)
+    TimeoutPacket {
+        /// Height of block containing the event
+        height: u64,
+        /// Timed out packet
+        packet: Packet,
+    },
+    /// A step of channel handshake (or channel closing) is executed on chain
+    Channel {
+        /// Height of block containing the event
+        height: u64,
+        /// Kind of channel event
+        kind: ChannelEventKind,
+        /// Port ID of channel
+        port_id: String,
+        /// Channel ID (absent in some events of older IBC versions)
+        channel_id: Option<String>,
+        /// Port ID of counterparty channel
+        counterparty_port_id: Option<String>,
+        /// Channel ID of counterparty channel (absent until counterparty channel is initialized)
+        counterparty_channel_id: Option<String>,
+        /// Connection ID of channel
+        connection_id: Option<String>,
+    },
+}
+
+impl IbcEvent {
+    /// Returns height of block in which the event occurred
+    pub fn height(&self) -> u64 {
+        match self {
+            Self::NewBlock { height, .. }
+            | Self::SendPacket { height, .. }
+            | Self::RecvPacket { height, .. }
+            | Self::WriteAcknowledgement { height, .. }
+            | Self::AcknowledgePacket { height, .. }
+            | Self::TimeoutPacket { height, .. }
+            | Self::Channel { height, .. } => *height,
+        }
+    }
+
+    /// Extracts typed IBC events from events emitted while executing a transaction at given height (non-IBC events
+    /// are ignored)
+    pub fn from_tx_events(height: u64, events: &[TxEvent]) -> Result<Vec<Self>> {
+        let mut ibc_events = Vec::new();
+
+        for event in events {
+            let ibc_event = match event.kind.as_str() {
+                "send_packet" => Self::SendPacket {
+                    height,
+                    packet: Packet::from_event_attributes(&event.attributes)?,
+                },
+                "recv_packet" => Self::RecvPacket {
+                    height,
+                    packet: Packet::from_event_attributes(&event.attributes)?,
+                },
+                "write_acknowledgement" => Self::WriteAcknowledgement {
+                    height,
+                    packet: Packet::from_event_attributes(&event.attributes)?,
+                    acknowledgement: event
+                        .attribute("packet_ack")
+                        .ok_or_else(|| anyhow!("`packet_ack` is missing from packet data"))?
+                        .as_bytes()
+                        .to_vec(),
+                },
+                "acknowledge_packet" => Self::AcknowledgePacket {
+                    height,
+                    packet: Packet::from_event_attributes(&event.attributes)?,
+                },
+                "timeout_packet" => Self::TimeoutPacket {
+                    height,
+                    packet: Packet::from_event_attributes(&event.attributes)?,
+                },
+                event_type => match ChannelEventKind::from_event_type(event_type) {
+                    None => continue,
+                    Some(kind) => Self::Channel {
+                        height,
+                        kind,
+                        port_id: event
+                            .attribute("port_id")
+                            .ok_or_else(|| anyhow!("`port_id` is missing from {} event", kind))?
+                            .to_string(),
+                        channel_id: non_empty_attribute(event, "channel_id"),
+                        counterparty_port_id: non_empty_attribute(event, "counterparty_port_id"),
+                        counterparty_channel_id: non_empty_attribute(
+                            event,
+                            "counterparty_channel_id",
+                        ),
+                        connection_id: non_empty_attribute(event, "connection_id"),
+                    },
+                },
+            };
+
+            ibc_events.push(ibc_event);
+        }
+
+        Ok(ibc_events)
+    }
+
+    fn from_rpc_event(event: RpcEvent) -> Result<Vec<Self>> {
+        match event.data {
+            EventData::NewBlock {
+                block: Some(block), ..
+            } => Ok(vec![Self::NewBlock {
+                height: block.header.height.value(),
+                time: std::time::SystemTime::from(block.header.time).into(),
+            }]),
+            EventData::Tx { tx_result } => {
+                let height = u64::try_from(tx_result.height)
+                    .context("invalid height of transaction event")?;
+                let events: Vec<TxEvent> = tx_result.result.events.iter().map(Into::into).collect();
+
+                Self::from_tx_events(height, &events)
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
+impl fmt::Display for IbcEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NewBlock { height, time } => write!(f, "[{}] new_block time={}", height, time),
+            Self::SendPacket { height, packet } => {
+                write!(f, "[{}] send_packet {}", height, DisplayPacket(packet))
+            }
+            Self::RecvPacket { height, packet } => {
+                write!(f, "[{}] recv_packet {}", height, DisplayPacket(packet))
+            }
+            Self::WriteAcknowledgement {
+                height,
+                packet,
+                acknowledgement,
+            } => write!(
+                f,
+                "[{}] write_acknowledgement {} ack={}",
+                height,
+                DisplayPacket(packet),
+                String::from_utf8_lossy(acknowledgement)
+            ),
+            Self::AcknowledgePacket { height, packet } => {
+                write!(
+                    f,
+                    "[{}] acknowledge_packet {}",
+                    height,
+                    DisplayPacket(packet)
+                )
+            }
+            Self::TimeoutPacket { height, packet } => {
+                write!(f, "[{}] timeout_packet {}", height, DisplayPacket(packet))
+            }
+            Self::Channel {
+                height,
+                kind,
+                port_id,
+                channel_id,
+                counterparty_port_id,
+                counterparty_channel_id,
+                connection_id,
+            } => write!(
+                f,
+                "[{}] {} channel={}/{} counterparty={}/{} connection={}",
+                height,
+                kind,
+                port_id,
+                channel_id.as_deref().unwrap_or("-"),
+                counterparty_port_id.as_deref().unwrap_or("-"),
+                counterparty_channel_id.as_deref().unwrap_or("-"),
+                connection_id.as_deref().unwrap_or("-"),
+            ),
+        }
+    }
+}
+
+struct DisplayPacket<'a>(&'a Packet);
+
+impl fmt::Display for DisplayPacket<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let packet = self.0;
+
+        write!(
+            f,
+            "sequence={} src={}/{} dst={}/{} timeout_height={} timeout_timestamp={}",
+            packet.sequence,
+            packet.source_port,
+            packet.source_channel,
+            packet.destination_port,
+            packet.destination_channel,
+            packet
+                .timeout_height
+                .as_ref()
+                .map_or_else(|| "-".to_string(), |height| height.to_string()),
+            packet.timeout_timestamp
+        )
+    }
+}
+
+/// Trait to handle IBC events observed on a chain
+#[async_trait]
+pub trait IbcEventHandler: Sync + Send {
+    /// Handles an IBC event observed on given chain
+    async fn handle(&self, chain_id: &ChainId, event: IbcEvent) -> Result<()>;
+}
+
+/// Subscribes to tendermint `Tx` and `NewBlock` events of a chain over websocket and dispatches typed IBC events to a
+/// handler. Events occurring while websocket connection is lost are not dispatched, so, handlers relying on seeing
+/// every event should catch up (e.g. using `tx_search`) after reconnecting.
+pub struct EventSubscriber {
+    chain_id: ChainId,
+    websocket_addr: String,
+    notifier: Option<UnboundedSender<Event>>,
+}
+
+impl EventSubscriber {
+    /// Creates a new subscriber for given chain. Websocket address is derived from RPC address of chain (e.g.
+    /// `http://localhost:26657` becomes `ws://localhost:26657/websocket`).
+    pub fn new(chain_id: ChainId, rpc_addr: &str) -> Self {
+        Self {
+            chain_id,
+            websocket_addr: websocket_addr(rpc_addr),
+            notifier: None,
+        }
+    }
+
+    /// Creates a new subscriber for given chain with notifier (used for reporting subscription failures)
+    pub fn new_with_notifier(
+        chain_id: ChainId,
+        rpc_addr: &str,
+        notifier: UnboundedSender<Event>,
+    ) -> Self {
+        Self {
+            chain_id,
+            websocket_addr: websocket_addr(rpc_addr),
+            notifier: Some(notifier),
+        }
+    }
+
+    /// Returns websocket address to which subscriber connects
+    pub fn websocket_addr(&self) -> &str {
+        &self.websocket_addr
+    }
+
+    /// Dispatches IBC events to handler until cancellation token is cancelled. Subscription is re-established when
+    /// websocket connection is lost. Errors returned by handler are logged and do not stop the subscription.
+    pub async fn run(&self, handler: &dyn IbcEventHandler, token: CancellationToken) -> Result<()> {
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => return Ok(()),
+                result = self.subscribe(handler) => {
+                    if let Err(err) = result {
+                        self.warn(format!(
+                            "websocket subscription to {} failed (reconnecting in {}s): {:#}",
+                            self.chain_id,
+                            RECONNECT_INTERVAL.as_secs(),
+                            err
+                        ))?;
+                    }
+                }
+            }
+
+            tokio::select! {
+                _ = token.cancelled() => return Ok(()),
+                _ = sleep(RECONNECT_INTERVAL) => {}
+            }
+        }
+    }
+
+    /// Subscribes to events and dispatches them to handler until websocket connection is lost
+    async fn subscribe(&self, handler: &dyn IbcEventHandler) -> Result<()> {
+        let (client, driver) = WebSocketClient::new(self.websocket_addr.as_str())
+            .await
+            .context(format!(
+                "unable to connect to websocket at {}",
+                self.websocket_addr
+            ))?;
+
+        let driver_handle = tokio::spawn(driver.run());
+
+        let result = async {
+            let tx_subscription = client
+                .subscribe(EventType::Tx.into())
+                .await
+                .context("unable to subscribe to tx events")?;
+            let block_subscription = client
+                .subscribe(EventType::NewBlock.into())
+                .await
+                .context("unable to subscribe to new block events")?;
+
+            let mut events = select(tx_subscription, block_subscription);
+
+            while let Some(event) = events.next().await {
+                let event = event.context("unable to receive event from websocket")?;
+
+                for ibc_event in IbcEvent::from_rpc_event(event)? {
+                    if let Err(err) = handler.handle(&self.chain_id, ibc_event).await {
+                        self.warn(format!(
+                            "unable to handle ibc event of {}: {:#}",
+                            self.chain_id, err
+                        ))?;
+                    }
+                }
+            }
+
+            Err(anyhow!("websocket subscription closed"))
+        }
+        .await;
+
+        let _ = client.close();
+        let _ = driver_handle.await;
+
+        result
+    }
+
+    fn warn(&self, message: String) -> Result<()> {
+        match self.notifier {
+            None => {
+                tracing::warn!("{}", message);
+                Ok(())
+            }
+            Some(_) => notify_event(&self.notifier, Event::Warning { message }),
+        }
+    }
+}
+
+/// Returns value of attribute of event with given key if it is present and non-empty
+fn non_empty_attribute(event: &TxEvent, key: &str) -> Option<String> {
+    event
+        .attribute(key)
+        .filter(|value| !value.is_empty())
+        .map(ToString::to_string)
+}
+
+/// Derives websocket address from RPC address of chain
+fn websocket_addr(rpc_addr: &str) -> String {
+    let rpc_addr = rpc_addr.trim_end_matches('/');
+
+    let addr = if let Some(rest) = rpc_addr.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = rpc_addr.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        rpc_addr.to_string()
+    };
+
+    if addr.ends_with("/websocket") {
+        addr
+    } else {
+        format!("{}/websocket", addr)
+    }
+}
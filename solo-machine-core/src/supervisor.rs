@@ -0,0 +1,220 @@
+//! Supervision of long running asynchronous tasks with explicit cancellation and restart policies
+use std::{any::Any, future::Future, sync::Arc, time::Duration};
+
+use anyhow::{anyhow, Context, Error, Result};
+use tokio::{
+    sync::{
+        mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+        watch,
+    },
+    task::JoinHandle,
+    time::sleep,
+};
+
+/// Token used to signal cancellation to supervised tasks
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    sender: Arc<watch::Sender<bool>>,
+    receiver: watch::Receiver<bool>,
+}
+
+impl CancellationToken {
+    /// Creates a new cancellation token
+    pub fn new() -> Self {
+        let (sender, receiver) = watch::channel(false);
+
+        Self {
+            sender: Arc::new(sender),
+            receiver,
+        }
+    }
+
+    /// Signals cancellation to all the holders of this token
+    pub fn cancel(&self) {
+        let _ = self.sender.send(true);
+    }
+
+    /// Returns `true` if cancellation is signalled
+    pub fn is_cancelled(&self) -> bool {
+        *self.receiver.borrow()
+    }
+
+    /// Waits until cancellation is signalled
+    pub async fn cancelled(&self) {
+        let mut receiver = self.receiver.clone();
+
+        while !*receiver.borrow() {
+            if receiver.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Policy for restarting a supervised task when it fails or panics
+#[derive(Debug, Clone, Copy)]
+pub enum RestartPolicy {
+    /// Task is never restarted (failure of task cancels all the other tasks of supervisor)
+    Never,
+    /// Task is restarted (after waiting for `backoff` duration) at most `max_restarts` times
+    OnFailure {
+        /// Maximum number of restarts
+        max_restarts: u32,
+        /// Duration to wait before restarting task
+        backoff: Duration,
+    },
+}
+
+/// Supervises a set of tasks. Failed (or panicked) tasks are logged and restarted according to their restart policy.
+/// When a task fails permanently, all the other tasks are cancelled.
+pub struct TaskSupervisor {
+    token: CancellationToken,
+    handles: Vec<JoinHandle<()>>,
+    result_sender: UnboundedSender<Result<()>>,
+    result_receiver: UnboundedReceiver<Result<()>>,
+}
+
+impl TaskSupervisor {
+    /// Creates a new task supervisor
+    pub fn new() -> Self {
+        let (result_sender, result_receiver) = unbounded_channel();
+
+        Self {
+            token: CancellationToken::new(),
+            handles: Default::default(),
+            result_sender,
+            result_receiver,
+        }
+    }
+
+    /// Returns the cancellation token shared by all the tasks of supervisor
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Spawns a new supervised task. `task` is called with supervisor's cancellation token every time the task is
+    /// (re)started and the returned future should complete soon after the token is cancelled.
+    pub fn spawn<F, Fut>(&mut self, name: impl Into<String>, policy: RestartPolicy, task: F)
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let name = name.into();
+        let token = self.token.clone();
+        let result_sender = self.result_sender.clone();
+
+        self.handles.push(tokio::spawn(async move {
+            let result = supervise(&name, policy, token, task).await;
+            let _ = result_sender.send(result);
+        }));
+    }
+
+    /// Waits for all the supervised tasks to complete. If any task fails permanently, all the other tasks are
+    /// cancelled and the first error is returned.
+    pub async fn join(self) -> Result<()> {
+        let Self {
+            token,
+            handles,
+            result_sender,
+            mut result_receiver,
+        } = self;
+
+        drop(result_sender);
+
+        let mut first_error = None;
+
+        while let Some(result) = result_receiver.recv().await {
+            if let Err(err) = result {
+                token.cancel();
+                first_error.get_or_insert(err);
+            }
+        }
+
+        for handle in handles {
+            handle.await.context("unable to join supervised task")?;
+        }
+
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Cancels all the supervised tasks and waits for them to complete
+    pub async fn shutdown(self) -> Result<()> {
+        self.token.cancel();
+        self.join().await
+    }
+}
+
+impl Default for TaskSupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn supervise<F, Fut>(
+    name: &str,
+    policy: RestartPolicy,
+    token: CancellationToken,
+    task: F,
+) -> Result<()>
+where
+    F: Fn(CancellationToken) -> Fut,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    let mut restarts = 0;
+
+    loop {
+        // every run is spawned separately so that panics are caught and reported instead of silently stopping the
+        // task
+        let error: Error = match tokio::spawn(task(token.clone())).await {
+            Ok(Ok(())) => return Ok(()),
+            Ok(Err(err)) => err,
+            Err(err) if err.is_panic() => anyhow!("panicked: {}", panic_message(err.into_panic())),
+            Err(err) => anyhow!("aborted: {}", err),
+        };
+
+        let error = error.context(format!("supervised task `{}` failed", name));
+
+        match policy {
+            RestartPolicy::OnFailure {
+                max_restarts,
+                backoff,
+            } if restarts < max_restarts && !token.is_cancelled() => {
+                restarts += 1;
+
+                tracing::error!(
+                    task = name,
+                    restart = restarts,
+                    max_restarts,
+                    "{:?} (restarting in {:?})",
+                    error,
+                    backoff
+                );
+
+                tokio::select! {
+                    _ = token.cancelled() => return Err(error),
+                    _ = sleep(backoff) => {}
+                }
+            }
+            _ => {
+                tracing::error!(task = name, "{:?}", error);
+                return Err(error);
+            }
+        }
+    }
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => match panic.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
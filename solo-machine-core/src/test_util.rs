@@ -0,0 +1,81 @@
+//! Helpers for tests running against an in-memory database
+use std::{str::FromStr, time::Duration};
+
+use sqlx::sqlite::SqlitePoolOptions;
+
+use crate::{
+    ibc::core::ics24_host::identifier::{ChainId, PortId},
+    model::{chain, ChainConfig, Fee},
+    run_migrations, DbPool,
+};
+
+/// Returns a migrated in-memory database (with a single connection, so that all the queries see the same database)
+pub(crate) async fn db_pool() -> DbPool {
+    let db_pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await
+        .expect("unable to connect to in-memory database");
+
+    run_migrations(&db_pool)
+        .await
+        .expect("unable to run migrations");
+
+    db_pool
+}
+
+/// Returns configuration of a chain which is never contacted by tests
+pub(crate) fn chain_config() -> ChainConfig {
+    ChainConfig {
+        grpc_addr: "http://0.0.0.0:9090".to_string(),
+        grpc_tls: Default::default(),
+        rpc_addr: "http://0.0.0.0:26657".to_string(),
+        fee: Fee {
+            amount: "1000".parse().unwrap(),
+            denom: "stake".parse().unwrap(),
+            gas_limit: 300000,
+            granter: None,
+        },
+        trust_level: "1/3".parse().unwrap(),
+        trusting_period: Duration::from_secs(14 * 24 * 60 * 60),
+        max_clock_drift: Duration::from_secs(3),
+        rpc_timeout: Duration::from_secs(60),
+        diversifier: "solo-machine-diversifier".to_string(),
+        port_id: PortId::from_str("transfer").unwrap(),
+        trusted_height: 1u32.into(),
+        trusted_hash: [0; 32],
+        name_resolver: None,
+        address_display: Default::default(),
+        address_format: Default::default(),
+        sign_mode: Default::default(),
+        address_algo: Default::default(),
+        halt_threshold: Duration::from_secs(120),
+        fee_strategy: Default::default(),
+        client_encoding: Default::default(),
+        inclusion_policy: Default::default(),
+        retry_policy: Default::default(),
+        timeouts: Default::default(),
+        rate_limit: None,
+        fallback_grpc_addrs: Vec::new(),
+        fallback_rpc_addrs: Vec::new(),
+        proxy: None,
+        timestamp_sync_offset: None,
+        ibc_version: None,
+        client_revision: None,
+        strict_preflight: false,
+    }
+}
+
+/// Adds a chain with given ID (and [`chain_config`]) to database
+pub(crate) async fn add_chain(db_pool: &DbPool, chain_id: &str) -> ChainId {
+    let chain_id: ChainId = chain_id.parse().unwrap();
+    let node_id = "0000000000000000000000000000000000000000".parse().unwrap();
+
+    chain::add_chain(db_pool, &chain_id, &node_id, &chain_config())
+        .await
+        .expect("unable to add chain");
+
+    chain_id
+}
@@ -1,24 +1,23 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, time::SystemTime};
 
+#[cfg(feature = "ethermint")]
+use crate::cosmos::eip712::{self, ExtensionOptionsWeb3Tx};
 #[cfg(feature = "solomachine-v2")]
 use crate::proto::ibc::lightclients::solomachine::v2::{
-    ChannelStateData, ClientState as SoloMachineClientState, ClientStateData, ConnectionStateData,
-    ConsensusState as SoloMachineConsensusState, ConsensusStateData, DataType,
-    Header as SoloMachineHeader, HeaderData, PacketAcknowledgementData, PacketCommitmentData,
-    SignBytes, TimestampedSignatureData,
+    ClientState as SoloMachineClientState, ConsensusState as SoloMachineConsensusState,
+    Header as SoloMachineHeader,
 };
 use anyhow::{anyhow, bail, ensure, Context, Result};
 use chrono::{DateTime, Utc};
 #[cfg(not(feature = "solomachine-v2"))]
 use cosmos_sdk_proto::ibc::lightclients::solomachine::v1::{
-    ChannelStateData, ClientState as SoloMachineClientState, ClientStateData, ConnectionStateData,
-    ConsensusState as SoloMachineConsensusState, ConsensusStateData, DataType,
-    Header as SoloMachineHeader, HeaderData, PacketAcknowledgementData, PacketCommitmentData,
-    SignBytes, TimestampedSignatureData,
+    ClientState as SoloMachineClientState, ConsensusState as SoloMachineConsensusState,
+    Header as SoloMachineHeader,
 };
 use cosmos_sdk_proto::{
     cosmos::{
         auth::v1beta1::{query_client::QueryClient as AuthQueryClient, QueryAccountRequest},
+        bank::v1beta1::MsgSend,
         base::v1beta1::Coin,
         staking::v1beta1::{query_client::QueryClient as StakingQueryClient, QueryParamsRequest},
         tx::{
@@ -40,13 +39,13 @@ use cosmos_sdk_proto::{
         core::{
             channel::v1::{
                 Channel, Counterparty as ChannelCounterparty, MsgAcknowledgement,
-                MsgChannelOpenAck, MsgChannelOpenInit, MsgRecvPacket, Order as ChannelOrder,
-                Packet, State as ChannelState,
+                MsgChannelOpenAck, MsgChannelOpenInit, MsgRecvPacket, MsgTimeout,
+                Order as ChannelOrder, Packet, State as ChannelState,
             },
             client::v1::{Height, MsgCreateClient, MsgUpdateClient},
             commitment::v1::MerklePrefix,
             connection::v1::{
-                Counterparty as ConnectionCounterparty, MsgConnectionOpenAck,
+                ConnectionEnd, Counterparty as ConnectionCounterparty, MsgConnectionOpenAck,
                 MsgConnectionOpenInit, Version as ConnectionVersion,
             },
         },
@@ -56,16 +55,29 @@ use cosmos_sdk_proto::{
         },
     },
 };
+use prost::Message as _;
 use prost_types::{Any, Duration};
+use rust_decimal::Decimal;
 use serde::Serialize;
-use serde_json::json;
+use serde_json::{json, Value};
 use sqlx::{Executor, Transaction};
 use tendermint::block::Header;
 use tendermint_light_client::supervisor::Instance;
 use tendermint_rpc::Client;
 
 use crate::{
-    cosmos::{account::Account, crypto::PublicKey},
+    client_encoder::{
+        ClientEncoder, ClientEncoding, RevisionClientEncoder, StandardClientEncoder,
+        TypeUrlClientEncoder, WasmClientEncoder,
+    },
+    cosmos::{
+        account::Account,
+        amino::{self, SignMode as TxSignMode},
+        crypto::PublicKey,
+        grpc_pool::grpc_channel,
+    },
+    failover::grpc_query,
+    fee::{FeeRequest, FeeStrategy as _, FeeStrategyKind, SimulateFeeStrategy, StaticFeeStrategy},
     ibc::{
         client::ics07_tendermint::consensus_state::IConsensusState,
         core::{
@@ -76,14 +88,27 @@ use crate::{
                 identifier::{ChainId, ChannelId, ClientId, ConnectionId, Identifier},
                 path::{
                     ChannelPath, ClientStatePath, ConnectionPath, ConsensusStatePath,
-                    PacketAcknowledgementPath, PacketCommitmentPath,
+                    PacketAcknowledgementPath, PacketCommitmentPath, PacketReceiptPath,
                 },
             },
         },
     },
-    model::{chain, ibc as ibc_handler, Chain},
-    proto::{proto_encode, AnyConvert},
-    signer::Message,
+    ibc_version::{default_solo_machine_revision, SoloMachineRevision},
+    model::{chain, ibc as ibc_handler, Chain, Fee as FeeConfig},
+    plugins::Plugins,
+    proto::{
+        cosmos::feegrant::v1beta1::{BasicAllowance, MsgGrantAllowance, MsgRevokeAllowance},
+        ibc::lightclients::solomachine::{
+            v2::{
+                DataType, HeaderData, PacketAcknowledgementData, PacketCommitmentData, SignBytes,
+                TimestampedSignatureData,
+            },
+            v3::{HeaderData as HeaderDataV3, SignBytes as SignBytesV3},
+        },
+        json, proto_encode, AnyConvert,
+    },
+    signer::{Message, SignBytesPreview},
+    verifier::{ClaimedState, SENTINEL_HEADER_PATH},
     Db, Signer, ToPublicKey,
 };
 
@@ -92,6 +117,7 @@ const DEFAULT_TIMEOUT_HEIGHT_OFFSET: u64 = 10;
 /// Builds a transaction to create a solo machine client on IBC enabled chain
 pub async fn msg_create_solo_machine_client(
     signer: impl Signer,
+    plugins: &Plugins,
     chain: &Chain,
     memo: String,
 ) -> Result<TxRaw> {
@@ -102,7 +128,9 @@ pub async fn msg_create_solo_machine_client(
         diversifier: chain.config.diversifier.clone(),
         timestamp: to_u64_timestamp(chain.consensus_timestamp)?,
     };
-    let any_consensus_state = consensus_state.to_any()?;
+    let any_consensus_state = encode_client_type(plugins, chain, |encoder| {
+        encoder.encode_consensus_state(chain, &consensus_state)
+    })?;
 
     let client_state = SoloMachineClientState {
         sequence: chain.sequence.into(),
@@ -113,21 +141,24 @@ pub async fn msg_create_solo_machine_client(
         consensus_state: Some(consensus_state),
         allow_update_after_proposal: true,
     };
-    let any_client_state = client_state.to_any()?;
+    let any_client_state = encode_client_type(plugins, chain, |encoder| {
+        encoder.encode_client_state(chain, &client_state)
+    })?;
 
     let message = MsgCreateClient {
         client_state: Some(any_client_state),
         consensus_state: Some(any_consensus_state),
-        signer: signer.to_account_address()?,
+        signer: chain.account_address(&signer)?,
     };
 
-    build(signer, chain, &[message], memo, None).await
+    build(signer, plugins, chain, &[message], memo, None).await
 }
 
 /// Builds a transaction to update solo machine client on IBC enabled chain
 pub async fn msg_update_solo_machine_client<'e>(
     executor: impl Executor<'e, Database = Db>,
     signer: impl Signer,
+    plugins: &Plugins,
     chain: &mut Chain,
     new_public_key: Option<&PublicKey>,
     memo: String,
@@ -141,20 +172,21 @@ pub async fn msg_update_solo_machine_client<'e>(
 
     let sequence = chain.sequence.into();
 
-    let any_public_key = match new_public_key {
-        Some(new_public_key) => new_public_key.to_any()?,
-        None => signer.to_public_key()?.to_any()?,
+    let new_public_key = match new_public_key {
+        Some(new_public_key) => new_public_key.clone(),
+        None => signer.to_public_key()?,
     };
+    let any_public_key = new_public_key.to_any()?;
 
     let signature = get_header_proof(
         &signer,
         chain,
-        Some(any_public_key.clone()),
+        new_public_key,
         chain.config.diversifier.clone(),
     )
     .await?;
 
-    *chain = chain::increment_sequence(executor, &chain.id).await?;
+    *chain = chain::consume_sequences(executor, &chain.id, chain.sequence, &[&signature]).await?;
 
     let header = SoloMachineHeader {
         sequence,
@@ -164,7 +196,9 @@ pub async fn msg_update_solo_machine_client<'e>(
         new_diversifier: chain.config.diversifier.clone(),
     };
 
-    let any_header = header.to_any()?;
+    let any_header = encode_client_type(plugins, chain, |encoder| {
+        encoder.encode_header(chain, &header)
+    })?;
 
     let connection_details = chain.connection_details.as_ref().ok_or_else(|| {
         anyhow!(
@@ -176,10 +210,10 @@ pub async fn msg_update_solo_machine_client<'e>(
     let message = MsgUpdateClient {
         client_id: connection_details.solo_machine_client_id.to_string(),
         header: Some(any_header),
-        signer: signer.to_account_address()?,
+        signer: chain.account_address(&signer)?,
     };
 
-    build(signer, chain, &[message], memo, None).await
+    build(signer, plugins, chain, &[message], memo, None).await
 }
 
 /// Builds a transaction to create a tendermint client on IBC enabled solo machine
@@ -193,7 +227,7 @@ pub async fn msg_create_tendermint_client(
     });
 
     let unbonding_period = Some(get_unbonding_period(chain).await?);
-    let latest_header = get_latest_header(instance)?;
+    let latest_header = get_latest_header(chain, instance).await?;
     let latest_height = get_block_height(chain, &latest_header);
 
     let client_state = TendermintClientState {
@@ -217,6 +251,7 @@ pub async fn msg_create_tendermint_client(
 
 pub async fn msg_connection_open_init(
     signer: impl Signer,
+    plugins: &Plugins,
     chain: &Chain,
     solo_machine_client_id: &ClientId,
     tendermint_client_id: &ClientId,
@@ -236,15 +271,18 @@ pub async fn msg_connection_open_init(
             features: vec!["ORDER_ORDERED".to_string(), "ORDER_UNORDERED".to_string()],
         }),
         delay_period: 0,
-        signer: signer.to_account_address()?,
+        signer: chain.account_address(&signer)?,
     };
 
-    build(signer, chain, &[message], memo, None).await
+    build(signer, plugins, chain, &[message], memo, None).await
 }
 
+#[tracing::instrument(skip_all, fields(chain_id = %chain.id))]
+#[allow(clippy::too_many_arguments)]
 pub async fn msg_connection_open_ack(
     transaction: &mut Transaction<'_, Db>,
     signer: impl Signer,
+    plugins: &Plugins,
     chain: &mut Chain,
     solo_machine_connection_id: &ConnectionId,
     tendermint_client_id: &ClientId,
@@ -256,19 +294,66 @@ pub async fn msg_connection_open_ack(
             .await?
             .ok_or_else(|| anyhow!("client for client id {} not found", tendermint_client_id))?;
 
-    let proof_height = Height::new(0, chain.sequence.into());
+    let tendermint_connection =
+        ibc_handler::get_connection(&mut *transaction, tendermint_connection_id)
+            .await?
+            .ok_or_else(|| anyhow!("connection with id {} not found", tendermint_connection_id))?;
 
-    let proof_try =
-        get_connection_proof(&mut *transaction, &signer, chain, tendermint_connection_id).await?;
-    *chain = chain::increment_sequence(&mut *transaction, &chain.id).await?;
+    let consensus_height = tendermint_client_state
+        .latest_height
+        .clone()
+        .ok_or_else(|| anyhow!("client state does not contain latest height"))?;
 
-    let proof_client =
-        get_client_proof(&mut *transaction, &signer, chain, tendermint_client_id).await?;
-    *chain = chain::increment_sequence(&mut *transaction, &chain.id).await?;
+    let tendermint_consensus_state = ibc_handler::get_tendermint_consensus_state(
+        &mut *transaction,
+        tendermint_client_id,
+        &consensus_height,
+    )
+    .await?
+    .ok_or_else(|| {
+        anyhow!(
+            "consensus state with id {} and height {} not found",
+            tendermint_client_id,
+            consensus_height.to_string(),
+        )
+    })?;
 
-    let proof_consensus =
-        get_consensus_proof(&mut *transaction, &signer, chain, tendermint_client_id).await?;
-    *chain = chain::increment_sequence(&mut *transaction, &chain.id).await?;
+    let sequence: u64 = chain.sequence.into();
+    let proof_height = Height::new(0, sequence);
+
+    // sequences of all the proofs are reserved up front (consecutive sequences starting from current one), so, they
+    // can be given to signer in one batch and signed concurrently
+    let sign_bytes = vec![
+        connection_state_sign_bytes(
+            chain,
+            sequence,
+            tendermint_connection_id,
+            tendermint_connection,
+        )?,
+        client_state_sign_bytes(
+            chain,
+            sequence + 1,
+            tendermint_client_id,
+            &tendermint_client_state,
+        )?,
+        consensus_state_sign_bytes(
+            chain,
+            sequence + 2,
+            tendermint_client_id,
+            &consensus_height,
+            &tendermint_consensus_state,
+        )?,
+    ];
+
+    let proofs = timestamped_sign_batch(&signer, chain, sign_bytes, None).await?;
+
+    // reserved sequences are consumed all at once only after all the proofs are signed
+    *chain =
+        chain::consume_sequences(&mut *transaction, &chain.id, chain.sequence, &proofs).await?;
+
+    let [proof_try, proof_client, proof_consensus]: [Vec<u8>; 3] = proofs
+        .try_into()
+        .map_err(|_| anyhow!("unexpected number of proofs for `MsgConnectionOpenAck`"))?;
 
     let message = MsgConnectionOpenAck {
         connection_id: solo_machine_connection_id.to_string(),
@@ -282,15 +367,16 @@ pub async fn msg_connection_open_ack(
         proof_try,
         proof_client,
         proof_consensus,
-        consensus_height: tendermint_client_state.latest_height,
-        signer: signer.to_account_address()?,
+        consensus_height: Some(consensus_height),
+        signer: chain.account_address(&signer)?,
     };
 
-    build(signer, chain, &[message], memo, None).await
+    build(signer, plugins, chain, &[message], memo, None).await
 }
 
 pub async fn msg_channel_open_init(
     signer: impl Signer,
+    plugins: &Plugins,
     chain: &Chain,
     solo_machine_connection_id: &ConnectionId,
     memo: String,
@@ -307,15 +393,17 @@ pub async fn msg_channel_open_init(
             connection_hops: vec![solo_machine_connection_id.to_string()],
             version: "ics20-1".to_string(),
         }),
-        signer: signer.to_account_address()?,
+        signer: chain.account_address(&signer)?,
     };
 
-    build(signer, chain, &[message], memo, None).await
+    build(signer, plugins, chain, &[message], memo, None).await
 }
 
+#[tracing::instrument(skip_all, fields(chain_id = %chain.id))]
 pub async fn msg_channel_open_ack(
     transaction: &mut Transaction<'_, Db>,
     signer: impl Signer,
+    plugins: &Plugins,
     chain: &mut Chain,
     solo_machine_channel_id: &ChannelId,
     tendermint_channel_id: &ChannelId,
@@ -325,7 +413,8 @@ pub async fn msg_channel_open_ack(
 
     let proof_try =
         get_channel_proof(&mut *transaction, &signer, chain, tendermint_channel_id).await?;
-    *chain = chain::increment_sequence(&mut *transaction, &chain.id).await?;
+    *chain = chain::consume_sequences(&mut *transaction, &chain.id, chain.sequence, &[&proof_try])
+        .await?;
 
     let message = MsgChannelOpenAck {
         port_id: chain.config.port_id.to_string(),
@@ -334,16 +423,17 @@ pub async fn msg_channel_open_ack(
         counterparty_version: "ics20-1".to_string(),
         proof_height: Some(proof_height),
         proof_try,
-        signer: signer.to_account_address()?,
+        signer: chain.account_address(&signer)?,
     };
 
-    build(signer, chain, &[message], memo, None).await
+    build(signer, plugins, chain, &[message], memo, None).await
 }
 
 #[allow(clippy::too_many_arguments)]
 pub async fn msg_token_send<C>(
     transaction: &mut Transaction<'_, Db>,
     signer: impl Signer,
+    plugins: &Plugins,
     rpc_client: &C,
     chain: &mut Chain,
     amount: u64,
@@ -362,7 +452,7 @@ where
         )
     })?;
 
-    let sender = signer.to_account_address()?;
+    let sender = chain.account_address(&signer)?;
 
     let packet_data = TokenTransferPacketData {
         denom: denom.to_string(),
@@ -391,7 +481,13 @@ where
 
     let proof_height = Height::new(0, chain.sequence.into());
 
-    *chain = chain::increment_sequence(&mut *transaction, &chain.id).await?;
+    *chain = chain::consume_sequences(
+        &mut *transaction,
+        &chain.id,
+        chain.sequence,
+        &[&proof_commitment],
+    )
+    .await?;
     *chain = chain::increment_packet_sequence(&mut *transaction, &chain.id).await?;
 
     let message = MsgRecvPacket {
@@ -401,11 +497,13 @@ where
         signer: sender,
     };
 
-    build(signer, chain, &[message], memo, request_id).await
+    build(signer, plugins, chain, &[message], memo, request_id).await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn msg_token_receive(
     signer: impl Signer,
+    plugins: &Plugins,
     chain: &Chain,
     amount: u64,
     denom: &Identifier,
@@ -427,7 +525,7 @@ pub async fn msg_token_receive(
         )
     })?;
 
-    let sender = signer.to_account_address()?;
+    let sender = chain.account_address(&signer)?;
 
     let message = MsgTransfer {
         source_port: chain.config.port_id.to_string(),
@@ -442,12 +540,13 @@ pub async fn msg_token_receive(
         timeout_timestamp: 0,
     };
 
-    build(signer, chain, &[message], memo, request_id).await
+    build(signer, plugins, chain, &[message], memo, request_id).await
 }
 
 pub async fn msg_token_receive_ack<'e>(
     executor: impl Executor<'e, Database = Db>,
     signer: impl Signer,
+    plugins: &Plugins,
     chain: &mut Chain,
     packet: Packet,
     memo: String,
@@ -465,21 +564,125 @@ pub async fn msg_token_receive_ack<'e>(
     )
     .await?;
 
-    *chain = chain::increment_sequence(executor, &chain.id).await?;
+    *chain = chain::consume_sequences(executor, &chain.id, chain.sequence, &[&proof_acked]).await?;
 
     let message = MsgAcknowledgement {
         packet: Some(packet),
         acknowledgement,
         proof_acked,
         proof_height: Some(proof_height),
-        signer: signer.to_account_address()?,
+        signer: chain.account_address(&signer)?,
+    };
+
+    build(signer, plugins, chain, &[message], memo, request_id).await
+}
+
+/// Builds `MsgTimeout` for a packet sent to solo machine whose timeout has passed on solo machine (proves absence of
+/// packet receipt on solo machine, so that the packet is timed out on chain and its sender is refunded)
+pub async fn msg_timeout<'e>(
+    executor: impl Executor<'e, Database = Db>,
+    signer: impl Signer,
+    plugins: &Plugins,
+    chain: &mut Chain,
+    packet: Packet,
+    memo: String,
+    request_id: Option<&str>,
+) -> Result<TxRaw> {
+    let proof_height = Height::new(0, chain.sequence.into());
+
+    let proof_unreceived =
+        get_packet_receipt_absence_proof(&signer, chain, packet.sequence, request_id).await?;
+
+    *chain =
+        chain::consume_sequences(executor, &chain.id, chain.sequence, &[&proof_unreceived]).await?;
+
+    let message = MsgTimeout {
+        next_sequence_recv: packet.sequence,
+        packet: Some(packet),
+        proof_unreceived,
+        proof_height: Some(proof_height),
+        signer: chain.account_address(&signer)?,
     };
 
-    build(signer, chain, &[message], memo, request_id).await
+    build(signer, plugins, chain, &[message], memo, request_id).await
+}
+
+/// Builds a transaction granting a basic fee allowance from `granter` to `grantee`
+#[allow(clippy::too_many_arguments)]
+pub async fn msg_grant_allowance(
+    granter: impl Signer,
+    plugins: &Plugins,
+    chain: &Chain,
+    grantee: String,
+    spend_limit: Option<Decimal>,
+    expiration: Option<DateTime<Utc>>,
+    memo: String,
+    request_id: Option<&str>,
+) -> Result<TxRaw> {
+    let allowance = BasicAllowance {
+        spend_limit: spend_limit
+            .map(|spend_limit| Coin {
+                denom: chain.config.fee.denom.to_string(),
+                amount: spend_limit.to_string(),
+            })
+            .into_iter()
+            .collect(),
+        expiration: expiration.map(|expiration| SystemTime::from(expiration).into()),
+    };
+
+    let message = MsgGrantAllowance {
+        granter: chain.account_address(&granter)?,
+        grantee,
+        allowance: Some(allowance.to_any()?),
+    };
+
+    build(granter, plugins, chain, &[message], memo, request_id).await
+}
+
+/// Builds a transaction revoking fee allowance granted by `granter` to `grantee`
+pub async fn msg_revoke_allowance(
+    granter: impl Signer,
+    plugins: &Plugins,
+    chain: &Chain,
+    grantee: String,
+    memo: String,
+    request_id: Option<&str>,
+) -> Result<TxRaw> {
+    let message = MsgRevokeAllowance {
+        granter: chain.account_address(&granter)?,
+        grantee,
+    };
+
+    build(granter, plugins, chain, &[message], memo, request_id).await
+}
+
+/// Builds a transaction sending `amount` tokens of `denom` from `signer` to `to_address`
+#[allow(clippy::too_many_arguments)]
+pub async fn msg_send(
+    signer: impl Signer,
+    plugins: &Plugins,
+    chain: &Chain,
+    to_address: String,
+    amount: u64,
+    denom: String,
+    memo: String,
+    request_id: Option<&str>,
+) -> Result<TxRaw> {
+    let message = MsgSend {
+        from_address: chain.account_address(&signer)?,
+        to_address,
+        amount: vec![Coin {
+            denom,
+            amount: amount.to_string(),
+        }],
+    };
+
+    build(signer, plugins, chain, &[message], memo, request_id).await
 }
 
 async fn build<T>(
     signer: impl Signer,
+    plugins: &Plugins,
     chain: &Chain,
     messages: &[T],
     memo: String,
@@ -493,26 +696,168 @@ where
 
     let (account_number, account_sequence) = get_account_details(&signer, chain).await?;
 
-    let auth_info =
-        build_auth_info(&signer, chain, account_sequence).context("unable to build auth info")?;
+    let fee_payer = match signer.fee_payer() {
+        Some(fee_payer) => {
+            let (payer_account_number, payer_account_sequence) =
+                get_account_details(fee_payer, chain).await?;
+            Some((fee_payer, payer_account_number, payer_account_sequence))
+        }
+        None => None,
+    };
+
+    let fee_payer_sequence = fee_payer.map(|(fee_payer, _, sequence)| (fee_payer, sequence));
+
+    let auth_info = build_auth_info(
+        &signer,
+        fee_payer_sequence,
+        chain,
+        &chain.config.fee,
+        account_sequence,
+    )
+    .context("unable to build auth info")?;
+
+    let fee = compute_fee(plugins, chain, &tx_body, &auth_info).await?;
+
+    let auth_info = build_auth_info(&signer, fee_payer_sequence, chain, &fee, account_sequence)
+        .context("unable to build auth info")?;
     let auth_info_bytes = proto_encode(&auth_info)?;
 
-    let signature = build_signature(
-        signer,
-        tx_body_bytes.clone(),
-        auth_info_bytes.clone(),
-        chain.id.to_string(),
+    let unsigned_tx = UnsignedTx {
+        messages,
+        tx_body: &tx_body,
+        tx_body_bytes: &tx_body_bytes,
+        auth_info: &auth_info,
+        auth_info_bytes: &auth_info_bytes,
+    };
+
+    let mut signatures = vec![build_signature(
+        &signer,
+        chain,
+        &unsigned_tx,
         account_number,
+        account_sequence,
         request_id,
     )
     .await
-    .context("unable to sign transaction")?;
+    .context("unable to sign transaction")?];
+
+    // fee payer is always the last signer of transaction
+    if let Some((fee_payer, payer_account_number, payer_account_sequence)) = fee_payer {
+        signatures.push(
+            build_signature(
+                fee_payer,
+                chain,
+                &unsigned_tx,
+                payer_account_number,
+                payer_account_sequence,
+                request_id,
+            )
+            .await
+            .context("unable to sign transaction with fee payer")?,
+        );
+    }
 
-    Ok(TxRaw {
+    // EIP-712 signature is verified from extension option of transaction body (which is not a part of sign doc)
+    #[cfg(feature = "ethermint")]
+    let tx_body_bytes = match chain.config.sign_mode {
+        TxSignMode::Eip712 => build_web3_tx_body(tx_body, &signer, chain, &signatures[0])?,
+        _ => tx_body_bytes,
+    };
+
+    let tx_raw = TxRaw {
         body_bytes: tx_body_bytes,
         auth_info_bytes,
-        signatures: vec![signature],
-    })
+        signatures,
+    };
+
+    if tracing::enabled!(tracing::Level::DEBUG) {
+        match json::tx_raw_to_proto_json(&tx_raw) {
+            Ok(tx) => tracing::debug!(chain_id = %chain.id, %tx, "built transaction"),
+            Err(err) => {
+                tracing::debug!(chain_id = %chain.id, "unable to render transaction: {:?}", err)
+            }
+        }
+    }
+
+    Ok(tx_raw)
+}
+
+/// Rebuilds and re-signs a transaction using latest account details of signer (and fee payer), keeping its messages
+/// and memo as is (solo machine proofs included in messages are not signed again)
+pub async fn rebuild(
+    signer: impl Signer,
+    plugins: &Plugins,
+    chain: &Chain,
+    tx: &TxRaw,
+    request_id: Option<&str>,
+) -> Result<TxRaw> {
+    let tx_body = TxBody::decode(tx.body_bytes.as_slice()).context("invalid transaction body")?;
+
+    // extension options (i.e., EIP-712 signature) are added to transaction body after signing
+    let messages = tx_body
+        .messages
+        .into_iter()
+        .map(EncodedMessage)
+        .collect::<Vec<_>>();
+
+    build(signer, plugins, chain, &messages, tx_body.memo, request_id).await
+}
+
+/// Message of a transaction which is already encoded as `Any`
+struct EncodedMessage(Any);
+
+impl AnyConvert for EncodedMessage {
+    fn from_any(value: &Any) -> Result<Self> {
+        Ok(Self(value.clone()))
+    }
+
+    fn to_any(&self) -> Result<Any> {
+        Ok(self.0.clone())
+    }
+
+    fn to_amino_json(&self) -> Result<Value> {
+        match self.0.type_url.as_str() {
+            "/ibc.applications.transfer.v1.MsgTransfer" => {
+                MsgTransfer::from_any(&self.0)?.to_amino_json()
+            }
+            "/cosmos.bank.v1beta1.MsgSend" => MsgSend::from_any(&self.0)?.to_amino_json(),
+            "/cosmos.feegrant.v1beta1.MsgGrantAllowance" => {
+                MsgGrantAllowance::from_any(&self.0)?.to_amino_json()
+            }
+            "/cosmos.feegrant.v1beta1.MsgRevokeAllowance" => {
+                MsgRevokeAllowance::from_any(&self.0)?.to_amino_json()
+            }
+            "/ibc.core.client.v1.MsgCreateClient" => {
+                MsgCreateClient::from_any(&self.0)?.to_amino_json()
+            }
+            "/ibc.core.client.v1.MsgUpdateClient" => {
+                MsgUpdateClient::from_any(&self.0)?.to_amino_json()
+            }
+            "/ibc.core.connection.v1.MsgConnectionOpenInit" => {
+                MsgConnectionOpenInit::from_any(&self.0)?.to_amino_json()
+            }
+            "/ibc.core.connection.v1.MsgConnectionOpenAck" => {
+                MsgConnectionOpenAck::from_any(&self.0)?.to_amino_json()
+            }
+            "/ibc.core.channel.v1.MsgChannelOpenInit" => {
+                MsgChannelOpenInit::from_any(&self.0)?.to_amino_json()
+            }
+            "/ibc.core.channel.v1.MsgChannelOpenAck" => {
+                MsgChannelOpenAck::from_any(&self.0)?.to_amino_json()
+            }
+            "/ibc.core.channel.v1.MsgRecvPacket" => {
+                MsgRecvPacket::from_any(&self.0)?.to_amino_json()
+            }
+            "/ibc.core.channel.v1.MsgAcknowledgement" => {
+                MsgAcknowledgement::from_any(&self.0)?.to_amino_json()
+            }
+            "/ibc.core.channel.v1.MsgTimeout" => MsgTimeout::from_any(&self.0)?.to_amino_json(),
+            type_url => bail!(
+                "legacy amino JSON encoding is not supported for `{}`",
+                type_url
+            ),
+        }
+    }
 }
 
 fn build_tx_body<T>(messages: &[T], memo: String) -> Result<TxBody>
@@ -533,75 +878,304 @@ where
     })
 }
 
+/// Encodes a solo machine client type using client encoding of chain (client types are converted to the revision
+/// expected by chain unless they're encoded by a client encoder plugin)
+fn encode_client_type<T>(
+    plugins: &Plugins,
+    chain: &Chain,
+    encode: impl FnOnce(&dyn ClientEncoder) -> Result<T>,
+) -> Result<T> {
+    let revision = chain.config.solo_machine_revision();
+
+    let standard = RevisionClientEncoder {
+        revision,
+        client_state_type_url: None,
+        consensus_state_type_url: None,
+        header_type_url: None,
+    };
+
+    match chain.config.client_encoding {
+        ClientEncoding::Standard if revision == default_solo_machine_revision() => {
+            encode(&StandardClientEncoder)
+        }
+        ClientEncoding::Standard => encode(&standard),
+        ClientEncoding::Custom {
+            ref client_state_type_url,
+            ref consensus_state_type_url,
+            ref header_type_url,
+        } if revision != default_solo_machine_revision() => encode(&RevisionClientEncoder {
+            revision,
+            client_state_type_url: client_state_type_url.as_deref(),
+            consensus_state_type_url: consensus_state_type_url.as_deref(),
+            header_type_url: header_type_url.as_deref(),
+        }),
+        ClientEncoding::Custom {
+            ref client_state_type_url,
+            ref consensus_state_type_url,
+            ref header_type_url,
+        } => encode(&TypeUrlClientEncoder {
+            client_state_type_url: client_state_type_url.as_deref(),
+            consensus_state_type_url: consensus_state_type_url.as_deref(),
+            header_type_url: header_type_url.as_deref(),
+        }),
+        ClientEncoding::Plugin => encode(plugins.client_encoder(&chain.id).ok_or_else(|| {
+            anyhow!(
+                "chain {} uses `plugin` client encoding but no client encoder is registered for it",
+                chain.id
+            )
+        })?),
+        ClientEncoding::Wasm { ref checksum } => {
+            let checksum = hex::decode(checksum).context("invalid checksum of wasm client code")?;
+
+            encode(&WasmClientEncoder {
+                inner: &standard,
+                checksum: &checksum,
+            })
+        }
+    }
+    .context("unable to encode solo machine client type")
+}
+
+/// Computes fee of transaction using fee strategy of chain
+async fn compute_fee(
+    plugins: &Plugins,
+    chain: &Chain,
+    tx_body: &TxBody,
+    auth_info: &AuthInfo,
+) -> Result<FeeConfig> {
+    let request = FeeRequest { tx_body, auth_info };
+
+    match chain.config.fee_strategy {
+        FeeStrategyKind::Static => StaticFeeStrategy.compute_fee(chain, request).await,
+        FeeStrategyKind::Simulate { gas_adjustment } => {
+            SimulateFeeStrategy { gas_adjustment }
+                .compute_fee(chain, request)
+                .await
+        }
+        FeeStrategyKind::Plugin => {
+            plugins
+                .fee_strategy()
+                .ok_or_else(|| {
+                    anyhow!(
+                    "chain {} uses `plugin` fee strategy but no fee strategy plugin is registered",
+                    chain.id
+                )
+                })?
+                .compute_fee(chain, request)
+                .await
+        }
+    }
+    .context("unable to compute fee of transaction")
+}
+
 fn build_auth_info(
     signer: impl ToPublicKey,
+    fee_payer: Option<(&dyn Signer, u64)>,
     chain: &Chain,
+    fee: &FeeConfig,
     account_sequence: u64,
 ) -> Result<AuthInfo> {
-    let signer_info = SignerInfo {
-        public_key: Some(signer.to_public_key()?.to_any()?),
-        mode_info: Some(ModeInfo {
-            sum: Some(Sum::Single(Single { mode: 1 })),
-        }),
-        sequence: account_sequence,
+    let mut signer_infos = vec![build_signer_info(&signer, chain, account_sequence)?];
+
+    let (payer, paying_account) = match fee_payer {
+        Some((fee_payer, payer_account_sequence)) => {
+            signer_infos.push(build_signer_info(fee_payer, chain, payer_account_sequence)?);
+            let payer = chain.account_address(fee_payer)?;
+            (payer.clone(), payer)
+        }
+        None => ("".to_owned(), chain.account_address(&signer)?),
+    };
+
+    // fee allowance is only used when fees are not paid by the granter itself (e.g. when granting allowance)
+    let granter = match fee.granter {
+        Some(ref granter) if granter != &paying_account => granter.clone(),
+        _ => "".to_owned(),
     };
 
     let fee = Fee {
         amount: vec![Coin {
-            denom: chain.config.fee.denom.to_string(),
-            amount: chain.config.fee.amount.to_string(),
+            denom: fee.denom.to_string(),
+            amount: fee.amount.to_string(),
         }],
-        gas_limit: chain.config.fee.gas_limit,
-        payer: "".to_owned(),
-        granter: "".to_owned(),
+        gas_limit: fee.gas_limit,
+        payer,
+        granter,
     };
 
     Ok(AuthInfo {
-        signer_infos: vec![signer_info],
+        signer_infos,
         fee: Some(fee),
     })
 }
 
-async fn build_signature(
+fn build_signer_info(
+    signer: impl ToPublicKey,
+    chain: &Chain,
+    account_sequence: u64,
+) -> Result<SignerInfo> {
+    Ok(SignerInfo {
+        public_key: Some(chain.account_public_key(signer)?.to_any()?),
+        mode_info: Some(ModeInfo {
+            sum: Some(Sum::Single(Single {
+                mode: chain.config.sign_mode.mode_info_value(),
+            })),
+        }),
+        sequence: account_sequence,
+    })
+}
+
+/// Transaction (along with its encoded parts) which is yet to be signed
+struct UnsignedTx<'a, T> {
+    messages: &'a [T],
+    tx_body: &'a TxBody,
+    tx_body_bytes: &'a [u8],
+    auth_info: &'a AuthInfo,
+    auth_info_bytes: &'a [u8],
+}
+
+#[tracing::instrument(skip_all)]
+async fn build_signature<T>(
     signer: impl Signer,
-    body_bytes: Vec<u8>,
-    auth_info_bytes: Vec<u8>,
-    chain_id: String,
+    chain: &Chain,
+    unsigned_tx: &UnsignedTx<'_, T>,
     account_number: u64,
+    account_sequence: u64,
     request_id: Option<&str>,
-) -> Result<Vec<u8>> {
-    let sign_doc = SignDoc {
-        body_bytes,
-        auth_info_bytes,
-        chain_id,
-        account_number,
-    };
+) -> Result<Vec<u8>>
+where
+    T: AnyConvert,
+{
+    match chain.config.sign_mode {
+        TxSignMode::Direct => {
+            let sign_doc = SignDoc {
+                body_bytes: unsigned_tx.tx_body_bytes.to_vec(),
+                auth_info_bytes: unsigned_tx.auth_info_bytes.to_vec(),
+                chain_id: chain.id.to_string(),
+                account_number,
+            };
+
+            let sign_doc_bytes = proto_encode(&sign_doc)?;
+
+            signer
+                .sign(request_id, Message::SignDoc(&sign_doc_bytes))
+                .await
+        }
+        TxSignMode::LegacyAminoJson => {
+            let sign_doc =
+                build_amino_sign_doc(chain, unsigned_tx, account_number, account_sequence)?;
+            let sign_doc_bytes = amino::canonical_json(sign_doc)?;
+
+            signer
+                .sign(request_id, Message::AminoJsonSignDoc(&sign_doc_bytes))
+                .await
+        }
+        #[cfg(feature = "ethermint")]
+        TxSignMode::Eip712 => {
+            let fee = unsigned_tx
+                .auth_info
+                .fee
+                .as_ref()
+                .ok_or_else(|| anyhow!("missing fee in auth info"))?;
+
+            ensure!(
+                fee.payer.is_empty() && fee.granter.is_empty(),
+                "fee payer and fee granter are not supported with EIP-712 signing"
+            );
+
+            let sign_doc =
+                build_amino_sign_doc(chain, unsigned_tx, account_number, account_sequence)?;
+            let sign_bytes = eip712::sign_bytes(
+                eip712::parse_eip155_chain_id(&chain.id.to_string())?,
+                &sign_doc,
+                &chain.account_address(&signer)?,
+            )?;
+
+            let signature = signer
+                .sign(request_id, Message::Eip712(&sign_bytes))
+                .await?;
+
+            let public_key = match chain.account_public_key(&signer)? {
+                PublicKey::EthSecp256k1(public_key) => public_key,
+                _ => bail!("EIP-712 signing is only supported for eth-secp256k1 keys"),
+            };
+
+            eip712::to_recoverable_signature(&public_key, &sign_bytes, &signature)
+        }
+    }
+}
+
+fn build_amino_sign_doc<T>(
+    chain: &Chain,
+    unsigned_tx: &UnsignedTx<'_, T>,
+    account_number: u64,
+    account_sequence: u64,
+) -> Result<Value>
+where
+    T: AnyConvert,
+{
+    let msgs = unsigned_tx
+        .messages
+        .iter()
+        .map(AnyConvert::to_amino_json)
+        .collect::<Result<Vec<_>>>()?;
 
-    let sign_doc_bytes = proto_encode(&sign_doc)?;
+    let fee = unsigned_tx
+        .auth_info
+        .fee
+        .as_ref()
+        .ok_or_else(|| anyhow!("missing fee in auth info"))?;
 
-    signer
-        .sign(request_id, Message::SignDoc(&sign_doc_bytes))
-        .await
+    Ok(amino::std_sign_doc(
+        &chain.id.to_string(),
+        account_number,
+        account_sequence,
+        fee,
+        msgs,
+        &unsigned_tx.tx_body.memo,
+    ))
 }
 
-async fn get_account_details(signer: impl ToPublicKey, chain: &Chain) -> Result<(u64, u64)> {
-    let mut query_client = AuthQueryClient::connect(chain.config.grpc_addr.clone())
-        .await
-        .context(format!(
-            "unable to connect to grpc query client at {}",
-            chain.config.grpc_addr
-        ))?;
+/// Adds `ExtensionOptionsWeb3Tx` (containing EIP-712 signature of signer) to transaction body and returns its encoded
+/// bytes
+#[cfg(feature = "ethermint")]
+fn build_web3_tx_body(
+    mut tx_body: TxBody,
+    signer: impl ToPublicKey,
+    chain: &Chain,
+    signature: &[u8],
+) -> Result<Vec<u8>> {
+    let extension_option = ExtensionOptionsWeb3Tx {
+        typed_data_chain_id: eip712::parse_eip155_chain_id(&chain.id.to_string())?,
+        fee_payer: chain.account_address(&signer)?,
+        fee_payer_sig: signature.to_vec(),
+    };
 
-    let account_address = signer.to_account_address()?;
+    tx_body.extension_options.push(extension_option.to_any()?);
 
-    let response = query_client
-        .account(QueryAccountRequest {
-            address: account_address.clone(),
-        })
-        .await?
-        .into_inner()
-        .account
-        .ok_or_else(|| anyhow!("unable to find account with address: {}", account_address))?;
+    proto_encode(&tx_body)
+}
+
+pub(crate) async fn get_account_details(
+    signer: impl ToPublicKey,
+    chain: &Chain,
+) -> Result<(u64, u64)> {
+    let config = &chain.config;
+    let account_address = chain.account_address(&signer)?;
+
+    let response = grpc_query(&chain.config, "account query", |grpc_addr| {
+        let address = account_address.clone();
+
+        async move {
+            AuthQueryClient::new(grpc_channel(&grpc_addr, config).await?)
+                .account(QueryAccountRequest { address })
+                .await
+                .with_context(|| format!("unable to query account from {}", grpc_addr))
+        }
+    })
+    .await?
+    .into_inner()
+    .account
+    .ok_or_else(|| anyhow!("unable to find account with address: {}", account_address))?;
 
     let account = Account::from_any(&response)?;
     let base_account = account
@@ -611,29 +1185,41 @@ async fn get_account_details(signer: impl ToPublicKey, chain: &Chain) -> Result<
     Ok((base_account.account_number, base_account.sequence))
 }
 
-async fn get_unbonding_period(chain: &Chain) -> Result<Duration> {
-    let mut query_client = StakingQueryClient::connect(chain.config.grpc_addr.clone())
-        .await
-        .context(format!(
-            "unable to connect to grpc query client at {}",
-            chain.config.grpc_addr
-        ))?;
-
-    query_client
-        .params(QueryParamsRequest::default())
-        .await?
-        .into_inner()
-        .params
-        .ok_or_else(|| anyhow!("staking params are empty"))?
-        .unbonding_time
-        .ok_or_else(|| anyhow!("missing unbonding period in staking params"))
+pub(crate) async fn get_unbonding_period(chain: &Chain) -> Result<Duration> {
+    let config = &chain.config;
+
+    grpc_query(
+        &chain.config,
+        "staking params query",
+        |grpc_addr| async move {
+            StakingQueryClient::new(grpc_channel(&grpc_addr, config).await?)
+                .params(QueryParamsRequest::default())
+                .await
+                .with_context(|| format!("unable to query staking params from {}", grpc_addr))
+        },
+    )
+    .await?
+    .into_inner()
+    .params
+    .ok_or_else(|| anyhow!("staking params are empty"))?
+    .unbonding_time
+    .ok_or_else(|| anyhow!("missing unbonding period in staking params"))
 }
 
 async fn get_latest_height<C>(chain: &Chain, rpc_client: &C) -> Result<Height>
 where
     C: Client + Send + Sync,
 {
-    let response = rpc_client.status().await?;
+    let response = chain
+        .config
+        .retry_policy
+        .retry("status query", || async {
+            rpc_client
+                .status()
+                .await
+                .context("unable to query status of chain")
+        })
+        .await?;
 
     ensure!(
         !response.sync_info.catching_up,
@@ -657,15 +1243,34 @@ where
     })
 }
 
-fn get_latest_header(instance: &mut Instance) -> Result<Header> {
-    let light_block = instance
-        .light_client
-        .verify_to_highest(&mut instance.state)?;
-
-    Ok(light_block.signed_header.header)
+/// Verifies headers of chain up to its latest block using light client and returns latest verified header (fetching
+/// of blocks is retried according to retry policy of chain)
+pub async fn get_latest_header(chain: &Chain, instance: &mut Instance) -> Result<Header> {
+    let mut attempt = 1;
+
+    loop {
+        match instance.light_client.verify_to_highest(&mut instance.state) {
+            Ok(light_block) => return Ok(light_block.signed_header.header),
+            Err(error) => {
+                let error = anyhow::Error::from(error);
+
+                if !chain
+                    .config
+                    .retry_policy
+                    .wait_before_retry("header verification", attempt, &error)
+                    .await
+                {
+                    return Err(error);
+                }
+
+                attempt += 1;
+            }
+        }
+    }
 }
 
-fn get_block_height(chain: &Chain, header: &Header) -> Height {
+/// Returns IBC height of given block header of chain
+pub fn get_block_height(chain: &Chain, header: &Header) -> Height {
     let revision_number = chain.id.version();
     let revision_height = header.height.value();
 
@@ -675,6 +1280,7 @@ fn get_block_height(chain: &Chain, header: &Header) -> Height {
     }
 }
 
+#[tracing::instrument(skip_all)]
 async fn get_packet_acknowledgement_proof(
     signer: impl Signer,
     chain: &Chain,
@@ -698,24 +1304,53 @@ async fn get_packet_acknowledgement_proof(
     );
     acknowledgement_path.apply_prefix(&"ibc".parse().unwrap());
 
-    let acknowledgement_data = PacketAcknowledgementData {
-        path: acknowledgement_path.into_bytes(),
-        acknowledgement,
-    };
+    let sign_bytes = build_sign_bytes(
+        chain,
+        chain.sequence.into(),
+        &ClaimedState::PacketAcknowledgement {
+            path: acknowledgement_path.into(),
+            acknowledgement,
+        },
+    )?;
 
-    let acknowledgement_data_bytes = proto_encode(&acknowledgement_data)?;
+    timestamped_sign(signer, chain, sign_bytes, request_id).await
+}
 
-    let sign_bytes = SignBytes {
-        sequence: chain.sequence.into(),
-        timestamp: to_u64_timestamp(chain.consensus_timestamp)?,
-        diversifier: chain.config.diversifier.to_owned(),
-        data_type: DataType::PacketAcknowledgement.into(),
-        data: acknowledgement_data_bytes,
-    };
+#[tracing::instrument(skip_all)]
+async fn get_packet_receipt_absence_proof(
+    signer: impl Signer,
+    chain: &Chain,
+    packet_sequence: u64,
+    request_id: Option<&str>,
+) -> Result<Vec<u8>> {
+    let mut receipt_path = PacketReceiptPath::new(
+        &chain.config.port_id,
+        &chain
+            .connection_details
+            .as_ref()
+            .ok_or_else(|| {
+                anyhow!(
+                    "connection details for chain with id {} not found",
+                    chain.id
+                )
+            })?
+            .tendermint_channel_id,
+        packet_sequence,
+    );
+    receipt_path.apply_prefix(&"ibc".parse().unwrap());
+
+    let sign_bytes = build_sign_bytes(
+        chain,
+        chain.sequence.into(),
+        &ClaimedState::PacketReceiptAbsence {
+            path: receipt_path.into(),
+        },
+    )?;
 
     timestamped_sign(signer, chain, sign_bytes, request_id).await
 }
 
+#[tracing::instrument(skip_all)]
 async fn get_packet_commitment_proof(
     signer: impl Signer,
     chain: &Chain,
@@ -740,26 +1375,21 @@ async fn get_packet_commitment_proof(
     );
     commitment_path.apply_prefix(&"ibc".parse().unwrap());
 
-    let packet_commitment_data = PacketCommitmentData {
-        path: commitment_path.into_bytes(),
-        commitment: commitment_bytes,
-    };
-
-    let packet_commitment_data_bytes = proto_encode(&packet_commitment_data)?;
-
-    let sign_bytes = SignBytes {
-        sequence: chain.sequence.into(),
-        timestamp: to_u64_timestamp(chain.consensus_timestamp)?,
-        diversifier: chain.config.diversifier.to_owned(),
-        data_type: DataType::PacketCommitment.into(),
-        data: packet_commitment_data_bytes,
-    };
+    let sign_bytes = build_sign_bytes(
+        chain,
+        chain.sequence.into(),
+        &ClaimedState::PacketCommitment {
+            path: commitment_path.into(),
+            commitment: commitment_bytes,
+        },
+    )?;
 
     timestamped_sign(signer, chain, sign_bytes, request_id).await
 }
 
+#[tracing::instrument(skip_all)]
 async fn get_channel_proof<'e>(
-    executor: impl Executor<'e, Database = Db>,
+    executor: impl ibc_handler::IbcExecutor<'e>,
     signer: impl Signer,
     chain: &Chain,
     channel_id: &ChannelId,
@@ -777,162 +1407,239 @@ async fn get_channel_proof<'e>(
     let mut channel_path = ChannelPath::new(&chain.config.port_id, channel_id);
     channel_path.apply_prefix(&"ibc".parse().unwrap());
 
-    let channel_state_data = ChannelStateData {
-        path: channel_path.into_bytes(),
-        channel: Some(channel),
-    };
-
-    let channel_state_data_bytes = proto_encode(&channel_state_data)?;
-
-    let sign_bytes = SignBytes {
-        sequence: chain.sequence.into(),
-        timestamp: to_u64_timestamp(chain.consensus_timestamp)?,
-        diversifier: chain.config.diversifier.to_owned(),
-        data_type: DataType::ChannelState.into(),
-        data: channel_state_data_bytes,
-    };
+    let sign_bytes = build_sign_bytes(
+        chain,
+        chain.sequence.into(),
+        &ClaimedState::Channel {
+            path: channel_path.into(),
+            channel,
+        },
+    )?;
 
     timestamped_sign(signer, chain, sign_bytes, None).await
 }
 
-async fn get_connection_proof<'e>(
-    executor: impl Executor<'e, Database = Db>,
-    signer: impl Signer,
+/// Builds `SignBytes` for proving connection state at given sequence (pure, so that it can be used with any storage)
+pub(crate) fn connection_state_sign_bytes(
     chain: &Chain,
+    sequence: u64,
     connection_id: &ConnectionId,
+    connection: ConnectionEnd,
 ) -> Result<Vec<u8>> {
-    let connection = ibc_handler::get_connection(executor, connection_id)
-        .await?
-        .ok_or_else(|| anyhow!("connection with id {} not found", connection_id))?;
-
     let mut connection_path = ConnectionPath::new(connection_id);
     connection_path.apply_prefix(&"ibc".parse().unwrap());
 
-    let connection_state_data = ConnectionStateData {
-        path: connection_path.into_bytes(),
-        connection: Some(connection),
-    };
-
-    let connection_state_data_bytes = proto_encode(&connection_state_data)?;
-
-    let sign_bytes = SignBytes {
-        sequence: chain.sequence.into(),
-        timestamp: to_u64_timestamp(chain.consensus_timestamp)?,
-        diversifier: chain.config.diversifier.to_owned(),
-        data_type: DataType::ConnectionState.into(),
-        data: connection_state_data_bytes,
-    };
-
-    timestamped_sign(signer, chain, sign_bytes, None).await
+    build_sign_bytes(
+        chain,
+        sequence,
+        &ClaimedState::Connection {
+            path: connection_path.into(),
+            connection,
+        },
+    )
 }
 
-async fn get_client_proof<'e>(
-    executor: impl Executor<'e, Database = Db>,
-    signer: impl Signer,
+/// Builds `SignBytes` for proving tendermint client state at given sequence
+pub(crate) fn client_state_sign_bytes(
     chain: &Chain,
+    sequence: u64,
     client_id: &ClientId,
+    client_state: &TendermintClientState,
 ) -> Result<Vec<u8>> {
-    let client_state = ibc_handler::get_tendermint_client_state(executor, client_id)
-        .await?
-        .ok_or_else(|| anyhow!("client with id {} not found", client_id))?
-        .to_any()?;
-
     let mut client_state_path = ClientStatePath::new(client_id);
     client_state_path.apply_prefix(&"ibc".parse().unwrap());
 
-    let client_state_data = ClientStateData {
-        path: client_state_path.into_bytes(),
-        client_state: Some(client_state),
-    };
-
-    let client_state_data_bytes = proto_encode(&client_state_data)?;
-
-    let sign_bytes = SignBytes {
-        sequence: chain.sequence.into(),
-        timestamp: to_u64_timestamp(chain.consensus_timestamp)?,
-        diversifier: chain.config.diversifier.to_owned(),
-        data_type: DataType::ClientState.into(),
-        data: client_state_data_bytes,
-    };
-
-    timestamped_sign(signer, chain, sign_bytes, None).await
+    build_sign_bytes(
+        chain,
+        sequence,
+        &ClaimedState::ClientState {
+            path: client_state_path.into(),
+            client_state: client_state.to_any()?,
+        },
+    )
 }
 
-async fn get_consensus_proof(
-    transaction: &mut Transaction<'_, Db>,
-    signer: impl Signer,
+/// Builds `SignBytes` for proving tendermint consensus state (at given height) at given sequence
+pub(crate) fn consensus_state_sign_bytes(
     chain: &Chain,
+    sequence: u64,
     client_id: &ClientId,
+    height: &Height,
+    consensus_state: &TendermintConsensusState,
 ) -> Result<Vec<u8>> {
-    let client_state = ibc_handler::get_tendermint_client_state(&mut *transaction, client_id)
-        .await?
-        .ok_or_else(|| anyhow!("client with id {} not found", client_id))?;
-
-    let height = client_state
-        .latest_height
-        .ok_or_else(|| anyhow!("client state does not contain latest height"))?;
-
-    let consensus_state =
-        ibc_handler::get_tendermint_consensus_state(&mut *transaction, client_id, &height)
-            .await?
-            .ok_or_else(|| {
-                anyhow!(
-                    "consensus state with id {} and height {} not found",
-                    client_id,
-                    height.to_string(),
-                )
-            })?
-            .to_any()?;
-
-    let mut consensus_state_path = ConsensusStatePath::new(client_id, &height);
+    let mut consensus_state_path = ConsensusStatePath::new(client_id, height);
     consensus_state_path.apply_prefix(&"ibc".parse().unwrap());
 
-    let consensus_state_data = ConsensusStateData {
-        path: consensus_state_path.into_bytes(),
-        consensus_state: Some(consensus_state),
-    };
-
-    let consensus_state_data_bytes = proto_encode(&consensus_state_data)?;
-
-    let sign_bytes = SignBytes {
-        sequence: chain.sequence.into(),
-        timestamp: to_u64_timestamp(chain.consensus_timestamp)?,
-        diversifier: chain.config.diversifier.to_owned(),
-        data_type: DataType::ConsensusState.into(),
-        data: consensus_state_data_bytes,
-    };
-
-    timestamped_sign(signer, chain, sign_bytes, None).await
+    build_sign_bytes(
+        chain,
+        sequence,
+        &ClaimedState::ConsensusState {
+            path: consensus_state_path.into(),
+            consensus_state: consensus_state.to_any()?,
+        },
+    )
 }
 
+#[tracing::instrument(skip_all)]
 async fn get_header_proof(
     signer: impl Signer,
     chain: &Chain,
-    new_public_key: Option<Any>,
+    new_public_key: PublicKey,
     new_diversifier: String,
 ) -> Result<Vec<u8>> {
-    let header_data = HeaderData {
-        new_pub_key: new_public_key,
-        new_diversifier,
+    let sign_bytes = build_sign_bytes(
+        chain,
+        chain.sequence.into(),
+        &ClaimedState::Header {
+            new_public_key,
+            new_diversifier,
+        },
+    )?;
+
+    sign(signer, None, sign_bytes).await
+}
+
+/// Builds protobuf encoded `SignBytes` for claimed state at given sequence using revision of solo machine client types
+/// of chain
+fn build_sign_bytes(chain: &Chain, sequence: u64, claimed_state: &ClaimedState) -> Result<Vec<u8>> {
+    claimed_state.sign_bytes(
+        chain.config.solo_machine_revision(),
+        sequence,
+        to_u64_timestamp(chain.consensus_timestamp)?,
+        &chain.config.diversifier,
+    )
+}
+
+/// Decodes protobuf encoded `SignBytes` (of given revision of solo machine client types) for previewing them before
+/// signing
+pub fn decode_sign_bytes(
+    revision: SoloMachineRevision,
+    sign_bytes: Vec<u8>,
+) -> Result<SignBytesPreview> {
+    if revision == SoloMachineRevision::V3 {
+        return decode_sign_bytes_v3(sign_bytes);
+    }
+
+    let decoded = SignBytes::decode(sign_bytes.as_slice()).context("invalid sign bytes")?;
+
+    let data_type = DataType::from_i32(decoded.data_type)
+        .ok_or_else(|| anyhow!("invalid data type: {}", decoded.data_type))?;
+
+    let data = match data_type {
+        DataType::Header => {
+            let header_data = HeaderData::decode(decoded.data.as_slice())?;
+
+            json!({
+                "new_public_key": header_data
+                    .new_pub_key
+                    .as_ref()
+                    .map(PublicKey::from_any)
+                    .transpose()?
+                    .map(|public_key| public_key.encode()),
+                "new_diversifier": header_data.new_diversifier,
+            })
+        }
+        DataType::PacketCommitment => {
+            let commitment_data = PacketCommitmentData::decode(decoded.data.as_slice())?;
+
+            json!({
+                "path": String::from_utf8_lossy(&commitment_data.path),
+                "commitment": hex::encode_upper(&commitment_data.commitment),
+            })
+        }
+        DataType::PacketAcknowledgement => {
+            let acknowledgement_data = PacketAcknowledgementData::decode(decoded.data.as_slice())?;
+
+            json!({
+                "path": String::from_utf8_lossy(&acknowledgement_data.path),
+                "acknowledgement": String::from_utf8_lossy(&acknowledgement_data.acknowledgement),
+            })
+        }
+        _ => json!({ "data": hex::encode_upper(&decoded.data) }),
     };
 
-    let header_data_bytes = proto_encode(&header_data)?;
+    Ok(SignBytesPreview {
+        sequence: decoded.sequence,
+        timestamp: decoded.timestamp,
+        diversifier: decoded.diversifier,
+        data_type: format!("{:?}", data_type),
+        data,
+        sign_bytes,
+    })
+}
 
-    let sign_bytes = SignBytes {
-        sequence: chain.sequence.into(),
-        timestamp: to_u64_timestamp(chain.consensus_timestamp)?,
-        diversifier: chain.config.diversifier.to_owned(),
-        data_type: DataType::Header.into(),
-        data: header_data_bytes,
+/// Decodes `solomachine.v3` `SignBytes` (which contain signed path and value instead of typed data)
+fn decode_sign_bytes_v3(sign_bytes: Vec<u8>) -> Result<SignBytesPreview> {
+    let decoded = SignBytesV3::decode(sign_bytes.as_slice()).context("invalid sign bytes")?;
+    let path = String::from_utf8_lossy(&decoded.path).into_owned();
+
+    let (data_type, data) = if path == SENTINEL_HEADER_PATH {
+        let header_data = HeaderDataV3::decode(decoded.data.as_slice())?;
+
+        (
+            "Header",
+            json!({
+                "new_public_key": header_data
+                    .new_pub_key
+                    .as_ref()
+                    .map(PublicKey::from_any)
+                    .transpose()?
+                    .map(|public_key| public_key.encode()),
+                "new_diversifier": header_data.new_diversifier,
+            }),
+        )
+    } else {
+        // path is prefixed and escaped (e.g. `/ibc/commitments%2Fports%2F...`)
+        let key = path
+            .trim_start_matches('/')
+            .split_once('/')
+            .map(|(_, key)| key)
+            .unwrap_or(&path);
+
+        let data_type = if key.starts_with("commitments") {
+            "PacketCommitment"
+        } else if key.starts_with("acks") {
+            "PacketAcknowledgement"
+        } else if key.starts_with("receipts") {
+            "PacketReceiptAbsence"
+        } else if key.starts_with("nextSequenceRecv") {
+            "NextSequenceRecv"
+        } else if key.starts_with("connections") {
+            "ConnectionState"
+        } else if key.starts_with("channelEnds") {
+            "ChannelState"
+        } else if key.contains("consensusStates") {
+            "ConsensusState"
+        } else if key.starts_with("clients") {
+            "ClientState"
+        } else {
+            "Unknown"
+        };
+
+        (
+            data_type,
+            json!({
+                "path": path,
+                "value": hex::encode_upper(&decoded.data),
+            }),
+        )
     };
 
-    sign(signer, None, sign_bytes).await
+    Ok(SignBytesPreview {
+        sequence: decoded.sequence,
+        timestamp: decoded.timestamp,
+        diversifier: decoded.diversifier,
+        data_type: data_type.to_string(),
+        data,
+        sign_bytes,
+    })
 }
 
+#[tracing::instrument(skip_all, fields(chain_id = %chain.id))]
 async fn timestamped_sign(
     signer: impl Signer,
     chain: &Chain,
-    sign_bytes: SignBytes,
+    sign_bytes: Vec<u8>,
     request_id: Option<&str>,
 ) -> Result<Vec<u8>> {
     let signature_data = sign(signer, request_id, sign_bytes).await?;
@@ -945,12 +1652,53 @@ async fn timestamped_sign(
     proto_encode(&timestamped_signature_data)
 }
 
+/// Signs multiple `SignBytes` of a single operation using one batch request to signer
+#[tracing::instrument(skip_all, fields(chain_id = %chain.id, proofs = sign_bytes.len()))]
+async fn timestamped_sign_batch(
+    signer: impl Signer,
+    chain: &Chain,
+    sign_bytes: Vec<Vec<u8>>,
+    request_id: Option<&str>,
+) -> Result<Vec<Vec<u8>>> {
+    let messages = sign_bytes
+        .iter()
+        .map(|bytes| Message::SignBytes(bytes))
+        .collect::<Vec<_>>();
+
+    let signatures = signer.sign_batch(request_id, &messages).await?;
+
+    ensure!(
+        signatures.len() == messages.len(),
+        "signer returned {} signatures for a batch of {} messages",
+        signatures.len(),
+        messages.len()
+    );
+
+    let timestamp = to_u64_timestamp(chain.consensus_timestamp)?;
+
+    signatures
+        .into_iter()
+        .map(|signature| {
+            let signature_data = proto_encode(&SignatureData {
+                sum: Some(SignatureDataInner::Single(SingleSignatureData {
+                    signature,
+                    mode: SignMode::Unspecified.into(),
+                })),
+            })?;
+
+            proto_encode(&TimestampedSignatureData {
+                signature_data,
+                timestamp,
+            })
+        })
+        .collect()
+}
+
 async fn sign(
     signer: impl Signer,
     request_id: Option<&str>,
-    sign_bytes: SignBytes,
+    sign_bytes: Vec<u8>,
 ) -> Result<Vec<u8>> {
-    let sign_bytes = proto_encode(&sign_bytes)?;
     let signature = signer
         .sign(request_id, Message::SignBytes(&sign_bytes))
         .await?;
@@ -0,0 +1,616 @@
+//! Verification of solo machine proofs (`TimestampedSignatureData`) submitted by external parties
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, bail, Context, Error, Result};
+use cosmos_sdk_proto::{
+    cosmos::{
+        crypto::multisig::v1beta1::CompactBitArray,
+        tx::signing::v1beta1::signature_descriptor::{data::Sum, Data as SignatureData},
+    },
+    ibc::core::{channel::v1::Channel, connection::v1::ConnectionEnd},
+};
+use prost::Message;
+use prost_types::Any;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+// `solomachine.v1` and `solomachine.v2` sign bytes (and signed data) are identical on wire
+use crate::proto::ibc::lightclients::solomachine::v2::{
+    ChannelStateData, ClientStateData, ConnectionStateData, ConsensusStateData, DataType,
+    HeaderData, NextSequenceRecvData, PacketAcknowledgementData, PacketCommitmentData,
+    PacketReceiptAbsenceData, SignBytes, TimestampedSignatureData,
+};
+use crate::{
+    cosmos::crypto::PublicKey,
+    ibc_version::{default_solo_machine_revision, SoloMachineRevision},
+    proto::{
+        ibc::lightclients::solomachine::v3::{
+            HeaderData as HeaderDataV3, SignBytes as SignBytesV3,
+        },
+        proto_encode, AnyConvert,
+    },
+};
+
+/// Path signed in `solomachine.v3` header sign bytes
+pub(crate) const SENTINEL_HEADER_PATH: &str = "solomachine:header";
+
+/// Type of data signed in a solo machine proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProofDataType {
+    /// Client state verification
+    ClientState,
+    /// Consensus state verification
+    ConsensusState,
+    /// Connection state verification
+    ConnectionState,
+    /// Channel state verification
+    ChannelState,
+    /// Packet commitment verification
+    PacketCommitment,
+    /// Packet acknowledgement verification
+    PacketAcknowledgement,
+    /// Packet receipt absence verification
+    PacketReceiptAbsence,
+    /// Next sequence recv verification
+    NextSequenceRecv,
+    /// Header verification
+    Header,
+}
+
+impl ProofDataType {
+    fn to_data_type(self) -> DataType {
+        match self {
+            Self::ClientState => DataType::ClientState,
+            Self::ConsensusState => DataType::ConsensusState,
+            Self::ConnectionState => DataType::ConnectionState,
+            Self::ChannelState => DataType::ChannelState,
+            Self::PacketCommitment => DataType::PacketCommitment,
+            Self::PacketAcknowledgement => DataType::PacketAcknowledgement,
+            Self::PacketReceiptAbsence => DataType::PacketReceiptAbsence,
+            Self::NextSequenceRecv => DataType::NextSequenceRecv,
+            Self::Header => DataType::Header,
+        }
+    }
+}
+
+impl fmt::Display for ProofDataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ClientState => write!(f, "client-state"),
+            Self::ConsensusState => write!(f, "consensus-state"),
+            Self::ConnectionState => write!(f, "connection-state"),
+            Self::ChannelState => write!(f, "channel-state"),
+            Self::PacketCommitment => write!(f, "packet-commitment"),
+            Self::PacketAcknowledgement => write!(f, "packet-acknowledgement"),
+            Self::PacketReceiptAbsence => write!(f, "packet-receipt-absence"),
+            Self::NextSequenceRecv => write!(f, "next-sequence-recv"),
+            Self::Header => write!(f, "header"),
+        }
+    }
+}
+
+impl FromStr for ProofDataType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "client-state" => Ok(Self::ClientState),
+            "consensus-state" => Ok(Self::ConsensusState),
+            "connection-state" => Ok(Self::ConnectionState),
+            "channel-state" => Ok(Self::ChannelState),
+            "packet-commitment" => Ok(Self::PacketCommitment),
+            "packet-acknowledgement" => Ok(Self::PacketAcknowledgement),
+            "packet-receipt-absence" => Ok(Self::PacketReceiptAbsence),
+            "next-sequence-recv" => Ok(Self::NextSequenceRecv),
+            "header" => Ok(Self::Header),
+            _ => Err(anyhow!("invalid proof data type: {}", s)),
+        }
+    }
+}
+
+/// State claimed to be attested by a solo machine proof. Paths are full commitment paths (including commitment
+/// prefix, e.g. `ibc`) as signed by solo machine.
+#[derive(Debug, Clone)]
+pub enum ClaimedState {
+    /// Client state stored at given path
+    ClientState {
+        /// Path of client state
+        path: String,
+        /// Client state
+        client_state: Any,
+    },
+    /// Consensus state stored at given path
+    ConsensusState {
+        /// Path of consensus state
+        path: String,
+        /// Consensus state
+        consensus_state: Any,
+    },
+    /// Connection stored at given path
+    Connection {
+        /// Path of connection
+        path: String,
+        /// Connection
+        connection: ConnectionEnd,
+    },
+    /// Channel stored at given path
+    Channel {
+        /// Path of channel
+        path: String,
+        /// Channel
+        channel: Channel,
+    },
+    /// Packet commitment stored at given path
+    PacketCommitment {
+        /// Path of packet commitment
+        path: String,
+        /// Packet commitment
+        commitment: Vec<u8>,
+    },
+    /// Packet acknowledgement stored at given path
+    PacketAcknowledgement {
+        /// Path of packet acknowledgement
+        path: String,
+        /// Packet acknowledgement
+        acknowledgement: Vec<u8>,
+    },
+    /// Absence of packet receipt at given path
+    PacketReceiptAbsence {
+        /// Path of packet receipt
+        path: String,
+    },
+    /// Next sequence to be received stored at given path
+    NextSequenceRecv {
+        /// Path of next sequence recv
+        path: String,
+        /// Next sequence to be received
+        next_seq_recv: u64,
+    },
+    /// Update of solo machine's public key and diversifier
+    Header {
+        /// New public key of solo machine
+        new_public_key: PublicKey,
+        /// New diversifier of solo machine
+        new_diversifier: String,
+    },
+    /// Already encoded data of given type (e.g. `PacketCommitmentData` for `solomachine.v1` and `solomachine.v2`, or
+    /// the signed value stored at path for `solomachine.v3`)
+    Raw {
+        /// Type of data
+        data_type: ProofDataType,
+        /// Path of data (only signed in `solomachine.v3` sign bytes)
+        path: Option<String>,
+        /// Encoded data
+        data: Vec<u8>,
+    },
+}
+
+impl ClaimedState {
+    /// Returns type of claimed data
+    pub fn data_type(&self) -> ProofDataType {
+        match self {
+            Self::ClientState { .. } => ProofDataType::ClientState,
+            Self::ConsensusState { .. } => ProofDataType::ConsensusState,
+            Self::Connection { .. } => ProofDataType::ConnectionState,
+            Self::Channel { .. } => ProofDataType::ChannelState,
+            Self::PacketCommitment { .. } => ProofDataType::PacketCommitment,
+            Self::PacketAcknowledgement { .. } => ProofDataType::PacketAcknowledgement,
+            Self::PacketReceiptAbsence { .. } => ProofDataType::PacketReceiptAbsence,
+            Self::NextSequenceRecv { .. } => ProofDataType::NextSequenceRecv,
+            Self::Header { .. } => ProofDataType::Header,
+            Self::Raw { data_type, .. } => *data_type,
+        }
+    }
+
+    /// Returns protobuf encoded `SignBytes` of claimed state at given sequence using given revision of solo machine
+    /// client types
+    pub fn sign_bytes(
+        &self,
+        revision: SoloMachineRevision,
+        sequence: u64,
+        timestamp: u64,
+        diversifier: &str,
+    ) -> Result<Vec<u8>> {
+        match revision {
+            SoloMachineRevision::V1 | SoloMachineRevision::V2 => proto_encode(&SignBytes {
+                sequence,
+                timestamp,
+                diversifier: diversifier.to_owned(),
+                data_type: self.data_type().to_data_type().into(),
+                data: self.encode()?,
+            }),
+            SoloMachineRevision::V3 => {
+                let (path, data) = self.encode_v3()?;
+
+                proto_encode(&SignBytesV3 {
+                    sequence,
+                    timestamp,
+                    diversifier: diversifier.to_owned(),
+                    path,
+                    data,
+                })
+            }
+        }
+    }
+
+    /// Returns protobuf encoded data (as included in `solomachine.v1` and `solomachine.v2` `SignBytes`)
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        match self {
+            Self::ClientState { path, client_state } => proto_encode(&ClientStateData {
+                path: path.as_bytes().to_vec(),
+                client_state: Some(client_state.clone()),
+            }),
+            Self::ConsensusState {
+                path,
+                consensus_state,
+            } => proto_encode(&ConsensusStateData {
+                path: path.as_bytes().to_vec(),
+                consensus_state: Some(consensus_state.clone()),
+            }),
+            Self::Connection { path, connection } => proto_encode(&ConnectionStateData {
+                path: path.as_bytes().to_vec(),
+                connection: Some(connection.clone()),
+            }),
+            Self::Channel { path, channel } => proto_encode(&ChannelStateData {
+                path: path.as_bytes().to_vec(),
+                channel: Some(channel.clone()),
+            }),
+            Self::PacketCommitment { path, commitment } => proto_encode(&PacketCommitmentData {
+                path: path.as_bytes().to_vec(),
+                commitment: commitment.clone(),
+            }),
+            Self::PacketAcknowledgement {
+                path,
+                acknowledgement,
+            } => proto_encode(&PacketAcknowledgementData {
+                path: path.as_bytes().to_vec(),
+                acknowledgement: acknowledgement.clone(),
+            }),
+            Self::PacketReceiptAbsence { path } => proto_encode(&PacketReceiptAbsenceData {
+                path: path.as_bytes().to_vec(),
+            }),
+            Self::NextSequenceRecv {
+                path,
+                next_seq_recv,
+            } => proto_encode(&NextSequenceRecvData {
+                path: path.as_bytes().to_vec(),
+                next_seq_recv: *next_seq_recv,
+            }),
+            Self::Header {
+                new_public_key,
+                new_diversifier,
+            } => proto_encode(&HeaderData {
+                new_pub_key: Some(new_public_key.to_any()?),
+                new_diversifier: new_diversifier.clone(),
+            }),
+            Self::Raw { data, .. } => Ok(data.clone()),
+        }
+    }
+
+    /// Returns path and encoded value (as included in `solomachine.v3` `SignBytes`). Packet acknowledgements are signed
+    /// as their commitments and packet receipt absence is signed with an empty value.
+    fn encode_v3(&self) -> Result<(Vec<u8>, Vec<u8>)> {
+        match self {
+            Self::ClientState { path, client_state } => {
+                Ok((path.as_bytes().to_vec(), proto_encode(client_state)?))
+            }
+            Self::ConsensusState {
+                path,
+                consensus_state,
+            } => Ok((path.as_bytes().to_vec(), proto_encode(consensus_state)?)),
+            Self::Connection { path, connection } => {
+                Ok((path.as_bytes().to_vec(), proto_encode(connection)?))
+            }
+            Self::Channel { path, channel } => Ok((path.as_bytes().to_vec(), proto_encode(channel)?)),
+            Self::PacketCommitment { path, commitment } => {
+                Ok((path.as_bytes().to_vec(), commitment.clone()))
+            }
+            Self::PacketAcknowledgement {
+                path,
+                acknowledgement,
+            } => Ok((
+                path.as_bytes().to_vec(),
+                Sha256::digest(acknowledgement).to_vec(),
+            )),
+            Self::PacketReceiptAbsence { path } => Ok((path.as_bytes().to_vec(), Vec::new())),
+            Self::NextSequenceRecv {
+                path,
+                next_seq_recv,
+            } => Ok((path.as_bytes().to_vec(), next_seq_recv.to_be_bytes().to_vec())),
+            Self::Header {
+                new_public_key,
+                new_diversifier,
+            } => Ok((
+                SENTINEL_HEADER_PATH.as_bytes().to_vec(),
+                proto_encode(&HeaderDataV3 {
+                    new_pub_key: Some(new_public_key.to_any()?),
+                    new_diversifier: new_diversifier.clone(),
+                })?,
+            )),
+            Self::Raw {
+                data_type,
+                path,
+                data,
+            } => match path {
+                Some(path) => Ok((path.as_bytes().to_vec(), data.clone())),
+                None => bail!(
+                    "path of raw {} data is required for verifying it against `solomachine.v3` sign bytes",
+                    data_type
+                ),
+            },
+        }
+    }
+}
+
+/// Details of a successfully verified solo machine proof
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifiedProof {
+    /// Sequence at which proof was signed
+    pub sequence: u64,
+    /// Timestamp (unix seconds) at which proof was signed
+    pub timestamp: u64,
+    /// Diversifier used when signing proof
+    pub diversifier: String,
+    /// Type of signed data
+    pub data_type: ProofDataType,
+}
+
+/// Decoded solo machine proof (`TimestampedSignatureData` along with inner `SignatureData`)
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedProof {
+    /// Timestamp (unix seconds) at which proof was signed
+    pub timestamp: u64,
+    /// Signature in proof
+    pub signature: ProofSignature,
+}
+
+/// Signature (`SignatureData`) in a solo machine proof
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum ProofSignature {
+    /// Signature of a single key
+    Single {
+        /// Sign mode of signature (e.g. `SIGN_MODE_UNSPECIFIED`)
+        sign_mode: String,
+        /// Hex encoded signature
+        signature: String,
+    },
+    /// Signatures of keys of a multisig key
+    Multi {
+        /// Keys of multisig key which signed (e.g. `xx_`)
+        bit_array: String,
+        /// Signatures of signing keys
+        signatures: Vec<ProofSignature>,
+    },
+}
+
+impl ProofSignature {
+    fn new(signature_data: &Sum) -> Result<Self> {
+        match signature_data {
+            Sum::Single(single) => Ok(Self::Single {
+                sign_mode: sign_mode_name(single.mode),
+                signature: hex::encode(&single.signature),
+            }),
+            Sum::Multi(multi) => Ok(Self::Multi {
+                bit_array: multi
+                    .bitarray
+                    .as_ref()
+                    .map(format_bit_array)
+                    .unwrap_or_default(),
+                signatures: multi
+                    .signatures
+                    .iter()
+                    .map(|signature| {
+                        signature
+                            .sum
+                            .as_ref()
+                            .ok_or_else(|| anyhow!("missing signature in multi signature"))
+                            .and_then(Self::new)
+                    })
+                    .collect::<Result<_>>()?,
+            }),
+        }
+    }
+}
+
+/// Decodes a protobuf encoded proof (`TimestampedSignatureData`) along with signature data in it
+pub fn decode_proof(proof: &[u8]) -> Result<DecodedProof> {
+    let (timestamp, signature_data) = decode_timestamped_signature_data(proof)?;
+
+    Ok(DecodedProof {
+        timestamp,
+        signature: ProofSignature::new(&signature_data)?,
+    })
+}
+
+/// Result of inspecting a solo machine proof against expected inputs of its sign bytes
+#[derive(Debug, Clone, Serialize)]
+pub struct ProofInspection {
+    /// Decoded proof
+    pub proof: DecodedProof,
+    /// Revision of solo machine client types used for computing sign bytes
+    pub revision: SoloMachineRevision,
+    /// Hex encoded sign bytes expected to be signed by proof
+    pub sign_bytes: String,
+    /// Whether proof is a valid signature over expected sign bytes
+    pub valid: bool,
+    /// Reason of verification failure (if proof is invalid)
+    pub error: Option<String>,
+    /// Probable causes of verification failure (e.g. a sequence or revision for which proof is valid)
+    pub hints: Vec<String>,
+}
+
+/// Verifies solo machine proofs against a known public key and diversifier of a solo machine
+#[derive(Debug, Clone)]
+pub struct ProofVerifier {
+    public_key: PublicKey,
+    diversifier: String,
+    revision: SoloMachineRevision,
+}
+
+impl ProofVerifier {
+    /// Creates a new proof verifier for solo machine with given public key and diversifier (proofs are verified
+    /// against sign bytes of default revision of solo machine client types)
+    pub fn new(public_key: PublicKey, diversifier: String) -> Self {
+        Self {
+            public_key,
+            diversifier,
+            revision: default_solo_machine_revision(),
+        }
+    }
+
+    /// Verifies proofs against sign bytes of given revision of solo machine client types
+    pub fn with_revision(mut self, revision: SoloMachineRevision) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    /// Verifies that protobuf encoded proof (`TimestampedSignatureData`) is a signature of solo machine over claimed
+    /// state at given sequence
+    pub fn verify(
+        &self,
+        sequence: u64,
+        proof: &[u8],
+        claimed_state: &ClaimedState,
+    ) -> Result<VerifiedProof> {
+        let (timestamp, signature_data) = decode_timestamped_signature_data(proof)?;
+
+        let data_type = claimed_state.data_type();
+
+        let sign_bytes =
+            claimed_state.sign_bytes(self.revision, sequence, timestamp, &self.diversifier)?;
+
+        self.public_key
+            .verify_signature(&sign_bytes, &signature_data)
+            .context(format!(
+                "proof is not a valid signature over claimed {} at sequence {}",
+                data_type, sequence
+            ))?;
+
+        Ok(VerifiedProof {
+            sequence,
+            timestamp,
+            diversifier: self.diversifier.clone(),
+            data_type,
+        })
+    }
+
+    /// Decodes protobuf encoded proof (`TimestampedSignatureData`) and verifies it against sign bytes of claimed state
+    /// at given sequence. Unlike [`verify`](Self::verify), an invalid proof is not an error: the reason of failure is
+    /// returned along with probable causes (e.g. an adjacent sequence or another revision of sign bytes for which the
+    /// proof is valid).
+    pub fn inspect(
+        &self,
+        sequence: u64,
+        proof: &[u8],
+        claimed_state: &ClaimedState,
+    ) -> Result<ProofInspection> {
+        let decoded_proof = decode_proof(proof)?;
+        let sign_bytes = claimed_state.sign_bytes(
+            self.revision,
+            sequence,
+            decoded_proof.timestamp,
+            &self.diversifier,
+        )?;
+
+        let error = self
+            .verify(sequence, proof, claimed_state)
+            .err()
+            .map(|err| format!("{:#}", err));
+        let mut hints = Vec::new();
+
+        if error.is_some() {
+            if decoded_proof.timestamp == 0 {
+                hints.push(
+                    "timestamp of proof is zero (rejected by solo machine clients)".to_owned(),
+                );
+            }
+
+            for other_sequence in [sequence.checked_sub(1), sequence.checked_add(1)]
+                .iter()
+                .flatten()
+            {
+                if self.verify(*other_sequence, proof, claimed_state).is_ok() {
+                    hints.push(format!(
+                        "proof is valid at sequence {} (sequence of solo machine client on chain may have diverged \
+                         from local sequence)",
+                        other_sequence
+                    ));
+                }
+            }
+
+            for revision in [
+                SoloMachineRevision::V1,
+                SoloMachineRevision::V2,
+                SoloMachineRevision::V3,
+            ] {
+                if revision == self.revision {
+                    continue;
+                }
+
+                let verifier = self.clone().with_revision(revision);
+
+                if verifier.verify(sequence, proof, claimed_state).is_ok() {
+                    hints.push(format!(
+                        "proof is valid against {} sign bytes (client on chain may use another revision of solo \
+                         machine client types)",
+                        revision
+                    ));
+                }
+            }
+        }
+
+        Ok(ProofInspection {
+            proof: decoded_proof,
+            revision: self.revision,
+            sign_bytes: hex::encode(sign_bytes),
+            valid: error.is_none(),
+            error,
+            hints,
+        })
+    }
+}
+
+fn decode_timestamped_signature_data(proof: &[u8]) -> Result<(u64, Sum)> {
+    let timestamped_signature_data = TimestampedSignatureData::decode(proof)
+        .context("invalid proof (expected `TimestampedSignatureData`)")?;
+
+    let signature_data =
+        SignatureData::decode(timestamped_signature_data.signature_data.as_slice())
+            .context("invalid signature data in proof")?
+            .sum
+            .ok_or_else(|| anyhow!("missing signature in proof"))?;
+
+    Ok((timestamped_signature_data.timestamp, signature_data))
+}
+
+/// Returns name of sign mode as in protobuf definition of `SignMode`
+fn sign_mode_name(mode: i32) -> String {
+    match mode {
+        0 => "SIGN_MODE_UNSPECIFIED".to_owned(),
+        1 => "SIGN_MODE_DIRECT".to_owned(),
+        2 => "SIGN_MODE_TEXTUAL".to_owned(),
+        127 => "SIGN_MODE_LEGACY_AMINO_JSON".to_owned(),
+        191 => "SIGN_MODE_EIP_191".to_owned(),
+        _ => mode.to_string(),
+    }
+}
+
+/// Formats compact bit array as a string of `x` (set) and `_` (unset) bits
+fn format_bit_array(bit_array: &CompactBitArray) -> String {
+    let len = match bit_array.extra_bits_stored {
+        0 => bit_array.elems.len() * 8,
+        extra_bits => (bit_array.elems.len().saturating_sub(1) * 8) + extra_bits as usize,
+    };
+
+    (0..len)
+        .map(|index| {
+            if bit_array.elems[index / 8] & (1 << (7 - (index % 8))) != 0 {
+                'x'
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
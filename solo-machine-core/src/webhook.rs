@@ -0,0 +1,202 @@
+//! Delivery of events to HTTP webhooks, so that external systems can react to operational events (e.g. completed
+//! handshakes, relayed packets, clients nearing expiry and failed broadcasts) without polling solo machine
+use std::time::Duration;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use hmac::{Hmac, Mac, NewMac};
+use hyper::{client::HttpConnector, header::CONTENT_TYPE, Body, Client, Method, Request, Uri};
+use hyper_rustls::HttpsConnector;
+use sha2::Sha256;
+
+use crate::Event;
+
+/// Types of events delivered to a webhook when it is not configured with any event type
+pub const DEFAULT_WEBHOOK_EVENTS: [&str; 5] = [
+    "ConnectionEstablished",
+    "PacketAcknowledged",
+    "PacketTimedOut",
+    "ClientNearingExpiry",
+    "BroadcastFailed",
+];
+
+/// Header containing hex encoded HMAC-SHA256 of request body (prefixed with `sha256=`) when webhook has a secret
+pub const SIGNATURE_HEADER: &str = "x-solo-machine-signature";
+
+/// Timeout of a single delivery attempt
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum number of attempts to deliver an event to a webhook
+const MAX_DELIVERY_ATTEMPTS: u32 = 3;
+
+/// Backoff before first retry of a failed delivery (doubled after every attempt)
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// HTTP endpoint to which events are delivered as JSON (`POST` with JSON encoding of [`Event`] as body, whose `type`
+/// field contains type of event)
+#[derive(Clone)]
+pub struct Webhook {
+    url: Uri,
+    events: Vec<String>,
+    secret: Option<String>,
+    client: HttpsClient,
+}
+
+impl Webhook {
+    /// Creates a new webhook delivering given types of events (e.g. `ConnectionEstablished`, [`DEFAULT_WEBHOOK_EVENTS`]
+    /// when empty) to given URL. Requests are signed using HMAC-SHA256 when a secret is provided.
+    pub fn new(url: &str, events: Vec<String>, secret: Option<String>) -> Result<Self> {
+        let url: Uri = url
+            .parse()
+            .with_context(|| format!("invalid webhook url: {}", url))?;
+
+        ensure!(
+            matches!(url.scheme_str(), Some("http") | Some("https")),
+            "invalid webhook url: {} (expected `http` or `https` url)",
+            url
+        );
+
+        let events = if events.is_empty() {
+            DEFAULT_WEBHOOK_EVENTS
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        } else {
+            events
+        };
+
+        Ok(Self {
+            url,
+            events,
+            secret,
+            client: https_client(),
+        })
+    }
+
+    /// Returns URL of webhook
+    pub fn url(&self) -> &Uri {
+        &self.url
+    }
+
+    /// Delivers given event to webhook (if webhook is configured with its type), retrying failed attempts
+    pub async fn deliver(&self, event: &Event) -> Result<()> {
+        let body = serde_json::to_value(event).context("unable to serialize event")?;
+
+        let event_type = body
+            .get("type")
+            .and_then(|event_type| event_type.as_str())
+            .ok_or_else(|| anyhow!("missing type of event"))?;
+
+        if !self.events.iter().any(|event| event == event_type) {
+            return Ok(());
+        }
+
+        let body = serde_json::to_vec(&body).context("unable to serialize event")?;
+
+        let mut headers = Vec::new();
+
+        if let Some(ref secret) = self.secret {
+            headers.push((SIGNATURE_HEADER, format!("sha256={}", sign(secret, &body)?)));
+        }
+
+        post_json(
+            &self.client,
+            &self.url,
+            &self.url.to_string(),
+            &body,
+            &headers,
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "unable to deliver {} event to webhook {}",
+                event_type, self.url
+            )
+        })
+    }
+}
+
+/// HTTP(S) client used to post to webhooks
+pub(crate) type HttpsClient = Client<HttpsConnector<HttpConnector>>;
+
+/// Creates a new HTTP(S) client (using native root certificates)
+pub(crate) fn https_client() -> HttpsClient {
+    Client::builder().build(HttpsConnector::with_native_roots())
+}
+
+/// Posts given JSON body (along with additional headers) to given URL, retrying failed attempts. `target` names the
+/// destination in logs and errors (URLs may contain secrets, e.g. tokens of bots).
+pub(crate) async fn post_json(
+    client: &HttpsClient,
+    url: &Uri,
+    target: &str,
+    body: &[u8],
+    headers: &[(&str, String)],
+) -> Result<()> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        match post(client, url, target, body, headers).await {
+            Ok(()) => return Ok(()),
+            Err(err) if attempt < MAX_DELIVERY_ATTEMPTS => {
+                tracing::warn!(
+                    webhook = target,
+                    attempt,
+                    "unable to post to webhook, retrying in {:?}: {:#}",
+                    backoff,
+                    err
+                );
+
+                tokio::time::sleep(backoff).await;
+
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(err) => return Err(err.context(format!("failed after {} attempts", attempt))),
+        }
+    }
+}
+
+async fn post(
+    client: &HttpsClient,
+    url: &Uri,
+    target: &str,
+    body: &[u8],
+    headers: &[(&str, String)],
+) -> Result<()> {
+    let mut request = Request::builder()
+        .method(Method::POST)
+        .uri(url.clone())
+        .header(CONTENT_TYPE, "application/json");
+
+    for (name, value) in headers {
+        request = request.header(*name, value.as_str());
+    }
+
+    let request = request
+        .body(Body::from(body.to_vec()))
+        .context("unable to build webhook request")?;
+
+    let response = tokio::time::timeout(DELIVERY_TIMEOUT, client.request(request))
+        .await
+        .map_err(|_| anyhow!("timed out posting to {}", target))?
+        .with_context(|| format!("unable to post to {}", target))?;
+
+    ensure!(
+        response.status().is_success(),
+        "{} responded with {}",
+        target,
+        response.status()
+    );
+
+    Ok(())
+}
+
+/// Returns hex encoded HMAC-SHA256 of given body
+fn sign(secret: &str, body: &[u8]) -> Result<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|_| anyhow!("invalid webhook secret"))?;
+    mac.update(body);
+
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
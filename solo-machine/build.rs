@@ -1,11 +1,34 @@
+use std::error::Error;
+
+#[cfg(feature = "server")]
 use std::{
-    error::Error,
+    env,
     fs::{read_dir, DirEntry},
     path::PathBuf,
 };
 
+#[cfg(feature = "server")]
 use prost_build::Config;
 
+/// Fields of type `google.protobuf.Timestamp` (serialized as RFC 3339 strings in JSON)
+#[cfg(feature = "server")]
+const TIMESTAMP_FIELDS: [&str; 3] = ["consensus_timestamp", "created_at", "updated_at"];
+
+/// Fields of type `google.protobuf.Duration` (serialized as human readable durations, e.g. `14days`, in JSON)
+#[cfg(feature = "server")]
+const DURATION_FIELDS: [&str; 3] = ["trusting_period", "max_clock_drift", "rpc_timeout"];
+
+/// Fields of type `bytes` (serialized as hex strings in JSON)
+#[cfg(feature = "server")]
+const BYTES_FIELDS: [&str; 1] = ["Packet.data"];
+
+#[cfg(not(feature = "server"))]
+fn main() -> Result<(), Box<dyn Error>> {
+    // protobuf definitions are only needed for gRPC server
+    Ok(())
+}
+
+#[cfg(feature = "server")]
 fn main() -> Result<(), Box<dyn Error>> {
     let mut files = Vec::new();
 
@@ -18,6 +41,26 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut config = Config::default();
     config.protoc_arg("--experimental_allow_proto3_optional");
 
+    // messages are also (de)serialized as JSON by REST server and OpenAPI spec of REST server is generated from file
+    // descriptors
+    config
+        .type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]")
+        .type_attribute(".", "#[serde(default)]");
+
+    for field in TIMESTAMP_FIELDS {
+        config.field_attribute(field, "#[serde(with = \"crate::server::json::timestamp\")]");
+    }
+
+    for field in DURATION_FIELDS {
+        config.field_attribute(field, "#[serde(with = \"crate::server::json::duration\")]");
+    }
+
+    for field in BYTES_FIELDS {
+        config.field_attribute(field, "#[serde(with = \"crate::server::json::hex_bytes\")]");
+    }
+
+    config.file_descriptor_set_path(PathBuf::from(env::var("OUT_DIR")?).join("descriptors.bin"));
+
     tonic_build::configure()
         .build_client(false)
         .compile_with_config(config, &files, &["proto".into()])?;
@@ -25,6 +68,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[cfg(feature = "server")]
 fn get_files(path: DirEntry) -> Result<Vec<PathBuf>, Box<dyn Error>> {
     if path.file_type()?.is_file() {
         return Ok(vec![path.path()]);
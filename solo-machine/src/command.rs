@@ -1,27 +1,121 @@
+mod api_key;
+mod api_token;
+mod audit;
 mod chain;
+mod doctor;
 mod ibc;
+#[cfg(feature = "keyring")]
+mod keychain;
+mod keystore;
+mod macros;
+mod pipeline;
+mod proof;
+mod quickstart;
+mod shell;
+mod state;
+mod stats;
+mod status;
+mod tx;
 
 use std::{
+    collections::BTreeMap,
     convert::TryFrom,
     fmt::Display,
     io::{stdout, Write},
-    net::SocketAddr,
     path::PathBuf,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
 };
 
-use anyhow::{ensure, Context, Result};
-use cli_table::{Cell, Row, RowStruct, Style};
-use solo_machine_core::{connect_db, event::HandlerRegistrar as _, init_db, run_migrations};
+#[cfg(feature = "server")]
+use std::net::SocketAddr;
+
+use anyhow::{anyhow, ensure, Context, Error, Result};
+use solo_machine_core::{
+    alert::{AlertChannel, Alerter},
+    attestation::BuildAttestation,
+    connect_db,
+    event::HandlerRegistrar as _,
+    init_db,
+    plugins::Plugins,
+    policy::SigningPolicy,
+    relayer::{Relayer, RelayerConfig},
+    run_migrations,
+    service::{
+        AuditService, BackupService, ChainService, IntegrityService, StateExport, StatsService,
+    },
+    signer::{AuditedSigner, DryRunReport, LockedSigner, PolicySigner, RateLimitedSigner},
+    storage::{set_ibc_cache_ttl, unlock_state},
+    supervisor::{RestartPolicy, TaskSupervisor},
+    webhook::Webhook,
+    DbPool, Event, Signer, ToPublicKey,
+};
 use structopt::{clap::Shell, StructOpt};
-use termcolor::{ColorChoice, ColorSpec, StandardStream, WriteColor};
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+use tokio::{signal, sync::mpsc::UnboundedSender};
+use tracing::level_filters::LevelFilter;
+use zeroize::Zeroizing;
 
 use crate::{
-    event::{cli_event_handler::CliEventHandler, env_logger::EnvLogger, HandlerRegistrar},
-    server::start_grpc,
-    signer::SignerRegistrar,
+    config::Config,
+    event::{
+        alert_notifier::AlertNotifier, audit_logger::AuditLogger,
+        cli_event_handler::CliEventHandler, stats_recorder::StatsRecorder,
+        webhook_notifier::WebhookNotifier, HandlerRegistrar,
+    },
+    logging::{self, LogFormat, LOG_FORMAT_VARIANTS},
+    output::{KeyValueTable, ListTable, OutputFormat, ToField, OUTPUT_FORMAT_VARIANTS},
+    signer::{load_named_signers, load_plugins, load_signer, ConfirmingSigner},
+};
+#[cfg(feature = "server")]
+use crate::{
+    event::event_logger::EventLogger,
+    server::{openapi_spec, start_grpc, start_rest, Authorizer, Tenant, Tenants, TlsConfig},
 };
 
-use self::{chain::ChainCommand, ibc::IbcCommand};
+#[cfg(feature = "keyring")]
+use self::keychain::KeychainCommand;
+use self::{
+    api_key::ApiKeyCommand, api_token::ApiTokenCommand, audit::AuditCommand, chain::ChainCommand,
+    doctor::DoctorCommand, ibc::IbcCommand, keystore::KeystoreCommand, macros::MacroCommand,
+    pipeline::PipelineCommand, proof::ProofCommand, quickstart::QuickstartCommand,
+    shell::ShellCommand, state::StateCommand, stats::StatsCommand, status::StatusCommand,
+    tx::TxCommand,
+};
+
+/// Interval at which uptime of long running solo machine processes is recorded
+const UPTIME_RECORD_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Possible values of `--export`
+const TX_EXPORT_VARIANTS: [&str; 2] = ["unsigned", "signed"];
+
+/// Kind of transaction export (in JSON layout of cosmos SDK CLIs)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxExport {
+    /// Transaction without signatures (to be signed using `tx sign`)
+    Unsigned,
+    /// Transaction signed by signer (to be broadcasted using `tx broadcast`)
+    Signed,
+}
+
+impl TxExport {
+    fn is_signed(self) -> bool {
+        matches!(self, Self::Signed)
+    }
+}
+
+impl FromStr for TxExport {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unsigned" => Ok(Self::Unsigned),
+            "signed" => Ok(Self::Signed),
+            _ => Err(anyhow!("invalid transaction export: {}", s)),
+        }
+    }
+}
 
 #[derive(Debug, StructOpt)]
 #[structopt(
@@ -32,6 +126,14 @@ pub struct Command {
     /// Does not print styled/colored statements
     #[structopt(long)]
     no_style: bool,
+    /// Format of output of commands (`json` and `yaml` print tables as objects or arrays and events as records, and
+    /// write human readable messages to stderr, so that stdout can be parsed by scripts)
+    #[structopt(long, default_value = "text", possible_values = &OUTPUT_FORMAT_VARIANTS, env = "SOLO_OUTPUT")]
+    output: OutputFormat,
+    /// Path of configuration file (TOML, or YAML with `.yaml`/`.yml` extension) defining database, signer, daemon
+    /// settings and chains. Settings in configuration file are used as defaults for corresponding options.
+    #[structopt(long, env = "SOLO_CONFIG")]
+    config: Option<PathBuf>,
     /// Database connection string
     #[cfg_attr(
         not(feature = "postgres"),
@@ -46,11 +148,106 @@ pub struct Command {
     /// Register a signer (path to signer's `*.so` file)
     #[structopt(long, env = "SOLO_SIGNER", hide_env_values = true)]
     signer: Option<PathBuf>,
+    /// Register a separate signer for paying transaction fees (path to signer's `*.so` file). When provided, fee
+    /// payer account is set in `Fee.payer` and fee payer signs all the transactions along with `signer`
+    #[structopt(long, env = "SOLO_FEE_PAYER_SIGNER", hide_env_values = true)]
+    fee_payer_signer: Option<PathBuf>,
+    /// Register a fee strategy plugin (path to plugin's `*.so` file) used for computing transaction fees on chains
+    /// with `plugin` fee strategy
+    #[structopt(long, env = "SOLO_FEE_STRATEGY_PLUGIN", hide_env_values = true)]
+    fee_strategy_plugin: Option<PathBuf>,
+    /// Register a client encoder plugin (path to plugin's `*.so` file) used for encoding solo machine client types on
+    /// chains with `plugin` client encoding
+    #[structopt(long, env = "SOLO_CLIENT_ENCODER_PLUGIN", hide_env_values = true)]
+    client_encoder_plugin: Option<PathBuf>,
+    /// Passphrase for unlocking encrypted stored state (see `state encrypt`)
+    #[structopt(long, env = "SOLO_STATE_PASSPHRASE", hide_env_values = true)]
+    state_passphrase: Option<String>,
+    /// Name of platform keychain entry storing passphrase for unlocking encrypted stored state (used when
+    /// `state-passphrase` is not provided)
+    #[cfg(feature = "keyring")]
+    #[structopt(long, env = "SOLO_STATE_PASSPHRASE_KEYCHAIN")]
+    state_passphrase_keychain: Option<String>,
+    /// Name of key (from signer's key registry) used for signing (default account of signer is used when not
+    /// provided)
+    #[structopt(long, env = "SOLO_KEY", hide_env_values = true)]
+    key: Option<String>,
+    /// Settings of signer of selected tenant (provided to signers instead of environment variables)
+    #[structopt(skip)]
+    signer_settings: Option<BTreeMap<String, String>>,
+    /// Time for which decoded client states, consensus states, connections and channels are cached in memory (`0s`
+    /// disables caching)
+    #[structopt(
+        long,
+        default_value = "30s",
+        env = "SOLO_IBC_CACHE_TTL",
+        parse(try_from_str = humantime::parse_duration)
+    )]
+    ibc_cache_ttl: Duration,
     /// Register an event handler. Multiple event handlers can be registered and they're executed in order they're
     /// provided in CLI. Also, if an event handler returns an error when handling a message, all the future event
     /// handlers will not get executed.
     #[structopt(long)]
     handler: Vec<PathBuf>,
+    /// URL to which operational events are posted as JSON (can be provided multiple times, along with `webhooks` in
+    /// configuration file)
+    #[structopt(long = "webhook", env = "SOLO_WEBHOOKS", use_delimiter = true)]
+    webhooks: Vec<String>,
+    /// Types of events posted to webhooks provided using `webhook` (e.g. `ConnectionEstablished`). Completed
+    /// handshakes, relayed packets, clients nearing expiry and failed broadcasts are posted when not provided
+    #[structopt(
+        long = "webhook-event",
+        env = "SOLO_WEBHOOK_EVENTS",
+        use_delimiter = true
+    )]
+    webhook_events: Vec<String>,
+    /// Secret used for signing requests to webhooks provided using `webhook` (hex encoded HMAC-SHA256 of request body
+    /// is sent in `x-solo-machine-signature` header)
+    #[structopt(long, env = "SOLO_WEBHOOK_SECRET", hide_env_values = true)]
+    webhook_secret: Option<String>,
+    /// Slack incoming webhook URL to which human-readable alerts are posted on critical conditions (frozen solo
+    /// machine client, repeated broadcast failures, divergence of sequence and tendermint clients nearing expiry)
+    #[structopt(long, env = "SOLO_SLACK_WEBHOOK_URL", hide_env_values = true)]
+    slack_webhook_url: Option<String>,
+    /// Token of Telegram bot posting alerts on critical conditions to `telegram-chat-id`
+    #[structopt(
+        long,
+        env = "SOLO_TELEGRAM_BOT_TOKEN",
+        hide_env_values = true,
+        requires = "telegram-chat-id"
+    )]
+    telegram_bot_token: Option<String>,
+    /// ID of Telegram chat (or `@username` of channel) to which alerts are posted
+    #[structopt(long, env = "SOLO_TELEGRAM_CHAT_ID", requires = "telegram-bot-token")]
+    telegram_chat_id: Option<String>,
+    /// Number of consecutive failed broadcasts on a chain after which an alert is posted
+    #[structopt(long, default_value = "3", env = "SOLO_ALERT_BROADCAST_FAILURES")]
+    alert_broadcast_failures: u32,
+    /// ID of tenant (from `tenants` in configuration file) whose database and signer are used instead of top-level
+    /// ones (e.g. for managing API tokens of a tenant)
+    #[structopt(long, env = "SOLO_TENANT")]
+    tenant: Option<String>,
+    /// Signs without showing a summary of every message and asking for confirmation (required when stdin is not a
+    /// terminal, e.g. in scripts)
+    #[structopt(long)]
+    yes: bool,
+    /// Approves transfers exceeding spending limits of signing policy whose action is `escalate` (for this
+    /// invocation only; cannot be used when starting gRPC server)
+    #[structopt(long)]
+    approve_escalation: bool,
+    /// OTLP (gRPC) endpoint of OpenTelemetry collector to which tracing spans of connection handshake steps, proof
+    /// generation, gRPC queries, broadcasts and server requests are exported (e.g. `http://localhost:4317`)
+    #[cfg(feature = "otlp")]
+    #[structopt(long, env = "SOLO_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+    /// Maximum level of log records written to stderr (`off`, `error`, `warn`, `info`, `debug` or `trace`). Records of
+    /// dependencies are never more verbose than `warn` (`RUST_LOG` directives, when set, override this option)
+    #[structopt(long, default_value = "warn", env = "SOLO_LOG_LEVEL")]
+    log_level: LevelFilter,
+    /// Format of log records written to stderr (`json` writes a JSON object per line with fields of record, e.g.
+    /// `chain_id`, `operation_id` and `sequence`, and of its current span)
+    #[structopt(long, default_value = "text", possible_values = &LOG_FORMAT_VARIANTS, env = "SOLO_LOG_FORMAT")]
+    log_format: LogFormat,
     #[structopt(subcommand)]
     subcommand: SubCommand,
 }
@@ -58,8 +255,26 @@ pub struct Command {
 #[derive(Debug, StructOpt)]
 #[allow(clippy::large_enum_variant)]
 pub enum SubCommand {
+    /// Channel scoped API keys used for authorizing transfers requested through gRPC server (creating, listing and
+    /// revoking)
+    ApiKey(ApiKeySubCommand),
+    /// API tokens with roles (`read-only`, `operator` and `admin`) used for authorizing requests to gRPC and REST
+    /// servers (creating, listing and revoking)
+    ApiToken(ApiTokenSubCommand),
+    /// Hash-chained audit log of all the events (verification and history)
+    Audit(AuditSubCommand),
     /// Chain operations (managing chain state and metadata)
     Chain(ChainSubCommand),
+    /// Diagnoses chains by querying them (connectivity, chain ID consistency, node sync status, account existence and
+    /// balance, unbonding period against trusting period and health of IBC clients) and prints actionable findings
+    Doctor(DoctorCommand),
+    /// Exports stored state of all the chains (chain records, sequences, diversifiers, client states and consensus
+    /// states) to a versioned JSON file (secrets of signer are never exported)
+    Export {
+        /// Path of file to write state export to (printed to stdout when not provided)
+        #[structopt(short, long)]
+        output: Option<PathBuf>,
+    },
     /// Generate completion scripts for solo-machine-cli
     GenCompletion {
         #[structopt(long, default_value = "bash")]
@@ -67,22 +282,196 @@ pub enum SubCommand {
     },
     /// Used to connect, mint tokens and burn tokens on IBC enabled chain
     Ibc(IbcSubCommand),
+    /// Imports state exported using `export` command (e.g. when restoring solo machine on another host). Import fails
+    /// if any of the exported chains already exists
+    Import {
+        /// Path of state export file
+        file: PathBuf,
+    },
     /// Initializes database for solo machine
     Init,
+    /// Platform keychain operations (storing and deleting mnemonic)
+    #[cfg(feature = "keyring")]
+    Keychain(KeychainSubCommand),
+    /// Lists all the named keys in signer's key registry
+    Keys,
+    /// Encrypted keystore operations (creating, importing and exporting mnemonic)
+    Keystore(KeystoreSubCommand),
+    /// Runs named macros (sequences of operations with parameters) with journaling
+    Macro(MacroSubCommand),
+    /// Prints OpenAPI spec of REST server (generated from protobuf definitions of gRPC services)
+    #[cfg(feature = "server")]
+    Openapi,
+    /// Runs pipelines (sequences of operations defined in YAML files) with per-step retries and resumption
+    Pipeline(PipelineSubCommand),
+    /// Solo machine proof operations (decoding and diagnosing)
+    Proof(ProofSubCommand),
+    /// Runs an end-to-end demo against a local development chain (simd/gaia): generates a key, registers the chain,
+    /// funds the key using `signer` (a funded account on chain), establishes IBC connection and performs a round-trip
+    /// transfer
+    Quickstart(QuickstartCommand),
+    /// Runs relayer in daemon mode until interrupted: watches connected chains, refreshes tendermint clients on solo
+    /// machine and acknowledges packets sent to solo machine (halted chains are skipped)
+    Relay {
+        /// Chain IDs (or labels) of IBC enabled chains to relay for (all the connected chains when not provided)
+        chain_ids: Vec<String>,
+        /// Interval between relaying rounds
+        #[structopt(
+            long,
+            default_value = "10s",
+            env = "SOLO_RELAY_INTERVAL",
+            parse(try_from_str = humantime::parse_duration)
+        )]
+        interval: Duration,
+        /// Memo used in transactions sent by relayer
+        #[structopt(long, default_value = "solo-machine-memo", env = "SOLO_RELAY_MEMO")]
+        memo: String,
+        /// Only relay in rounds (by default, chains are also subscribed to over websocket and packets sent to solo
+        /// machine are acknowledged as soon as they are observed)
+        #[structopt(long)]
+        poll_only: bool,
+    },
+    /// Repairs inconsistent stored state of chains and accepts break in hash chain of audit log (signing is refused
+    /// until integrity issues reported on startup are repaired)
+    Repair {
+        /// Chain ID (or label) of IBC enabled chain (all the chains with integrity issues and audit log are repaired
+        /// when not provided)
+        chain_id: Option<String>,
+    },
+    /// Compares local sequence of a chain with sequence of solo machine client on chain and resynchronizes local
+    /// sequence when they diverge. Without `--yes`, only prints both sequences.
+    Resync {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Resynchronizes local sequence (only a preview is printed otherwise)
+        #[structopt(long)]
+        yes: bool,
+    },
     /// Starts gRPC server for solo machine
+    #[cfg(feature = "server")]
     Start {
         /// gRPC server address
         #[structopt(short, long, env = "SOLO_GRPC_ADDR", default_value = "0.0.0.0:9000")]
         addr: SocketAddr,
+        /// Requires a channel scoped API key (created using `api-key create`) for every request. Only mint and burn
+        /// requests within the scope of the API key are served
+        #[structopt(long)]
+        require_api_key: bool,
+        /// Requires an API token with a role (created using `api-token create`) authorized for the requested operation
+        /// for every request (mint and burn requests can also be authorized by a channel scoped API key). When not
+        /// set, requests without a token are served (presented tokens are still verified)
+        #[structopt(long, conflicts_with = "require-api-key")]
+        require_token: bool,
+        /// Address of REST server (HTTP+JSON facade of gRPC services, not started when not provided)
+        #[structopt(long, env = "SOLO_REST_ADDR")]
+        rest_addr: Option<SocketAddr>,
+        /// Path of PEM encoded certificate chain of gRPC and REST servers (enables mutual TLS along with `tls-key` and
+        /// `tls-client-ca`)
+        #[structopt(long, env = "SOLO_TLS_CERT", requires_all = &["tls-key", "tls-client-ca"])]
+        tls_cert: Option<PathBuf>,
+        /// Path of PEM encoded private key (PKCS#8 or RSA) of gRPC and REST servers
+        #[structopt(
+            long,
+            env = "SOLO_TLS_KEY",
+            hide_env_values = true,
+            requires = "tls-cert"
+        )]
+        tls_key: Option<PathBuf>,
+        /// Path of PEM encoded CA certificates which issue client certificates. Clients are required to present a
+        /// certificate issued by one of these CAs
+        #[structopt(long, env = "SOLO_TLS_CLIENT_CA", requires = "tls-cert")]
+        tls_client_ca: Option<PathBuf>,
+        /// SHA-256 fingerprint (hex) of a client certificate allowed to access the servers. Multiple fingerprints can
+        /// be provided (all the clients with a certificate issued by client CA are allowed when not provided)
+        #[structopt(long, requires = "tls-cert")]
+        tls_allowed_client: Vec<String>,
+    },
+    /// Starts an interactive shell in which signer and database are initialized once and chain, ibc and status
+    /// commands can be run successively (with tab completion of commands, options and chain IDs)
+    Shell(ShellCommand),
+    /// Encryption of stored state at rest (encrypting and decrypting)
+    State(StateCommand),
+    /// Prints persistent stats (packets relayed, successful operations and uptime) of chains along with their daily
+    /// aggregates
+    Stats(StatsCommand),
+    /// Prints aggregated status of solo machine: storage schema version, signer, integrity, pending operations and,
+    /// for each chain, connection and channel states, tendermint client health and pending packets
+    Status(StatusCommand),
+    /// Transaction operations (decoding)
+    Tx(TxSubCommand),
+    /// Prints version of solo machine
+    Version {
+        /// Prints build attestation (exact crate versions, hashes of protobuf definitions and build metadata) of
+        /// signing logic, which is also recorded in audit log with each entry
+        #[structopt(long)]
+        attest: bool,
+        /// Prints build attestation as canonical JSON (from which attestation digest is computed)
+        #[structopt(long, requires = "attest")]
+        json: bool,
     },
 }
 
+#[derive(Debug, StructOpt)]
+pub struct ApiKeySubCommand {
+    #[structopt(subcommand)]
+    subcommand: ApiKeyCommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ApiTokenSubCommand {
+    #[structopt(subcommand)]
+    subcommand: ApiTokenCommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct AuditSubCommand {
+    #[structopt(subcommand)]
+    subcommand: AuditCommand,
+}
+
 #[derive(Debug, StructOpt)]
 pub struct ChainSubCommand {
     #[structopt(subcommand)]
     subcommand: ChainCommand,
 }
 
+#[cfg(feature = "keyring")]
+#[derive(Debug, StructOpt)]
+pub struct KeychainSubCommand {
+    #[structopt(subcommand)]
+    subcommand: KeychainCommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct KeystoreSubCommand {
+    #[structopt(subcommand)]
+    subcommand: KeystoreCommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct MacroSubCommand {
+    #[structopt(subcommand)]
+    subcommand: MacroCommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct PipelineSubCommand {
+    #[structopt(subcommand)]
+    subcommand: PipelineCommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct ProofSubCommand {
+    #[structopt(subcommand)]
+    subcommand: ProofCommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct TxSubCommand {
+    #[structopt(subcommand)]
+    subcommand: TxCommand,
+}
+
 #[derive(Debug, StructOpt)]
 pub struct IbcSubCommand {
     #[structopt(subcommand)]
@@ -90,14 +479,178 @@ pub struct IbcSubCommand {
 }
 
 impl Command {
-    pub async fn execute(self) -> Result<()> {
+    /// Returns passphrase for unlocking encrypted stored state (from CLI options or platform keychain)
+    fn state_passphrase(&self) -> Result<Option<Zeroizing<String>>> {
+        if let Some(ref passphrase) = self.state_passphrase {
+            return Ok(Some(Zeroizing::new(passphrase.clone())));
+        }
+
+        #[cfg(feature = "keyring")]
+        if let Some(ref name) = self.state_passphrase_keychain {
+            return solo_machine_core::keychain::load_secret(name).map(Some);
+        }
+
+        Ok(None)
+    }
+
+    /// Uses database and signer of given tenant (from configuration file) instead of top-level ones
+    fn use_tenant(&mut self, id: &str) -> Result<()> {
+        let path = self
+            .config
+            .as_ref()
+            .ok_or_else(|| anyhow!("`config` is required for selecting a tenant"))?;
+        let tenant = Config::load(path)?
+            .tenants
+            .remove(id)
+            .ok_or_else(|| anyhow!("tenant `{}` not found in {}", id, path.display()))?;
+
+        self.db_uri = tenant.db_uri;
+        self.signer = tenant.signer.path.clone();
+        self.fee_payer_signer = tenant.signer.fee_payer.clone();
+        self.fee_strategy_plugin = tenant.signer.fee_strategy_plugin.clone();
+        self.client_encoder_plugin = tenant.signer.client_encoder_plugin.clone();
+        self.key = tenant.signer.key.clone();
+        self.signer_settings = Some(tenant.signer.settings());
+
+        Ok(())
+    }
+
+    /// Returns signing policy from configuration file (of selected tenant, if any)
+    fn signing_policy(&self) -> Result<SigningPolicy> {
+        let path = match self.config {
+            Some(ref path) => path,
+            None => return Ok(Default::default()),
+        };
+
+        let mut config = Config::load(path)?;
+
+        let mut signing_policy = match self.tenant {
+            None => config.signing_policy,
+            Some(ref id) => config
+                .tenants
+                .remove(id)
+                .map(|tenant| tenant.signing_policy)
+                .unwrap_or_default(),
+        };
+
+        signing_policy.validate()?;
+        signing_policy.escalation_approved = self.approve_escalation;
+
+        Ok(signing_policy)
+    }
+
+    /// Returns webhooks provided using CLI options and configuration file
+    fn webhooks(&self) -> Result<Vec<Webhook>> {
+        let mut webhooks = self
+            .webhooks
+            .iter()
+            .map(|url| {
+                Webhook::new(
+                    url,
+                    self.webhook_events.clone(),
+                    self.webhook_secret.clone(),
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if let Some(ref path) = self.config {
+            for webhook in Config::load(path)?.webhooks {
+                webhooks.push(Webhook::new(&webhook.url, webhook.events, webhook.secret)?);
+            }
+        }
+
+        Ok(webhooks)
+    }
+
+    /// Returns alerter posting to Slack and Telegram channels provided using CLI options (or configuration file)
+    fn alerter(&self) -> Result<Arc<Alerter>> {
+        let mut channels = Vec::new();
+
+        if let Some(ref webhook_url) = self.slack_webhook_url {
+            channels.push(AlertChannel::Slack {
+                webhook_url: webhook_url.clone(),
+            });
+        }
+
+        if let (Some(bot_token), Some(chat_id)) = (&self.telegram_bot_token, &self.telegram_chat_id)
+        {
+            channels.push(AlertChannel::Telegram {
+                bot_token: bot_token.clone(),
+                chat_id: chat_id.clone(),
+            });
+        }
+
+        Ok(Arc::new(Alerter::new(
+            channels,
+            self.alert_broadcast_failures,
+        )?))
+    }
+
+    pub async fn execute(mut self) -> Result<()> {
+        logging::init(
+            self.log_level,
+            self.log_format,
+            self.no_style,
+            #[cfg(feature = "otlp")]
+            self.otlp_endpoint.as_deref(),
+        )?;
+
+        if let Some(tenant) = self.tenant.clone() {
+            self.use_tenant(&tenant)?;
+        }
+
         let color_choice = if self.no_style {
             ColorChoice::Never
         } else {
             ColorChoice::Auto
         };
+        let output = self.output;
+
+        set_ibc_cache_ttl(self.ibc_cache_ttl);
+
+        let webhooks = self.webhooks()?;
+        let alerter = self.alerter()?;
+        let signing_policy = self.signing_policy()?;
+
+        let state_passphrase = self.state_passphrase()?;
+
+        if let (Some(db_uri), Some(passphrase)) = (&self.db_uri, &state_passphrase) {
+            if !matches!(self.subcommand, SubCommand::Init | SubCommand::State(_)) {
+                unlock_state(&connect_db(db_uri).await?, passphrase.as_str()).await?;
+            }
+        }
 
         match self.subcommand {
+            SubCommand::ApiKey(api_key) => {
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                api_key
+                    .subcommand
+                    .execute(db_pool, color_choice, output)
+                    .await
+            }
+            SubCommand::ApiToken(api_token) => {
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                api_token
+                    .subcommand
+                    .execute(db_pool, color_choice, output)
+                    .await
+            }
+            SubCommand::Audit(audit) => {
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                audit
+                    .subcommand
+                    .execute(db_pool, color_choice, output)
+                    .await
+            }
             SubCommand::Chain(chain) => {
                 ensure!(
                     self.signer.is_some(),
@@ -108,19 +661,90 @@ impl Command {
                 let db_pool = connect_db(&self.db_uri.unwrap()).await?;
 
                 let mut handler_registrar = HandlerRegistrar::try_from(self.handler)?;
-                handler_registrar.register(Box::new(CliEventHandler::new(color_choice)));
-                let (sender, handle) = handler_registrar.spawn();
+                handler_registrar.register(Box::new(AuditLogger::new(db_pool.clone())));
+                handler_registrar.register(Box::new(StatsRecorder::new(db_pool.clone())));
+                handler_registrar.register(Box::new(CliEventHandler::new(color_choice, output)));
+                handler_registrar.register(Box::new(WebhookNotifier::new(webhooks.clone())));
+                handler_registrar.register(Box::new(AlertNotifier::new(alerter.clone())));
+                let mut supervisor = TaskSupervisor::new();
+                let sender = handler_registrar.spawn(&mut supervisor, RestartPolicy::Never);
 
-                let signer = SignerRegistrar::try_from(self.signer.unwrap())?.unwrap()?;
+                let signer = load_signer(
+                    self.signer.unwrap(),
+                    self.fee_payer_signer,
+                    self.key.as_deref(),
+                    self.signer_settings.as_ref(),
+                )?;
+                let plugins = load_plugins(self.fee_strategy_plugin, self.client_encoder_plugin)?;
+                let signer = verify_integrity(&db_pool, signer, &signing_policy, &sender).await?;
+                let signer = confirm_signatures(signer, self.yes);
 
                 chain
                     .subcommand
-                    .execute(db_pool, signer, sender, color_choice)
+                    .execute(
+                        db_pool,
+                        signer,
+                        plugins,
+                        sender,
+                        color_choice,
+                        output,
+                        self.config,
+                    )
                     .await?;
 
-                handle
-                    .await
-                    .context("unable to join event hook registrar task")?
+                supervisor.join().await
+            }
+            SubCommand::Doctor(doctor) => {
+                ensure!(
+                    self.signer.is_some(),
+                    "`signer` is required for doctor command"
+                );
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                let signer = load_signer(
+                    self.signer.unwrap(),
+                    self.fee_payer_signer,
+                    self.key.as_deref(),
+                    self.signer_settings.as_ref(),
+                )?;
+
+                doctor.execute(db_pool, signer, color_choice, output).await
+            }
+            SubCommand::Export { output: path } => {
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+                let state = BackupService::new(db_pool).export().await?;
+                let json = serde_json::to_string_pretty(&state)
+                    .context("unable to serialize state export")?;
+
+                match path {
+                    None => println!("{}", json),
+                    Some(path) => {
+                        std::fs::write(&path, json).with_context(|| {
+                            format!("unable to write state export to {}", path.display())
+                        })?;
+
+                        print_stream(
+                            &mut output.stream(color_choice),
+                            ColorSpec::new().set_bold(true),
+                            format!(
+                                "Exported state of {} chain(s) to {}",
+                                state.chains.len(),
+                                path.display()
+                            ),
+                        )?;
+                    }
+                }
+
+                Ok(())
+            }
+            #[cfg(feature = "server")]
+            SubCommand::Openapi => {
+                println!("{}", serde_json::to_string_pretty(&openapi_spec()?)?);
+                Ok(())
             }
             SubCommand::GenCompletion { shell } => {
                 Self::clap().gen_completions_to("solo-machine", shell, &mut stdout());
@@ -136,18 +760,54 @@ impl Command {
                 let db_pool = connect_db(&self.db_uri.unwrap()).await?;
 
                 let mut handler_registrar = HandlerRegistrar::try_from(self.handler)?;
-                handler_registrar.register(Box::new(CliEventHandler::new(color_choice)));
-                let (sender, handle) = handler_registrar.spawn();
+                handler_registrar.register(Box::new(AuditLogger::new(db_pool.clone())));
+                handler_registrar.register(Box::new(StatsRecorder::new(db_pool.clone())));
+                handler_registrar.register(Box::new(CliEventHandler::new(color_choice, output)));
+                handler_registrar.register(Box::new(WebhookNotifier::new(webhooks.clone())));
+                handler_registrar.register(Box::new(AlertNotifier::new(alerter.clone())));
+                let mut supervisor = TaskSupervisor::new();
+                let sender = handler_registrar.spawn(&mut supervisor, RestartPolicy::Never);
 
-                let signer = SignerRegistrar::try_from(self.signer.unwrap())?.unwrap()?;
+                let signer = load_signer(
+                    self.signer.unwrap(),
+                    self.fee_payer_signer,
+                    self.key.as_deref(),
+                    self.signer_settings.as_ref(),
+                )?;
+                let plugins = load_plugins(self.fee_strategy_plugin, self.client_encoder_plugin)?;
+                let signer = verify_integrity(&db_pool, signer, &signing_policy, &sender).await?;
+                let signer = confirm_signatures(signer, self.yes);
 
                 ibc.subcommand
-                    .execute(db_pool, signer, sender, color_choice)
+                    .execute(db_pool, signer, plugins, sender, color_choice, output)
                     .await?;
 
-                handle
-                    .await
-                    .context("unable to join event hook registrar task")?
+                supervisor.join().await
+            }
+            SubCommand::Import { file } => {
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let state: StateExport = serde_json::from_slice(
+                    &std::fs::read(&file)
+                        .with_context(|| format!("unable to read {}", file.display()))?,
+                )
+                .context("invalid state export")?;
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                let mut handler_registrar = HandlerRegistrar::try_from(self.handler)?;
+                handler_registrar.register(Box::new(AuditLogger::new(db_pool.clone())));
+                handler_registrar.register(Box::new(CliEventHandler::new(color_choice, output)));
+                handler_registrar.register(Box::new(WebhookNotifier::new(webhooks.clone())));
+                handler_registrar.register(Box::new(AlertNotifier::new(alerter.clone())));
+                let mut supervisor = TaskSupervisor::new();
+                let sender = handler_registrar.spawn(&mut supervisor, RestartPolicy::Never);
+
+                BackupService::new_with_notifier(db_pool, sender)
+                    .import(state)
+                    .await?;
+
+                supervisor.join().await
             }
             SubCommand::Init => {
                 ensure!(self.db_uri.is_some(), "`db-uri` is required");
@@ -159,39 +819,803 @@ impl Command {
 
                 run_migrations(&db_pool).await?;
 
-                let mut stdout = StandardStream::stdout(color_choice);
+                let mut stdout = output.stream(color_choice);
                 print_stream(
                     &mut stdout,
                     ColorSpec::new().set_bold(true),
                     "Initialized solo machine!",
                 )
             }
-            SubCommand::Start { addr } => {
+            SubCommand::Keys => {
+                ensure!(
+                    self.signer.is_some(),
+                    "`signer` is required for keys command"
+                );
+
+                let named_signers =
+                    load_named_signers(self.signer.unwrap(), self.signer_settings.as_ref())?;
+
+                let rows = named_signers
+                    .into_iter()
+                    .map(|(name, signer)| {
+                        Ok(vec![
+                            name.field(),
+                            signer.to_account_address()?.field(),
+                            signer.to_public_key()?.encode().field(),
+                        ])
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                ListTable::new(vec!["Name", "Address", "Public key"], rows)
+                    .print(output, color_choice)
+            }
+            #[cfg(feature = "keyring")]
+            SubCommand::Keychain(keychain) => keychain.subcommand.execute(color_choice, output),
+            SubCommand::Keystore(keystore) => keystore.subcommand.execute(color_choice, output),
+            SubCommand::Macro(macro_command) => {
+                ensure!(
+                    self.signer.is_some(),
+                    "`signer` is required for macro commands"
+                );
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                let mut handler_registrar = HandlerRegistrar::try_from(self.handler)?;
+                handler_registrar.register(Box::new(AuditLogger::new(db_pool.clone())));
+                handler_registrar.register(Box::new(StatsRecorder::new(db_pool.clone())));
+                handler_registrar.register(Box::new(CliEventHandler::new(color_choice, output)));
+                handler_registrar.register(Box::new(WebhookNotifier::new(webhooks.clone())));
+                handler_registrar.register(Box::new(AlertNotifier::new(alerter.clone())));
+                let mut supervisor = TaskSupervisor::new();
+                let sender = handler_registrar.spawn(&mut supervisor, RestartPolicy::Never);
+
+                let signer = load_signer(
+                    self.signer.unwrap(),
+                    self.fee_payer_signer,
+                    self.key.as_deref(),
+                    self.signer_settings.as_ref(),
+                )?;
+                let plugins = load_plugins(self.fee_strategy_plugin, self.client_encoder_plugin)?;
+                let signer = verify_integrity(&db_pool, signer, &signing_policy, &sender).await?;
+                let signer = confirm_signatures(signer, self.yes);
+
+                macro_command
+                    .subcommand
+                    .execute(db_pool, signer, plugins, sender, color_choice, output)
+                    .await?;
+
+                supervisor.join().await
+            }
+            SubCommand::Pipeline(pipeline) => {
+                ensure!(
+                    self.signer.is_some(),
+                    "`signer` is required for pipeline commands"
+                );
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                let mut handler_registrar = HandlerRegistrar::try_from(self.handler)?;
+                handler_registrar.register(Box::new(AuditLogger::new(db_pool.clone())));
+                handler_registrar.register(Box::new(StatsRecorder::new(db_pool.clone())));
+                handler_registrar.register(Box::new(CliEventHandler::new(color_choice, output)));
+                handler_registrar.register(Box::new(WebhookNotifier::new(webhooks.clone())));
+                handler_registrar.register(Box::new(AlertNotifier::new(alerter.clone())));
+                let mut supervisor = TaskSupervisor::new();
+                let sender = handler_registrar.spawn(&mut supervisor, RestartPolicy::Never);
+
+                let signer = load_signer(
+                    self.signer.unwrap(),
+                    self.fee_payer_signer,
+                    self.key.as_deref(),
+                    self.signer_settings.as_ref(),
+                )?;
+                let plugins = load_plugins(self.fee_strategy_plugin, self.client_encoder_plugin)?;
+                let signer = verify_integrity(&db_pool, signer, &signing_policy, &sender).await?;
+                let signer = confirm_signatures(signer, self.yes);
+
+                pipeline
+                    .subcommand
+                    .execute(db_pool, signer, plugins, sender, color_choice, output)
+                    .await?;
+
+                supervisor.join().await
+            }
+            SubCommand::Proof(proof) => proof.subcommand.execute(output),
+            SubCommand::Quickstart(quickstart) => {
                 ensure!(
                     self.signer.is_some(),
-                    "`signer` is required for gRPC server"
+                    "`signer` is required for quickstart command"
                 );
                 ensure!(self.db_uri.is_some(), "`db-uri` is required");
 
+                let db_uri = self.db_uri.unwrap();
+
+                let db_pool = match connect_db(&db_uri).await {
+                    Ok(db_pool) => db_pool,
+                    Err(_) => {
+                        init_db(&db_uri).await?;
+                        connect_db(&db_uri).await?
+                    }
+                };
+
+                run_migrations(&db_pool).await?;
+
+                let mut handler_registrar = HandlerRegistrar::try_from(self.handler)?;
+                handler_registrar.register(Box::new(AuditLogger::new(db_pool.clone())));
+                handler_registrar.register(Box::new(StatsRecorder::new(db_pool.clone())));
+                handler_registrar.register(Box::new(CliEventHandler::new(color_choice, output)));
+                handler_registrar.register(Box::new(WebhookNotifier::new(webhooks.clone())));
+                handler_registrar.register(Box::new(AlertNotifier::new(alerter.clone())));
+                let mut supervisor = TaskSupervisor::new();
+                let sender = handler_registrar.spawn(&mut supervisor, RestartPolicy::Never);
+
+                let signer = load_signer(
+                    self.signer.unwrap(),
+                    self.fee_payer_signer,
+                    self.key.as_deref(),
+                    self.signer_settings.as_ref(),
+                )?;
+                let plugins = load_plugins(self.fee_strategy_plugin, self.client_encoder_plugin)?;
+                let signer = verify_integrity(&db_pool, signer, &signing_policy, &sender).await?;
+                let signer = confirm_signatures(signer, self.yes);
+
+                let summary = quickstart.execute(db_pool, signer, plugins, sender).await?;
+
+                supervisor.join().await?;
+
+                summary.print(color_choice, output)
+            }
+            SubCommand::Repair { chain_id } => {
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+                let integrity_service = IntegrityService::new(db_pool.clone());
+                let audit_service = AuditService::new(db_pool.clone());
+
+                let (chain_ids, broken_audit_log_entry) = match chain_id {
+                    Some(chain_id) => (
+                        vec![
+                            ChainService::new(db_pool)
+                                .resolve_chain_id(&chain_id)
+                                .await?,
+                        ],
+                        None,
+                    ),
+                    None => {
+                        let mut chain_ids = integrity_service
+                            .verify()
+                            .await?
+                            .into_iter()
+                            .filter_map(|issue| issue.chain_id)
+                            .collect::<Vec<_>>();
+                        chain_ids.dedup();
+
+                        let broken_entry = audit_service.verify().await?.broken_entry;
+
+                        (chain_ids, broken_entry)
+                    }
+                };
+
+                let mut stdout = output.stream(color_choice);
+
+                if let Some((id, ref reason)) = broken_audit_log_entry {
+                    audit_service.accept_break(id, reason).await?;
+
+                    print_stream(
+                        &mut stdout,
+                        ColorSpec::new().set_bold(true),
+                        format!("Accepted broken audit log at entry {} ({})", id, reason),
+                    )?;
+                }
+
+                if chain_ids.is_empty() && broken_audit_log_entry.is_none() {
+                    return print_stream(
+                        &mut stdout,
+                        ColorSpec::new().set_bold(true),
+                        "No integrity issues found!",
+                    );
+                }
+
+                for chain_id in chain_ids {
+                    let sequence = integrity_service.repair(&chain_id).await?;
+
+                    print_stream(
+                        &mut stdout,
+                        ColorSpec::new().set_bold(true),
+                        format!("Repaired chain {} (sequence: {})", chain_id, sequence),
+                    )?;
+                }
+
+                Ok(())
+            }
+            SubCommand::Resync { chain_id, yes } => {
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+                let chain_service = ChainService::new(db_pool);
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+
+                let status = if yes {
+                    chain_service.resync_sequence(&chain_id).await?
+                } else {
+                    chain_service.check_sequence(&chain_id).await?
+                };
+
+                let mut table = KeyValueTable::default();
+
+                table.add("Solo machine client", &status.client_id);
+                table.add("Local sequence", status.local);
+                table.add("On-chain sequence", status.on_chain);
+
+                table.print(output, color_choice)?;
+
+                let mut stdout = output.stream(color_choice);
+
+                if status.is_in_sync() {
+                    return print_stream(
+                        &mut stdout,
+                        ColorSpec::new().set_bold(true),
+                        format!("Sequence of chain {} is in sync", chain_id),
+                    );
+                }
+
+                if yes {
+                    return print_stream(
+                        &mut stdout,
+                        ColorSpec::new().set_bold(true),
+                        format!(
+                            "Resynchronized sequence of chain {} from {} to {}",
+                            chain_id, status.local, status.on_chain
+                        ),
+                    );
+                }
+
+                if status.local > status.on_chain {
+                    print_stream(
+                        &mut stdout,
+                        ColorSpec::new().set_bold(true).set_fg(Some(Color::Yellow)),
+                        format!(
+                            "Local sequence is ahead of chain: sequences {} to {} will be signed again (only safe if signatures made at these sequences never reached chain)",
+                            status.on_chain,
+                            status.local - 1
+                        ),
+                    )?;
+                }
+
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Local sequence of chain {} will be set to {}. Re-run with `--yes` to resynchronize.",
+                        chain_id, status.on_chain
+                    ),
+                )
+            }
+            SubCommand::Relay {
+                chain_ids,
+                interval,
+                memo,
+                poll_only,
+            } => {
+                ensure!(self.signer.is_some(), "`signer` is required for relayer");
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
                 let db_pool = connect_db(&self.db_uri.unwrap()).await?;
                 let mut handler_registrar = HandlerRegistrar::try_from(self.handler)?;
-                handler_registrar.register(Box::new(EnvLogger::new()));
-                let (sender, handle) = handler_registrar.spawn();
+                handler_registrar.register(Box::new(AuditLogger::new(db_pool.clone())));
+                handler_registrar.register(Box::new(StatsRecorder::new(db_pool.clone())));
+                handler_registrar.register(Box::new(CliEventHandler::new(color_choice, output)));
+                handler_registrar.register(Box::new(WebhookNotifier::new(webhooks.clone())));
+                handler_registrar.register(Box::new(AlertNotifier::new(alerter.clone())));
+
+                // event hooks of a long running relayer are restarted on failure instead of stopping the relayer
+                let mut supervisor = TaskSupervisor::new();
+                let sender = handler_registrar.spawn(
+                    &mut supervisor,
+                    RestartPolicy::OnFailure {
+                        max_restarts: 10,
+                        backoff: Duration::from_secs(1),
+                    },
+                );
+
+                let chain_service = ChainService::new(db_pool.clone());
+                let mut resolved_chain_ids = Vec::with_capacity(chain_ids.len());
+
+                for chain_id in chain_ids {
+                    resolved_chain_ids.push(chain_service.resolve_chain_id(&chain_id).await?);
+                }
+
+                let signer = load_signer(
+                    self.signer.unwrap(),
+                    self.fee_payer_signer,
+                    self.key.as_deref(),
+                    self.signer_settings.as_ref(),
+                )?;
+                let plugins = load_plugins(self.fee_strategy_plugin, self.client_encoder_plugin)?;
+                let signer = verify_integrity(&db_pool, signer, &signing_policy, &sender).await?;
+
+                spawn_uptime_tracker(&mut supervisor, db_pool.clone());
+
+                let relayer = Arc::new(
+                    Relayer::new_with_notifier(
+                        db_pool,
+                        sender,
+                        RelayerConfig {
+                            interval,
+                            chain_ids: resolved_chain_ids,
+                            memo,
+                            subscribe: !poll_only,
+                        },
+                    )
+                    .with_plugins(plugins),
+                );
+
+                supervisor.spawn("relayer", RestartPolicy::Never, move |token| {
+                    let relayer = relayer.clone();
+                    let signer = signer.clone();
+
+                    async move { relayer.run(signer, token).await }
+                });
+
+                let token = supervisor.cancellation_token();
+
+                supervisor.spawn("shutdown-signal", RestartPolicy::Never, move |_| {
+                    let token = token.clone();
+
+                    async move {
+                        tokio::select! {
+                            result = signal::ctrl_c() => {
+                                result.context("unable to listen for shutdown signal")?;
+                                token.cancel();
+                            }
+                            _ = token.cancelled() => {}
+                        }
+
+                        Ok(())
+                    }
+                });
+
+                supervisor.join().await
+            }
+            #[cfg(feature = "server")]
+            SubCommand::Start {
+                addr,
+                require_api_key,
+                require_token,
+                rest_addr,
+                tls_cert,
+                tls_key,
+                tls_client_ca,
+                tls_allowed_client,
+            } => {
+                // requests to server are never approved by escalation
+                ensure!(
+                    !self.approve_escalation,
+                    "`approve-escalation` cannot be used when starting gRPC server"
+                );
+
+                let tls =
+                    match (tls_cert, tls_key, tls_client_ca) {
+                        (Some(cert), Some(key), Some(client_ca)) => Some(Arc::new(
+                            TlsConfig::load(&cert, &key, &client_ca, &tls_allowed_client)?,
+                        )),
+                        _ => None,
+                    };
+
+                let tenant_configs = match (&self.tenant, &self.config) {
+                    (None, Some(path)) => Config::load(path)?.tenants,
+                    _ => Default::default(),
+                };
+
+                let mut supervisor = TaskSupervisor::new();
+
+                let tenants = if tenant_configs.is_empty() {
+                    ensure!(
+                        self.signer.is_some(),
+                        "`signer` is required for gRPC server"
+                    );
+                    ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                    let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+                    let signer = load_signer(
+                        self.signer.unwrap(),
+                        self.fee_payer_signer,
+                        self.key.as_deref(),
+                        self.signer_settings.as_ref(),
+                    )?;
+                    let plugins =
+                        load_plugins(self.fee_strategy_plugin, self.client_encoder_plugin)?;
 
-                let signer = SignerRegistrar::try_from(self.signer.unwrap())?.unwrap()?;
+                    Tenants::Single(
+                        start_tenant(
+                            &mut supervisor,
+                            db_pool,
+                            signer,
+                            plugins,
+                            &signing_policy,
+                            &self.handler,
+                            &webhooks,
+                            &alerter,
+                            require_api_key,
+                            require_token,
+                        )
+                        .await?,
+                    )
+                } else {
+                    // stored state is unlocked with a process wide key which cannot differ between tenants
+                    ensure!(
+                        state_passphrase.is_none(),
+                        "encrypted state is not supported when serving multiple tenants"
+                    );
+                    // decoded IBC data is cached by ICS-24 paths which are not unique across databases of tenants
+                    set_ibc_cache_ttl(Duration::from_secs(0));
 
-                start_grpc(db_pool, signer, sender, addr).await?;
+                    let mut tenants = BTreeMap::new();
+                    let mut public_keys = BTreeMap::new();
 
-                handle
+                    for (id, tenant_config) in tenant_configs {
+                        let db_uri = tenant_config
+                            .db_uri
+                            .ok_or_else(|| anyhow!("`db_uri` is required for tenant `{}`", id))?;
+                        let signer = tenant_config
+                            .signer
+                            .load_signer()
+                            .with_context(|| format!("unable to load signer of tenant `{}`", id))?;
+                        let plugins = tenant_config.signer.load_plugins().with_context(|| {
+                            format!("unable to load plugins of tenant `{}`", id)
+                        })?;
+
+                        if let Some(other) =
+                            public_keys.insert(signer.to_public_key()?.encode(), id.clone())
+                        {
+                            anyhow::bail!(
+                                "tenants `{}` and `{}` use the same signing key",
+                                other,
+                                id
+                            );
+                        }
+
+                        let tenant = start_tenant(
+                            &mut supervisor,
+                            connect_db(&db_uri).await?,
+                            signer,
+                            plugins,
+                            &tenant_config.signing_policy,
+                            &self.handler,
+                            &webhooks,
+                            &alerter,
+                            require_api_key,
+                            require_token,
+                        )
+                        .await
+                        .with_context(|| format!("unable to start tenant `{}`", id))?;
+
+                        tracing::info!(tenant = %id, "serving tenant");
+                        tenants.insert(id, tenant);
+                    }
+
+                    Tenants::Multiple(tenants)
+                };
+
+                if let Some(rest_addr) = rest_addr {
+                    let tenants = tenants.clone();
+                    let tls = tls.clone();
+
+                    supervisor.spawn("rest-server", RestartPolicy::Never, move |token| {
+                        start_rest(tenants.clone(), rest_addr, tls.clone(), token)
+                    });
+                }
+
+                supervisor.spawn("grpc-server", RestartPolicy::Never, move |token| {
+                    start_grpc(tenants.clone(), addr, tls.clone(), token)
+                });
+
+                let token = supervisor.cancellation_token();
+
+                supervisor.spawn("shutdown-signal", RestartPolicy::Never, move |_| {
+                    let token = token.clone();
+
+                    async move {
+                        tokio::select! {
+                            result = signal::ctrl_c() => {
+                                result.context("unable to listen for shutdown signal")?;
+                                tracing::info!("shutting down solo machine");
+                                token.cancel();
+                            }
+                            _ = token.cancelled() => {}
+                        }
+
+                        Ok(())
+                    }
+                });
+
+                supervisor.join().await
+            }
+            SubCommand::Shell(shell) => {
+                ensure!(
+                    self.signer.is_some(),
+                    "`signer` is required for shell command"
+                );
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                let mut handler_registrar = HandlerRegistrar::try_from(self.handler.clone())?;
+                handler_registrar.register(Box::new(AuditLogger::new(db_pool.clone())));
+                handler_registrar.register(Box::new(StatsRecorder::new(db_pool.clone())));
+                handler_registrar.register(Box::new(CliEventHandler::new(color_choice, output)));
+                handler_registrar.register(Box::new(WebhookNotifier::new(webhooks.clone())));
+                handler_registrar.register(Box::new(AlertNotifier::new(alerter.clone())));
+                let mut supervisor = TaskSupervisor::new();
+                let sender = handler_registrar.spawn(&mut supervisor, RestartPolicy::Never);
+
+                let signer = load_signer(
+                    self.signer.unwrap(),
+                    self.fee_payer_signer,
+                    self.key.as_deref(),
+                    self.signer_settings.as_ref(),
+                )?;
+                let plugins = load_plugins(self.fee_strategy_plugin, self.client_encoder_plugin)?;
+                let signer = verify_integrity(&db_pool, signer, &signing_policy, &sender).await?;
+                let signer = confirm_signatures(signer, self.yes);
+
+                // every command in shell gets its own event pipeline
+                drop(sender);
+                supervisor.join().await?;
+
+                shell
+                    .execute(
+                        db_pool,
+                        signer,
+                        plugins,
+                        self.handler,
+                        color_choice,
+                        output,
+                        self.config,
+                    )
                     .await
-                    .context("unable to join event hook registrar task")?
+            }
+            SubCommand::State(state) => {
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                state
+                    .execute(db_pool, state_passphrase, color_choice, output)
+                    .await
+            }
+            SubCommand::Stats(stats) => {
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                stats.execute(db_pool, color_choice, output).await
+            }
+            SubCommand::Status(status) => {
+                ensure!(self.db_uri.is_some(), "`db-uri` is required");
+
+                let db_pool = connect_db(&self.db_uri.unwrap()).await?;
+
+                let signer = match self.signer {
+                    None => None,
+                    Some(signer) => Some(load_signer(
+                        signer,
+                        self.fee_payer_signer,
+                        self.key.as_deref(),
+                        self.signer_settings.as_ref(),
+                    )),
+                };
+
+                status.execute(db_pool, signer, color_choice, output).await
+            }
+            SubCommand::Tx(tx) => tx.subcommand.execute(output),
+            SubCommand::Version { attest, json } => {
+                if !attest {
+                    if output.is_text() {
+                        println!("solo-machine {}", env!("CARGO_PKG_VERSION"));
+                        return Ok(());
+                    }
+
+                    let mut table = KeyValueTable::default();
+                    table.add("Version", env!("CARGO_PKG_VERSION"));
+                    return table.print(output, color_choice);
+                }
+
+                print_attestation(&BuildAttestation::current()?, json, color_choice, output)
             }
         }
     }
 }
 
-fn add_row(table: &mut Vec<RowStruct>, title: &str, value: impl Display) {
-    table.push(vec![title.cell().bold(true), value.cell()].row());
+/// Verifies integrity of stored state on startup and returns a signer which refuses to sign if any inconsistency is
+/// found (until it is fixed using `repair` command), or which evaluates signing policy (including its signature rate
+/// limits) before signing and records all of its signatures in signature log otherwise
+async fn verify_integrity(
+    db_pool: &DbPool,
+    signer: Arc<dyn Signer>,
+    signing_policy: &SigningPolicy,
+    sender: &UnboundedSender<Event>,
+) -> Result<Arc<dyn Signer>> {
+    let issues = IntegrityService::new(db_pool.clone()).verify().await?;
+
+    if issues.is_empty() {
+        let signer: Arc<dyn Signer> = if signing_policy.is_empty() {
+            signer
+        } else {
+            Arc::new(PolicySigner::new(signer, signing_policy.clone()))
+        };
+
+        let signer: Arc<dyn Signer> = Arc::new(AuditedSigner::new(signer, db_pool.clone()));
+        let rate_limits = signing_policy.signature_rate_limits();
+
+        // signatures are counted in signature log, so, rate limited signer wraps the one recording signatures
+        return Ok(if rate_limits.is_empty() {
+            signer
+        } else {
+            Arc::new(RateLimitedSigner::new(signer, db_pool.clone(), rate_limits))
+        });
+    }
+
+    for issue in issues.iter() {
+        sender
+            .send(Event::Warning {
+                message: format!("storage integrity check failed: {}", issue),
+            })
+            .context("unable to send event")?;
+    }
+
+    Ok(Arc::new(LockedSigner::new(
+        signer,
+        "storage integrity check failed on startup (run `solo-machine repair` after investigating reported issues)"
+            .to_string(),
+    )))
+}
+
+/// Returns a signer asking for confirmation on terminal before every signature (unless confirmation is skipped)
+fn confirm_signatures(signer: Arc<dyn Signer>, yes: bool) -> Arc<dyn Signer> {
+    if yes {
+        signer
+    } else {
+        Arc::new(ConfirmingSigner::new(signer))
+    }
+}
+
+/// Starts event pipeline (restarted on failure) and uptime tracking of a solo machine identity served by gRPC and REST
+/// servers after verifying integrity of its stored state
+#[cfg(feature = "server")]
+#[allow(clippy::too_many_arguments)]
+async fn start_tenant(
+    supervisor: &mut TaskSupervisor,
+    db_pool: DbPool,
+    signer: Arc<dyn Signer>,
+    plugins: Plugins,
+    signing_policy: &SigningPolicy,
+    handlers: &[PathBuf],
+    webhooks: &[Webhook],
+    alerter: &Arc<Alerter>,
+    require_api_key: bool,
+    require_token: bool,
+) -> Result<Tenant<Arc<dyn Signer>>> {
+    signing_policy.validate()?;
+
+    let mut handler_registrar = HandlerRegistrar::try_from(handlers.to_vec())?;
+    handler_registrar.register(Box::new(AuditLogger::new(db_pool.clone())));
+    handler_registrar.register(Box::new(StatsRecorder::new(db_pool.clone())));
+    handler_registrar.register(Box::new(EventLogger::new()));
+    handler_registrar.register(Box::new(WebhookNotifier::new(webhooks.to_vec())));
+    handler_registrar.register(Box::new(AlertNotifier::new(alerter.clone())));
+
+    // event hooks of a long running server are restarted on failure instead of stopping the server
+    let sender = handler_registrar.spawn(
+        supervisor,
+        RestartPolicy::OnFailure {
+            max_restarts: 10,
+            backoff: Duration::from_secs(1),
+        },
+    );
+
+    let signer = verify_integrity(&db_pool, signer, signing_policy, &sender).await?;
+
+    spawn_uptime_tracker(supervisor, db_pool.clone());
+
+    Ok(Tenant {
+        authorizer: Arc::new(Authorizer::new(
+            db_pool.clone(),
+            require_api_key,
+            require_token,
+        )),
+        db_pool,
+        signer,
+        plugins,
+        sender,
+    })
+}
+
+/// Spawns a supervised task which records uptime of a long running solo machine process in persistent daily stats
+fn spawn_uptime_tracker(supervisor: &mut TaskSupervisor, db_pool: DbPool) {
+    let stats_service = Arc::new(StatsService::new(db_pool));
+
+    supervisor.spawn(
+        "uptime-tracker",
+        RestartPolicy::OnFailure {
+            max_restarts: 10,
+            backoff: Duration::from_secs(1),
+        },
+        move |token| {
+            let stats_service = stats_service.clone();
+
+            async move {
+                stats_service
+                    .track_uptime(UPTIME_RECORD_INTERVAL, token)
+                    .await
+            }
+        },
+    );
+}
+
+/// Prints transaction built in dry-run mode as JSON (or YAML for `yaml` output)
+fn print_dry_run(report: &DryRunReport, output: OutputFormat) -> Result<()> {
+    output.print(report)
+}
+
+/// Prints exported transaction as JSON
+fn print_tx_export(tx: &serde_json::Value) -> Result<()> {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(tx).context("unable to serialize exported transaction")?
+    );
+    Ok(())
+}
+
+fn print_attestation(
+    attestation: &BuildAttestation,
+    json: bool,
+    color_choice: ColorChoice,
+    output: OutputFormat,
+) -> Result<()> {
+    if json || output == OutputFormat::Json {
+        println!("{}", attestation.to_canonical_json()?);
+        return Ok(());
+    }
+
+    if output == OutputFormat::Yaml {
+        return output.print(attestation);
+    }
+
+    let mut table = KeyValueTable::default();
+
+    table.add("Digest", attestation.digest()?);
+    table.add(
+        "Crate",
+        format!("{} {}", attestation.crate_name, attestation.crate_version),
+    );
+    table.add(
+        "Git commit",
+        attestation.git_commit.as_deref().unwrap_or("-"),
+    );
+    table.add(
+        "Rust compiler",
+        attestation.rustc_version.as_deref().unwrap_or("-"),
+    );
+    table.add("Target", &attestation.target);
+    table.add("Profile", &attestation.profile);
+    table.add("Features", attestation.features.join(", "));
+    table.add(
+        "Cargo.lock hash",
+        attestation.lockfile_hash.as_deref().unwrap_or("-"),
+    );
+
+    for (name, version) in attestation.dependencies.iter() {
+        table.add(&format!("Dependency {}", name), version);
+    }
+
+    for (file, hash) in attestation.proto_hashes.iter() {
+        table.add(&format!("Proto {}", file), hash);
+    }
+
+    table.print(output, color_choice)
 }
 
 fn print_stream(
@@ -0,0 +1,186 @@
+use std::io::Write;
+
+use anyhow::Result;
+use cli_table::{format::Justify, Color, ColorChoice};
+use solo_machine_core::{
+    ibc::core::ics24_host::identifier::Identifier,
+    model::{ApiKey, ApiOperation},
+    service::{ApiKeyService, ChainService},
+    DbPool,
+};
+use structopt::StructOpt;
+use termcolor::ColorSpec;
+
+use crate::{
+    command::print_stream,
+    output::{Field, KeyValueTable, ListTable, OutputFormat, ToField},
+};
+
+#[derive(Debug, StructOpt)]
+pub enum ApiKeyCommand {
+    /// Creates a new API key scoped to the current channel of an IBC enabled chain (API token is printed only once)
+    Create {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Operation authorized by API key (can be provided multiple times)
+        #[structopt(
+            long = "operation",
+            required = true,
+            possible_values = &["mint", "burn"]
+        )]
+        operations: Vec<ApiOperation>,
+        /// Denom to which operations are restricted (any denom is allowed when not provided)
+        #[structopt(long)]
+        denom: Option<Identifier>,
+        /// Maximum amount of a single operation (amount is not limited when not provided)
+        #[structopt(long)]
+        max_amount: Option<u64>,
+        /// Human-readable label of API key
+        #[structopt(long)]
+        label: Option<String>,
+    },
+    /// Lists API keys
+    List {
+        /// Chain ID (or label) of IBC enabled chain (API keys of all the chains are listed when not provided)
+        chain_id: Option<String>,
+    },
+    /// Revokes an API key
+    Revoke {
+        /// ID of API key
+        id: String,
+    },
+}
+
+impl ApiKeyCommand {
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        color_choice: ColorChoice,
+        output: OutputFormat,
+    ) -> Result<()> {
+        let chain_service = ChainService::new(db_pool.clone());
+        let api_key_service = ApiKeyService::new(db_pool);
+
+        match self {
+            Self::Create {
+                chain_id,
+                operations,
+                denom,
+                max_amount,
+                label,
+            } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+
+                let (api_key, token) = api_key_service
+                    .create(&chain_id, operations, denom, max_amount, label)
+                    .await?;
+
+                let mut table = KeyValueTable::default();
+
+                table.add("ID", &api_key.id);
+                table.add("Chain ID", &api_key.chain_id);
+                table.add("Channel ID", &api_key.channel_id);
+                table.add("Operations", display_operations(&api_key));
+                table.add("Denom", display_denom(&api_key));
+                table.add("Max amount", display_max_amount(&api_key));
+                table.add("Label", api_key.label.as_deref().unwrap_or("-"));
+                table.add("API token", &token);
+
+                table.print(output, color_choice)?;
+
+                let mut stdout = output.stream(color_choice);
+                writeln!(stdout)?;
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new()
+                        .set_bold(true)
+                        .set_fg(Some(termcolor::Color::Yellow)),
+                    "Store API token securely, it cannot be retrieved again!",
+                )
+            }
+            Self::List { chain_id } => {
+                let chain_id = match chain_id {
+                    None => None,
+                    Some(ref chain_id) => Some(chain_service.resolve_chain_id(chain_id).await?),
+                };
+
+                let rows = api_key_service
+                    .list(chain_id.as_ref())
+                    .await?
+                    .into_iter()
+                    .map(into_row)
+                    .collect::<Vec<_>>();
+
+                ListTable::new(
+                    vec![
+                        "ID",
+                        "Chain ID",
+                        "Channel ID",
+                        "Operations",
+                        "Denom",
+                        "Max amount",
+                        "Label",
+                        "Status",
+                        "Created at",
+                    ],
+                    rows,
+                )
+                .print(output, color_choice)
+            }
+            Self::Revoke { id } => {
+                api_key_service.revoke(&id).await?;
+
+                let mut stdout = output.stream(color_choice);
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!("Revoked API key {}", id),
+                )
+            }
+        }
+    }
+}
+
+fn into_row(api_key: ApiKey) -> Vec<Field> {
+    let status = match api_key.revoked_at {
+        None => "active".field().foreground_color(Some(Color::Green)),
+        Some(revoked_at) => format!("revoked at {}", revoked_at)
+            .field()
+            .foreground_color(Some(Color::Red)),
+    };
+
+    vec![
+        api_key.id.as_str().field(),
+        (&api_key.chain_id).field(),
+        (&api_key.channel_id).field(),
+        display_operations(&api_key).field(),
+        display_denom(&api_key).field(),
+        display_max_amount(&api_key).field().justify(Justify::Right),
+        api_key.label.as_deref().unwrap_or("-").field(),
+        status,
+        (&api_key.created_at).field(),
+    ]
+}
+
+fn display_operations(api_key: &ApiKey) -> String {
+    api_key
+        .operations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn display_denom(api_key: &ApiKey) -> String {
+    api_key
+        .denom
+        .as_ref()
+        .map_or_else(|| "any".to_string(), ToString::to_string)
+}
+
+fn display_max_amount(api_key: &ApiKey) -> String {
+    api_key.max_amount.map_or_else(
+        || "unlimited".to_string(),
+        |max_amount| max_amount.to_string(),
+    )
+}
@@ -0,0 +1,111 @@
+use std::io::Write;
+
+use anyhow::Result;
+use cli_table::{Color, ColorChoice};
+use solo_machine_core::{
+    model::{ApiRole, ApiToken},
+    service::ApiTokenService,
+    DbPool,
+};
+use structopt::StructOpt;
+use termcolor::ColorSpec;
+
+use crate::{
+    command::print_stream,
+    output::{Field, KeyValueTable, ListTable, OutputFormat, ToField},
+};
+
+#[derive(Debug, StructOpt)]
+pub enum ApiTokenCommand {
+    /// Creates a new API token with a role (API token is printed only once)
+    Create {
+        /// Role of API token (`read-only` can only query state, `operator` can also sign and broadcast transactions
+        /// and `admin` can also add chains, update signer and modify sequences)
+        #[structopt(long, possible_values = &["read-only", "operator", "admin"])]
+        role: ApiRole,
+        /// Human-readable label of API token
+        #[structopt(long)]
+        label: Option<String>,
+    },
+    /// Lists API tokens
+    List,
+    /// Revokes an API token
+    Revoke {
+        /// ID of API token
+        id: String,
+    },
+}
+
+impl ApiTokenCommand {
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        color_choice: ColorChoice,
+        output: OutputFormat,
+    ) -> Result<()> {
+        let api_token_service = ApiTokenService::new(db_pool);
+
+        match self {
+            Self::Create { role, label } => {
+                let (api_token, token) = api_token_service.create(role, label).await?;
+
+                let mut table = KeyValueTable::default();
+
+                table.add("ID", &api_token.id);
+                table.add("Role", api_token.role);
+                table.add("Label", api_token.label.as_deref().unwrap_or("-"));
+                table.add("API token", &token);
+
+                table.print(output, color_choice)?;
+
+                let mut stdout = output.stream(color_choice);
+                writeln!(stdout)?;
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new()
+                        .set_bold(true)
+                        .set_fg(Some(termcolor::Color::Yellow)),
+                    "Store API token securely, it cannot be retrieved again!",
+                )
+            }
+            Self::List => {
+                let rows = api_token_service
+                    .list()
+                    .await?
+                    .into_iter()
+                    .map(into_row)
+                    .collect::<Vec<_>>();
+
+                ListTable::new(vec!["ID", "Role", "Label", "Status", "Created at"], rows)
+                    .print(output, color_choice)
+            }
+            Self::Revoke { id } => {
+                api_token_service.revoke(&id).await?;
+
+                let mut stdout = output.stream(color_choice);
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!("Revoked API token {}", id),
+                )
+            }
+        }
+    }
+}
+
+fn into_row(api_token: ApiToken) -> Vec<Field> {
+    let status = match api_token.revoked_at {
+        None => "active".field().foreground_color(Some(Color::Green)),
+        Some(revoked_at) => format!("revoked at {}", revoked_at)
+            .field()
+            .foreground_color(Some(Color::Red)),
+    };
+
+    vec![
+        api_token.id.as_str().field(),
+        api_token.role.field(),
+        api_token.label.as_deref().unwrap_or("-").field(),
+        status,
+        (&api_token.created_at).field(),
+    ]
+}
@@ -0,0 +1,179 @@
+use anyhow::{anyhow, Result};
+use cli_table::{format::Justify, ColorChoice};
+use solo_machine_core::{
+    model::{AuditLogEntry, SignatureLogEntry},
+    service::AuditService,
+    DbPool,
+};
+use structopt::StructOpt;
+
+use crate::{
+    command::print_attestation,
+    output::{Field, KeyValueTable, ListTable, OutputFormat, ToField},
+};
+
+#[derive(Debug, StructOpt)]
+pub enum AuditCommand {
+    /// Verifies hash chain of audit log
+    Verify,
+    /// Check entries of audit log
+    History {
+        #[structopt(long, default_value = "10")]
+        limit: u32,
+        #[structopt(long, default_value)]
+        offset: u32,
+    },
+    /// Verifies hash chain of signature log
+    VerifySignatures,
+    /// Check entries of signature log (messages signed by signer along with their signatures)
+    Signatures {
+        #[structopt(long, default_value = "10")]
+        limit: u32,
+        #[structopt(long, default_value)]
+        offset: u32,
+    },
+    /// Shows recorded build attestation (referred by audit log entries) of solo machine which logged the entries
+    Attestation {
+        /// Digest of build attestation
+        digest: String,
+        /// Prints build attestation as canonical JSON (from which attestation digest is computed)
+        #[structopt(long)]
+        json: bool,
+    },
+}
+
+impl AuditCommand {
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        color_choice: ColorChoice,
+        output: OutputFormat,
+    ) -> Result<()> {
+        let audit_service = AuditService::new(db_pool);
+
+        match self {
+            Self::Verify => {
+                let verification = audit_service.verify().await?;
+
+                let mut table = KeyValueTable::default();
+
+                table.add("Verified entries", verification.verified_entries);
+                table.add("Head hash", &verification.head_hash);
+                table.add(
+                    "Accepted breaks",
+                    if verification.accepted_breaks.is_empty() {
+                        "-".to_string()
+                    } else {
+                        verification
+                            .accepted_breaks
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    },
+                );
+                table.add(
+                    "Status",
+                    match verification.broken_entry {
+                        None => "valid".to_string(),
+                        Some((id, ref reason)) => format!("broken at entry {} ({})", id, reason),
+                    },
+                );
+
+                table.print(output, color_choice)
+            }
+            Self::History { limit, offset } => {
+                let entries = audit_service.entries(limit, offset).await?;
+
+                let rows = entries
+                    .into_iter()
+                    .map(into_row)
+                    .collect::<Result<Vec<_>>>()?;
+
+                ListTable::new(
+                    vec!["ID", "Event", "Hash", "Attestation", "Created at"],
+                    rows,
+                )
+                .print(output, color_choice)
+            }
+            Self::VerifySignatures => {
+                let verification = audit_service.verify_signatures().await?;
+
+                let mut table = KeyValueTable::default();
+
+                table.add("Verified entries", verification.verified_entries);
+                table.add("Head hash", &verification.head_hash);
+                table.add(
+                    "Status",
+                    match verification.broken_entry {
+                        None => "valid".to_string(),
+                        Some((id, ref reason)) => format!("broken at entry {} ({})", id, reason),
+                    },
+                );
+
+                table.print(output, color_choice)
+            }
+            Self::Signatures { limit, offset } => {
+                let entries = audit_service.signatures(limit, offset).await?;
+
+                let rows = entries
+                    .into_iter()
+                    .map(into_signature_row)
+                    .collect::<Vec<_>>();
+
+                ListTable::new(
+                    vec![
+                        "ID",
+                        "Type",
+                        "Purpose",
+                        "Chain ID",
+                        "Sequence",
+                        "Message hash",
+                        "Signature",
+                        "Signed at",
+                    ],
+                    rows,
+                )
+                .print(output, color_choice)
+            }
+            Self::Attestation { digest, json } => {
+                let attestation = audit_service
+                    .attestation(&digest)
+                    .await?
+                    .ok_or_else(|| anyhow!("build attestation with digest {} not found", digest))?;
+
+                print_attestation(&attestation, json, color_choice, output)
+            }
+        }
+    }
+}
+
+fn into_row(entry: AuditLogEntry) -> Result<Vec<Field>> {
+    Ok(vec![
+        entry.id.field().justify(Justify::Right),
+        serde_json::to_string(&entry.event)?.field(),
+        entry.hash.field(),
+        entry.attestation.as_deref().unwrap_or("-").field(),
+        entry.created_at.field(),
+    ])
+}
+
+fn into_signature_row(entry: SignatureLogEntry) -> Vec<Field> {
+    let record = entry.record;
+
+    vec![
+        entry.id.field().justify(Justify::Right),
+        record.message_type.field(),
+        record.purpose.field(),
+        record.chain_id.as_deref().unwrap_or("-").field(),
+        record
+            .sequence
+            .map(|sequence| sequence.to_string())
+            .unwrap_or_else(|| "-".to_string())
+            .field()
+            .justify(Justify::Right),
+        record.message_hash.field(),
+        record.signature.field(),
+        entry.created_at.field(),
+    ]
+}
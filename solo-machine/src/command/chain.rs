@@ -1,24 +1,72 @@
-use std::{io::Write, time::Duration};
+use std::{fs, io::Write, path::PathBuf, time::Duration};
 
-use anyhow::{ensure, Context, Result};
-use cli_table::{format::Justify, print_stdout, Cell, Row, RowStruct, Style, Table};
+use anyhow::{anyhow, ensure, Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use cli_table::format::Justify;
 use humantime::format_duration;
 use num_rational::Ratio;
 use rust_decimal::Decimal;
 use solo_machine_core::{
+    broadcaster::{BroadcastMode, BroadcastTransport, InclusionPolicy},
+    client_encoder::ClientEncoding,
+    cosmos::{
+        address::AddressFormat,
+        amino::SignMode,
+        chain_registry::{ChainRegistryEntry, DEFAULT_REGISTRY_URL},
+        name_resolver::{AddressDisplay, NameResolverConfig, NameResolverKind},
+    },
+    fee::FeeStrategyKind,
     ibc::core::ics24_host::identifier::{ChainId, Identifier, PortId},
-    model::{ChainConfig, ChainKey, Fee},
+    ibc_version::SoloMachineRevision,
+    model::{
+        ChainConfig, ChainKey, ChainLabel, Fee, GeneratedIdentifiers, GrpcTlsConfig, LabelKind,
+        NetworkTimeouts,
+    },
+    plugins::Plugins,
+    proxy::Proxy,
+    rate_limit::RateLimit,
+    retry::{RetryPolicy, RetryableCode},
     service::ChainService,
-    DbPool, Event, ToPublicKey,
+    signer::AddressAlgo,
+    subscriber::{EventSubscriber, IbcEvent, IbcEventHandler},
+    supervisor::CancellationToken,
+    DbPool, Event, Signer,
 };
 use structopt::StructOpt;
 use tendermint::block::Height as BlockHeight;
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
-use tokio::sync::mpsc::UnboundedSender;
+use tokio::{signal, sync::mpsc::UnboundedSender};
+
+use crate::{
+    command::{print_dry_run, print_stream, print_tx_export, TxExport, TX_EXPORT_VARIANTS},
+    config::{ChainEntry, Config},
+    output::{Field, KeyValueTable, ListTable, OutputFormat, ToField},
+};
 
-use crate::command::add_row;
+const NAME_RESOLVER_VARIANTS: [&str; 2] = ["icns", "stargaze-names"];
+const ADDRESS_DISPLAY_VARIANTS: [&str; 3] = ["address", "name", "name-and-address"];
+const ADDRESS_FORMAT_VARIANTS: [&str; 2] = ["bech32", "eip55"];
+const SIGN_MODE_VARIANTS: [&str; 3] = ["direct", "legacy-amino-json", "eip712"];
+const ADDRESS_ALGO_VARIANTS: [&str; 2] = ["secp256k1", "eth-secp256k1"];
+const LABEL_KIND_VARIANTS: [&str; 3] = ["chain", "connection", "channel"];
+const FEE_STRATEGY_VARIANTS: [&str; 3] = ["static", "simulate", "plugin"];
+const CLIENT_ENCODING_VARIANTS: [&str; 4] = ["standard", "custom", "plugin", "wasm"];
+const CLIENT_REVISION_VARIANTS: [&str; 3] = ["v1", "v2", "v3"];
+const RETRYABLE_CODE_VARIANTS: [&str; 7] = [
+    "cancelled",
+    "unknown",
+    "deadline-exceeded",
+    "resource-exhausted",
+    "aborted",
+    "internal",
+    "unavailable",
+];
+const BROADCAST_TRANSPORT_VARIANTS: [&str; 2] = ["rpc", "grpc"];
+const BROADCAST_MODE_VARIANTS: [&str; 3] = ["sync", "async", "commit"];
 
 #[derive(Debug, StructOpt)]
+#[allow(clippy::large_enum_variant)]
 pub enum ChainCommand {
     /// Adds metadata for new IBC enabled chain
     Add {
@@ -30,6 +78,13 @@ pub enum ChainCommand {
             hide_env_values = true
         )]
         grpc_addr: String,
+        /// PEM encoded root CA certificate trusted for `https` gRPC address (in addition to native root certificates)
+        #[structopt(long, env = "SOLO_GRPC_CA_CERT", hide_env_values = true)]
+        grpc_ca_cert: Option<PathBuf>,
+        /// Domain name used for SNI and for verifying TLS certificate of `https` gRPC address (host of gRPC address
+        /// by default)
+        #[structopt(long, env = "SOLO_GRPC_TLS_DOMAIN", hide_env_values = true)]
+        grpc_tls_domain: Option<String>,
         /// RPC address of IBC enabled chain
         #[structopt(
             long,
@@ -38,6 +93,28 @@ pub enum ChainCommand {
             hide_env_values = true
         )]
         rpc_addr: String,
+        /// Fallback gRPC addresses used when gRPC endpoint of chain is failing (comma separated, in order of
+        /// preference)
+        #[structopt(
+            long,
+            use_delimiter = true,
+            env = "SOLO_FALLBACK_GRPC_ADDRESSES",
+            hide_env_values = true
+        )]
+        fallback_grpc_addrs: Vec<String>,
+        /// Fallback RPC addresses used when RPC endpoint of chain is failing or serving stale data (comma separated,
+        /// in order of preference)
+        #[structopt(
+            long,
+            use_delimiter = true,
+            env = "SOLO_FALLBACK_RPC_ADDRESSES",
+            hide_env_values = true
+        )]
+        fallback_rpc_addrs: Vec<String>,
+        /// Proxy through which RPC and gRPC connections to chain are tunnelled (`http://[user:pass@]host:port`,
+        /// `socks5://...` or `socks5h://...`, e.g. `socks5h://127.0.0.1:9050` for Tor)
+        #[structopt(long, env = "SOLO_PROXY", hide_env_values = true)]
+        proxy: Option<Proxy>,
         /// Fee amount
         #[structopt(
             long,
@@ -119,12 +196,233 @@ pub enum ChainCommand {
         /// Block hash at trusted height of the chain
         #[structopt(long, env = "SOLO_TRUSTED_HASH", hide_env_values = true, parse(try_from_str = parse_trusted_hash))]
         trusted_hash: [u8; 32],
+        /// Name service used to resolve human-readable names to account addresses
+        #[structopt(long, possible_values = &NAME_RESOLVER_VARIANTS, env = "SOLO_NAME_RESOLVER", hide_env_values = true, requires_all = &["name-resolver-grpc-addr", "name-resolver-contract"])]
+        name_resolver: Option<NameResolverKind>,
+        /// gRPC address of the chain hosting name resolver contract
+        #[structopt(long, env = "SOLO_NAME_RESOLVER_GRPC_ADDRESS", hide_env_values = true)]
+        name_resolver_grpc_addr: Option<String>,
+        /// Address of name resolver contract
+        #[structopt(long, env = "SOLO_NAME_RESOLVER_CONTRACT", hide_env_values = true)]
+        name_resolver_contract: Option<String>,
+        /// Format used when displaying account addresses
+        #[structopt(
+            long,
+            possible_values = &ADDRESS_DISPLAY_VARIANTS,
+            default_value = "address",
+            env = "SOLO_ADDRESS_DISPLAY",
+            hide_env_values = true
+        )]
+        address_display: AddressDisplay,
+        /// Format in which account addresses are displayed and accepted (`eip55` displays and accepts 0x-prefixed
+        /// checksummed addresses on ethermint based chains, bech32 addresses are still accepted)
+        #[structopt(
+            long,
+            possible_values = &ADDRESS_FORMAT_VARIANTS,
+            default_value = "bech32",
+            env = "SOLO_ADDRESS_FORMAT",
+            hide_env_values = true
+        )]
+        address_format: AddressFormat,
+        /// Sign mode used when signing transactions for chain (some chains and hardware wallets only support
+        /// `legacy-amino-json`, ethermint based chains may require `eip712`)
+        #[structopt(
+            long,
+            possible_values = &SIGN_MODE_VARIANTS,
+            default_value = "direct",
+            env = "SOLO_SIGN_MODE",
+            hide_env_values = true
+        )]
+        sign_mode: SignMode,
+        /// Algorithm used for generating account addresses on chain (ethermint based chains with native addresses
+        /// use `eth-secp256k1`). Account addresses of signer on chain are derived using this algorithm.
+        #[structopt(
+            long,
+            possible_values = &ADDRESS_ALGO_VARIANTS,
+            default_value = "secp256k1",
+            env = "SOLO_CHAIN_ADDRESS_ALGO",
+            hide_env_values = true
+        )]
+        address_algo: AddressAlgo,
+        /// Maximum age of latest block after which chain is considered halted (operations consuming sequences are
+        /// paused until block production resumes, `0s` disables halt detection)
+        #[structopt(
+            long,
+            default_value = "2 min",
+            env = "SOLO_HALT_THRESHOLD",
+            hide_env_values = true,
+            parse(try_from_str = humantime::parse_duration)
+        )]
+        halt_threshold: Duration,
+        /// Maximum time to wait for a transaction to be included in a block
+        #[structopt(
+            long,
+            default_value = "1 min",
+            env = "SOLO_INCLUSION_TIMEOUT",
+            hide_env_values = true,
+            parse(try_from_str = humantime::parse_duration)
+        )]
+        inclusion_timeout: Duration,
+        /// Interval at which chain is polled while waiting for a transaction to be included in a block
+        #[structopt(
+            long,
+            default_value = "1s",
+            env = "SOLO_INCLUSION_POLL_INTERVAL",
+            hide_env_values = true,
+            parse(try_from_str = humantime::parse_duration)
+        )]
+        inclusion_poll_interval: Duration,
+        /// Maximum number of attempts of queries made to chain which fail due to transient network errors (`1`
+        /// disables retries)
+        #[structopt(
+            long,
+            default_value = "3",
+            env = "SOLO_RETRY_MAX_ATTEMPTS",
+            hide_env_values = true
+        )]
+        retry_max_attempts: u32,
+        /// Backoff before first retry of a failed query (doubled after every retry)
+        #[structopt(
+            long,
+            default_value = "500ms",
+            env = "SOLO_RETRY_INITIAL_BACKOFF",
+            hide_env_values = true,
+            parse(try_from_str = humantime::parse_duration)
+        )]
+        retry_initial_backoff: Duration,
+        /// Maximum backoff between retries of a failed query
+        #[structopt(
+            long,
+            default_value = "10s",
+            env = "SOLO_RETRY_MAX_BACKOFF",
+            hide_env_values = true,
+            parse(try_from_str = humantime::parse_duration)
+        )]
+        retry_max_backoff: Duration,
+        /// Fraction of backoff randomly added to or subtracted from it (between 0 and 1)
+        #[structopt(
+            long,
+            default_value = "0.2",
+            env = "SOLO_RETRY_JITTER",
+            hide_env_values = true
+        )]
+        retry_jitter: f64,
+        /// gRPC status codes on which failed queries are retried (comma separated)
+        #[structopt(
+            long,
+            possible_values = &RETRYABLE_CODE_VARIANTS,
+            use_delimiter = true,
+            default_value = "unavailable,deadline-exceeded,resource-exhausted,aborted",
+            env = "SOLO_RETRY_CODES",
+            hide_env_values = true
+        )]
+        retry_codes: Vec<RetryableCode>,
+        /// Maximum time for establishing a connection with gRPC endpoint of chain (including TLS handshake)
+        #[structopt(
+            long,
+            default_value = "10s",
+            env = "SOLO_CONNECT_TIMEOUT",
+            hide_env_values = true,
+            parse(try_from_str = humantime::parse_duration)
+        )]
+        connect_timeout: Duration,
+        /// Maximum time for completing a single RPC or gRPC request made to chain
+        #[structopt(
+            long,
+            default_value = "30s",
+            env = "SOLO_REQUEST_TIMEOUT",
+            hide_env_values = true,
+            parse(try_from_str = humantime::parse_duration)
+        )]
+        request_timeout: Duration,
+        /// Maximum number of RPC and gRPC requests per second made to each endpoint of chain (not rate limited when
+        /// not provided)
+        #[structopt(long, env = "SOLO_RATE_LIMIT", hide_env_values = true)]
+        rate_limit: Option<f64>,
+        /// Maximum number of requests which can be made to an endpoint in a burst (defaults to one second worth of
+        /// requests)
+        #[structopt(
+            long,
+            requires = "rate-limit",
+            env = "SOLO_RATE_LIMIT_BURST",
+            hide_env_values = true
+        )]
+        rate_limit_burst: Option<u32>,
+        /// Derives consensus timestamp of solo machine from time of latest verified block of chain plus given offset
+        /// (synced whenever a new header of chain is verified and never moved backwards) instead of using host clock
+        #[structopt(
+            long,
+            env = "SOLO_TIMESTAMP_SYNC_OFFSET",
+            hide_env_values = true,
+            parse(try_from_str = humantime::parse_duration)
+        )]
+        timestamp_sync_offset: Option<Duration>,
+        /// Strategy used for computing transaction fees (`static` uses configured fee as is, `simulate` computes gas
+        /// limit by simulating transactions and `plugin` uses fee strategy plugin provided using
+        /// `--fee-strategy-plugin`)
+        #[structopt(
+            long,
+            possible_values = &FEE_STRATEGY_VARIANTS,
+            default_value = "static",
+            env = "SOLO_FEE_STRATEGY",
+            hide_env_values = true
+        )]
+        fee_strategy: String,
+        /// Multiplier applied to simulated gas usage (used with `simulate` fee strategy)
+        #[structopt(
+            long,
+            default_value = "1.3",
+            env = "SOLO_GAS_ADJUSTMENT",
+            hide_env_values = true
+        )]
+        gas_adjustment: f64,
+        /// Encoding of solo machine client types (`standard` uses ibc-go type URLs, `custom` uses type URLs provided
+        /// using `--*-type-url` options, `plugin` uses client encoder plugin provided using `--client-encoder-plugin`
+        /// and `wasm` wraps standard types in ICS-08 wasm client types)
+        #[structopt(
+            long,
+            possible_values = &CLIENT_ENCODING_VARIANTS,
+            default_value = "standard",
+            env = "SOLO_CLIENT_ENCODING",
+            hide_env_values = true
+        )]
+        client_encoding: String,
+        /// Type URL of solo machine client state (used with `custom` client encoding)
+        #[structopt(long, env = "SOLO_CLIENT_STATE_TYPE_URL", hide_env_values = true)]
+        client_state_type_url: Option<String>,
+        /// Type URL of solo machine consensus state (used with `custom` client encoding)
+        #[structopt(long, env = "SOLO_CONSENSUS_STATE_TYPE_URL", hide_env_values = true)]
+        consensus_state_type_url: Option<String>,
+        /// Type URL of solo machine header (used with `custom` client encoding)
+        #[structopt(long, env = "SOLO_HEADER_TYPE_URL", hide_env_values = true)]
+        header_type_url: Option<String>,
+        /// Hex encoded checksum of wasm light client code stored on chain (used with `wasm` client encoding)
+        #[structopt(long, env = "SOLO_WASM_CHECKSUM", hide_env_values = true)]
+        wasm_checksum: Option<String>,
+        /// Revision of solo machine client types (`v1` for cosmos SDK and ibc-go v1, `v2` for ibc-go v2 to v6 and `v3`
+        /// for ibc-go v7 onwards, selected based on detected IBC version of chain when not provided)
+        #[structopt(
+            long,
+            possible_values = &CLIENT_REVISION_VARIANTS,
+            env = "SOLO_CLIENT_REVISION",
+            hide_env_values = true
+        )]
+        client_revision: Option<SoloMachineRevision>,
+        /// Verifies (using gRPC IBC queries) that client, connection and channel referenced by a message are in
+        /// expected state on chain before signing it, failing fast instead of consuming sequences on doomed
+        /// transactions
+        #[structopt(long)]
+        strict_preflight: bool,
     },
     /// Fetches current state and metadata for an IBC enabled chain
-    Get { chain_id: ChainId },
+    Get {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+    },
     /// Fetches all the public keys associated with solo machine client on given chain
     GetPublicKeys {
-        chain_id: ChainId,
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
         #[structopt(long, default_value = "10")]
         limit: u32,
         #[structopt(long, default_value)]
@@ -132,30 +430,226 @@ pub enum ChainCommand {
     },
     /// Returns the final denom of a token on solo machine after sending it on given chain
     GetIbcDenom {
-        chain_id: ChainId,
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
         denom: Identifier,
     },
     /// Fetches balance of given denom on IBC enabled chain
     Balance {
-        chain_id: ChainId,
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
         denom: Identifier,
     },
+    /// Broadcasts an encoded and signed transaction (`TxRaw` bytes read from given file) to IBC enabled chain
+    Broadcast {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Path of file containing encoded transaction (`TxRaw`)
+        tx_file: PathBuf,
+        /// Endpoint of chain used for broadcasting transaction
+        #[structopt(
+            long,
+            possible_values = &BROADCAST_TRANSPORT_VARIANTS,
+            default_value = "rpc",
+            env = "SOLO_BROADCAST_TRANSPORT",
+            hide_env_values = true
+        )]
+        transport: BroadcastTransport,
+        /// Broadcast mode (`sync` waits for `check_tx`, `async` returns immediately and `commit` waits for
+        /// transaction to be committed in a block)
+        #[structopt(
+            long,
+            possible_values = &BROADCAST_MODE_VARIANTS,
+            default_value = "commit",
+            env = "SOLO_BROADCAST_MODE",
+            hide_env_values = true
+        )]
+        mode: BroadcastMode,
+        /// Waits for transaction to be included in a block (using inclusion timeout and poll interval of chain)
+        #[structopt(long)]
+        wait: bool,
+    },
+    /// Grants fee allowance from fee payer account (`--fee-payer-signer`) to signer and uses it for paying fees of
+    /// all the future transactions on given chain
+    GrantAllowance {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Maximum amount of fee denom that can be spent using the allowance (no limit if not provided)
+        #[structopt(long)]
+        spend_limit: Option<Decimal>,
+        /// Duration after which the allowance expires (never expires if not provided)
+        #[structopt(long, parse(try_from_str = humantime::parse_duration))]
+        expires_in: Option<Duration>,
+        /// Optional memo to include in transactions
+        #[structopt(
+            long,
+            default_value = "solo-machine-memo",
+            env = "SOLO_MEMO",
+            hide_env_values = true
+        )]
+        memo: String,
+        /// Optional request ID (for tracking purposes)
+        #[structopt(long)]
+        request_id: Option<String>,
+        /// Prints the transaction which would be built (messages, fee and sign bytes in hex) as JSON without signing,
+        /// persisting or broadcasting anything
+        #[structopt(long)]
+        dry_run: bool,
+        /// Prints the transaction (`unsigned` or `signed`) in JSON layout of cosmos SDK CLIs (can be signed and
+        /// broadcasted using `tx sign` and `tx broadcast` of chain's CLI) instead of broadcasting it
+        #[structopt(long, possible_values = &TX_EXPORT_VARIANTS, conflicts_with = "dry-run")]
+        export: Option<TxExport>,
+    },
+    /// Adds an IBC enabled chain using its entry in cosmos chain registry (`chain.json` and `assetlist.json`): chain
+    /// ID, fee denom and gas price, and RPC/gRPC endpoints are taken from registry and latest block of chain is
+    /// trusted. Bech32 prefix of chain should match account prefix of signer.
+    ImportRegistry {
+        /// Name of chain in registry (e.g. `cosmoshub`, or `testnets/cosmoshubtestnet` for testnets)
+        chain_name: String,
+        /// Base URL of chain registry
+        #[structopt(
+            long,
+            default_value = DEFAULT_REGISTRY_URL,
+            env = "SOLO_CHAIN_REGISTRY_URL",
+            hide_env_values = true
+        )]
+        registry_url: String,
+        /// RPC address of chain (first reachable RPC endpoint listed in registry is used when not provided)
+        #[structopt(long)]
+        rpc_addr: Option<String>,
+        /// gRPC address of chain (first gRPC endpoint listed in registry is used when not provided)
+        #[structopt(long)]
+        grpc_addr: Option<String>,
+        /// Gas limit (fee amount is computed using average gas price of fee denom listed in registry)
+        #[structopt(
+            long,
+            default_value = "300000",
+            env = "SOLO_GAS_LIMIT",
+            hide_env_values = true
+        )]
+        gas_limit: u64,
+        /// Port ID used to create connection with chain
+        #[structopt(
+            long,
+            default_value = "transfer",
+            env = "SOLO_PORT_ID",
+            hide_env_values = true
+        )]
+        port_id: PortId,
+    },
+    /// Assigns a label to a chain (or to a connection/channel on chain) which can be used in place of its ID. If the
+    /// label is already in use, it is reassigned (older assignments are preserved in label history)
+    SetLabel {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Label to assign
+        label: String,
+        /// Assign label to given connection on chain instead of chain itself
+        #[structopt(long, conflicts_with = "channel-id")]
+        connection_id: Option<String>,
+        /// Assign label to given channel on chain instead of chain itself
+        #[structopt(long)]
+        channel_id: Option<String>,
+    },
+    /// Removes a label of a chain, connection or channel (label is preserved in label history)
+    RemoveLabel {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Label to remove
+        label: String,
+        /// Kind of labelled entity
+        #[structopt(long, possible_values = &LABEL_KIND_VARIANTS, default_value = "chain")]
+        kind: LabelKind,
+    },
+    /// Fetches history of all the labels (including removed ones)
+    LabelHistory {
+        #[structopt(long, default_value = "10")]
+        limit: u32,
+        #[structopt(long, default_value)]
+        offset: u32,
+    },
+    /// Revokes fee allowance granted by fee payer account (`--fee-payer-signer`) to signer on given chain
+    RevokeAllowance {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Optional memo to include in transactions
+        #[structopt(
+            long,
+            default_value = "solo-machine-memo",
+            env = "SOLO_MEMO",
+            hide_env_values = true
+        )]
+        memo: String,
+        /// Optional request ID (for tracking purposes)
+        #[structopt(long)]
+        request_id: Option<String>,
+        /// Prints the transaction which would be built (messages, fee and sign bytes in hex) as JSON without signing,
+        /// persisting or broadcasting anything
+        #[structopt(long)]
+        dry_run: bool,
+        /// Prints the transaction (`unsigned` or `signed`) in JSON layout of cosmos SDK CLIs (can be signed and
+        /// broadcasted using `tx sign` and `tx broadcast` of chain's CLI) instead of broadcasting it
+        #[structopt(long, possible_values = &TX_EXPORT_VARIANTS, conflicts_with = "dry-run")]
+        export: Option<TxExport>,
+    },
+    /// Resets IBC state of a chain which has been reset (e.g. a devnet restarted from a new genesis): removes stored
+    /// tendermint client, connection and channel of chain along with their labels and trusts a new block of chain
+    /// (keys and other chains are preserved). Without `--yes`, only checks chain for a reset and prints what would be
+    /// removed.
+    ResetState {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Trusted height of the reset chain (latest block of chain is trusted when not provided)
+        #[structopt(long, requires = "trusted-hash")]
+        trusted_height: Option<BlockHeight>,
+        /// Block hash at trusted height of the reset chain
+        #[structopt(long, requires = "trusted-height", parse(try_from_str = parse_trusted_hash))]
+        trusted_hash: Option<[u8; 32]>,
+        /// Resets state even when no reset of chain is detected
+        #[structopt(long)]
+        force: bool,
+        /// Performs the reset (only a preview is printed otherwise)
+        #[structopt(long)]
+        yes: bool,
+    },
+    /// Adds all the chains defined in configuration file (`--config`) which are not added yet (chains which are
+    /// already added are skipped and their stored configuration is left unchanged)
+    Sync,
+    /// Subscribes to events of chain over websocket and prints IBC events (packets, acknowledgements and channel
+    /// handshake steps) as they occur, until interrupted
+    Watch {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Also prints new blocks
+        #[structopt(long)]
+        blocks: bool,
+    },
 }
 
 impl ChainCommand {
+    #[allow(clippy::needless_borrows_for_generic_args, clippy::too_many_arguments)]
     pub async fn execute(
         self,
         db_pool: DbPool,
-        signer: impl ToPublicKey,
+        signer: impl Signer,
+        plugins: Plugins,
         sender: UnboundedSender<Event>,
         color_choice: ColorChoice,
+        output: OutputFormat,
+        config: Option<PathBuf>,
     ) -> Result<()> {
-        let chain_service = ChainService::new_with_notifier(db_pool, sender);
+        let chain_service =
+            ChainService::new_with_notifier(db_pool, sender.clone()).with_plugins(plugins);
 
         match self {
             Self::Add {
                 grpc_addr,
+                grpc_ca_cert,
+                grpc_tls_domain,
                 rpc_addr,
+                fallback_grpc_addrs,
+                fallback_rpc_addrs,
+                proxy,
                 fee_amount,
                 fee_denom,
                 gas_limit,
@@ -167,14 +661,116 @@ impl ChainCommand {
                 port_id,
                 trusted_height,
                 trusted_hash,
+                name_resolver,
+                name_resolver_grpc_addr,
+                name_resolver_contract,
+                address_display,
+                address_format,
+                sign_mode,
+                address_algo,
+                halt_threshold,
+                inclusion_timeout,
+                inclusion_poll_interval,
+                retry_max_attempts,
+                retry_initial_backoff,
+                retry_max_backoff,
+                retry_jitter,
+                retry_codes,
+                connect_timeout,
+                request_timeout,
+                rate_limit,
+                rate_limit_burst,
+                timestamp_sync_offset,
+                fee_strategy,
+                gas_adjustment,
+                client_encoding,
+                client_state_type_url,
+                consensus_state_type_url,
+                header_type_url,
+                wasm_checksum,
+                client_revision,
+                strict_preflight,
             } => {
+                ensure!(
+                    !inclusion_poll_interval.is_zero(),
+                    "inclusion poll interval should be greater than zero"
+                );
+
+                let fee_strategy = match fee_strategy.as_str() {
+                    "static" => FeeStrategyKind::Static,
+                    "simulate" => {
+                        ensure!(gas_adjustment > 0.0, "gas adjustment should be positive");
+                        FeeStrategyKind::Simulate { gas_adjustment }
+                    }
+                    "plugin" => FeeStrategyKind::Plugin,
+                    other => return Err(anyhow!("unknown fee strategy: {}", other)),
+                };
+
+                let client_encoding = match client_encoding.as_str() {
+                    "standard" => ClientEncoding::Standard,
+                    "custom" => {
+                        ensure!(
+                            client_state_type_url.is_some()
+                                || consensus_state_type_url.is_some()
+                                || header_type_url.is_some(),
+                            "at least one type URL should be provided with `custom` client encoding"
+                        );
+                        ClientEncoding::Custom {
+                            client_state_type_url,
+                            consensus_state_type_url,
+                            header_type_url,
+                        }
+                    }
+                    "plugin" => ClientEncoding::Plugin,
+                    "wasm" => {
+                        let checksum = wasm_checksum.ok_or_else(|| {
+                            anyhow!(
+                                "`--wasm-checksum` should be provided with `wasm` client encoding"
+                            )
+                        })?;
+                        ensure!(
+                            matches!(hex::decode(&checksum), Ok(bytes) if bytes.len() == 32),
+                            "invalid wasm checksum: expected 32 bytes hex encoded checksum"
+                        );
+                        ClientEncoding::Wasm {
+                            checksum: checksum.to_lowercase(),
+                        }
+                    }
+                    other => return Err(anyhow!("unknown client encoding: {}", other)),
+                };
+
+                let name_resolver = name_resolver.map(|kind| {
+                    NameResolverConfig::new(
+                        kind,
+                        name_resolver_grpc_addr.unwrap_or_default(),
+                        name_resolver_contract.unwrap_or_default(),
+                    )
+                });
+
+                // chain configuration outlives current directory, so, path of CA certificate is stored as absolute path
+                let grpc_ca_cert = grpc_ca_cert
+                    .map(|path| {
+                        fs::canonicalize(&path).with_context(|| {
+                            format!("unable to find grpc ca certificate {}", path.display())
+                        })
+                    })
+                    .transpose()?;
+
                 let config = ChainConfig {
                     grpc_addr,
+                    grpc_tls: GrpcTlsConfig {
+                        ca_cert: grpc_ca_cert,
+                        domain_name: grpc_tls_domain,
+                    },
                     rpc_addr,
+                    fallback_grpc_addrs,
+                    fallback_rpc_addrs,
+                    proxy,
                     fee: Fee {
                         amount: fee_amount,
                         denom: fee_denom,
                         gas_limit,
+                        granter: None,
                     },
                     trust_level,
                     trusting_period,
@@ -184,6 +780,34 @@ impl ChainCommand {
                     port_id,
                     trusted_height,
                     trusted_hash,
+                    name_resolver,
+                    address_display,
+                    address_format,
+                    sign_mode,
+                    address_algo,
+                    halt_threshold,
+                    fee_strategy,
+                    client_encoding,
+                    inclusion_policy: InclusionPolicy {
+                        timeout: inclusion_timeout,
+                        interval: inclusion_poll_interval,
+                    },
+                    retry_policy: RetryPolicy {
+                        max_attempts: retry_max_attempts,
+                        initial_backoff: retry_initial_backoff,
+                        max_backoff: retry_max_backoff,
+                        jitter: retry_jitter,
+                        retryable_codes: retry_codes,
+                    },
+                    timeouts: NetworkTimeouts {
+                        connect: connect_timeout,
+                        request: request_timeout,
+                    },
+                    rate_limit: rate_limit.map(|rps| RateLimit::new(rps, rate_limit_burst)),
+                    timestamp_sync_offset,
+                    ibc_version: None,
+                    client_revision,
+                    strict_preflight,
                 };
 
                 chain_service
@@ -192,11 +816,12 @@ impl ChainCommand {
                     .map(|_| ())
             }
             Self::Get { ref chain_id } => {
+                let chain_id = &chain_service.resolve_chain_id(chain_id).await?;
                 let chain = chain_service.get(chain_id).await?;
 
                 match chain {
                     None => {
-                        let mut stdout = StandardStream::stdout(color_choice);
+                        let mut stdout = output.stream(color_choice);
                         stdout
                             .set_color(ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)))?;
                         writeln!(&mut stdout, "Chain with id `{}` not found!", chain_id)
@@ -204,102 +829,244 @@ impl ChainCommand {
                         stdout.reset().context("unable to reset stdout")
                     }
                     Some(ref chain) => {
-                        let mut table = Vec::new();
-
-                        add_row(&mut table, "ID", &chain.id);
-                        add_row(&mut table, "Node ID", &chain.node_id);
-                        add_row(&mut table, "gRPC address", &chain.config.grpc_addr);
-                        add_row(&mut table, "RPC address", &chain.config.rpc_addr);
-                        add_row(&mut table, "Fee amount", &chain.config.fee.amount);
-                        add_row(&mut table, "Fee denom", &chain.config.fee.denom);
-                        add_row(&mut table, "Gas limit", &chain.config.fee.gas_limit);
-                        add_row(&mut table, "Trust level", &chain.config.trust_level);
-                        add_row(
-                            &mut table,
+                        let mut table = KeyValueTable::default();
+
+                        table.add("ID", &chain.id);
+                        table.add(
+                            "Label",
+                            chain_service
+                                .get_label(&chain.id, LabelKind::Chain, &chain.id.to_string())
+                                .await?
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                        table.add("Node ID", &chain.node_id);
+                        table.add("gRPC address", &chain.config.grpc_addr);
+                        table.add(
+                            "gRPC CA certificate",
+                            chain
+                                .config
+                                .grpc_tls
+                                .ca_cert
+                                .as_ref()
+                                .map(|path| path.display().to_string())
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                        table.add(
+                            "gRPC TLS domain",
+                            chain.config.grpc_tls.domain_name.as_deref().unwrap_or("-"),
+                        );
+                        table.add("RPC address", &chain.config.rpc_addr);
+                        table.add(
+                            "Fallback gRPC addresses",
+                            if chain.config.fallback_grpc_addrs.is_empty() {
+                                "-".to_string()
+                            } else {
+                                chain.config.fallback_grpc_addrs.join(", ")
+                            },
+                        );
+                        table.add(
+                            "Fallback RPC addresses",
+                            if chain.config.fallback_rpc_addrs.is_empty() {
+                                "-".to_string()
+                            } else {
+                                chain.config.fallback_rpc_addrs.join(", ")
+                            },
+                        );
+                        table.add(
+                            "Proxy",
+                            chain
+                                .config
+                                .proxy
+                                .as_ref()
+                                .map(ToString::to_string)
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                        table.add("Fee amount", &chain.config.fee.amount);
+                        table.add("Fee denom", &chain.config.fee.denom);
+                        table.add("Gas limit", &chain.config.fee.gas_limit);
+                        table.add(
+                            "Fee granter",
+                            chain.config.fee.granter.as_deref().unwrap_or("-"),
+                        );
+                        table.add("Trust level", &chain.config.trust_level);
+                        table.add(
                             "Trusting period",
                             format_duration(chain.config.trusting_period),
                         );
-                        add_row(
-                            &mut table,
+                        table.add(
                             "Maximum clock drift",
                             format_duration(chain.config.max_clock_drift),
                         );
-                        add_row(
-                            &mut table,
-                            "RPC timeout",
-                            format_duration(chain.config.rpc_timeout),
-                        );
-                        add_row(&mut table, "Diversifier", &chain.config.diversifier);
-                        add_row(&mut table, "Port ID", &chain.config.port_id);
-                        add_row(&mut table, "Trusted height", &chain.config.trusted_height);
-                        add_row(
-                            &mut table,
+                        table.add("RPC timeout", format_duration(chain.config.rpc_timeout));
+                        table.add("Diversifier", &chain.config.diversifier);
+                        table.add("Port ID", &chain.config.port_id);
+                        table.add("Trusted height", &chain.config.trusted_height);
+                        table.add(
                             "Trusted hash",
                             hex::encode_upper(&chain.config.trusted_hash),
                         );
-                        add_row(
-                            &mut table,
-                            "Consensus timestamp",
-                            &chain.consensus_timestamp,
+                        table.add(
+                            "Name resolver",
+                            chain
+                                .config
+                                .name_resolver
+                                .as_ref()
+                                .map(|name_resolver| name_resolver.kind().to_string())
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                        table.add("Address display", chain.config.address_display);
+                        table.add("Address format", chain.config.address_format);
+                        table.add("Sign mode", chain.config.sign_mode);
+                        table.add("Address algorithm", chain.config.address_algo);
+                        table.add(
+                            "Halt threshold",
+                            format_duration(chain.config.halt_threshold),
+                        );
+                        table.add(
+                            "Inclusion timeout",
+                            format_duration(chain.config.inclusion_policy.timeout),
+                        );
+                        table.add(
+                            "Inclusion poll interval",
+                            format_duration(chain.config.inclusion_policy.interval),
+                        );
+                        table.add(
+                            "Retry policy",
+                            format!(
+                                "{} attempts, {} to {} backoff, {} jitter (codes: {})",
+                                chain.config.retry_policy.max_attempts,
+                                format_duration(chain.config.retry_policy.initial_backoff),
+                                format_duration(chain.config.retry_policy.max_backoff),
+                                chain.config.retry_policy.jitter,
+                                chain
+                                    .config
+                                    .retry_policy
+                                    .retryable_codes
+                                    .iter()
+                                    .map(ToString::to_string)
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            ),
+                        );
+                        table.add(
+                            "Network timeouts",
+                            format!(
+                                "{} connect, {} request",
+                                format_duration(chain.config.timeouts.connect),
+                                format_duration(chain.config.timeouts.request)
+                            ),
                         );
-                        add_row(&mut table, "Sequence", &chain.sequence);
-                        add_row(&mut table, "Packet sequence", &chain.packet_sequence);
+                        table.add(
+                            "Rate limit",
+                            chain
+                                .config
+                                .rate_limit
+                                .as_ref()
+                                .map(|rate_limit| {
+                                    format!(
+                                        "{} requests/s (burst {})",
+                                        rate_limit.requests_per_second, rate_limit.burst
+                                    )
+                                })
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                        table.add("Fee strategy", chain.config.fee_strategy);
+                        table.add("Client encoding", &chain.config.client_encoding);
+                        table.add(
+                            "Timestamp sync offset",
+                            chain
+                                .config
+                                .timestamp_sync_offset
+                                .map(|offset| format_duration(offset).to_string())
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                        table.add(
+                            "IBC version",
+                            chain
+                                .config
+                                .ibc_version
+                                .as_ref()
+                                .map(ToString::to_string)
+                                .unwrap_or_else(|| "-".to_string()),
+                        );
+                        table.add(
+                            "Client revision",
+                            match chain.config.client_revision {
+                                Some(revision) => revision.to_string(),
+                                None => {
+                                    format!("{} (auto)", chain.config.solo_machine_revision())
+                                }
+                            },
+                        );
+                        table.add("Strict pre-flight", chain.config.strict_preflight);
+                        table.add("Consensus timestamp", &chain.consensus_timestamp);
+                        table.add("Sequence", &chain.sequence);
+                        table.add("Packet sequence", &chain.packet_sequence);
 
                         match chain.connection_details {
-                            None => table.push(
-                                vec![
-                                    "Connection status".cell().bold(true),
-                                    "Not Connected".cell().foreground_color(Some(Color::Red)),
-                                ]
-                                .row(),
-                            ),
+                            None => match chain.handshake_progress {
+                                None => table.add_field(
+                                    "Connection status",
+                                    "Not Connected".field().foreground_color(Some(Color::Red)),
+                                ),
+                                Some(ref handshake_progress) => {
+                                    table.add_field(
+                                        "Connection status",
+                                        "Handshake Interrupted"
+                                            .field()
+                                            .foreground_color(Some(Color::Yellow)),
+                                    );
+
+                                    table.add("Handshake progress", handshake_progress);
+                                }
+                            },
                             Some(ref connection_details) => {
-                                table.push(
-                                    vec![
-                                        "Connection status".cell().bold(true),
-                                        "Connected".cell().foreground_color(Some(Color::Green)),
-                                    ]
-                                    .row(),
+                                table.add_field(
+                                    "Connection status",
+                                    "Connected".field().foreground_color(Some(Color::Green)),
                                 );
 
-                                add_row(
-                                    &mut table,
+                                table.add(
                                     "Solo machine client ID",
                                     &connection_details.solo_machine_channel_id,
                                 );
-                                add_row(
-                                    &mut table,
+                                table.add(
                                     "Tendermint client ID",
                                     &connection_details.tendermint_client_id,
                                 );
-                                add_row(
-                                    &mut table,
+                                table.add(
                                     "Solo machine connection ID",
                                     &connection_details.solo_machine_connection_id,
                                 );
-                                add_row(
-                                    &mut table,
+                                table.add(
                                     "Tendermint connection ID",
                                     &connection_details.tendermint_connection_id,
                                 );
-                                add_row(
-                                    &mut table,
+                                table.add(
                                     "Solo machine channel ID",
                                     &connection_details.solo_machine_channel_id,
                                 );
-                                add_row(
-                                    &mut table,
+                                table.add(
                                     "Tendermint channel ID",
                                     &connection_details.tendermint_channel_id,
                                 );
                             }
                         }
 
-                        add_row(&mut table, "Created at", &chain.created_at);
-                        add_row(&mut table, "Updated at", &chain.updated_at);
+                        if !chain.generated_identifiers.is_empty() {
+                            table.add("Generated identifiers", &chain.generated_identifiers);
+                        }
 
-                        print_stdout(table.table().color_choice(color_choice))
-                            .context("unable to print table to stdout")
+                        table.add(
+                            "Genesis time",
+                            chain
+                                .genesis_time
+                                .map_or_else(|| "-".to_string(), |time| time.to_string()),
+                        );
+                        table.add("Created at", &chain.created_at);
+                        table.add("Updated at", &chain.updated_at);
+
+                        table.print(output, color_choice)
                     }
                 }
             }
@@ -308,57 +1075,537 @@ impl ChainCommand {
                 limit,
                 offset,
             } => {
+                let chain_id = &chain_service.resolve_chain_id(chain_id).await?;
                 let keys = chain_service
                     .get_public_keys(chain_id, limit, offset)
                     .await?;
 
-                let table = keys
-                    .into_iter()
-                    .map(into_row)
-                    .collect::<Vec<RowStruct>>()
-                    .table()
-                    .title(vec![
-                        "ID".cell().bold(true),
-                        "Chain ID".cell().bold(true),
-                        "Public key".cell().bold(true),
-                        "Created at".cell().bold(true),
-                    ])
-                    .color_choice(color_choice);
-
-                print_stdout(table).context("unable to print table to stdout")
+                let rows = keys.into_iter().map(into_row).collect::<Vec<_>>();
+
+                ListTable::new(vec!["ID", "Chain ID", "Public key", "Created at"], rows)
+                    .print(output, color_choice)
             }
             Self::GetIbcDenom {
                 ref chain_id,
                 ref denom,
             } => {
+                let chain_id = &chain_service.resolve_chain_id(chain_id).await?;
                 let ibc_denom = chain_service.get_ibc_denom(chain_id, denom).await?;
 
-                let table = vec![vec![
-                    "IBC denom".cell().bold(true),
+                let mut table = KeyValueTable::default();
+
+                table.add_field(
+                    "IBC denom",
                     ibc_denom
-                        .cell()
+                        .field()
                         .bold(true)
                         .foreground_color(Some(Color::Green))
                         .justify(Justify::Right),
-                ]]
-                .table()
-                .color_choice(color_choice);
+                );
 
-                print_stdout(table).context("unable to print table to stdout")
+                table.print(output, color_choice)
             }
             Self::Balance { chain_id, denom } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
                 let balance = chain_service.balance(signer, &chain_id, &denom).await?;
 
-                let table = vec![vec![
-                    "Balance".cell().bold(true),
-                    format!("{} {}", balance, denom).cell(),
-                ]]
-                .table()
-                .color_choice(color_choice);
+                let mut table = KeyValueTable::default();
+
+                table.add("Balance", format!("{} {}", balance, denom));
+
+                table.print(output, color_choice)
+            }
+            Self::Broadcast {
+                chain_id,
+                tx_file,
+                transport,
+                mode,
+                wait,
+            } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+                let tx_bytes = fs::read(&tx_file).context(format!(
+                    "unable to read transaction from {}",
+                    tx_file.display()
+                ))?;
+
+                let result = chain_service
+                    .broadcast(&chain_id, tx_bytes, transport, mode, wait)
+                    .await?;
+
+                let mut table = KeyValueTable::default();
+
+                table.add("Mode", result.mode);
+                table.add("Transaction hash", &result.transaction_hash);
+                table.add(
+                    "Height",
+                    result
+                        .height
+                        .map(|height| height.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                );
+
+                for (stage, execution) in [
+                    ("Check tx", &result.check_tx),
+                    ("Deliver tx", &result.deliver_tx),
+                ] {
+                    if let Some(execution) = execution {
+                        table.add(
+                            &format!("{} code", stage),
+                            format!("{} ({})", execution.code, execution.codespace),
+                        );
+                        table.add(&format!("{} log", stage), &execution.log);
+                        table.add(
+                            &format!("{} gas (used / wanted)", stage),
+                            format!("{} / {}", execution.gas_used, execution.gas_wanted),
+                        );
+                    }
+                }
+
+                let generated_identifiers = GeneratedIdentifiers::from_events(result.events())?;
+
+                if !generated_identifiers.is_empty() {
+                    table.add("Generated identifiers", generated_identifiers);
+                }
+
+                table.print(output, color_choice)?;
+
+                result.ensure_success().map(|_| ())
+            }
+            Self::GrantAllowance {
+                chain_id,
+                spend_limit,
+                expires_in,
+                memo,
+                request_id,
+                dry_run,
+                export,
+            } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+                let granter = signer.fee_payer().ok_or_else(|| {
+                    anyhow!("`fee-payer-signer` is required for granting fee allowance")
+                })?;
+
+                let expiration = expires_in
+                    .map(|expires_in| {
+                        chrono::Duration::from_std(expires_in).map(|d| Utc::now() + d)
+                    })
+                    .transpose()
+                    .context("invalid allowance expiration duration")?;
+
+                if dry_run {
+                    let report = chain_service
+                        .dry_run_grant_allowance(
+                            granter,
+                            &signer,
+                            &chain_id,
+                            spend_limit,
+                            expiration,
+                            memo,
+                        )
+                        .await?;
+                    return print_dry_run(&report, output);
+                }
+
+                if let Some(export) = export {
+                    let tx = chain_service
+                        .export_grant_allowance(
+                            granter,
+                            &signer,
+                            &chain_id,
+                            spend_limit,
+                            expiration,
+                            memo,
+                            request_id,
+                            export.is_signed(),
+                        )
+                        .await?;
+                    return print_tx_export(&tx);
+                }
+
+                chain_service
+                    .grant_allowance(
+                        granter,
+                        &signer,
+                        &chain_id,
+                        spend_limit,
+                        expiration,
+                        memo,
+                        request_id,
+                    )
+                    .await
+                    .map(|_| ())
+            }
+            Self::RevokeAllowance {
+                chain_id,
+                memo,
+                request_id,
+                dry_run,
+                export,
+            } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+                let granter = signer.fee_payer().ok_or_else(|| {
+                    anyhow!("`fee-payer-signer` is required for revoking fee allowance")
+                })?;
+
+                if dry_run {
+                    let report = chain_service
+                        .dry_run_revoke_allowance(granter, &signer, &chain_id, memo)
+                        .await?;
+                    return print_dry_run(&report, output);
+                }
+
+                if let Some(export) = export {
+                    let tx = chain_service
+                        .export_revoke_allowance(
+                            granter,
+                            &signer,
+                            &chain_id,
+                            memo,
+                            request_id,
+                            export.is_signed(),
+                        )
+                        .await?;
+                    return print_tx_export(&tx);
+                }
+
+                chain_service
+                    .revoke_allowance(granter, &signer, &chain_id, memo, request_id)
+                    .await
+                    .map(|_| ())
+            }
+            Self::ImportRegistry {
+                chain_name,
+                registry_url,
+                rpc_addr,
+                grpc_addr,
+                gas_limit,
+                port_id,
+            } => {
+                let registry = ChainRegistryEntry::fetch(&registry_url, &chain_name).await?;
+
+                ensure!(
+                    signer.get_account_prefix() == registry.chain.bech32_prefix,
+                    "account prefix of signer (`{}`) does not match bech32 prefix of {} (`{}`)",
+                    signer.get_account_prefix(),
+                    registry.chain.chain_id,
+                    registry.chain.bech32_prefix
+                );
+
+                let chain_id: ChainId = registry.chain.chain_id.parse()?;
+
+                ensure!(
+                    chain_service.get(&chain_id).await?.is_none(),
+                    "chain {} is already added",
+                    chain_id
+                );
+
+                let fee_denom = registry.fee_denom().ok_or_else(|| {
+                    anyhow!("no fee denom is listed for {}", registry.chain.chain_id)
+                })?;
 
-                print_stdout(table).context("unable to print table to stdout")
+                let grpc_addr = match grpc_addr {
+                    Some(grpc_addr) => grpc_addr,
+                    None => registry.grpc_addrs().into_iter().next().ok_or_else(|| {
+                        anyhow!("no gRPC endpoint is listed for {}", registry.chain.chain_id)
+                    })?,
+                };
+
+                let rpc_addrs = match rpc_addr {
+                    Some(rpc_addr) => vec![rpc_addr],
+                    None => registry.rpc_addrs(),
+                };
+
+                ensure!(
+                    !rpc_addrs.is_empty(),
+                    "no RPC endpoint is listed for {}",
+                    registry.chain.chain_id
+                );
+
+                let mut entry = ChainEntry {
+                    id: Some(registry.chain.chain_id.clone()),
+                    grpc_addr: Some(grpc_addr),
+                    fee_amount: registry.fee_amount(gas_limit),
+                    fee_denom: Some(fee_denom.parse()?),
+                    gas_limit: Some(gas_limit),
+                    port_id: Some(port_id),
+                    ..Default::default()
+                };
+
+                let mut stdout = output.stream(color_choice);
+                let mut status = None;
+
+                for rpc_addr in rpc_addrs {
+                    entry.rpc_addr = Some(rpc_addr.clone());
+
+                    match entry.status().await {
+                        Ok(response) => {
+                            status = Some(response);
+                            break;
+                        }
+                        Err(err) => print_stream(
+                            &mut stdout,
+                            ColorSpec::new().set_fg(Some(Color::Yellow)),
+                            format!("Skipping RPC endpoint {}: {:#}", rpc_addr, err),
+                        )?,
+                    }
+                }
+
+                let status = status.ok_or_else(|| {
+                    anyhow!(
+                        "none of the RPC endpoints of {} is reachable",
+                        registry.chain.chain_id
+                    )
+                })?;
+
+                let config = entry.to_chain_config(&status)?;
+
+                chain_service
+                    .add(&config, &signer.to_public_key()?.encode())
+                    .await?;
+
+                let mut table = KeyValueTable::default();
+
+                table.add("Chain ID", &chain_id);
+                table.add("Bech32 prefix", &registry.chain.bech32_prefix);
+                table.add("RPC address", &config.rpc_addr);
+                table.add("gRPC address", &config.grpc_addr);
+                table.add("Fee amount", config.fee.amount);
+                table.add(
+                    "Fee denom",
+                    match registry
+                        .asset(fee_denom)
+                        .and_then(|asset| asset.symbol.as_ref())
+                    {
+                        Some(symbol) => format!("{} ({})", fee_denom, symbol),
+                        None => fee_denom.to_string(),
+                    },
+                );
+                table.add("Trusted height", config.trusted_height);
+
+                table.print(output, color_choice)
+            }
+            Self::SetLabel {
+                chain_id,
+                label,
+                connection_id,
+                channel_id,
+            } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+
+                let (kind, target) = match (connection_id, channel_id) {
+                    (Some(connection_id), _) => (LabelKind::Connection, Some(connection_id)),
+                    (_, Some(channel_id)) => (LabelKind::Channel, Some(channel_id)),
+                    (None, None) => (LabelKind::Chain, None),
+                };
+
+                chain_service
+                    .set_label(&chain_id, kind, target.as_deref(), &label)
+                    .await
+            }
+            Self::RemoveLabel {
+                chain_id,
+                label,
+                kind,
+            } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+                chain_service.remove_label(&chain_id, kind, &label).await
+            }
+            Self::LabelHistory { limit, offset } => {
+                let labels = chain_service.get_labels(limit, offset).await?;
+
+                let rows = labels.into_iter().map(label_into_row).collect::<Vec<_>>();
+
+                ListTable::new(
+                    vec![
+                        "ID",
+                        "Chain ID",
+                        "Kind",
+                        "Target",
+                        "Label",
+                        "Assigned at",
+                        "Removed at",
+                    ],
+                    rows,
+                )
+                .print(output, color_choice)
+            }
+            Self::ResetState {
+                chain_id,
+                trusted_height,
+                trusted_hash,
+                force,
+                yes,
+            } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+                let chain = chain_service
+                    .get(&chain_id)
+                    .await?
+                    .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+                let mut stdout = output.stream(color_choice);
+
+                match chain_service.detect_reset(&chain_id).await? {
+                    Some(evidence) => print_stream(
+                        &mut stdout,
+                        ColorSpec::new().set_bold(true).set_fg(Some(Color::Yellow)),
+                        format!("Chain {} has been reset: {}", chain_id, evidence),
+                    )?,
+                    None => {
+                        ensure!(
+                            force,
+                            "no reset detected for chain {} (use `--force` to reset its state anyway)",
+                            chain_id
+                        );
+
+                        print_stream(
+                            &mut stdout,
+                            ColorSpec::new().set_bold(true),
+                            format!("No reset detected for chain {}", chain_id),
+                        )?;
+                    }
+                }
+
+                if yes {
+                    return chain_service
+                        .reset_state(&chain_id, trusted_height.zip(trusted_hash))
+                        .await;
+                }
+
+                let mut table = KeyValueTable::default();
+
+                table.add(
+                    "Connection details",
+                    chain.connection_details.as_ref().map_or_else(
+                        || "-".to_string(),
+                        |connection_details| {
+                            format!(
+                                "connection: {}, channel: {}",
+                                connection_details.solo_machine_connection_id,
+                                connection_details.solo_machine_channel_id
+                            )
+                        },
+                    ),
+                );
+                table.add(
+                    "Handshake progress",
+                    chain
+                        .handshake_progress
+                        .as_ref()
+                        .map_or_else(|| "-".to_string(), ToString::to_string),
+                );
+                table.add(
+                    "New trusted height",
+                    trusted_height
+                        .map_or_else(|| "latest block".to_string(), |height| height.to_string()),
+                );
+
+                table.print(output, color_choice)?;
+
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Stored client, connection and channel of chain {} (along with their labels) will be removed. Re-run with `--yes` to reset.",
+                        chain_id
+                    ),
+                )
+            }
+            Self::Sync => {
+                let path =
+                    config.ok_or_else(|| anyhow!("`config` is required for syncing chains"))?;
+                let config = Config::load(&path)?;
+
+                ensure!(
+                    !config.chains.is_empty(),
+                    "no chains are defined in {}",
+                    path.display()
+                );
+
+                let public_key = signer.to_public_key()?.encode();
+                let mut stdout = output.stream(color_choice);
+
+                for entry in config.chains.iter() {
+                    let status = entry.status().await?;
+                    let chain_id: ChainId = status.node_info.network.to_string().parse()?;
+
+                    if chain_service.get(&chain_id).await?.is_some() {
+                        print_stream(
+                            &mut stdout,
+                            ColorSpec::new().set_bold(true),
+                            format!("Chain {} is already added, skipping", chain_id),
+                        )?;
+                        continue;
+                    }
+
+                    chain_service
+                        .add(&entry.to_chain_config(&status)?, &public_key)
+                        .await?;
+                }
+
+                Ok(())
+            }
+            Self::Watch { chain_id, blocks } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+                let chain = chain_service
+                    .get(&chain_id)
+                    .await?
+                    .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+                let subscriber =
+                    EventSubscriber::new_with_notifier(chain_id, &chain.config.rpc_addr, sender);
+
+                let mut stdout = output.stream(color_choice);
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Watching IBC events of chain {} at {} (press Ctrl-C to stop)",
+                        chain.id,
+                        subscriber.websocket_addr()
+                    ),
+                )?;
+
+                let printer = IbcEventPrinter {
+                    blocks,
+                    color_choice,
+                };
+
+                tokio::select! {
+                    result = subscriber.run(&printer, CancellationToken::new()) => result,
+                    result = signal::ctrl_c() => result.context("unable to listen for shutdown signal"),
+                }
+            }
+        }
+    }
+}
+
+/// Prints IBC events observed on a chain
+struct IbcEventPrinter {
+    blocks: bool,
+    color_choice: ColorChoice,
+}
+
+#[async_trait]
+impl IbcEventHandler for IbcEventPrinter {
+    async fn handle(&self, _: &ChainId, event: IbcEvent) -> Result<()> {
+        let mut color_spec = ColorSpec::new();
+
+        match event {
+            IbcEvent::NewBlock { .. } if !self.blocks => return Ok(()),
+            IbcEvent::NewBlock { .. } => {}
+            IbcEvent::Channel { .. } => {
+                color_spec.set_fg(Some(Color::Cyan));
+            }
+            _ => {
+                color_spec.set_bold(true);
             }
         }
+
+        print_stream(
+            &mut StandardStream::stdout(self.color_choice),
+            &color_spec,
+            event,
+        )
     }
 }
 
@@ -374,12 +1621,27 @@ fn parse_trusted_hash(hash: &str) -> Result<[u8; 32]> {
     Ok(trusted_hash)
 }
 
-fn into_row(key: ChainKey) -> RowStruct {
+fn label_into_row(label: ChainLabel) -> Vec<Field> {
+    vec![
+        label.id.field().justify(Justify::Right),
+        label.chain_id.field(),
+        label.kind.field(),
+        label.target.field(),
+        label.label.field(),
+        label.created_at.field(),
+        label
+            .removed_at
+            .map(|removed_at| removed_at.to_string())
+            .unwrap_or_else(|| "-".to_string())
+            .field(),
+    ]
+}
+
+fn into_row(key: ChainKey) -> Vec<Field> {
     vec![
-        key.id.cell().justify(Justify::Right),
-        key.chain_id.cell(),
-        key.public_key.cell(),
-        key.created_at.cell(),
+        key.id.field().justify(Justify::Right),
+        key.chain_id.field(),
+        key.public_key.field(),
+        key.created_at.field(),
     ]
-    .row()
 }
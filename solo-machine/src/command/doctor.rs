@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use cli_table::ColorChoice;
+use solo_machine_core::{
+    service::{ChainService, DoctorService, Finding, Severity},
+    DbPool, Signer,
+};
+use structopt::StructOpt;
+use termcolor::{Color, ColorSpec};
+
+use crate::{
+    command::print_stream,
+    output::{Field, ListTable, OutputFormat, ToField},
+};
+
+#[derive(Debug, StructOpt)]
+pub struct DoctorCommand {
+    /// Chain IDs (or labels) of IBC enabled chains to diagnose (all the chains when not provided)
+    chain_ids: Vec<String>,
+    /// Prints findings as JSON
+    #[structopt(long)]
+    json: bool,
+    /// Only prints findings with issues (warnings and errors)
+    #[structopt(long)]
+    issues_only: bool,
+}
+
+impl DoctorCommand {
+    /// Diagnoses chains and prints findings along with suggested actions. Fails if any check reports an error.
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        signer: Arc<dyn Signer>,
+        color_choice: ColorChoice,
+        output: OutputFormat,
+    ) -> Result<()> {
+        let chain_service = ChainService::new(db_pool.clone());
+
+        let mut chain_ids = Vec::with_capacity(self.chain_ids.len());
+
+        for chain_id in self.chain_ids.iter() {
+            chain_ids.push(chain_service.resolve_chain_id(chain_id).await?);
+        }
+
+        let mut findings = DoctorService::new(db_pool)
+            .diagnose(signer, &chain_ids)
+            .await?;
+
+        if self.issues_only {
+            findings.retain(|finding| finding.severity != Severity::Ok);
+        }
+
+        let errors = findings
+            .iter()
+            .filter(|finding| finding.severity == Severity::Error)
+            .count();
+        let warnings = findings
+            .iter()
+            .filter(|finding| finding.severity == Severity::Warning)
+            .count();
+
+        if self.json {
+            OutputFormat::Json.print(&findings)?;
+        } else if !output.is_text() {
+            output.print(&findings)?;
+        } else {
+            let rows = findings.iter().map(into_finding_row).collect::<Vec<_>>();
+
+            ListTable::new(
+                vec!["Chain ID", "Check", "Result", "Finding", "Action"],
+                rows,
+            )
+            .print(output, color_choice)?;
+
+            let (color, summary) = match (errors, warnings) {
+                (0, 0) => (Color::Green, "No issues found".to_string()),
+                (0, warnings) => (Color::Yellow, format!("Found {} warning(s)", warnings)),
+                (errors, warnings) => (
+                    Color::Red,
+                    format!("Found {} error(s) and {} warning(s)", errors, warnings),
+                ),
+            };
+
+            print_stream(
+                &mut output.stream(color_choice),
+                ColorSpec::new().set_bold(true).set_fg(Some(color)),
+                summary,
+            )?;
+        }
+
+        if errors > 0 {
+            bail!("{} check(s) failed", errors);
+        }
+
+        Ok(())
+    }
+}
+
+fn into_finding_row(finding: &Finding) -> Vec<Field> {
+    vec![
+        finding.chain_id.as_str().field(),
+        finding.check.field(),
+        finding.severity.to_string().field(),
+        finding.message.as_str().field(),
+        finding.action.as_deref().unwrap_or("-").field(),
+    ]
+}
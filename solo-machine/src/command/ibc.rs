@@ -1,26 +1,52 @@
-use anyhow::{Context, Result};
-use cli_table::{
-    format::Justify, print_stdout, Cell, Color, ColorChoice, Row, RowStruct, Style, Table,
-};
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context, Result};
+use cli_table::{format::Justify, Color, ColorChoice};
 use k256::ecdsa::VerifyingKey;
 use solo_machine_core::{
     cosmos::crypto::{PublicKey, PublicKeyAlgo},
-    ibc::core::ics24_host::identifier::{ChainId, Identifier},
-    model::{Operation, OperationType},
-    service::IbcService,
+    ibc::core::ics24_host::identifier::{ChannelId, Identifier},
+    model::{
+        Operation, OperationType, PacketState, PendingOperation, PendingOperationStatus,
+        TrackedPacket,
+    },
+    plugins::Plugins,
+    service::{ChainService, IbcService},
+    verifier::{ClaimedState, ProofDataType, ProofVerifier},
     DbPool, Event, Signer,
 };
 use structopt::StructOpt;
 use tokio::sync::mpsc::UnboundedSender;
 
-const PUBLIC_KEY_ALGO_VARIANTS: [&str; 2] = ["secp256k1", "eth-secp256k1"];
+use crate::{
+    command::{print_dry_run, print_tx_export, TxExport, TX_EXPORT_VARIANTS},
+    output::{Field, KeyValueTable, ListTable, OutputFormat, ToField},
+};
+
+const PACKET_STATE_VARIANTS: [&str; 4] = ["committed", "received", "acknowledged", "timed-out"];
+
+const PENDING_OPERATION_STATUS_VARIANTS: [&str; 4] = ["pending", "completed", "failed", "reverted"];
+
+pub(super) const PUBLIC_KEY_ALGO_VARIANTS: [&str; 2] = ["secp256k1", "eth-secp256k1"];
+
+pub(super) const PROOF_DATA_TYPE_VARIANTS: [&str; 9] = [
+    "client-state",
+    "consensus-state",
+    "connection-state",
+    "channel-state",
+    "packet-commitment",
+    "packet-acknowledgement",
+    "packet-receipt-absence",
+    "next-sequence-recv",
+    "header",
+];
 
 #[derive(Debug, StructOpt)]
 pub enum IbcCommand {
     /// Establishes connection with an IBC enabled chain
     Connect {
-        /// Chain ID of IBC enabled chain
-        chain_id: ChainId,
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
         /// Optional memo to include in transactions
         #[structopt(
             long,
@@ -29,19 +55,25 @@ pub enum IbcCommand {
             hide_env_values = true
         )]
         memo: String,
-        /// Force create a new connection even if one already exists
+        /// Force create a new connection even if one already exists (an interrupted handshake is resumed unless
+        /// this flag is set)
         #[structopt(long)]
         force: bool,
+        /// Prints the next transaction of handshake which would be built (messages, fee and sign bytes in hex) as JSON
+        /// without signing, persisting or broadcasting anything
+        #[structopt(long)]
+        dry_run: bool,
     },
     /// Mint some tokens on IBC enabled chain
     Mint {
-        /// Chain ID of IBC enabled chain
-        chain_id: ChainId,
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
         /// Amount to send to IBC enabled chain
         amount: u64,
         /// Denom of tokens to send to IBC enabled chain
         denom: Identifier,
-        /// Optional receiver address (if this is not provided, tokens will be sent to signer's address)
+        /// Optional receiver address or name resolvable by chain's name resolver (if this is not provided, tokens will
+        /// be sent to signer's address)
         receiver: Option<String>,
         /// Optional memo to include in transactions
         #[structopt(
@@ -54,11 +86,15 @@ pub enum IbcCommand {
         /// Optional request ID (for tracking purposes)
         #[structopt(long)]
         request_id: Option<String>,
+        /// Prints the transaction which would be built (messages, fee and sign bytes in hex) as JSON without signing,
+        /// persisting or broadcasting anything
+        #[structopt(long)]
+        dry_run: bool,
     },
     /// Burn some tokens on IBC enabled chain
     Burn {
-        /// Chain ID of IBC enabled chain
-        chain_id: ChainId,
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
         /// Amount to receive from IBC enabled chain
         amount: u64,
         /// Denom of tokens to receive from IBC enabled chain
@@ -74,11 +110,19 @@ pub enum IbcCommand {
         /// Optional request ID (for tracking purposes)
         #[structopt(long)]
         request_id: Option<String>,
+        /// Prints the transaction which would be built (messages, fee and sign bytes in hex) as JSON without signing,
+        /// persisting or broadcasting anything
+        #[structopt(long)]
+        dry_run: bool,
+        /// Prints the transaction (`unsigned` or `signed`) in JSON layout of cosmos SDK CLIs (can be signed and
+        /// broadcasted using `tx sign` and `tx broadcast` of chain's CLI) instead of broadcasting it
+        #[structopt(long, possible_values = &TX_EXPORT_VARIANTS, conflicts_with = "dry-run")]
+        export: Option<TxExport>,
     },
     /// Updates signer's public key on IBC enabled chain for future messages from solo machine
     UpdateSigner {
-        /// Chain ID of IBC enabled chain
-        chain_id: ChainId,
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
         /// Hex encoded public key
         #[structopt(long, env = "SOLO_NEW_PUBLIC_KEY", hide_env_values = true)]
         new_public_key: String,
@@ -93,6 +137,50 @@ pub enum IbcCommand {
             hide_env_values = true
         )]
         memo: String,
+        /// Prints the transaction which would be built (messages, fee and sign bytes in hex) as JSON without signing,
+        /// persisting or broadcasting anything
+        #[structopt(long)]
+        dry_run: bool,
+    },
+    /// Previews the sign bytes which will be signed next for an operation (without invoking signer)
+    Preview {
+        /// Prints preview as JSON
+        #[structopt(long)]
+        json: bool,
+        #[structopt(subcommand)]
+        operation: PreviewOperation,
+    },
+    /// Verifies a solo machine proof (`TimestampedSignatureData`) over claimed data against signer's public key (or
+    /// public key of another solo machine) and diversifier of given chain
+    VerifyProof {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Hex encoded proof (`TimestampedSignatureData`)
+        proof: String,
+        /// Sequence at which proof was signed
+        #[structopt(long)]
+        sequence: u64,
+        /// Type of claimed data
+        #[structopt(long, possible_values = &PROOF_DATA_TYPE_VARIANTS)]
+        data_type: ProofDataType,
+        /// Hex encoded claimed data (e.g. `PacketCommitmentData`, or the value stored at `--path` for chains using
+        /// `solomachine.v3` client types)
+        #[structopt(long)]
+        data: String,
+        /// Prefixed commitment path of claimed data (e.g. `/ibc/connections%2Fconnection-0`, required for chains using
+        /// `solomachine.v3` client types)
+        #[structopt(long)]
+        path: Option<String>,
+        /// Hex encoded public key of another solo machine (signer's public key is used when not provided, in which
+        /// case the sequence must already be signed by solo machine)
+        #[structopt(long)]
+        public_key: Option<String>,
+        /// Type of public key
+        #[structopt(long, possible_values = &PUBLIC_KEY_ALGO_VARIANTS, default_value = "secp256k1")]
+        public_key_algo: PublicKeyAlgo,
+        /// Diversifier of another solo machine (diversifier of chain is used when not provided)
+        #[structopt(long, requires = "public-key")]
+        diversifier: Option<String>,
     },
     /// Check history of operations on solo machine
     History {
@@ -101,6 +189,77 @@ pub enum IbcCommand {
         #[structopt(long, default_value)]
         offset: u32,
     },
+    /// Lists packets sent to solo machine by a chain along with their lifecycle state (`committed` → `received` →
+    /// `acknowledged` / `timed-out`)
+    Packets {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Only lists packets sent on given channel (connected channel is used for `--sequence` when not provided)
+        #[structopt(long)]
+        channel_id: Option<ChannelId>,
+        /// Only lists packets in given state
+        #[structopt(long, possible_values = &PACKET_STATE_VARIANTS)]
+        state: Option<PacketState>,
+        /// Only prints packet with given sequence
+        #[structopt(long, conflicts_with = "state")]
+        sequence: Option<u64>,
+        #[structopt(long, default_value = "10")]
+        limit: u32,
+        #[structopt(long, default_value)]
+        offset: u32,
+    },
+    /// Lists transactions queued for submission to chains along with their status
+    PendingOperations {
+        /// Only lists operations of given chain (chain ID or label)
+        chain_id: Option<String>,
+        /// Only lists operations in given status
+        #[structopt(long, possible_values = &PENDING_OPERATION_STATUS_VARIANTS)]
+        status: Option<PendingOperationStatus>,
+        #[structopt(long, default_value = "10")]
+        limit: u32,
+        #[structopt(long, default_value)]
+        offset: u32,
+    },
+    /// Resolves operations of a chain left pending by an interrupted submission (this is also done automatically
+    /// before next operation on the chain)
+    Resume {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+    },
+    /// Returns sequences consumed by failed operations of a chain which were never accepted on chain (only the failed
+    /// operations queued after the latest operation which is not failed are rolled back; this is also done
+    /// automatically when an operation fails)
+    Rollback {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub enum PreviewOperation {
+    /// Previews sign bytes for minting some tokens on IBC enabled chain
+    Mint {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Amount to send to IBC enabled chain
+        amount: u64,
+        /// Denom of tokens to send to IBC enabled chain
+        denom: Identifier,
+        /// Optional receiver address or name resolvable by chain's name resolver (if this is not provided, tokens will
+        /// be sent to signer's address)
+        receiver: Option<String>,
+    },
+    /// Previews sign bytes for updating signer's public key on IBC enabled chain
+    UpdateSigner {
+        /// Chain ID (or label) of IBC enabled chain
+        chain_id: String,
+        /// Hex encoded public key
+        #[structopt(long, env = "SOLO_NEW_PUBLIC_KEY", hide_env_values = true)]
+        new_public_key: String,
+        /// Type of public key
+        #[structopt(long, possible_values = &PUBLIC_KEY_ALGO_VARIANTS, default_value = "secp256k1", env = "SOLO_PUBLIC_KEY_ALGO", hide_env_values = true)]
+        public_key_algo: PublicKeyAlgo,
+    },
 }
 
 impl IbcCommand {
@@ -108,17 +267,32 @@ impl IbcCommand {
         self,
         db_pool: DbPool,
         signer: impl Signer,
+        plugins: Plugins,
         sender: UnboundedSender<Event>,
         color_choice: ColorChoice,
+        output: OutputFormat,
     ) -> Result<()> {
-        let ibc_service = IbcService::new_with_notifier(db_pool, sender);
+        let chain_service = ChainService::new(db_pool.clone());
+        let ibc_service = IbcService::new_with_notifier(db_pool, sender).with_plugins(plugins);
 
         match self {
             Self::Connect {
                 chain_id,
                 memo,
                 force,
-            } => ibc_service.connect(signer, chain_id, memo, force).await,
+                dry_run,
+            } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+
+                if dry_run {
+                    let report = ibc_service
+                        .dry_run_connect(signer, chain_id, memo, force)
+                        .await?;
+                    return print_dry_run(&report, output);
+                }
+
+                ibc_service.connect(signer, chain_id, memo, force).await
+            }
             Self::Mint {
                 chain_id,
                 amount,
@@ -126,88 +300,394 @@ impl IbcCommand {
                 receiver,
                 memo,
                 request_id,
-            } => ibc_service
-                .mint(signer, chain_id, request_id, amount, denom, receiver, memo)
-                .await
-                .map(|_| ()),
+                dry_run,
+            } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+
+                if dry_run {
+                    let report = ibc_service
+                        .dry_run_mint(signer, chain_id, amount, denom, receiver, memo)
+                        .await?;
+                    return print_dry_run(&report, output);
+                }
+
+                ibc_service
+                    .mint(signer, chain_id, request_id, amount, denom, receiver, memo)
+                    .await
+                    .map(|_| ())
+            }
             Self::Burn {
                 chain_id,
                 amount,
                 denom,
                 memo,
                 request_id,
-            } => ibc_service
-                .burn(signer, chain_id, request_id, amount, denom, memo)
-                .await
-                .map(|_| ()),
+                dry_run,
+                export,
+            } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+
+                if dry_run {
+                    let report = ibc_service
+                        .dry_run_burn(signer, chain_id, amount, denom, memo)
+                        .await?;
+                    return print_dry_run(&report, output);
+                }
+
+                if let Some(export) = export {
+                    let tx = ibc_service
+                        .export_burn(
+                            signer,
+                            chain_id,
+                            amount,
+                            denom,
+                            memo,
+                            request_id,
+                            export.is_signed(),
+                        )
+                        .await?;
+                    return print_tx_export(&tx);
+                }
+
+                ibc_service
+                    .burn(signer, chain_id, request_id, amount, denom, memo)
+                    .await
+                    .map(|_| ())
+            }
             Self::UpdateSigner {
                 chain_id,
                 new_public_key,
                 public_key_algo,
                 memo,
+                dry_run,
             } => {
-                let new_public_key_bytes =
-                    hex::decode(&new_public_key).context("unable to decode hex bytes")?;
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+                let new_public_key = parse_public_key(&new_public_key, public_key_algo)?;
 
-                let new_verifying_key = VerifyingKey::from_sec1_bytes(&new_public_key_bytes)
-                    .context("invalid secp256k1 bytes")?;
-
-                let new_public_key = match public_key_algo {
-                    PublicKeyAlgo::Secp256k1 => PublicKey::Secp256k1(new_verifying_key),
-                    #[cfg(feature = "ethermint")]
-                    PublicKeyAlgo::EthSecp256k1 => PublicKey::EthSecp256k1(new_verifying_key),
-                };
+                if dry_run {
+                    let report = ibc_service
+                        .dry_run_update_signer(signer, chain_id, new_public_key, memo)
+                        .await?;
+                    return print_dry_run(&report, output);
+                }
 
                 ibc_service
                     .update_signer(signer, chain_id, new_public_key, memo)
                     .await
             }
+            Self::Preview { json, operation } => {
+                let preview = match operation {
+                    PreviewOperation::Mint {
+                        chain_id,
+                        amount,
+                        denom,
+                        receiver,
+                    } => {
+                        let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+                        ibc_service
+                            .preview_mint(signer, chain_id, amount, denom, receiver)
+                            .await?
+                    }
+                    PreviewOperation::UpdateSigner {
+                        chain_id,
+                        new_public_key,
+                        public_key_algo,
+                    } => {
+                        let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+                        let new_public_key = parse_public_key(&new_public_key, public_key_algo)?;
+
+                        ibc_service
+                            .preview_update_signer(signer, chain_id, new_public_key)
+                            .await?
+                    }
+                };
+
+                if json {
+                    return OutputFormat::Json.print(&preview);
+                }
+
+                if !output.is_text() {
+                    return output.print(&preview);
+                }
+
+                let mut table = KeyValueTable::default();
+
+                table.add("Sequence", preview.sequence);
+                table.add("Timestamp", preview.timestamp);
+                table.add("Diversifier", &preview.diversifier);
+                table.add("Data type", &preview.data_type);
+                table.add("Data", serde_json::to_string_pretty(&preview.data)?);
+                table.add("Sign bytes", hex::encode_upper(&preview.sign_bytes));
+
+                table.print(output, color_choice)
+            }
+            Self::VerifyProof {
+                chain_id,
+                proof,
+                sequence,
+                data_type,
+                data,
+                path,
+                public_key,
+                public_key_algo,
+                diversifier,
+            } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+                let proof = hex::decode(&proof).context("unable to decode hex bytes of proof")?;
+                let claimed_state = ClaimedState::Raw {
+                    data_type,
+                    path,
+                    data: hex::decode(&data).context("unable to decode hex bytes of data")?,
+                };
+
+                let verified_proof = match public_key {
+                    None => {
+                        ibc_service
+                            .verify_proof(signer, &chain_id, sequence, &proof, &claimed_state)
+                            .await?
+                    }
+                    Some(public_key) => {
+                        let public_key = parse_public_key(&public_key, public_key_algo)?;
+                        let chain = chain_service
+                            .get(&chain_id)
+                            .await?
+                            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+                        let revision = chain.config.solo_machine_revision();
+                        let diversifier = diversifier.unwrap_or(chain.config.diversifier);
+
+                        ProofVerifier::new(public_key, diversifier)
+                            .with_revision(revision)
+                            .verify(sequence, &proof, &claimed_state)?
+                    }
+                };
+
+                let mut table = KeyValueTable::default();
+
+                table.add("Sequence", verified_proof.sequence);
+                table.add("Timestamp", verified_proof.timestamp);
+                table.add("Diversifier", &verified_proof.diversifier);
+                table.add("Data type", verified_proof.data_type);
+                table.add_field(
+                    "Status",
+                    "Valid".field().foreground_color(Some(Color::Green)),
+                );
+
+                table.print(output, color_choice)
+            }
             Self::History { limit, offset } => {
                 let history = ibc_service.history(signer, limit, offset).await?;
 
-                let table = history
+                let mut rows = Vec::with_capacity(history.len());
+                let mut address_displayers = HashMap::new();
+
+                for mut operation in history {
+                    let chain_id = operation.operation_type.chain_id();
+
+                    if !address_displayers.contains_key(chain_id) {
+                        let address_displayer =
+                            chain_service.address_displayer(chain_id).await.ok();
+                        address_displayers.insert(chain_id.clone(), address_displayer);
+                    }
+
+                    if let Some(address_displayer) = &address_displayers[chain_id] {
+                        operation.address = address_displayer.display(&operation.address).await;
+                    }
+
+                    rows.push(into_row(operation));
+                }
+
+                ListTable::new(
+                    vec![
+                        "ID",
+                        "Request ID",
+                        "Address",
+                        "Denom",
+                        "Amount",
+                        "Type",
+                        "Transaction Hash",
+                        "Time",
+                    ],
+                    rows,
+                )
+                .print(output, color_choice)
+            }
+            Self::Packets {
+                chain_id,
+                channel_id,
+                state,
+                sequence,
+                limit,
+                offset,
+            } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+
+                let packets = match sequence {
+                    Some(sequence) => ibc_service
+                        .packet(&chain_id, channel_id.as_ref(), sequence)
+                        .await?
+                        .into_iter()
+                        .collect(),
+                    None => {
+                        ibc_service
+                            .packets(&chain_id, channel_id.as_ref(), state, limit, offset)
+                            .await?
+                    }
+                };
+
+                let rows = packets.into_iter().map(packet_into_row).collect::<Vec<_>>();
+
+                ListTable::new(
+                    vec![
+                        "Channel",
+                        "Sequence",
+                        "State",
+                        "Height",
+                        "Transaction Hash",
+                        "Updated at",
+                    ],
+                    rows,
+                )
+                .print(output, color_choice)
+            }
+            Self::PendingOperations {
+                chain_id,
+                status,
+                limit,
+                offset,
+            } => {
+                let chain_id = match chain_id {
+                    Some(chain_id) => Some(chain_service.resolve_chain_id(&chain_id).await?),
+                    None => None,
+                };
+
+                let rows = ibc_service
+                    .pending_operations(chain_id.as_ref(), status, limit, offset)
+                    .await?
                     .into_iter()
-                    .map(into_row)
-                    .collect::<Vec<RowStruct>>()
-                    .table()
-                    .title(vec![
-                        "ID".cell().bold(true),
-                        "Request ID".cell().bold(true),
-                        "Address".cell().bold(true),
-                        "Denom".cell().bold(true),
-                        "Amount".cell().bold(true),
-                        "Type".cell().bold(true),
-                        "Transaction Hash".cell().bold(true),
-                        "Time".cell().bold(true),
-                    ])
-                    .color_choice(color_choice);
-
-                print_stdout(table).context("unable to print table to stdout")
+                    .map(pending_operation_into_row)
+                    .collect::<Vec<_>>();
+
+                ListTable::new(
+                    vec![
+                        "ID",
+                        "Chain ID",
+                        "Kind",
+                        "Request ID",
+                        "Status",
+                        "Transaction Hash",
+                        "Error",
+                        "Updated at",
+                    ],
+                    rows,
+                )
+                .print(output, color_choice)
+            }
+            Self::Resume { chain_id } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+
+                ibc_service
+                    .resume_pending_operations(signer, &chain_id)
+                    .await
+                    .map(|_| ())
+            }
+            Self::Rollback { chain_id } => {
+                let chain_id = chain_service.resolve_chain_id(&chain_id).await?;
+
+                match ibc_service.rollback_failed_operations(&chain_id).await? {
+                    Some(sequence) => {
+                        println!("Rolled back sequence of chain {} to {}", chain_id, sequence)
+                    }
+                    None => println!("Nothing to roll back for chain {}", chain_id),
+                }
+
+                Ok(())
             }
         }
     }
 }
 
-fn into_row(operation: Operation) -> RowStruct {
+pub(super) fn parse_public_key(
+    public_key: &str,
+    public_key_algo: PublicKeyAlgo,
+) -> Result<PublicKey> {
+    let public_key_bytes = hex::decode(public_key).context("unable to decode hex bytes")?;
+
+    let verifying_key =
+        VerifyingKey::from_sec1_bytes(&public_key_bytes).context("invalid secp256k1 bytes")?;
+
+    match public_key_algo {
+        PublicKeyAlgo::Secp256k1 => Ok(PublicKey::Secp256k1(verifying_key)),
+        #[cfg(feature = "ethermint")]
+        PublicKeyAlgo::EthSecp256k1 => Ok(PublicKey::EthSecp256k1(verifying_key)),
+    }
+}
+
+fn into_row(operation: Operation) -> Vec<Field> {
     let color = get_color_for_operation_type(&operation.operation_type);
 
     vec![
-        operation.id.cell().justify(Justify::Right),
+        operation.id.field().justify(Justify::Right),
         operation
             .request_id
             .unwrap_or_else(|| "-".to_string())
-            .cell(),
-        operation.address.cell(),
-        operation.denom.cell(),
-        operation.amount.cell().justify(Justify::Right),
+            .field(),
+        operation.address.field(),
+        operation.denom.field(),
+        operation.amount.field().justify(Justify::Right),
         operation
             .operation_type
-            .cell()
+            .field()
             .foreground_color(Some(color)),
-        operation.transaction_hash.cell(),
-        operation.created_at.cell(),
+        operation.transaction_hash.field(),
+        operation.created_at.field(),
+    ]
+}
+
+fn packet_into_row(packet: TrackedPacket) -> Vec<Field> {
+    let color = match packet.state {
+        PacketState::Committed => Color::Yellow,
+        PacketState::Received => Color::Cyan,
+        PacketState::Acknowledged => Color::Green,
+        PacketState::TimedOut => Color::Red,
+    };
+
+    vec![
+        packet.channel_id.field(),
+        packet.sequence.field().justify(Justify::Right),
+        packet.state.field().foreground_color(Some(color)),
+        packet
+            .height
+            .map_or_else(|| "-".to_string(), |height| height.to_string())
+            .field()
+            .justify(Justify::Right),
+        packet
+            .transaction_hash
+            .unwrap_or_else(|| "-".to_string())
+            .field(),
+        packet.updated_at.field(),
+    ]
+}
+
+fn pending_operation_into_row(operation: PendingOperation) -> Vec<Field> {
+    let color = match operation.status {
+        PendingOperationStatus::Pending => Color::Yellow,
+        PendingOperationStatus::Completed => Color::Green,
+        PendingOperationStatus::Failed | PendingOperationStatus::Reverted => Color::Red,
+    };
+
+    vec![
+        operation.id.field().justify(Justify::Right),
+        operation.chain_id.field(),
+        operation.kind.field(),
+        operation
+            .request_id
+            .unwrap_or_else(|| "-".to_string())
+            .field(),
+        operation.status.field().foreground_color(Some(color)),
+        operation.transaction_hash.field(),
+        operation.error.unwrap_or_else(|| "-".to_string()).field(),
+        operation.updated_at.field(),
     ]
-    .row()
 }
 
 fn get_color_for_operation_type(operation_type: &OperationType) -> Color {
@@ -0,0 +1,80 @@
+use anyhow::{anyhow, ensure, Context, Result};
+use bip32::{Language, Mnemonic};
+use solo_machine_core::keychain;
+use structopt::StructOpt;
+use termcolor::{ColorChoice, ColorSpec};
+use zeroize::Zeroizing;
+
+use crate::{command::print_stream, output::OutputFormat};
+
+#[derive(Debug, StructOpt)]
+pub enum KeychainCommand {
+    /// Stores a mnemonic (read from terminal without echoing it) in platform keychain
+    Store {
+        /// Name of keychain entry
+        name: String,
+    },
+    /// Stores a state passphrase (read from terminal without echoing it) in platform keychain (used with
+    /// `--state-passphrase-keychain`)
+    StorePassphrase {
+        /// Name of keychain entry
+        name: String,
+    },
+    /// Deletes a mnemonic (or state passphrase) from platform keychain
+    Delete {
+        /// Name of keychain entry
+        name: String,
+    },
+}
+
+impl KeychainCommand {
+    pub fn execute(self, color_choice: ColorChoice, output: OutputFormat) -> Result<()> {
+        let mut stdout = output.stream(color_choice);
+
+        match self {
+            Self::Store { name } => {
+                let phrase = Zeroizing::new(
+                    rpassword::read_password_from_tty(Some("Enter mnemonic: "))
+                        .context("unable to read mnemonic from terminal")?,
+                );
+                let mnemonic = Mnemonic::new(phrase.trim(), Language::English)
+                    .map_err(|_| anyhow!("invalid mnemonic"))?;
+
+                keychain::store_secret(&name, mnemonic.phrase())?;
+
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!("Mnemonic stored in keychain entry `{}`", name),
+                )
+            }
+            Self::StorePassphrase { name } => {
+                let passphrase = Zeroizing::new(
+                    rpassword::read_password_from_tty(Some("Enter state passphrase: "))
+                        .context("unable to read state passphrase from terminal")?,
+                );
+                ensure!(
+                    !passphrase.as_str().is_empty(),
+                    "state passphrase cannot be empty"
+                );
+
+                keychain::store_secret(&name, passphrase.as_str())?;
+
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!("State passphrase stored in keychain entry `{}`", name),
+                )
+            }
+            Self::Delete { name } => {
+                keychain::delete_secret(&name)?;
+
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!("Keychain entry `{}` deleted", name),
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::{env, io::Write, path::PathBuf};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use bip32::{Language, Mnemonic};
+use rand::rngs::OsRng;
+use solo_machine_core::keystore::Keystore;
+use structopt::StructOpt;
+use termcolor::{ColorChoice, ColorSpec};
+use zeroize::Zeroizing;
+
+use crate::{command::print_stream, output::OutputFormat};
+
+const PASSWORD_ENV: &str = "SOLO_KEYSTORE_PASSWORD";
+
+#[derive(Debug, StructOpt)]
+pub enum KeystoreCommand {
+    /// Generates a new mnemonic and stores it in an encrypted keystore file
+    Create {
+        /// Path of keystore file
+        path: PathBuf,
+    },
+    /// Encrypts an existing mnemonic (read from terminal without echoing it) and stores it in a keystore file
+    Import {
+        /// Path of keystore file
+        path: PathBuf,
+    },
+    /// Decrypts a keystore file and prints the stored mnemonic (for backup)
+    Export {
+        /// Path of keystore file
+        path: PathBuf,
+    },
+}
+
+impl KeystoreCommand {
+    pub fn execute(self, color_choice: ColorChoice, output: OutputFormat) -> Result<()> {
+        let mut stdout = output.stream(color_choice);
+
+        match self {
+            Self::Create { path } => {
+                let mnemonic = Mnemonic::random(OsRng, Language::English);
+                let password = read_new_password()?;
+
+                Keystore::encrypt(mnemonic.phrase().as_bytes(), &password)?.save(&path)?;
+
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Keystore created at {} (use `keystore export` to back up the mnemonic)",
+                        path.display()
+                    ),
+                )
+            }
+            Self::Import { path } => {
+                let phrase = Zeroizing::new(
+                    rpassword::read_password_from_tty(Some("Enter mnemonic: "))
+                        .context("unable to read mnemonic from terminal")?,
+                );
+                let mnemonic = Mnemonic::new(phrase.trim(), Language::English)
+                    .map_err(|_| anyhow!("invalid mnemonic"))?;
+                let password = read_new_password()?;
+
+                Keystore::encrypt(mnemonic.phrase().as_bytes(), &password)?.save(&path)?;
+
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!("Mnemonic imported to keystore at {}", path.display()),
+                )
+            }
+            Self::Export { path } => {
+                let keystore = Keystore::load(&path)?;
+                let password = read_password("Enter keystore password: ")?;
+
+                let secret = keystore.decrypt(&password)?;
+                let mnemonic =
+                    std::str::from_utf8(&secret).context("invalid mnemonic in keystore")?;
+
+                writeln!(stdout, "{}", mnemonic).context("unable to write to stdout")
+            }
+        }
+    }
+}
+
+/// Reads keystore password from `SOLO_KEYSTORE_PASSWORD` environment variable or terminal
+fn read_password(prompt: &str) -> Result<Zeroizing<String>> {
+    match env::var(PASSWORD_ENV) {
+        Ok(password) => Ok(Zeroizing::new(password)),
+        Err(_) => rpassword::read_password_from_tty(Some(prompt))
+            .map(Zeroizing::new)
+            .context("unable to read keystore password from terminal"),
+    }
+}
+
+/// Reads a new keystore password (with confirmation when read from terminal)
+fn read_new_password() -> Result<Zeroizing<String>> {
+    if let Ok(password) = env::var(PASSWORD_ENV) {
+        return Ok(Zeroizing::new(password));
+    }
+
+    let password = read_password("Enter new keystore password: ")?;
+    ensure!(!password.is_empty(), "keystore password cannot be empty");
+
+    let confirmation = read_password("Confirm keystore password: ")?;
+    ensure!(password == confirmation, "keystore passwords do not match");
+
+    Ok(password)
+}
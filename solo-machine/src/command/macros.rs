@@ -0,0 +1,216 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use cli_table::{format::Justify, ColorChoice};
+use solo_machine_core::{
+    model::MacroRun,
+    plugins::Plugins,
+    service::{MacroDefinition, MacroService},
+    DbPool, Event, Signer,
+};
+use structopt::StructOpt;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::output::{Field, KeyValueTable, ListTable, OutputFormat, ToField};
+
+#[derive(Debug, StructOpt)]
+pub enum MacroCommand {
+    /// Lists all the macros defined in macro file
+    List {
+        /// Path of JSON file containing macro definitions
+        #[structopt(
+            long,
+            default_value = "macros.json",
+            env = "SOLO_MACRO_FILE",
+            hide_env_values = true
+        )]
+        file: PathBuf,
+    },
+    /// Runs a macro defined in macro file
+    Run {
+        /// Name of macro
+        name: String,
+        /// Parameters of macro (e.g. `--param receiver=cosmos1...`)
+        #[structopt(long = "param", parse(try_from_str = parse_param))]
+        params: Vec<(String, String)>,
+        /// Path of JSON file containing macro definitions
+        #[structopt(
+            long,
+            default_value = "macros.json",
+            env = "SOLO_MACRO_FILE",
+            hide_env_values = true
+        )]
+        file: PathBuf,
+        /// Optional memo to include in transactions
+        #[structopt(
+            long,
+            default_value = "solo-machine-memo",
+            env = "SOLO_MEMO",
+            hide_env_values = true
+        )]
+        memo: String,
+    },
+    /// Resumes a failed macro run from its first incomplete step
+    Resume {
+        /// ID of macro run
+        run_id: i64,
+        /// Optional memo to include in transactions
+        #[structopt(
+            long,
+            default_value = "solo-machine-memo",
+            env = "SOLO_MEMO",
+            hide_env_values = true
+        )]
+        memo: String,
+    },
+    /// Fetches details and journal of a macro run
+    Get {
+        /// ID of macro run
+        run_id: i64,
+    },
+    /// Check history of macro runs
+    History {
+        #[structopt(long, default_value = "10")]
+        limit: u32,
+        #[structopt(long, default_value)]
+        offset: u32,
+    },
+}
+
+impl MacroCommand {
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        signer: impl Signer,
+        plugins: Plugins,
+        sender: UnboundedSender<Event>,
+        color_choice: ColorChoice,
+        output: OutputFormat,
+    ) -> Result<()> {
+        let macro_service = MacroService::new_with_notifier(db_pool, sender).with_plugins(plugins);
+
+        match self {
+            Self::List { file } => {
+                let macros = load_macros(&file)?;
+
+                let rows = macros
+                    .into_iter()
+                    .map(|(name, definition)| {
+                        vec![
+                            name.field(),
+                            definition.params.join(", ").field(),
+                            definition.steps.len().field().justify(Justify::Right),
+                            definition.description.unwrap_or_default().field(),
+                        ]
+                    })
+                    .collect::<Vec<_>>();
+
+                ListTable::new(vec!["Name", "Parameters", "Steps", "Description"], rows)
+                    .print(output, color_choice)
+            }
+            Self::Run {
+                name,
+                params,
+                file,
+                memo,
+            } => {
+                let mut macros = load_macros(&file)?;
+                let definition = macros
+                    .remove(&name)
+                    .ok_or_else(|| anyhow!("macro `{}` not found in {}", name, file.display()))?;
+
+                let params = params.into_iter().collect::<HashMap<_, _>>();
+
+                macro_service
+                    .run(signer, &name, &definition, &params, memo)
+                    .await
+                    .map(|_| ())
+            }
+            Self::Resume { run_id, memo } => {
+                macro_service.resume(signer, run_id, memo).await.map(|_| ())
+            }
+            Self::Get { run_id } => {
+                let run = macro_service
+                    .get_run(run_id)
+                    .await?
+                    .ok_or_else(|| anyhow!("macro run with id {} not found", run_id))?;
+
+                let mut table = KeyValueTable::default();
+
+                table.add("ID", run.id);
+                table.add("Name", &run.name);
+                table.add("Status", run.status);
+                table.add("Error", run.error.as_deref().unwrap_or("-"));
+
+                for (index, step) in run.steps.iter().enumerate() {
+                    let result = run
+                        .journal
+                        .iter()
+                        .find(|result| result.step == index)
+                        .map(|result| format!("done [{}] ({})", result.output, result.completed_at))
+                        .unwrap_or_else(|| "pending".to_string());
+
+                    table.add(
+                        &format!("Step {}", index),
+                        format!("{} => {}", serde_json::to_string(step)?, result),
+                    );
+                }
+
+                table.add("Created at", run.created_at);
+                table.add("Updated at", run.updated_at);
+
+                table.print(output, color_choice)
+            }
+            Self::History { limit, offset } => {
+                let runs = macro_service.get_runs(limit, offset).await?;
+
+                let rows = runs.into_iter().map(into_row).collect::<Vec<_>>();
+
+                ListTable::new(
+                    vec![
+                        "ID",
+                        "Name",
+                        "Status",
+                        "Completed steps",
+                        "Created at",
+                        "Updated at",
+                    ],
+                    rows,
+                )
+                .print(output, color_choice)
+            }
+        }
+    }
+}
+
+fn load_macros(file: &PathBuf) -> Result<HashMap<String, MacroDefinition>> {
+    let bytes = fs::read(file).context(format!("unable to read macro file {}", file.display()))?;
+    serde_json::from_slice(&bytes).context(format!("invalid macro file {}", file.display()))
+}
+
+fn parse_param(param: &str) -> Result<(String, String)> {
+    let mut split = param.splitn(2, '=');
+
+    let name = split
+        .next()
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| anyhow!("invalid macro parameter: {}", param))?;
+    let value = split
+        .next()
+        .ok_or_else(|| anyhow!("macro parameter should be of form `name=value`: {}", param))?;
+
+    Ok((name.to_string(), value.to_string()))
+}
+
+fn into_row(run: MacroRun) -> Vec<Field> {
+    vec![
+        run.id.field().justify(Justify::Right),
+        run.name.field(),
+        run.status.field(),
+        format!("{}/{}", run.journal.len(), run.steps.len())
+            .field()
+            .justify(Justify::Right),
+        run.created_at.field(),
+        run.updated_at.field(),
+    ]
+}
@@ -0,0 +1,268 @@
+use std::{
+    fs, iter,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use cli_table::{format::Justify, Color, ColorChoice};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use solo_machine_core::{
+    model::{MacroOperation, MacroRun, MacroRunStatus, MacroStep},
+    plugins::Plugins,
+    service::MacroService,
+    DbPool, Event, Signer,
+};
+use structopt::StructOpt;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    config::ChainEntry,
+    output::{Field, ListTable, OutputFormat, ToField},
+};
+
+#[derive(Debug, StructOpt)]
+pub enum PipelineCommand {
+    /// Runs a pipeline (sequence of operations, e.g. adding a chain, creating clients, connection handshake and
+    /// transfers) defined in a YAML (or JSON) file as a single journaled run
+    Run {
+        /// Path of YAML (or JSON) file defining pipeline
+        file: PathBuf,
+        /// Optional memo to include in transactions
+        #[structopt(
+            long,
+            default_value = "solo-machine-memo",
+            env = "SOLO_MEMO",
+            hide_env_values = true
+        )]
+        memo: String,
+    },
+    /// Resumes a failed (or interrupted) pipeline run from its first incomplete step
+    Resume {
+        /// ID of pipeline run
+        run_id: i64,
+        /// Optional memo to include in transactions
+        #[structopt(
+            long,
+            default_value = "solo-machine-memo",
+            env = "SOLO_MEMO",
+            hide_env_values = true
+        )]
+        memo: String,
+    },
+    /// Shows status of each step of a pipeline run
+    Status {
+        /// ID of pipeline run
+        run_id: i64,
+    },
+}
+
+/// Pipeline of operations defined in a YAML (or JSON) file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Pipeline {
+    /// Name of pipeline (used as name of its runs)
+    name: String,
+    /// Description of pipeline
+    #[serde(default)]
+    #[allow(dead_code)]
+    description: Option<String>,
+    /// Number of times each step is retried when it fails (can be overridden by steps)
+    #[serde(default)]
+    retries: u32,
+    /// Delay between retries of each step (e.g. `10 sec`, can be overridden by steps)
+    #[serde(default)]
+    retry_delay: Option<String>,
+    /// Steps of pipeline
+    steps: Vec<PipelineStep>,
+}
+
+/// A step of pipeline: an operation of macros (`add-chain` takes definition of chain in the same format as
+/// `[[chains]]` of configuration file) along with its retry settings
+#[derive(Debug, Deserialize)]
+struct PipelineStep {
+    /// Number of times step is executed (e.g. for multiple transfers)
+    #[serde(default = "default_repeat")]
+    repeat: u32,
+    /// Number of times step is retried when it fails
+    #[serde(default)]
+    retries: Option<u32>,
+    /// Delay between retries of step
+    #[serde(default)]
+    retry_delay: Option<String>,
+    /// Operation of step (`operation` tag along with fields of operation)
+    #[serde(flatten)]
+    operation: Map<String, Value>,
+}
+
+impl PipelineCommand {
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        signer: impl Signer,
+        plugins: Plugins,
+        sender: UnboundedSender<Event>,
+        color_choice: ColorChoice,
+        output: OutputFormat,
+    ) -> Result<()> {
+        let macro_service = MacroService::new_with_notifier(db_pool, sender).with_plugins(plugins);
+
+        match self {
+            Self::Run { file, memo } => {
+                let pipeline = load_pipeline(&file)?;
+                let steps = pipeline.steps(file.parent()).await?;
+
+                let run = macro_service
+                    .run_steps(signer, &pipeline.name, &steps, memo)
+                    .await?;
+
+                print_run(&run, output, color_choice)
+            }
+            Self::Resume { run_id, memo } => {
+                let run = macro_service.resume(signer, run_id, memo).await?;
+                print_run(&run, output, color_choice)
+            }
+            Self::Status { run_id } => {
+                let run = macro_service
+                    .get_run(run_id)
+                    .await?
+                    .ok_or_else(|| anyhow!("pipeline run with id {} not found", run_id))?;
+
+                print_run(&run, output, color_choice)
+            }
+        }
+    }
+}
+
+impl Pipeline {
+    /// Returns steps of pipeline to be executed as a macro run (resolving configuration of chains added by
+    /// `add-chain` steps and repeating steps)
+    async fn steps(&self, base: Option<&Path>) -> Result<Vec<MacroStep>> {
+        let mut steps = Vec::new();
+
+        for (index, step) in self.steps.iter().enumerate() {
+            ensure!(
+                step.repeat > 0,
+                "`repeat` of step {} of pipeline `{}` should be greater than zero",
+                index,
+                self.name
+            );
+
+            let operation = step
+                .operation(base)
+                .await
+                .with_context(|| format!("invalid step {} of pipeline `{}`", index, self.name))?;
+
+            let macro_step = MacroStep {
+                operation,
+                retries: step.retries.unwrap_or(self.retries),
+                retry_delay: step
+                    .retry_delay
+                    .clone()
+                    .or_else(|| self.retry_delay.clone()),
+            };
+
+            steps.extend(iter::repeat_n(macro_step, step.repeat as usize));
+        }
+
+        Ok(steps)
+    }
+}
+
+impl PipelineStep {
+    /// Returns operation of step. Configuration of chain added by `add-chain` step is resolved using its current
+    /// status (latest block is trusted when trusted height is not provided).
+    async fn operation(&self, base: Option<&Path>) -> Result<MacroOperation> {
+        if self.operation.get("operation").and_then(Value::as_str) != Some("add-chain") {
+            return serde_json::from_value(Value::Object(self.operation.clone()))
+                .context("invalid operation");
+        }
+
+        let mut fields = self.operation.clone();
+        fields.remove("operation");
+
+        let mut entry: ChainEntry =
+            serde_json::from_value(Value::Object(fields)).context("invalid chain definition")?;
+
+        if let (Some(base), Some(ref mut ca_cert)) = (base, &mut entry.grpc_ca_cert) {
+            if ca_cert.is_relative() {
+                *ca_cert = base.join(&*ca_cert);
+            }
+        }
+
+        let status = entry.status().await?;
+
+        Ok(MacroOperation::AddChain {
+            chain_id: status.node_info.network.to_string(),
+            config: Box::new(entry.to_chain_config(&status)?),
+        })
+    }
+}
+
+fn default_repeat() -> u32 {
+    1
+}
+
+fn load_pipeline(file: &Path) -> Result<Pipeline> {
+    let contents = fs::read_to_string(file)
+        .with_context(|| format!("unable to read pipeline file {}", file.display()))?;
+    serde_yaml::from_str(&contents)
+        .with_context(|| format!("invalid pipeline file {}", file.display()))
+}
+
+/// Prints status of each step of a pipeline run
+fn print_run(run: &MacroRun, output: OutputFormat, color_choice: ColorChoice) -> Result<()> {
+    let rows = run
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(index, step)| into_step_row(run, index, step))
+        .collect();
+
+    ListTable::new(
+        vec![
+            "Step",
+            "Operation",
+            "Chain ID",
+            "Retries",
+            "Status",
+            "Output",
+            "Completed at",
+        ],
+        rows,
+    )
+    .print(output, color_choice)
+}
+
+fn into_step_row(run: &MacroRun, index: usize, step: &MacroStep) -> Vec<Field> {
+    let result = run.journal.iter().find(|result| result.step == index);
+    let current = result.is_none() && index == run.journal.len();
+
+    let (status, step_output) = match result {
+        Some(result) => (
+            "completed".field().foreground_color(Some(Color::Green)),
+            result.output.clone(),
+        ),
+        None if current && run.status == MacroRunStatus::Failed => (
+            "failed".field().foreground_color(Some(Color::Red)),
+            run.error.clone().unwrap_or_else(|| "-".to_string()),
+        ),
+        None if current && run.status == MacroRunStatus::Running => (
+            "running".field().foreground_color(Some(Color::Yellow)),
+            "-".to_string(),
+        ),
+        None => ("pending".field(), "-".to_string()),
+    };
+
+    vec![
+        index.field().justify(Justify::Right),
+        step.operation.name().field(),
+        step.operation.chain_id().unwrap_or("-").field(),
+        step.retries.field().justify(Justify::Right),
+        status,
+        step_output.field(),
+        result
+            .map_or_else(|| "-".to_string(), |result| result.completed_at.to_string())
+            .field(),
+    ]
+}
@@ -0,0 +1,101 @@
+use anyhow::{Context, Result};
+use solo_machine_core::{
+    cosmos::crypto::PublicKeyAlgo,
+    ibc_version::SoloMachineRevision,
+    verifier::{decode_proof, ClaimedState, ProofDataType, ProofVerifier},
+};
+use structopt::StructOpt;
+
+use super::ibc::{parse_public_key, PROOF_DATA_TYPE_VARIANTS, PUBLIC_KEY_ALGO_VARIANTS};
+use crate::output::OutputFormat;
+
+const CLIENT_REVISION_VARIANTS: [&str; 3] = ["v1", "v2", "v3"];
+
+#[derive(Debug, StructOpt)]
+pub enum ProofCommand {
+    /// Decodes a solo machine proof (`TimestampedSignatureData` along with inner `SignatureData`) and prints it as
+    /// JSON. When public key of solo machine is provided, also computes expected sign bytes and verifies the proof
+    /// against them locally (to diagnose "invalid proof" errors returned by chains)
+    Inspect {
+        /// Hex encoded proof (`TimestampedSignatureData`)
+        proof: String,
+        /// Hex encoded public key of solo machine which signed the proof (proof is only decoded when not provided)
+        #[structopt(long, requires_all = &["sequence", "data-type", "data", "diversifier"])]
+        public_key: Option<String>,
+        /// Type of public key
+        #[structopt(long, possible_values = &PUBLIC_KEY_ALGO_VARIANTS, default_value = "secp256k1")]
+        public_key_algo: PublicKeyAlgo,
+        /// Diversifier of solo machine
+        #[structopt(long)]
+        diversifier: Option<String>,
+        /// Sequence at which proof is expected to be signed
+        #[structopt(long)]
+        sequence: Option<u64>,
+        /// Type of claimed data
+        #[structopt(long, possible_values = &PROOF_DATA_TYPE_VARIANTS)]
+        data_type: Option<ProofDataType>,
+        /// Hex encoded claimed data (e.g. `PacketCommitmentData`, or the value stored at `--path` for chains using
+        /// `solomachine.v3` client types)
+        #[structopt(long)]
+        data: Option<String>,
+        /// Prefixed commitment path of claimed data (e.g. `/ibc/connections%2Fconnection-0`, required for chains using
+        /// `solomachine.v3` client types)
+        #[structopt(long)]
+        path: Option<String>,
+        /// Revision of solo machine client types used by chain (default revision of solo machine when not provided)
+        #[structopt(long, possible_values = &CLIENT_REVISION_VARIANTS)]
+        client_revision: Option<SoloMachineRevision>,
+    },
+}
+
+impl ProofCommand {
+    pub fn execute(self, output: OutputFormat) -> Result<()> {
+        match self {
+            Self::Inspect {
+                proof,
+                public_key,
+                public_key_algo,
+                diversifier,
+                sequence,
+                data_type,
+                data,
+                path,
+                client_revision,
+            } => {
+                let proof = hex::decode(&proof).context("unable to decode hex bytes of proof")?;
+
+                let inspection = match (public_key, diversifier, sequence, data_type, data) {
+                    (
+                        Some(public_key),
+                        Some(diversifier),
+                        Some(sequence),
+                        Some(data_type),
+                        Some(data),
+                    ) => {
+                        let claimed_state = ClaimedState::Raw {
+                            data_type,
+                            path,
+                            data: hex::decode(&data)
+                                .context("unable to decode hex bytes of data")?,
+                        };
+
+                        let mut verifier = ProofVerifier::new(
+                            parse_public_key(&public_key, public_key_algo)?,
+                            diversifier,
+                        );
+
+                        if let Some(revision) = client_revision {
+                            verifier = verifier.with_revision(revision);
+                        }
+
+                        serde_json::to_value(verifier.inspect(sequence, &proof, &claimed_state)?)
+                    }
+                    _ => serde_json::to_value(decode_proof(&proof)?),
+                }
+                .context("unable to serialize proof inspection")?;
+
+                output.print(&inspection)
+            }
+        }
+    }
+}
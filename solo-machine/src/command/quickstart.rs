@@ -0,0 +1,374 @@
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use async_trait::async_trait;
+use bip32::{DerivationPath, ExtendedPrivateKey, Language, Mnemonic};
+use humantime::format_duration;
+use k256::ecdsa::{signature::Signer as _, Signature, SigningKey};
+use rand::rngs::OsRng;
+use rust_decimal::Decimal;
+use solo_machine_core::{
+    cosmos::crypto::PublicKey,
+    ibc::core::ics24_host::identifier::{ChainId, Identifier, PortId},
+    model::{ChainConfig, ConnectionDetails, Fee, NetworkTimeouts},
+    plugins::Plugins,
+    rpc_client::TimeoutRpcClient,
+    service::{ChainService, IbcService},
+    signer::Message,
+    DbPool, Event, Signer, ToPublicKey,
+};
+use structopt::StructOpt;
+use tendermint::Hash;
+use tendermint_rpc::Client;
+use termcolor::{Color, ColorChoice, ColorSpec};
+use tokio::sync::mpsc::UnboundedSender;
+use zeroize::Zeroizing;
+
+use crate::{
+    command::print_stream,
+    output::{KeyValueTable, OutputFormat},
+};
+
+const HD_PATH: &str = "m/44'/118'/0'/0/0";
+
+#[derive(Debug, StructOpt)]
+pub struct QuickstartCommand {
+    /// gRPC address of local development chain
+    #[structopt(
+        long,
+        default_value = "http://0.0.0.0:9090",
+        env = "SOLO_GRPC_ADDRESS",
+        hide_env_values = true
+    )]
+    grpc_addr: String,
+    /// RPC address of local development chain
+    #[structopt(
+        long,
+        default_value = "http://0.0.0.0:26657",
+        env = "SOLO_RPC_ADDRESS",
+        hide_env_values = true
+    )]
+    rpc_addr: String,
+    /// Account prefix of addresses on local development chain
+    #[structopt(
+        long,
+        default_value = "cosmos",
+        env = "SOLO_ACCOUNT_PREFIX",
+        hide_env_values = true
+    )]
+    account_prefix: String,
+    /// Fee amount
+    #[structopt(
+        long,
+        default_value = "1000",
+        env = "SOLO_FEE_AMOUNT",
+        hide_env_values = true
+    )]
+    fee_amount: Decimal,
+    /// Fee denom (also used for funding generated key)
+    #[structopt(
+        long,
+        default_value = "stake",
+        env = "SOLO_FEE_DENOM",
+        hide_env_values = true
+    )]
+    fee_denom: Identifier,
+    /// Gas limit
+    #[structopt(
+        long,
+        default_value = "300000",
+        env = "SOLO_GAS_LIMIT",
+        hide_env_values = true
+    )]
+    gas_limit: u64,
+    /// Amount of fee denom sent from `signer` (a funded account on development chain) to generated key for paying
+    /// transaction fees
+    #[structopt(long, default_value = "1000000")]
+    funding_amount: u64,
+    /// Denom of tokens minted and burnt in round-trip transfer
+    #[structopt(long, default_value = "gld")]
+    denom: Identifier,
+    /// Amount of tokens minted and burnt in round-trip transfer
+    #[structopt(long, default_value = "100")]
+    amount: u64,
+    /// Optional memo to include in transactions
+    #[structopt(
+        long,
+        default_value = "solo-machine-memo",
+        env = "SOLO_MEMO",
+        hide_env_values = true
+    )]
+    memo: String,
+}
+
+/// Outcome of a quickstart run
+pub struct QuickstartSummary {
+    chain_id: ChainId,
+    mnemonic: Zeroizing<String>,
+    address: String,
+    connection_details: ConnectionDetails,
+    ibc_denom: String,
+    funding_transaction_hash: String,
+    mint_transaction_hash: String,
+    burn_transaction_hash: String,
+    elapsed: Duration,
+}
+
+impl QuickstartCommand {
+    /// Generates a new key, registers the chain, funds the key using `funder`, runs IBC handshake and performs a
+    /// round-trip transfer (mint followed by burn)
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        funder: impl Signer,
+        plugins: Plugins,
+        sender: UnboundedSender<Event>,
+    ) -> Result<QuickstartSummary> {
+        let start = Instant::now();
+
+        let chain_service = ChainService::new_with_notifier(db_pool.clone(), sender.clone())
+            .with_plugins(plugins.clone());
+        let ibc_service = IbcService::new_with_notifier(db_pool, sender).with_plugins(plugins);
+
+        let key = QuickstartSigner::generate(self.account_prefix)?;
+        let address = key.to_account_address()?;
+
+        let rpc_client = TimeoutRpcClient::new(&self.rpc_addr, &NetworkTimeouts::default(), None)?;
+        let status = rpc_client
+            .status()
+            .await
+            .context("unable to query status of development chain (is the node running?)")?;
+
+        let chain_id: ChainId = status.node_info.network.to_string().parse()?;
+
+        ensure!(
+            chain_service.get(&chain_id).await?.is_none(),
+            "chain {} is already registered in database (use a fresh database for quickstart)",
+            chain_id
+        );
+
+        let trusted_hash = match status.sync_info.latest_block_hash {
+            Hash::Sha256(hash) => hash,
+            Hash::None => return Err(anyhow!("development chain has not produced any block")),
+        };
+
+        let config = ChainConfig {
+            grpc_addr: self.grpc_addr,
+            grpc_tls: Default::default(),
+            rpc_addr: self.rpc_addr,
+            fee: Fee {
+                amount: self.fee_amount,
+                denom: self.fee_denom.clone(),
+                gas_limit: self.gas_limit,
+                granter: None,
+            },
+            trust_level: "1/3".parse()?,
+            trusting_period: Duration::from_secs(14 * 24 * 60 * 60),
+            max_clock_drift: Duration::from_secs(3),
+            rpc_timeout: Duration::from_secs(60),
+            diversifier: "solo-machine-diversifier".to_string(),
+            port_id: PortId::from_str("transfer")?,
+            trusted_height: status.sync_info.latest_block_height,
+            trusted_hash,
+            name_resolver: None,
+            address_display: Default::default(),
+            address_format: Default::default(),
+            sign_mode: Default::default(),
+            address_algo: Default::default(),
+            halt_threshold: Duration::from_secs(120),
+            fee_strategy: Default::default(),
+            client_encoding: Default::default(),
+            inclusion_policy: Default::default(),
+            retry_policy: Default::default(),
+            timeouts: Default::default(),
+            rate_limit: None,
+            fallback_grpc_addrs: Vec::new(),
+            fallback_rpc_addrs: Vec::new(),
+            proxy: None,
+            timestamp_sync_offset: None,
+            ibc_version: None,
+            client_revision: None,
+            strict_preflight: false,
+        };
+
+        chain_service
+            .add(&config, &key.to_public_key()?.encode())
+            .await?;
+
+        let funding_transaction_hash = chain_service
+            .send_tokens(
+                funder,
+                &chain_id,
+                address.clone(),
+                self.funding_amount,
+                self.fee_denom.to_string(),
+                self.memo.clone(),
+                None,
+            )
+            .await
+            .context("unable to fund generated key (`signer` should be a funded account)")?;
+
+        ibc_service
+            .connect(&key, chain_id.clone(), self.memo.clone(), false)
+            .await?;
+
+        let mint_transaction_hash = ibc_service
+            .mint(
+                &key,
+                chain_id.clone(),
+                None,
+                self.amount,
+                self.denom.clone(),
+                None,
+                self.memo.clone(),
+            )
+            .await?;
+
+        let burn_transaction_hash = ibc_service
+            .burn(
+                &key,
+                chain_id.clone(),
+                None,
+                self.amount,
+                self.denom.clone(),
+                self.memo,
+            )
+            .await?;
+
+        let chain = chain_service
+            .get(&chain_id)
+            .await?
+            .ok_or_else(|| anyhow!("chain details for {} not found", chain_id))?;
+
+        let ibc_denom = chain
+            .get_ibc_denom(&self.denom)
+            .ok_or_else(|| anyhow!("connection details not found for {}", chain_id))?;
+        let connection_details = chain
+            .connection_details
+            .ok_or_else(|| anyhow!("connection details not found for {}", chain_id))?;
+
+        Ok(QuickstartSummary {
+            chain_id,
+            mnemonic: Zeroizing::new(key.mnemonic.phrase().to_string()),
+            address,
+            connection_details,
+            ibc_denom,
+            funding_transaction_hash,
+            mint_transaction_hash,
+            burn_transaction_hash,
+            elapsed: start.elapsed(),
+        })
+    }
+}
+
+impl QuickstartSummary {
+    pub fn print(&self, color_choice: ColorChoice, output: OutputFormat) -> Result<()> {
+        let mut stdout = output.stream(color_choice);
+
+        print_stream(
+            &mut stdout,
+            ColorSpec::new().set_bold(true),
+            "Quickstart completed!",
+        )?;
+
+        let mut table = KeyValueTable::default();
+
+        table.add("Chain ID", &self.chain_id);
+        table.add("Address", &self.address);
+        table.add(
+            "Solo machine client ID",
+            &self.connection_details.solo_machine_client_id,
+        );
+        table.add(
+            "Tendermint client ID",
+            &self.connection_details.tendermint_client_id,
+        );
+        table.add(
+            "Solo machine connection ID",
+            &self.connection_details.solo_machine_connection_id,
+        );
+        table.add(
+            "Tendermint connection ID",
+            &self.connection_details.tendermint_connection_id,
+        );
+        table.add(
+            "Solo machine channel ID",
+            &self.connection_details.solo_machine_channel_id,
+        );
+        table.add(
+            "Tendermint channel ID",
+            &self.connection_details.tendermint_channel_id,
+        );
+        table.add("IBC denom", &self.ibc_denom);
+        table.add("Funding transaction hash", &self.funding_transaction_hash);
+        table.add("Mint transaction hash", &self.mint_transaction_hash);
+        table.add("Burn transaction hash", &self.burn_transaction_hash);
+        table.add(
+            "Elapsed",
+            format_duration(Duration::from_secs(self.elapsed.as_secs())),
+        );
+
+        table.print(output, color_choice)?;
+
+        print_stream(
+            &mut stdout,
+            ColorSpec::new().set_fg(Some(Color::Yellow)),
+            format!(
+                "Mnemonic of generated key (for development only, use it with mnemonic signer to continue): {}",
+                self.mnemonic.as_str()
+            ),
+        )
+    }
+}
+
+/// Signer backed by a freshly generated mnemonic (kept in memory only)
+struct QuickstartSigner {
+    mnemonic: Mnemonic,
+    signing_key: SigningKey,
+    account_prefix: String,
+}
+
+impl QuickstartSigner {
+    fn generate(account_prefix: String) -> Result<Self> {
+        let mnemonic = Mnemonic::random(OsRng, Language::English);
+        let hd_path = DerivationPath::from_str(HD_PATH).context("invalid HD path")?;
+        let signing_key = ExtendedPrivateKey::<SigningKey>::derive_from_path(
+            mnemonic.to_seed("").as_bytes(),
+            &hd_path,
+        )
+        .map_err(|_| anyhow!("unable to derive signing key from mnemonic"))?
+        .into();
+
+        Ok(Self {
+            mnemonic,
+            signing_key,
+            account_prefix,
+        })
+    }
+}
+
+impl ToPublicKey for QuickstartSigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        Ok(PublicKey::Secp256k1(self.signing_key.verifying_key()))
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        &self.account_prefix
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.to_public_key()?
+            .account_address(self.get_account_prefix())
+    }
+}
+
+#[async_trait]
+impl Signer for QuickstartSigner {
+    async fn sign(&self, _request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
+        let signature: Signature = self.signing_key.sign(message.as_ref());
+        Ok(signature.as_ref().to_vec())
+    }
+}
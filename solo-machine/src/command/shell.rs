@@ -0,0 +1,328 @@
+use std::{convert::TryFrom, path::PathBuf, sync::Arc};
+
+use anyhow::{Context, Result};
+use cli_table::ColorChoice;
+use rustyline::{
+    completion::{Completer, Pair},
+    error::ReadlineError,
+    highlight::Highlighter,
+    hint::Hinter,
+    validate::Validator,
+    Editor, Helper,
+};
+use solo_machine_core::{
+    event::HandlerRegistrar as _,
+    plugins::Plugins,
+    service::ChainService,
+    supervisor::{RestartPolicy, TaskSupervisor},
+    DbPool, Signer,
+};
+use structopt::{
+    clap::{App, AppSettings},
+    StructOpt,
+};
+use termcolor::{Color, ColorSpec, StandardStream};
+
+use crate::{
+    command::{chain::ChainCommand, ibc::IbcCommand, print_stream, status::StatusCommand},
+    event::{
+        audit_logger::AuditLogger, cli_event_handler::CliEventHandler,
+        stats_recorder::StatsRecorder, HandlerRegistrar,
+    },
+    output::OutputFormat,
+};
+
+const PROMPT: &str = "solo-machine> ";
+
+#[derive(Debug, StructOpt)]
+pub struct ShellCommand {
+    /// Path of file in which command history is persisted across sessions (history is only kept in memory when not
+    /// provided)
+    #[structopt(long, env = "SOLO_SHELL_HISTORY")]
+    history: Option<PathBuf>,
+}
+
+/// Commands available in interactive shell
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "solo-machine",
+    setting = AppSettings::NoBinaryName,
+    setting = AppSettings::DisableVersion,
+    setting = AppSettings::VersionlessSubcommands
+)]
+enum ReplCommand {
+    /// Chain operations (managing chain state and metadata)
+    Chain(ChainCommand),
+    /// Used to connect, mint tokens and burn tokens on IBC enabled chain
+    Ibc(IbcCommand),
+    /// Prints aggregated status of solo machine
+    Status(StatusCommand),
+    /// Exits interactive shell
+    #[structopt(alias = "quit")]
+    Exit,
+}
+
+/// Interactive shell context (initialized once and shared by all the commands run in shell)
+struct Shell {
+    db_pool: DbPool,
+    signer: Arc<dyn Signer>,
+    plugins: Plugins,
+    handlers: Vec<PathBuf>,
+    color_choice: ColorChoice,
+    output: OutputFormat,
+    config: Option<PathBuf>,
+}
+
+impl ShellCommand {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        signer: Arc<dyn Signer>,
+        plugins: Plugins,
+        handlers: Vec<PathBuf>,
+        color_choice: ColorChoice,
+        output: OutputFormat,
+        config: Option<PathBuf>,
+    ) -> Result<()> {
+        let shell = Shell {
+            db_pool,
+            signer,
+            plugins,
+            handlers,
+            color_choice,
+            output,
+            config,
+        };
+
+        let mut editor = Editor::<ShellHelper>::new();
+        editor.set_helper(Some(ShellHelper {
+            app: ReplCommand::clap(),
+            chain_ids: shell.chain_ids().await?,
+        }));
+
+        if let Some(ref history) = self.history {
+            // history file does not exist on first run
+            let _ = editor.load_history(history);
+        }
+
+        print_stream(
+            &mut output.stream(color_choice),
+            ColorSpec::new().set_bold(true),
+            "Interactive shell of solo machine (type `help` for available commands, `exit` or Ctrl-D to exit)",
+        )?;
+
+        loop {
+            let line = match tokio::task::block_in_place(|| editor.readline(PROMPT)) {
+                Ok(line) => line,
+                Err(ReadlineError::Interrupted) => continue,
+                Err(ReadlineError::Eof) => break,
+                Err(err) => return Err(err).context("unable to read command"),
+            };
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            editor.add_history_entry(line.as_str());
+
+            let command = match shell_words::split(&line)
+                .context("unable to parse command")
+                .and_then(|words| Ok(ReplCommand::from_iter_safe(words)?))
+            {
+                Ok(command) => command,
+                Err(err) => {
+                    match err.downcast_ref::<structopt::clap::Error>() {
+                        Some(err) if err.use_stderr() => eprintln!("{}", err.message),
+                        Some(err) => println!("{}", err.message),
+                        None => shell.print_error(&err)?,
+                    }
+                    continue;
+                }
+            };
+
+            if let ReplCommand::Exit = command {
+                break;
+            }
+
+            if let Err(err) = shell.run(command).await {
+                shell.print_error(&err)?;
+            }
+
+            // chains may be added by previous command
+            if let Some(helper) = editor.helper_mut() {
+                helper.chain_ids = shell.chain_ids().await?;
+            }
+        }
+
+        if let Some(ref history) = self.history {
+            editor.save_history(history).with_context(|| {
+                format!("unable to save shell history to {}", history.display())
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Shell {
+    /// Runs a command with a fresh event pipeline (so that all the events of a command are handled before next prompt)
+    async fn run(&self, command: ReplCommand) -> Result<()> {
+        let mut handler_registrar = HandlerRegistrar::try_from(self.handlers.clone())?;
+        handler_registrar.register(Box::new(AuditLogger::new(self.db_pool.clone())));
+        handler_registrar.register(Box::new(StatsRecorder::new(self.db_pool.clone())));
+        handler_registrar.register(Box::new(CliEventHandler::new(
+            self.color_choice,
+            self.output,
+        )));
+        let mut supervisor = TaskSupervisor::new();
+        let sender = handler_registrar.spawn(&mut supervisor, RestartPolicy::Never);
+
+        let result = match command {
+            ReplCommand::Chain(chain) => {
+                chain
+                    .execute(
+                        self.db_pool.clone(),
+                        self.signer.clone(),
+                        self.plugins.clone(),
+                        sender,
+                        self.color_choice,
+                        self.output,
+                        self.config.clone(),
+                    )
+                    .await
+            }
+            ReplCommand::Ibc(ibc) => {
+                ibc.execute(
+                    self.db_pool.clone(),
+                    self.signer.clone(),
+                    self.plugins.clone(),
+                    sender,
+                    self.color_choice,
+                    self.output,
+                )
+                .await
+            }
+            ReplCommand::Status(status) => {
+                drop(sender);
+                status
+                    .execute(
+                        self.db_pool.clone(),
+                        Some(Ok(self.signer.clone())),
+                        self.color_choice,
+                        self.output,
+                    )
+                    .await
+            }
+            ReplCommand::Exit => Ok(()),
+        };
+
+        supervisor.join().await?;
+        result
+    }
+
+    async fn chain_ids(&self) -> Result<Vec<String>> {
+        Ok(ChainService::new(self.db_pool.clone())
+            .get_all()
+            .await?
+            .into_iter()
+            .map(|chain| chain.id.to_string())
+            .collect())
+    }
+
+    fn print_error(&self, err: &anyhow::Error) -> Result<()> {
+        print_stream(
+            &mut StandardStream::stderr(self.color_choice),
+            ColorSpec::new().set_fg(Some(Color::Red)),
+            format!("error: {:#}", err).trim_end(),
+        )
+    }
+}
+
+/// Tab completion of subcommands, options and chain IDs in interactive shell
+struct ShellHelper {
+    app: App<'static, 'static>,
+    chain_ids: Vec<String>,
+}
+
+impl ShellHelper {
+    /// Returns candidates for given partial word of a command (preceded by given complete words)
+    fn candidates(&self, words: &[String], partial: &str) -> Vec<String> {
+        let mut app = &self.app;
+
+        for word in words {
+            match app.p.subcommands.iter().find(|app| app.get_name() == word) {
+                Some(subcommand) => app = subcommand,
+                None => break,
+            }
+        }
+
+        let mut candidates: Vec<String> = if partial.starts_with('-') {
+            app.p
+                .flags
+                .iter()
+                .filter_map(|flag| flag.s.long)
+                .chain(app.p.opts.iter().filter_map(|opt| opt.s.long))
+                .map(|long| format!("--{}", long))
+                .collect()
+        } else if !app.p.subcommands.is_empty() {
+            app.p
+                .subcommands
+                .iter()
+                .map(|app| app.get_name().to_owned())
+                .chain(std::iter::once("help".to_owned()))
+                .collect()
+        } else {
+            self.chain_ids.clone()
+        };
+
+        candidates.retain(|candidate| candidate.starts_with(partial));
+        candidates.sort();
+        candidates.dedup();
+        candidates
+    }
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let line = &line[..pos];
+        let start = line
+            .rfind(char::is_whitespace)
+            .map(|index| index + 1)
+            .unwrap_or(0);
+
+        let words: Vec<String> = line[..start]
+            .split_whitespace()
+            .map(ToOwned::to_owned)
+            .collect();
+
+        let candidates = self
+            .candidates(&words, &line[start..])
+            .into_iter()
+            .map(|candidate| Pair {
+                display: candidate.clone(),
+                replacement: format!("{} ", candidate),
+            })
+            .collect();
+
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
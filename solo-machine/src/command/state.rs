@@ -0,0 +1,90 @@
+use anyhow::{ensure, Context, Result};
+use solo_machine_core::{
+    storage::{disable_state_encryption, enable_state_encryption},
+    DbPool,
+};
+use structopt::StructOpt;
+use termcolor::{ColorChoice, ColorSpec};
+use zeroize::Zeroizing;
+
+use crate::{command::print_stream, output::OutputFormat};
+
+#[derive(Debug, StructOpt)]
+pub enum StateCommand {
+    /// Encrypts stored state (chain configurations, client states, consensus states, connections and channels) with a
+    /// key derived from state passphrase (all the future commands need the passphrase to unlock stored state)
+    Encrypt,
+    /// Decrypts stored state encrypted using `state encrypt`
+    Decrypt,
+}
+
+impl StateCommand {
+    /// Executes state command. `passphrase` is the state passphrase provided in CLI options (it is read from terminal
+    /// when not provided).
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        passphrase: Option<Zeroizing<String>>,
+        color_choice: ColorChoice,
+        output: OutputFormat,
+    ) -> Result<()> {
+        let mut stdout = output.stream(color_choice);
+
+        match self {
+            Self::Encrypt => {
+                let passphrase = match passphrase {
+                    Some(passphrase) => passphrase,
+                    None => read_new_passphrase()?,
+                };
+
+                let count = enable_state_encryption(&db_pool, passphrase.as_str()).await?;
+
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Encrypted stored state ({} records), provide state passphrase in all the future commands",
+                        count
+                    ),
+                )
+            }
+            Self::Decrypt => {
+                let passphrase = match passphrase {
+                    Some(passphrase) => passphrase,
+                    None => read_passphrase("Enter state passphrase: ")?,
+                };
+
+                let count = disable_state_encryption(&db_pool, passphrase.as_str()).await?;
+
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!("Decrypted stored state ({} records)", count),
+                )
+            }
+        }
+    }
+}
+
+fn read_passphrase(prompt: &str) -> Result<Zeroizing<String>> {
+    rpassword::read_password_from_tty(Some(prompt))
+        .map(Zeroizing::new)
+        .context("unable to read state passphrase from terminal")
+}
+
+/// Reads a new state passphrase from terminal (with confirmation)
+fn read_new_passphrase() -> Result<Zeroizing<String>> {
+    let passphrase = read_passphrase("Enter new state passphrase: ")?;
+    ensure!(
+        !passphrase.as_str().is_empty(),
+        "state passphrase cannot be empty"
+    );
+
+    let confirmation = read_passphrase("Confirm state passphrase: ")?;
+    ensure!(
+        passphrase.as_str() == confirmation.as_str(),
+        "state passphrases do not match"
+    );
+
+    Ok(passphrase)
+}
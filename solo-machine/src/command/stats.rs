@@ -0,0 +1,127 @@
+use std::{io::Write, time::Duration};
+
+use anyhow::Result;
+use chrono::Utc;
+use cli_table::{format::Justify, ColorChoice};
+use humantime::format_duration;
+use serde_json::json;
+use solo_machine_core::{
+    service::{ChainService, ChainStats, DailyStats, StatsService},
+    DbPool,
+};
+use structopt::StructOpt;
+use termcolor::ColorSpec;
+
+use crate::{
+    command::print_stream,
+    output::{Field, ListTable, OutputFormat, ToField},
+};
+
+/// Number of seconds in a day
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, StructOpt)]
+pub struct StatsCommand {
+    /// Chain ID (or label) of IBC enabled chain (stats of all the chains are printed when not provided)
+    chain_id: Option<String>,
+    /// Number of days (including today) for which daily aggregates are printed
+    #[structopt(long, default_value = "7")]
+    days: u32,
+}
+
+impl StatsCommand {
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        color_choice: ColorChoice,
+        output: OutputFormat,
+    ) -> Result<()> {
+        let chain_service = ChainService::new(db_pool.clone());
+        let stats_service = StatsService::new(db_pool);
+
+        let chain_id = match self.chain_id {
+            None => None,
+            Some(ref chain_id) => Some(chain_service.resolve_chain_id(chain_id).await?),
+        };
+
+        let chains_table = ListTable::new(
+            vec![
+                "Chain ID",
+                "Packets relayed",
+                "Operations",
+                "Last successful operation",
+            ],
+            stats_service
+                .chains(chain_id.as_ref())
+                .await?
+                .into_iter()
+                .map(into_chain_row)
+                .collect(),
+        );
+
+        let daily_table = ListTable::new(
+            vec!["Day", "Uptime", "Uptime %", "Packets relayed", "Operations"],
+            stats_service
+                .daily(chain_id.as_ref(), self.days)
+                .await?
+                .into_iter()
+                .map(into_daily_row)
+                .collect(),
+        );
+
+        if !output.is_text() {
+            return output.print(&json!({
+                "chains": chains_table.to_json(),
+                "daily": daily_table.to_json(),
+            }));
+        }
+
+        let mut stdout = output.stream(color_choice);
+
+        print_stream(&mut stdout, ColorSpec::new().set_bold(true), "Chains")?;
+        chains_table.print(output, color_choice)?;
+
+        writeln!(stdout)?;
+        print_stream(&mut stdout, ColorSpec::new().set_bold(true), "Daily (UTC)")?;
+        daily_table.print(output, color_choice)
+    }
+}
+
+fn into_chain_row(chain_stats: ChainStats) -> Vec<Field> {
+    vec![
+        chain_stats.chain_id.field(),
+        chain_stats.packets_relayed.field().justify(Justify::Right),
+        chain_stats.operations.field().justify(Justify::Right),
+        chain_stats
+            .last_success_at
+            .map_or_else(|| "-".to_string(), |time| time.to_string())
+            .field(),
+    ]
+}
+
+fn into_daily_row(daily_stats: DailyStats) -> Vec<Field> {
+    let now = Utc::now();
+
+    // uptime of today is relative to the time elapsed since midnight
+    let day_seconds = if daily_stats.day == now.date().naive_utc() {
+        (now - now.date().and_hms(0, 0, 0)).num_seconds().max(1) as u64
+    } else {
+        SECONDS_PER_DAY
+    };
+
+    let uptime_percent =
+        (daily_stats.uptime_seconds.min(day_seconds) as f64 * 100.0) / day_seconds as f64;
+
+    vec![
+        daily_stats.day.field(),
+        format_duration(Duration::from_secs(daily_stats.uptime_seconds))
+            .to_string()
+            .field()
+            .justify(Justify::Right),
+        format!("{:.2}%", uptime_percent)
+            .field()
+            .justify(Justify::Right),
+        daily_stats.packets_relayed.field().justify(Justify::Right),
+        daily_stats.operations.field().justify(Justify::Right),
+    ]
+}
@@ -0,0 +1,178 @@
+use std::{io::Write, sync::Arc};
+
+use anyhow::Result;
+use cli_table::{format::Justify, ColorChoice};
+use solo_machine_core::{
+    service::{ChainStatus, SignerStatus, StatusService},
+    DbPool, Signer,
+};
+use structopt::StructOpt;
+use termcolor::{Color, ColorSpec};
+
+use crate::{
+    command::print_stream,
+    output::{Field, KeyValueTable, ListTable, OutputFormat, ToField},
+};
+
+#[derive(Debug, StructOpt)]
+pub struct StatusCommand {
+    /// Prints status report as JSON
+    #[structopt(long)]
+    json: bool,
+}
+
+impl StatusCommand {
+    /// Prints aggregated status of solo machine. `signer` is the result of loading signer (`None` if no signer is
+    /// provided), a signer which fails to load is reported instead of failing the command.
+    pub async fn execute(
+        self,
+        db_pool: DbPool,
+        signer: Option<Result<Arc<dyn Signer>>>,
+        color_choice: ColorChoice,
+        output: OutputFormat,
+    ) -> Result<()> {
+        let status_service = StatusService::new(db_pool);
+
+        let report = match signer {
+            None => status_service.status(None::<Arc<dyn Signer>>).await?,
+            Some(Ok(signer)) => status_service.status(Some(signer)).await?,
+            Some(Err(err)) => {
+                let mut report = status_service.status(None::<Arc<dyn Signer>>).await?;
+                report.signer = Some(SignerStatus {
+                    public_key: None,
+                    account_address: None,
+                    error: Some(format!("{:#}", err)),
+                });
+                report
+            }
+        };
+
+        if self.json {
+            return OutputFormat::Json.print(&report);
+        }
+
+        if !output.is_text() {
+            return output.print(&report);
+        }
+
+        let mut table = KeyValueTable::default();
+
+        table.add("Version", report.version);
+        table.add("Storage backend", report.storage.backend);
+        table.add(
+            "State encryption",
+            if report.storage.encrypted {
+                "enabled"
+            } else {
+                "disabled"
+            },
+        );
+        table.add(
+            "Schema version",
+            match (
+                report.storage.schema_version,
+                report.storage.latest_schema_version,
+            ) {
+                (Some(version), Some(latest)) if version == latest => version.to_string(),
+                (Some(version), Some(latest)) => format!("{} (latest: {})", version, latest),
+                (version, _) => version.map_or_else(|| "-".to_string(), |v| v.to_string()),
+            },
+        );
+
+        match report.signer {
+            None => table.add("Signer", "-"),
+            Some(ref signer) => {
+                table.add(
+                    "Signer",
+                    signer
+                        .account_address
+                        .as_deref()
+                        .or(signer.public_key.as_deref())
+                        .unwrap_or("-"),
+                );
+
+                if let Some(ref error) = signer.error {
+                    table.add("Signer error", error);
+                }
+            }
+        }
+
+        table.add(
+            "Signing",
+            if report.integrity_issues.is_empty() {
+                "enabled".to_string()
+            } else {
+                format!(
+                    "refused ({} integrity issues, run `repair`)",
+                    report.integrity_issues.len()
+                )
+            },
+        );
+        table.add("Pending operations", report.pending_operations);
+        table.add("Generated at", report.generated_at);
+
+        table.print(output, color_choice)?;
+
+        let mut stdout = output.stream(color_choice);
+
+        for issue in report.integrity_issues.iter() {
+            print_stream(
+                &mut stdout,
+                ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)),
+                format!("Integrity issue: {}", issue),
+            )?;
+        }
+
+        writeln!(stdout)?;
+        print_stream(&mut stdout, ColorSpec::new().set_bold(true), "Chains")?;
+
+        let rows = report.chains.iter().map(into_chain_row).collect::<Vec<_>>();
+
+        ListTable::new(
+            vec![
+                "Chain ID",
+                "Connection",
+                "Tendermint client",
+                "Client expires at",
+                "Connection state",
+                "Channel state",
+                "Sequence",
+                "Pending packets",
+                "Pending operations",
+            ],
+            rows,
+        )
+        .print(output, color_choice)
+    }
+}
+
+fn into_chain_row(chain: &ChainStatus) -> Vec<Field> {
+    let connection = match chain.handshake_step {
+        Some(step) => format!("{} (next step: {})", chain.connection, step),
+        None => chain.connection.to_string(),
+    };
+
+    let (client, expires_at) = match chain.tendermint_client {
+        None => ("-".to_string(), "-".to_string()),
+        Some(ref client) => (
+            format!("{} (height: {})", client.client_id, client.latest_height),
+            if client.expired {
+                format!("{} (expired)", client.expires_at)
+            } else {
+                client.expires_at.to_string()
+            },
+        ),
+    };
+
+    vec![
+        chain.chain_id.to_string().field(),
+        connection.field(),
+        client.field(),
+        expires_at.field(),
+        chain.connection_state.as_deref().unwrap_or("-").field(),
+        chain.channel_state.as_deref().unwrap_or("-").field(),
+        chain.sequence.field().justify(Justify::Right),
+        chain.pending_packets.field().justify(Justify::Right),
+        chain.pending_operations.field().justify(Justify::Right),
+    ]
+}
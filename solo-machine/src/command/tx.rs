@@ -0,0 +1,76 @@
+use std::{
+    io::{stdin, Read},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context, Error, Result};
+use solo_machine_core::proto::json::decode_tx;
+use structopt::StructOpt;
+
+use crate::output::OutputFormat;
+
+const TX_ENCODING_VARIANTS: [&str; 2] = ["base64", "hex"];
+
+#[derive(Debug, StructOpt)]
+pub enum TxCommand {
+    /// Decodes an encoded transaction (`TxRaw`, e.g. of a failed submission) and prints its body (including messages
+    /// and IBC payloads packed in them), auth info, signatures and hash as JSON
+    Decode {
+        /// Encoded transaction (read from stdin when not provided)
+        tx: Option<String>,
+        /// Encoding of transaction
+        #[structopt(long, possible_values = &TX_ENCODING_VARIANTS, default_value = "base64")]
+        encoding: TxEncoding,
+    },
+}
+
+/// Encoding of transaction bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxEncoding {
+    /// Base64 (used by tendermint RPC and cosmos SDK CLIs)
+    Base64,
+    /// Hex
+    Hex,
+}
+
+impl FromStr for TxEncoding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "base64" => Ok(Self::Base64),
+            "hex" => Ok(Self::Hex),
+            _ => Err(anyhow!("invalid transaction encoding: {}", s)),
+        }
+    }
+}
+
+impl TxCommand {
+    pub fn execute(self, output: OutputFormat) -> Result<()> {
+        match self {
+            Self::Decode { tx, encoding } => {
+                let tx = match tx {
+                    Some(tx) => tx,
+                    None => {
+                        let mut tx = String::new();
+                        stdin()
+                            .read_to_string(&mut tx)
+                            .context("unable to read transaction from stdin")?;
+                        tx
+                    }
+                };
+                let tx = tx.trim();
+
+                let tx_bytes = match encoding {
+                    TxEncoding::Base64 => {
+                        base64::decode(tx).context("invalid base64 encoding of transaction")?
+                    }
+                    TxEncoding::Hex => hex::decode(tx.trim_start_matches("0x"))
+                        .context("invalid hex encoding of transaction")?,
+                };
+
+                output.print(&decode_tx(&tx_bytes)?)
+            }
+        }
+    }
+}
@@ -0,0 +1,535 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+#[cfg(feature = "server")]
+use std::sync::Arc;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use num_rational::Ratio;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use solo_machine_core::{
+    ibc::core::ics24_host::identifier::{Identifier, PortId},
+    ibc_version::SoloMachineRevision,
+    model::{ChainConfig, Fee, GrpcTlsConfig, NetworkTimeouts},
+    policy::SigningPolicy,
+    proxy::Proxy,
+    rate_limit::RateLimit,
+    retry::{RetryPolicy, RetryableCode},
+    rpc_client::TimeoutRpcClient,
+};
+#[cfg(feature = "server")]
+use solo_machine_core::{plugins::Plugins, Signer};
+use tendermint::{block::Height as BlockHeight, Hash};
+use tendermint_rpc::{endpoint::status::Response as StatusResponse, Client};
+
+#[cfg(feature = "server")]
+use crate::signer::{load_plugins, load_signer};
+
+/// Environment variable containing path of configuration file
+pub const CONFIG_ENV: &str = "SOLO_CONFIG";
+
+/// Declarative configuration of solo machine (TOML, or YAML when file has `.yaml`/`.yml` extension)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Database connection string
+    pub db_uri: Option<String>,
+    /// Signer settings
+    pub signer: SignerConfig,
+    /// Settings of long running processes (relayer and gRPC server)
+    pub daemon: DaemonConfig,
+    /// Logging settings
+    pub log: LogConfig,
+    /// HTTP endpoints to which operational events are posted
+    pub webhooks: Vec<WebhookConfig>,
+    /// Slack and Telegram alerts on critical conditions
+    pub alerts: AlertConfig,
+    /// Policy evaluated before every signature
+    pub signing_policy: SigningPolicy,
+    /// IBC enabled chains (added to database using `chain sync`)
+    pub chains: Vec<ChainEntry>,
+    /// Independent solo machine identities served by `start` (keyed by tenant ID), each with its own database and
+    /// signer
+    pub tenants: BTreeMap<String, TenantConfig>,
+}
+
+/// Signer settings
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SignerConfig {
+    /// Path to signer's `*.so` file
+    pub path: Option<PathBuf>,
+    /// Path to fee payer signer's `*.so` file
+    pub fee_payer: Option<PathBuf>,
+    /// Path to fee strategy plugin's `*.so` file
+    pub fee_strategy_plugin: Option<PathBuf>,
+    /// Path to client encoder plugin's `*.so` file
+    pub client_encoder_plugin: Option<PathBuf>,
+    /// Name of key (from signer's key registry) used for signing
+    pub key: Option<String>,
+    /// Bech32 prefix of account addresses generated by signer
+    pub account_prefix: Option<String>,
+    /// Additional environment variables read by signer (e.g. `SOLO_MNEMONIC`)
+    pub env: BTreeMap<String, String>,
+}
+
+/// Settings of a tenant served by gRPC and REST servers
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TenantConfig {
+    /// Database connection string (chain state, API tokens, API keys and audit log of tenant)
+    pub db_uri: Option<String>,
+    /// Signer settings of tenant (`env` should contain tenant's own key material, e.g. `SOLO_MNEMONIC`)
+    pub signer: SignerConfig,
+    /// Policy evaluated before every signature of tenant's signer
+    pub signing_policy: SigningPolicy,
+}
+
+/// Settings of long running processes
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DaemonConfig {
+    /// gRPC server address
+    pub grpc_addr: Option<String>,
+    /// Interval between relaying rounds (e.g. `10s`)
+    pub relay_interval: Option<String>,
+    /// Memo used in transactions sent by relayer
+    pub relay_memo: Option<String>,
+}
+
+/// Logging settings
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    /// Maximum level of log records (e.g. `info`)
+    pub level: Option<String>,
+    /// Format of log records (`text` or `json`)
+    pub format: Option<String>,
+}
+
+/// Settings of a webhook
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// URL to which events are posted
+    pub url: String,
+    /// Types of events posted to webhook (e.g. `ConnectionEstablished`)
+    pub events: Vec<String>,
+    /// Secret used for signing requests
+    pub secret: Option<String>,
+}
+
+/// Settings of alerts posted to Slack and Telegram
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertConfig {
+    /// Slack incoming webhook URL
+    pub slack_webhook_url: Option<String>,
+    /// Token of Telegram bot
+    pub telegram_bot_token: Option<String>,
+    /// ID of Telegram chat
+    pub telegram_chat_id: Option<String>,
+    /// Number of consecutive failed broadcasts on a chain after which an alert is posted
+    pub broadcast_failure_threshold: Option<u32>,
+}
+
+/// Definition of an IBC enabled chain (unspecified values default to the ones used by `chain add`)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChainEntry {
+    /// Expected chain ID (verified against the chain when provided)
+    pub id: Option<String>,
+    /// gRPC address
+    pub grpc_addr: Option<String>,
+    /// PEM encoded root CA certificate trusted for `https` gRPC address (relative to directory of configuration file)
+    pub grpc_ca_cert: Option<PathBuf>,
+    /// Domain name used for SNI and for verifying TLS certificate of `https` gRPC address
+    pub grpc_tls_domain: Option<String>,
+    /// RPC address
+    pub rpc_addr: Option<String>,
+    /// Fallback gRPC addresses (in order of preference)
+    pub fallback_grpc_addrs: Vec<String>,
+    /// Fallback RPC addresses (in order of preference)
+    pub fallback_rpc_addrs: Vec<String>,
+    /// Proxy through which RPC and gRPC connections to chain are tunnelled (e.g. `socks5h://127.0.0.1:9050`)
+    pub proxy: Option<Proxy>,
+    /// Fee amount
+    pub fee_amount: Option<Decimal>,
+    /// Fee denom
+    pub fee_denom: Option<Identifier>,
+    /// Gas limit
+    pub gas_limit: Option<u64>,
+    /// Trust level (e.g. `1/3`)
+    pub trust_level: Option<String>,
+    /// Trusting period (e.g. `14 days`)
+    pub trusting_period: Option<String>,
+    /// Maximum clock drift (e.g. `3 sec`)
+    pub max_clock_drift: Option<String>,
+    /// RPC timeout duration (e.g. `60 sec`)
+    pub rpc_timeout: Option<String>,
+    /// Diversifier used in transactions for chain
+    pub diversifier: Option<String>,
+    /// Port ID used to create connection with chain
+    pub port_id: Option<PortId>,
+    /// Maximum age of latest block after which chain is considered halted (e.g. `2 min`)
+    pub halt_threshold: Option<String>,
+    /// Trusted height of the chain (latest block of chain is trusted when not provided)
+    pub trusted_height: Option<u64>,
+    /// Hex encoded block hash at trusted height of the chain (required with `trusted_height`)
+    pub trusted_hash: Option<String>,
+    /// Maximum number of attempts of queries which fail due to transient network errors (`1` disables retries)
+    pub retry_max_attempts: Option<u32>,
+    /// Backoff before first retry of a failed query (e.g. `500ms`)
+    pub retry_initial_backoff: Option<String>,
+    /// Maximum backoff between retries of a failed query (e.g. `10s`)
+    pub retry_max_backoff: Option<String>,
+    /// Fraction of backoff randomly added to or subtracted from it (between 0 and 1)
+    pub retry_jitter: Option<f64>,
+    /// gRPC status codes on which failed queries are retried (e.g. `["unavailable", "deadline-exceeded"]`)
+    pub retry_codes: Option<Vec<RetryableCode>>,
+    /// Maximum time for establishing a connection with gRPC endpoint (e.g. `10s`)
+    pub connect_timeout: Option<String>,
+    /// Maximum time for completing a single RPC or gRPC request (e.g. `30s`)
+    pub request_timeout: Option<String>,
+    /// Maximum number of RPC and gRPC requests per second made to each endpoint (not rate limited when not provided)
+    pub rate_limit: Option<f64>,
+    /// Maximum number of requests which can be made to an endpoint in a burst (defaults to one second worth of
+    /// requests)
+    pub rate_limit_burst: Option<u32>,
+    /// Revision of solo machine client types (`v1`, `v2` or `v3`, selected based on detected IBC version of chain when
+    /// not provided)
+    pub client_revision: Option<SoloMachineRevision>,
+    /// Verifies state of client, connection and channel on chain before signing messages referencing them
+    pub strict_preflight: bool,
+}
+
+impl Config {
+    /// Loads configuration from given file
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("unable to read config file {}", path.display()))?;
+
+        let mut config: Self = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&contents)
+                .with_context(|| format!("invalid config file {}", path.display()))?,
+            _ => toml::from_str(&contents)
+                .with_context(|| format!("invalid config file {}", path.display()))?,
+        };
+
+        if let Some(base) = path.parent() {
+            config.signer.resolve_paths(base);
+
+            for tenant in config.tenants.values_mut() {
+                tenant.signer.resolve_paths(base);
+            }
+
+            for chain in config.chains.iter_mut() {
+                if let Some(ref mut ca_cert) = chain.grpc_ca_cert {
+                    if ca_cert.is_relative() {
+                        *ca_cert = base.join(&*ca_cert);
+                    }
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Exports settings of configuration as environment variables read by CLI options. Variables which are already
+    /// set (in environment or `.env` file) are not overridden, so, CLI options and environment always take precedence
+    /// over configuration file.
+    pub fn apply_env(&self) {
+        let mut vars: Vec<(&str, OsString)> = Vec::new();
+
+        let mut push = |name, value: Option<OsString>| {
+            if let Some(value) = value {
+                vars.push((name, value));
+            }
+        };
+
+        push("SOLO_DB_URI", self.db_uri.clone().map(Into::into));
+        push("SOLO_SIGNER", self.signer.path.clone().map(Into::into));
+        push(
+            "SOLO_FEE_PAYER_SIGNER",
+            self.signer.fee_payer.clone().map(Into::into),
+        );
+        push(
+            "SOLO_FEE_STRATEGY_PLUGIN",
+            self.signer.fee_strategy_plugin.clone().map(Into::into),
+        );
+        push(
+            "SOLO_CLIENT_ENCODER_PLUGIN",
+            self.signer.client_encoder_plugin.clone().map(Into::into),
+        );
+        push("SOLO_KEY", self.signer.key.clone().map(Into::into));
+        push(
+            "SOLO_ACCOUNT_PREFIX",
+            self.signer.account_prefix.clone().map(Into::into),
+        );
+        push(
+            "SOLO_GRPC_ADDR",
+            self.daemon.grpc_addr.clone().map(Into::into),
+        );
+        push(
+            "SOLO_RELAY_INTERVAL",
+            self.daemon.relay_interval.clone().map(Into::into),
+        );
+        push(
+            "SOLO_RELAY_MEMO",
+            self.daemon.relay_memo.clone().map(Into::into),
+        );
+        push("SOLO_LOG_LEVEL", self.log.level.clone().map(Into::into));
+        push("SOLO_LOG_FORMAT", self.log.format.clone().map(Into::into));
+        push(
+            "SOLO_SLACK_WEBHOOK_URL",
+            self.alerts.slack_webhook_url.clone().map(Into::into),
+        );
+        push(
+            "SOLO_TELEGRAM_BOT_TOKEN",
+            self.alerts.telegram_bot_token.clone().map(Into::into),
+        );
+        push(
+            "SOLO_TELEGRAM_CHAT_ID",
+            self.alerts.telegram_chat_id.clone().map(Into::into),
+        );
+        push(
+            "SOLO_ALERT_BROADCAST_FAILURES",
+            self.alerts
+                .broadcast_failure_threshold
+                .map(|threshold| threshold.to_string().into()),
+        );
+
+        for (name, value) in self.signer.env.iter() {
+            vars.push((name, value.into()));
+        }
+
+        for (name, value) in vars {
+            if env::var_os(name).is_none() {
+                env::set_var(name, value);
+            }
+        }
+    }
+}
+
+impl SignerConfig {
+    /// Loads signer providing it these settings (environment variables of process are not read by signer), so that
+    /// signers of different tenants are loaded from their own key material
+    #[cfg(feature = "server")]
+    pub fn load_signer(&self) -> Result<Arc<dyn Signer>> {
+        let path = self
+            .path
+            .clone()
+            .ok_or_else(|| anyhow!("signer path is required"))?;
+
+        load_signer(
+            path,
+            self.fee_payer.clone(),
+            self.key.as_deref(),
+            Some(&self.settings()),
+        )
+    }
+
+    /// Loads fee strategy and client encoder plugins configured along with signer
+    #[cfg(feature = "server")]
+    pub fn load_plugins(&self) -> Result<Plugins> {
+        load_plugins(
+            self.fee_strategy_plugin.clone(),
+            self.client_encoder_plugin.clone(),
+        )
+    }
+
+    /// Returns settings read by signer (`env` along with account prefix)
+    pub fn settings(&self) -> BTreeMap<String, String> {
+        let mut settings = self.env.clone();
+
+        if let Some(ref account_prefix) = self.account_prefix {
+            settings.insert("SOLO_ACCOUNT_PREFIX".to_string(), account_prefix.clone());
+        }
+
+        settings
+    }
+
+    /// Resolves relative paths of plugins against directory of configuration file
+    fn resolve_paths(&mut self, base: &Path) {
+        let paths = vec![
+            &mut self.path,
+            &mut self.fee_payer,
+            &mut self.fee_strategy_plugin,
+            &mut self.client_encoder_plugin,
+        ];
+
+        for path in paths.into_iter().flatten() {
+            if path.is_relative() {
+                *path = base.join(&*path);
+            }
+        }
+    }
+}
+
+impl ChainEntry {
+    /// Returns chain configuration using given status of chain (latest block of chain is used as trusted block when
+    /// trusted height is not configured)
+    pub fn to_chain_config(&self, status: &StatusResponse) -> Result<ChainConfig> {
+        let (trusted_height, trusted_hash) = match (self.trusted_height, &self.trusted_hash) {
+            (Some(height), Some(hash)) => {
+                let mut trusted_hash = [0; 32];
+                hex::decode_to_slice(hash, &mut trusted_hash)
+                    .context("invalid trusted hash: expected 32 bytes hex encoded hash")?;
+
+                (BlockHeight::from(height as u32), trusted_hash)
+            }
+            (None, None) => match status.sync_info.latest_block_hash {
+                Hash::Sha256(hash) => (status.sync_info.latest_block_height, hash),
+                Hash::None => return Err(anyhow!("chain has not produced any block")),
+            },
+            _ => {
+                return Err(anyhow!(
+                    "`trusted_height` and `trusted_hash` should be provided together"
+                ))
+            }
+        };
+
+        let trust_level: Ratio<u64> = self
+            .trust_level
+            .as_deref()
+            .unwrap_or("1/3")
+            .parse()
+            .context("invalid trust level")?;
+
+        Ok(ChainConfig {
+            grpc_addr: self
+                .grpc_addr
+                .clone()
+                .unwrap_or_else(|| "http://0.0.0.0:9090".to_string()),
+            grpc_tls: GrpcTlsConfig {
+                ca_cert: self.grpc_ca_cert.clone(),
+                domain_name: self.grpc_tls_domain.clone(),
+            },
+            rpc_addr: self.rpc_addr(),
+            fallback_grpc_addrs: self.fallback_grpc_addrs.clone(),
+            fallback_rpc_addrs: self.fallback_rpc_addrs.clone(),
+            proxy: self.proxy.clone(),
+            fee: Fee {
+                amount: self.fee_amount.unwrap_or_else(|| Decimal::from(1000)),
+                denom: match self.fee_denom {
+                    Some(ref denom) => denom.clone(),
+                    None => "stake".parse()?,
+                },
+                gas_limit: self.gas_limit.unwrap_or(300000),
+                granter: None,
+            },
+            trust_level,
+            trusting_period: parse_duration(&self.trusting_period, "14 days")?,
+            max_clock_drift: parse_duration(&self.max_clock_drift, "3 sec")?,
+            rpc_timeout: parse_duration(&self.rpc_timeout, "60 sec")?,
+            diversifier: self
+                .diversifier
+                .clone()
+                .unwrap_or_else(|| "solo-machine-diversifier".to_string()),
+            port_id: match self.port_id {
+                Some(ref port_id) => port_id.clone(),
+                None => "transfer".parse()?,
+            },
+            trusted_height,
+            trusted_hash,
+            name_resolver: None,
+            address_display: Default::default(),
+            address_format: Default::default(),
+            sign_mode: Default::default(),
+            address_algo: Default::default(),
+            halt_threshold: parse_duration(&self.halt_threshold, "2 min")?,
+            fee_strategy: Default::default(),
+            client_encoding: Default::default(),
+            inclusion_policy: Default::default(),
+            retry_policy: self.retry_policy()?,
+            timeouts: self.timeouts()?,
+            rate_limit: self
+                .rate_limit
+                .map(|rps| RateLimit::new(rps, self.rate_limit_burst)),
+            timestamp_sync_offset: None,
+            ibc_version: None,
+            client_revision: self.client_revision,
+            strict_preflight: self.strict_preflight,
+        })
+    }
+
+    /// Returns network timeouts of chain
+    pub fn timeouts(&self) -> Result<NetworkTimeouts> {
+        Ok(NetworkTimeouts {
+            connect: parse_duration(&self.connect_timeout, "10s")?,
+            request: parse_duration(&self.request_timeout, "30s")?,
+        })
+    }
+
+    /// Returns retry policy of chain (unspecified values default to the ones of default retry policy)
+    pub fn retry_policy(&self) -> Result<RetryPolicy> {
+        let default = RetryPolicy::default();
+
+        Ok(RetryPolicy {
+            max_attempts: self.retry_max_attempts.unwrap_or(default.max_attempts),
+            initial_backoff: parse_duration(&self.retry_initial_backoff, "500ms")?,
+            max_backoff: parse_duration(&self.retry_max_backoff, "10s")?,
+            jitter: self.retry_jitter.unwrap_or(default.jitter),
+            retryable_codes: self.retry_codes.clone().unwrap_or(default.retryable_codes),
+        })
+    }
+
+    /// Returns RPC address of chain
+    pub fn rpc_addr(&self) -> String {
+        self.rpc_addr
+            .clone()
+            .unwrap_or_else(|| "http://0.0.0.0:26657".to_string())
+    }
+
+    /// Fetches status of chain and verifies that it matches expected chain ID
+    pub async fn status(&self) -> Result<StatusResponse> {
+        let rpc_addr = self.rpc_addr();
+        let status = TimeoutRpcClient::new(&rpc_addr, &self.timeouts()?, self.proxy.as_ref())
+            .with_context(|| format!("unable to connect to rpc client at {}", rpc_addr))?
+            .status()
+            .await
+            .with_context(|| format!("unable to query status of chain at {}", rpc_addr))?;
+
+        if let Some(ref id) = self.id {
+            ensure!(
+                status.node_info.network.as_str() == id,
+                "chain at {} has id `{}` (expected `{}`)",
+                rpc_addr,
+                status.node_info.network,
+                id
+            );
+        }
+
+        Ok(status)
+    }
+}
+
+/// Returns path of configuration file provided using `--config` option or `SOLO_CONFIG` environment variable. This
+/// is resolved before parsing CLI options so that settings in configuration file can be used as their defaults.
+pub fn config_path() -> Option<PathBuf> {
+    let mut args = env::args_os().skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        }
+
+        if let Some(path) = arg.to_str().and_then(|arg| arg.strip_prefix("--config=")) {
+            return Some(PathBuf::from(path));
+        }
+    }
+
+    env::var_os(CONFIG_ENV).map(PathBuf::from)
+}
+
+fn parse_duration(value: &Option<String>, default: &str) -> Result<Duration> {
+    let value = value.as_deref().unwrap_or(default);
+    humantime::parse_duration(value).with_context(|| format!("invalid duration: {}", value))
+}
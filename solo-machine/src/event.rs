@@ -1,18 +1,24 @@
+pub mod alert_notifier;
+pub mod audit_logger;
 pub mod cli_event_handler;
-pub mod env_logger;
+#[cfg(feature = "server")]
+pub mod event_logger;
+pub mod stats_recorder;
+pub mod webhook_notifier;
 
-use std::{convert::TryFrom, ffi::OsStr, path::PathBuf};
+use std::{convert::TryFrom, ffi::OsStr, path::PathBuf, sync::Arc};
 
 use anyhow::{Context, Error, Result};
 use async_trait::async_trait;
 use libloading::{Library, Symbol};
 use solo_machine_core::{
     event::{EventHandler, HandlerRegistrar as IHandlerRegistrar},
+    supervisor::{RestartPolicy, TaskSupervisor},
     Event,
 };
-use tokio::{
-    sync::mpsc::{unbounded_channel, UnboundedSender},
-    task::JoinHandle,
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedSender},
+    Mutex,
 };
 
 #[derive(Default)]
@@ -21,18 +27,41 @@ pub struct HandlerRegistrar {
 }
 
 impl HandlerRegistrar {
-    pub fn spawn(self) -> (UnboundedSender<Event>, JoinHandle<Result<()>>) {
-        let (sender, mut receiver) = unbounded_channel();
-
-        let handle = tokio::spawn(async move {
-            while let Some(event) = receiver.recv().await {
-                self.handle(event).await?;
+    /// Spawns a supervised task which handles events sent on returned sender. The task completes when all the
+    /// senders are dropped (or when the supervisor is cancelled, after handling already received events).
+    pub fn spawn(
+        self,
+        supervisor: &mut TaskSupervisor,
+        policy: RestartPolicy,
+    ) -> UnboundedSender<Event> {
+        let (sender, receiver) = unbounded_channel();
+
+        let registrar = Arc::new(self);
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        supervisor.spawn("event-hook-registrar", policy, move |token| {
+            let registrar = registrar.clone();
+            let receiver = receiver.clone();
+
+            async move {
+                let mut receiver = receiver.lock().await;
+
+                loop {
+                    let event = tokio::select! {
+                        biased;
+                        event = receiver.recv() => event,
+                        _ = token.cancelled() => None,
+                    };
+
+                    match event {
+                        Some(event) => registrar.handle(event).await?,
+                        None => return Ok(()),
+                    }
+                }
             }
-
-            Ok(())
         });
 
-        (sender, handle)
+        sender
     }
 
     // TODO: remove conditional compilation when this issue is fixed:
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use solo_machine_core::{alert::Alerter, event::EventHandler, Event};
+
+/// Posts alerts on critical conditions to Slack and Telegram (failed posts are logged instead of stopping event
+/// handling)
+pub struct AlertNotifier {
+    alerter: Arc<Alerter>,
+}
+
+impl AlertNotifier {
+    pub fn new(alerter: Arc<Alerter>) -> Self {
+        Self { alerter }
+    }
+}
+
+#[async_trait]
+impl EventHandler for AlertNotifier {
+    async fn handle(&self, event: Event) -> Result<()> {
+        if self.alerter.is_empty() {
+            return Ok(());
+        }
+
+        if let Err(err) = self.alerter.alert(&event).await {
+            tracing::error!("{:#}", err);
+        }
+
+        Ok(())
+    }
+}
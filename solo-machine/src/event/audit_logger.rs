@@ -0,0 +1,23 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use solo_machine_core::{event::EventHandler, service::AuditService, DbPool, Event};
+
+/// Records all the events in hash-chained audit log
+pub struct AuditLogger {
+    audit_service: AuditService,
+}
+
+impl AuditLogger {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self {
+            audit_service: AuditService::new(db_pool),
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler for AuditLogger {
+    async fn handle(&self, event: Event) -> Result<()> {
+        self.audit_service.log(&event).await
+    }
+}
@@ -8,19 +8,29 @@ use cli_table::{
 use solo_machine_core::{event::EventHandler, Event};
 use termcolor::{ColorSpec, StandardStream, WriteColor};
 
+use crate::output::OutputFormat;
+
 pub struct CliEventHandler {
     color_choice: ColorChoice,
+    output: OutputFormat,
 }
 
 impl CliEventHandler {
-    pub fn new(color_choice: ColorChoice) -> Self {
-        Self { color_choice }
+    pub fn new(color_choice: ColorChoice, output: OutputFormat) -> Self {
+        Self {
+            color_choice,
+            output,
+        }
     }
 }
 
 #[async_trait]
 impl EventHandler for CliEventHandler {
     async fn handle(&self, event: Event) -> Result<()> {
+        if !self.output.is_text() {
+            return self.output.print_record(&event);
+        }
+
         let mut stdout = StandardStream::stdout(self.color_choice);
 
         match event {
@@ -46,6 +56,195 @@ impl EventHandler for CliEventHandler {
 
                 print_stdout(table).context("unable to print table to stdout")?;
             }
+            Event::ChainImported { chain_id } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!("Imported state of chain {}", chain_id),
+                )?;
+            }
+            Event::ChainStateReset {
+                chain_id,
+                trusted_height,
+                trusted_hash,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "State of chain {} reset (trusted height: {}, trusted hash: {}), connection can be established again",
+                        chain_id, trusted_height, trusted_hash
+                    ),
+                )?;
+            }
+            Event::FeeAllowanceGranted {
+                chain_id,
+                granter,
+                grantee,
+                transaction_hash,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    "Fee allowance granted!",
+                )?;
+                writeln!(stdout)?;
+
+                let mut table = Vec::new();
+
+                add_row(&mut table, "Chain ID", chain_id);
+                add_row(&mut table, "Granter", granter);
+                add_row(&mut table, "Grantee", grantee);
+                add_row(&mut table, "Transaction Hash", transaction_hash);
+
+                print_stdout(table.table().color_choice(self.color_choice))
+                    .context("unable to print table to stdout")?;
+            }
+            Event::FeeAllowanceRevoked {
+                chain_id,
+                granter,
+                grantee,
+                transaction_hash,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    "Fee allowance revoked!",
+                )?;
+                writeln!(stdout)?;
+
+                let mut table = Vec::new();
+
+                add_row(&mut table, "Chain ID", chain_id);
+                add_row(&mut table, "Granter", granter);
+                add_row(&mut table, "Grantee", grantee);
+                add_row(&mut table, "Transaction Hash", transaction_hash);
+
+                print_stdout(table.table().color_choice(self.color_choice))
+                    .context("unable to print table to stdout")?;
+            }
+            Event::TokensSent {
+                chain_id,
+                from_address,
+                to_address,
+                amount,
+                denom,
+                transaction_hash,
+            } => {
+                print_stream(&mut stdout, ColorSpec::new().set_bold(true), "Tokens sent!")?;
+                writeln!(stdout)?;
+
+                let mut table = Vec::new();
+
+                add_row(&mut table, "Chain ID", chain_id);
+                add_row(&mut table, "From", from_address);
+                add_row(&mut table, "To", to_address);
+                add_row(&mut table, "Amount", amount);
+                add_row(&mut table, "Denom", denom);
+                add_row(&mut table, "Transaction Hash", transaction_hash);
+
+                print_stdout(table.table().color_choice(self.color_choice))
+                    .context("unable to print table to stdout")?;
+            }
+            Event::LabelAssigned {
+                chain_id,
+                kind,
+                target,
+                label,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    "Label assigned!",
+                )?;
+                writeln!(stdout)?;
+
+                let mut table = Vec::new();
+
+                add_row(&mut table, "Chain ID", chain_id);
+                add_row(&mut table, "Kind", kind);
+                add_row(&mut table, "Target", target);
+                add_row(&mut table, "Label", label);
+
+                print_stdout(table.table().color_choice(self.color_choice))
+                    .context("unable to print table to stdout")?;
+            }
+            Event::LabelRemoved {
+                chain_id,
+                kind,
+                target,
+                label,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    "Label removed!",
+                )?;
+                writeln!(stdout)?;
+
+                let mut table = Vec::new();
+
+                add_row(&mut table, "Chain ID", chain_id);
+                add_row(&mut table, "Kind", kind);
+                add_row(&mut table, "Target", target);
+                add_row(&mut table, "Label", label);
+
+                print_stdout(table.table().color_choice(self.color_choice))
+                    .context("unable to print table to stdout")?;
+            }
+            Event::MacroStepCompleted {
+                run_id,
+                name,
+                step,
+                output,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_fg(Some(Color::Green)),
+                    format!(
+                        "Step {} of macro `{}` completed: {} [Run ID = {}]",
+                        step, name, output, run_id
+                    ),
+                )?;
+            }
+            Event::MacroStepRetried {
+                run_id,
+                name,
+                step,
+                attempt,
+                error,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_fg(Some(Color::Yellow)),
+                    format!(
+                        "Step {} of macro `{}` failed, retrying (attempt {}): {} [Run ID = {}]",
+                        step, name, attempt, error, run_id
+                    ),
+                )?;
+            }
+            Event::MacroRunCompleted { run_id, name } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!("Macro `{}` completed! [Run ID = {}]", name, run_id),
+                )?;
+            }
+            Event::MacroRunFailed {
+                run_id,
+                name,
+                step,
+                error,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)),
+                    format!(
+                        "Step {} of macro `{}` failed: {} [Run ID = {}]",
+                        step, name, error, run_id
+                    ),
+                )?;
+            }
             Event::TokensMinted {
                 chain_id,
                 request_id,
@@ -123,6 +322,174 @@ impl EventHandler for CliEventHandler {
                 print_stdout(table.table().color_choice(self.color_choice))
                     .context("unable to print table to stdout")?;
             }
+            Event::PacketAcknowledged {
+                chain_id,
+                packet_sequence,
+                transaction_hash,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Acknowledged packet {} of chain {} [Transaction Hash = {}]",
+                        packet_sequence, chain_id, transaction_hash
+                    ),
+                )?;
+            }
+            Event::PacketTimedOut {
+                chain_id,
+                packet_sequence,
+                transaction_hash,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Timed out packet {} of chain {} [Transaction Hash = {}]",
+                        packet_sequence, chain_id, transaction_hash
+                    ),
+                )?;
+            }
+            Event::PendingOperationResolved {
+                chain_id,
+                operation_id,
+                kind,
+                status,
+                transaction_hash,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Resolved pending {} operation {} of chain {} as {} [Transaction Hash = {}]",
+                        kind, operation_id, chain_id, status, transaction_hash
+                    ),
+                )?;
+            }
+            Event::BroadcastFailed {
+                chain_id,
+                operation_id,
+                kind,
+                error,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)),
+                    format!(
+                        "Broadcast of {} operation {} failed on chain {}: {}",
+                        kind, operation_id, chain_id, error
+                    ),
+                )?;
+            }
+            Event::ClientFrozen {
+                chain_id,
+                client_id,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)),
+                    format!(
+                        "Solo machine client{} on chain {} is frozen or expired",
+                        client_id
+                            .as_ref()
+                            .map(|client_id| format!(" {}", client_id))
+                            .unwrap_or_default(),
+                        chain_id
+                    ),
+                )?;
+            }
+            Event::SequenceDiverged {
+                chain_id,
+                client_id,
+                local,
+                on_chain,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)),
+                    format!(
+                        "Local sequence ({}) of chain {} diverged from sequence ({}) of solo machine client {} on chain",
+                        local, chain_id, on_chain, client_id
+                    ),
+                )?;
+            }
+            Event::SpendingLimitExceeded {
+                chain_id,
+                denom,
+                amount,
+                spent,
+                limit,
+                window,
+                approved,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Yellow)),
+                    format!(
+                        "Transfer of {} {} on chain {} exceeds spending limit of {} {} per {} ({} {} already transferred){}",
+                        amount,
+                        denom,
+                        chain_id,
+                        limit,
+                        denom,
+                        window,
+                        spent,
+                        denom,
+                        if approved { ", approved by escalation" } else { "" }
+                    ),
+                )?;
+            }
+            Event::ClientNearingExpiry {
+                chain_id,
+                client_id,
+                expires_at,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Yellow)),
+                    format!(
+                        "Tendermint client {} of chain {} expires at {} unless it is refreshed",
+                        client_id, chain_id, expires_at
+                    ),
+                )?;
+            }
+            Event::TendermintClientRefreshed {
+                chain_id,
+                client_id,
+                height,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Refreshed tendermint client {} of chain {} at height {}",
+                        client_id, chain_id, height
+                    ),
+                )?;
+            }
+            Event::ConsensusTimestampSynced {
+                chain_id,
+                consensus_timestamp,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!(
+                        "Synced consensus timestamp of solo machine for chain {} to {}",
+                        chain_id, consensus_timestamp
+                    ),
+                )?;
+            }
+            Event::ConnectionHandshakeResumed { chain_id, progress } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Yellow)),
+                    format!(
+                        "Resuming interrupted connection handshake with chain {} [{}]",
+                        chain_id, progress
+                    ),
+                )?;
+            }
             Event::CreatedSoloMachineClient { client_id } => {
                 print_stream(
                     &mut stdout,
@@ -271,6 +638,40 @@ impl EventHandler for CliEventHandler {
                 print_stdout(table.table().color_choice(self.color_choice))
                     .context("unable to print table to stdout")?;
             }
+            Event::ChainHalted {
+                chain_id,
+                latest_height,
+                latest_block_time,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Yellow)),
+                    format!(
+                        "Chain {} halted at height {} (latest block time: {}), waiting for block production to resume",
+                        chain_id, latest_height, latest_block_time
+                    ),
+                )?;
+            }
+            Event::ChainResumed {
+                chain_id,
+                latest_height,
+            } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true),
+                    format!("Chain {} resumed at height {}", chain_id, latest_height),
+                )?;
+            }
+            Event::AuditLogBreakAccepted { entry_id, reason } => {
+                print_stream(
+                    &mut stdout,
+                    ColorSpec::new().set_bold(true).set_fg(Some(Color::Yellow)),
+                    format!(
+                        "Accepted broken audit log at entry {} ({})",
+                        entry_id, reason
+                    ),
+                )?;
+            }
             Event::Warning { message } => {
                 print_stream(
                     &mut stdout,
@@ -0,0 +1,369 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use solo_machine_core::{event::EventHandler, Event};
+
+/// Logs events as structured log records (with chain ID, identifiers, amounts and transaction hashes as fields)
+pub struct EventLogger {}
+
+impl EventLogger {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl EventHandler for EventLogger {
+    async fn handle(&self, event: Event) -> Result<()> {
+        match event {
+            Event::TokensMinted {
+                chain_id,
+                request_id,
+                to_address,
+                amount,
+                denom,
+                transaction_hash,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                request_id = ?request_id,
+                address = %to_address,
+                amount = %amount,
+                denom = %denom,
+                transaction_hash = %transaction_hash,
+                "minted new tokens"
+            ),
+            Event::TokensBurnt {
+                chain_id,
+                request_id,
+                from_address,
+                amount,
+                denom,
+                transaction_hash,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                request_id = ?request_id,
+                address = %from_address,
+                amount = %amount,
+                denom = %denom,
+                transaction_hash = %transaction_hash,
+                "burnt tokens"
+            ),
+            Event::SignerUpdated {
+                chain_id,
+                old_public_key: _,
+                new_public_key: _,
+            } => tracing::info!(chain_id = %chain_id, "updated signer's public key"),
+            Event::PacketAcknowledged {
+                chain_id,
+                packet_sequence,
+                transaction_hash,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                packet_sequence,
+                transaction_hash = %transaction_hash,
+                "acknowledged packet"
+            ),
+            Event::PacketTimedOut {
+                chain_id,
+                packet_sequence,
+                transaction_hash,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                packet_sequence,
+                transaction_hash = %transaction_hash,
+                "timed out packet"
+            ),
+            Event::PendingOperationResolved {
+                chain_id,
+                operation_id,
+                kind,
+                status,
+                transaction_hash,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                operation_id,
+                operation = %kind,
+                status = %status,
+                transaction_hash = %transaction_hash,
+                "resolved pending operation"
+            ),
+            Event::BroadcastFailed {
+                chain_id,
+                operation_id,
+                kind,
+                error,
+            } => tracing::error!(
+                chain_id = %chain_id,
+                operation_id,
+                operation = %kind,
+                error = %error,
+                "broadcast failed"
+            ),
+            Event::ClientFrozen {
+                chain_id,
+                client_id,
+            } => tracing::error!(
+                chain_id = %chain_id,
+                client_id = ?client_id.as_ref().map(ToString::to_string),
+                "solo machine client on chain is frozen or expired"
+            ),
+            Event::SequenceDiverged {
+                chain_id,
+                client_id,
+                local,
+                on_chain,
+            } => tracing::error!(
+                chain_id = %chain_id,
+                client_id = %client_id,
+                local,
+                on_chain,
+                "local sequence diverged from sequence of solo machine client on chain"
+            ),
+            Event::SpendingLimitExceeded {
+                chain_id,
+                denom,
+                amount,
+                spent,
+                limit,
+                window,
+                approved,
+            } => tracing::warn!(
+                chain_id = %chain_id,
+                denom = %denom,
+                amount,
+                spent,
+                limit,
+                window = %window,
+                approved,
+                "transfer exceeds spending limit of signing policy"
+            ),
+            Event::ClientNearingExpiry {
+                chain_id,
+                client_id,
+                expires_at,
+            } => tracing::warn!(
+                chain_id = %chain_id,
+                client_id = %client_id,
+                expires_at = %expires_at,
+                "tendermint client on solo machine is nearing expiry"
+            ),
+            Event::TendermintClientRefreshed {
+                chain_id,
+                client_id,
+                height,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                client_id = %client_id,
+                height = %height,
+                "refreshed tendermint client on solo machine"
+            ),
+            Event::ConsensusTimestampSynced {
+                chain_id,
+                consensus_timestamp,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                consensus_timestamp = %consensus_timestamp,
+                "synced consensus timestamp of solo machine"
+            ),
+            Event::ConnectionHandshakeResumed { chain_id, progress } => tracing::warn!(
+                chain_id = %chain_id,
+                progress = %progress,
+                "resuming interrupted connection handshake"
+            ),
+            Event::CreatedSoloMachineClient { client_id } => tracing::info!(
+                client_id = %client_id,
+                "created solo machine client on IBC enabled chain"
+            ),
+            Event::CreatedTendermintClient { client_id } => tracing::info!(
+                client_id = %client_id,
+                "created tendermint client on solo machine"
+            ),
+            Event::InitializedConnectionOnTendermint { connection_id } => tracing::info!(
+                connection_id = %connection_id,
+                "initialized connection on IBC enabled chain"
+            ),
+            Event::InitializedConnectionOnSoloMachine { connection_id } => tracing::info!(
+                connection_id = %connection_id,
+                "initialized connection on solo machine"
+            ),
+            Event::ConfirmedConnectionOnTendermint { connection_id } => tracing::info!(
+                connection_id = %connection_id,
+                "confirmed connection on IBC enabled chain"
+            ),
+            Event::ConfirmedConnectionOnSoloMachine { connection_id } => tracing::info!(
+                connection_id = %connection_id,
+                "confirmed connection on solo machine"
+            ),
+            Event::InitializedChannelOnTendermint { channel_id } => tracing::info!(
+                channel_id = %channel_id,
+                "initialized channel on IBC enabled chain"
+            ),
+            Event::InitializedChannelOnSoloMachine { channel_id } => tracing::info!(
+                channel_id = %channel_id,
+                "initialized channel on solo machine"
+            ),
+            Event::ConfirmedChannelOnTendermint { channel_id } => tracing::info!(
+                channel_id = %channel_id,
+                "confirmed channel on IBC enabled chain"
+            ),
+            Event::ConfirmedChannelOnSoloMachine { channel_id } => tracing::info!(
+                channel_id = %channel_id,
+                "confirmed channel on solo machine"
+            ),
+            Event::ConnectionEstablished {
+                chain_id,
+                connection_details,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                details = %serde_json::to_string(&connection_details)?,
+                "connection successfully established"
+            ),
+            Event::ChainAdded { chain_id } => {
+                tracing::info!(chain_id = %chain_id, "added new chain")
+            }
+            Event::ChainImported { chain_id } => {
+                tracing::info!(chain_id = %chain_id, "imported state of chain")
+            }
+            Event::ChainStateReset {
+                chain_id,
+                trusted_height,
+                trusted_hash,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                trusted_height = %trusted_height,
+                trusted_hash = %trusted_hash,
+                "reset state of chain"
+            ),
+            Event::FeeAllowanceGranted {
+                chain_id,
+                granter,
+                grantee,
+                transaction_hash,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                granter = %granter,
+                grantee = %grantee,
+                transaction_hash = %transaction_hash,
+                "granted fee allowance"
+            ),
+            Event::FeeAllowanceRevoked {
+                chain_id,
+                granter,
+                grantee,
+                transaction_hash,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                granter = %granter,
+                grantee = %grantee,
+                transaction_hash = %transaction_hash,
+                "revoked fee allowance"
+            ),
+            Event::TokensSent {
+                chain_id,
+                from_address,
+                to_address,
+                amount,
+                denom,
+                transaction_hash,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                from = %from_address,
+                to = %to_address,
+                amount = %amount,
+                denom = %denom,
+                transaction_hash = %transaction_hash,
+                "sent tokens"
+            ),
+            Event::LabelAssigned {
+                chain_id,
+                kind,
+                target,
+                label,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                kind = %kind,
+                target = %target,
+                label = %label,
+                "assigned label"
+            ),
+            Event::LabelRemoved {
+                chain_id,
+                kind,
+                target,
+                label,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                kind = %kind,
+                target = %target,
+                label = %label,
+                "removed label"
+            ),
+            Event::MacroStepCompleted {
+                run_id,
+                name,
+                step,
+                output,
+            } => tracing::info!(
+                run_id = %run_id,
+                name = %name,
+                step = %step,
+                output = %output,
+                "completed macro step"
+            ),
+            Event::MacroStepRetried {
+                run_id,
+                name,
+                step,
+                attempt,
+                error,
+            } => tracing::warn!(
+                run_id = %run_id,
+                name = %name,
+                step = %step,
+                attempt = %attempt,
+                error = %error,
+                "retrying failed macro step"
+            ),
+            Event::MacroRunCompleted { run_id, name } => {
+                tracing::info!(run_id = %run_id, name = %name, "completed macro run")
+            }
+            Event::MacroRunFailed {
+                run_id,
+                name,
+                step,
+                error,
+            } => tracing::error!(
+                run_id = %run_id,
+                name = %name,
+                step = %step,
+                error = %error,
+                "macro run failed"
+            ),
+            Event::ChainHalted {
+                chain_id,
+                latest_height,
+                latest_block_time,
+            } => tracing::warn!(
+                chain_id = %chain_id,
+                latest_height = %latest_height,
+                latest_block_time = %latest_block_time,
+                "chain halted, pausing operations"
+            ),
+            Event::ChainResumed {
+                chain_id,
+                latest_height,
+            } => tracing::info!(
+                chain_id = %chain_id,
+                latest_height = %latest_height,
+                "chain resumed, resuming operations"
+            ),
+            Event::AuditLogBreakAccepted { entry_id, reason } => tracing::warn!(
+                entry_id = %entry_id,
+                reason = %reason,
+                "accepted broken audit log"
+            ),
+            Event::Warning { message } => tracing::warn!("{}", message),
+        }
+
+        Ok(())
+    }
+}
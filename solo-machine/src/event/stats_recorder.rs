@@ -0,0 +1,23 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use solo_machine_core::{event::EventHandler, service::StatsService, DbPool, Event};
+
+/// Records successful operations in persistent daily stats of chains
+pub struct StatsRecorder {
+    stats_service: StatsService,
+}
+
+impl StatsRecorder {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self {
+            stats_service: StatsService::new(db_pool),
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler for StatsRecorder {
+    async fn handle(&self, event: Event) -> Result<()> {
+        self.stats_service.record(&event).await
+    }
+}
@@ -0,0 +1,27 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use solo_machine_core::{event::EventHandler, webhook::Webhook, Event};
+
+/// Posts events to configured webhooks (failed deliveries are logged instead of stopping event handling)
+pub struct WebhookNotifier {
+    webhooks: Vec<Webhook>,
+}
+
+impl WebhookNotifier {
+    pub fn new(webhooks: Vec<Webhook>) -> Self {
+        Self { webhooks }
+    }
+}
+
+#[async_trait]
+impl EventHandler for WebhookNotifier {
+    async fn handle(&self, event: Event) -> Result<()> {
+        for webhook in self.webhooks.iter() {
+            if let Err(err) = webhook.deliver(&event).await {
+                tracing::error!(webhook = %webhook.url(), "{:#}", err);
+            }
+        }
+
+        Ok(())
+    }
+}
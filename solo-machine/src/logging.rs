@@ -0,0 +1,101 @@
+//! Structured logging of solo machine (as human readable text or JSON lines written to stderr)
+use std::{
+    cmp, env,
+    io::{self, IsTerminal},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context, Error, Result};
+use tracing_subscriber::{
+    filter::{LevelFilter, Targets},
+    fmt,
+    layer::SubscriberExt,
+    util::SubscriberInitExt,
+    Layer, Registry,
+};
+
+/// Environment variable containing log filter directives (e.g. `solo_machine_core=debug,sqlx=info`) which override
+/// `--log-level`
+const FILTER_ENV: &str = "RUST_LOG";
+
+/// Targets whose records are filtered using `--log-level` (records of dependencies are never more verbose than
+/// `warn`)
+const TARGETS: [&str; 2] = ["solo_machine", "solo_machine_core"];
+
+/// Possible values of `--log-format`
+pub const LOG_FORMAT_VARIANTS: [&str; 2] = ["text", "json"];
+
+/// Format of log records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human readable text
+    Text,
+    /// JSON object per line (with fields of record and current span)
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(anyhow!("invalid log format: {}", s)),
+        }
+    }
+}
+
+/// Installs a global tracing subscriber which writes log records to stderr (and exports spans to OTLP collector
+/// at given endpoint, if any)
+pub fn init(
+    level: LevelFilter,
+    format: LogFormat,
+    no_style: bool,
+    #[cfg(feature = "otlp")] otlp_endpoint: Option<&str>,
+) -> Result<()> {
+    let targets = match env::var(FILTER_ENV) {
+        Ok(directives) if !directives.is_empty() => directives
+            .parse::<Targets>()
+            .with_context(|| format!("invalid `{}` directives: {}", FILTER_ENV, directives))?,
+        _ => Targets::new()
+            .with_default(cmp::min(level, LevelFilter::WARN))
+            .with_targets(TARGETS.iter().map(|target| (*target, level))),
+    };
+
+    let text_layer = match format {
+        LogFormat::Text => Some(
+            fmt::layer()
+                .with_writer(io::stderr)
+                .with_ansi(!no_style && io::stderr().is_terminal())
+                .with_filter(targets.clone()),
+        ),
+        LogFormat::Json => None,
+    };
+
+    let json_layer = match format {
+        LogFormat::Text => None,
+        LogFormat::Json => Some(
+            fmt::layer()
+                .json()
+                .with_current_span(true)
+                .with_span_list(false)
+                .with_writer(io::stderr)
+                .with_filter(targets),
+        ),
+    };
+
+    let subscriber = Registry::default().with(text_layer).with(json_layer);
+
+    #[cfg(feature = "otlp")]
+    let subscriber = subscriber.with(
+        otlp_endpoint
+            .map(crate::telemetry::tracer)
+            .transpose()?
+            .map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer)),
+    );
+
+    subscriber
+        .try_init()
+        .context("unable to install tracing subscriber")
+}
@@ -1,14 +1,32 @@
 use anyhow::Result;
 use command::Command;
+use config::{config_path, Config, CONFIG_ENV};
 use structopt::StructOpt;
 
 mod command;
+mod config;
 mod event;
+mod logging;
+mod output;
+#[cfg(feature = "server")]
 mod server;
 mod signer;
+#[cfg(feature = "otlp")]
+mod telemetry;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let _ = dotenv::dotenv();
-    Command::from_args().execute().await
+
+    if let Some(path) = config_path() {
+        Config::load(&path)?.apply_env();
+        std::env::set_var(CONFIG_ENV, path);
+    }
+
+    let result = Command::from_args().execute().await;
+
+    #[cfg(feature = "otlp")]
+    telemetry::shutdown().await;
+
+    result
 }
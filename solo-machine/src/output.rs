@@ -0,0 +1,263 @@
+//! Output of CLI commands (human readable tables and messages, or JSON/YAML which can be parsed by scripts)
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Context, Error, Result};
+use cli_table::{
+    format::Justify, print_stdout, Cell, CellStruct, Color, ColorChoice, Row, Style, Table,
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use termcolor::StandardStream;
+
+/// Possible values of `--output`
+pub const OUTPUT_FORMAT_VARIANTS: [&str; 3] = ["text", "json", "yaml"];
+
+/// Format of output of commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable tables and messages
+    Text,
+    /// JSON (tables are printed as objects and arrays of objects with snake case keys)
+    Json,
+    /// YAML (tables are printed as mappings and sequences of mappings with snake case keys)
+    Yaml,
+}
+
+impl FromStr for OutputFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "yaml" => Ok(Self::Yaml),
+            _ => Err(anyhow!("invalid output format: {}", s)),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Returns `true` if output is human readable text
+    pub fn is_text(self) -> bool {
+        matches!(self, Self::Text)
+    }
+
+    /// Returns the stream to which human readable messages are written (stderr for JSON/YAML output so that stdout
+    /// only contains machine readable output)
+    pub fn stream(self, color_choice: ColorChoice) -> StandardStream {
+        match self {
+            Self::Text => StandardStream::stdout(color_choice),
+            Self::Json | Self::Yaml => StandardStream::stderr(color_choice),
+        }
+    }
+
+    /// Prints structured data to stdout (as pretty JSON for `text` and `json` output, and as YAML for `yaml` output)
+    pub fn print(self, value: &impl Serialize) -> Result<()> {
+        match self {
+            Self::Text | Self::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(value).context("unable to serialize output")?
+            ),
+            Self::Yaml => print!(
+                "{}",
+                serde_yaml::to_string(value).context("unable to serialize output")?
+            ),
+        }
+
+        Ok(())
+    }
+
+    /// Prints a record of a stream (e.g. an event) to stdout (as a single line of JSON for `text` and `json` output,
+    /// and as a YAML document for `yaml` output)
+    pub fn print_record(self, value: &impl Serialize) -> Result<()> {
+        match self {
+            Self::Text | Self::Json => println!(
+                "{}",
+                serde_json::to_string(value).context("unable to serialize output")?
+            ),
+            Self::Yaml => print!(
+                "{}",
+                serde_yaml::to_string(value).context("unable to serialize output")?
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+/// Value of a table cell along with its styling (which is only applied to `text` output)
+#[derive(Debug, Clone)]
+pub struct Field {
+    value: String,
+    justify: Justify,
+    color: Option<Color>,
+    bold: bool,
+}
+
+impl Field {
+    /// Justifies value in table
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// Sets foreground color of value in table
+    pub fn foreground_color(mut self, color: Option<Color>) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Prints value in bold in table
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.bold = bold;
+        self
+    }
+
+    fn to_cell(&self) -> CellStruct {
+        self.value
+            .as_str()
+            .cell()
+            .justify(self.justify)
+            .foreground_color(self.color)
+            .bold(self.bold)
+    }
+
+    /// Missing values (rendered as `-` in tables) are `null`
+    fn to_json(&self) -> Value {
+        if self.value == "-" {
+            Value::Null
+        } else {
+            Value::String(self.value.clone())
+        }
+    }
+}
+
+/// Conversion of values into table fields
+pub trait ToField {
+    /// Converts value into a table field
+    fn field(self) -> Field;
+}
+
+impl<T: Display> ToField for T {
+    fn field(self) -> Field {
+        Field {
+            value: self.to_string(),
+            justify: Justify::Left,
+            color: None,
+            bold: false,
+        }
+    }
+}
+
+/// Key-value table (e.g. details of a chain) printed as a two column table for `text` output, or as an object with
+/// snake case keys derived from titles of rows otherwise
+#[derive(Debug, Default)]
+pub struct KeyValueTable {
+    rows: Vec<(String, Field)>,
+}
+
+impl KeyValueTable {
+    /// Adds a row to table
+    pub fn add(&mut self, title: &str, value: impl Display) {
+        self.rows.push((title.to_owned(), value.field()));
+    }
+
+    /// Adds a row with a styled value to table
+    pub fn add_field(&mut self, title: &str, field: Field) {
+        self.rows.push((title.to_owned(), field));
+    }
+
+    /// Prints table to stdout in given output format
+    pub fn print(&self, output: OutputFormat, color_choice: ColorChoice) -> Result<()> {
+        if !output.is_text() {
+            return output.print(&Value::Object(
+                self.rows
+                    .iter()
+                    .map(|(title, field)| (snake_case(title), field.to_json()))
+                    .collect(),
+            ));
+        }
+
+        let table = self
+            .rows
+            .iter()
+            .map(|(title, field)| vec![title.as_str().cell().bold(true), field.to_cell()].row())
+            .collect::<Vec<_>>()
+            .table()
+            .color_choice(color_choice);
+
+        print_stdout(table).context("unable to print table to stdout")
+    }
+}
+
+/// Table with a title row (e.g. history of operations) printed as a table for `text` output, or as an array of objects
+/// with snake case keys derived from column titles otherwise
+#[derive(Debug)]
+pub struct ListTable {
+    titles: Vec<&'static str>,
+    rows: Vec<Vec<Field>>,
+}
+
+impl ListTable {
+    /// Creates a table with given column titles and rows
+    pub fn new(titles: Vec<&'static str>, rows: Vec<Vec<Field>>) -> Self {
+        Self { titles, rows }
+    }
+
+    /// Returns rows of table as an array of objects with snake case keys
+    pub fn to_json(&self) -> Value {
+        let titles = self
+            .titles
+            .iter()
+            .map(|title| snake_case(title))
+            .collect::<Vec<_>>();
+
+        Value::Array(
+            self.rows
+                .iter()
+                .map(|row| {
+                    Value::Object(
+                        titles
+                            .iter()
+                            .cloned()
+                            .zip(row.iter().map(Field::to_json))
+                            .collect::<Map<_, _>>(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Prints table to stdout in given output format
+    pub fn print(&self, output: OutputFormat, color_choice: ColorChoice) -> Result<()> {
+        if !output.is_text() {
+            return output.print(&self.to_json());
+        }
+
+        let table = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(Field::to_cell).collect::<Vec<_>>().row())
+            .collect::<Vec<_>>()
+            .table()
+            .title(
+                self.titles
+                    .iter()
+                    .map(|title| title.cell().bold(true))
+                    .collect::<Vec<_>>(),
+            )
+            .color_choice(color_choice);
+
+        print_stdout(table).context("unable to print table to stdout")
+    }
+}
+
+/// Converts a title (e.g. `Transaction Hash`) into a snake case key (e.g. `transaction_hash`)
+fn snake_case(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
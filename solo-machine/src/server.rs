@@ -1,35 +1,144 @@
+mod auth;
 mod chain;
 mod ibc;
+mod json;
+mod openapi;
+mod rest;
+mod tenant;
+mod tls;
 
-use std::{net::SocketAddr, time::Duration};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
-use anyhow::{Context, Result};
-use solo_machine_core::{DbPool, Event, Signer};
-use tokio::sync::mpsc::UnboundedSender;
-use tonic::transport::Server as GrpcServer;
+use anyhow::{Context, Error, Result};
+use solo_machine_core::{
+    policy::PolicyViolation,
+    service::{TxError, TxErrorKind},
+    supervisor::CancellationToken,
+    Signer,
+};
+use tonic::{
+    transport::{Server as GrpcServer, ServerTlsConfig},
+    Code, Request, Status,
+};
+
+pub use self::{
+    auth::Authorizer,
+    openapi::spec as openapi_spec,
+    rest::start_rest,
+    tenant::{Tenant, Tenants},
+    tls::TlsConfig,
+};
 
 use self::{
     chain::{chain_server::ChainServer, ChainService},
     ibc::{ibc_server::IbcServer, IbcService},
 };
 
-/// Starts gRPC server
-pub async fn start_grpc(
-    db_pool: DbPool,
-    signer: impl Signer + Clone + Send + Sync + 'static,
-    sender: UnboundedSender<Event>,
+/// Starts gRPC server (server is gracefully shut down when given cancellation token is cancelled). Requests are routed
+/// to one of the `tenants` and authorized using API tokens and API keys of that tenant. When `tls` is provided, only
+/// clients presenting an allowed certificate are served (mutual TLS).
+#[allow(clippy::result_large_err)]
+pub async fn start_grpc<S>(
+    tenants: Tenants<Tenant<S>>,
     addr: SocketAddr,
-) -> Result<()> {
-    let chain_service = ChainService::new(db_pool.clone(), sender.clone(), signer.clone());
-    let ibc_service = IbcService::new(db_pool, sender, signer);
+    tls: Option<Arc<TlsConfig>>,
+    token: CancellationToken,
+) -> Result<()>
+where
+    S: Signer + Clone + Send + Sync + 'static,
+{
+    let chain_service = tenants.map(ChainService::from_tenant);
+    let ibc_service = tenants.map(IbcService::from_tenant);
+
+    let mut builder = GrpcServer::builder();
+
+    if let Some(ref tls) = tls {
+        let mut tls_config = ServerTlsConfig::new();
+        tls_config.rustls_server_config(tls.server_config(&[b"h2"])?);
+
+        builder = builder
+            .tls_config(tls_config)
+            .context("invalid tls configuration of grpc server")?;
+    }
+
+    tracing::info!(%addr, "starting grpc server");
 
-    log::info!("starting grpc server at {}", addr);
+    let chain_tls = tls.clone();
 
-    GrpcServer::builder()
+    builder
         .timeout(Duration::from_secs(60))
-        .add_service(ChainServer::new(chain_service))
-        .add_service(IbcServer::new(ibc_service))
-        .serve(addr)
+        .add_service(ChainServer::with_interceptor(
+            chain_service,
+            move |request: Request<()>| {
+                authorize_client(&request, chain_tls.as_deref())?;
+                Ok(request)
+            },
+        ))
+        .add_service(IbcServer::with_interceptor(
+            ibc_service,
+            move |request: Request<()>| {
+                authorize_client(&request, tls.as_deref())?;
+                Ok(request)
+            },
+        ))
+        .serve_with_shutdown(addr, async move { token.cancelled().await })
         .await
         .context(format!("unable to start grpc server at: {}", addr))
 }
+
+/// Authorizes client of a gRPC request using its certificate (when mutual TLS is enabled)
+#[allow(clippy::result_large_err)]
+fn authorize_client(request: &Request<()>, tls: Option<&TlsConfig>) -> Result<(), Status> {
+    match tls {
+        None => Ok(()),
+        Some(tls) => {
+            let peer_certs = request.peer_certs();
+            tls.authorize(
+                peer_certs
+                    .as_ref()
+                    .map(|certs| certs.iter().map(|cert| cert.get_ref())),
+            )
+        }
+    }
+}
+
+/// Converts an error into gRPC status (errors returned by chain are mapped to specific status codes along with their
+/// remediation hints). Error is logged along with fields of current span (e.g. chain ID of requested operation).
+fn into_status(err: Error) -> Status {
+    let status = match err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<TxError>())
+    {
+        Some(tx_error) => {
+            let code = match tx_error.kind {
+                TxErrorKind::ClientNotFound
+                | TxErrorKind::ConnectionNotFound
+                | TxErrorKind::ChannelNotFound => Code::NotFound,
+                TxErrorKind::ClientNotActive
+                | TxErrorKind::InvalidProof
+                | TxErrorKind::InsufficientFunds
+                | TxErrorKind::InsufficientFee => Code::FailedPrecondition,
+                TxErrorKind::SequenceMismatch | TxErrorKind::AlreadyInMempool => Code::Aborted,
+                TxErrorKind::OutOfGas => Code::ResourceExhausted,
+                TxErrorKind::Unauthorized => Code::PermissionDenied,
+                TxErrorKind::Unknown => Code::Internal,
+            };
+
+            Status::new(code, tx_error.to_string())
+        }
+        None => match err
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<PolicyViolation>())
+        {
+            Some(violation) => Status::permission_denied(violation.to_string()),
+            None => Status::internal(err.to_string()),
+        },
+    };
+
+    match status.code() {
+        Code::Internal => tracing::error!("{:#}", err),
+        code => tracing::warn!(?code, "{:#}", err),
+    }
+
+    status
+}
@@ -0,0 +1,144 @@
+//! Authorization of requests to gRPC and REST servers. Requests carry either an API token with a role or a channel
+//! scoped API key (only for mint and burn) in `authorization: Bearer <token>` metadata.
+use solo_machine_core::{
+    ibc::core::ics24_host::identifier::{ChainId, Identifier},
+    model::{ApiOperation, ApiRole, ApiToken},
+    service::{ApiKeyService, ApiTokenService},
+    DbPool,
+};
+use tonic::{metadata::MetadataMap, Status};
+
+/// Authorizes requests using API tokens and API keys
+pub struct Authorizer {
+    api_token_service: ApiTokenService,
+    api_key_service: ApiKeyService,
+    require_api_key: bool,
+    require_token: bool,
+}
+
+impl Authorizer {
+    /// Creates a new authorizer. When `require_api_key` is set, only mint and burn requests authorized by a channel
+    /// scoped API key are served. When `require_token` is set, every request requires an API token with a role
+    /// authorized for requested operation (or a channel scoped API key for mint and burn).
+    pub fn new(db_pool: DbPool, require_api_key: bool, require_token: bool) -> Self {
+        Self {
+            api_token_service: ApiTokenService::new(db_pool.clone()),
+            api_key_service: ApiKeyService::new(db_pool),
+            require_api_key,
+            require_token,
+        }
+    }
+
+    /// Authorizes a request for an operation which requires given role
+    pub async fn authorize(&self, metadata: &MetadataMap, role: ApiRole) -> Result<(), Status> {
+        let token = match api_token(metadata)? {
+            Some(token) if !self.require_api_key => token,
+            Some(_) => return Err(api_key_not_allowed()),
+            None if self.require_api_key || self.require_token => {
+                return Err(Status::unauthenticated("api token is required"))
+            }
+            None => return Ok(()),
+        };
+
+        match self.authenticate(&token).await? {
+            Some(api_token) => ensure_role(&api_token, role),
+            None => Err(api_key_not_allowed()),
+        }
+    }
+
+    /// Authorizes a transfer (mint or burn) using an API token with `operator` role or a channel scoped API key
+    pub async fn authorize_transfer(
+        &self,
+        metadata: &MetadataMap,
+        operation: ApiOperation,
+        chain_id: &ChainId,
+        denom: &Identifier,
+        amount: u64,
+    ) -> Result<(), Status> {
+        let token = match api_token(metadata)? {
+            Some(token) => token,
+            None if self.require_api_key || self.require_token => {
+                return Err(Status::unauthenticated("api token is required"))
+            }
+            None => return Ok(()),
+        };
+
+        if !self.require_api_key {
+            if let Some(api_token) = self.authenticate(&token).await? {
+                ensure_role(&api_token, ApiRole::Operator)?;
+
+                tracing::info!(
+                    operation = %operation,
+                    amount,
+                    denom = %denom,
+                    chain_id = %chain_id,
+                    api_token = %api_token.id,
+                    "authorized transfer using api token"
+                );
+
+                return Ok(());
+            }
+        }
+
+        let api_key = self
+            .api_key_service
+            .authorize(&token, operation, chain_id, denom, amount)
+            .await
+            .map_err(|err| Status::permission_denied(err.to_string()))?;
+
+        tracing::info!(
+            operation = %operation,
+            amount,
+            denom = %denom,
+            chain_id = %chain_id,
+            api_key = %api_key.id,
+            "authorized transfer using api key"
+        );
+
+        Ok(())
+    }
+
+    /// Authenticates an API token (returns `None` if token is not an API token with a role)
+    async fn authenticate(&self, token: &str) -> Result<Option<ApiToken>, Status> {
+        self.api_token_service
+            .authenticate(token)
+            .await
+            .map_err(|err| Status::unauthenticated(err.to_string()))
+    }
+}
+
+/// Extracts API token from `authorization: Bearer <token>` metadata of a request
+#[allow(clippy::result_large_err)]
+fn api_token(metadata: &MetadataMap) -> Result<Option<String>, Status> {
+    let value = match metadata.get("authorization") {
+        None => return Ok(None),
+        Some(value) => value
+            .to_str()
+            .map_err(|_| Status::unauthenticated("invalid authorization metadata"))?,
+    };
+
+    value
+        .strip_prefix("Bearer ")
+        .map(|token| Some(token.trim().to_owned()))
+        .ok_or_else(|| {
+            Status::unauthenticated("expected `Bearer` api token in authorization metadata")
+        })
+}
+
+#[allow(clippy::result_large_err)]
+fn ensure_role(api_token: &ApiToken, role: ApiRole) -> Result<(), Status> {
+    if api_token.role >= role {
+        Ok(())
+    } else {
+        Err(Status::permission_denied(format!(
+            "api token {} with {} role is not authorized for this operation (requires {} role)",
+            api_token.id, api_token.role, role
+        )))
+    }
+}
+
+fn api_key_not_allowed() -> Status {
+    Status::permission_denied(
+        "operation cannot be authorized by an api key (only mint and burn are allowed)",
+    )
+}
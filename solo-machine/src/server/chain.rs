@@ -3,18 +3,21 @@ tonic::include_proto!("chain");
 use std::{
     convert::{TryFrom, TryInto},
     num::TryFromIntError,
+    sync::Arc,
     time::{Duration, SystemTime},
 };
 
 use solo_machine_core::{
-    model::{ChainConfig as CoreChainConfig, Fee},
-    service::ChainService as CoreChainService,
+    model::{ApiRole, Chain as CoreChain, ChainConfig as CoreChainConfig, Fee},
+    plugins::Plugins,
+    service::{ChainService as CoreChainService, SequenceStatus as CoreSequenceStatus},
     DbPool, Event, Signer,
 };
 use tokio::sync::mpsc::UnboundedSender;
 use tonic::{Request, Response, Status};
 
 use self::chain_server::Chain;
+use super::{into_status, Authorizer, Tenant};
 
 const DEFAULT_GRPC_ADDR: &str = "http://0.0.0.0:9090";
 const DEFAULT_RPC_ADDR: &str = "http://0.0.0.0:26657";
@@ -25,24 +28,48 @@ const DEFAULT_TRUST_LEVEL: &str = "1/3";
 const DEFAULT_TRUSTING_PERIOD: Duration = Duration::from_secs(336 * 60 * 60); // 14 days
 const DEFAULT_MAX_CLOCK_DRIFT: Duration = Duration::from_secs(3); // 3 secs
 const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(60); // 60 secs
+const DEFAULT_HALT_THRESHOLD: Duration = Duration::from_secs(120); // 2 mins
 const DEFAULT_DIVERSIFIER: &str = "solo-machine-diversifier";
 const DEFAULT_PORT_ID: &str = "transfer";
 
 pub struct ChainService<S> {
     core_service: CoreChainService,
+    authorizer: Arc<Authorizer>,
     signer: S,
 }
 
 impl<S> ChainService<S> {
     /// Creates a new instance of gRPC chain service
-    pub fn new(db_pool: DbPool, notifier: UnboundedSender<Event>, signer: S) -> Self {
-        let core_service = CoreChainService::new_with_notifier(db_pool, notifier);
+    pub fn new(
+        db_pool: DbPool,
+        notifier: UnboundedSender<Event>,
+        signer: S,
+        plugins: Plugins,
+        authorizer: Arc<Authorizer>,
+    ) -> Self {
+        let core_service =
+            CoreChainService::new_with_notifier(db_pool, notifier).with_plugins(plugins);
 
         Self {
             core_service,
+            authorizer,
             signer,
         }
     }
+
+    /// Creates a new instance of gRPC chain service for a tenant
+    pub fn from_tenant(tenant: &Tenant<S>) -> Self
+    where
+        S: Clone,
+    {
+        Self::new(
+            tenant.db_pool.clone(),
+            tenant.sender.clone(),
+            tenant.signer.clone(),
+            tenant.plugins.clone(),
+            tenant.authorizer.clone(),
+        )
+    }
 }
 
 #[tonic::async_trait]
@@ -54,6 +81,10 @@ where
         &self,
         request: Request<AddChainRequest>,
     ) -> Result<Response<AddChainResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::Admin)
+            .await?;
+
         let request = request.into_inner();
 
         let config = request
@@ -85,6 +116,7 @@ where
                 .parse()
                 .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?,
             gas_limit: fee_config.gas_limit.unwrap_or(DEFAULT_GAS_LIMIT),
+            granter: None,
         };
 
         let trust_level = config
@@ -149,6 +181,7 @@ where
 
         let core_config = CoreChainConfig {
             grpc_addr,
+            grpc_tls: Default::default(),
             rpc_addr,
             fee,
             trust_level,
@@ -159,23 +192,35 @@ where
             port_id,
             trusted_height,
             trusted_hash,
+            name_resolver: None,
+            address_display: Default::default(),
+            address_format: Default::default(),
+            sign_mode: Default::default(),
+            address_algo: Default::default(),
+            halt_threshold: DEFAULT_HALT_THRESHOLD,
+            fee_strategy: Default::default(),
+            client_encoding: Default::default(),
+            inclusion_policy: Default::default(),
+            retry_policy: Default::default(),
+            timeouts: Default::default(),
+            rate_limit: None,
+            fallback_grpc_addrs: Vec::new(),
+            fallback_rpc_addrs: Vec::new(),
+            proxy: None,
+            timestamp_sync_offset: None,
+            ibc_version: None,
+            client_revision: None,
+            strict_preflight: config.strict_preflight.unwrap_or_default(),
         };
 
         let chain_id = self
             .core_service
             .add(
                 &core_config,
-                &self
-                    .signer
-                    .to_public_key()
-                    .map_err(|err| Status::internal(err.to_string()))?
-                    .encode(),
+                &self.signer.to_public_key().map_err(into_status)?.encode(),
             )
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?
+            .map_err(into_status)?
             .to_string();
 
         Ok(Response::new(AddChainResponse { chain_id }))
@@ -185,81 +230,43 @@ where
         &self,
         request: Request<QueryChainRequest>,
     ) -> Result<Response<QueryChainResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::ReadOnly)
+            .await?;
+
         let request = request.into_inner();
 
-        let chain_id = request
-            .chain_id
-            .parse()
-            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let chain_id = self
+            .core_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
 
         let chain = self
             .core_service
             .get(&chain_id)
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?
+            .map_err(into_status)?
             .ok_or_else(|| Status::not_found("chain details not found"))?;
 
-        let response = QueryChainResponse {
-            chain_id: chain.id.to_string(),
-            node_id: chain.node_id.to_string(),
-            config: Some(ChainConfig {
-                grpc_addr: Some(chain.config.grpc_addr),
-                rpc_addr: Some(chain.config.rpc_addr),
-                fee_config: Some(FeeConfig {
-                    fee_amount: Some(chain.config.fee.amount.to_string()),
-                    fee_denom: Some(chain.config.fee.denom.to_string()),
-                    gas_limit: Some(chain.config.fee.gas_limit),
-                }),
-                trust_level: Some(chain.config.trust_level.to_string()),
-                trusting_period: Some(chain.config.trusting_period.into()),
-                max_clock_drift: Some(chain.config.max_clock_drift.into()),
-                rpc_timeout: Some(chain.config.rpc_timeout.into()),
-                diversifier: Some(chain.config.diversifier),
-                port_id: Some(chain.config.port_id.to_string()),
-                trusted_height: Some(
-                    u64::from(chain.config.trusted_height)
-                        .try_into()
-                        .map_err(|err: TryFromIntError| Status::internal(err.to_string()))?,
-                ),
-                trusted_hash: Some(hex::encode(chain.config.trusted_hash)),
-            }),
-            consensus_timestamp: Some(SystemTime::from(chain.consensus_timestamp).into()),
-            sequence: chain.sequence,
-            packet_sequence: chain.packet_sequence,
-            connection_details: chain.connection_details.map(|connection_details| {
-                ConnectionDetails {
-                    solo_machine_client_id: connection_details.solo_machine_client_id.to_string(),
-                    tendermint_client_id: connection_details.tendermint_client_id.to_string(),
-                    solo_machine_connection_id: connection_details
-                        .solo_machine_connection_id
-                        .to_string(),
-                    tendermint_connection_id: connection_details
-                        .tendermint_connection_id
-                        .to_string(),
-                    solo_machine_channel_id: connection_details.solo_machine_channel_id.to_string(),
-                    tendermint_channel_id: connection_details.tendermint_channel_id.to_string(),
-                }
-            }),
-            created_at: Some(SystemTime::from(chain.created_at).into()),
-            updated_at: Some(SystemTime::from(chain.updated_at).into()),
-        };
-
-        Ok(Response::new(response))
+        Ok(Response::new(chain_into_response(chain)?))
     }
 
     async fn get_ibc_denom(
         &self,
         request: Request<GetIbcDenomRequest>,
     ) -> Result<Response<GetIbcDenomResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::ReadOnly)
+            .await?;
+
         let request = request.into_inner();
 
-        let chain_id = request
-            .chain_id
-            .parse()
-            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let chain_id = self
+            .core_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
 
         let denom = request
             .denom
@@ -270,10 +277,7 @@ where
             .core_service
             .get_ibc_denom(&chain_id, &denom)
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?;
+            .map_err(into_status)?;
 
         let response = GetIbcDenomResponse { ibc_denom };
 
@@ -284,12 +288,17 @@ where
         &self,
         request: Request<QueryBalanceRequest>,
     ) -> Result<Response<QueryBalanceResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::ReadOnly)
+            .await?;
+
         let request = request.into_inner();
 
-        let chain_id = request
-            .chain_id
-            .parse()
-            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let chain_id = self
+            .core_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
 
         let denom = request
             .denom
@@ -300,14 +309,180 @@ where
             .core_service
             .balance(&self.signer, &chain_id, &denom)
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?
+            .map_err(into_status)?
             .to_string();
 
         let response = QueryBalanceResponse { balance };
 
         Ok(Response::new(response))
     }
+
+    async fn list(
+        &self,
+        request: Request<ListChainsRequest>,
+    ) -> Result<Response<ListChainsResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::ReadOnly)
+            .await?;
+
+        let chains = self
+            .core_service
+            .get_all()
+            .await
+            .map_err(into_status)?
+            .into_iter()
+            .map(chain_into_response)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Response::new(ListChainsResponse { chains }))
+    }
+
+    async fn check_sequence(
+        &self,
+        request: Request<CheckSequenceRequest>,
+    ) -> Result<Response<CheckSequenceResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::ReadOnly)
+            .await?;
+
+        let request = request.into_inner();
+
+        let chain_id = self
+            .core_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let status = self
+            .core_service
+            .check_sequence(&chain_id)
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(CheckSequenceResponse {
+            status: Some(status.into()),
+        }))
+    }
+
+    async fn resync(
+        &self,
+        request: Request<ResyncRequest>,
+    ) -> Result<Response<ResyncResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::Admin)
+            .await?;
+
+        let request = request.into_inner();
+
+        let chain_id = self
+            .core_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let status = self
+            .core_service
+            .resync_sequence(&chain_id)
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(ResyncResponse {
+            status: Some(status.into()),
+        }))
+    }
+}
+
+impl From<CoreSequenceStatus> for SequenceStatus {
+    fn from(status: CoreSequenceStatus) -> Self {
+        Self {
+            client_id: status.client_id.to_string(),
+            local_sequence: status.local,
+            on_chain_sequence: status.on_chain,
+        }
+    }
+}
+
+/// Converts details of a chain into response of chain query
+#[allow(clippy::result_large_err)]
+fn chain_into_response(chain: CoreChain) -> Result<QueryChainResponse, Status> {
+    let response = QueryChainResponse {
+        chain_id: chain.id.to_string(),
+        node_id: chain.node_id.to_string(),
+        config: Some(ChainConfig {
+            grpc_addr: Some(chain.config.grpc_addr),
+            rpc_addr: Some(chain.config.rpc_addr),
+            fee_config: Some(FeeConfig {
+                fee_amount: Some(chain.config.fee.amount.to_string()),
+                fee_denom: Some(chain.config.fee.denom.to_string()),
+                gas_limit: Some(chain.config.fee.gas_limit),
+            }),
+            trust_level: Some(chain.config.trust_level.to_string()),
+            trusting_period: Some(chain.config.trusting_period.into()),
+            max_clock_drift: Some(chain.config.max_clock_drift.into()),
+            rpc_timeout: Some(chain.config.rpc_timeout.into()),
+            diversifier: Some(chain.config.diversifier),
+            port_id: Some(chain.config.port_id.to_string()),
+            trusted_height: Some(
+                u64::from(chain.config.trusted_height)
+                    .try_into()
+                    .map_err(|err: TryFromIntError| Status::internal(err.to_string()))?,
+            ),
+            trusted_hash: Some(hex::encode(chain.config.trusted_hash)),
+            strict_preflight: Some(chain.config.strict_preflight),
+        }),
+        consensus_timestamp: Some(SystemTime::from(chain.consensus_timestamp).into()),
+        sequence: chain.sequence,
+        packet_sequence: chain.packet_sequence,
+        connection_details: chain
+            .connection_details
+            .map(|connection_details| ConnectionDetails {
+                solo_machine_client_id: connection_details.solo_machine_client_id.to_string(),
+                tendermint_client_id: connection_details.tendermint_client_id.to_string(),
+                solo_machine_connection_id: connection_details
+                    .solo_machine_connection_id
+                    .to_string(),
+                tendermint_connection_id: connection_details.tendermint_connection_id.to_string(),
+                solo_machine_channel_id: connection_details.solo_machine_channel_id.to_string(),
+                tendermint_channel_id: connection_details.tendermint_channel_id.to_string(),
+            }),
+        created_at: Some(SystemTime::from(chain.created_at).into()),
+        updated_at: Some(SystemTime::from(chain.updated_at).into()),
+        generated_identifiers: Some(GeneratedIdentifiers {
+            client_id: chain
+                .generated_identifiers
+                .client_id
+                .map(|client_id| client_id.to_string()),
+            connection_id: chain
+                .generated_identifiers
+                .connection_id
+                .map(|connection_id| connection_id.to_string()),
+            channel_id: chain
+                .generated_identifiers
+                .channel_id
+                .map(|channel_id| channel_id.to_string()),
+        }),
+        handshake_progress: chain.handshake_progress.map(|progress| HandshakeProgress {
+            step: progress.step.to_string(),
+            solo_machine_client_id: progress
+                .solo_machine_client_id
+                .map(|client_id| client_id.to_string()),
+            tendermint_client_id: progress
+                .tendermint_client_id
+                .map(|client_id| client_id.to_string()),
+            solo_machine_connection_id: progress
+                .solo_machine_connection_id
+                .map(|connection_id| connection_id.to_string()),
+            tendermint_connection_id: progress
+                .tendermint_connection_id
+                .map(|connection_id| connection_id.to_string()),
+            solo_machine_channel_id: progress
+                .solo_machine_channel_id
+                .map(|channel_id| channel_id.to_string()),
+            tendermint_channel_id: progress
+                .tendermint_channel_id
+                .map(|channel_id| channel_id.to_string()),
+        }),
+    };
+
+    Ok(response)
 }
@@ -1,36 +1,65 @@
 tonic::include_proto!("ibc");
 
-use std::time::SystemTime;
+use std::{sync::Arc, time::SystemTime};
 
 use k256::ecdsa::VerifyingKey;
 use solo_machine_core::{
     cosmos::crypto::{PublicKey, PublicKeyAlgo},
-    ibc::core::ics24_host::identifier::ChainId,
-    service::IbcService as CoreIbcService,
+    ibc::core::ics24_host::identifier::ChannelId,
+    model::{ApiOperation, ApiRole, PendingOperation as CorePendingOperation},
+    plugins::Plugins,
+    service::{ChainService as CoreChainService, IbcService as CoreIbcService},
     DbPool, Event, Signer,
 };
 use tokio::sync::mpsc::UnboundedSender;
 use tonic::{Request, Response, Status};
 
 use self::ibc_server::Ibc;
+use super::{into_status, Authorizer, Tenant};
 
 const DEFAULT_MEMO: &str = "solo-machine-memo";
 
 pub struct IbcService<S> {
     core_service: CoreIbcService,
+    chain_service: CoreChainService,
+    authorizer: Arc<Authorizer>,
     signer: S,
 }
 
 impl<S> IbcService<S> {
     /// Creates a new instance of gRPC IBC service
-    pub fn new(db_pool: DbPool, notifier: UnboundedSender<Event>, signer: S) -> Self {
-        let core_service = CoreIbcService::new_with_notifier(db_pool, notifier);
+    pub fn new(
+        db_pool: DbPool,
+        notifier: UnboundedSender<Event>,
+        signer: S,
+        plugins: Plugins,
+        authorizer: Arc<Authorizer>,
+    ) -> Self {
+        let core_service = CoreIbcService::new_with_notifier(db_pool.clone(), notifier)
+            .with_plugins(plugins.clone());
+        let chain_service = CoreChainService::new(db_pool).with_plugins(plugins);
 
         Self {
             core_service,
+            chain_service,
+            authorizer,
             signer,
         }
     }
+
+    /// Creates a new instance of gRPC IBC service for a tenant
+    pub fn from_tenant(tenant: &Tenant<S>) -> Self
+    where
+        S: Clone,
+    {
+        Self::new(
+            tenant.db_pool.clone(),
+            tenant.sender.clone(),
+            tenant.signer.clone(),
+            tenant.plugins.clone(),
+            tenant.authorizer.clone(),
+        )
+    }
 }
 
 #[tonic::async_trait]
@@ -42,33 +71,37 @@ where
         &self,
         request: Request<ConnectRequest>,
     ) -> Result<Response<ConnectResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::Operator)
+            .await?;
+
         let request = request.into_inner();
 
-        let chain_id = request
-            .chain_id
-            .parse()
-            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let chain_id = self
+            .chain_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
         let memo = request.memo.unwrap_or_else(|| DEFAULT_MEMO.to_owned());
         let force = request.force;
 
         self.core_service
             .connect(&self.signer, chain_id, memo, force)
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?;
+            .map_err(into_status)?;
 
         Ok(Response::new(ConnectResponse {}))
     }
 
     async fn mint(&self, request: Request<MintRequest>) -> Result<Response<MintResponse>, Status> {
+        let metadata = request.metadata().clone();
         let request = request.into_inner();
 
-        let chain_id = request
-            .chain_id
-            .parse()
-            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let chain_id = self
+            .chain_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
         let request_id = request.request_id;
         let memo = request.memo.unwrap_or_else(|| DEFAULT_MEMO.to_owned());
         let amount = request.amount;
@@ -78,6 +111,10 @@ where
             .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
         let receiver = request.receiver_address;
 
+        self.authorizer
+            .authorize_transfer(&metadata, ApiOperation::Mint, &chain_id, &denom, amount)
+            .await?;
+
         let transaction_hash = self
             .core_service
             .mint(
@@ -90,21 +127,20 @@ where
                 memo,
             )
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?;
+            .map_err(into_status)?;
 
         Ok(Response::new(MintResponse { transaction_hash }))
     }
 
     async fn burn(&self, request: Request<BurnRequest>) -> Result<Response<BurnResponse>, Status> {
+        let metadata = request.metadata().clone();
         let request = request.into_inner();
 
-        let chain_id = request
-            .chain_id
-            .parse()
-            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let chain_id = self
+            .chain_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
         let request_id = request.request_id;
         let memo = request.memo.unwrap_or_else(|| DEFAULT_MEMO.to_owned());
         let amount = request.amount;
@@ -113,14 +149,15 @@ where
             .parse()
             .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
 
+        self.authorizer
+            .authorize_transfer(&metadata, ApiOperation::Burn, &chain_id, &denom, amount)
+            .await?;
+
         let transaction_hash = self
             .core_service
             .burn(&self.signer, chain_id, request_id, amount, denom, memo)
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?;
+            .map_err(into_status)?;
 
         Ok(Response::new(BurnResponse { transaction_hash }))
     }
@@ -129,12 +166,17 @@ where
         &self,
         request: Request<UpdateSignerRequest>,
     ) -> Result<Response<UpdateSignerResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::Admin)
+            .await?;
+
         let request = request.into_inner();
 
-        let chain_id: ChainId = request
-            .chain_id
-            .parse()
-            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let chain_id = self
+            .chain_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
 
         let memo = request.memo.unwrap_or_else(|| DEFAULT_MEMO.to_owned());
 
@@ -160,10 +202,7 @@ where
         self.core_service
             .update_signer(&self.signer, chain_id, new_public_key, memo)
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?;
+            .map_err(into_status)?;
 
         Ok(Response::new(UpdateSignerResponse {}))
     }
@@ -172,6 +211,10 @@ where
         &self,
         request: Request<QueryHistoryRequest>,
     ) -> Result<Response<QueryHistoryResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::ReadOnly)
+            .await?;
+
         let request = request.into_inner();
 
         let limit = request.limit.unwrap_or(10);
@@ -181,10 +224,7 @@ where
             .core_service
             .history(&self.signer, limit, offset)
             .await
-            .map_err(|err| {
-                log::error!("{}", err);
-                Status::internal(err.to_string())
-            })?;
+            .map_err(into_status)?;
 
         let response = QueryHistoryResponse {
             operations: history
@@ -204,4 +244,198 @@ where
 
         Ok(Response::new(response))
     }
+
+    async fn relay_packets(
+        &self,
+        request: Request<RelayPacketsRequest>,
+    ) -> Result<Response<RelayPacketsResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::Operator)
+            .await?;
+
+        let request = request.into_inner();
+
+        let chain_id = self
+            .chain_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        let memo = request.memo.unwrap_or_else(|| DEFAULT_MEMO.to_owned());
+        let from_height = request.from_height.unwrap_or_default();
+
+        let report = self
+            .core_service
+            .relay_pending_packets(&self.signer, &chain_id, from_height, memo)
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(RelayPacketsResponse {
+            relayed_height: report.relayed_height,
+            acknowledged: report.acknowledged,
+            timed_out: report.timed_out,
+        }))
+    }
+
+    async fn query_packets(
+        &self,
+        request: Request<QueryPacketsRequest>,
+    ) -> Result<Response<QueryPacketsResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::ReadOnly)
+            .await?;
+
+        let request = request.into_inner();
+
+        let chain_id = self
+            .chain_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        let channel_id: Option<ChannelId> = request
+            .channel_id
+            .map(|channel_id| channel_id.parse())
+            .transpose()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let state = request
+            .state
+            .map(|state| state.parse())
+            .transpose()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let limit = request.limit.unwrap_or(10);
+        let offset = request.offset.unwrap_or(0);
+
+        let packets = self
+            .core_service
+            .packets(&chain_id, channel_id.as_ref(), state, limit, offset)
+            .await
+            .map_err(into_status)?;
+
+        let response = QueryPacketsResponse {
+            packets: packets
+                .into_iter()
+                .map(|tracked| Packet {
+                    chain_id: tracked.chain_id.to_string(),
+                    port_id: tracked.port_id.to_string(),
+                    channel_id: tracked.channel_id.to_string(),
+                    sequence: tracked.sequence,
+                    state: tracked.state.to_string(),
+                    height: tracked.height,
+                    data: tracked.packet.data,
+                    transaction_hash: tracked.transaction_hash,
+                    created_at: Some(SystemTime::from(tracked.created_at).into()),
+                    updated_at: Some(SystemTime::from(tracked.updated_at).into()),
+                })
+                .collect(),
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn query_pending_operations(
+        &self,
+        request: Request<QueryPendingOperationsRequest>,
+    ) -> Result<Response<QueryPendingOperationsResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::ReadOnly)
+            .await?;
+
+        let request = request.into_inner();
+
+        let chain_id = match request.chain_id {
+            Some(chain_id) => Some(
+                self.chain_service
+                    .resolve_chain_id(&chain_id)
+                    .await
+                    .map_err(|err| Status::invalid_argument(err.to_string()))?,
+            ),
+            None => None,
+        };
+        let status = request
+            .status
+            .map(|status| status.parse())
+            .transpose()
+            .map_err(|err: anyhow::Error| Status::invalid_argument(err.to_string()))?;
+        let limit = request.limit.unwrap_or(10);
+        let offset = request.offset.unwrap_or(0);
+
+        let operations = self
+            .core_service
+            .pending_operations(chain_id.as_ref(), status, limit, offset)
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(QueryPendingOperationsResponse {
+            operations: operations.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn resume_pending_operations(
+        &self,
+        request: Request<ResumePendingOperationsRequest>,
+    ) -> Result<Response<ResumePendingOperationsResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::Operator)
+            .await?;
+
+        let request = request.into_inner();
+
+        let chain_id = self
+            .chain_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let operations = self
+            .core_service
+            .resume_pending_operations(&self.signer, &chain_id)
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(ResumePendingOperationsResponse {
+            operations: operations.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn rollback_failed_operations(
+        &self,
+        request: Request<RollbackFailedOperationsRequest>,
+    ) -> Result<Response<RollbackFailedOperationsResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), ApiRole::Admin)
+            .await?;
+
+        let request = request.into_inner();
+
+        let chain_id = self
+            .chain_service
+            .resolve_chain_id(&request.chain_id)
+            .await
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let sequence = self
+            .core_service
+            .rollback_failed_operations(&chain_id)
+            .await
+            .map_err(into_status)?;
+
+        Ok(Response::new(RollbackFailedOperationsResponse { sequence }))
+    }
+}
+
+impl From<CorePendingOperation> for PendingOperation {
+    fn from(operation: CorePendingOperation) -> Self {
+        Self {
+            id: operation.id,
+            chain_id: operation.chain_id.to_string(),
+            kind: operation.kind.to_string(),
+            request_id: operation.request_id,
+            status: operation.status.to_string(),
+            transaction_hash: operation.transaction_hash,
+            sequence: operation.sequence,
+            packet_sequence: operation.packet_sequence,
+            error: operation.error,
+            created_at: Some(SystemTime::from(operation.created_at).into()),
+            updated_at: Some(SystemTime::from(operation.updated_at).into()),
+        }
+    }
 }
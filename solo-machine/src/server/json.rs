@@ -0,0 +1,88 @@
+//! JSON representations of protobuf types which do not implement serde (used in REST server)
+
+/// `google.protobuf.Timestamp` as RFC 3339 string
+pub mod timestamp {
+    use std::time::SystemTime;
+
+    use chrono::{DateTime, Utc};
+    use prost_types::Timestamp;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(timestamp: &Option<Timestamp>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        timestamp
+            .clone()
+            .map(|timestamp| DateTime::<Utc>::from(SystemTime::from(timestamp)).to_rfc3339())
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Timestamp>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|timestamp| {
+                DateTime::parse_from_rfc3339(&timestamp)
+                    .map(|time| SystemTime::from(time).into())
+                    .map_err(D::Error::custom)
+            })
+            .transpose()
+    }
+}
+
+/// `google.protobuf.Duration` as human readable duration (e.g. `14days`)
+pub mod duration {
+    use std::{convert::TryFrom, time::Duration as StdDuration};
+
+    use prost_types::Duration;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        duration
+            .clone()
+            .map(|duration| {
+                StdDuration::try_from(duration)
+                    .map(|duration| humantime::format_duration(duration).to_string())
+                    .map_err(|_| serde::ser::Error::custom("negative duration"))
+            })
+            .transpose()?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|duration| {
+                humantime::parse_duration(&duration)
+                    .map(Into::into)
+                    .map_err(D::Error::custom)
+            })
+            .transpose()
+    }
+}
+
+/// `bytes` as hex string
+pub mod hex_bytes {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        hex::decode(String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,248 @@
+//! OpenAPI spec of REST server generated from protobuf definitions of gRPC services (so that both APIs are always in
+//! sync)
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use prost::Message;
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+};
+use serde_json::{json, Map, Value};
+
+use super::tenant::TENANT_METADATA_KEY;
+
+/// Encoded file descriptors of protobuf definitions (generated by build script)
+const FILE_DESCRIPTORS: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/descriptors.bin"));
+
+/// Prefix of paths of REST endpoints
+pub const PATH_PREFIX: &str = "/v1/";
+
+/// Returns path of REST endpoint of a gRPC method (e.g. `/v1/chain/get-ibc-denom` for `chain.Chain/GetIbcDenom`)
+pub fn endpoint_path(package: &str, method: &str) -> String {
+    let mut path = format!("{}{}/", PATH_PREFIX, package);
+
+    for (i, c) in method.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i != 0 {
+                path.push('-');
+            }
+            path.push(c.to_ascii_lowercase());
+        } else {
+            path.push(c);
+        }
+    }
+
+    path
+}
+
+/// Generates OpenAPI (v3) spec of REST server
+pub fn spec() -> Result<Value> {
+    let descriptors = FileDescriptorSet::decode(FILE_DESCRIPTORS)
+        .context("unable to decode file descriptors of protobuf definitions")?;
+
+    let mut paths = Map::new();
+    let mut schemas = Map::new();
+
+    for file in descriptors.file.iter() {
+        let package = file.package();
+        let comments = Comments::new(file);
+
+        for (i, message) in file.message_type.iter().enumerate() {
+            schemas.insert(
+                format!("{}.{}", package, message.name()),
+                message_schema(message, &comments, &[4, i as i32]),
+            );
+        }
+
+        for (i, service) in file.service.iter().enumerate() {
+            for (j, method) in service.method.iter().enumerate() {
+                let summary = comments
+                    .get(&[6, i as i32, 2, j as i32])
+                    .unwrap_or_else(|| method.name());
+
+                paths.insert(
+                    endpoint_path(package, method.name()),
+                    json!({
+                        "post": {
+                            "operationId": format!("{}.{}.{}", package, service.name(), method.name()),
+                            "tags": [service.name()],
+                            "summary": summary,
+                            "parameters": [{ "$ref": "#/components/parameters/TenantId" }],
+                            "requestBody": {
+                                "required": true,
+                                "content": {
+                                    "application/json": {
+                                        "schema": schema_ref(method.input_type()),
+                                    },
+                                },
+                            },
+                            "responses": {
+                                "200": {
+                                    "description": "Successful response",
+                                    "content": {
+                                        "application/json": {
+                                            "schema": schema_ref(method.output_type()),
+                                        },
+                                    },
+                                },
+                                "default": {
+                                    "description": "Error response (with gRPC status code)",
+                                    "content": {
+                                        "application/json": {
+                                            "schema": { "$ref": "#/components/schemas/Status" },
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    }),
+                );
+            }
+        }
+    }
+
+    schemas.insert(
+        "Status".to_owned(),
+        json!({
+            "type": "object",
+            "properties": {
+                "code": {
+                    "type": "integer",
+                    "format": "int32",
+                    "description": "gRPC status code",
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Error message",
+                },
+            },
+        }),
+    );
+
+    Ok(json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "IBC solo machine",
+            "description": "HTTP+JSON API of IBC solo machine (same operations as gRPC API)",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": schemas,
+            "parameters": {
+                "TenantId": {
+                    "name": TENANT_METADATA_KEY,
+                    "in": "header",
+                    "required": false,
+                    "description": "ID of tenant to which request is routed (required when server is serving \
+                                    multiple tenants)",
+                    "schema": { "type": "string" },
+                },
+            },
+            "securitySchemes": {
+                "apiKey": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "description": "API token with a role created using `api-token create` or channel scoped API key \
+                                    created using `api-key create` (only for mint and burn)",
+                },
+            },
+        },
+        "security": [{}, { "apiKey": [] }],
+    }))
+}
+
+fn message_schema(message: &DescriptorProto, comments: &Comments, path: &[i32]) -> Value {
+    let mut properties = Map::new();
+
+    for (i, field) in message.field.iter().enumerate() {
+        let mut schema = field_schema(field);
+
+        let mut field_path = path.to_vec();
+        field_path.extend_from_slice(&[2, i as i32]);
+
+        if let (Some(description), Some(schema)) =
+            (comments.get(&field_path), schema.as_object_mut())
+        {
+            schema.insert("description".to_owned(), description.into());
+        }
+
+        properties.insert(field.name().to_owned(), schema);
+    }
+
+    let mut schema = json!({
+        "type": "object",
+        "properties": properties,
+    });
+
+    if let (Some(description), Some(schema)) = (comments.get(path), schema.as_object_mut()) {
+        schema.insert("description".to_owned(), description.into());
+    }
+
+    schema
+}
+
+fn field_schema(field: &FieldDescriptorProto) -> Value {
+    let schema = match field.r#type() {
+        Type::Double | Type::Float => json!({ "type": "number" }),
+        Type::Int64 | Type::Uint64 | Type::Sint64 | Type::Fixed64 | Type::Sfixed64 => {
+            json!({ "type": "integer", "format": "int64" })
+        }
+        Type::Int32 | Type::Uint32 | Type::Sint32 | Type::Fixed32 | Type::Sfixed32 => {
+            json!({ "type": "integer", "format": "int32" })
+        }
+        Type::Bool => json!({ "type": "boolean" }),
+        Type::String | Type::Enum => json!({ "type": "string" }),
+        Type::Bytes => json!({ "type": "string", "format": "hex" }),
+        Type::Message | Type::Group => match field.type_name() {
+            ".google.protobuf.Timestamp" => json!({ "type": "string", "format": "date-time" }),
+            ".google.protobuf.Duration" => {
+                json!({ "type": "string", "format": "duration", "example": "14days" })
+            }
+            type_name => json!({ "allOf": [schema_ref(type_name)] }),
+        },
+    };
+
+    if field.label() == Label::Repeated {
+        json!({ "type": "array", "items": schema })
+    } else {
+        schema
+    }
+}
+
+fn schema_ref(type_name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", type_name.trim_start_matches('.')) })
+}
+
+/// Leading comments of protobuf definitions indexed by their paths in file descriptor
+struct Comments(BTreeMap<Vec<i32>, String>);
+
+impl Comments {
+    fn new(file: &FileDescriptorProto) -> Self {
+        let comments = file
+            .source_code_info
+            .iter()
+            .flat_map(|source_code_info| source_code_info.location.iter())
+            .filter_map(|location| {
+                let comment = location
+                    .leading_comments()
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                if comment.is_empty() {
+                    None
+                } else {
+                    Some((location.path.clone(), comment))
+                }
+            })
+            .collect();
+
+        Self(comments)
+    }
+
+    fn get(&self, path: &[i32]) -> Option<&str> {
+        self.0.get(path).map(AsRef::as_ref)
+    }
+}
@@ -0,0 +1,369 @@
+//! HTTP+JSON facade of gRPC services for integrations which cannot use gRPC. Every gRPC method is served at
+//! `POST /v1/<package>/<method-in-kebab-case>` (e.g. `POST /v1/ibc/mint`) with request and response messages encoded
+//! as JSON, and OpenAPI spec of all the endpoints is served at `GET /openapi.json`. Liveness and readiness of solo
+//! machine (of all the tenants) are reported at `GET /healthz` and `GET /readyz` respectively.
+use std::{collections::BTreeMap, convert::Infallible, net::SocketAddr, sync::Arc};
+
+use anyhow::{Context, Result};
+use hyper::{
+    body::to_bytes,
+    header::CONTENT_TYPE,
+    server::conn::Http,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request as HttpRequest, Response as HttpResponse, Server, StatusCode,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use solo_machine_core::{
+    service::{HealthReport, HealthService},
+    supervisor::CancellationToken,
+    Signer,
+};
+use tokio::net::TcpListener;
+use tokio_rustls::{rustls::Session, TlsAcceptor};
+use tonic::{metadata::MetadataMap, Code, Request, Status};
+
+use super::{
+    chain::{chain_server::Chain, ChainService},
+    ibc::{ibc_server::Ibc, IbcService},
+    openapi::{self, PATH_PREFIX},
+    tenant::{Tenant, Tenants},
+    tls::TlsConfig,
+};
+
+/// Services called by REST server (same as the ones served by gRPC server)
+struct Services<S> {
+    chain: Tenants<ChainService<S>>,
+    ibc: Tenants<IbcService<S>>,
+    /// Health service and signer of every tenant
+    health: Tenants<(HealthService, S)>,
+}
+
+/// Starts REST server (server is gracefully shut down when given cancellation token is cancelled). Tenants (using
+/// `x-tenant-id` header), API tokens, API keys (using `authorization: Bearer <token>` header) and client certificates
+/// (when `tls` is provided) are handled in the same way as gRPC server.
+pub async fn start_rest<S>(
+    tenants: Tenants<Tenant<S>>,
+    addr: SocketAddr,
+    tls: Option<Arc<TlsConfig>>,
+    token: CancellationToken,
+) -> Result<()>
+where
+    S: Signer + Clone + 'static,
+{
+    let services = Arc::new(Services {
+        chain: tenants.map(ChainService::from_tenant),
+        ibc: tenants.map(IbcService::from_tenant),
+        health: tenants.map(|tenant| {
+            (
+                HealthService::new(tenant.db_pool.clone()),
+                tenant.signer.clone(),
+            )
+        }),
+    });
+
+    if let Some(tls) = tls {
+        return serve_tls(services, addr, tls, token).await;
+    }
+
+    let make_service = make_service_fn(move |_| {
+        let services = services.clone();
+
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let services = services.clone();
+                async move { Ok::<_, Infallible>(handle(&services, request, Ok(())).await) }
+            }))
+        }
+    });
+
+    tracing::info!(%addr, "starting rest server");
+
+    Server::try_bind(&addr)
+        .with_context(|| format!("unable to bind rest server to {}", addr))?
+        .serve(make_service)
+        .with_graceful_shutdown(async move { token.cancelled().await })
+        .await
+        .with_context(|| format!("unable to start rest server at: {}", addr))
+}
+
+/// Serves REST server over mutual TLS (each connection is authorized using client certificate presented in TLS
+/// handshake)
+async fn serve_tls<S>(
+    services: Arc<Services<S>>,
+    addr: SocketAddr,
+    tls: Arc<TlsConfig>,
+    token: CancellationToken,
+) -> Result<()>
+where
+    S: Signer + Send + Sync + 'static,
+{
+    let acceptor = TlsAcceptor::from(Arc::new(tls.server_config(&[b"http/1.1"])?));
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("unable to bind rest server to {}", addr))?;
+
+    tracing::info!(%addr, "starting rest server (mutual tls)");
+
+    loop {
+        let stream = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(err) => {
+                    tracing::warn!("unable to accept rest connection: {}", err);
+                    continue;
+                }
+            },
+            _ = token.cancelled() => return Ok(()),
+        };
+
+        let acceptor = acceptor.clone();
+        let services = services.clone();
+        let tls = tls.clone();
+        let token = token.clone();
+
+        tokio::spawn(async move {
+            let stream = match acceptor.accept(stream).await {
+                Ok(stream) => stream,
+                Err(err) => {
+                    tracing::warn!("tls handshake with rest client failed: {}", err);
+                    return;
+                }
+            };
+
+            let client = tls.authorize(
+                stream
+                    .get_ref()
+                    .1
+                    .get_peer_certificates()
+                    .as_ref()
+                    .map(|certs| certs.iter().map(|cert| cert.0.as_slice())),
+            );
+
+            let service = service_fn(move |request| {
+                let services = services.clone();
+                let client = client.clone();
+                async move { Ok::<_, Infallible>(handle(&services, request, client).await) }
+            });
+
+            let connection = Http::new().serve_connection(stream, service);
+            tokio::pin!(connection);
+
+            let result = tokio::select! {
+                result = connection.as_mut() => result,
+                _ = token.cancelled() => {
+                    connection.as_mut().graceful_shutdown();
+                    connection.await
+                }
+            };
+
+            if let Err(err) = result {
+                tracing::debug!("unable to serve rest connection: {}", err);
+            }
+        });
+    }
+}
+
+/// Handles a request of a client (`client` is the result of authorizing client certificate)
+#[allow(clippy::result_large_err)]
+#[tracing::instrument(name = "rest_request", skip_all, fields(method = %request.method(), path = %request.uri().path()))]
+async fn handle<S>(
+    services: &Services<S>,
+    request: HttpRequest<Body>,
+    client: Result<(), Status>,
+) -> HttpResponse<Body>
+where
+    S: Signer + Send + Sync + 'static,
+{
+    let path = request.uri().path().to_owned();
+
+    if let Err(status) = client {
+        return error_response(status);
+    }
+
+    let result = match (request.method(), path.as_str()) {
+        (&Method::GET, "/openapi.json") => openapi::spec()
+            .map_err(|err| Status::internal(err.to_string()))
+            .and_then(|spec| json_response(&spec)),
+        (&Method::GET, "/healthz") => health_response(&services.health, false).await,
+        (&Method::GET, "/readyz") => health_response(&services.health, true).await,
+        (&Method::POST, path) if path.starts_with(PATH_PREFIX) => {
+            call(services, &path[PATH_PREFIX.len()..], request).await
+        }
+        _ => Err(Status::not_found(format!(
+            "no endpoint at {} {}",
+            request.method(),
+            path
+        ))),
+    };
+
+    result.unwrap_or_else(|status| {
+        tracing::debug!(code = ?status.code(), "{}", status.message());
+        error_response(status)
+    })
+}
+
+/// Calls gRPC method of endpoint at given path (relative to [`PATH_PREFIX`])
+async fn call<S>(
+    services: &Services<S>,
+    path: &str,
+    request: HttpRequest<Body>,
+) -> Result<HttpResponse<Body>, Status>
+where
+    S: Signer + Send + Sync + 'static,
+{
+    let (parts, body) = request.into_parts();
+
+    let metadata = MetadataMap::from_headers(parts.headers);
+    let body = to_bytes(body)
+        .await
+        .map_err(|err| Status::invalid_argument(format!("unable to read request body: {}", err)))?;
+
+    let chain = &services.chain;
+    let ibc = &services.ibc;
+
+    macro_rules! unary {
+        ($service:ident . $method:ident) => {
+            json_response(
+                &$service
+                    .$method(grpc_request(&metadata, &body)?)
+                    .await?
+                    .into_inner(),
+            )
+        };
+    }
+
+    match path {
+        "chain/add" => unary!(chain.add),
+        "chain/query" => unary!(chain.query),
+        "chain/get-ibc-denom" => unary!(chain.get_ibc_denom),
+        "chain/query-balance" => unary!(chain.query_balance),
+        "chain/list" => unary!(chain.list),
+        "chain/check-sequence" => unary!(chain.check_sequence),
+        "chain/resync" => unary!(chain.resync),
+        "ibc/connect" => unary!(ibc.connect),
+        "ibc/mint" => unary!(ibc.mint),
+        "ibc/burn" => unary!(ibc.burn),
+        "ibc/update-signer" => unary!(ibc.update_signer),
+        "ibc/query-history" => unary!(ibc.query_history),
+        "ibc/relay-packets" => unary!(ibc.relay_packets),
+        "ibc/query-packets" => unary!(ibc.query_packets),
+        "ibc/query-pending-operations" => unary!(ibc.query_pending_operations),
+        "ibc/resume-pending-operations" => unary!(ibc.resume_pending_operations),
+        "ibc/rollback-failed-operations" => unary!(ibc.rollback_failed_operations),
+        _ => Err(Status::not_found(format!(
+            "no endpoint at POST {}{}",
+            PATH_PREFIX, path
+        ))),
+    }
+}
+
+/// Reports liveness (or readiness, when `ready` is `true`) of all the tenants (`503 Service Unavailable` when any check
+/// of any tenant fails). Reports of multiple tenants are keyed by tenant ID.
+#[allow(clippy::result_large_err)]
+async fn health_response<S>(
+    health: &Tenants<(HealthService, S)>,
+    ready: bool,
+) -> Result<HttpResponse<Body>, Status>
+where
+    S: Signer,
+{
+    async fn check<S: Signer>((service, signer): &(HealthService, S), ready: bool) -> HealthReport {
+        if ready {
+            service.readiness(signer).await
+        } else {
+            service.liveness().await
+        }
+    }
+
+    let (healthy, body) = match health {
+        Tenants::Single(tenant) => {
+            let report = check(tenant, ready).await;
+            (report.healthy, serde_json::to_vec(&report))
+        }
+        Tenants::Multiple(tenants) => {
+            let mut reports = BTreeMap::new();
+
+            for (id, tenant) in tenants {
+                reports.insert(id, check(tenant, ready).await);
+            }
+
+            (
+                reports.values().all(|report| report.healthy),
+                serde_json::to_vec(&reports),
+            )
+        }
+    };
+
+    let body = body.map_err(|err| Status::internal(err.to_string()))?;
+
+    HttpResponse::builder()
+        .status(if healthy {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        })
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .map_err(|err| Status::internal(err.to_string()))
+}
+
+/// Decodes JSON request body into a gRPC request (along with metadata from HTTP headers). An empty body is decoded as
+/// default request.
+#[allow(clippy::result_large_err)]
+fn grpc_request<M>(metadata: &MetadataMap, body: &[u8]) -> Result<Request<M>, Status>
+where
+    M: DeserializeOwned + Default,
+{
+    let message = if body.iter().all(u8::is_ascii_whitespace) {
+        M::default()
+    } else {
+        serde_json::from_slice(body)
+            .map_err(|err| Status::invalid_argument(format!("invalid request body: {}", err)))?
+    };
+
+    let mut request = Request::new(message);
+    *request.metadata_mut() = metadata.clone();
+
+    Ok(request)
+}
+
+#[allow(clippy::result_large_err)]
+fn json_response<T: Serialize>(body: &T) -> Result<HttpResponse<Body>, Status> {
+    let body = serde_json::to_vec(body).map_err(|err| Status::internal(err.to_string()))?;
+
+    HttpResponse::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .map_err(|err| Status::internal(err.to_string()))
+}
+
+/// Converts gRPC status into HTTP response (status codes are mapped in the same way as gRPC gateway)
+fn error_response(status: Status) -> HttpResponse<Body> {
+    let http_status = match status.code() {
+        Code::Ok => StatusCode::OK,
+        Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => {
+            StatusCode::BAD_REQUEST
+        }
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+
+    let body = serde_json::json!({
+        "code": status.code() as i32,
+        "message": status.message(),
+    });
+
+    HttpResponse::builder()
+        .status(http_status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap_or_else(|_| HttpResponse::new(Body::empty()))
+}
@@ -0,0 +1,139 @@
+//! Multi-tenant mode of gRPC and REST servers. Each tenant is an independent solo machine identity (with its own
+//! signer, database, API tokens and event pipeline) and requests are routed to a tenant using `x-tenant-id` metadata
+//! (or HTTP header).
+use std::{collections::BTreeMap, sync::Arc};
+
+use solo_machine_core::{plugins::Plugins, DbPool, Event, Signer};
+use tokio::sync::mpsc::UnboundedSender;
+use tonic::{metadata::MetadataMap, Request, Response, Status};
+use tracing::Instrument;
+
+use super::{
+    auth::Authorizer,
+    chain::{self, chain_server::Chain, ChainService},
+    ibc::{self, ibc_server::Ibc, IbcService},
+};
+
+/// Metadata key (or HTTP header) containing ID of tenant to which a request is routed
+pub(super) const TENANT_METADATA_KEY: &str = "x-tenant-id";
+
+/// Solo machine identity served by gRPC and REST servers
+#[derive(Clone)]
+pub struct Tenant<S> {
+    /// Database of tenant
+    pub db_pool: DbPool,
+    /// Signer of tenant
+    pub signer: S,
+    /// Plugins used for chains of tenant with `plugin` fee strategy or client encoding
+    pub plugins: Plugins,
+    /// Sender of tenant's event pipeline
+    pub sender: UnboundedSender<Event>,
+    /// Authorizer of requests using API tokens and API keys of tenant
+    pub authorizer: Arc<Authorizer>,
+}
+
+/// Tenants served by gRPC and REST servers
+#[derive(Clone)]
+pub enum Tenants<T> {
+    /// A single tenant serving all the requests (`x-tenant-id` metadata is ignored)
+    Single(T),
+    /// Multiple tenants keyed by tenant ID (every request requires `x-tenant-id` metadata)
+    Multiple(BTreeMap<String, T>),
+}
+
+impl<T> Tenants<T> {
+    /// Maps every tenant to a value (e.g. a service of tenant)
+    pub(super) fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Tenants<U> {
+        match self {
+            Self::Single(tenant) => Tenants::Single(f(tenant)),
+            Self::Multiple(tenants) => Tenants::Multiple(
+                tenants
+                    .iter()
+                    .map(|(id, tenant)| (id.clone(), f(tenant)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Selects tenant of a request using its metadata
+    #[allow(clippy::result_large_err)]
+    pub(super) fn select(&self, metadata: &MetadataMap) -> Result<&T, Status> {
+        match self {
+            Self::Single(tenant) => Ok(tenant),
+            Self::Multiple(tenants) => {
+                let id = metadata
+                    .get(TENANT_METADATA_KEY)
+                    .ok_or_else(|| {
+                        Status::invalid_argument(format!(
+                            "`{}` metadata is required",
+                            TENANT_METADATA_KEY
+                        ))
+                    })?
+                    .to_str()
+                    .map_err(|_| {
+                        Status::invalid_argument(format!(
+                            "invalid `{}` metadata",
+                            TENANT_METADATA_KEY
+                        ))
+                    })?;
+
+                tenants
+                    .get(id)
+                    .ok_or_else(|| Status::not_found(format!("tenant not found: {}", id)))
+            }
+        }
+    }
+}
+
+/// Implements a gRPC service trait for tenants of a service by forwarding every method (in a tracing span) to the
+/// service of tenant selected by request metadata
+macro_rules! forward {
+    ($trait:ident for $service:ident { $($method:ident($request:ty) -> $response:ty;)* }) => {
+        #[tonic::async_trait]
+        impl<S> $trait for Tenants<$service<S>>
+        where
+            S: Signer + Send + Sync + 'static,
+        {
+            $(
+                async fn $method(
+                    &self,
+                    request: Request<$request>,
+                ) -> Result<Response<$response>, Status> {
+                    let span = tracing::info_span!(
+                        "rpc",
+                        rpc.service = stringify!($trait),
+                        rpc.method = stringify!($method),
+                    );
+
+                    self.select(request.metadata())?
+                        .$method(request)
+                        .instrument(span)
+                        .await
+                }
+            )*
+        }
+    };
+}
+
+forward!(Chain for ChainService {
+    add(chain::AddChainRequest) -> chain::AddChainResponse;
+    query(chain::QueryChainRequest) -> chain::QueryChainResponse;
+    get_ibc_denom(chain::GetIbcDenomRequest) -> chain::GetIbcDenomResponse;
+    query_balance(chain::QueryBalanceRequest) -> chain::QueryBalanceResponse;
+    list(chain::ListChainsRequest) -> chain::ListChainsResponse;
+    check_sequence(chain::CheckSequenceRequest) -> chain::CheckSequenceResponse;
+    resync(chain::ResyncRequest) -> chain::ResyncResponse;
+});
+
+forward!(Ibc for IbcService {
+    connect(ibc::ConnectRequest) -> ibc::ConnectResponse;
+    mint(ibc::MintRequest) -> ibc::MintResponse;
+    burn(ibc::BurnRequest) -> ibc::BurnResponse;
+    update_signer(ibc::UpdateSignerRequest) -> ibc::UpdateSignerResponse;
+    query_history(ibc::QueryHistoryRequest) -> ibc::QueryHistoryResponse;
+    relay_packets(ibc::RelayPacketsRequest) -> ibc::RelayPacketsResponse;
+    query_packets(ibc::QueryPacketsRequest) -> ibc::QueryPacketsResponse;
+    query_pending_operations(ibc::QueryPendingOperationsRequest) -> ibc::QueryPendingOperationsResponse;
+    resume_pending_operations(ibc::ResumePendingOperationsRequest) -> ibc::ResumePendingOperationsResponse;
+    rollback_failed_operations(ibc::RollbackFailedOperationsRequest) -> ibc::RollbackFailedOperationsResponse;
+});
@@ -0,0 +1,139 @@
+//! Mutual TLS of gRPC and REST servers. Clients are required to present a certificate issued by configured client CA
+//! during TLS handshake and, when an allowlist of certificate fingerprints is configured, requests are only served for
+//! clients presenting one of the allowed certificates.
+use std::{collections::HashSet, fs::File, io::BufReader, path::Path};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use sha2::{Digest, Sha256};
+use tokio_rustls::rustls::{
+    internal::pemfile::{certs, pkcs8_private_keys, rsa_private_keys},
+    AllowAnyAuthenticatedClient, Certificate, PrivateKey, RootCertStore, ServerConfig,
+};
+use tonic::Status;
+
+/// TLS configuration of API servers
+pub struct TlsConfig {
+    certs: Vec<Certificate>,
+    key: PrivateKey,
+    client_ca: RootCertStore,
+    allowed_clients: HashSet<Vec<u8>>,
+}
+
+impl TlsConfig {
+    /// Loads server certificate chain, private key and client CA certificates from PEM files. `allowed_clients` are
+    /// hex encoded SHA-256 fingerprints of DER encoded client certificates (all the clients with a certificate issued
+    /// by client CA are allowed when empty).
+    pub fn load(
+        cert: &Path,
+        key: &Path,
+        client_ca: &Path,
+        allowed_clients: &[String],
+    ) -> Result<Self> {
+        let certs = certs(&mut open_pem(cert)?)
+            .map_err(|_| anyhow!("invalid certificate in {}", cert.display()))?;
+        ensure!(
+            !certs.is_empty(),
+            "no certificate found in {}",
+            cert.display()
+        );
+
+        let key = load_private_key(key)?;
+
+        let mut client_ca_store = RootCertStore::empty();
+        let (added, _) = client_ca_store
+            .add_pem_file(&mut open_pem(client_ca)?)
+            .map_err(|_| anyhow!("invalid certificate in {}", client_ca.display()))?;
+        ensure!(
+            added > 0,
+            "no valid client CA certificate found in {}",
+            client_ca.display()
+        );
+
+        let allowed_clients = allowed_clients
+            .iter()
+            .map(|fingerprint| parse_fingerprint(fingerprint))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            certs,
+            key,
+            client_ca: client_ca_store,
+            allowed_clients,
+        })
+    }
+
+    /// Returns rustls server configuration requiring client certificates (with given ALPN protocols)
+    pub(super) fn server_config(&self, protocols: &[&[u8]]) -> Result<ServerConfig> {
+        let mut config =
+            ServerConfig::new(AllowAnyAuthenticatedClient::new(self.client_ca.clone()));
+        config
+            .set_single_cert(self.certs.clone(), self.key.clone())
+            .context("invalid server certificate or private key")?;
+        config.set_protocols(
+            &protocols
+                .iter()
+                .map(|protocol| protocol.to_vec())
+                .collect::<Vec<_>>(),
+        );
+
+        Ok(config)
+    }
+
+    /// Authorizes a client using its certificate chain (DER encoded, starting with client's own certificate)
+    #[allow(clippy::result_large_err)]
+    pub(super) fn authorize<'a>(
+        &self,
+        peer_certs: Option<impl IntoIterator<Item = &'a [u8]>>,
+    ) -> Result<(), Status> {
+        let client_cert = peer_certs
+            .and_then(|certs| certs.into_iter().next())
+            .ok_or_else(|| Status::unauthenticated("client certificate is required"))?;
+
+        if self.allowed_clients.is_empty()
+            || self
+                .allowed_clients
+                .contains(Sha256::digest(client_cert).as_slice())
+        {
+            Ok(())
+        } else {
+            Err(Status::permission_denied(
+                "client certificate is not allowed to access solo machine",
+            ))
+        }
+    }
+}
+
+fn open_pem(path: &Path) -> Result<BufReader<File>> {
+    File::open(path)
+        .map(BufReader::new)
+        .with_context(|| format!("unable to open {}", path.display()))
+}
+
+/// Loads a PKCS#8 or RSA private key from a PEM file
+fn load_private_key(path: &Path) -> Result<PrivateKey> {
+    let invalid_key = || anyhow!("invalid private key in {}", path.display());
+
+    let mut keys = pkcs8_private_keys(&mut open_pem(path)?).map_err(|_| invalid_key())?;
+
+    if keys.is_empty() {
+        keys = rsa_private_keys(&mut open_pem(path)?).map_err(|_| invalid_key())?;
+    }
+
+    keys.into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("no private key found in {}", path.display()))
+}
+
+/// Parses SHA-256 fingerprint of a certificate (hex encoded, optionally separated by colons)
+fn parse_fingerprint(fingerprint: &str) -> Result<Vec<u8>> {
+    let bytes = hex::decode(fingerprint.replace(':', ""))
+        .with_context(|| format!("invalid certificate fingerprint: {}", fingerprint))?;
+
+    ensure!(
+        bytes.len() == 32,
+        "invalid certificate fingerprint: {} (expected SHA-256 fingerprint)",
+        fingerprint
+    );
+
+    Ok(bytes)
+}
@@ -1,40 +1,148 @@
-use std::{convert::TryFrom, ffi::OsStr, path::PathBuf, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::OsStr,
+    io::{self, BufRead, IsTerminal, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
-use anyhow::{anyhow, Context, Error, Result};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use async_trait::async_trait;
 use libloading::{Library, Symbol};
-use solo_machine_core::{signer::SignerRegistrar as ISignerRegistrar, Signer};
+use solo_machine_core::{
+    client_encoder::{ClientEncoder, ClientEncoderRegistrar as IClientEncoderRegistrar},
+    cosmos::crypto::PublicKey,
+    fee::{FeeStrategy, FeeStrategyRegistrar as IFeeStrategyRegistrar},
+    plugins::Plugins,
+    policy::SigningPolicy,
+    sign_summary::SignSummary,
+    signer::{FeePayerSigner, Message, SignerRegistrar as ISignerRegistrar},
+    Signer, ToPublicKey,
+};
+
+const REGISTER_SIGNER_FN: &str = "register_signer";
+const REGISTER_FEE_PAYER_FN: &str = "register_fee_payer";
+const REGISTER_FEE_STRATEGY_FN: &str = "register_fee_strategy";
+const REGISTER_CLIENT_ENCODERS_FN: &str = "register_client_encoders";
+
+/// Loads signer from given file and, optionally, a separate fee payer signer. When `key` is provided, the named signer
+/// with given name is selected from signer's key registry instead of the default signer. When `settings` are provided
+/// (e.g. for a tenant), signers read only them instead of environment variables.
+pub fn load_signer(
+    signer: PathBuf,
+    fee_payer_signer: Option<PathBuf>,
+    key: Option<&str>,
+    settings: Option<&BTreeMap<String, String>>,
+) -> Result<Arc<dyn Signer>> {
+    let registrar = SignerRegistrar::load(signer, settings)?;
+
+    let signer = match key {
+        None => registrar.unwrap()?,
+        Some(key) => registrar.unwrap_named(key)?,
+    };
+
+    match fee_payer_signer {
+        None => Ok(signer),
+        Some(fee_payer_signer) => {
+            let mut registrar = SignerRegistrar::with_settings(settings);
+            registrar.register_signer(fee_payer_signer, REGISTER_FEE_PAYER_FN)?;
+            let fee_payer = registrar.unwrap()?;
+
+            Ok(Arc::new(FeePayerSigner::new(signer, fee_payer)))
+        }
+    }
+}
+
+/// Loads fee strategy plugin and client encoder plugin (if provided) used by services for chains with `plugin` fee
+/// strategy or client encoding
+pub fn load_plugins(
+    fee_strategy_plugin: Option<PathBuf>,
+    client_encoder_plugin: Option<PathBuf>,
+) -> Result<Plugins> {
+    let fee_strategy = match fee_strategy_plugin {
+        None => None,
+        Some(fee_strategy_plugin) => {
+            let mut registrar = FeeStrategyRegistrar::default();
+            registrar.register_fee_strategy(fee_strategy_plugin)?;
+            Some(registrar.unwrap()?)
+        }
+    };
+
+    let client_encoders = match client_encoder_plugin {
+        None => HashMap::new(),
+        Some(client_encoder_plugin) => {
+            let mut registrar = ClientEncoderRegistrar::default();
+            registrar.register_client_encoders(client_encoder_plugin)?;
+            registrar.client_encoders
+        }
+    };
+
+    Ok(Plugins::new(fee_strategy, client_encoders))
+}
+
+/// Loads all the named signers from signer's key registry
+pub fn load_named_signers(
+    signer: PathBuf,
+    settings: Option<&BTreeMap<String, String>>,
+) -> Result<BTreeMap<String, Arc<dyn Signer>>> {
+    Ok(SignerRegistrar::load(signer, settings)?.named_signers)
+}
 
 #[derive(Default)]
 pub struct SignerRegistrar {
     signer: Option<Arc<dyn Signer>>,
+    named_signers: BTreeMap<String, Arc<dyn Signer>>,
+    /// Settings of signer (environment variables of process are used when not provided)
+    settings: Option<BTreeMap<String, String>>,
 }
 
 impl SignerRegistrar {
+    /// Creates a new registrar providing given settings to signers
+    pub fn with_settings(settings: Option<&BTreeMap<String, String>>) -> Self {
+        Self {
+            settings: settings.cloned(),
+            ..Default::default()
+        }
+    }
+
+    /// Loads signer from given file providing given settings to it
+    pub fn load(file: PathBuf, settings: Option<&BTreeMap<String, String>>) -> Result<Self> {
+        let mut registrar = Self::with_settings(settings);
+        registrar.register_signer(file, REGISTER_SIGNER_FN)?;
+
+        Ok(registrar)
+    }
+
     pub fn unwrap(self) -> Result<Arc<dyn Signer>> {
         self.signer.ok_or_else(|| anyhow!("signer not registered"))
     }
 
-    // TODO: remove conditional compilation when this issue is fixed:
-    // https://github.com/nagisa/rust_libloading/issues/41
-    fn register_signer(&mut self, file: impl AsRef<OsStr>) -> Result<()> {
+    pub fn unwrap_named(mut self, name: &str) -> Result<Arc<dyn Signer>> {
+        self.named_signers.remove(name).ok_or_else(|| {
+            anyhow!(
+                "key `{}` not found in key registry of signer (available keys: [{}])",
+                name,
+                self.named_signers
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+    }
+
+    fn register_signer(&mut self, file: impl AsRef<OsStr>, register_fn_name: &str) -> Result<()> {
         unsafe {
-            #[cfg(target_os = "linux")]
-            let library: Library = {
-                // Load library with `RTLD_NOW | RTLD_NODELETE` to fix a SIGSEGV
-                libloading::os::unix::Library::open(
-                    Some(file),
-                    libloading::os::unix::RTLD_NOW | 0x1000,
-                )
-                .context("unable to load signer")?
-                .into()
-            };
-            #[cfg(not(target_os = "linux"))]
-            let library = Library::new(file).context("unable to load signer")?;
+            let library = open_library(file).context("unable to load signer")?;
 
             let register_fn: Symbol<unsafe extern "C" fn(&mut dyn ISignerRegistrar) -> Result<()>> =
-                library
-                    .get("register_signer".as_bytes())
-                    .context("unable to load `register_signer` function from signer")?;
+                library.get(register_fn_name.as_bytes()).context(format!(
+                    "unable to load `{}` function from signer",
+                    register_fn_name
+                ))?;
 
             register_fn(self)?;
         }
@@ -47,15 +155,212 @@ impl ISignerRegistrar for SignerRegistrar {
     fn register(&mut self, signer: Arc<dyn Signer>) {
         self.signer = Some(signer);
     }
+
+    fn register_named(&mut self, name: &str, signer: Arc<dyn Signer>) {
+        self.named_signers.insert(name.to_string(), signer);
+    }
+
+    fn setting(&self, name: &str) -> Option<String> {
+        match self.settings {
+            Some(ref settings) => settings.get(name).cloned(),
+            None => std::env::var(name).ok(),
+        }
+    }
 }
 
-impl TryFrom<PathBuf> for SignerRegistrar {
-    type Error = Error;
+#[derive(Default)]
+pub struct FeeStrategyRegistrar {
+    fee_strategy: Option<Arc<dyn FeeStrategy>>,
+}
 
-    fn try_from(file: PathBuf) -> Result<Self, Self::Error> {
-        let mut registrar = Self::default();
-        registrar.register_signer(file)?;
+impl FeeStrategyRegistrar {
+    pub fn unwrap(self) -> Result<Arc<dyn FeeStrategy>> {
+        self.fee_strategy
+            .ok_or_else(|| anyhow!("fee strategy not registered"))
+    }
 
-        Ok(registrar)
+    fn register_fee_strategy(&mut self, file: impl AsRef<OsStr>) -> Result<()> {
+        unsafe {
+            let library = open_library(file).context("unable to load fee strategy plugin")?;
+
+            let register_fn: Symbol<
+                unsafe extern "C" fn(&mut dyn IFeeStrategyRegistrar) -> Result<()>,
+            > = library
+                .get(REGISTER_FEE_STRATEGY_FN.as_bytes())
+                .context(format!(
+                    "unable to load `{}` function from fee strategy plugin",
+                    REGISTER_FEE_STRATEGY_FN
+                ))?;
+
+            register_fn(self)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl IFeeStrategyRegistrar for FeeStrategyRegistrar {
+    fn register(&mut self, fee_strategy: Arc<dyn FeeStrategy>) {
+        self.fee_strategy = Some(fee_strategy);
+    }
+}
+
+#[derive(Default)]
+pub struct ClientEncoderRegistrar {
+    client_encoders: HashMap<String, Arc<dyn ClientEncoder>>,
+}
+
+impl ClientEncoderRegistrar {
+    fn register_client_encoders(&mut self, file: impl AsRef<OsStr>) -> Result<()> {
+        unsafe {
+            let library = open_library(file).context("unable to load client encoder plugin")?;
+
+            let register_fn: Symbol<
+                unsafe extern "C" fn(&mut dyn IClientEncoderRegistrar) -> Result<()>,
+            > = library
+                .get(REGISTER_CLIENT_ENCODERS_FN.as_bytes())
+                .context(format!(
+                    "unable to load `{}` function from client encoder plugin",
+                    REGISTER_CLIENT_ENCODERS_FN
+                ))?;
+
+            register_fn(self)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl IClientEncoderRegistrar for ClientEncoderRegistrar {
+    fn register(&mut self, chain_id: &str, client_encoder: Arc<dyn ClientEncoder>) {
+        self.client_encoders
+            .insert(chain_id.to_string(), client_encoder);
+    }
+}
+
+// TODO: remove conditional compilation when this issue is fixed:
+// https://github.com/nagisa/rust_libloading/issues/41
+unsafe fn open_library(file: impl AsRef<OsStr>) -> Result<Library> {
+    #[cfg(target_os = "linux")]
+    let library: Library = {
+        // Load library with `RTLD_NOW | RTLD_NODELETE` to fix a SIGSEGV
+        libloading::os::unix::Library::open(Some(file), libloading::os::unix::RTLD_NOW | 0x1000)?
+            .into()
+    };
+    #[cfg(not(target_os = "linux"))]
+    let library = Library::new(file)?;
+
+    Ok(library)
+}
+
+/// Signer which shows a summary of every message (message types, transfers, fee and consumed sequence) on terminal and
+/// signs it only after operator confirms it (or confirms all the remaining signatures of current invocation)
+pub struct ConfirmingSigner {
+    signer: Arc<dyn Signer>,
+    confirmed_all: AtomicBool,
+    /// Serializes prompts of concurrently signed messages
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl ConfirmingSigner {
+    /// Creates a new signer asking for confirmation before signing using wrapped signer
+    pub fn new(signer: Arc<dyn Signer>) -> Self {
+        Self {
+            signer,
+            confirmed_all: AtomicBool::new(false),
+            lock: Default::default(),
+        }
+    }
+
+    /// Asks operator to confirm signing of given messages
+    async fn confirm(&self, messages: &[Message<'_>]) -> Result<()> {
+        let _lock = self.lock.lock().await;
+
+        if self.confirmed_all.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        ensure!(
+            io::stdin().is_terminal(),
+            "signatures require confirmation on terminal (use `--yes` to sign without confirmation)"
+        );
+
+        let summaries = messages
+            .iter()
+            .map(|message| SignSummary::new(*message).to_string())
+            .collect::<Vec<_>>();
+
+        let answer = tokio::task::spawn_blocking(move || prompt(&summaries))
+            .await
+            .context("unable to read confirmation from terminal")??;
+
+        match answer.trim().to_lowercase().as_str() {
+            "y" | "yes" => Ok(()),
+            "a" | "all" => {
+                self.confirmed_all.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+            _ => bail!("signature rejected by operator"),
+        }
+    }
+}
+
+/// Prints summaries of messages to stderr and reads answer of operator from stdin
+fn prompt(summaries: &[String]) -> Result<String> {
+    let mut stderr = io::stderr();
+
+    for summary in summaries {
+        writeln!(stderr, "\n{}", summary).context("unable to write to stderr")?;
+    }
+
+    write!(stderr, "\nSign? [y]es / [n]o / [a]ll remaining: ")
+        .context("unable to write to stderr")?;
+    stderr.flush().context("unable to flush stderr")?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .context("unable to read confirmation from terminal")?;
+
+    Ok(answer)
+}
+
+impl ToPublicKey for ConfirmingSigner {
+    fn to_public_key(&self) -> Result<PublicKey> {
+        self.signer.to_public_key()
+    }
+
+    fn get_account_prefix(&self) -> &str {
+        self.signer.get_account_prefix()
+    }
+
+    fn to_account_address(&self) -> Result<String> {
+        self.signer.to_account_address()
+    }
+}
+
+#[async_trait]
+impl Signer for ConfirmingSigner {
+    async fn sign(&self, request_id: Option<&str>, message: Message<'_>) -> Result<Vec<u8>> {
+        self.confirm(&[message]).await?;
+        self.signer.sign(request_id, message).await
+    }
+
+    async fn sign_batch(
+        &self,
+        request_id: Option<&str>,
+        messages: &[Message<'_>],
+    ) -> Result<Vec<Vec<u8>>> {
+        self.confirm(messages).await?;
+        self.signer.sign_batch(request_id, messages).await
+    }
+
+    fn fee_payer(&self) -> Option<&dyn Signer> {
+        self.signer.fee_payer()
+    }
+
+    fn signing_policy(&self) -> Option<&SigningPolicy> {
+        self.signer.signing_policy()
     }
 }
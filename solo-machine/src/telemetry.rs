@@ -0,0 +1,36 @@
+//! Export of tracing spans (connection handshake steps, proof generation, gRPC queries, broadcasts and requests served
+//! by gRPC and REST servers) to an OpenTelemetry collector using OTLP (gRPC)
+use anyhow::{Context, Result};
+use opentelemetry::{
+    global,
+    sdk::{
+        trace::{self, Tracer},
+        Resource,
+    },
+    KeyValue,
+};
+
+/// Name of service reported with exported spans
+const SERVICE_NAME: &str = "solo-machine";
+
+/// Installs an exporter which exports spans in batches to OTLP collector at given endpoint (e.g.
+/// `http://localhost:4317`) and returns its tracer (used by tracing subscriber installed in [`crate::logging::init`])
+pub fn tracer(endpoint: &str) -> Result<Tracer> {
+    opentelemetry_otlp::new_pipeline()
+        .with_endpoint(endpoint)
+        .with_trace_config(
+            trace::config().with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                SERVICE_NAME,
+            )])),
+        )
+        .with_tonic()
+        .install_batch(opentelemetry::runtime::Tokio)
+        .with_context(|| format!("unable to install otlp exporter for {}", endpoint))
+}
+
+/// Exports all the spans which are not exported yet (spans of a command are only exported in batches)
+pub async fn shutdown() {
+    // shutting down tracer provider blocks until pending spans are exported
+    let _ = tokio::task::spawn_blocking(global::shutdown_tracer_provider).await;
+}